@@ -0,0 +1,31 @@
+//! Command console example
+//!
+//! Drives a patient from the terminal: each line typed is run as a console
+//! command ("give epi 1mg", "shock 200J", "labs abg", "wait 5 min") and the
+//! answer printed; `help` lists the commands and `quit` ends the session
+
+use medicallib::*;
+use std::io::{self, BufRead, Write};
+
+fn main() {
+    let mut patient = initialize_patient(1, 12);
+    let mut console = Console::new();
+    println!("Type a command (help for the list, quit to end)");
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        io::stdout().flush().expect("stdout should be writable");
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).expect("stdin should be readable") == 0 {
+            break;
+        }
+        match line.trim() {
+            "" => continue,
+            "quit" | "exit" => break,
+            command => match console.execute(&mut patient, command) {
+                Ok(answer) => println!("{}", answer),
+                Err(error) => println!("error: {}", error),
+            },
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Scripted scenario example
+//!
+//! Loads a scenario script and runs it on a patient, printing each event as it
+//! fires, the vital signs every minute and a case summary at the end
+
+use medicallib::organs::heart::Heart;
+use medicallib::*;
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| concat!(env!("CARGO_MANIFEST_DIR"), "/examples/scenarios/anterior_stemi.toml").to_string());
+    let text = std::fs::read_to_string(&path).expect("scenario script should be readable");
+    let scenario = match Scenario::parse(&text) {
+        Ok(scenario) => scenario,
+        Err(error) => {
+            eprintln!("{}: {}", path, error);
+            std::process::exit(1);
+        }
+    };
+
+    println!("=== {} ({:.0} s) ===\n", scenario.name, scenario.duration_s);
+    let mut patient = initialize_patient(1, 12);
+    let mut runner = ScenarioRunner::new(scenario);
+    let mut recorder = CaseRecorder::new();
+    recorder.record(&patient, 0.0);
+    let mut next_report_s = 0.0;
+    while !runner.is_finished() {
+        for event in runner.step(&mut patient) {
+            println!("{}", event);
+        }
+        recorder.record(&patient, runner.scenario.time_step_s);
+        if runner.time_s >= next_report_s {
+            let heart = patient.get_organ::<Heart>().unwrap();
+            println!(
+                "  t={:>4.0} s  HR {:>3.0}  BP {:.0}/{:.0}  CO {:.1} L/min  lactate {:.1}",
+                runner.time_s,
+                heart.heart_rate_bpm,
+                patient.blood.blood_pressure_systolic,
+                patient.blood.blood_pressure_diastolic,
+                heart.cardiac_output_l_min,
+                patient.blood.chemistry.lactate_mmol_l
+            );
+            next_report_s += 60.0;
+        }
+    }
+
+    println!("\n{}", runner.get_summary());
+    recorder.note_scenario_events(runner.log());
+    println!("\n=== CASE SUMMARY ===\n\n{}", recorder.narrative(&patient));
+    for report in patient.labs.reports() {
+        println!("\n{}", report.get_summary());
+    }
+}
@@ -30,24 +30,20 @@ fn main() {
     let delta_time_s = 0.1;
     let num_steps = (simulation_time_s / delta_time_s) as i32;
 
-    // Get mutable access to organs we want to interact with
-    if let Some(stomach) = patient.get_organ_mut::<stomach::Stomach>("Stomach") {
-        stomach.add_substance(300.0, 7.0); // Simulate eating a meal (300mL, pH 7.0)
-        println!("A 300mL meal has been consumed.");
-    }
+    // Eat a meal of 60 g carbohydrate, 20 g protein and 15 g fat with 250 mL of water
+    patient.eat(stomach::Meal::new(60.0, 20.0, 15.0, 250.0, 600.0));
+    println!("A 455 kcal meal has been consumed.");
 
     println!("\n--- Simulating {} seconds... ---", simulation_time_s);
 
-    for i in 0..num_steps {
-        let current_time = i as f64 * delta_time_s;
-
+    for _ in 0..num_steps {
         // Clear console on Unix systems
         #[cfg(unix)]
         print!("\x1B[2J\x1B[1;1H");
 
         // --- Event scripting ---
-        if (current_time - 20.0).abs() < delta_time_s / 2.0 {
-            if let Some(lungs) = patient.get_organ_mut::<lungs::Lungs>("Lungs") {
+        if (patient.sim_time() - 20.0).abs() < delta_time_s / 2.0 {
+            if let Some(lungs) = patient.get_organ_mut::<lungs::Lungs>() {
                 println!("\n*** LUNG INJURY EVENT ***\n");
                 lungs.inflict_damage(0, 0.8); // Damage lobe 0 by 80%
             }
@@ -55,7 +51,7 @@ fn main() {
 
         update_patient(&mut patient, delta_time_s);
 
-        println!("Time: {:.1}s / {}s\n", current_time, simulation_time_s);
+        println!("Time: {:.1}s / {}s\n", patient.sim_time(), simulation_time_s);
         println!("{}\n", patient.blood.get_summary());
 
         // Print select organ summaries
@@ -73,6 +69,7 @@ fn main() {
     println!("{}\n", patient.blood.get_cbc_summary());
     println!("{}\n", patient.blood.get_cmp_summary());
     println!("{}\n", patient.blood.get_abg_summary());
+    println!("{}\n", patient.get_io_report().get_summary());
 
     // Calculate some derived values
     let oxygen_content = patient.blood.calculate_oxygen_content();
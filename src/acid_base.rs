@@ -0,0 +1,400 @@
+//! Acid-base physiology
+//!
+//! This module provides:
+//! - The single place pH, the actual bicarbonate and base excess are derived
+//!   from PaCO2 and the metabolic bicarbonate (Henderson-Hasselbalch)
+//! - Bicarbonate buffering of organic acids (lactate, ketoacids) and of
+//!   external acid or base loads
+//! - Respiratory compensation of metabolic disorders over hours (Winter's formula)
+//!   and renal compensation of respiratory disorders over days
+//! - Interpretation of blood gases into primary and mixed disorders
+
+use crate::blood::{BloodChemistry, BloodComposition, BloodGases};
+
+/// Normal PaCO2 (mmHg)
+pub const NORMAL_PACO2_MMHG: f64 = 40.0;
+
+/// Normal plasma bicarbonate (mEq/L)
+pub const NORMAL_BICARBONATE_MEQ_L: f64 = 24.0;
+
+/// Normal anion gap (mEq/L)
+const NORMAL_ANION_GAP_MEQ_L: f64 = 12.0;
+
+/// Anion gap above which unmeasured anions are accumulating (mEq/L)
+const ELEVATED_ANION_GAP_MEQ_L: f64 = 16.0;
+
+/// Time constant of respiratory compensation for a metabolic disorder (s)
+const RESPIRATORY_COMPENSATION_S: f64 = 7_200.0;
+
+/// Bicarbonate change per mmHg of PaCO2 from red cell and protein buffering (mEq/L/mmHg)
+const ACUTE_HYPERCAPNIA_BUFFERING: f64 = 0.1;
+const ACUTE_HYPOCAPNIA_BUFFERING: f64 = 0.2;
+
+/// Bicarbonate change per mmHg of PaCO2 once the kidneys have compensated (mEq/L/mmHg)
+const CHRONIC_HYPERCAPNIA_COMPENSATION: f64 = 0.35;
+const CHRONIC_HYPOCAPNIA_COMPENSATION: f64 = 0.5;
+
+/// PaCO2 deviation from normal the kidneys ignore (mmHg)
+const RENAL_PACO2_DEAD_BAND_MMHG: f64 = 2.0;
+
+/// Highest PaCO2 that hypoventilation reaches compensating a metabolic alkalosis (mmHg)
+const MAX_COMPENSATORY_PACO2_MMHG: f64 = 55.0;
+
+/// Acid-base disorder
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AcidBaseDisorder {
+    /// Metabolic acidosis from unmeasured anions (lactate, ketones, toxic alcohols, uremia)
+    AnionGapMetabolicAcidosis,
+    /// Hyperchloremic metabolic acidosis (diarrhea, renal tubular acidosis, saline)
+    NonAnionGapMetabolicAcidosis,
+    MetabolicAlkalosis,
+    /// Respiratory acidosis without (acute) or with (chronic) renal compensation
+    RespiratoryAcidosis { chronic: bool },
+    /// Respiratory alkalosis without (acute) or with (chronic) renal compensation
+    RespiratoryAlkalosis { chronic: bool },
+}
+
+/// Stepwise interpretation of a blood gas and chemistry panel
+#[derive(Debug, Clone)]
+pub struct AcidBaseAnalysis {
+    pub ph: f64,
+    pub paco2_mmhg: f64,
+    pub hco3_meq_l: f64,
+    /// Anion gap corrected for albumin (mEq/L)
+    pub anion_gap_meq_l: f64,
+    /// Rise in anion gap over fall in bicarbonate, when the gap is elevated
+    pub delta_ratio: Option<f64>,
+    /// PaCO2 expected from respiratory compensation of a metabolic disorder (mmHg)
+    pub expected_paco2_mmhg: Option<f64>,
+    /// Bicarbonate expected from compensation of a respiratory disorder (mEq/L)
+    pub expected_hco3_meq_l: Option<f64>,
+    /// Disorders found, primary disorder first
+    pub disorders: Vec<AcidBaseDisorder>,
+}
+
+impl AcidBaseAnalysis {
+    /// Interpret a blood gas and chemistry panel
+    ///
+    /// Identifies the primary disorder from the pH, checks whether the
+    /// compensation is appropriate, and uses the albumin-corrected anion gap
+    /// and delta ratio to uncover hidden metabolic disorders.
+    ///
+    /// # Arguments
+    /// * `gases` - Arterial blood gas
+    /// * `chemistry` - Chemistry panel (sodium, chloride, albumin)
+    pub fn interpret(gases: &BloodGases, chemistry: &BloodChemistry) -> Self {
+        let ph = gases.ph;
+        let paco2 = gases.paco2_mmhg;
+        let hco3 = gases.hco3_meq_l;
+        let anion_gap = gases.calculate_anion_gap(chemistry.sodium_meq_l, chemistry.chloride_meq_l)
+            + 2.5 * (4.0 - chemistry.albumin_g_dl);
+
+        let mut disorders = Vec::new();
+        let mut expected_paco2 = None;
+        let mut expected_hco3 = None;
+        fn add(disorder: AcidBaseDisorder, disorders: &mut Vec<AcidBaseDisorder>) {
+            if !disorders.contains(&disorder) {
+                disorders.push(disorder);
+            }
+        }
+        let gap_acidosis = anion_gap > ELEVATED_ANION_GAP_MEQ_L;
+        let metabolic_acidosis = if gap_acidosis {
+            AcidBaseDisorder::AnionGapMetabolicAcidosis
+        } else {
+            AcidBaseDisorder::NonAnionGapMetabolicAcidosis
+        };
+
+        let acid_side = ph < 7.35 || (ph < 7.40 && (hco3 < 22.0 || paco2 > 45.0));
+        let alkaline_side = ph > 7.45 || (ph >= 7.40 && (hco3 > 26.0 || paco2 < 35.0));
+        let paco2_change = paco2 - NORMAL_PACO2_MMHG;
+
+        if acid_side && hco3 < 22.0 && paco2 <= 45.0 {
+            // Winter's formula
+            let expected = 1.5 * hco3 + 8.0;
+            expected_paco2 = Some(expected);
+            add(metabolic_acidosis, &mut disorders);
+            if paco2 > expected + 2.0 {
+                add(AcidBaseDisorder::RespiratoryAcidosis { chronic: false }, &mut disorders);
+            } else if paco2 < expected - 2.0 {
+                add(AcidBaseDisorder::RespiratoryAlkalosis { chronic: false }, &mut disorders);
+            }
+        } else if acid_side && paco2 > 45.0 {
+            let acute = NORMAL_BICARBONATE_MEQ_L + ACUTE_HYPERCAPNIA_BUFFERING * paco2_change;
+            let chronic = NORMAL_BICARBONATE_MEQ_L + CHRONIC_HYPERCAPNIA_COMPENSATION * paco2_change;
+            let is_chronic = hco3 > (acute + chronic) / 2.0;
+            expected_hco3 = Some(if is_chronic { chronic } else { acute });
+            add(AcidBaseDisorder::RespiratoryAcidosis { chronic: is_chronic }, &mut disorders);
+            if hco3 < acute - 2.0 {
+                add(metabolic_acidosis, &mut disorders);
+            } else if hco3 > chronic + 2.0 {
+                add(AcidBaseDisorder::MetabolicAlkalosis, &mut disorders);
+            }
+        } else if alkaline_side && hco3 > 26.0 && paco2 >= 35.0 {
+            let expected = (NORMAL_PACO2_MMHG + 0.7 * (hco3 - NORMAL_BICARBONATE_MEQ_L)).min(MAX_COMPENSATORY_PACO2_MMHG);
+            expected_paco2 = Some(expected);
+            add(AcidBaseDisorder::MetabolicAlkalosis, &mut disorders);
+            if paco2 > expected + 5.0 {
+                add(AcidBaseDisorder::RespiratoryAcidosis { chronic: false }, &mut disorders);
+            } else if paco2 < expected - 5.0 {
+                add(AcidBaseDisorder::RespiratoryAlkalosis { chronic: false }, &mut disorders);
+            }
+        } else if alkaline_side && paco2 < 35.0 {
+            let acute = NORMAL_BICARBONATE_MEQ_L + ACUTE_HYPOCAPNIA_BUFFERING * paco2_change;
+            let chronic = NORMAL_BICARBONATE_MEQ_L + CHRONIC_HYPOCAPNIA_COMPENSATION * paco2_change;
+            let is_chronic = hco3 < (acute + chronic) / 2.0;
+            expected_hco3 = Some(if is_chronic { chronic } else { acute });
+            add(AcidBaseDisorder::RespiratoryAlkalosis { chronic: is_chronic }, &mut disorders);
+            if hco3 > acute + 2.0 {
+                add(AcidBaseDisorder::MetabolicAlkalosis, &mut disorders);
+            } else if hco3 < chronic - 2.0 {
+                add(metabolic_acidosis, &mut disorders);
+            }
+        }
+
+        // An elevated gap means a gap acidosis even when the bicarbonate looks normal;
+        // the delta ratio shows what else is moving the bicarbonate
+        let mut delta_ratio = None;
+        if gap_acidosis {
+            add(AcidBaseDisorder::AnionGapMetabolicAcidosis, &mut disorders);
+            let bicarbonate_fall = NORMAL_BICARBONATE_MEQ_L - hco3;
+            let gap_rise = anion_gap - NORMAL_ANION_GAP_MEQ_L;
+            if bicarbonate_fall > 0.5 {
+                let ratio = gap_rise / bicarbonate_fall;
+                delta_ratio = Some(ratio);
+                if ratio < 1.0 {
+                    add(AcidBaseDisorder::NonAnionGapMetabolicAcidosis, &mut disorders);
+                } else if ratio > 2.0 {
+                    add(AcidBaseDisorder::MetabolicAlkalosis, &mut disorders);
+                }
+            } else {
+                add(AcidBaseDisorder::MetabolicAlkalosis, &mut disorders);
+            }
+        }
+
+        Self {
+            ph,
+            paco2_mmhg: paco2,
+            hco3_meq_l: hco3,
+            anion_gap_meq_l: anion_gap,
+            delta_ratio,
+            expected_paco2_mmhg: expected_paco2,
+            expected_hco3_meq_l: expected_hco3,
+            disorders,
+        }
+    }
+
+    /// Check whether more than one disorder is present
+    pub fn is_mixed(&self) -> bool {
+        self.disorders.len() > 1
+    }
+
+    /// Get a summary of the interpretation
+    pub fn get_summary(&self) -> String {
+        let disorders = if self.disorders.is_empty() {
+            "No acid-base disorder".to_string()
+        } else {
+            self.disorders.iter().map(|d| format!("{:?}", d)).collect::<Vec<_>>().join(" + ")
+        };
+        let expected = match (self.expected_paco2_mmhg, self.expected_hco3_meq_l) {
+            (Some(paco2), _) => format!(", expected PaCO2 {:.0}", paco2),
+            (_, Some(hco3)) => format!(", expected HCO3 {:.0}", hco3),
+            _ => String::new(),
+        };
+        let delta = match self.delta_ratio {
+            Some(ratio) => format!(", delta ratio {:.1}", ratio),
+            None => String::new(),
+        };
+        format!(
+            "{:.2}/{:.0}/{:.0}, AG {:.0}{}{}: {}",
+            self.ph, self.paco2_mmhg, self.hco3_meq_l, self.anion_gap_meq_l, expected, delta, disorders
+        )
+    }
+}
+
+/// Whole-body acid-base balance
+#[derive(Debug, Clone)]
+pub struct AcidBase {
+    /// Ventilatory chemoreflex drive compensating a metabolic disorder
+    /// (1.0 = none, >1 hyperventilation, <1 hypoventilation)
+    pub chemoreflex_drive: f64,
+    /// Bicarbonate the kidneys are working towards, including compensation for
+    /// a respiratory disorder (mEq/L)
+    pub renal_bicarbonate_setpoint_meq_l: f64,
+    /// Lactate and ketoacids already titrated against bicarbonate (mmol/L)
+    buffered_organic_acids_mmol_l: Option<f64>,
+    /// Bicarbonate currently added (or removed) by CO2 buffering, offset by the chloride shift (mEq/L)
+    co2_buffering_meq_l: f64,
+}
+
+impl AcidBase {
+    /// Create a normal acid-base balance
+    pub fn new() -> Self {
+        Self {
+            chemoreflex_drive: 1.0,
+            renal_bicarbonate_setpoint_meq_l: NORMAL_BICARBONATE_MEQ_L,
+            buffered_organic_acids_mmol_l: None,
+            co2_buffering_meq_l: 0.0,
+        }
+    }
+
+    /// Add a fixed acid load (e.g. toxic alcohol metabolites, or diarrheal bicarbonate loss)
+    ///
+    /// # Arguments
+    /// * `chemistry` - Blood chemistry
+    /// * `meq_l` - Acid added per litre of extracellular fluid (mEq/L)
+    /// * `unmeasured_anion` - Whether the conjugate anion is unmeasured (raising the
+    ///   anion gap) rather than replaced by chloride
+    pub fn add_acid(chemistry: &mut BloodChemistry, meq_l: f64, unmeasured_anion: bool) {
+        let meq_l = meq_l.max(0.0).min(chemistry.bicarbonate_meq_l - 2.0).max(0.0);
+        chemistry.bicarbonate_meq_l -= meq_l;
+        if !unmeasured_anion {
+            chemistry.chloride_meq_l += meq_l;
+        }
+    }
+
+    /// Add a base load (e.g. loss of gastric acid with vomiting, or a bicarbonate infusion)
+    ///
+    /// # Arguments
+    /// * `chemistry` - Blood chemistry
+    /// * `meq_l` - Base added per litre of extracellular fluid (mEq/L)
+    /// * `chloride_loss` - Whether chloride is lost alongside (vomiting) rather than
+    ///   sodium gained with the base (sodium bicarbonate)
+    pub fn add_base(chemistry: &mut BloodChemistry, meq_l: f64, chloride_loss: bool) {
+        let meq_l = meq_l.max(0.0);
+        chemistry.bicarbonate_meq_l += meq_l;
+        if chloride_loss {
+            chemistry.chloride_meq_l = (chemistry.chloride_meq_l - meq_l).max(60.0);
+        } else {
+            chemistry.sodium_meq_l += meq_l;
+        }
+    }
+
+    /// Bicarbonate added or removed by buffering a PaCO2 change (mEq/L)
+    fn acute_co2_buffering(paco2_mmhg: f64) -> f64 {
+        let change = paco2_mmhg - NORMAL_PACO2_MMHG;
+        if change > 0.0 {
+            ACUTE_HYPERCAPNIA_BUFFERING * change
+        } else {
+            ACUTE_HYPOCAPNIA_BUFFERING * change
+        }
+    }
+
+    /// Bicarbonate the kidneys retain or excrete for a sustained PaCO2 change,
+    /// beyond acute buffering (mEq/L)
+    fn renal_co2_compensation(paco2_mmhg: f64) -> f64 {
+        // PaCO2 within a couple of mmHg of normal draws no renal response
+        let deviation = paco2_mmhg - NORMAL_PACO2_MMHG;
+        let change = deviation - deviation.clamp(-RENAL_PACO2_DEAD_BAND_MMHG, RENAL_PACO2_DEAD_BAND_MMHG);
+        if change > 0.0 {
+            (CHRONIC_HYPERCAPNIA_COMPENSATION - ACUTE_HYPERCAPNIA_BUFFERING) * change
+        } else {
+            (CHRONIC_HYPOCAPNIA_COMPENSATION - ACUTE_HYPOCAPNIA_BUFFERING) * change
+        }
+    }
+
+    /// PaCO2 that appropriate respiratory compensation would reach (mmHg)
+    ///
+    /// # Arguments
+    /// * `metabolic_bicarbonate` - Bicarbonate at a normal PaCO2 (mEq/L)
+    pub fn expected_compensated_paco2(metabolic_bicarbonate: f64) -> f64 {
+        let deviation = metabolic_bicarbonate - NORMAL_BICARBONATE_MEQ_L;
+        if deviation < 0.0 {
+            (NORMAL_PACO2_MMHG + 1.2 * deviation).max(10.0)
+        } else {
+            (NORMAL_PACO2_MMHG + 0.7 * deviation).min(MAX_COMPENSATORY_PACO2_MMHG)
+        }
+    }
+
+    /// Bring pH, bicarbonate and base excess into agreement with PaCO2 and the acid loads
+    ///
+    /// `chemistry.bicarbonate_meq_l` holds the metabolic bicarbonate (at a PaCO2 of
+    /// 40 mmHg); the measured bicarbonate additionally reflects CO2 buffering.
+    ///
+    /// # Arguments
+    /// * `blood` - Blood composition, with PaCO2 from the lungs
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, blood: &mut BloodComposition, delta_time_s: f64) {
+        let chemistry = &mut blood.chemistry;
+
+        // Lactic and ketoacids are buffered by bicarbonate mole for mole, widening
+        // the anion gap; their metabolism regenerates it
+        let organic_acids = chemistry.lactate_mmol_l + chemistry.beta_hydroxybutyrate_mmol_l;
+        if let Some(buffered) = self.buffered_organic_acids_mmol_l {
+            chemistry.bicarbonate_meq_l -= organic_acids - buffered;
+        }
+        self.buffered_organic_acids_mmol_l = Some(organic_acids);
+        chemistry.bicarbonate_meq_l = chemistry.bicarbonate_meq_l.clamp(2.0, 50.0);
+        let metabolic_bicarbonate = chemistry.bicarbonate_meq_l;
+
+        // Red cells exchange the bicarbonate made from CO2 for plasma chloride
+        // (chloride shift), so buffering leaves the anion gap unchanged
+        let paco2 = blood.gases.paco2_mmhg.max(5.0);
+        let buffering = Self::acute_co2_buffering(paco2);
+        chemistry.chloride_meq_l -= buffering - self.co2_buffering_meq_l;
+        self.co2_buffering_meq_l = buffering;
+
+        // Henderson-Hasselbalch: pH = 6.1 + log10(HCO3 / (0.03 × PaCO2))
+        let gases = &mut blood.gases;
+        let bicarbonate = (metabolic_bicarbonate + buffering).max(1.0);
+        gases.hco3_meq_l = bicarbonate;
+        gases.ph = 6.1 + (bicarbonate / (0.03 * paco2)).log10();
+        // Van Slyke
+        gases.base_excess_meq_l = 0.93 * (bicarbonate - 24.4 + 14.8 * (gases.ph - 7.4));
+
+        // Peripheral and central chemoreceptors shift ventilation towards the
+        // PaCO2 that compensates the metabolic disorder
+        let target_drive =
+            (NORMAL_PACO2_MMHG / Self::expected_compensated_paco2(metabolic_bicarbonate)).clamp(0.6, 4.0);
+        self.chemoreflex_drive +=
+            (target_drive - self.chemoreflex_drive) * (1.0 - (-delta_time_s / RESPIRATORY_COMPENSATION_S).exp());
+
+        // The kidneys retain or excrete bicarbonate to offset a sustained PaCO2 change
+        self.renal_bicarbonate_setpoint_meq_l = NORMAL_BICARBONATE_MEQ_L + Self::renal_co2_compensation(paco2);
+    }
+
+    /// Interpret the current blood gas
+    ///
+    /// # Arguments
+    /// * `blood` - Blood composition
+    pub fn analyze(blood: &BloodComposition) -> AcidBaseAnalysis {
+        AcidBaseAnalysis::interpret(&blood.gases, &blood.chemistry)
+    }
+}
+
+impl Default for AcidBase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gas(ph: f64, paco2: f64, hco3: f64) -> BloodGases {
+        BloodGases { ph, paco2_mmhg: paco2, hco3_meq_l: hco3, ..BloodGases::default() }
+    }
+
+    #[test]
+    fn test_mixed_disorder_interpretation() {
+        // DKA with appropriate respiratory compensation
+        let mut chemistry = BloodChemistry { chloride_meq_l: 100.0, ..BloodChemistry::default() };
+        let dka = AcidBaseAnalysis::interpret(&gas(7.22, 23.0, 10.0), &chemistry);
+        assert_eq!(dka.disorders, vec![AcidBaseDisorder::AnionGapMetabolicAcidosis]);
+
+        // The same bicarbonate with a normal PaCO2: the patient is tiring
+        let tiring = AcidBaseAnalysis::interpret(&gas(6.98, 40.0, 10.0), &chemistry);
+        assert!(tiring.disorders.contains(&AcidBaseDisorder::RespiratoryAcidosis { chronic: false }));
+
+        // Vomiting on top of a gap acidosis leaves the bicarbonate near normal
+        chemistry.chloride_meq_l = 88.0;
+        let hidden = AcidBaseAnalysis::interpret(&gas(7.40, 40.0, 24.0), &chemistry);
+        assert!(hidden.disorders.contains(&AcidBaseDisorder::AnionGapMetabolicAcidosis));
+        assert!(hidden.disorders.contains(&AcidBaseDisorder::MetabolicAlkalosis));
+
+        // COPD retainer with renal compensation
+        chemistry.chloride_meq_l = 94.0;
+        let copd = AcidBaseAnalysis::interpret(&gas(7.35, 60.0, 31.0), &chemistry);
+        assert_eq!(copd.disorders, vec![AcidBaseDisorder::RespiratoryAcidosis { chronic: true }]);
+    }
+}
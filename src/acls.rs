@@ -0,0 +1,772 @@
+//! Code blue timeline and ACLS assistant
+//!
+//! This module provides:
+//! - A timestamped code timeline (arrest, CPR, rhythm checks, shocks, drugs,
+//!   reversible causes, return of spontaneous circulation)
+//! - The adult cardiac arrest algorithm as a state machine recommending the next
+//!   actions for shockable (VF/pVT) and non-shockable (PEA/asystole) rhythms
+//! - A reversible-cause (Hs and Ts) checklist mapped to the causes actually
+//!   present in the model, with a ground-truth query of each cause's severity
+//!   and evidence, and the causes underlying the arrest as distinct from those
+//!   the arrest itself produced
+//! - Scoring of the resuscitation against ACLS timing targets
+
+use crate::organs::heart::{Heart, Rhythm};
+use crate::organs::lungs::Lungs;
+use crate::organs::vascular::VascularSystem;
+use crate::patient::Patient;
+
+/// Length of one CPR cycle between rhythm checks (s)
+pub const CPR_CYCLE_S: f64 = 120.0;
+
+/// Shortest recommended interval between epinephrine doses (s)
+const EPINEPHRINE_MIN_INTERVAL_S: f64 = 180.0;
+
+/// Longest recommended interval between epinephrine doses (s)
+const EPINEPHRINE_MAX_INTERVAL_S: f64 = 300.0;
+
+/// Target time from arrest to first compressions (s)
+const TARGET_TIME_TO_CPR_S: f64 = 60.0;
+
+/// Target time from arrest to first shock in a shockable rhythm (s)
+const TARGET_TIME_TO_SHOCK_S: f64 = 120.0;
+
+/// Target chest compression fraction during arrest
+const TARGET_CPR_FRACTION: f64 = 0.8;
+
+/// Reversible cause of cardiac arrest (Hs and Ts)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReversibleCause {
+    Hypovolemia,
+    Hypoxia,
+    /// Hydrogen ion (acidosis)
+    Acidosis,
+    /// Hyper- or hypokalemia
+    Potassium,
+    Hypothermia,
+    TensionPneumothorax,
+    /// Cardiac tamponade from a pericardial effusion
+    Tamponade,
+    Toxins,
+    /// Massive pulmonary embolism
+    PulmonaryThrombosis,
+    /// Acute coronary occlusion
+    CoronaryThrombosis,
+}
+
+impl ReversibleCause {
+    /// All Hs and Ts
+    pub const ALL: [ReversibleCause; 10] = [
+        ReversibleCause::Hypovolemia,
+        ReversibleCause::Hypoxia,
+        ReversibleCause::Acidosis,
+        ReversibleCause::Potassium,
+        ReversibleCause::Hypothermia,
+        ReversibleCause::TensionPneumothorax,
+        ReversibleCause::Tamponade,
+        ReversibleCause::Toxins,
+        ReversibleCause::PulmonaryThrombosis,
+        ReversibleCause::CoronaryThrombosis,
+    ];
+
+    /// Look for the cause in the patient
+    ///
+    /// # Arguments
+    /// * `patient` - The patient
+    ///
+    /// # Returns
+    /// The finding with its severity and the measurement that shows it, or None if absent
+    pub fn assess(&self, patient: &Patient) -> Option<CauseFinding> {
+        let blood = &patient.blood;
+        let (severity, evidence) = match self {
+            ReversibleCause::Hypovolemia => {
+                let deficit = 1.0 - patient.fluids.blood_volume_ml() / patient.fluids.reference_blood_volume_ml;
+                if deficit <= 0.25 {
+                    return None;
+                }
+                (deficit / 0.5, format!("Blood volume {:.0}% of normal", (1.0 - deficit) * 100.0))
+            }
+            ReversibleCause::Hypoxia => {
+                let sao2 = blood.gases.sao2_percent;
+                if sao2 >= 85.0 {
+                    return None;
+                }
+                ((85.0 - sao2) / 60.0, format!("SaO2 {:.0}%, PaO2 {:.0} mmHg", sao2, blood.gases.pao2_mmhg))
+            }
+            ReversibleCause::Acidosis => {
+                let ph = blood.gases.ph;
+                if ph >= 7.2 {
+                    return None;
+                }
+                (
+                    (7.2 - ph) / 0.4,
+                    format!("pH {:.2}, PaCO2 {:.0} mmHg, HCO3 {:.0} mEq/L", ph, blood.gases.paco2_mmhg, blood.gases.hco3_meq_l),
+                )
+            }
+            ReversibleCause::Potassium => {
+                let potassium = blood.chemistry.potassium_meq_l;
+                if potassium > 6.5 {
+                    ((potassium - 6.5) / 3.0, format!("Hyperkalemia, K+ {:.1} mEq/L", potassium))
+                } else if potassium < 2.5 {
+                    ((2.5 - potassium) / 1.0, format!("Hypokalemia, K+ {:.1} mEq/L", potassium))
+                } else {
+                    return None;
+                }
+            }
+            ReversibleCause::Hypothermia => {
+                let temperature = patient.newborn.as_ref().map(|newborn| newborn.temperature_c)?;
+                if temperature >= 35.0 {
+                    return None;
+                }
+                ((35.0 - temperature) / 7.0, format!("Temperature {:.1} °C", temperature))
+            }
+            ReversibleCause::TensionPneumothorax => {
+                let lungs = patient.get_organ::<Lungs>()?;
+                let side = match (lungs.left_pleura.is_tension(), lungs.right_pleura.is_tension()) {
+                    (true, true) => "Bilateral",
+                    (true, false) => "Left",
+                    (false, true) => "Right",
+                    (false, false) => return None,
+                };
+                let impairment = patient
+                    .get_organ::<Heart>()
+                    .map(|heart| 1.0 - heart.venous_return_factor)
+                    .unwrap_or(0.5);
+                (
+                    impairment.max(0.3),
+                    format!(
+                        "{} tension pneumothorax, pleural pressure {:.0}/{:.0} cmH2O",
+                        side, lungs.left_pleura.pressure_cmh2o, lungs.right_pleura.pressure_cmh2o
+                    ),
+                )
+            }
+            ReversibleCause::Tamponade => {
+                let heart = patient.get_organ::<Heart>()?;
+                if !heart.has_tamponade() {
+                    return None;
+                }
+                (
+                    1.0 - heart.pericardial_filling_factor(),
+                    format!(
+                        "Pericardial effusion {:.0} mL, pericardial pressure {:.0} mmHg",
+                        heart.pericardial_effusion_ml, heart.pericardial_pressure_mmhg
+                    ),
+                )
+            }
+            ReversibleCause::Toxins => {
+                let toxins = blood.chemistry.toxin_level_au;
+                if toxins <= 50.0 {
+                    return None;
+                }
+                ((toxins - 50.0) / 450.0, format!("Toxin level {:.0} AU", toxins))
+            }
+            ReversibleCause::PulmonaryThrombosis => {
+                let obstruction = patient.get_organ::<VascularSystem>()?.get_pulmonary_obstruction();
+                if obstruction <= 0.5 {
+                    return None;
+                }
+                (
+                    (obstruction - 0.5) / 0.4,
+                    format!("{:.0}% of the pulmonary circulation obstructed", obstruction * 100.0),
+                )
+            }
+            ReversibleCause::CoronaryThrombosis => {
+                let heart = patient.get_organ::<Heart>()?;
+                let territories: Vec<&str> =
+                    heart.infarcted_territories().iter().map(|territory| territory.abbreviation()).collect();
+                if territories.is_empty() {
+                    return None;
+                }
+                (
+                    1.0 - heart.segmental_contractility(),
+                    format!(
+                        "{} occlusion, {} of 17 segments hypokinetic",
+                        territories.join(" and "),
+                        heart.hypokinetic_segment_count()
+                    ),
+                )
+            }
+        };
+        Some(CauseFinding { cause: *self, severity: severity.clamp(0.05, 1.0), evidence })
+    }
+
+    /// Check whether the cause is present in the patient
+    ///
+    /// # Arguments
+    /// * `patient` - The patient
+    pub fn is_present(&self, patient: &Patient) -> bool {
+        self.assess(patient).is_some()
+    }
+}
+
+/// A reversible cause actually present in the model
+#[derive(Debug, Clone)]
+pub struct CauseFinding {
+    pub cause: ReversibleCause,
+    /// How far the derangement goes (0.0-1.0)
+    pub severity: f64,
+    /// The measurement that shows it
+    pub evidence: String,
+}
+
+/// Find every reversible cause present in the patient, most severe first
+///
+/// This is ground truth from the model, for scenario engines checking whether
+/// the learner found the real cause; it is not what a clinician could see.
+///
+/// # Arguments
+/// * `patient` - The patient
+pub fn find_reversible_causes(patient: &Patient) -> Vec<CauseFinding> {
+    let mut findings: Vec<CauseFinding> =
+        ReversibleCause::ALL.iter().filter_map(|cause| cause.assess(patient)).collect();
+    findings.sort_by(|a, b| b.severity.total_cmp(&a.severity));
+    findings
+}
+
+/// Something that happened during the code
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodeEventKind {
+    /// Loss of pulse in the given rhythm
+    ArrestRecognised(Rhythm),
+    /// The arrest rhythm changed
+    RhythmChanged(Rhythm),
+    CprStarted,
+    CprStopped,
+    /// Pause for a rhythm check showing the given rhythm
+    RhythmCheck(Rhythm),
+    /// Defibrillation shock and whether it terminated the rhythm
+    Shock { energy_j: f64, converted: bool },
+    /// Epinephrine 1 mg
+    Epinephrine,
+    /// Amiodarone bolus (mg)
+    Amiodarone(f64),
+    /// A reversible cause was treated; `present` records whether it was actually there
+    CauseAddressed { cause: ReversibleCause, present: bool },
+    /// Return of spontaneous circulation
+    Rosc,
+}
+
+/// Timestamped code event
+#[derive(Debug, Clone)]
+pub struct CodeEvent {
+    /// Time since the assistant started (s)
+    pub time_s: f64,
+    pub kind: CodeEventKind,
+}
+
+/// Next action recommended by the algorithm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AclsAction {
+    StartCpr,
+    /// Pause compressions for a rhythm (and pulse) check
+    CheckRhythm,
+    Defibrillate,
+    GiveEpinephrine,
+    /// Give amiodarone (mg)
+    GiveAmiodarone(f64),
+    /// Search for and treat the Hs and Ts
+    TreatReversibleCauses,
+    /// Stop compressions: the patient has a pulse
+    StopCpr,
+    /// Post-cardiac arrest care after ROSC
+    PostArrestCare,
+}
+
+/// How the resuscitation measured up against ACLS targets
+#[derive(Debug, Clone)]
+pub struct AclsScore {
+    /// Time from arrest to first compressions (s)
+    pub time_to_cpr_s: Option<f64>,
+    /// Time from arrest to first shock (s), for shockable arrests
+    pub time_to_first_shock_s: Option<f64>,
+    /// Fraction of arrest time with compressions
+    pub cpr_fraction: f64,
+    /// Epinephrine intervals outside 3-5 minutes
+    pub epinephrine_interval_deviations: usize,
+    /// Reversible causes present that were never addressed
+    pub missed_causes: Vec<ReversibleCause>,
+    /// Causes of the arrest itself (present when it began) that were never addressed
+    pub missed_underlying_causes: Vec<ReversibleCause>,
+    /// Causes treated that were not present in the patient
+    pub addressed_absent_causes: Vec<ReversibleCause>,
+    /// Deviations from the algorithm, in plain words
+    pub deviations: Vec<String>,
+    /// Overall score (0-100)
+    pub score_percent: f64,
+}
+
+/// ACLS assistant tracking a code and recommending the next steps
+#[derive(Debug, Clone)]
+pub struct AclsAssistant {
+    /// Time since the assistant started (s)
+    pub elapsed_s: f64,
+    /// Code timeline
+    pub events: Vec<CodeEvent>,
+    /// Current rhythm seen by the monitor
+    pub rhythm: Rhythm,
+    /// When the current arrest began (s)
+    pub arrest_started_s: Option<f64>,
+    /// When spontaneous circulation returned (s)
+    pub rosc_s: Option<f64>,
+    /// Whether the arrest began in a shockable rhythm
+    pub initially_shockable: bool,
+    /// When the current two-minute CPR cycle began (s)
+    pub cycle_started_s: Option<f64>,
+    /// Completed CPR cycles
+    pub cpr_cycles: u32,
+    /// Compression time during arrest (s)
+    pub compression_time_s: f64,
+    /// Total arrest time (s)
+    pub arrest_time_s: f64,
+    /// Shocks delivered
+    pub shocks: u32,
+    /// Epinephrine dose times (s)
+    pub epinephrine_times_s: Vec<f64>,
+    /// Amiodarone doses given
+    pub amiodarone_doses: u32,
+    /// Reversible causes found when the arrest began, most severe first
+    pub underlying_causes: Vec<CauseFinding>,
+    /// Reversible causes found present at any point during the arrest
+    pub causes_present: Vec<ReversibleCause>,
+    /// Reversible causes the team addressed
+    pub causes_addressed: Vec<ReversibleCause>,
+    /// Whether compressions are being delivered
+    cpr_active: bool,
+    /// Whether a shock has been delivered in the current cycle
+    shocked_this_cycle: bool,
+}
+
+impl AclsAssistant {
+    /// Create an assistant with an empty timeline
+    pub fn new() -> Self {
+        Self {
+            elapsed_s: 0.0,
+            events: Vec::new(),
+            rhythm: Rhythm::NormalSinus,
+            arrest_started_s: None,
+            rosc_s: None,
+            initially_shockable: false,
+            cycle_started_s: None,
+            cpr_cycles: 0,
+            compression_time_s: 0.0,
+            arrest_time_s: 0.0,
+            shocks: 0,
+            epinephrine_times_s: Vec::new(),
+            amiodarone_doses: 0,
+            underlying_causes: Vec::new(),
+            causes_present: Vec::new(),
+            causes_addressed: Vec::new(),
+            cpr_active: false,
+            shocked_this_cycle: false,
+        }
+    }
+
+    fn log(&mut self, kind: CodeEventKind) {
+        self.events.push(CodeEvent { time_s: self.elapsed_s, kind });
+    }
+
+    /// Check whether the patient is currently in arrest
+    pub fn is_in_arrest(&self) -> bool {
+        self.arrest_started_s.is_some() && self.rosc_s.is_none()
+    }
+
+    /// Check whether the current rhythm is shockable
+    pub fn is_shockable(&self) -> bool {
+        matches!(
+            self.rhythm,
+            Rhythm::VentricularFibrillation | Rhythm::PulselessVentricularTachycardia | Rhythm::TorsadesDePointes
+        )
+    }
+
+    /// Record compressions starting
+    pub fn record_cpr_started(&mut self) {
+        if !self.cpr_active {
+            self.cpr_active = true;
+            if self.cycle_started_s.is_none() {
+                self.cycle_started_s = Some(self.elapsed_s);
+            }
+            self.log(CodeEventKind::CprStarted);
+        }
+    }
+
+    /// Record compressions stopping
+    pub fn record_cpr_stopped(&mut self) {
+        if self.cpr_active {
+            self.cpr_active = false;
+            self.log(CodeEventKind::CprStopped);
+        }
+    }
+
+    /// Record a rhythm check, which starts a new CPR cycle
+    pub fn record_rhythm_check(&mut self, rhythm: Rhythm) {
+        self.log(CodeEventKind::RhythmCheck(rhythm));
+        if self.cycle_started_s.is_some() {
+            self.cpr_cycles += 1;
+        }
+        self.cycle_started_s = Some(self.elapsed_s);
+        self.shocked_this_cycle = false;
+    }
+
+    /// Record a defibrillation shock
+    pub fn record_shock(&mut self, energy_j: f64, converted: bool) {
+        self.shocks += 1;
+        self.shocked_this_cycle = true;
+        self.log(CodeEventKind::Shock { energy_j, converted });
+    }
+
+    /// Record epinephrine 1 mg
+    pub fn record_epinephrine(&mut self) {
+        self.epinephrine_times_s.push(self.elapsed_s);
+        self.log(CodeEventKind::Epinephrine);
+    }
+
+    /// Record an amiodarone bolus
+    pub fn record_amiodarone(&mut self, mg: f64) {
+        self.amiodarone_doses += 1;
+        self.log(CodeEventKind::Amiodarone(mg));
+    }
+
+    /// Record treatment aimed at a reversible cause
+    ///
+    /// # Arguments
+    /// * `cause` - The cause addressed
+    /// * `present` - Whether the cause is actually present in the model
+    pub fn record_cause_addressed(&mut self, cause: ReversibleCause, present: bool) {
+        if !self.causes_addressed.contains(&cause) {
+            self.causes_addressed.push(cause);
+        }
+        self.log(CodeEventKind::CauseAddressed { cause, present });
+    }
+
+    /// Follow the monitor and the patient's reversible causes
+    ///
+    /// # Arguments
+    /// * `patient` - The patient
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, patient: &Patient, delta_time_s: f64) {
+        self.elapsed_s += delta_time_s;
+        let Some(heart) = patient.get_organ::<Heart>() else {
+            return;
+        };
+        let previous = self.rhythm;
+        self.rhythm = heart.rhythm;
+        let in_arrest = heart.is_in_cardiac_arrest();
+
+        if in_arrest && !self.is_in_arrest() {
+            self.arrest_started_s = Some(self.elapsed_s);
+            self.rosc_s = None;
+            self.initially_shockable = heart.is_shockable();
+            self.cycle_started_s = if self.cpr_active { Some(self.elapsed_s) } else { None };
+            self.underlying_causes = find_reversible_causes(patient);
+            self.log(CodeEventKind::ArrestRecognised(self.rhythm));
+        } else if in_arrest && self.rhythm != previous {
+            self.log(CodeEventKind::RhythmChanged(self.rhythm));
+        } else if !in_arrest && self.is_in_arrest() {
+            self.rosc_s = Some(self.elapsed_s);
+            self.log(CodeEventKind::Rosc);
+        }
+
+        if self.is_in_arrest() {
+            self.arrest_time_s += delta_time_s;
+            if self.cpr_active {
+                self.compression_time_s += delta_time_s;
+            }
+            for cause in ReversibleCause::ALL {
+                if !self.causes_present.contains(&cause) && cause.is_present(patient) {
+                    self.causes_present.push(cause);
+                }
+            }
+        }
+    }
+
+    /// Actions the algorithm calls for now, most urgent first
+    pub fn recommended_actions(&self) -> Vec<AclsAction> {
+        let mut actions = Vec::new();
+        if !self.is_in_arrest() {
+            if self.rosc_s.is_some() {
+                if self.cpr_active {
+                    actions.push(AclsAction::StopCpr);
+                }
+                actions.push(AclsAction::PostArrestCare);
+            }
+            return actions;
+        }
+        if !self.cpr_active && self.shocks == 0 && !self.is_shockable() {
+            actions.push(AclsAction::StartCpr);
+        }
+        let cycle_elapsed = self.cycle_started_s.map(|start| self.elapsed_s - start);
+        let cycle_due = cycle_elapsed.is_some_and(|elapsed| elapsed >= CPR_CYCLE_S);
+        if self.is_shockable() && (!self.shocked_this_cycle || cycle_due) {
+            if cycle_due {
+                actions.push(AclsAction::CheckRhythm);
+            }
+            actions.push(AclsAction::Defibrillate);
+        } else if cycle_due {
+            actions.push(AclsAction::CheckRhythm);
+        }
+        if !self.cpr_active && !actions.contains(&AclsAction::StartCpr) {
+            actions.push(AclsAction::StartCpr);
+        }
+
+        // Epinephrine as soon as possible in PEA/asystole; after the second shock in VF/pVT
+        let epinephrine_indicated = !self.is_shockable() || self.shocks >= 2;
+        let epinephrine_due = match self.epinephrine_times_s.last() {
+            None => true,
+            Some(&last) => self.elapsed_s - last >= EPINEPHRINE_MIN_INTERVAL_S,
+        };
+        if epinephrine_indicated && epinephrine_due {
+            actions.push(AclsAction::GiveEpinephrine);
+        }
+        if self.is_shockable() {
+            if self.shocks >= 3 && self.amiodarone_doses == 0 {
+                actions.push(AclsAction::GiveAmiodarone(300.0));
+            } else if self.shocks >= 5 && self.amiodarone_doses == 1 {
+                actions.push(AclsAction::GiveAmiodarone(150.0));
+            }
+        }
+        if self.causes_present.iter().any(|cause| !self.causes_addressed.contains(cause))
+            || (!self.is_shockable() && self.causes_addressed.is_empty())
+        {
+            actions.push(AclsAction::TreatReversibleCauses);
+        }
+        actions
+    }
+
+    /// Score the resuscitation against ACLS timing targets
+    pub fn score(&self) -> AclsScore {
+        let arrest_start = self.arrest_started_s.unwrap_or(0.0);
+        let first_after = |matches: &dyn Fn(&CodeEventKind) -> bool| {
+            self.events
+                .iter()
+                .find(|event| event.time_s >= arrest_start && matches(&event.kind))
+                .map(|event| event.time_s - arrest_start)
+        };
+        let time_to_cpr = first_after(&|kind| *kind == CodeEventKind::CprStarted);
+        let time_to_first_shock = if self.initially_shockable {
+            first_after(&|kind| matches!(kind, CodeEventKind::Shock { .. }))
+        } else {
+            None
+        };
+        let cpr_fraction = if self.arrest_time_s > 0.0 { self.compression_time_s / self.arrest_time_s } else { 1.0 };
+        let epinephrine_interval_deviations = self
+            .epinephrine_times_s
+            .windows(2)
+            .filter(|pair| {
+                let interval = pair[1] - pair[0];
+                !(EPINEPHRINE_MIN_INTERVAL_S - 15.0..=EPINEPHRINE_MAX_INTERVAL_S + 15.0).contains(&interval)
+            })
+            .count();
+        let missed_causes: Vec<ReversibleCause> = self
+            .causes_present
+            .iter()
+            .copied()
+            .filter(|cause| !self.causes_addressed.contains(cause))
+            .collect();
+        let missed_underlying_causes: Vec<ReversibleCause> = self
+            .underlying_causes
+            .iter()
+            .map(|finding| finding.cause)
+            .filter(|cause| !self.causes_addressed.contains(cause))
+            .collect();
+        let mut addressed_absent_causes = Vec::new();
+        for event in &self.events {
+            if let CodeEventKind::CauseAddressed { cause, present: false } = event.kind {
+                if !self.causes_present.contains(&cause) && !addressed_absent_causes.contains(&cause) {
+                    addressed_absent_causes.push(cause);
+                }
+            }
+        }
+
+        let mut deviations = Vec::new();
+        let mut penalty = 0.0;
+        if self.arrest_started_s.is_some() {
+            match time_to_cpr {
+                Some(time) if time <= TARGET_TIME_TO_CPR_S => {}
+                Some(time) => {
+                    deviations.push(format!("Compressions started after {:.0} s", time));
+                    penalty += 15.0;
+                }
+                None => {
+                    deviations.push("No compressions".to_string());
+                    penalty += 40.0;
+                }
+            }
+            if self.initially_shockable {
+                match time_to_first_shock {
+                    Some(time) if time <= TARGET_TIME_TO_SHOCK_S => {}
+                    Some(time) => {
+                        deviations.push(format!("First shock after {:.0} s", time));
+                        penalty += 20.0;
+                    }
+                    None => {
+                        deviations.push("Shockable rhythm never shocked".to_string());
+                        penalty += 40.0;
+                    }
+                }
+            }
+            if self.arrest_time_s >= CPR_CYCLE_S && cpr_fraction < TARGET_CPR_FRACTION {
+                deviations.push(format!("Compression fraction {:.0}%", cpr_fraction * 100.0));
+                penalty += 15.0;
+            }
+            if !self.initially_shockable && self.epinephrine_times_s.is_empty() && self.arrest_time_s > 120.0 {
+                deviations.push("No epinephrine in a non-shockable arrest".to_string());
+                penalty += 15.0;
+            }
+        }
+        if epinephrine_interval_deviations > 0 {
+            deviations.push(format!("{} epinephrine interval(s) outside 3-5 min", epinephrine_interval_deviations));
+            penalty += 5.0 * epinephrine_interval_deviations as f64;
+        }
+        // Missing the cause of the arrest matters more than missing its consequences
+        for cause in &missed_causes {
+            if missed_underlying_causes.contains(cause) {
+                deviations.push(format!("{:?} caused the arrest but was not addressed", cause));
+                penalty += 25.0;
+            } else {
+                deviations.push(format!("{:?} present but not addressed", cause));
+                penalty += 10.0;
+            }
+        }
+        for cause in &addressed_absent_causes {
+            deviations.push(format!("{:?} treated but not present", cause));
+            penalty += 5.0;
+        }
+
+        AclsScore {
+            time_to_cpr_s: time_to_cpr,
+            time_to_first_shock_s: time_to_first_shock,
+            cpr_fraction,
+            epinephrine_interval_deviations,
+            missed_causes,
+            missed_underlying_causes,
+            addressed_absent_causes,
+            deviations,
+            score_percent: (100.0 - penalty).max(0.0),
+        }
+    }
+
+    /// Get a summary for a teaching overlay
+    pub fn get_summary(&self) -> String {
+        let Some(start) = self.arrest_started_s else {
+            return "ACLS: no arrest".to_string();
+        };
+        let clock = |seconds: f64| format!("{}:{:02}", (seconds / 60.0) as u32, (seconds % 60.0) as u32);
+        let status = match self.rosc_s {
+            Some(rosc) => format!("ROSC at {}", clock(rosc - start)),
+            None => format!("{:?}, T+{}", self.rhythm, clock(self.elapsed_s - start)),
+        };
+        let cycle = match self.cycle_started_s {
+            Some(cycle_start) if self.is_in_arrest() => {
+                format!(", CPR cycle {} ({})", self.cpr_cycles + 1, clock(self.elapsed_s - cycle_start))
+            }
+            _ => String::new(),
+        };
+        let last_epinephrine = match self.epinephrine_times_s.last() {
+            Some(&last) => format!(" (last {} ago)", clock(self.elapsed_s - last)),
+            None => String::new(),
+        };
+        format!(
+            "ACLS: {}{}, shocks={}, epinephrine x{}{}, amiodarone x{} | Next: {:?}",
+            status,
+            cycle,
+            self.shocks,
+            self.epinephrine_times_s.len(),
+            last_epinephrine,
+            self.amiodarone_doses,
+            self.recommended_actions()
+        )
+    }
+}
+
+impl Default for AclsAssistant {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::heart::CoronaryTerritory;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_vf_arrest_algorithm() {
+        let mut patient = initialize_patient(1, 3);
+        patient.start_acls_assistant();
+        patient.get_organ_mut::<Heart>().unwrap().induce_ventricular_fibrillation();
+        update_patient(&mut patient, 1.0);
+
+        let acls = patient.acls.as_ref().unwrap();
+        assert!(acls.is_in_arrest());
+        assert!(acls.recommended_actions().contains(&AclsAction::Defibrillate));
+
+        patient.start_cpr();
+        for _ in 0..30 {
+            update_patient(&mut patient, 1.0);
+        }
+        assert!(patient.defibrillate(200.0));
+        for _ in 0..10 {
+            update_patient(&mut patient, 1.0);
+        }
+
+        let acls = patient.acls.as_ref().unwrap();
+        assert!(acls.rosc_s.is_some());
+        assert!(acls.recommended_actions().contains(&AclsAction::StopCpr));
+        let score = acls.score();
+        assert!(score.time_to_first_shock_s.unwrap() <= TARGET_TIME_TO_SHOCK_S);
+        assert!(score.time_to_cpr_s.unwrap() <= TARGET_TIME_TO_CPR_S);
+
+        // Addressing every cause the checklist found leaves nothing missed
+        for cause in acls.causes_present.clone() {
+            patient.address_reversible_cause(cause);
+        }
+        let score = patient.acls.as_ref().unwrap().score();
+        assert!(score.missed_causes.is_empty());
+        assert!(score.score_percent > 80.0, "{:?}", score.deviations);
+    }
+
+    #[test]
+    fn test_reversible_causes_find_tamponade_until_it_is_drained() {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        assert!(patient.reversible_causes().is_empty());
+        let baseline_map = patient.blood.get_mean_arterial_pressure();
+
+        // An effusion past the pericardial reserve restricts filling and drops the pressure
+        patient.get_organ_mut::<Heart>().unwrap().add_pericardial_effusion(175.0);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        let findings = patient.reversible_causes();
+        assert_eq!(findings[0].cause, ReversibleCause::Tamponade);
+        assert!(findings[0].severity > 0.3);
+        assert!(findings[0].evidence.starts_with("Pericardial effusion 175 mL"));
+        assert!(patient.blood.get_mean_arterial_pressure() < baseline_map - 10.0);
+
+        // Pericardiocentesis removes the cause and the pressure recovers
+        assert_eq!(patient.pericardiocentesis(175.0), 175.0);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        assert!(!ReversibleCause::Tamponade.is_present(&patient));
+        assert!(patient.blood.get_mean_arterial_pressure() > baseline_map - 5.0);
+    }
+
+    #[test]
+    fn test_occluded_coronary_is_found_as_a_reversible_cause() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 1.0);
+        assert!(!ReversibleCause::CoronaryThrombosis.is_present(&patient));
+
+        let heart = patient.get_organ_mut::<Heart>().unwrap();
+        heart.set_territory_contractility(CoronaryTerritory::LeftAnteriorDescending, 0.0);
+        update_patient(&mut patient, 1.0);
+        let findings = patient.reversible_causes();
+        let thrombosis = findings.iter().find(|f| f.cause == ReversibleCause::CoronaryThrombosis).unwrap();
+        assert!(thrombosis.evidence.starts_with("LAD occlusion"));
+        assert!(thrombosis.severity > 0.3);
+    }
+}
@@ -0,0 +1,232 @@
+//! Batch simulation
+//!
+//! This module provides:
+//! - A batch of independent patients, each created, stepped and read on a
+//!   worker thread, spread over the available cores, so population-scale
+//!   studies (thousands of STEMI variants) need no threading of their own
+//! - Stop conditions ending a run early (a threshold crossed, a lethal rhythm), checked
+//!   after every step
+//! - Per-patient results collected in the order the patients were created
+//!
+//! Runs are handed to the workers one at a time, so runs that stop early do
+//! not leave a worker idle while another works through a fixed share.
+
+use crate::patient::{update_patient, Patient};
+use crate::validation::Measure;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Predicate ending a run early
+pub struct StopCondition {
+    /// Name reported when the condition stops a run
+    pub name: String,
+    predicate: Box<dyn Fn(&Patient) -> bool + Send + Sync>,
+}
+
+impl StopCondition {
+    /// Create a stop condition
+    ///
+    /// # Arguments
+    /// * `name` - Name reported when the condition stops a run
+    /// * `predicate` - True when the run should stop
+    pub fn new(name: &str, predicate: impl Fn(&Patient) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            name: name.to_string(),
+            predicate: Box::new(predicate),
+        }
+    }
+
+    /// Stop when a measure falls below a threshold
+    ///
+    /// # Arguments
+    /// * `measure` - Quantity watched
+    /// * `threshold` - Value it must stay at or above
+    pub fn below(measure: Measure, threshold: f64) -> Self {
+        Self::new(&format!("{:?} < {}", measure, threshold), move |patient| {
+            measure.measure(patient) < threshold
+        })
+    }
+
+    /// Stop when a measure rises above a threshold
+    ///
+    /// # Arguments
+    /// * `measure` - Quantity watched
+    /// * `threshold` - Value it must stay at or below
+    pub fn above(measure: Measure, threshold: f64) -> Self {
+        Self::new(&format!("{:?} > {}", measure, threshold), move |patient| {
+            measure.measure(patient) > threshold
+        })
+    }
+
+    /// Whether the run should stop
+    pub fn is_met(&self, patient: &Patient) -> bool {
+        (self.predicate)(patient)
+    }
+}
+
+/// Outcome of one patient's run
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRun<R> {
+    /// Index of the patient in the batch
+    pub index: usize,
+    /// Simulated time when the run ended (s)
+    pub time_s: f64,
+    /// Name of the stop condition that ended the run, if it ended early
+    pub stopped_by: Option<String>,
+    /// Result collected from the patient at the end of the run
+    pub result: R,
+}
+
+/// Batch of independent patients simulated in parallel
+pub struct SimulationBatch {
+    /// Number of patients
+    pub count: usize,
+    /// Simulated time of each run (s)
+    pub duration_s: f64,
+    /// Simulation time step (s)
+    pub time_step_s: f64,
+    /// Most worker threads used (0 = one per available core)
+    pub max_workers: usize,
+    /// Conditions ending a run early, checked in order after every step
+    pub stop_conditions: Vec<StopCondition>,
+}
+
+impl SimulationBatch {
+    /// Create a batch without stop conditions
+    ///
+    /// # Arguments
+    /// * `count` - Number of patients
+    /// * `duration_s` - Simulated time of each run (s)
+    pub fn new(count: usize, duration_s: f64) -> Self {
+        Self {
+            count,
+            duration_s: duration_s.max(0.0),
+            time_step_s: 0.1,
+            max_workers: 0,
+            stop_conditions: Vec::new(),
+        }
+    }
+
+    /// Add a condition ending a run early
+    pub fn add_stop_condition(&mut self, condition: StopCondition) {
+        self.stop_conditions.push(condition);
+    }
+
+    /// Run every patient
+    ///
+    /// Each patient is created by `create` on the worker that simulates it,
+    /// stepped until the duration passes or a stop condition is met, and read
+    /// by `collect`.
+    ///
+    /// # Arguments
+    /// * `create` - Creates the patient with the given batch index
+    /// * `collect` - Reads the result from the patient at the end of its run
+    ///
+    /// # Returns
+    /// One run per patient, in index order
+    pub fn run<R: Send>(
+        &self,
+        create: impl Fn(usize) -> Patient + Sync,
+        collect: impl Fn(usize, &Patient) -> R + Sync,
+    ) -> Vec<BatchRun<R>> {
+        let mut runs = parallel_map(self.count, self.max_workers, |index| self.simulate(index, &create, &collect));
+        runs.sort_by_key(|run| run.index);
+        runs
+    }
+
+    /// Simulate one patient
+    fn simulate<R>(
+        &self,
+        index: usize,
+        create: &impl Fn(usize) -> Patient,
+        collect: &impl Fn(usize, &Patient) -> R,
+    ) -> BatchRun<R> {
+        let mut patient = create(index);
+        let step = self.time_step_s.max(1e-3);
+        let steps = (self.duration_s / step).round() as usize;
+        let mut time_s = 0.0;
+        let mut stopped_by = None;
+        for _ in 0..steps {
+            update_patient(&mut patient, step);
+            time_s += step;
+            if let Some(condition) = self.stop_conditions.iter().find(|c| c.is_met(&patient)) {
+                stopped_by = Some(condition.name.clone());
+                break;
+            }
+        }
+        BatchRun {
+            index,
+            time_s,
+            stopped_by,
+            result: collect(index, &patient),
+        }
+    }
+}
+
+/// Compute `f` for every index on worker threads, handing out one index at a time
+///
+/// # Arguments
+/// * `count` - Number of indices
+/// * `max_workers` - Most worker threads used (0 = one per available core)
+/// * `f` - Computation for one index
+///
+/// # Returns
+/// The results, in completion order
+pub(crate) fn parallel_map<R: Send>(count: usize, max_workers: usize, f: impl Fn(usize) -> R + Sync) -> Vec<R> {
+    let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let workers = match max_workers {
+        0 => available,
+        max => max,
+    }
+    .min(count)
+    .max(1);
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(count));
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                if index >= count {
+                    break;
+                }
+                let result = f(index);
+                results.lock().expect("batch result lock poisoned").push(result);
+            });
+        }
+    });
+    results.into_inner().expect("batch result lock poisoned")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::heart::{CoronaryTerritory, Heart};
+    use crate::patient::initialize_patient;
+
+    #[test]
+    fn test_batch_runs_in_order_and_stops_early() {
+        // LAD occlusions of increasing severity; the severe ones drop the pressure
+        let mut batch = SimulationBatch::new(8, 120.0);
+        batch.time_step_s = 0.5;
+        batch.add_stop_condition(StopCondition::below(Measure::MeanArterialPressure, 88.0));
+        let runs = batch.run(
+            |index| {
+                let mut patient = initialize_patient(index as i32, 3);
+                if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                    let severity = index as f64 / 7.0;
+                    heart.set_territory_contractility(CoronaryTerritory::LeftAnteriorDescending, 1.0 - severity);
+                }
+                patient
+            },
+            |index, patient| (index as i32 == patient.id, Measure::MeanArterialPressure.measure(patient)),
+        );
+
+        assert_eq!(runs.len(), 8);
+        assert!(runs.iter().enumerate().all(|(i, run)| run.index == i && run.result.0));
+        assert!(runs[0].stopped_by.is_none());
+        assert!((runs[0].time_s - 120.0).abs() < 1e-6);
+        let stopped = runs.last().unwrap();
+        assert_eq!(stopped.stopped_by.as_deref(), Some("MeanArterialPressure < 88"));
+        assert!(stopped.time_s < 120.0 && stopped.result.1 < 88.0);
+    }
+}
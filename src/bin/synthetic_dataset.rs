@@ -0,0 +1,98 @@
+//! Synthetic dataset generator
+//!
+//! Generates a cohort, runs it through an optional scenario script and writes
+//! the charted vital signs, labs, medications, diagnoses and outcomes as CSV
+//! files (and FHIR resources as NDJSON with the `fhir` feature)
+
+use medicallib::*;
+use std::path::PathBuf;
+use std::process::exit;
+
+const USAGE: &str = "Usage: synthetic-dataset [options]
+
+Options:
+  --patients <n>       Number of patients (default 100)
+  --seed <n>           Seed of the cohort (default 0)
+  --acuity <profile>   outpatient, emergency or icu (default emergency)
+  --min-age <years>    Youngest age drawn (default 18)
+  --max-age <years>    Oldest age drawn (default 90)
+  --duration <s>       Length of each encounter without a scenario (default 3600)
+  --vitals-every <s>   Time between charted vital signs (default 300)
+  --labs-every <s>     Time between laboratory draws (default 1800)
+  --scenario <file>    Scenario script (JSON or TOML) run on every patient
+  --workers <n>        Worker threads (default: one per core)
+  --out <dir>          Output directory (default synthetic-dataset)
+  -h, --help           Show this help";
+
+/// Report a usage error and exit
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    exit(2);
+}
+
+/// Parse an option's value
+fn value<T: std::str::FromStr>(option: &str, value: Option<String>) -> T {
+    let value = value.unwrap_or_else(|| fail(&format!("{} needs a value", option)));
+    value.parse().unwrap_or_else(|_| fail(&format!("invalid value for {}: {}", option, value)))
+}
+
+fn main() {
+    let mut config = DatasetConfig::default();
+    let mut out = PathBuf::from("synthetic-dataset");
+    let mut args = std::env::args().skip(1);
+    while let Some(option) = args.next() {
+        match option.as_str() {
+            "--patients" => config.patients = value(&option, args.next()),
+            "--seed" => config.seed = value(&option, args.next()),
+            "--acuity" => {
+                config.generator.acuity = match value::<String>(&option, args.next()).as_str() {
+                    "outpatient" => AcuityProfile::outpatient(),
+                    "emergency" => AcuityProfile::emergency_department(),
+                    "icu" => AcuityProfile::intensive_care(),
+                    other => fail(&format!("unknown acuity profile: {}", other)),
+                }
+            }
+            "--min-age" => config.generator.min_age_years = value(&option, args.next()),
+            "--max-age" => config.generator.max_age_years = value(&option, args.next()),
+            "--duration" => config.duration_s = value(&option, args.next()),
+            "--vitals-every" => config.vitals_interval_s = value(&option, args.next()),
+            "--labs-every" => config.labs_interval_s = value(&option, args.next()),
+            "--scenario" => {
+                let path: String = value(&option, args.next());
+                let text = std::fs::read_to_string(&path).unwrap_or_else(|e| fail(&format!("{}: {}", path, e)));
+                config.scenario = Some(Scenario::parse(&text).unwrap_or_else(|e| fail(&format!("{}: {}", path, e))));
+            }
+            "--workers" => config.max_workers = value(&option, args.next()),
+            "--out" => out = value(&option, args.next()),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            other => fail(&format!("unknown option: {}", other)),
+        }
+    }
+
+    eprintln!("Generating {} patients (seed {})...", config.patients, config.seed);
+    let dataset = Dataset::generate(&config);
+    let files = [
+        ("vitals.csv", dataset.vitals_csv()),
+        ("labs.csv", dataset.labs_csv()),
+        ("medications.csv", dataset.medications_csv()),
+        ("diagnoses.csv", dataset.diagnoses_csv()),
+        ("outcomes.csv", dataset.outcomes_csv()),
+        #[cfg(feature = "fhir")]
+        ("resources.ndjson", dataset.fhir_ndjson()),
+    ];
+    if let Err(error) = std::fs::create_dir_all(&out) {
+        eprintln!("error: {}: {}", out.display(), error);
+        exit(1);
+    }
+    for (name, contents) in files {
+        let path = out.join(name);
+        if let Err(error) = std::fs::write(&path, contents) {
+            eprintln!("error: {}: {}", path.display(), error);
+            exit(1);
+        }
+        eprintln!("  wrote {}", path.display());
+    }
+}
@@ -6,9 +6,30 @@
 //! - Blood chemistry panel
 //! - Coagulation factors
 //! - Arterial blood gas (ABG) analysis
+//! - Getters and setters for the values organs read and write most, documented
+//!   with their units and kept within physical limits
 
 use std::fmt;
 
+/// Average glucose per percentage point of HbA1c in the ADAG relation
+/// eAG = 28.7 × HbA1c − 46.7 (mg/dL per %)
+pub const ADAG_GLUCOSE_PER_HBA1C_PERCENT: f64 = 28.7;
+
+/// Glucose intercept of the ADAG relation (mg/dL)
+pub const ADAG_GLUCOSE_INTERCEPT_MG_DL: f64 = 46.7;
+
+/// HbA1c of transfused donor red cells (%)
+pub const DONOR_HBA1C_PERCENT: f64 = 5.0;
+
+/// HbA1c a red cell population of normal lifespan settles at when exposed to
+/// a constant glucose (%)
+///
+/// # Arguments
+/// * `glucose_mg_dl` - Average blood glucose (mg/dL)
+pub fn steady_state_hba1c_percent(glucose_mg_dl: f64) -> f64 {
+    (glucose_mg_dl + ADAG_GLUCOSE_INTERCEPT_MG_DL) / ADAG_GLUCOSE_PER_HBA1C_PERCENT
+}
+
 /// ABO blood type system
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AboType {
@@ -150,6 +171,17 @@ pub struct BloodCells {
     /// Mean platelet volume (fL)
     /// Normal: 7.5-11.5 fL
     pub mpv_fl: f64,
+
+    /// Glycated hemoglobin (%) - glucose exposure over the red cells' lifespan
+    /// Normal: 4.0-5.6%, diabetes ≥6.5%
+    pub hba1c_percent: f64,
+}
+
+impl BloodCells {
+    /// Average glucose implied by the HbA1c (mg/dL)
+    pub fn estimated_average_glucose_mg_dl(&self) -> f64 {
+        ADAG_GLUCOSE_PER_HBA1C_PERCENT * self.hba1c_percent - ADAG_GLUCOSE_INTERCEPT_MG_DL
+    }
 }
 
 impl Default for BloodCells {
@@ -166,6 +198,7 @@ impl Default for BloodCells {
             wbc_differential: WbcDifferential::default(),
             platelet_count_thousand_per_ul: 250.0,
             mpv_fl: 9.5,
+            hba1c_percent: steady_state_hba1c_percent(90.0),
         }
     }
 }
@@ -241,6 +274,30 @@ pub struct BloodChemistry {
     /// Normal: 44-147 U/L
     pub alp_u_l: f64,
 
+    /// Lactate dehydrogenase (U/L) - tissue damage and hemolysis marker
+    /// Normal: 140-280 U/L
+    pub ldh_u_l: f64,
+
+    /// Amylase (U/L) - pancreatic and salivary enzyme
+    /// Normal: 30-110 U/L
+    pub amylase_u_l: f64,
+
+    /// Lipase (U/L) - pancreatic enzyme, above 3x normal in acute pancreatitis
+    /// Normal: 13-60 U/L
+    pub lipase_u_l: f64,
+
+    /// Serum iron (µg/dL)
+    /// Normal: 60-170 µg/dL
+    pub serum_iron_ug_dl: f64,
+
+    /// Ferritin (ng/mL) - reflects storage iron
+    /// Normal: 12-300 ng/mL, iron deficiency: <15 ng/mL
+    pub ferritin_ng_ml: f64,
+
+    /// Total iron-binding capacity (µg/dL) - rises in iron deficiency
+    /// Normal: 250-400 µg/dL
+    pub tibc_ug_dl: f64,
+
     /// Total cholesterol (mg/dL)
     /// Desirable: <200 mg/dL
     pub cholesterol_total_mg_dl: f64,
@@ -261,11 +318,32 @@ pub struct BloodChemistry {
     /// Normal: 0.5-2.2 mmol/L
     pub lactate_mmol_l: f64,
 
+    /// High-sensitivity cardiac troponin T (ng/L) - myocardial injury marker
+    /// Normal: <14 ng/L (women), <22 ng/L (men)
+    pub troponin_ng_l: f64,
+
+    /// Creatine kinase (U/L) - released by injured muscle, above 5x normal in rhabdomyolysis
+    /// Normal: 30-200 U/L
+    pub ck_u_l: f64,
+
+    /// Myoglobin (ng/mL) - muscle heme protein, nephrotoxic in the tubules
+    /// Normal: <85 ng/mL
+    pub myoglobin_ng_ml: f64,
+
+    /// Plasma insulin (µU/mL)
+    /// Normal fasting: 2-20 µU/mL
+    pub insulin_uu_ml: f64,
+
+    /// Plasma glucagon (pg/mL)
+    /// Normal fasting: 50-100 pg/mL
+    pub glucagon_pg_ml: f64,
+
+    /// Beta-hydroxybutyrate (mmol/L) - principal ketone body
+    /// Normal: <0.6 mmol/L, DKA: >3.0 mmol/L
+    pub beta_hydroxybutyrate_mmol_l: f64,
+
     /// Toxin levels (arbitrary units) - from original system
     pub toxin_level_au: f64,
-
-    /// Angiotensin II concentration (arbitrary units) - from original system
-    pub angiotensin_ii_au: f64,
 }
 
 impl Default for BloodChemistry {
@@ -288,13 +366,24 @@ impl Default for BloodChemistry {
             alt_u_l: 25.0,
             ast_u_l: 22.0,
             alp_u_l: 70.0,
+            ldh_u_l: 180.0,
+            amylase_u_l: 60.0,
+            lipase_u_l: 30.0,
+            serum_iron_ug_dl: 100.0,
+            ferritin_ng_ml: 125.0,
+            tibc_ug_dl: 300.0,
             cholesterol_total_mg_dl: 180.0,
             hdl_cholesterol_mg_dl: 55.0,
             ldl_cholesterol_mg_dl: 100.0,
             triglycerides_mg_dl: 100.0,
             lactate_mmol_l: 1.0,
+            troponin_ng_l: 5.0,
+            ck_u_l: 100.0,
+            myoglobin_ng_ml: 50.0,
+            insulin_uu_ml: 10.0,
+            glucagon_pg_ml: 75.0,
+            beta_hydroxybutyrate_mmol_l: 0.2,
             toxin_level_au: 0.0,
-            angiotensin_ii_au: 0.0,
         }
     }
 }
@@ -457,6 +546,100 @@ impl Default for BloodComposition {
 }
 
 impl BloodComposition {
+    /// Get blood glucose (mg/dL)
+    /// Normal fasting: 70-100 mg/dL
+    pub fn get_glucose_mg_dl(&self) -> f64 {
+        self.chemistry.glucose_mg_dl
+    }
+
+    /// Set blood glucose (mg/dL), never below zero
+    pub fn set_glucose_mg_dl(&mut self, glucose_mg_dl: f64) {
+        self.chemistry.glucose_mg_dl = glucose_mg_dl.max(0.0);
+    }
+
+    /// Get arterial oxygen saturation (%)
+    /// Normal: 95-100%
+    pub fn get_sao2_percent(&self) -> f64 {
+        self.gases.sao2_percent
+    }
+
+    /// Set arterial oxygen saturation (%), limited to 0-100%
+    pub fn set_sao2_percent(&mut self, sao2_percent: f64) {
+        self.gases.sao2_percent = sao2_percent.clamp(0.0, 100.0);
+    }
+
+    /// Get arterial partial pressure of oxygen (mmHg)
+    /// Normal: 75-100 mmHg
+    pub fn get_pao2_mmhg(&self) -> f64 {
+        self.gases.pao2_mmhg
+    }
+
+    /// Set arterial partial pressure of oxygen (mmHg), never below zero
+    pub fn set_pao2_mmhg(&mut self, pao2_mmhg: f64) {
+        self.gases.pao2_mmhg = pao2_mmhg.max(0.0);
+    }
+
+    /// Get arterial partial pressure of carbon dioxide (mmHg)
+    /// Normal: 35-45 mmHg
+    pub fn get_paco2_mmhg(&self) -> f64 {
+        self.gases.paco2_mmhg
+    }
+
+    /// Set arterial partial pressure of carbon dioxide (mmHg), never below zero
+    pub fn set_paco2_mmhg(&mut self, paco2_mmhg: f64) {
+        self.gases.paco2_mmhg = paco2_mmhg.max(0.0);
+    }
+
+    /// Get arterial pH
+    /// Normal: 7.35-7.45
+    pub fn get_ph(&self) -> f64 {
+        self.gases.ph
+    }
+
+    /// Get hemoglobin (g/dL)
+    /// Normal: 12-17 g/dL
+    pub fn get_hemoglobin_g_dl(&self) -> f64 {
+        self.cells.hemoglobin_g_dl
+    }
+
+    /// Set hemoglobin (g/dL), never below zero
+    pub fn set_hemoglobin_g_dl(&mut self, hemoglobin_g_dl: f64) {
+        self.cells.hemoglobin_g_dl = hemoglobin_g_dl.max(0.0);
+    }
+
+    /// Get serum sodium (mEq/L)
+    /// Normal: 135-145 mEq/L
+    pub fn get_sodium_meq_l(&self) -> f64 {
+        self.chemistry.sodium_meq_l
+    }
+
+    /// Set serum sodium (mEq/L), never below zero
+    pub fn set_sodium_meq_l(&mut self, sodium_meq_l: f64) {
+        self.chemistry.sodium_meq_l = sodium_meq_l.max(0.0);
+    }
+
+    /// Get serum potassium (mEq/L)
+    /// Normal: 3.5-5.0 mEq/L
+    pub fn get_potassium_meq_l(&self) -> f64 {
+        self.chemistry.potassium_meq_l
+    }
+
+    /// Set serum potassium (mEq/L), never below zero
+    pub fn set_potassium_meq_l(&mut self, potassium_meq_l: f64) {
+        self.chemistry.potassium_meq_l = potassium_meq_l.max(0.0);
+    }
+
+    /// Get blood lactate (mmol/L)
+    /// Normal: 0.5-2.0 mmol/L
+    pub fn get_lactate_mmol_l(&self) -> f64 {
+        self.chemistry.lactate_mmol_l
+    }
+
+    /// Set blood lactate (mmol/L), never below zero
+    pub fn set_lactate_mmol_l(&mut self, lactate_mmol_l: f64) {
+        self.chemistry.lactate_mmol_l = lactate_mmol_l.max(0.0);
+    }
+
     /// Get mean arterial pressure (MAP)
     /// Formula: MAP = DBP + 1/3(SBP - DBP)
     pub fn get_mean_arterial_pressure(&self) -> f64 {
@@ -586,6 +769,25 @@ impl BloodComposition {
         )
     }
 
+    /// Get iron studies summary
+    pub fn get_iron_summary(&self) -> String {
+        let transferrin_saturation = if self.chemistry.tibc_ug_dl > 0.0 {
+            self.chemistry.serum_iron_ug_dl / self.chemistry.tibc_ug_dl * 100.0
+        } else {
+            0.0
+        };
+        format!(
+            "=== Iron Studies ===\n\
+             Serum Iron: {:.0} µg/dL | TIBC: {:.0} µg/dL | Transferrin Sat: {:.0}%\n\
+             Ferritin: {:.0} ng/mL | MCV: {:.1} fL",
+            self.chemistry.serum_iron_ug_dl,
+            self.chemistry.tibc_ug_dl,
+            transferrin_saturation,
+            self.chemistry.ferritin_ng_ml,
+            self.cells.mcv_fl
+        )
+    }
+
     /// Get coagulation panel summary
     pub fn get_coag_summary(&self) -> String {
         format!(
@@ -603,3 +805,56 @@ impl BloodComposition {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organ::Organ;
+    use crate::organs::bones::Bones;
+    use crate::patient::{initialize_patient, Patient};
+
+    #[test]
+    fn test_setters_keep_values_within_physical_limits() {
+        let mut blood = BloodComposition::default();
+        blood.set_glucose_mg_dl(-20.0);
+        blood.set_sao2_percent(104.0);
+        blood.set_potassium_meq_l(-1.0);
+        blood.set_hemoglobin_g_dl(-0.5);
+        assert_eq!(blood.get_glucose_mg_dl(), 0.0);
+        assert_eq!(blood.get_sao2_percent(), 100.0);
+        assert_eq!(blood.get_potassium_meq_l(), 0.0);
+        assert_eq!(blood.get_hemoglobin_g_dl(), 0.0);
+
+        // Values within the limits are stored as given
+        blood.set_sao2_percent(88.0);
+        blood.set_lactate_mmol_l(4.5);
+        assert_eq!(blood.get_sao2_percent(), 88.0);
+        assert_eq!(blood.get_lactate_mmol_l(), 4.5);
+    }
+
+    #[test]
+    fn test_hba1c_tracks_weeks_of_glucose_not_a_single_reading() {
+        let mut patient = initialize_patient(1, 3);
+        let normal = patient.blood.cells.hba1c_percent;
+        assert!(normal < 5.7);
+
+        // Red cell turnover is slow, so the marrow is stepped hourly for weeks
+        let mut bones = patient.get_organ::<Bones>().unwrap().clone();
+        let mut hold_glucose = |patient: &mut Patient, days: usize, glucose_mg_dl: f64| {
+            for _ in 0..days * 24 {
+                patient.blood.chemistry.glucose_mg_dl = glucose_mg_dl;
+                bones.update(patient, 3600.0);
+            }
+        };
+        hold_glucose(&mut patient, 1, 250.0);
+        assert!(patient.blood.cells.hba1c_percent - normal < 0.1);
+        hold_glucose(&mut patient, 90, 250.0);
+        let diabetic = patient.blood.cells.hba1c_percent;
+        assert!(diabetic > 6.5 && diabetic < steady_state_hba1c_percent(250.0), "{}", diabetic);
+        assert!(patient.blood.cells.estimated_average_glucose_mg_dl() > 150.0);
+
+        // Tight control for a day barely moves it
+        hold_glucose(&mut patient, 1, 90.0);
+        assert!(diabetic - patient.blood.cells.hba1c_percent < 0.1);
+    }
+}
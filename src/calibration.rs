@@ -0,0 +1,299 @@
+//! Calibration of model parameters against data
+//!
+//! This module provides:
+//! - A uniform map of tunable organ parameters, each with a name, units and
+//!   bounds, that can be read from and written to a patient
+//! - Conversion of the map to and from a normalized vector for external optimizers
+//! - An objective comparing simulated outputs with measured targets, and a
+//!   simple coordinate search that fits chosen parameters to them
+
+use crate::organs::heart::Heart;
+use crate::organs::kidneys::Kidneys;
+use crate::organs::lungs::Lungs;
+use crate::patient::{initialize_patient, update_patient, Patient};
+use crate::validation::Measure;
+
+/// Golden-section iterations per parameter per pass of the search
+const GOLDEN_SECTION_ITERATIONS: usize = 16;
+
+/// Tunable model parameter
+#[derive(Debug, Clone, Copy)]
+pub struct ParameterSpec {
+    /// Name, as "Organ.field"
+    pub name: &'static str,
+    /// Units ("" if dimensionless)
+    pub units: &'static str,
+    /// Lowest allowed value
+    pub low: f64,
+    /// Highest allowed value
+    pub high: f64,
+    get: fn(&Patient) -> Option<f64>,
+    set: fn(&mut Patient, f64),
+}
+
+impl ParameterSpec {
+    /// Read the parameter from a patient
+    ///
+    /// # Returns
+    /// The value, or None if the patient lacks the organ
+    pub fn read(&self, patient: &Patient) -> Option<f64> {
+        (self.get)(patient)
+    }
+
+    /// Write the parameter to a patient, clamped to its bounds
+    pub fn write(&self, patient: &mut Patient, value: f64) {
+        (self.set)(patient, value.clamp(self.low, self.high));
+    }
+}
+
+/// Define a parameter backed by a field of an organ
+macro_rules! organ_parameter {
+    ($organ:ty, $type_name:literal, $field:ident, $units:literal, $low:expr, $high:expr) => {
+        ParameterSpec {
+            name: concat!($type_name, ".", stringify!($field)),
+            units: $units,
+            low: $low,
+            high: $high,
+            get: |patient| patient.get_organ::<$organ>().map(|organ| organ.$field),
+            set: |patient, value| {
+                if let Some(organ) = patient.get_organ_mut::<$organ>() {
+                    organ.$field = value;
+                }
+            },
+        }
+    };
+}
+
+/// Every tunable parameter the model exposes
+pub fn parameter_specs() -> Vec<ParameterSpec> {
+    vec![
+        organ_parameter!(Heart, "Heart", systemic_resistance_scale, "", 0.3, 3.0),
+        organ_parameter!(Heart, "Heart", filling_pressure_scale, "", 0.5, 2.0),
+        organ_parameter!(Heart, "Heart", resting_heart_rate_bpm, "bpm", 40.0, 110.0),
+        organ_parameter!(Heart, "Heart", adrenergic_sensitivity, "", 0.2, 3.0),
+        organ_parameter!(Heart, "Heart", diastolic_dysfunction, "", 0.0, 1.0),
+        organ_parameter!(Lungs, "Lungs", resting_respiration_rate_bpm, "breaths/min", 8.0, 30.0),
+        organ_parameter!(Lungs, "Lungs", resting_tidal_volume_ml, "mL", 250.0, 800.0),
+        organ_parameter!(Kidneys, "Kidneys", baseline_gfr_ml_per_min, "mL/min", 10.0, 150.0),
+    ]
+}
+
+/// Values of the tunable parameters of a patient
+#[derive(Debug, Clone)]
+pub struct Parameters {
+    specs: Vec<ParameterSpec>,
+    values: Vec<f64>,
+}
+
+impl Parameters {
+    /// Read every parameter a patient has
+    pub fn from_patient(patient: &Patient) -> Self {
+        let (specs, values) = parameter_specs()
+            .into_iter()
+            .filter_map(|spec| spec.read(patient).map(|value| (spec, value)))
+            .unzip();
+        Self { specs, values }
+    }
+
+    /// Parameter names, in vector order
+    pub fn names(&self) -> Vec<&'static str> {
+        self.specs.iter().map(|s| s.name).collect()
+    }
+
+    /// Definition of a parameter
+    pub fn spec(&self, name: &str) -> Option<&ParameterSpec> {
+        self.specs.iter().find(|s| s.name == name)
+    }
+
+    /// Value of a parameter
+    pub fn get(&self, name: &str) -> Option<f64> {
+        let index = self.specs.iter().position(|s| s.name == name)?;
+        Some(self.values[index])
+    }
+
+    /// Set a parameter, clamped to its bounds
+    ///
+    /// # Arguments
+    /// * `name` - Parameter name
+    /// * `value` - New value
+    ///
+    /// # Returns
+    /// The value set, or None if there is no such parameter
+    pub fn set(&mut self, name: &str, value: f64) -> Option<f64> {
+        let index = self.specs.iter().position(|s| s.name == name)?;
+        let spec = &self.specs[index];
+        self.values[index] = value.clamp(spec.low, spec.high);
+        Some(self.values[index])
+    }
+
+    /// Bounds of a parameter as (low, high)
+    pub fn bounds(&self, name: &str) -> Option<(f64, f64)> {
+        self.spec(name).map(|s| (s.low, s.high))
+    }
+
+    /// Write every parameter to a patient
+    pub fn apply(&self, patient: &mut Patient) {
+        for (spec, &value) in self.specs.iter().zip(&self.values) {
+            spec.write(patient, value);
+        }
+    }
+
+    /// Values scaled to 0.0-1.0 across their bounds, in name order
+    pub fn to_normalized(&self) -> Vec<f64> {
+        self.specs
+            .iter()
+            .zip(&self.values)
+            .map(|(spec, value)| (value - spec.low) / (spec.high - spec.low))
+            .collect()
+    }
+
+    /// Set the values from a vector scaled to 0.0-1.0 across their bounds
+    ///
+    /// # Arguments
+    /// * `normalized` - Scaled values, in name order; missing entries are left unchanged
+    pub fn set_normalized(&mut self, normalized: &[f64]) {
+        for ((spec, value), &scaled) in self.specs.iter().zip(self.values.iter_mut()).zip(normalized) {
+            *value = spec.low + scaled.clamp(0.0, 1.0) * (spec.high - spec.low);
+        }
+    }
+}
+
+/// Measured output the simulation should reproduce
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibrationTarget {
+    /// Output compared
+    pub measure: Measure,
+    /// Measured value
+    pub value: f64,
+    /// Weight of this target in the objective
+    pub weight: f64,
+}
+
+impl CalibrationTarget {
+    /// Create a target of unit weight
+    pub fn new(measure: Measure, value: f64) -> Self {
+        Self { measure, value, weight: 1.0 }
+    }
+}
+
+/// Fit of model parameters to measured outputs
+#[derive(Debug, Clone)]
+pub struct Calibration {
+    /// Outputs to reproduce
+    pub targets: Vec<CalibrationTarget>,
+    /// Simulated time before the outputs are compared (s)
+    pub duration_s: f64,
+    /// Simulation time step (s)
+    pub time_step_s: f64,
+}
+
+impl Calibration {
+    /// Create a calibration against measured outputs
+    pub fn new(targets: Vec<CalibrationTarget>) -> Self {
+        Self {
+            targets,
+            duration_s: 60.0,
+            time_step_s: 0.1,
+        }
+    }
+
+    /// Simulate a standard adult with the given parameters
+    pub fn simulate(&self, parameters: &Parameters) -> Patient {
+        let mut patient = initialize_patient(1, 12);
+        parameters.apply(&mut patient);
+        let step = self.time_step_s.max(1e-3);
+        for _ in 0..(self.duration_s / step).round() as usize {
+            update_patient(&mut patient, step);
+        }
+        patient
+    }
+
+    /// Weighted sum of squared relative errors between simulation and targets
+    pub fn objective(&self, parameters: &Parameters) -> f64 {
+        let patient = self.simulate(parameters);
+        self.targets
+            .iter()
+            .map(|t| {
+                let error = (t.measure.measure(&patient) - t.value) / t.value.abs().max(1e-9);
+                t.weight * error * error
+            })
+            .sum()
+    }
+
+    /// Fit chosen parameters by coordinate search
+    ///
+    /// Each pass minimizes the objective along one parameter at a time with a
+    /// golden-section search over its bounds. External optimizers can instead
+    /// drive `objective` through `Parameters::set_normalized`.
+    ///
+    /// # Arguments
+    /// * `parameters` - Starting values, updated with the fit
+    /// * `names` - Parameters to fit; unknown names are ignored
+    /// * `passes` - Number of passes over the parameters
+    ///
+    /// # Returns
+    /// The objective at the fitted values
+    pub fn fit(&self, parameters: &mut Parameters, names: &[&str], passes: usize) -> f64 {
+        let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+        for _ in 0..passes {
+            for &name in names {
+                let Some((mut low, mut high)) = parameters.bounds(name) else { continue };
+                let mut evaluate = |value: f64| {
+                    parameters.set(name, value);
+                    self.objective(parameters)
+                };
+                let mut a = high - ratio * (high - low);
+                let mut b = low + ratio * (high - low);
+                let (mut fa, mut fb) = (evaluate(a), evaluate(b));
+                for _ in 0..GOLDEN_SECTION_ITERATIONS {
+                    if fa < fb {
+                        high = b;
+                        (b, fb) = (a, fa);
+                        a = high - ratio * (high - low);
+                        fa = evaluate(a);
+                    } else {
+                        low = a;
+                        (a, fa) = (b, fb);
+                        b = low + ratio * (high - low);
+                        fb = evaluate(b);
+                    }
+                }
+                parameters.set(name, if fa < fb { a } else { b });
+            }
+        }
+        self.objective(parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parameter_map_bounds_and_vector() {
+        let patient = initialize_patient(1, 12);
+        let mut parameters = Parameters::from_patient(&patient);
+        assert_eq!(parameters.get("Heart.systemic_resistance_scale"), Some(1.0));
+        assert_eq!(parameters.set("Heart.resting_heart_rate_bpm", 300.0), Some(110.0));
+        assert_eq!(parameters.set("Heart.unknown", 1.0), None);
+
+        let vector = parameters.to_normalized();
+        assert_eq!(vector.len(), parameters.names().len());
+        let mut copy = Parameters::from_patient(&patient);
+        copy.set_normalized(&vector);
+        assert!((copy.get("Heart.resting_heart_rate_bpm").unwrap() - 110.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fit_resistance_to_mean_arterial_pressure() {
+        let patient = initialize_patient(1, 12);
+        let mut parameters = Parameters::from_patient(&patient);
+        let calibration = Calibration::new(vec![CalibrationTarget::new(Measure::MeanArterialPressure, 110.0)]);
+        let before = calibration.objective(&parameters);
+        let after = calibration.fit(&mut parameters, &["Heart.systemic_resistance_scale"], 1);
+        assert!(after < before);
+        assert!(parameters.get("Heart.systemic_resistance_scale").unwrap() > 1.0);
+        let map = Measure::MeanArterialPressure.measure(&calibration.simulate(&parameters));
+        assert!((map - 110.0).abs() < 5.0, "MAP {}", map);
+    }
+}
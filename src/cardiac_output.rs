@@ -0,0 +1,351 @@
+//! Cardiac output measurement
+//!
+//! This module provides virtual bedside cardiac output monitors whose readings
+//! can be compared with the heart's true output:
+//! - Pulmonary artery catheter thermodilution (Stewart-Hamilton) with its error
+//!   sources: injectate temperature and volume, respiratory timing, tricuspid
+//!   regurgitation and indicator loss at low output
+//! - Arterial pulse-contour analysis, calibrated or uncalibrated, which drifts
+//!   when vascular tone changes and is misled by line damping and arrhythmias
+
+use crate::organs::heart::Heart;
+
+/// Pulmonary artery blood temperature (°C)
+const BLOOD_TEMPERATURE_C: f64 = 37.0;
+
+/// Ratio of injectate to blood density x specific heat (5% dextrose)
+const INJECTATE_CONSTANT: f64 = 1.08;
+
+/// Sampling interval of the thermodilution curve (s)
+const CURVE_SAMPLE_INTERVAL_S: f64 = 0.1;
+
+/// Length of the recorded thermodilution curve (s)
+const CURVE_DURATION_S: f64 = 40.0;
+
+/// Population arterial compliance used by uncalibrated pulse-contour devices (mL/mmHg)
+const POPULATION_STROKE_VOLUME_PER_PULSE_PRESSURE: f64 = 1.75;
+
+/// Thermodilution injection made through the proximal port of a PA catheter
+#[derive(Debug, Clone)]
+pub struct ThermodilutionInjection {
+    /// Injectate volume entered into the computer (mL)
+    pub injectate_volume_ml: f64,
+    /// Injectate temperature (°C)
+    pub injectate_temperature_c: f64,
+    /// Volume actually injected (mL); less than entered makes the computer overestimate
+    pub delivered_volume_ml: f64,
+}
+
+impl ThermodilutionInjection {
+    /// Create an injection
+    ///
+    /// # Arguments
+    /// * `injectate_volume_ml` - Injectate volume in mL (5 or 10)
+    /// * `injectate_temperature_c` - Injectate temperature in °C
+    pub fn new(injectate_volume_ml: f64, injectate_temperature_c: f64) -> Self {
+        Self {
+            injectate_volume_ml,
+            injectate_temperature_c,
+            delivered_volume_ml: injectate_volume_ml,
+        }
+    }
+
+    /// 10 mL of iced injectate (best signal-to-noise)
+    pub fn iced() -> Self {
+        Self::new(10.0, 0.0)
+    }
+
+    /// 10 mL of room-temperature injectate
+    pub fn room_temperature() -> Self {
+        Self::new(10.0, 22.0)
+    }
+}
+
+/// Result of one thermodilution injection
+#[derive(Debug, Clone)]
+pub struct ThermodilutionMeasurement {
+    /// Cardiac output computed by the monitor (L/min)
+    pub cardiac_output_l_min: f64,
+    /// The heart's true cardiac output (L/min)
+    pub true_cardiac_output_l_min: f64,
+    /// Fall in pulmonary artery temperature after injection (°C), one sample per interval
+    pub curve_c: Vec<f64>,
+    /// Sampling interval of the curve (s)
+    pub sample_interval_s: f64,
+}
+
+impl ThermodilutionMeasurement {
+    /// Measurement error relative to the true output (%)
+    pub fn error_percent(&self) -> f64 {
+        measurement_error_percent(self.cardiac_output_l_min, self.true_cardiac_output_l_min)
+    }
+}
+
+/// Arterial line damping
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineDamping {
+    /// Optimally damped system
+    Optimal,
+    /// Air bubbles, clot or kinks blunt the waveform and narrow the pulse pressure
+    Overdamped,
+    /// Resonance overshoots systole and widens the pulse pressure
+    Underdamped,
+}
+
+impl LineDamping {
+    /// Pulse pressure displayed relative to the true pulse pressure
+    fn pulse_pressure_factor(self) -> f64 {
+        match self {
+            LineDamping::Optimal => 1.0,
+            LineDamping::Overdamped => 0.7,
+            LineDamping::Underdamped => 1.25,
+        }
+    }
+}
+
+/// Arterial pulse-contour cardiac output monitor
+#[derive(Debug, Clone)]
+pub struct PulseContourMonitor {
+    /// Stroke volume per mmHg of pulse pressure (mL/mmHg)
+    pub stroke_volume_per_pulse_pressure: f64,
+    /// Whether the monitor was calibrated against a reference measurement
+    pub calibrated: bool,
+    /// Damping of the arterial line feeding the monitor
+    pub damping: LineDamping,
+}
+
+/// Result of a pulse-contour reading
+#[derive(Debug, Clone)]
+pub struct PulseContourMeasurement {
+    /// Cardiac output estimated from the waveform (L/min)
+    pub cardiac_output_l_min: f64,
+    /// Stroke volume estimated from the waveform (mL)
+    pub stroke_volume_ml: f64,
+    /// The heart's true cardiac output (L/min)
+    pub true_cardiac_output_l_min: f64,
+    /// Conditions under which the estimate should not be trusted
+    pub warnings: Vec<&'static str>,
+}
+
+impl PulseContourMeasurement {
+    /// Measurement error relative to the true output (%)
+    pub fn error_percent(&self) -> f64 {
+        measurement_error_percent(self.cardiac_output_l_min, self.true_cardiac_output_l_min)
+    }
+}
+
+impl PulseContourMonitor {
+    /// Create an uncalibrated monitor using population vascular compliance
+    pub fn uncalibrated() -> Self {
+        Self {
+            stroke_volume_per_pulse_pressure: POPULATION_STROKE_VOLUME_PER_PULSE_PRESSURE,
+            calibrated: false,
+            damping: LineDamping::Optimal,
+        }
+    }
+
+    /// Calibrate against a reference cardiac output (e.g. thermodilution)
+    ///
+    /// The calibration holds only while vascular tone stays as it was.
+    ///
+    /// # Arguments
+    /// * `heart` - Heart producing the arterial waveform
+    /// * `reference_cardiac_output_l_min` - Reference cardiac output in L/min
+    pub fn calibrate(&mut self, heart: &Heart, reference_cardiac_output_l_min: f64) {
+        let pulse_pressure = self.displayed_pulse_pressure(heart);
+        if heart.heart_rate_bpm <= 0.0 || pulse_pressure <= 0.0 {
+            return;
+        }
+        let stroke_volume = reference_cardiac_output_l_min * 1000.0 / heart.heart_rate_bpm;
+        self.stroke_volume_per_pulse_pressure = stroke_volume / pulse_pressure;
+        self.calibrated = true;
+    }
+
+    /// Pulse pressure shown by the arterial line (mmHg)
+    fn displayed_pulse_pressure(&self, heart: &Heart) -> f64 {
+        (heart.aortic_pressure_systolic - heart.aortic_pressure_diastolic).max(0.0) * self.damping.pulse_pressure_factor()
+    }
+
+    /// Estimate cardiac output from the arterial waveform
+    ///
+    /// Stroke volume is taken as proportional to pulse pressure, so anything
+    /// that changes pulse pressure without changing stroke volume (vasopressors,
+    /// compensatory vasoconstriction, line damping) is misread as a change in output.
+    ///
+    /// # Arguments
+    /// * `heart` - Heart producing the arterial waveform
+    pub fn measure(&self, heart: &Heart) -> PulseContourMeasurement {
+        let stroke_volume = self.stroke_volume_per_pulse_pressure * self.displayed_pulse_pressure(heart);
+        let mut warnings = Vec::new();
+        if !self.calibrated {
+            warnings.push("Uncalibrated: population compliance assumed");
+        }
+        if self.damping != LineDamping::Optimal {
+            warnings.push("Arterial line damping distorts the waveform");
+        }
+        if heart.rr_variability > 0.1 {
+            warnings.push("Irregular rhythm: beat-to-beat stroke volume varies");
+        }
+        if heart.aortic_valve.regurgitation_severity > 0.3 {
+            warnings.push("Aortic regurgitation: diastolic runoff invalidates the contour");
+        }
+        PulseContourMeasurement {
+            cardiac_output_l_min: stroke_volume * heart.heart_rate_bpm.max(0.0) / 1000.0,
+            stroke_volume_ml: stroke_volume,
+            true_cardiac_output_l_min: heart.cardiac_output_l_min,
+            warnings,
+        }
+    }
+}
+
+/// Measurement error of a reading relative to the true value (%)
+///
+/// # Arguments
+/// * `measured` - Measured value
+/// * `true_value` - True value
+pub fn measurement_error_percent(measured: f64, true_value: f64) -> f64 {
+    if true_value > 0.0 {
+        (measured - true_value) / true_value * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Perform one thermodilution cardiac output measurement
+///
+/// The monitor applies the Stewart-Hamilton equation to the area under the
+/// temperature curve. Injections at different points of the respiratory cycle
+/// differ by up to ~10% (more with room-temperature injectate); tricuspid
+/// regurgitation recirculates the indicator and prolongs the curve
+/// (underestimation); at low output indicator is lost to the surrounding
+/// tissue (overestimation).
+///
+/// # Arguments
+/// * `heart` - Heart whose output is measured
+/// * `injection` - Injection made
+/// * `respiratory_phase` - Point in the respiratory cycle at injection (0.0-1.0)
+pub fn thermodilution(heart: &Heart, injection: &ThermodilutionInjection, respiratory_phase: f64) -> ThermodilutionMeasurement {
+    let true_output = heart.cardiac_output_l_min;
+    let samples = (CURVE_DURATION_S / CURVE_SAMPLE_INTERVAL_S) as usize;
+    let temperature_difference = (BLOOD_TEMPERATURE_C - injection.injectate_temperature_c).max(0.0);
+    if true_output < 0.1 || temperature_difference <= 0.0 {
+        return ThermodilutionMeasurement {
+            cardiac_output_l_min: 0.0,
+            true_cardiac_output_l_min: true_output,
+            curve_c: vec![0.0; samples],
+            sample_interval_s: CURVE_SAMPLE_INTERVAL_S,
+        };
+    }
+
+    // Output varies over the breath; the smaller signal of warm injectate is noisier
+    let variation = 0.05 + 0.1 * (injection.injectate_temperature_c / 25.0).clamp(0.0, 1.0);
+    let sampled_output =
+        true_output * (1.0 + variation * (2.0 * std::f64::consts::PI * respiratory_phase).sin());
+    let indicator_loss = (0.1 * (2.5 / true_output - 1.0)).clamp(0.0, 0.3);
+
+    // Indicator reaching the thermistor (mL x °C), divided by flow, gives the area
+    let indicator = injection.delivered_volume_ml * temperature_difference * INJECTATE_CONSTANT * (1.0 - indicator_loss);
+    let area = indicator / (sampled_output * 1000.0 / 60.0);
+
+    // Gamma-variate curve (area = 2 A beta^3); regurgitant indicator washes out slowly
+    let regurgitation = heart.tricuspid_valve.regurgitation_severity.clamp(0.0, 1.0);
+    let beta = (1.2 * 5.0 / sampled_output).clamp(0.4, 6.0);
+    let recirculating = 0.4 * regurgitation;
+    let main_amplitude = area * (1.0 - recirculating) / (2.0 * beta.powi(3));
+    let tail_beta = 3.0 * beta;
+    let tail_amplitude = area * recirculating * 1.6 / (2.0 * tail_beta.powi(3));
+    let curve_c: Vec<f64> = (0..samples)
+        .map(|i| {
+            let t = i as f64 * CURVE_SAMPLE_INTERVAL_S;
+            main_amplitude * t * t * (-t / beta).exp() + tail_amplitude * t * t * (-t / tail_beta).exp()
+        })
+        .collect();
+
+    let measured_area: f64 = curve_c.iter().sum::<f64>() * CURVE_SAMPLE_INTERVAL_S;
+    let entered_indicator = injection.injectate_volume_ml * temperature_difference * INJECTATE_CONSTANT;
+    let cardiac_output = if measured_area > 0.0 { entered_indicator / measured_area * 60.0 / 1000.0 } else { 0.0 };
+
+    ThermodilutionMeasurement {
+        cardiac_output_l_min: cardiac_output,
+        true_cardiac_output_l_min: true_output,
+        curve_c,
+        sample_interval_s: CURVE_SAMPLE_INTERVAL_S,
+    }
+}
+
+/// Average several thermodilution injections spread over the respiratory cycle
+///
+/// # Arguments
+/// * `heart` - Heart whose output is measured
+/// * `injection` - Injection repeated each time
+/// * `count` - Number of injections (usually 3)
+///
+/// # Returns
+/// Mean measured cardiac output (L/min)
+pub fn thermodilution_average(heart: &Heart, injection: &ThermodilutionInjection, count: usize) -> f64 {
+    let count = count.max(1);
+    (0..count)
+        .map(|i| thermodilution(heart, injection, i as f64 / count as f64).cardiac_output_l_min)
+        .sum::<f64>()
+        / count as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_thermodilution_errors() {
+        let mut heart = Heart::new(0, 12);
+        let iced = ThermodilutionInjection::iced();
+        let averaged = thermodilution_average(&heart, &iced, 3);
+        assert!(measurement_error_percent(averaged, heart.cardiac_output_l_min).abs() < 3.0);
+
+        // Injecting less than the entered volume overestimates output
+        let mut short = iced.clone();
+        short.delivered_volume_ml = 8.0;
+        assert!(thermodilution(&heart, &short, 0.0).error_percent() > 20.0);
+
+        // Tricuspid regurgitation recirculates indicator and underestimates output
+        heart.tricuspid_valve.regurgitation_severity = 1.0;
+        assert!(thermodilution(&heart, &iced, 0.0).error_percent() < -10.0);
+    }
+
+    #[test]
+    fn test_pulse_contour_drifts_with_vascular_tone_and_line_damping() {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        let mut monitor = PulseContourMonitor::uncalibrated();
+        let heart = patient.get_organ::<Heart>().unwrap();
+        assert!(monitor.measure(heart).warnings.iter().any(|w| w.starts_with("Uncalibrated")));
+        monitor.calibrate(heart, heart.cardiac_output_l_min);
+        let calibrated = monitor.measure(heart);
+        assert!(calibrated.error_percent().abs() < 1.0);
+        assert!(calibrated.warnings.is_empty());
+
+        // Vasoconstriction raises the pressure the same ejection produces, which reads as a rise in output
+        patient.get_organ_mut::<Heart>().unwrap().systemic_pressure_scale = 1.3;
+        for _ in 0..300 {
+            update_patient(&mut patient, 1.0);
+        }
+        let heart = patient.get_organ::<Heart>().unwrap();
+        let after_vasopressor = monitor.measure(heart);
+        assert!(after_vasopressor.error_percent() > 10.0, "error {:.1}%", after_vasopressor.error_percent());
+        // Recalibrating against a reference corrects it until the tone changes again
+        monitor.calibrate(heart, heart.cardiac_output_l_min);
+        assert!(monitor.measure(heart).error_percent().abs() < 1.0);
+
+        // A damped line narrows the pulse pressure and a resonant one widens it
+        monitor.damping = LineDamping::Overdamped;
+        let overdamped = monitor.measure(heart);
+        assert!(overdamped.error_percent() < -20.0);
+        assert!(overdamped.warnings.contains(&"Arterial line damping distorts the waveform"));
+        monitor.damping = LineDamping::Underdamped;
+        let underdamped = monitor.measure(heart);
+        assert!(underdamped.error_percent() > 20.0);
+        assert!(underdamped.warnings.contains(&"Arterial line damping distorts the waveform"));
+    }
+}
@@ -0,0 +1,98 @@
+//! Simulation clock
+//!
+//! This module provides:
+//! - The simulated time a patient has been advanced, the one time base for lab
+//!   orders and results, drug doses, waveform samples and corrected values
+//! - An optional wall-clock anchor placing simulation time zero on the
+//!   calendar, so events can be stamped as ISO 8601 instants
+
+use crate::timestamp::CivilTime;
+
+/// Simulated time of a patient, optionally anchored to the calendar
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SimulationClock {
+    /// Simulated time since the patient was created (s)
+    pub elapsed_s: f64,
+    /// Wall-clock time of simulation time zero (Unix seconds), if anchored
+    pub start_unix_s: Option<i64>,
+}
+
+impl SimulationClock {
+    /// Create a clock at zero without a calendar anchor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a clock at zero with simulation time zero at a wall-clock time
+    ///
+    /// # Arguments
+    /// * `start_unix_s` - Wall-clock time of simulation time zero (Unix seconds)
+    pub fn anchored(start_unix_s: i64) -> Self {
+        Self {
+            elapsed_s: 0.0,
+            start_unix_s: Some(start_unix_s),
+        }
+    }
+
+    /// Advance the clock
+    ///
+    /// # Arguments
+    /// * `delta_time_s` - Time step in seconds
+    pub fn advance(&mut self, delta_time_s: f64) {
+        self.elapsed_s += delta_time_s.max(0.0);
+    }
+
+    /// Wall-clock time of a simulation time (Unix seconds), if anchored
+    ///
+    /// # Arguments
+    /// * `time_s` - Simulation time (s)
+    pub fn unix_s(&self, time_s: f64) -> Option<f64> {
+        self.start_unix_s.map(|start| start as f64 + time_s)
+    }
+
+    /// ISO 8601 instant of a simulation time, if anchored
+    ///
+    /// # Arguments
+    /// * `time_s` - Simulation time (s)
+    pub fn instant(&self, time_s: f64) -> Option<String> {
+        self.start_unix_s
+            .map(|start| CivilTime::from_unix(start + time_s.floor() as i64).iso8601())
+    }
+
+    /// ISO 8601 instant of the current simulation time, if anchored
+    pub fn now(&self) -> Option<String> {
+        self.instant(self.elapsed_s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::labs::LabPanel;
+    use crate::organs::heart::Heart;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_patient_events_share_the_clock() {
+        let mut patient = initialize_patient(1, 3);
+        patient.anchor_clock(1_704_067_200);
+        for _ in 0..300 {
+            update_patient(&mut patient, 0.1);
+        }
+        assert!((patient.sim_time() - 30.0).abs() < 1e-9);
+        let order = patient.order_labs(LabPanel::Lactate);
+        patient.give_epinephrine(1.0);
+        update_patient(&mut patient, 600.0);
+
+        assert!((patient.sim_time() - 630.0).abs() < 1e-9);
+        let report = patient.labs.report(order).unwrap();
+        assert!((report.ordered_at_s - 30.0).abs() < 1e-9);
+        assert_eq!(patient.doses[0].medication, "Epinephrine");
+        assert!((patient.doses[0].time_s - 30.0).abs() < 1e-9);
+        let heart = patient.get_organ::<Heart>().unwrap();
+        let (last_sample_s, _) = *heart.ekg_sampler.timestamped(&heart.ekg_leads[0]).last().unwrap();
+        assert!((patient.sim_time() - last_sample_s).abs() < 0.01);
+        assert_eq!(patient.clock.now().as_deref(), Some("2024-01-01T00:10:30Z"));
+        assert_eq!(SimulationClock::new().now(), None);
+    }
+}
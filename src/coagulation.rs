@@ -0,0 +1,444 @@
+//! Coagulation
+//!
+//! This module provides:
+//! - Plasma clotting factor levels made by the liver and lost to dilution,
+//!   bleeding and consumption
+//! - Anticoagulants: warfarin (vitamin K antagonist, reversed by vitamin K),
+//!   unfractionated heparin and direct oral anticoagulants (factor Xa inhibitors)
+//! - Disseminated intravascular coagulation (DIC) during sepsis or massive tissue
+//!   necrosis: falling fibrinogen and platelets, rising D-dimer, ISTH DIC score
+//! - PT/INR, aPTT, anti-Xa, fibrinogen, D-dimer and bleeding time written to the
+//!   blood's clotting panel
+//! - How much longer bleeding vessels take to seal with too few platelets, a high
+//!   INR or aPTT, or low fibrinogen
+//! - Plasma (FFP) and cryoprecipitate transfusions restoring factors and fibrinogen
+
+use crate::blood::BloodComposition;
+use crate::fluids::FluidCompartments;
+
+/// Time constant of clotting factor turnover (factor VII half-life ~5 h, s)
+const CLOTTING_FACTOR_TURNOVER_S: f64 = 26_000.0;
+
+/// Time constant of fibrinogen turnover (half-life ~4 days, s)
+const FIBRINOGEN_TURNOVER_S: f64 = 500_000.0;
+
+/// Normal plasma fibrinogen (mg/dL)
+const NORMAL_FIBRINOGEN_MG_DL: f64 = 300.0;
+
+/// Warfarin elimination time constant (half-life ~40 h, s)
+const WARFARIN_ELIMINATION_S: f64 = 208_000.0;
+
+/// Warfarin in the body inhibiting half of vitamin K recycling (mg)
+const WARFARIN_EC50_MG: f64 = 2.6;
+
+/// Vitamin K elimination time constant (s)
+const VITAMIN_K_ELIMINATION_S: f64 = 125_000.0;
+
+/// Heparin elimination time constant (half-life ~90 min, s)
+const HEPARIN_ELIMINATION_S: f64 = 7800.0;
+
+/// Heparin in the body that doubles the aPTT above its baseline (units)
+const HEPARIN_APTT_UNITS: f64 = 1900.0;
+
+/// Factor Xa inhibitor elimination time constant (apixaban half-life ~12 h, s)
+const DOAC_ELIMINATION_S: f64 = 62_000.0;
+
+/// Time constant of consumption at full DIC drive (s)
+const DIC_CONSUMPTION_S: f64 = 43_200.0;
+
+/// Fibrinogen in a unit of fresh frozen plasma (mg/dL)
+const PLASMA_PRODUCT_FIBRINOGEN_MG_DL: f64 = 250.0;
+
+/// Fibrinogen in one unit of cryoprecipitate (mg)
+const CRYOPRECIPITATE_FIBRINOGEN_MG: f64 = 250.0;
+
+/// Factor VIII in one unit of cryoprecipitate (IU; plasma normal is 1 IU/mL)
+const CRYOPRECIPITATE_FACTOR_VIII_IU: f64 = 100.0;
+
+/// Longest hemostatic delay relative to normal
+const MAX_HEMOSTATIC_DELAY: f64 = 50.0;
+
+/// Patient coagulation state
+#[derive(Debug, Clone)]
+pub struct Coagulation {
+    /// Vitamin K-dependent factors II, VII, IX and X relative to normal
+    pub vitamin_k_dependent_factors: f64,
+    /// Factor VIII relative to normal
+    pub factor_viii: f64,
+    /// Plasma fibrinogen (mg/dL)
+    pub fibrinogen_mg_dl: f64,
+    /// Hepatic synthetic capacity relative to normal, set from the liver
+    pub hepatic_synthesis: f64,
+    /// Sepsis driving tissue factor expression (0.0 = none, 1.0 = septic shock)
+    pub sepsis_severity: f64,
+    /// Fraction of tissue undergoing necrosis, set from the organs
+    pub tissue_necrosis: f64,
+    /// Warfarin in the body (mg)
+    pub warfarin_mg: f64,
+    /// Vitamin K in the body (mg)
+    pub vitamin_k_mg: f64,
+    /// Unfractionated heparin in the body (units)
+    pub heparin_units: f64,
+    /// Heparin infusion rate (units/h)
+    pub heparin_infusion_units_h: f64,
+    /// Factor Xa inhibitor (apixaban-equivalent) in the body (mg)
+    pub doac_mg: f64,
+    /// Anti-factor Xa activity (IU/mL; heparin target 0.3-0.7)
+    pub anti_xa_iu_ml: f64,
+    /// Plasma volume at the last update (mL)
+    last_plasma_ml: f64,
+    /// Plasma lost with whole blood at the last update (mL, cumulative)
+    last_plasma_lost_with_blood_ml: f64,
+}
+
+impl Coagulation {
+    /// Create normal coagulation
+    pub fn new() -> Self {
+        Self {
+            vitamin_k_dependent_factors: 1.0,
+            factor_viii: 1.0,
+            fibrinogen_mg_dl: NORMAL_FIBRINOGEN_MG_DL,
+            hepatic_synthesis: 1.0,
+            sepsis_severity: 0.0,
+            tissue_necrosis: 0.0,
+            warfarin_mg: 0.0,
+            vitamin_k_mg: 0.0,
+            heparin_units: 0.0,
+            heparin_infusion_units_h: 0.0,
+            doac_mg: 0.0,
+            anti_xa_iu_ml: 0.0,
+            last_plasma_ml: 0.0,
+            last_plasma_lost_with_blood_ml: 0.0,
+        }
+    }
+
+    /// Give an oral dose of warfarin
+    ///
+    /// # Arguments
+    /// * `mg` - Dose in mg (typically 2-10 mg daily)
+    pub fn give_warfarin(&mut self, mg: f64) {
+        self.warfarin_mg += mg.max(0.0);
+    }
+
+    /// Give vitamin K (phytonadione) to reverse warfarin
+    ///
+    /// # Arguments
+    /// * `mg` - Dose in mg (1-10 mg)
+    pub fn give_vitamin_k(&mut self, mg: f64) {
+        self.vitamin_k_mg += mg.max(0.0);
+    }
+
+    /// Give an intravenous heparin bolus
+    ///
+    /// # Arguments
+    /// * `units` - Dose in units (typically 80 units/kg)
+    pub fn give_heparin_bolus(&mut self, units: f64) {
+        self.heparin_units += units.max(0.0);
+    }
+
+    /// Set the heparin infusion rate
+    ///
+    /// # Arguments
+    /// * `units_per_hour` - Infusion rate in units/h (0 to stop)
+    pub fn set_heparin_infusion(&mut self, units_per_hour: f64) {
+        self.heparin_infusion_units_h = units_per_hour.max(0.0);
+    }
+
+    /// Give an oral direct factor Xa inhibitor
+    ///
+    /// # Arguments
+    /// * `mg` - Dose in apixaban-equivalent mg (typically 5 mg twice daily)
+    pub fn give_doac(&mut self, mg: f64) {
+        self.doac_mg += mg.max(0.0);
+    }
+
+    /// Fraction of vitamin K recycling blocked by warfarin (0.0-1.0)
+    pub fn warfarin_effect(&self) -> f64 {
+        let inhibition = self.warfarin_mg / (self.warfarin_mg + WARFARIN_EC50_MG);
+        // Vitamin K bypasses the blocked epoxide reductase
+        inhibition * (1.0 - self.vitamin_k_mg / (self.vitamin_k_mg + 1.0))
+    }
+
+    /// Factor Xa inhibition by a direct oral anticoagulant (0.0-1.0)
+    pub fn doac_effect(&self) -> f64 {
+        self.doac_mg / (self.doac_mg + 5.0)
+    }
+
+    /// Thrombin generation driving consumption (0.0 = none, 1.0 = overt DIC)
+    pub fn dic_drive(&self) -> f64 {
+        let necrosis = ((self.tissue_necrosis - 0.1) / 0.3).clamp(0.0, 1.0);
+        (self.sepsis_severity.clamp(0.0, 1.0) + necrosis).min(1.5)
+    }
+
+    /// Calculate the ISTH overt DIC score
+    ///
+    /// # Arguments
+    /// * `blood` - Blood with the current platelet count and clotting panel
+    ///
+    /// # Returns
+    /// Score from 0 to 8; 5 or more is compatible with overt DIC
+    pub fn dic_score(blood: &BloodComposition) -> u8 {
+        let platelets = blood.cells.platelet_count_thousand_per_ul;
+        let clotting = &blood.clotting;
+        let platelet_points = if platelets < 50.0 {
+            2
+        } else if platelets < 100.0 {
+            1
+        } else {
+            0
+        };
+        let d_dimer_points = if clotting.d_dimer_ng_ml > 4000.0 {
+            3
+        } else if clotting.d_dimer_ng_ml > 1000.0 {
+            2
+        } else {
+            0
+        };
+        let prolongation = clotting.pt_seconds - 12.0;
+        let pt_points = if prolongation > 6.0 {
+            2
+        } else if prolongation > 3.0 {
+            1
+        } else {
+            0
+        };
+        let fibrinogen_points = if clotting.fibrinogen_mg_dl < 100.0 { 1 } else { 0 };
+        platelet_points + d_dimer_points + pt_points + fibrinogen_points
+    }
+
+    /// Check for overt DIC (ISTH score >= 5)
+    ///
+    /// # Arguments
+    /// * `blood` - Blood with the current platelet count and clotting panel
+    pub fn is_dic(blood: &BloodComposition) -> bool {
+        Self::dic_score(blood) >= 5
+    }
+
+    /// How many times longer than normal a bleeding vessel takes to seal
+    ///
+    /// Platelets form the primary plug, thrombin generation (the worse of the
+    /// PT/INR and aPTT) stabilises it and fibrinogen provides the fibrin mesh.
+    ///
+    /// # Arguments
+    /// * `blood` - Blood with the current platelet count and clotting panel
+    ///
+    /// # Returns
+    /// 1.0 with normal hemostasis, up to 50.0
+    pub fn hemostatic_delay(blood: &BloodComposition) -> f64 {
+        let clotting = &blood.clotting;
+        let platelets = (100.0 / blood.cells.platelet_count_thousand_per_ul.max(5.0)).max(1.0).powf(1.5);
+        let thrombin = clotting.inr.max(clotting.aptt_seconds / 30.0).max(1.0).powi(2);
+        let fibrin = (150.0 / clotting.fibrinogen_mg_dl.max(20.0)).max(1.0);
+        (platelets * thrombin * fibrin).min(MAX_HEMOSTATIC_DELAY)
+    }
+
+    /// Transfuse fresh frozen plasma (or the plasma of another blood product)
+    ///
+    /// The donor plasma carries normal factor levels and mixes with the patient's.
+    /// The caller adds the volume to the circulation.
+    ///
+    /// # Arguments
+    /// * `plasma_ml` - Patient plasma volume before the transfusion (mL)
+    /// * `volume_ml` - Volume transfused (mL; ~250 mL per unit)
+    pub fn transfuse_plasma(&mut self, plasma_ml: f64, volume_ml: f64) {
+        let volume_ml = volume_ml.max(0.0);
+        let donor_fraction = volume_ml / (plasma_ml.max(0.0) + volume_ml).max(1.0);
+        self.vitamin_k_dependent_factors += (1.0 - self.vitamin_k_dependent_factors) * donor_fraction;
+        self.factor_viii += (1.0 - self.factor_viii) * donor_fraction;
+        self.fibrinogen_mg_dl += (PLASMA_PRODUCT_FIBRINOGEN_MG_DL - self.fibrinogen_mg_dl) * donor_fraction;
+        self.add_factor_containing_volume(volume_ml);
+    }
+
+    /// Transfuse cryoprecipitate, concentrated fibrinogen and factor VIII
+    ///
+    /// The caller adds the volume (~15 mL per unit) to the circulation.
+    ///
+    /// # Arguments
+    /// * `plasma_ml` - Patient plasma volume before the transfusion (mL)
+    /// * `units` - Units transfused (typically a pool of 10)
+    pub fn transfuse_cryoprecipitate(&mut self, plasma_ml: f64, units: f64) {
+        let units = units.max(0.0);
+        let plasma_ml = plasma_ml.max(100.0);
+        self.fibrinogen_mg_dl += units * CRYOPRECIPITATE_FIBRINOGEN_MG / (plasma_ml / 100.0);
+        self.factor_viii += units * CRYOPRECIPITATE_FACTOR_VIII_IU / plasma_ml;
+        self.add_factor_containing_volume(units * 15.0);
+    }
+
+    /// Count transfused plasma as already mixed so it does not dilute the factors
+    fn add_factor_containing_volume(&mut self, volume_ml: f64) {
+        if self.last_plasma_ml > 0.0 {
+            self.last_plasma_ml += volume_ml;
+        }
+    }
+
+    /// Check whether any anticoagulant is having a meaningful effect
+    pub fn is_anticoagulated(&self) -> bool {
+        self.warfarin_effect() > 0.3 || self.anti_xa_iu_ml > 0.1
+    }
+
+    /// Advance factor levels, drugs and DIC, and write the clotting panel
+    ///
+    /// # Arguments
+    /// * `blood` - Blood receiving the clotting panel and losing platelets to DIC
+    /// * `fluids` - Body fluid compartments (plasma volume for dilution)
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, blood: &mut BloodComposition, fluids: &FluidCompartments, delta_time_s: f64) {
+        let relax = |time_constant: f64| 1.0 - (-delta_time_s / time_constant).exp();
+
+        // Plasma lost with whole blood takes its factors and platelets with it; plasma
+        // gained from crystalloid, packed cells or capillary refill dilutes what is left
+        let lost_with_blood = fluids.plasma_lost_with_blood_ml - self.last_plasma_lost_with_blood_ml;
+        let previous_plasma = self.last_plasma_ml - lost_with_blood;
+        if self.last_plasma_ml > 0.0 && fluids.plasma_ml > 0.0 {
+            let dilution = (previous_plasma / fluids.plasma_ml).clamp(0.5, 1.5);
+            // Hemostasis at the bleeding site consumes factors as well
+            let bleeding_consumption = 1.0 - (0.3 * lost_with_blood / self.last_plasma_ml).clamp(0.0, 0.5);
+            let factor = dilution * bleeding_consumption;
+            self.vitamin_k_dependent_factors *= factor;
+            self.factor_viii *= factor;
+            self.fibrinogen_mg_dl *= factor;
+            blood.cells.platelet_count_thousand_per_ul *= factor;
+        }
+        self.last_plasma_ml = fluids.plasma_ml;
+        self.last_plasma_lost_with_blood_ml = fluids.plasma_lost_with_blood_ml;
+
+        // Drug elimination
+        self.warfarin_mg *= 1.0 - relax(WARFARIN_ELIMINATION_S);
+        self.vitamin_k_mg *= 1.0 - relax(VITAMIN_K_ELIMINATION_S);
+        self.heparin_units = self.heparin_units * (1.0 - relax(HEPARIN_ELIMINATION_S))
+            + self.heparin_infusion_units_h * delta_time_s / 3600.0;
+        self.doac_mg *= 1.0 - relax(DOAC_ELIMINATION_S);
+
+        // Synthesis: warfarin starves the vitamin K-dependent factors; inflammation raises fibrinogen
+        let synthesis = self.hepatic_synthesis.clamp(0.0, 1.2);
+        let acute_phase = 1.0 + 0.5 * self.sepsis_severity.clamp(0.0, 1.0);
+        self.vitamin_k_dependent_factors += (synthesis * (1.0 - self.warfarin_effect())
+            - self.vitamin_k_dependent_factors)
+            * relax(CLOTTING_FACTOR_TURNOVER_S);
+        self.factor_viii += (acute_phase - self.factor_viii) * relax(CLOTTING_FACTOR_TURNOVER_S);
+        self.fibrinogen_mg_dl += (NORMAL_FIBRINOGEN_MG_DL * synthesis * acute_phase - self.fibrinogen_mg_dl)
+            * relax(FIBRINOGEN_TURNOVER_S);
+
+        // DIC: widespread thrombin generation consumes factors, fibrinogen and platelets
+        let drive = self.dic_drive();
+        let consumed = 1.0 - (-drive * delta_time_s / DIC_CONSUMPTION_S).exp();
+        self.vitamin_k_dependent_factors *= 1.0 - consumed;
+        self.factor_viii *= 1.0 - consumed;
+        self.fibrinogen_mg_dl *= 1.0 - consumed;
+        blood.cells.platelet_count_thousand_per_ul *= 1.0 - consumed;
+
+        self.anti_xa_iu_ml = self.heparin_units / 5600.0 + 0.05 * self.doac_mg;
+
+        // Clotting panel
+        let clotting = &mut blood.clotting;
+        let doac = self.doac_effect();
+        clotting.inr = (1.0 / self.vitamin_k_dependent_factors.max(0.05)).sqrt() * (1.0 + 0.3 * doac);
+        clotting.pt_seconds = 12.0 * clotting.inr;
+        let intrinsic = 0.5 * self.vitamin_k_dependent_factors + 0.5 * self.factor_viii.min(1.0);
+        clotting.aptt_seconds = 30.0
+            * (1.0 / intrinsic.max(0.05)).sqrt()
+            * (1.0 + self.heparin_units / HEPARIN_APTT_UNITS)
+            * (1.0 + 0.2 * doac);
+        clotting.clotting_time_min = 8.0 * clotting.aptt_seconds / 30.0;
+        clotting.fibrinogen_mg_dl = self.fibrinogen_mg_dl;
+        // Bleeding time lengthens once platelets fall below ~100 K/µL (Harker)
+        let platelets = blood.cells.platelet_count_thousand_per_ul;
+        clotting.bleeding_time_min = if platelets < 100.0 { (30.5 - platelets.max(0.0) / 3.85).clamp(4.0, 30.0) } else { 4.0 };
+        // Fibrinolysis of microvascular clot; the vascular system clears D-dimer over hours
+        clotting.d_dimer_ng_ml += drive * 8000.0 * delta_time_s / 21_600.0;
+    }
+
+    /// Get a summary of coagulation
+    ///
+    /// # Arguments
+    /// * `blood` - Blood with the current clotting panel
+    pub fn get_summary(&self, blood: &BloodComposition) -> String {
+        let clotting = &blood.clotting;
+        let mut flags = String::new();
+        if self.warfarin_effect() > 0.3 {
+            flags.push_str(", WARFARIN");
+        }
+        if self.heparin_units > 100.0 {
+            flags.push_str(&format!(", HEPARIN (anti-Xa {:.2})", self.heparin_units / 5600.0));
+        }
+        if self.doac_effect() > 0.1 {
+            flags.push_str(", FACTOR Xa INHIBITOR");
+        }
+        if Self::is_dic(blood) {
+            flags.push_str(&format!(", DIC (ISTH {})", Self::dic_score(blood)));
+        }
+        format!(
+            "Coagulation: INR={:.2}, aPTT={:.0} s, Fibrinogen={:.0} mg/dL, D-dimer={:.0} ng/mL{}",
+            clotting.inr, clotting.aptt_seconds, clotting.fibrinogen_mg_dl, clotting.d_dimer_ng_ml, flags
+        )
+    }
+}
+
+impl Default for Coagulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organ::Organ;
+    use crate::organs::intestines::{Intestines, StoolCharacter};
+    use crate::organs::stomach::Stomach;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_anticoagulants_prolong_clotting_times() {
+        let mut blood = BloodComposition::default();
+        let fluids = FluidCompartments::new(70.0);
+        let mut coagulation = Coagulation::new();
+
+        for hour in 0..96 {
+            if hour % 24 == 0 {
+                coagulation.give_warfarin(5.0);
+            }
+            coagulation.update(&mut blood, &fluids, 3600.0);
+        }
+        assert!(blood.clotting.inr > 1.8 && blood.clotting.inr < 3.5, "INR {}", blood.clotting.inr);
+
+        coagulation.give_vitamin_k(10.0);
+        for _ in 0..24 {
+            coagulation.update(&mut blood, &fluids, 3600.0);
+        }
+        assert!(blood.clotting.inr < 1.3, "INR {}", blood.clotting.inr);
+
+        coagulation.give_heparin_bolus(6000.0);
+        coagulation.set_heparin_infusion(1300.0);
+        for _ in 0..12 {
+            coagulation.update(&mut blood, &fluids, 3600.0);
+        }
+        assert!(blood.clotting.aptt_seconds > 60.0, "aPTT {}", blood.clotting.aptt_seconds);
+        assert!(coagulation.is_anticoagulated());
+    }
+
+    #[test]
+    fn test_anticoagulation_makes_an_ulcer_bleed_into_the_gut() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 60.0);
+        patient.get_organ_mut::<Stomach>().unwrap().develop_ulcer(0.4);
+        let mut heparinized = patient.fork();
+        heparinized.coagulation.give_heparin_bolus(6000.0);
+        heparinized.coagulation.set_heparin_infusion(1300.0);
+        for _ in 0..180 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut heparinized, 60.0);
+        }
+
+        // The shallow ulcer only bleeds once clotting is impaired; the blood is digested
+        // to melena and its protein absorbed as urea, raising BUN but not creatinine
+        assert_eq!(patient.get_organ::<Stomach>().unwrap().ulcer_blood_loss_ml, 0.0);
+        let stomach = heparinized.get_organ::<Stomach>().unwrap();
+        assert!(stomach.is_ulcer_bleeding() && stomach.ulcer_blood_loss_ml > 500.0, "{}", stomach.get_summary());
+        let intestines = heparinized.get_organ::<Intestines>().unwrap();
+        assert_eq!(intestines.last_stool_character, Some(StoolCharacter::Melena));
+        assert!(heparinized.blood.cells.hemoglobin_g_dl < patient.blood.cells.hemoglobin_g_dl - 0.5);
+        let bun_creatinine = |p: &crate::patient::Patient| p.blood.chemistry.bun_mg_dl / p.blood.chemistry.creatinine_mg_dl;
+        assert!(bun_creatinine(&heparinized) > bun_creatinine(&patient) + 5.0);
+    }
+}
@@ -0,0 +1,348 @@
+//! Patient cohorts
+//!
+//! This module provides:
+//! - A cohort of patients advanced together for population-scale studies
+//! - An optional structure-of-arrays layout holding the cohort's blood
+//!   chemistry column by column, refreshed in a single pass after each step,
+//!   so population statistics and cohort-wide chemistry changes run as tight
+//!   loops over contiguous values rather than striding across patient structs
+//! - Derived chemistry (anion gap, serum osmolality) computed for the whole
+//!   cohort at once
+
+use crate::blood::BloodChemistry;
+use crate::patient::{update_patient, Patient};
+
+/// Define the chemistry columns, one per `BloodChemistry` field
+macro_rules! chemistry_columns {
+    ($($field:ident),* $(,)?) => {
+        /// Blood chemistry of a cohort stored column by column, one value per patient
+        #[derive(Debug, Clone, Default, PartialEq)]
+        pub struct ChemistryColumns {
+            $(
+                #[doc = concat!("`", stringify!($field), "` of each patient, in cohort order")]
+                pub $field: Vec<f64>,
+            )*
+        }
+
+        impl ChemistryColumns {
+            /// Column names, as the `BloodChemistry` field names
+            pub const NAMES: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            /// Copy the chemistry of each patient into the columns, reusing their storage
+            pub fn gather(&mut self, patients: &[Patient]) {
+                $( self.$field.resize(patients.len(), 0.0); )*
+                for (i, patient) in patients.iter().enumerate() {
+                    let chemistry = &patient.blood.chemistry;
+                    $( self.$field[i] = chemistry.$field; )*
+                }
+            }
+
+            /// Values of a column
+            pub fn column(&self, name: &str) -> Option<&[f64]> {
+                match name {
+                    $( stringify!($field) => Some(&self.$field), )*
+                    _ => None,
+                }
+            }
+
+            /// Values of a column, for writing
+            pub fn column_mut(&mut self, name: &str) -> Option<&mut [f64]> {
+                match name {
+                    $( stringify!($field) => Some(&mut self.$field), )*
+                    _ => None,
+                }
+            }
+
+            /// Read a named value from one patient's chemistry
+            pub fn read(chemistry: &BloodChemistry, name: &str) -> Option<f64> {
+                match name {
+                    $( stringify!($field) => Some(chemistry.$field), )*
+                    _ => None,
+                }
+            }
+
+            /// Write a named value to one patient's chemistry
+            ///
+            /// # Returns
+            /// false if there is no such value
+            pub fn write(chemistry: &mut BloodChemistry, name: &str, value: f64) -> bool {
+                match name {
+                    $( stringify!($field) => chemistry.$field = value, )*
+                    _ => return false,
+                }
+                true
+            }
+        }
+    };
+}
+
+chemistry_columns!(
+    glucose_mg_dl,
+    bun_mg_dl,
+    creatinine_mg_dl,
+    sodium_meq_l,
+    potassium_meq_l,
+    chloride_meq_l,
+    bicarbonate_meq_l,
+    calcium_mg_dl,
+    magnesium_mg_dl,
+    phosphate_mg_dl,
+    total_protein_g_dl,
+    albumin_g_dl,
+    bilirubin_total_mg_dl,
+    bilirubin_direct_mg_dl,
+    alt_u_l,
+    ast_u_l,
+    alp_u_l,
+    ldh_u_l,
+    amylase_u_l,
+    lipase_u_l,
+    serum_iron_ug_dl,
+    ferritin_ng_ml,
+    tibc_ug_dl,
+    cholesterol_total_mg_dl,
+    hdl_cholesterol_mg_dl,
+    ldl_cholesterol_mg_dl,
+    triglycerides_mg_dl,
+    lactate_mmol_l,
+    troponin_ng_l,
+    ck_u_l,
+    myoglobin_ng_ml,
+    insulin_uu_ml,
+    glucagon_pg_ml,
+    beta_hydroxybutyrate_mmol_l,
+    toxin_level_au,
+);
+
+impl ChemistryColumns {
+    /// Number of patients held
+    pub fn len(&self) -> usize {
+        self.glucose_mg_dl.len()
+    }
+
+    /// No patients are held
+    pub fn is_empty(&self) -> bool {
+        self.glucose_mg_dl.is_empty()
+    }
+
+    /// Anion gap of each patient, Na - (Cl + HCO3) (mEq/L)
+    pub fn anion_gap(&self) -> Vec<f64> {
+        self.sodium_meq_l
+            .iter()
+            .zip(&self.chloride_meq_l)
+            .zip(&self.bicarbonate_meq_l)
+            .map(|((na, cl), hco3)| na - cl - hco3)
+            .collect()
+    }
+
+    /// Calculated serum osmolality of each patient, 2 Na + glucose/18 + BUN/2.8 (mOsm/kg)
+    pub fn serum_osmolality(&self) -> Vec<f64> {
+        self.sodium_meq_l
+            .iter()
+            .zip(&self.glucose_mg_dl)
+            .zip(&self.bun_mg_dl)
+            .map(|((na, glucose), bun)| 2.0 * na + glucose / 18.0 + bun / 2.8)
+            .collect()
+    }
+}
+
+/// Memory layout of a cohort's blood chemistry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CohortLayout {
+    /// Chemistry is read from each patient's own struct
+    #[default]
+    PerPatient,
+    /// Chemistry is also kept in columns refreshed after every step
+    Columnar,
+}
+
+/// Group of patients advanced together
+#[derive(Clone)]
+pub struct Cohort {
+    /// Patients, in cohort order
+    pub patients: Vec<Patient>,
+    /// Layout of the blood chemistry
+    layout: CohortLayout,
+    /// Chemistry columns, current when the layout is columnar
+    columns: ChemistryColumns,
+}
+
+impl Cohort {
+    /// Create a cohort of existing patients, with the per-patient layout
+    pub fn new(patients: Vec<Patient>) -> Self {
+        Self {
+            patients,
+            layout: CohortLayout::PerPatient,
+            columns: ChemistryColumns::default(),
+        }
+    }
+
+    /// Create a cohort by initializing each patient
+    ///
+    /// # Arguments
+    /// * `size` - Number of patients
+    /// * `initialize` - Creates the patient at each index
+    pub fn generate(size: usize, initialize: impl FnMut(usize) -> Patient) -> Self {
+        Self::new((0..size).map(initialize).collect())
+    }
+
+    /// Number of patients
+    pub fn len(&self) -> usize {
+        self.patients.len()
+    }
+
+    /// The cohort has no patients
+    pub fn is_empty(&self) -> bool {
+        self.patients.is_empty()
+    }
+
+    /// Layout of the blood chemistry
+    pub fn layout(&self) -> CohortLayout {
+        self.layout
+    }
+
+    /// Switch the chemistry layout; switching to columns fills them immediately
+    pub fn set_layout(&mut self, layout: CohortLayout) {
+        self.layout = layout;
+        match layout {
+            CohortLayout::Columnar => self.columns.gather(&self.patients),
+            CohortLayout::PerPatient => self.columns = ChemistryColumns::default(),
+        }
+    }
+
+    /// Chemistry columns, when the layout is columnar
+    ///
+    /// Patients edited directly through `patients` are reflected after the
+    /// next `update`.
+    pub fn chemistry(&self) -> Option<&ChemistryColumns> {
+        (self.layout == CohortLayout::Columnar).then_some(&self.columns)
+    }
+
+    /// Advance every patient by one step
+    ///
+    /// # Arguments
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, delta_time_s: f64) {
+        for patient in &mut self.patients {
+            update_patient(patient, delta_time_s);
+        }
+        if self.layout == CohortLayout::Columnar {
+            self.columns.gather(&self.patients);
+        }
+    }
+
+    /// Values of a chemistry field across the cohort, in cohort order
+    ///
+    /// # Arguments
+    /// * `name` - `BloodChemistry` field name
+    pub fn values(&self, name: &str) -> Option<Vec<f64>> {
+        match self.chemistry() {
+            Some(columns) => columns.column(name).map(<[f64]>::to_vec),
+            None => self.patients.iter().map(|p| ChemistryColumns::read(&p.blood.chemistry, name)).collect(),
+        }
+    }
+
+    /// Mean of a chemistry field across the cohort
+    pub fn mean(&self, name: &str) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        let sum: f64 = match self.chemistry() {
+            Some(columns) => columns.column(name)?.iter().sum(),
+            None => self
+                .patients
+                .iter()
+                .map(|p| ChemistryColumns::read(&p.blood.chemistry, name))
+                .sum::<Option<f64>>()?,
+        };
+        Some(sum / self.len() as f64)
+    }
+
+    /// Percentile of a chemistry field across the cohort (nearest rank)
+    ///
+    /// # Arguments
+    /// * `name` - `BloodChemistry` field name
+    /// * `percentile` - Percentile (0-100)
+    pub fn percentile(&self, name: &str, percentile: f64) -> Option<f64> {
+        let mut values = self.values(name)?;
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * values.len() as f64).ceil() as usize;
+        Some(values[rank.clamp(1, values.len()) - 1])
+    }
+
+    /// Apply a change to a chemistry field of every patient
+    ///
+    /// With the columnar layout the change runs over the column and is then
+    /// written back to the patients.
+    ///
+    /// # Arguments
+    /// * `name` - `BloodChemistry` field name
+    /// * `change` - New value from the current one
+    ///
+    /// # Returns
+    /// false if there is no such field
+    pub fn update_chemistry(&mut self, name: &str, change: impl Fn(f64) -> f64) -> bool {
+        if self.layout == CohortLayout::Columnar {
+            let Some(column) = self.columns.column_mut(name) else { return false };
+            for value in column.iter_mut() {
+                *value = change(*value);
+            }
+            for (patient, &value) in self.patients.iter_mut().zip(column.iter()) {
+                ChemistryColumns::write(&mut patient.blood.chemistry, name, value);
+            }
+            true
+        } else {
+            if ChemistryColumns::read(&BloodChemistry::default(), name).is_none() {
+                return false;
+            }
+            for patient in &mut self.patients {
+                let chemistry = &mut patient.blood.chemistry;
+                let value = ChemistryColumns::read(chemistry, name).unwrap_or_default();
+                ChemistryColumns::write(chemistry, name, change(value));
+            }
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::initialize_patient;
+
+    #[test]
+    fn test_columnar_layout_matches_per_patient() {
+        let setup = |i: usize| {
+            let mut patient = initialize_patient(i as i32, 12);
+            patient.blood.chemistry.glucose_mg_dl = 80.0 + i as f64;
+            patient
+        };
+        let mut per_patient = Cohort::generate(20, setup);
+        let mut columnar = Cohort::generate(20, setup);
+        columnar.set_layout(CohortLayout::Columnar);
+        assert!(per_patient.chemistry().is_none());
+        assert_eq!(columnar.chemistry().unwrap().len(), 20);
+
+        for cohort in [&mut per_patient, &mut columnar] {
+            for _ in 0..10 {
+                cohort.update(0.1);
+            }
+            assert!(cohort.update_chemistry("potassium_meq_l", |k| k + 1.0));
+            assert!(!cohort.update_chemistry("unknown", |k| k));
+        }
+        for name in ChemistryColumns::NAMES {
+            assert_eq!(per_patient.values(name), columnar.values(name), "{}", name);
+        }
+        assert_eq!(per_patient.mean("sodium_meq_l"), columnar.mean("sodium_meq_l"));
+        assert!(columnar.percentile("glucose_mg_dl", 90.0).unwrap() > columnar.percentile("glucose_mg_dl", 10.0).unwrap());
+        // The change reached the patients as well as the column
+        let potassium = columnar.patients[0].blood.chemistry.potassium_meq_l;
+        assert_eq!(columnar.chemistry().unwrap().potassium_meq_l[0], potassium);
+        assert!(potassium > 4.5);
+        let gap = columnar.chemistry().unwrap().anion_gap();
+        assert!(gap.iter().all(|g| (5.0..20.0).contains(g)));
+    }
+}
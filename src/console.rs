@@ -0,0 +1,435 @@
+//! Command console
+//!
+//! This module provides:
+//! - A parser turning short bedside commands ("give epi 1mg", "shock 200J",
+//!   "labs abg", "wait 5 min") into the scenario actions and queries they stand for
+//! - A console that runs commands against a live patient and answers in text,
+//!   so terminal trainers and chat-style interfaces can drive the simulator
+//!   without glue code of their own
+//!
+//! Commands are case-insensitive. Doses take their unit with or without a
+//! space ("1mg", "1 mg"); a missing dose falls back to the usual one (1 mg
+//! epinephrine, 300 mg amiodarone, 40 mg furosemide, 200 J, a 1 L bolus).
+//! `help` lists the commands.
+
+use crate::fluids::IntakeRoute;
+use crate::labs::LabPanel;
+use crate::organs::heart::{CoronaryTerritory, Heart};
+use crate::organs::lungs::Lungs;
+use crate::patient::{update_patient, Patient};
+use crate::scenario::Action;
+use crate::sepsis::BundleGuideline;
+use std::fmt;
+
+/// Default simulation time step while waiting (s)
+const DEFAULT_TIME_STEP_S: f64 = 0.1;
+
+/// Longest time one `wait` may advance (s)
+const MAX_WAIT_S: f64 = 86_400.0;
+
+/// Volume of a unit of packed red cells (mL)
+const RED_CELL_UNIT_VOLUME_ML: f64 = 300.0;
+
+/// Commands listed by `help`
+const HELP: &str = "Commands:
+  give epi [mg]              Epinephrine bolus (also: amio, furosemide, antibiotics)
+  give fluid [mL|L]          Crystalloid bolus (also: blood [units], platelets, ffp, cryo)
+  start norepi <mcg/kg/min>  Vasoactive infusion (also: nitroprusside); stop norepi
+  fio2 <fraction|%>          Inspired oxygen
+  shock [J]                  Defibrillate
+  start cpr / stop cpr       Chest compressions
+  labs <panel>               Order cbc, cmp, abg, vbg, troponin, coags or lactate
+  cultures                   Draw blood cultures
+  results [panel]            Released lab results
+  vitals                     Current vital signs
+  wait <time> [s|min|h]      Advance the simulation (up to 24 h)
+  occlude <LAD|LCx|RCA> [%], vf, asystole, bleed <mL>, infect <source>
+                             Insults, for the instructor";
+
+/// Doses in milligrams
+const MG: [(&str, f64); 3] = [("mg", 1.0), ("mcg", 1e-3), ("g", 1000.0)];
+
+/// Volumes in millilitres
+const ML: [(&str, f64); 2] = [("ml", 1.0), ("l", 1000.0)];
+
+/// Infusion rates in mcg/kg/min
+const MCG_KG_MIN: [(&str, f64); 1] = [("mcg/kg/min", 1.0)];
+
+/// Energies in joules
+const JOULES: [(&str, f64); 1] = [("j", 1.0)];
+
+/// Blood product units
+const UNITS: [(&str, f64); 2] = [("unit", 1.0), ("units", 1.0)];
+
+/// Durations in seconds
+const SECONDS: [(&str, f64); 8] = [
+    ("s", 1.0),
+    ("sec", 1.0),
+    ("m", 60.0),
+    ("min", 60.0),
+    ("mins", 60.0),
+    ("h", 3600.0),
+    ("hr", 3600.0),
+    ("hours", 3600.0),
+];
+
+/// Lab panels by command name
+const PANELS: [(&str, LabPanel); 21] = [
+    ("cbc", LabPanel::Cbc),
+    ("cmp", LabPanel::Cmp),
+    ("bmp", LabPanel::Cmp),
+    ("abg", LabPanel::Abg),
+    ("vbg", LabPanel::Vbg),
+    ("troponin", LabPanel::Troponin),
+    ("trop", LabPanel::Troponin),
+    ("coags", LabPanel::Coagulation),
+    ("coagulation", LabPanel::Coagulation),
+    ("lactate", LabPanel::Lactate),
+    ("hba1c", LabPanel::Hba1c),
+    ("lipase", LabPanel::Lipase),
+    ("ck", LabPanel::Ck),
+    ("cpk", LabPanel::Ck),
+    ("myoglobin", LabPanel::Myoglobin),
+    ("digoxin", LabPanel::Digoxin),
+    ("lithium", LabPanel::Lithium),
+    ("phenytoin", LabPanel::Phenytoin),
+    ("acetaminophen", LabPanel::Acetaminophen),
+    ("salicylate", LabPanel::Salicylate),
+    ("a1c", LabPanel::Hba1c),
+];
+
+/// Error in a console command
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsoleError {
+    /// What is wrong
+    pub message: String,
+}
+
+impl ConsoleError {
+    fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+}
+
+impl fmt::Display for ConsoleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ConsoleError {}
+
+/// Parsed console command
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Apply an intervention or insult
+    Act(Action),
+    /// Advance the simulation (s)
+    Wait(f64),
+    /// Report the vital signs
+    Vitals,
+    /// Report released lab results, of one panel or all
+    Results(Option<LabPanel>),
+    /// List the commands
+    Help,
+}
+
+impl Command {
+    /// Parse a command line
+    ///
+    /// # Arguments
+    /// * `line` - Command such as "give epi 1mg"
+    ///
+    /// # Returns
+    /// The command, or what is wrong with the line
+    pub fn parse(line: &str) -> Result<Self, ConsoleError> {
+        let normalized = split_units(&line.trim().to_lowercase());
+        let mut words: Vec<&str> = normalized.split_whitespace().collect();
+        if words.is_empty() {
+            return Err(ConsoleError::new("empty command"));
+        }
+        // "give", "push" and "start" only introduce the drug; "stop" sets an infusion to zero
+        let stop = words[0] == "stop";
+        if matches!(words[0], "give" | "push" | "administer" | "start" | "hang" | "stop") && words.len() > 1 {
+            words.remove(0);
+        }
+        let (verb, args) = (words[0], &words[1..]);
+
+        let action = match verb {
+            "epi" | "epinephrine" | "adrenaline" => Action::GiveEpinephrine {
+                mg: quantity(args, &MG, Some(1.0))?,
+            },
+            "amio" | "amiodarone" => Action::GiveAmiodarone {
+                mg: quantity(args, &MG, Some(300.0))?,
+            },
+            "furosemide" | "lasix" => Action::GiveFurosemide {
+                mg: quantity(args, &MG, Some(40.0))?,
+            },
+            "antibiotics" | "abx" => Action::GiveAntibiotics { covers_organism: true },
+            "fluid" | "fluids" | "bolus" | "saline" | "ns" | "lr" => Action::GiveFluid {
+                route: IntakeRoute::Intravenous,
+                volume_ml: quantity(args, &ML, Some(1000.0))?,
+            },
+            "blood" | "prbc" => Action::GiveFluid {
+                route: IntakeRoute::BloodProduct,
+                volume_ml: quantity(args, &UNITS, Some(1.0))? * RED_CELL_UNIT_VOLUME_ML,
+            },
+            "platelets" => Action::TransfusePlatelets {
+                units: quantity(args, &UNITS, Some(1.0))?,
+            },
+            "ffp" | "plasma" => Action::TransfusePlasma {
+                units: quantity(args, &UNITS, Some(1.0))?,
+            },
+            "cryo" | "cryoprecipitate" => Action::TransfuseCryoprecipitate {
+                units: quantity(args, &UNITS, Some(1.0))?,
+            },
+            "norepi" | "norepinephrine" | "levophed" => Action::Norepinephrine {
+                mcg_kg_min: if stop { 0.0 } else { quantity(args, &MCG_KG_MIN, None)? },
+            },
+            "nitroprusside" | "nipride" => Action::Nitroprusside {
+                mcg_kg_min: if stop { 0.0 } else { quantity(args, &MCG_KG_MIN, None)? },
+            },
+            "cpr" | "compressions" if stop => Action::StopCpr,
+            "cpr" | "compressions" => Action::StartCpr,
+            "fio2" | "o2" | "oxygen" => {
+                let percent = args.contains(&"%");
+                let fio2 = quantity(args, &[("%", 1.0)], None)?;
+                Action::SetFio2 {
+                    fio2: if percent || fio2 > 1.0 { fio2 / 100.0 } else { fio2 },
+                }
+            }
+            "shock" | "defib" | "defibrillate" => Action::Defibrillate {
+                energy_j: quantity(args, &JOULES, Some(200.0))?,
+            },
+            "labs" | "lab" | "order" => Action::OrderLabs {
+                panel: panel(args.first().copied())?.ok_or_else(|| ConsoleError::new("labs needs a panel"))?,
+            },
+            "cultures" => Action::DrawBloodCultures,
+            "occlude" => {
+                let territory = match args.first().copied() {
+                    Some("lad") => CoronaryTerritory::LeftAnteriorDescending,
+                    Some("lcx") => CoronaryTerritory::LeftCircumflex,
+                    Some("rca") => CoronaryTerritory::RightCoronary,
+                    _ => return Err(ConsoleError::new("occlude needs LAD, LCx or RCA")),
+                };
+                let severity = quantity(&args[1..], &[("%", 1.0)], Some(100.0))? / 100.0;
+                Action::OccludeCoronary {
+                    territory,
+                    severity: severity.clamp(0.0, 1.0),
+                }
+            }
+            "vf" => Action::InduceVentricularFibrillation,
+            "asystole" => Action::InduceAsystole,
+            "bleed" | "hemorrhage" => Action::Hemorrhage {
+                volume_ml: quantity(args, &ML, None)?,
+            },
+            "infect" | "infection" => Action::StartInfection {
+                source: args.first().copied().unwrap_or("unknown").to_string(),
+                virulence: 1.0,
+                guideline: BundleGuideline::HourOne,
+            },
+            "wait" | "advance" => {
+                let duration_s = quantity(args, &SECONDS, None)?;
+                if duration_s > MAX_WAIT_S {
+                    return Err(ConsoleError::new("wait at most 24 h at a time"));
+                }
+                return Ok(Command::Wait(duration_s));
+            }
+            "vitals" | "status" => return Ok(Command::Vitals),
+            "results" => return Ok(Command::Results(panel(args.first().copied())?)),
+            "help" | "?" => return Ok(Command::Help),
+            _ => return Err(ConsoleError::new(format!("unknown command \"{}\" (type help)", verb))),
+        };
+        Ok(Command::Act(action))
+    }
+}
+
+/// Separate numbers from the units written against them ("1mg" becomes "1 mg")
+fn split_units(line: &str) -> String {
+    let mut split = String::with_capacity(line.len() + 4);
+    let mut previous = ' ';
+    for c in line.chars() {
+        if (previous.is_ascii_digit() || previous == '.') && (c.is_alphabetic() || c == '%') {
+            split.push(' ');
+        }
+        split.push(c);
+        previous = c;
+    }
+    split
+}
+
+/// Read a number and its optional unit from the arguments
+///
+/// # Arguments
+/// * `args` - Words after the command
+/// * `units` - Accepted units with their factor to the base unit
+/// * `default` - Value when no number is given, or None if one is required
+///
+/// # Returns
+/// The value in the base unit
+fn quantity(args: &[&str], units: &[(&str, f64)], default: Option<f64>) -> Result<f64, ConsoleError> {
+    let Some(index) = args.iter().position(|word| word.parse::<f64>().is_ok()) else {
+        return default.ok_or_else(|| ConsoleError::new("a value is needed"));
+    };
+    let value: f64 = args[index].parse().unwrap_or_default();
+    if !value.is_finite() || value < 0.0 {
+        return Err(ConsoleError::new(format!("invalid value {}", args[index])));
+    }
+    match args.get(index + 1) {
+        None => Ok(value),
+        Some(unit) => match units.iter().find(|(name, _)| name == unit) {
+            Some((_, factor)) => Ok(value * factor),
+            None => Err(ConsoleError::new(format!("unknown unit \"{}\"", unit))),
+        },
+    }
+}
+
+/// Read an optional lab panel name
+fn panel(name: Option<&str>) -> Result<Option<LabPanel>, ConsoleError> {
+    match name {
+        None => Ok(None),
+        Some(name) => match PANELS.iter().find(|(panel_name, _)| *panel_name == name) {
+            Some((_, panel)) => Ok(Some(*panel)),
+            None => Err(ConsoleError::new(format!("unknown lab panel \"{}\"", name))),
+        },
+    }
+}
+
+/// Console driving a live patient with text commands
+#[derive(Debug, Clone)]
+pub struct Console {
+    /// Simulation time step while waiting (s)
+    pub time_step_s: f64,
+}
+
+impl Console {
+    /// Create a console stepping at 0.1 s
+    pub fn new() -> Self {
+        Self {
+            time_step_s: DEFAULT_TIME_STEP_S,
+        }
+    }
+
+    /// Parse and run a command line
+    ///
+    /// # Arguments
+    /// * `patient` - Patient the command acts on
+    /// * `line` - Command such as "give epi 1mg"
+    ///
+    /// # Returns
+    /// The console's answer, or what is wrong with the line
+    pub fn execute(&mut self, patient: &mut Patient, line: &str) -> Result<String, ConsoleError> {
+        let command = Command::parse(line)?;
+        Ok(self.run(patient, &command))
+    }
+
+    /// Run a parsed command
+    ///
+    /// # Arguments
+    /// * `patient` - Patient the command acts on
+    /// * `command` - Command to run
+    ///
+    /// # Returns
+    /// The console's answer
+    pub fn run(&mut self, patient: &mut Patient, command: &Command) -> String {
+        match command {
+            Command::Act(action) => {
+                action.apply(patient);
+                match action {
+                    Action::OrderLabs { panel } => {
+                        format!("{} (results in {:.0} min)", action, patient.labs.turnaround_s(*panel) / 60.0)
+                    }
+                    _ => action.to_string(),
+                }
+            }
+            Command::Wait(duration_s) => {
+                let released = patient.labs.reports().len();
+                let step = self.time_step_s.max(1e-3);
+                for _ in 0..(duration_s / step).round() as usize {
+                    update_patient(patient, step);
+                }
+                let mut answer = self.vitals(patient);
+                for report in &patient.labs.reports()[released..] {
+                    answer.push_str(&format!("\n{}", report.get_summary()));
+                }
+                answer
+            }
+            Command::Vitals => self.vitals(patient),
+            Command::Results(panel) => {
+                let reports: Vec<String> = patient
+                    .labs
+                    .reports()
+                    .iter()
+                    .filter(|report| panel.is_none_or(|panel| report.panel == panel))
+                    .map(|report| report.get_summary())
+                    .collect();
+                if reports.is_empty() {
+                    "No results released".to_string()
+                } else {
+                    reports.join("\n")
+                }
+            }
+            Command::Help => HELP.to_string(),
+        }
+    }
+
+    /// Format the current vital signs
+    fn vitals(&self, patient: &Patient) -> String {
+        let heart = patient.get_organ::<Heart>();
+        let lungs = patient.get_organ::<Lungs>();
+        let seconds = patient.sim_time().round();
+        let minutes = (seconds / 60.0).floor();
+        format!(
+            "T+{:.0}:{:02.0}  {}  HR {:.0}  BP {:.0}/{:.0} ({:.0})  SpO2 {:.0}%  RR {:.0}  EtCO2 {:.0}  T {:.1}",
+            minutes,
+            seconds - minutes * 60.0,
+            heart.map_or("No heart".to_string(), |h| format!("{:?}", h.rhythm)),
+            heart.map_or(0.0, |h| h.heart_rate_bpm),
+            patient.blood.blood_pressure_systolic,
+            patient.blood.blood_pressure_diastolic,
+            patient.blood.get_mean_arterial_pressure(),
+            patient.blood.gases.sao2_percent,
+            lungs.map_or(0.0, |l| l.respiration_rate_bpm),
+            lungs.map_or(0.0, |l| l.end_tidal_co2_mmhg),
+            patient.oxygen_transport.body_temperature_c
+        )
+    }
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::heart::Rhythm;
+    use crate::patient::initialize_patient;
+
+    #[test]
+    fn test_commands_drive_a_patient() {
+        assert_eq!(Command::parse("give epi 1mg"), Ok(Command::Act(Action::GiveEpinephrine { mg: 1.0 })));
+        assert_eq!(Command::parse("Push Amio"), Ok(Command::Act(Action::GiveAmiodarone { mg: 300.0 })));
+        assert_eq!(Command::parse("shock 200J"), Ok(Command::Act(Action::Defibrillate { energy_j: 200.0 })));
+        assert_eq!(Command::parse("fio2 40%"), Ok(Command::Act(Action::SetFio2 { fio2: 0.4 })));
+        assert_eq!(Command::parse("stop norepi"), Ok(Command::Act(Action::Norepinephrine { mcg_kg_min: 0.0 })));
+        assert_eq!(Command::parse("labs abg"), Ok(Command::Act(Action::OrderLabs { panel: LabPanel::Abg })));
+        assert_eq!(Command::parse("wait 2 min"), Ok(Command::Wait(120.0)));
+        assert!(Command::parse("wait 1e300 h").is_err());
+        assert!(Command::parse("give epi 1 banana").is_err());
+        assert!(Command::parse("dance").unwrap_err().message.contains("unknown command"));
+
+        let mut patient = initialize_patient(1, 3);
+        let mut console = Console::new();
+        console.execute(&mut patient, "vf").unwrap();
+        assert!(console.execute(&mut patient, "labs lactate").unwrap().contains("results in"));
+        console.execute(&mut patient, "shock 200J").unwrap();
+        let answer = console.execute(&mut patient, "wait 10 min").unwrap();
+        assert!(answer.starts_with("T+10:00"), "{}", answer);
+        assert!(answer.contains("Lactate #"), "{}", answer);
+        assert_ne!(patient.get_organ::<Heart>().unwrap().rhythm, Rhythm::VentricularFibrillation);
+        assert!(console.execute(&mut patient, "results lactate").unwrap().contains("Lactate"));
+    }
+}
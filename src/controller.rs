@@ -0,0 +1,369 @@
+//! Closed-loop therapy controllers
+//!
+//! This module provides:
+//! - A `Controller` trait for closed-loop algorithms that read the patient at
+//!   a fixed interval and answer with interventions, so automated critical-care
+//!   controllers can be studied against the simulator
+//! - A loop advancing the patient, running each controller when its decision
+//!   is due and logging every intervention it issues
+//! - Reference controllers: norepinephrine titrated to a mean arterial pressure
+//!   and an insulin infusion titrated to a blood glucose
+//!
+//! Controllers see the patient read-only and act only through scenario
+//! actions, the interventions available at the bedside. The reference
+//! controllers are incremental PI controllers: each decision moves the rate by
+//! the change in error (proportional) and by the error itself (integral), so
+//! the rate settles where the error is zero and cannot wind up beyond the
+//! pump's limits. Rates are rounded to the pump's resolution; the unrounded
+//! rate is kept, so corrections smaller than the resolution still add up.
+
+use crate::patient::{update_patient, Patient};
+use crate::scenario::Action;
+use std::fmt;
+
+/// Default time between norepinephrine titrations (s)
+const DEFAULT_MAP_INTERVAL_S: f64 = 30.0;
+
+/// Default time between glucose checks (s)
+const DEFAULT_GLUCOSE_INTERVAL_S: f64 = 900.0;
+
+/// Resolution of a vasopressor pump (mcg/kg/min)
+const NOREPINEPHRINE_RESOLUTION_MCG_KG_MIN: f64 = 0.01;
+
+/// Resolution of an insulin pump (units/h)
+const INSULIN_RESOLUTION_UNITS_PER_HR: f64 = 0.1;
+
+/// Closed-loop algorithm deciding interventions from the patient's state
+///
+/// Controllers are `Send + Sync` like organs and processes, so a loop can run
+/// on a worker thread.
+pub trait Controller: fmt::Debug + Send + Sync {
+    /// Name shown in the intervention log
+    fn get_name(&self) -> &str;
+
+    /// Time between decisions (s)
+    fn interval_s(&self) -> f64;
+
+    /// Decide the interventions for this tick
+    ///
+    /// # Arguments
+    /// * `patient` - Patient as monitored now
+    ///
+    /// # Returns
+    /// Interventions to apply, none to leave the therapy unchanged
+    fn decide(&mut self, patient: &Patient) -> Vec<Action>;
+}
+
+/// Intervention issued by a controller
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlEvent {
+    /// Patient's simulation time of the decision (s)
+    pub time_s: f64,
+    /// Controller that issued it
+    pub controller: String,
+    /// Intervention applied
+    pub action: Action,
+}
+
+impl fmt::Display for ControlEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:>6.0} s] {}: {}", self.time_s, self.controller, self.action)
+    }
+}
+
+/// Controllers driving a patient in closed loop
+#[derive(Debug, Default)]
+pub struct ClosedLoop {
+    /// Controllers, each with the simulation time of its next decision (s)
+    controllers: Vec<(Box<dyn Controller>, f64)>,
+    /// Interventions issued, oldest first
+    log: Vec<ControlEvent>,
+}
+
+impl ClosedLoop {
+    /// Create a loop without controllers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a controller, deciding at the next step
+    pub fn add(&mut self, controller: Box<dyn Controller>) {
+        self.controllers.push((controller, f64::NEG_INFINITY));
+    }
+
+    /// Run the controllers that are due, then advance the patient one step
+    ///
+    /// # Arguments
+    /// * `patient` - Patient under control
+    /// * `delta_time_s` - Time step in seconds
+    pub fn step(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let time_s = patient.sim_time();
+        for (controller, next_decision_s) in self.controllers.iter_mut() {
+            if time_s + 1e-9 < *next_decision_s {
+                continue;
+            }
+            *next_decision_s = time_s + controller.interval_s().max(delta_time_s);
+            for action in controller.decide(patient) {
+                action.apply(patient);
+                self.log.push(ControlEvent {
+                    time_s,
+                    controller: controller.get_name().to_string(),
+                    action,
+                });
+            }
+        }
+        update_patient(patient, delta_time_s);
+    }
+
+    /// Run for a duration
+    ///
+    /// # Arguments
+    /// * `patient` - Patient under control
+    /// * `duration_s` - Simulated time to run (s)
+    /// * `time_step_s` - Time step in seconds
+    pub fn run(&mut self, patient: &mut Patient, duration_s: f64, time_step_s: f64) {
+        let step = time_step_s.max(1e-3);
+        for _ in 0..(duration_s / step).round() as usize {
+            self.step(patient, step);
+        }
+    }
+
+    /// Interventions issued, oldest first
+    pub fn log(&self) -> &[ControlEvent] {
+        &self.log
+    }
+
+    /// Controllers in the order they were added
+    pub fn controllers(&self) -> impl Iterator<Item = &dyn Controller> {
+        self.controllers.iter().map(|(controller, _)| controller.as_ref())
+    }
+}
+
+/// Incremental PI update of an infusion rate
+///
+/// # Arguments
+/// * `rate` - Current rate
+/// * `error` - Target minus measured value now
+/// * `previous_error` - Error at the previous decision, if any
+/// * `proportional_gain` - Rate change per unit change in error
+/// * `integral_gain` - Rate change per unit error per minute
+/// * `interval_s` - Time since the previous decision (s)
+/// * `max_rate` - Pump limit
+///
+/// # Returns
+/// The new rate before rounding to the pump's resolution, so changes smaller
+/// than the resolution accumulate across decisions
+fn pi_update(
+    rate: f64,
+    error: f64,
+    previous_error: Option<f64>,
+    (proportional_gain, integral_gain): (f64, f64),
+    interval_s: f64,
+    max_rate: f64,
+) -> f64 {
+    let change = proportional_gain * (error - previous_error.unwrap_or(0.0)) + integral_gain * error * interval_s / 60.0;
+    (rate + change).clamp(0.0, max_rate.max(0.0))
+}
+
+/// Round a rate to the pump's resolution
+fn round_to_resolution(rate: f64, resolution: f64) -> f64 {
+    (rate / resolution).round() * resolution
+}
+
+/// Norepinephrine titrated to a mean arterial pressure
+#[derive(Debug, Clone)]
+pub struct MapNorepinephrineController {
+    /// Target mean arterial pressure (mmHg)
+    pub target_map_mmhg: f64,
+    /// Rate change per mmHg change in error (mcg/kg/min per mmHg)
+    pub proportional_gain: f64,
+    /// Rate change per mmHg of error per minute (mcg/kg/min per mmHg·min)
+    pub integral_gain: f64,
+    /// Highest rate given (mcg/kg/min)
+    pub max_rate_mcg_kg_min: f64,
+    /// Time between titrations (s)
+    pub interval_s: f64,
+    /// Rate currently running (mcg/kg/min)
+    pub rate_mcg_kg_min: f64,
+    /// Rate the controller asks for before rounding (mcg/kg/min)
+    requested_rate: f64,
+    /// Error at the previous titration (mmHg)
+    previous_error: Option<f64>,
+}
+
+impl MapNorepinephrineController {
+    /// Create a controller titrating every 30 s up to 1 mcg/kg/min
+    ///
+    /// # Arguments
+    /// * `target_map_mmhg` - Target mean arterial pressure (mmHg, 65 in septic shock)
+    pub fn new(target_map_mmhg: f64) -> Self {
+        Self {
+            target_map_mmhg,
+            proportional_gain: 0.001,
+            integral_gain: 0.0005,
+            max_rate_mcg_kg_min: 1.0,
+            interval_s: DEFAULT_MAP_INTERVAL_S,
+            rate_mcg_kg_min: 0.0,
+            requested_rate: 0.0,
+            previous_error: None,
+        }
+    }
+}
+
+impl Controller for MapNorepinephrineController {
+    fn get_name(&self) -> &str {
+        "MAP-targeted norepinephrine"
+    }
+
+    fn interval_s(&self) -> f64 {
+        self.interval_s
+    }
+
+    fn decide(&mut self, patient: &Patient) -> Vec<Action> {
+        let map = patient.blood.get_mean_arterial_pressure();
+        if !map.is_finite() {
+            return Vec::new();
+        }
+        let error = self.target_map_mmhg - map;
+        self.requested_rate = pi_update(
+            self.requested_rate,
+            error,
+            self.previous_error,
+            (self.proportional_gain, self.integral_gain),
+            self.interval_s,
+            self.max_rate_mcg_kg_min,
+        );
+        self.previous_error = Some(error);
+        let rate = round_to_resolution(self.requested_rate, NOREPINEPHRINE_RESOLUTION_MCG_KG_MIN);
+        if (rate - self.rate_mcg_kg_min).abs() < NOREPINEPHRINE_RESOLUTION_MCG_KG_MIN / 2.0 {
+            return Vec::new();
+        }
+        self.rate_mcg_kg_min = rate;
+        vec![Action::Norepinephrine { mcg_kg_min: rate }]
+    }
+}
+
+/// Insulin infusion titrated to a blood glucose
+///
+/// The infusion is held while glucose is below a safety threshold, as in
+/// bedside insulin protocols, and resumes from zero once it recovers.
+#[derive(Debug, Clone)]
+pub struct GlucoseInsulinController {
+    /// Target blood glucose (mg/dL)
+    pub target_glucose_mg_dl: f64,
+    /// Glucose below which the infusion is held (mg/dL)
+    pub hold_below_mg_dl: f64,
+    /// Rate change per mg/dL change in error (units/h per mg/dL)
+    pub proportional_gain: f64,
+    /// Rate change per mg/dL of error per minute (units/h per mg/dL·min)
+    pub integral_gain: f64,
+    /// Highest rate given (units/h)
+    pub max_rate_units_per_hr: f64,
+    /// Time between glucose checks (s)
+    pub interval_s: f64,
+    /// Rate currently running (units/h)
+    pub rate_units_per_hr: f64,
+    /// Rate the controller asks for before rounding (units/h)
+    requested_rate: f64,
+    /// Error at the previous check (mg/dL)
+    previous_error: Option<f64>,
+}
+
+impl GlucoseInsulinController {
+    /// Create a controller checking glucose every 15 min, holding below 100 mg/dL
+    ///
+    /// # Arguments
+    /// * `target_glucose_mg_dl` - Target blood glucose (mg/dL, 140-180 in critical illness)
+    pub fn new(target_glucose_mg_dl: f64) -> Self {
+        Self {
+            target_glucose_mg_dl,
+            hold_below_mg_dl: 100.0,
+            proportional_gain: 0.01,
+            integral_gain: 0.0005,
+            max_rate_units_per_hr: 15.0,
+            interval_s: DEFAULT_GLUCOSE_INTERVAL_S,
+            rate_units_per_hr: 0.0,
+            requested_rate: 0.0,
+            previous_error: None,
+        }
+    }
+}
+
+impl Controller for GlucoseInsulinController {
+    fn get_name(&self) -> &str {
+        "Glucose-targeted insulin"
+    }
+
+    fn interval_s(&self) -> f64 {
+        self.interval_s
+    }
+
+    fn decide(&mut self, patient: &Patient) -> Vec<Action> {
+        let glucose = patient.blood.chemistry.glucose_mg_dl;
+        // Insulin lowers glucose, so the error is measured above the target
+        let error = glucose - self.target_glucose_mg_dl;
+        if glucose < self.hold_below_mg_dl {
+            self.previous_error = None;
+            self.requested_rate = 0.0;
+        } else {
+            self.requested_rate = pi_update(
+                self.requested_rate,
+                error,
+                self.previous_error,
+                (self.proportional_gain, self.integral_gain),
+                self.interval_s,
+                self.max_rate_units_per_hr,
+            );
+            self.previous_error = Some(error);
+        }
+        let rate = round_to_resolution(self.requested_rate, INSULIN_RESOLUTION_UNITS_PER_HR);
+        if (rate - self.rate_units_per_hr).abs() < INSULIN_RESOLUTION_UNITS_PER_HR / 2.0 {
+            return Vec::new();
+        }
+        self.rate_units_per_hr = rate;
+        vec![Action::InsulinInfusion { units_per_hr: rate }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::pancreas::DiabetesType;
+    use crate::patient::initialize_patient;
+    use crate::presets::{self, Preset};
+
+    #[test]
+    fn test_norepinephrine_holds_target_pressure() {
+        let mut patient = initialize_patient(1, 3);
+        patient.hemorrhage(0.4 * patient.fluids.reference_blood_volume_ml);
+        let mut control = ClosedLoop::new();
+        control.add(Box::new(MapNorepinephrineController::new(90.0)));
+        control.run(&mut patient, 1800.0, 1.0);
+
+        let map = patient.blood.get_mean_arterial_pressure();
+        assert!((map - 90.0).abs() < 3.0, "MAP {}", map);
+        assert_eq!(control.controllers().next().unwrap().get_name(), "MAP-targeted norepinephrine");
+        let Action::Norepinephrine { mcg_kg_min } = control.log().last().unwrap().action else {
+            panic!("expected a norepinephrine titration");
+        };
+        assert!(mcg_kg_min > 0.0 && mcg_kg_min < 0.3);
+        assert_eq!(patient.doses.last().unwrap().medication, "Norepinephrine");
+    }
+
+    #[test]
+    fn test_insulin_brings_glucose_to_target() {
+        let mut patient = initialize_patient(1, 3);
+        presets::apply(&mut patient, Preset::Diabetes { diabetes_type: DiabetesType::Type1 });
+        let mut control = ClosedLoop::new();
+        control.add(Box::new(GlucoseInsulinController::new(140.0)));
+        let mut lowest = f64::INFINITY;
+        for _ in 0..(8 * 60) {
+            control.run(&mut patient, 60.0, 1.0);
+            lowest = lowest.min(patient.blood.chemistry.glucose_mg_dl);
+        }
+
+        let glucose = patient.blood.chemistry.glucose_mg_dl;
+        assert!((glucose - 140.0).abs() < 20.0, "glucose {}", glucose);
+        assert!(lowest > 90.0, "lowest glucose {}", lowest);
+    }
+}
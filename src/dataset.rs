@@ -0,0 +1,464 @@
+//! Synthetic datasets
+//!
+//! This module provides:
+//! - Longitudinal EHR-style datasets from generated cohorts: each patient is
+//!   drawn by the patient generator, optionally driven through a scripted
+//!   scenario, and charted as the simulation runs
+//! - Tables of vital signs, laboratory results, medications, coded diagnoses
+//!   and outcomes, exportable as CSV (and, with the `fhir` feature, as FHIR R4
+//!   resources in NDJSON)
+//! - Parallel generation across the available cores, reproducible from a seed
+//!
+//! Every patient is synthetic, so the data carry no protected health
+//! information. Records are keyed by a sequential patient number, times are
+//! seconds from the start of the encounter, and ages of 90 and above are
+//! reported as 90, as under the HIPAA Safe Harbor rule.
+
+use crate::batch::parallel_map;
+use crate::diagnosis::DiagnosisEngine;
+use crate::generator::{Acuity, AcuteIllness, GeneratorConfig, PatientGenerator};
+use crate::growth::Sex;
+use crate::labs::LabPanel;
+use crate::organs::heart::Heart;
+use crate::organs::lungs::Lungs;
+use crate::patient::{update_patient, Patient};
+use crate::scenario::{Scenario, ScenarioRunner};
+use crate::validation::Measure;
+
+#[cfg(feature = "fhir")]
+use crate::fhir::FhirExport;
+
+/// Age at and above which ages are reported as 90 (years)
+const MAX_REPORTED_AGE_YEARS: u32 = 90;
+
+/// Panels drawn at each laboratory interval
+const DRAWN_PANELS: [LabPanel; 3] = [LabPanel::Cbc, LabPanel::Cmp, LabPanel::Lactate];
+
+/// What to generate
+#[derive(Debug, Clone)]
+pub struct DatasetConfig {
+    /// Number of patients
+    pub patients: usize,
+    /// Seed of the cohort; each patient draws from its own stream derived from it
+    pub seed: u64,
+    /// Population the patients are drawn from
+    pub generator: GeneratorConfig,
+    /// Scenario every patient is driven through; it sets the duration and time step
+    pub scenario: Option<Scenario>,
+    /// Simulated time of each encounter without a scenario (s)
+    pub duration_s: f64,
+    /// Simulation time step without a scenario (s)
+    pub time_step_s: f64,
+    /// Time between charted vital signs (s)
+    pub vitals_interval_s: f64,
+    /// Time between laboratory draws (CBC, CMP, lactate) (s)
+    pub labs_interval_s: f64,
+    /// Most worker threads used (0 = one per available core)
+    pub max_workers: usize,
+}
+
+impl Default for DatasetConfig {
+    fn default() -> Self {
+        Self {
+            patients: 100,
+            seed: 0,
+            generator: GeneratorConfig::default(),
+            scenario: None,
+            duration_s: 3600.0,
+            time_step_s: 0.5,
+            vitals_interval_s: 300.0,
+            labs_interval_s: 1800.0,
+            max_workers: 0,
+        }
+    }
+}
+
+/// Charted vital signs
+#[derive(Debug, Clone, PartialEq)]
+pub struct VitalsRecord {
+    /// Patient number
+    pub patient: usize,
+    /// Time from the start of the encounter (s)
+    pub time_s: f64,
+    /// Heart rate (bpm)
+    pub heart_rate_bpm: f64,
+    /// Systolic pressure (mmHg)
+    pub systolic_mmhg: f64,
+    /// Diastolic pressure (mmHg)
+    pub diastolic_mmhg: f64,
+    /// Respiratory rate (breaths/min)
+    pub respiratory_rate_bpm: f64,
+    /// Arterial oxygen saturation (%)
+    pub spo2_percent: f64,
+    /// Body temperature (°C)
+    pub temperature_c: f64,
+}
+
+/// Released laboratory result
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabRecord {
+    /// Patient number
+    pub patient: usize,
+    /// Time the specimen was drawn (s)
+    pub drawn_s: f64,
+    /// Time the result was released (s)
+    pub resulted_s: f64,
+    /// LOINC code of the analyte
+    pub loinc: &'static str,
+    /// Report name of the analyte
+    pub analyte: &'static str,
+    /// Reported value
+    pub value: f64,
+    /// Units of the value
+    pub units: &'static str,
+    /// Interpretation flag symbol (empty when normal)
+    pub flag: &'static str,
+}
+
+/// Medication or other treatment given by the scenario
+#[derive(Debug, Clone, PartialEq)]
+pub struct MedicationRecord {
+    /// Patient number
+    pub patient: usize,
+    /// Time the treatment was given (s)
+    pub time_s: f64,
+    /// Treatment and dose
+    pub description: String,
+}
+
+/// Coded diagnosis with its onset
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagnosisRecord {
+    /// Patient number
+    pub patient: usize,
+    /// ICD-10 code
+    pub icd10: &'static str,
+    /// SNOMED CT concept ID
+    pub snomed: &'static str,
+    /// Description
+    pub description: &'static str,
+    /// Onset (s)
+    pub onset_s: f64,
+    /// Resolution, if resolved (s)
+    pub resolved_s: Option<f64>,
+}
+
+/// Demographics and outcome of one encounter
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutcomeRecord {
+    /// Patient number
+    pub patient: usize,
+    /// Biological sex
+    pub sex: Sex,
+    /// Age in whole years, 90 and above reported as 90
+    pub age_years: u32,
+    /// Acuity at presentation
+    pub acuity: Acuity,
+    /// Acute illness at presentation
+    pub illness: Option<AcuteIllness>,
+    /// Comorbidity bundles
+    pub comorbidities: Vec<String>,
+    /// Length of the encounter (s)
+    pub duration_s: f64,
+    /// Lowest mean arterial pressure (mmHg)
+    pub lowest_map_mmhg: f64,
+    /// Highest lactate (mmol/L)
+    pub peak_lactate_mmol_l: f64,
+    /// Alive with a pulse at the end of the encounter
+    pub survived: bool,
+}
+
+/// Synthetic dataset of a cohort
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dataset {
+    /// Charted vital signs, by patient then time
+    pub vitals: Vec<VitalsRecord>,
+    /// Laboratory results, by patient then release
+    pub labs: Vec<LabRecord>,
+    /// Treatments, by patient then time
+    pub medications: Vec<MedicationRecord>,
+    /// Coded diagnoses, by patient then onset
+    pub diagnoses: Vec<DiagnosisRecord>,
+    /// One outcome per patient
+    pub outcomes: Vec<OutcomeRecord>,
+    /// FHIR R4 resources (Observations, DiagnosticReports, Conditions), one JSON document each
+    #[cfg(feature = "fhir")]
+    pub fhir_resources: Vec<String>,
+}
+
+impl Dataset {
+    /// Generate a dataset
+    ///
+    /// # Arguments
+    /// * `config` - What to generate
+    pub fn generate(config: &DatasetConfig) -> Self {
+        let mut encounters = parallel_map(config.patients, config.max_workers, |index| {
+            (index, simulate_encounter(config, index))
+        });
+        encounters.sort_by_key(|(index, _)| *index);
+        let mut dataset = Dataset::default();
+        for (_, encounter) in encounters {
+            dataset.vitals.extend(encounter.vitals);
+            dataset.labs.extend(encounter.labs);
+            dataset.medications.extend(encounter.medications);
+            dataset.diagnoses.extend(encounter.diagnoses);
+            dataset.outcomes.extend(encounter.outcomes);
+            #[cfg(feature = "fhir")]
+            dataset.fhir_resources.extend(encounter.fhir_resources);
+        }
+        dataset
+    }
+
+    /// Vital signs as CSV
+    pub fn vitals_csv(&self) -> String {
+        let mut csv = String::from("patient,time_s,heart_rate_bpm,systolic_mmhg,diastolic_mmhg,respiratory_rate_bpm,spo2_percent,temperature_c\n");
+        for v in &self.vitals {
+            csv.push_str(&format!(
+                "{},{:.0},{:.0},{:.0},{:.0},{:.0},{:.0},{:.1}\n",
+                v.patient,
+                v.time_s,
+                v.heart_rate_bpm,
+                v.systolic_mmhg,
+                v.diastolic_mmhg,
+                v.respiratory_rate_bpm,
+                v.spo2_percent,
+                v.temperature_c
+            ));
+        }
+        csv
+    }
+
+    /// Laboratory results as CSV
+    pub fn labs_csv(&self) -> String {
+        let mut csv = String::from("patient,drawn_s,resulted_s,loinc,analyte,value,units,flag\n");
+        for l in &self.labs {
+            csv.push_str(&format!(
+                "{},{:.0},{:.0},{},{},{},{},{}\n",
+                l.patient, l.drawn_s, l.resulted_s, l.loinc, l.analyte, l.value, l.units, l.flag
+            ));
+        }
+        csv
+    }
+
+    /// Treatments as CSV
+    pub fn medications_csv(&self) -> String {
+        let mut csv = String::from("patient,time_s,description\n");
+        for m in &self.medications {
+            csv.push_str(&format!("{},{:.0},\"{}\"\n", m.patient, m.time_s, m.description.replace('"', "\"\"")));
+        }
+        csv
+    }
+
+    /// Coded diagnoses as CSV
+    pub fn diagnoses_csv(&self) -> String {
+        let mut csv = String::from("patient,icd10,snomed,description,onset_s,resolved_s\n");
+        for d in &self.diagnoses {
+            let resolved = d.resolved_s.map(|t| format!("{:.0}", t)).unwrap_or_default();
+            csv.push_str(&format!(
+                "{},{},{},{},{:.0},{}\n",
+                d.patient, d.icd10, d.snomed, d.description, d.onset_s, resolved
+            ));
+        }
+        csv
+    }
+
+    /// Demographics and outcomes as CSV
+    pub fn outcomes_csv(&self) -> String {
+        let mut csv = String::from(
+            "patient,sex,age_years,acuity,illness,comorbidities,duration_s,lowest_map_mmhg,peak_lactate_mmol_l,survived\n",
+        );
+        for o in &self.outcomes {
+            let illness = o.illness.map(|i| format!("{:?}", i)).unwrap_or_default();
+            csv.push_str(&format!(
+                "{},{:?},{},{:?},{},\"{}\",{:.0},{:.0},{:.1},{}\n",
+                o.patient,
+                o.sex,
+                o.age_years,
+                o.acuity,
+                illness,
+                o.comorbidities.join(";"),
+                o.duration_s,
+                o.lowest_map_mmhg,
+                o.peak_lactate_mmol_l,
+                o.survived
+            ));
+        }
+        csv
+    }
+
+    /// FHIR resources as NDJSON, one resource per line
+    #[cfg(feature = "fhir")]
+    pub fn fhir_ndjson(&self) -> String {
+        let mut ndjson = String::new();
+        for resource in &self.fhir_resources {
+            ndjson.push_str(resource);
+            ndjson.push('\n');
+        }
+        ndjson
+    }
+}
+
+/// Seed of one patient's stream, decorrelated from its neighbours
+fn patient_seed(seed: u64, index: usize) -> u64 {
+    seed ^ (index as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+/// Generate and chart one patient
+fn simulate_encounter(config: &DatasetConfig, index: usize) -> Dataset {
+    let number = index + 1;
+    let mut generator = PatientGenerator::new(patient_seed(config.seed, index), config.generator.clone());
+    generator.next_id = number as i32;
+    let generated = generator.generate();
+    let mut patient = generated.patient;
+    let mut runner = config.scenario.clone().map(ScenarioRunner::new);
+    let (duration_s, dt) = match &config.scenario {
+        Some(scenario) => (scenario.duration_s, scenario.time_step_s),
+        None => (config.duration_s, config.time_step_s.max(1e-3)),
+    };
+    #[cfg(feature = "fhir")]
+    let fhir = FhirExport::new(&patient);
+
+    let mut dataset = Dataset::default();
+    let mut diagnoses = DiagnosisEngine::new();
+    let (mut next_vitals_s, mut next_labs_s) = (0.0, 0.0);
+    let mut reports_seen = 0;
+    let mut lowest_map = f64::INFINITY;
+    let mut peak_lactate: f64 = 0.0;
+    let mut time_s = 0.0;
+    loop {
+        if time_s + dt / 2.0 >= next_vitals_s {
+            dataset.vitals.push(chart_vitals(&patient, number, time_s));
+            #[cfg(feature = "fhir")]
+            dataset.fhir_resources.extend(fhir.vital_signs(&patient, time_s));
+            next_vitals_s += config.vitals_interval_s.max(dt);
+        }
+        if time_s + dt / 2.0 >= duration_s {
+            break;
+        }
+        if time_s + dt / 2.0 >= next_labs_s {
+            for panel in DRAWN_PANELS {
+                patient.order_labs(panel);
+            }
+            next_labs_s += config.labs_interval_s.max(dt);
+        }
+
+        match runner.as_mut() {
+            Some(runner) => {
+                for fired in runner.step(&mut patient).iter().filter(|f| !f.action.is_insult()) {
+                    dataset.medications.push(MedicationRecord {
+                        patient: number,
+                        time_s: fired.time_s,
+                        description: fired.action.to_string(),
+                    });
+                }
+            }
+            None => update_patient(&mut patient, dt),
+        }
+        time_s += dt;
+        diagnoses.update(&patient, dt);
+        lowest_map = lowest_map.min(patient.blood.get_mean_arterial_pressure());
+        peak_lactate = peak_lactate.max(patient.blood.chemistry.lactate_mmol_l);
+
+        for report in &patient.labs.reports()[reports_seen..] {
+            for result in &report.results {
+                let flag = result.flag.symbol();
+                dataset.labs.push(LabRecord {
+                    patient: number,
+                    drawn_s: report.ordered_at_s,
+                    resulted_s: report.reported_at_s,
+                    loinc: result.analyte.loinc().0,
+                    analyte: result.analyte.name(),
+                    value: result.value,
+                    units: result.analyte.units(),
+                    flag,
+                });
+            }
+            #[cfg(feature = "fhir")]
+            dataset.fhir_resources.push(fhir.lab_report(report));
+        }
+        reports_seen = patient.labs.reports().len();
+    }
+
+    for record in diagnoses.records() {
+        let (icd10, _) = record.diagnosis.icd10();
+        let (snomed, description) = record.diagnosis.snomed();
+        dataset.diagnoses.push(DiagnosisRecord {
+            patient: number,
+            icd10,
+            snomed,
+            description,
+            onset_s: record.onset_s,
+            resolved_s: record.resolved_s,
+        });
+    }
+    #[cfg(feature = "fhir")]
+    dataset.fhir_resources.extend(fhir.conditions(&patient, time_s));
+    let survived = patient.get_organ::<Heart>().is_some_and(|heart| !heart.is_in_cardiac_arrest());
+    dataset.outcomes.push(OutcomeRecord {
+        patient: number,
+        sex: patient.demographics.sex,
+        age_years: (patient.demographics.age_years as u32).min(MAX_REPORTED_AGE_YEARS),
+        acuity: generated.acuity,
+        illness: generated.illness,
+        comorbidities: generated.comorbidities,
+        duration_s: time_s,
+        lowest_map_mmhg: lowest_map.min(patient.blood.get_mean_arterial_pressure()),
+        peak_lactate_mmol_l: peak_lactate.max(patient.blood.chemistry.lactate_mmol_l),
+        survived,
+    });
+    dataset
+}
+
+/// Vital signs of the patient now
+fn chart_vitals(patient: &Patient, number: usize, time_s: f64) -> VitalsRecord {
+    VitalsRecord {
+        patient: number,
+        time_s,
+        heart_rate_bpm: Measure::HeartRate.measure(patient),
+        systolic_mmhg: patient.blood.blood_pressure_systolic,
+        diastolic_mmhg: patient.blood.blood_pressure_diastolic,
+        respiratory_rate_bpm: patient.get_organ::<Lungs>().map_or(0.0, |lungs| lungs.respiration_rate_bpm),
+        spo2_percent: patient.blood.gases.sao2_percent,
+        temperature_c: patient.oxygen_transport.body_temperature_c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::AcuityProfile;
+
+    #[test]
+    fn test_dataset_is_charted_and_reproducible() {
+        let config = DatasetConfig {
+            patients: 4,
+            seed: 42,
+            generator: GeneratorConfig {
+                acuity: AcuityProfile::intensive_care(),
+                ..GeneratorConfig::default()
+            },
+            duration_s: 1800.0,
+            time_step_s: 1.0,
+            vitals_interval_s: 300.0,
+            labs_interval_s: 900.0,
+            ..DatasetConfig::default()
+        };
+        let dataset = Dataset::generate(&config);
+
+        assert_eq!(dataset.outcomes.len(), 4);
+        assert!(dataset.outcomes.iter().enumerate().all(|(i, o)| o.patient == i + 1 && o.illness.is_some()));
+        // Charted at 0, 5, ... 30 minutes
+        assert_eq!(dataset.vitals.iter().filter(|v| v.patient == 1).count(), 7);
+        // Lactates drawn at 0 and 15 minutes, with a 5 minute turnaround
+        let drawn: Vec<f64> =
+            dataset.labs.iter().filter(|l| l.patient == 1 && l.analyte == "Lactate").map(|l| l.drawn_s).collect();
+        assert_eq!(drawn, [0.0, 900.0]);
+        assert!(dataset.labs_csv().lines().nth(1).unwrap().starts_with("1,0,"));
+        assert!(dataset.outcomes_csv().starts_with("patient,sex,age_years"));
+        assert!(dataset.outcomes.iter().all(|o| o.age_years <= 90));
+
+        let again = Dataset::generate(&DatasetConfig { max_workers: 1, ..config });
+        assert_eq!(dataset.vitals, again.vitals);
+        assert_eq!(dataset.outcomes, again.outcomes);
+    }
+}
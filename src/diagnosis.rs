@@ -0,0 +1,521 @@
+//! Coded diagnoses
+//!
+//! This module provides:
+//! - Diagnoses recognized from the modeled state (ST-elevation infarction by
+//!   wall, arrest rhythms, acute tubular necrosis, electrolyte disorders,
+//!   rhabdomyolysis, sepsis and shock, respiratory failure) and from
+//!   established chronic disease
+//! - ICD-10 and SNOMED CT codes for each diagnosis
+//! - A diagnosis engine following a simulation, recording when each diagnosis
+//!   begins and resolves, with wall-clock timestamps, so simulated encounters
+//!   can populate the coded fields of EHR and analytics test pipelines
+//!
+//! Chronic disease present at the first assessment is recorded with onset at
+//! that time. Acute kidney injury is staged against the renal function at the
+//! first assessment, so chronic kidney disease is not mistaken for it; it is
+//! coded as acute tubular necrosis after sustained renal hypoperfusion or
+//! when myoglobin has injured the tubules.
+
+use crate::organs::heart::{CoronaryTerritory, Heart, Rhythm};
+use crate::organs::kidneys::Kidneys;
+use crate::organs::liver::Liver;
+use crate::organs::lungs::Lungs;
+use crate::organs::pancreas::{DiabetesType, Pancreas};
+use crate::patient::Patient;
+use crate::timestamp::{CivilTime, DEFAULT_START_UNIX_S};
+
+/// Time a diagnosis must be absent before it is resolved (s)
+const RESOLUTION_DELAY_S: f64 = 60.0;
+
+/// Renal hypoperfusion after which acute kidney injury is tubular necrosis (s)
+const ATN_HYPOPERFUSION_S: f64 = 1800.0;
+
+/// Mean nephron function below which kidney disease is chronic (GFR < 60)
+const CKD_NEPHRON_FUNCTION: f64 = 0.6;
+
+/// Tubular injury at which acute kidney injury is tubular necrosis
+const ATN_TUBULAR_INJURY: f64 = 0.1;
+
+/// Creatine kinase of rhabdomyolysis, five times the upper reference limit (U/L)
+const RHABDOMYOLYSIS_CK_U_L: f64 = 1000.0;
+
+/// Mean arterial pressure of shock (mmHg)
+const SHOCK_MAP_MMHG: f64 = 65.0;
+
+/// Blood volume below which shock is hypovolemic (fraction of the reference)
+const HYPOVOLEMIC_BLOOD_VOLUME: f64 = 0.75;
+
+/// Diagnosis recognized from the modeled state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Diagnosis {
+    /// Acute ST-elevation myocardial infarction of a coronary territory
+    StElevationMyocardialInfarction(CoronaryTerritory),
+    /// Healed myocardial infarction
+    OldMyocardialInfarction,
+    /// Chronic heart failure
+    HeartFailure,
+    /// Cardiac arrest
+    CardiacArrest,
+    /// Ventricular fibrillation
+    VentricularFibrillation,
+    /// Ventricular tachycardia, including torsades de pointes
+    VentricularTachycardia,
+    /// Atrial fibrillation or flutter
+    AtrialFibrillation,
+    /// Acute kidney injury after sustained hypoperfusion or pigment nephropathy
+    AcuteTubularNecrosis,
+    /// Acute kidney injury
+    AcuteKidneyInjury,
+    /// Chronic kidney disease by stage (3-5)
+    ChronicKidneyDisease { stage: u8 },
+    /// Serum potassium above 5.5 mEq/L
+    Hyperkalemia,
+    /// Serum potassium below 3.5 mEq/L
+    Hypokalemia,
+    /// Serum sodium above 145 mEq/L
+    Hypernatremia,
+    /// Serum sodium below 135 mEq/L
+    Hyponatremia,
+    /// Lactate above 4 mmol/L with acidemia
+    LacticAcidosis,
+    /// Glucose below 70 mg/dL
+    Hypoglycemia,
+    /// Creatine kinase above five times the upper reference limit
+    Rhabdomyolysis,
+    /// Sepsis
+    Sepsis,
+    /// Sepsis with hypotension and a raised lactate
+    SepticShock,
+    /// Hypotension from lost blood volume
+    HypovolemicShock,
+    /// Acute respiratory distress syndrome
+    Ards,
+    /// Acute hypoxemic or hypercapnic respiratory failure
+    AcuteRespiratoryFailure,
+    /// Chronic obstructive pulmonary disease
+    Copd,
+    /// Cirrhosis of the liver
+    Cirrhosis,
+    /// Diabetes mellitus
+    Diabetes(DiabetesType),
+}
+
+impl Diagnosis {
+    /// ICD-10 code and title
+    pub fn icd10(&self) -> (&'static str, &'static str) {
+        match self {
+            Diagnosis::StElevationMyocardialInfarction(territory) => match territory {
+                CoronaryTerritory::LeftAnteriorDescending => {
+                    ("I21.0", "Acute transmural myocardial infarction of anterior wall")
+                }
+                CoronaryTerritory::RightCoronary => ("I21.1", "Acute transmural myocardial infarction of inferior wall"),
+                CoronaryTerritory::LeftCircumflex => {
+                    ("I21.2", "Acute transmural myocardial infarction of other sites")
+                }
+            },
+            Diagnosis::OldMyocardialInfarction => ("I25.2", "Old myocardial infarction"),
+            Diagnosis::HeartFailure => ("I50.9", "Heart failure, unspecified"),
+            Diagnosis::CardiacArrest => ("I46.9", "Cardiac arrest, unspecified"),
+            Diagnosis::VentricularFibrillation => ("I49.0", "Ventricular fibrillation and flutter"),
+            Diagnosis::VentricularTachycardia => ("I47.2", "Ventricular tachycardia"),
+            Diagnosis::AtrialFibrillation => ("I48.9", "Atrial fibrillation and atrial flutter, unspecified"),
+            Diagnosis::AcuteTubularNecrosis => ("N17.0", "Acute renal failure with tubular necrosis"),
+            Diagnosis::AcuteKidneyInjury => ("N17.9", "Acute renal failure, unspecified"),
+            Diagnosis::ChronicKidneyDisease { stage } => match stage {
+                3 => ("N18.3", "Chronic kidney disease, stage 3"),
+                4 => ("N18.4", "Chronic kidney disease, stage 4"),
+                _ => ("N18.5", "Chronic kidney disease, stage 5"),
+            },
+            Diagnosis::Hyperkalemia => ("E87.5", "Hyperkalaemia"),
+            Diagnosis::Hypokalemia => ("E87.6", "Hypokalaemia"),
+            Diagnosis::Hypernatremia => ("E87.0", "Hyperosmolality and hypernatraemia"),
+            Diagnosis::Hyponatremia => ("E87.1", "Hypo-osmolality and hyponatraemia"),
+            Diagnosis::LacticAcidosis => ("E87.2", "Acidosis"),
+            Diagnosis::Hypoglycemia => ("E16.2", "Hypoglycaemia, unspecified"),
+            Diagnosis::Rhabdomyolysis => ("M62.82", "Rhabdomyolysis"),
+            Diagnosis::Sepsis => ("A41.9", "Sepsis, unspecified"),
+            Diagnosis::SepticShock => ("R57.2", "Septic shock"),
+            Diagnosis::HypovolemicShock => ("R57.1", "Hypovolaemic shock"),
+            Diagnosis::Ards => ("J80", "Adult respiratory distress syndrome"),
+            Diagnosis::AcuteRespiratoryFailure => ("J96.0", "Acute respiratory failure"),
+            Diagnosis::Copd => ("J44.9", "Chronic obstructive pulmonary disease, unspecified"),
+            Diagnosis::Cirrhosis => ("K74.6", "Other and unspecified cirrhosis of liver"),
+            Diagnosis::Diabetes(DiabetesType::Type1) => ("E10.9", "Type 1 diabetes mellitus without complications"),
+            Diagnosis::Diabetes(DiabetesType::Type2) => ("E11.9", "Type 2 diabetes mellitus without complications"),
+        }
+    }
+
+    /// SNOMED CT concept ID and term
+    pub fn snomed(&self) -> (&'static str, &'static str) {
+        match self {
+            Diagnosis::StElevationMyocardialInfarction(_) => {
+                ("401303003", "Acute ST segment elevation myocardial infarction")
+            }
+            Diagnosis::OldMyocardialInfarction => ("1755008", "Old myocardial infarction"),
+            Diagnosis::HeartFailure => ("84114007", "Heart failure"),
+            Diagnosis::CardiacArrest => ("410429000", "Cardiac arrest"),
+            Diagnosis::VentricularFibrillation => ("71908006", "Ventricular fibrillation"),
+            Diagnosis::VentricularTachycardia => ("25569003", "Ventricular tachycardia"),
+            Diagnosis::AtrialFibrillation => ("49436004", "Atrial fibrillation"),
+            Diagnosis::AcuteTubularNecrosis => ("35455006", "Acute tubular necrosis"),
+            Diagnosis::AcuteKidneyInjury => ("14669001", "Acute kidney injury"),
+            Diagnosis::ChronicKidneyDisease { stage } => match stage {
+                3 => ("433144002", "Chronic kidney disease stage 3"),
+                4 => ("431857002", "Chronic kidney disease stage 4"),
+                _ => ("433146000", "Chronic kidney disease stage 5"),
+            },
+            Diagnosis::Hyperkalemia => ("14140009", "Hyperkalemia"),
+            Diagnosis::Hypokalemia => ("43339004", "Hypokalemia"),
+            Diagnosis::Hypernatremia => ("39355002", "Hypernatremia"),
+            Diagnosis::Hyponatremia => ("89627008", "Hyponatremia"),
+            Diagnosis::LacticAcidosis => ("91273001", "Lactic acidosis"),
+            Diagnosis::Hypoglycemia => ("302866003", "Hypoglycemia"),
+            Diagnosis::Rhabdomyolysis => ("240131006", "Rhabdomyolysis"),
+            Diagnosis::Sepsis => ("91302008", "Sepsis"),
+            Diagnosis::SepticShock => ("76571007", "Septic shock"),
+            Diagnosis::HypovolemicShock => ("39419009", "Hypovolemic shock"),
+            Diagnosis::Ards => ("67782005", "Acute respiratory distress syndrome"),
+            Diagnosis::AcuteRespiratoryFailure => ("65710008", "Acute respiratory failure"),
+            Diagnosis::Copd => ("13645005", "Chronic obstructive lung disease"),
+            Diagnosis::Cirrhosis => ("19943007", "Cirrhosis of liver"),
+            Diagnosis::Diabetes(DiabetesType::Type1) => ("46635009", "Diabetes mellitus type 1"),
+            Diagnosis::Diabetes(DiabetesType::Type2) => ("44054006", "Diabetes mellitus type 2"),
+        }
+    }
+
+    /// Whether the diagnosis is established chronic disease rather than an acute event
+    pub fn is_chronic(&self) -> bool {
+        matches!(
+            self,
+            Diagnosis::OldMyocardialInfarction
+                | Diagnosis::HeartFailure
+                | Diagnosis::ChronicKidneyDisease { .. }
+                | Diagnosis::Copd
+                | Diagnosis::Cirrhosis
+                | Diagnosis::Diabetes(_)
+        )
+    }
+}
+
+/// A diagnosis with its onset and resolution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CodedDiagnosis {
+    /// Diagnosis
+    pub diagnosis: Diagnosis,
+    /// Simulation time the diagnosis was first met (s)
+    pub onset_s: f64,
+    /// Simulation time the diagnosis was last met, once resolved (s)
+    pub resolved_s: Option<f64>,
+    /// Simulation time the diagnosis was last met (s)
+    last_met_s: f64,
+}
+
+impl CodedDiagnosis {
+    /// Whether the diagnosis is still present
+    pub fn is_active(&self) -> bool {
+        self.resolved_s.is_none()
+    }
+}
+
+/// Engine recognizing and timing diagnoses over a simulation
+#[derive(Debug, Clone)]
+pub struct DiagnosisEngine {
+    /// Wall-clock time of simulation time zero (Unix seconds)
+    pub start_unix_s: i64,
+    /// Simulation time of the latest assessment (s)
+    pub time_s: f64,
+    /// Mean nephron function at the first assessment
+    admission_nephron_function: Option<f64>,
+    /// Time the kidneys have been continuously below their autoregulatory range (s)
+    renal_hypoperfusion_s: f64,
+    /// Every diagnosis made, in order of onset
+    records: Vec<CodedDiagnosis>,
+}
+
+impl DiagnosisEngine {
+    /// Create an engine starting at 2024-01-01
+    pub fn new() -> Self {
+        Self {
+            start_unix_s: DEFAULT_START_UNIX_S,
+            time_s: 0.0,
+            admission_nephron_function: None,
+            renal_hypoperfusion_s: 0.0,
+            records: Vec::new(),
+        }
+    }
+
+    /// Diagnoses the patient meets now
+    ///
+    /// # Arguments
+    /// * `patient` - Patient to assess
+    pub fn assess(&self, patient: &Patient) -> Vec<Diagnosis> {
+        let mut diagnoses = Vec::new();
+        let chemistry = &patient.blood.chemistry;
+        let gases = &patient.blood.gases;
+        let map = patient.blood.get_mean_arterial_pressure();
+
+        if let Some(heart) = patient.get_organ::<Heart>() {
+            for territory in heart.infarcted_territories() {
+                diagnoses.push(Diagnosis::StElevationMyocardialInfarction(territory));
+            }
+            if heart.myocardial_segments.iter().any(|segment| segment.scar) {
+                diagnoses.push(Diagnosis::OldMyocardialInfarction);
+            }
+            if heart.systolic_dysfunction.max(heart.diastolic_dysfunction) > 0.1 {
+                diagnoses.push(Diagnosis::HeartFailure);
+            }
+            if heart.is_in_cardiac_arrest() {
+                diagnoses.push(Diagnosis::CardiacArrest);
+            }
+            match heart.rhythm {
+                Rhythm::VentricularFibrillation => diagnoses.push(Diagnosis::VentricularFibrillation),
+                Rhythm::PulselessVentricularTachycardia | Rhythm::TorsadesDePointes => {
+                    diagnoses.push(Diagnosis::VentricularTachycardia)
+                }
+                Rhythm::AtrialFibrillation | Rhythm::AtrialFlutter => diagnoses.push(Diagnosis::AtrialFibrillation),
+                _ => {}
+            }
+        }
+
+        if let Some(kidneys) = patient.get_organ::<Kidneys>() {
+            let admission_function = self.admission_nephron_function.unwrap_or_else(|| nephron_function(kidneys));
+            let creatinine_ratio =
+                kidneys.baseline_gfr_ml_per_min * admission_function / kidneys.gfr_ml_per_min.max(1e-6);
+            if creatinine_ratio >= 1.5 {
+                let tubular_necrosis = self.renal_hypoperfusion_s >= ATN_HYPOPERFUSION_S
+                    || kidneys.tubular_injury >= ATN_TUBULAR_INJURY;
+                diagnoses.push(if tubular_necrosis {
+                    Diagnosis::AcuteTubularNecrosis
+                } else {
+                    Diagnosis::AcuteKidneyInjury
+                });
+            }
+            if admission_function < CKD_NEPHRON_FUNCTION {
+                let gfr = kidneys.baseline_gfr_ml_per_min * admission_function;
+                let stage = match gfr {
+                    g if g < 15.0 => 5,
+                    g if g < 30.0 => 4,
+                    _ => 3,
+                };
+                diagnoses.push(Diagnosis::ChronicKidneyDisease { stage });
+            }
+        }
+
+        if chemistry.potassium_meq_l > 5.5 {
+            diagnoses.push(Diagnosis::Hyperkalemia);
+        } else if chemistry.potassium_meq_l < 3.5 {
+            diagnoses.push(Diagnosis::Hypokalemia);
+        }
+        if chemistry.sodium_meq_l > 145.0 {
+            diagnoses.push(Diagnosis::Hypernatremia);
+        } else if chemistry.sodium_meq_l < 135.0 {
+            diagnoses.push(Diagnosis::Hyponatremia);
+        }
+        if chemistry.lactate_mmol_l > 4.0 && gases.ph < 7.35 {
+            diagnoses.push(Diagnosis::LacticAcidosis);
+        }
+        if chemistry.glucose_mg_dl < 70.0 {
+            diagnoses.push(Diagnosis::Hypoglycemia);
+        }
+        if chemistry.ck_u_l > RHABDOMYOLYSIS_CK_U_L {
+            diagnoses.push(Diagnosis::Rhabdomyolysis);
+        }
+
+        let septic = patient.sepsis.as_ref().is_some_and(|sepsis| sepsis.is_septic());
+        if septic {
+            diagnoses.push(Diagnosis::Sepsis);
+            if map < SHOCK_MAP_MMHG && chemistry.lactate_mmol_l > 2.0 {
+                diagnoses.push(Diagnosis::SepticShock);
+            }
+        }
+        let blood_volume = patient.fluids.blood_volume_ml() / patient.fluids.reference_blood_volume_ml.max(1.0);
+        if map < SHOCK_MAP_MMHG && blood_volume < HYPOVOLEMIC_BLOOD_VOLUME {
+            diagnoses.push(Diagnosis::HypovolemicShock);
+        }
+
+        if let Some(lungs) = patient.get_organ::<Lungs>() {
+            if lungs.get_ards_severity(gases.pao2_mmhg).is_some() {
+                diagnoses.push(Diagnosis::Ards);
+            }
+            if lungs.main_bronchus.chronic_obstruction > 0.0 {
+                diagnoses.push(Diagnosis::Copd);
+            }
+        }
+        if gases.pao2_mmhg < 60.0 || (gases.paco2_mmhg > 50.0 && gases.ph < 7.35) {
+            diagnoses.push(Diagnosis::AcuteRespiratoryFailure);
+        }
+        if patient.get_organ::<Liver>().is_some_and(|liver| liver.is_cirrhotic()) {
+            diagnoses.push(Diagnosis::Cirrhosis);
+        }
+        if let Some(diabetes_type) = patient.get_organ::<Pancreas>().and_then(|p| p.diabetes) {
+            diagnoses.push(Diagnosis::Diabetes(diabetes_type));
+        }
+        diagnoses
+    }
+
+    /// Assess the patient after a step, recording onsets and resolutions
+    ///
+    /// # Arguments
+    /// * `patient` - Patient to assess
+    /// * `delta_time_s` - Time since the last assessment (s)
+    pub fn update(&mut self, patient: &Patient, delta_time_s: f64) {
+        self.time_s += delta_time_s.max(0.0);
+        if let Some(kidneys) = patient.get_organ::<Kidneys>() {
+            self.admission_nephron_function.get_or_insert_with(|| nephron_function(kidneys));
+            if patient.blood.get_mean_arterial_pressure() < kidneys.autoregulation_map_mmhg {
+                self.renal_hypoperfusion_s += delta_time_s.max(0.0);
+            } else {
+                self.renal_hypoperfusion_s = 0.0;
+            }
+        }
+
+        let time_s = self.time_s;
+        let current = self.assess(patient);
+        for &diagnosis in &current {
+            match self.records.iter_mut().find(|r| r.diagnosis == diagnosis && r.is_active()) {
+                Some(record) => record.last_met_s = time_s,
+                None => self.records.push(CodedDiagnosis {
+                    diagnosis,
+                    onset_s: time_s,
+                    resolved_s: None,
+                    last_met_s: time_s,
+                }),
+            }
+        }
+        for record in self.records.iter_mut().filter(|r| r.is_active() && !current.contains(&r.diagnosis)) {
+            if time_s - record.last_met_s >= RESOLUTION_DELAY_S {
+                record.resolved_s = Some(record.last_met_s);
+            }
+        }
+    }
+
+    /// Diagnoses still present
+    pub fn active(&self) -> Vec<&CodedDiagnosis> {
+        self.records.iter().filter(|r| r.is_active()).collect()
+    }
+
+    /// Every diagnosis made, in order of onset
+    pub fn records(&self) -> &[CodedDiagnosis] {
+        &self.records
+    }
+
+    /// Wall-clock instant of a simulation time (ISO 8601)
+    ///
+    /// # Arguments
+    /// * `time_s` - Simulation time (s)
+    pub fn instant(&self, time_s: f64) -> String {
+        CivilTime::from_unix(self.start_unix_s + time_s.floor() as i64).iso8601()
+    }
+
+    /// Format the diagnoses as CSV, one row per diagnosis in order of onset
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("icd10,snomed,description,onset,resolved\n");
+        for record in &self.records {
+            let (icd10, _) = record.diagnosis.icd10();
+            let (snomed, description) = record.diagnosis.snomed();
+            let resolved = record.resolved_s.map(|t| self.instant(t)).unwrap_or_default();
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                icd10,
+                snomed,
+                description,
+                self.instant(record.onset_s),
+                resolved
+            ));
+        }
+        csv
+    }
+}
+
+impl Default for DiagnosisEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Mean filtration efficiency of the nephrons (1.0 = normal)
+fn nephron_function(kidneys: &Kidneys) -> f64 {
+    if kidneys.nephrons.is_empty() {
+        return 1.0;
+    }
+    kidneys.nephrons.iter().map(|n| n.filtration_efficiency).sum::<f64>() / kidneys.nephrons.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fidelity::Fidelity;
+    use crate::organs::muscle::Muscle;
+    use crate::patient::{initialize_patient, update_patient};
+    use crate::presets::{self, Preset};
+
+    #[test]
+    fn test_anterior_stemi_and_hyperkalemia_are_coded_with_onsets() {
+        let mut patient = presets::initialize_with(1, 3, &[Preset::ChronicKidneyDisease { stage: 3 }]);
+        let mut engine = DiagnosisEngine::new();
+        for step in 0..600 {
+            if step == 120 {
+                if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                    heart.set_territory_contractility(CoronaryTerritory::LeftAnteriorDescending, 0.0);
+                }
+            }
+            update_patient(&mut patient, 1.0);
+            // Potassium is held high for two minutes, then corrected
+            if (300..420).contains(&step) {
+                patient.blood.chemistry.potassium_meq_l = 6.5;
+            } else if step == 420 {
+                patient.blood.chemistry.potassium_meq_l = 4.0;
+            }
+            engine.update(&patient, 1.0);
+        }
+
+        let record = |diagnosis| engine.records().iter().find(|r| r.diagnosis == diagnosis).copied();
+        let ckd = record(Diagnosis::ChronicKidneyDisease { stage: 3 }).unwrap();
+        assert_eq!(ckd.onset_s, 1.0);
+        // Kidney disease present at the start is not acute kidney injury
+        assert!(record(Diagnosis::AcuteKidneyInjury).is_none());
+
+        let stemi = record(Diagnosis::StElevationMyocardialInfarction(CoronaryTerritory::LeftAnteriorDescending));
+        let stemi = stemi.unwrap();
+        assert!(stemi.onset_s > 120.0 && stemi.is_active());
+        assert_eq!(stemi.diagnosis.icd10().0, "I21.0");
+
+        let hyperkalemia = record(Diagnosis::Hyperkalemia).unwrap();
+        assert_eq!(hyperkalemia.onset_s, 301.0);
+        assert_eq!(hyperkalemia.resolved_s, Some(420.0));
+        assert_eq!(hyperkalemia.diagnosis.icd10().0, "E87.5");
+
+        let csv = engine.to_csv();
+        assert!(csv.contains("E87.5,14140009,Hyperkalemia,2024-01-01T00:05:01Z,2024-01-01T00:07:00Z"));
+        assert!(csv.contains("N18.3,433144002"));
+    }
+
+    #[test]
+    fn test_released_crush_codes_rhabdomyolysis_and_acute_tubular_necrosis() {
+        let mut patient = initialize_patient(1, 3);
+        patient.fidelity = Fidelity::Fast;
+        update_patient(&mut patient, 600.0);
+        patient.get_organ_mut::<Muscle>().unwrap().crush(0.2);
+        for _ in 0..240 {
+            update_patient(&mut patient, 60.0);
+        }
+        patient.get_organ_mut::<Muscle>().unwrap().release_compression();
+
+        // Only the acidemic patient's myoglobin precipitates in the tubules
+        let mut acidemic = patient.fork();
+        acidemic.blood.chemistry.bicarbonate_meq_l = 12.0;
+        let (mut engine, mut acidemic_engine) = (DiagnosisEngine::new(), DiagnosisEngine::new());
+        for _ in 0..720 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut acidemic, 60.0);
+            engine.update(&patient, 60.0);
+            acidemic_engine.update(&acidemic, 60.0);
+        }
+        let rhabdomyolysis = engine.records().iter().find(|r| r.diagnosis == Diagnosis::Rhabdomyolysis).unwrap();
+        assert!(rhabdomyolysis.onset_s < 600.0 && rhabdomyolysis.is_active());
+        assert_eq!(rhabdomyolysis.diagnosis.icd10().0, "M62.82");
+        let diagnosed = |engine: &DiagnosisEngine, diagnosis| engine.records().iter().any(|r| r.diagnosis == diagnosis);
+        assert!(diagnosed(&acidemic_engine, Diagnosis::AcuteTubularNecrosis));
+        assert!(!diagnosed(&engine, Diagnosis::AcuteTubularNecrosis));
+    }
+}
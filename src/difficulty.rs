@@ -0,0 +1,101 @@
+//! Scenario difficulty
+//!
+//! This module provides:
+//! - Global modifiers that scale the same underlying pathology without editing
+//!   organ internals: disease progression speed, treatment responsiveness and
+//!   physiologic reserve
+//! - Presets from a forgiving classroom mode to an unforgiving board-review mode
+
+/// Global difficulty modifiers for a scenario (1.0 = standard physiology)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty {
+    /// Speed of pathologic processes: bacterial growth and ischemic injury
+    pub disease_progression: f64,
+    /// Efficacy of treatment: vasoactive drugs, defibrillation and antibiotics
+    pub treatment_responsiveness: f64,
+    /// Compensatory capacity: baroreflex tachycardia, oxygen extraction and
+    /// tolerance of ischemia
+    pub physiologic_reserve: f64,
+}
+
+impl Difficulty {
+    /// Create difficulty modifiers
+    ///
+    /// # Arguments
+    /// * `disease_progression` - Speed of pathology (0.1-5.0, 1.0 = standard)
+    /// * `treatment_responsiveness` - Treatment efficacy (0.1-5.0, 1.0 = standard)
+    /// * `physiologic_reserve` - Compensatory capacity (0.1-5.0, 1.0 = standard)
+    pub fn new(disease_progression: f64, treatment_responsiveness: f64, physiologic_reserve: f64) -> Self {
+        Self {
+            disease_progression: disease_progression.clamp(0.1, 5.0),
+            treatment_responsiveness: treatment_responsiveness.clamp(0.1, 5.0),
+            physiologic_reserve: physiologic_reserve.clamp(0.1, 5.0),
+        }
+    }
+
+    /// Standard physiology
+    pub fn standard() -> Self {
+        Self::new(1.0, 1.0, 1.0)
+    }
+
+    /// Slow pathology, brisk treatment responses and a robust patient
+    pub fn classroom() -> Self {
+        Self::new(0.6, 1.4, 1.3)
+    }
+
+    /// Fast pathology, blunted treatment responses and a frail patient
+    pub fn board_review() -> Self {
+        Self::new(1.5, 0.7, 0.7)
+    }
+
+    /// Time over which ischemic injury accrues during a step (s)
+    ///
+    /// # Arguments
+    /// * `delta_time_s` - Time step in seconds
+    pub fn ischemic_time_s(&self, delta_time_s: f64) -> f64 {
+        delta_time_s * self.disease_progression / self.physiologic_reserve
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::heart::Heart;
+    use crate::patient::{initialize_patient, update_patient, Patient};
+
+    #[test]
+    fn test_modifiers_are_clamped_and_scale_ischemic_time() {
+        assert_eq!(Difficulty::new(0.0, 10.0, 1.0), Difficulty::new(0.1, 5.0, 1.0));
+        assert_eq!(Difficulty::standard().ischemic_time_s(10.0), 10.0);
+        assert!(Difficulty::board_review().ischemic_time_s(10.0) > 20.0);
+        assert!(Difficulty::classroom().ischemic_time_s(10.0) < 5.0);
+    }
+
+    #[test]
+    fn test_frail_patients_compensate_less_for_the_same_hemorrhage() {
+        let bled = |difficulty: Difficulty| {
+            let mut patient = initialize_patient(1, 3);
+            patient.difficulty = difficulty;
+            for _ in 0..30 {
+                update_patient(&mut patient, 1.0);
+            }
+            patient.hemorrhage(0.25 * patient.fluids.reference_blood_volume_ml);
+            for _ in 0..120 {
+                update_patient(&mut patient, 1.0);
+            }
+            patient
+        };
+        let heart_rate = |patient: &Patient| patient.get_organ::<Heart>().unwrap().heart_rate_bpm;
+        let classroom = bled(Difficulty::classroom());
+        let standard = bled(Difficulty::standard());
+        let board_review = bled(Difficulty::board_review());
+        assert!(heart_rate(&classroom) > heart_rate(&standard));
+        assert!(heart_rate(&standard) > heart_rate(&board_review));
+    }
+}
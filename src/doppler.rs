@@ -0,0 +1,226 @@
+//! Non-invasive vascular diagnostics
+//!
+//! This module provides:
+//! - Arterial duplex Doppler: peak systolic and end-diastolic velocities, resistive
+//!   index and waveform character (triphasic, biphasic, low-resistance, monophasic, absent)
+//! - Velocity acceleration through stenoses and damped flow beyond them
+//! - Dissection flaps seen on duplex imaging
+//! - Venous compression ultrasound for deep vein thrombosis (DVT)
+//! - Ankle-brachial index (ABI) for peripheral arterial disease (PAD)
+
+use crate::organs::lungs::Side;
+use crate::organs::vascular::{VascularSystem, VesselType};
+
+/// Shape of the arterial Doppler waveform
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DopplerWaveform {
+    /// Forward systolic flow, early diastolic reversal, late forward flow (normal limb artery)
+    Triphasic,
+    /// Loss of late diastolic forward flow (mild disease or vasodilation)
+    Biphasic,
+    /// Forward flow throughout diastole into a low-resistance bed (brain, kidney, gut)
+    LowResistance,
+    /// Slow-rising continuous forward flow beyond a significant stenosis
+    Monophasic,
+    /// No detectable flow
+    Absent,
+}
+
+/// Arterial duplex Doppler study of one artery
+#[derive(Debug, Clone)]
+pub struct ArterialDopplerStudy {
+    /// Artery insonated
+    pub artery: String,
+    /// Peak systolic velocity (cm/s)
+    pub peak_systolic_velocity_cm_s: f64,
+    /// End-diastolic velocity (cm/s)
+    pub end_diastolic_velocity_cm_s: f64,
+    /// Resistive index ((PSV - EDV) / PSV)
+    pub resistive_index: f64,
+    /// Waveform character
+    pub waveform: DopplerWaveform,
+    /// Diameter narrowing at the insonated segment (%)
+    pub narrowing_percent: f64,
+    /// Whether an intimal flap with a false lumen is seen
+    pub dissection_flap: bool,
+}
+
+/// Venous compression ultrasound of one vein
+#[derive(Debug, Clone)]
+pub struct VenousDopplerStudy {
+    /// Vein examined
+    pub vein: String,
+    /// Whether the vein collapses under probe pressure (non-compressible = thrombus)
+    pub compressible: bool,
+    /// Whether flow varies with respiration
+    pub phasic_flow: bool,
+    /// Whether squeezing the distal limb increases flow
+    pub augmentation: bool,
+}
+
+impl VenousDopplerStudy {
+    /// Check whether the study shows deep vein thrombosis
+    pub fn shows_thrombosis(&self) -> bool {
+        !self.compressible
+    }
+}
+
+/// Arteries supplying low-resistance vascular beds
+const LOW_RESISTANCE_ARTERIES: [&str; 5] = ["Carotid", "Cerebral", "Basilar", "Renal", "Celiac"];
+
+/// Normal peak systolic velocity for an artery of the given diameter (cm/s)
+fn normal_peak_systolic_velocity(diameter_mm: f64) -> f64 {
+    60.0 + 4.0 * diameter_mm
+}
+
+/// Perform an arterial Doppler study
+///
+/// Flow accelerates through a narrowed lumen, so velocity rises at a
+/// stenosis; beyond a flow-limiting lesion velocities fall and the
+/// waveform loses its diastolic reversal.
+///
+/// # Arguments
+/// * `vascular` - Vascular system
+/// * `artery` - Artery name (e.g. "Femoral Artery (L)")
+///
+/// # Returns
+/// The study, or None if the vessel doesn't exist or isn't an artery
+pub fn arterial_doppler(vascular: &VascularSystem, artery: &str) -> Option<ArterialDopplerStudy> {
+    let vessel = vascular.vessel(artery).filter(|v| v.vessel_type == VesselType::Artery)?;
+    let path = vascular.arterial_path(artery);
+    let flow_fraction: f64 = path.iter().map(|v| v.patency()).product();
+    let upstream_patency: f64 = path.iter().filter(|v| v.name != vessel.name).map(|v| v.patency()).product();
+    let narrowing = 1.0 - vessel.effective_diameter() / vessel.diameter_mm.max(1e-6);
+
+    // Cardiac output sets velocities everywhere; a stenosis accelerates the flow it lets through
+    let cardiac_factor = (vascular.heart_rate_bpm / 75.0).clamp(0.0, 1.5).sqrt();
+    let acceleration = 1.0 / (1.0 - narrowing).max(0.05);
+    let peak_systolic_velocity = normal_peak_systolic_velocity(vessel.baseline_diameter_mm)
+        * cardiac_factor
+        * flow_fraction
+        * acceleration;
+
+    let worst_upstream_narrowing = path
+        .iter()
+        .map(|v| 1.0 - v.effective_diameter() / v.diameter_mm.max(1e-6))
+        .fold(0.0, f64::max);
+    let waveform = if peak_systolic_velocity < 5.0 {
+        DopplerWaveform::Absent
+    } else if upstream_patency < 1.0 || flow_fraction < 0.8 {
+        DopplerWaveform::Monophasic
+    } else if LOW_RESISTANCE_ARTERIES.iter().any(|bed| artery.contains(bed)) {
+        DopplerWaveform::LowResistance
+    } else if worst_upstream_narrowing >= 0.5 {
+        DopplerWaveform::Biphasic
+    } else {
+        DopplerWaveform::Triphasic
+    };
+
+    // High-resistance limb flow has little diastolic flow; damped flow is more continuous
+    let resistive_index = match waveform {
+        DopplerWaveform::Triphasic => 1.0,
+        DopplerWaveform::Biphasic => 0.85,
+        DopplerWaveform::LowResistance => 0.6,
+        DopplerWaveform::Monophasic => 0.4 + 0.4 * upstream_patency.min(flow_fraction),
+        DopplerWaveform::Absent => 0.0,
+    };
+    // A jet through a tight stenosis keeps flowing in diastole
+    let resistive_index = if narrowing >= 0.7 { resistive_index.min(0.6) } else { resistive_index };
+
+    Some(ArterialDopplerStudy {
+        artery: artery.to_string(),
+        peak_systolic_velocity_cm_s: peak_systolic_velocity,
+        end_diastolic_velocity_cm_s: peak_systolic_velocity * (1.0 - resistive_index),
+        resistive_index,
+        waveform,
+        narrowing_percent: narrowing * 100.0,
+        dissection_flap: vessel.dissection > 0.0,
+    })
+}
+
+/// Perform venous compression ultrasound
+///
+/// # Arguments
+/// * `vascular` - Vascular system
+/// * `vein` - Vein name (e.g. "Femoral Vein (L)")
+///
+/// # Returns
+/// The study, or None if the vessel doesn't exist or isn't a vein
+pub fn venous_doppler(vascular: &VascularSystem, vein: &str) -> Option<VenousDopplerStudy> {
+    let vessel = vascular.vessel(vein).filter(|v| v.vessel_type == VesselType::Vein)?;
+    // Even non-occlusive thrombus keeps the vein from collapsing
+    let compressible = vessel.occlusion < 0.2;
+    let phasic_flow = vessel.occlusion < 0.5 && vascular.heart_rate_bpm > 0.0;
+    Some(VenousDopplerStudy {
+        vein: vein.to_string(),
+        compressible,
+        phasic_flow,
+        augmentation: phasic_flow,
+    })
+}
+
+/// Calculate the ankle-brachial index for one leg
+///
+/// The higher of the dorsalis pedis and posterior tibial pressures divided
+/// by the higher of the two brachial pressures.
+///
+/// # Arguments
+/// * `vascular` - Vascular system
+/// * `side` - Leg
+pub fn ankle_brachial_index(vascular: &VascularSystem, side: Side) -> f64 {
+    let suffix = match side {
+        Side::Left => "(L)",
+        Side::Right => "(R)",
+    };
+    let systolic = |artery: &str| vascular.local_blood_pressure(&format!("{} {}", artery, suffix)).0;
+    let ankle = systolic("Dorsalis Pedis Artery").max(systolic("Posterior Tibial Artery"));
+    let [(left_arm, _), (right_arm, _)] = vascular.get_arm_blood_pressures();
+    let brachial = left_arm.max(right_arm);
+    if brachial > 0.0 {
+        ankle / brachial
+    } else {
+        0.0
+    }
+}
+
+/// Interpret an ankle-brachial index
+///
+/// # Arguments
+/// * `abi` - Ankle-brachial index
+pub fn interpret_abi(abi: f64) -> &'static str {
+    if abi > 1.4 {
+        "Non-compressible (calcified) arteries"
+    } else if abi >= 1.0 {
+        "Normal"
+    } else if abi > 0.9 {
+        "Borderline"
+    } else if abi > 0.4 {
+        "Peripheral arterial disease"
+    } else {
+        "Critical limb ischemia"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_femoral_occlusion_lowers_abi_and_damps_waveform() {
+        let mut vascular = VascularSystem::new(1);
+        assert!(ankle_brachial_index(&vascular, Side::Left) >= 1.0);
+        assert_eq!(
+            arterial_doppler(&vascular, "Dorsalis Pedis Artery (L)").unwrap().waveform,
+            DopplerWaveform::Triphasic
+        );
+
+        vascular.occlude_vessel("Femoral Artery (L)", 0.9);
+        let abi = ankle_brachial_index(&vascular, Side::Left);
+        assert!(abi < 0.9, "ABI {abi}");
+        assert_eq!(interpret_abi(ankle_brachial_index(&vascular, Side::Right)), "Normal");
+        assert_eq!(
+            arterial_doppler(&vascular, "Dorsalis Pedis Artery (L)").unwrap().waveform,
+            DopplerWaveform::Monophasic
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! EKG export
+//!
+//! This module provides:
+//! - A recording of the heart's EKG leads with lead names, sampling rate and calibration
+//! - Export as a WFDB record (header plus format-16 signal file), readable by
+//!   PhysioNet tools and most ECG viewers
+//! - Export as CSV with a timestamp column and a commented header
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::organs::heart::Heart;
+
+/// WFDB gain (analog-to-digital units per mV)
+pub const WFDB_GAIN_ADU_PER_MV: f64 = 200.0;
+
+/// Resolution of the WFDB format-16 samples (bits)
+const WFDB_ADC_RESOLUTION_BITS: u32 = 16;
+
+/// Snapshot of the EKG leads for export
+#[derive(Debug, Clone, PartialEq)]
+pub struct EcgRecording {
+    /// Lead names, in channel order
+    pub lead_names: Vec<String>,
+    /// Samples per second (Hz)
+    pub sampling_rate_hz: f64,
+    /// Simulation time of the first sample (s)
+    pub start_time_s: f64,
+    /// Samples of each lead (mV), in channel order
+    pub leads_mv: Vec<Vec<f64>>,
+    /// Rhythm at the time of the snapshot, recorded as an annotation
+    pub rhythm: String,
+}
+
+impl EcgRecording {
+    /// Take the EKG leads currently buffered by a heart
+    pub fn from_heart(heart: &Heart) -> Self {
+        Self {
+            lead_names: heart.ekg_lead_names().iter().map(|n| n.to_string()).collect(),
+            sampling_rate_hz: heart.ekg_sampling_rate_hz(),
+            start_time_s: heart.ekg_sampler.start_time_s(),
+            leads_mv: heart.ekg_leads.iter().map(|lead| lead.iter().collect()).collect(),
+            rhythm: format!("{:?}", heart.rhythm),
+        }
+    }
+
+    /// Number of samples per lead (the shortest lead, if they differ)
+    pub fn num_samples(&self) -> usize {
+        self.leads_mv.iter().map(Vec::len).min().unwrap_or(0)
+    }
+
+    /// Length of the recording (s)
+    pub fn duration_s(&self) -> f64 {
+        self.num_samples() as f64 / self.sampling_rate_hz
+    }
+
+    /// Format the recording as CSV
+    ///
+    /// Comment lines starting with `#` give the sampling rate, units and
+    /// rhythm, followed by a header row and one row per sample with its
+    /// simulation time.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!(
+            "# sampling_rate_hz={}\n# units=mV\n# rhythm={}\ntime_s,{}\n",
+            self.sampling_rate_hz,
+            self.rhythm,
+            self.lead_names.join(",")
+        );
+        for i in 0..self.num_samples() {
+            csv.push_str(&format!("{:.4}", self.start_time_s + i as f64 / self.sampling_rate_hz));
+            for lead in &self.leads_mv {
+                csv.push_str(&format!(",{:.4}", lead[i]));
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Encode the recording as a WFDB record
+    ///
+    /// Samples are interleaved 16-bit little-endian integers (format 16) at
+    /// `WFDB_GAIN_ADU_PER_MV` with a zero baseline; values beyond the 16-bit
+    /// range are clipped.
+    ///
+    /// # Arguments
+    /// * `record_name` - Record name, used for the `.hea` and `.dat` files
+    pub fn to_wfdb(&self, record_name: &str) -> WfdbRecord {
+        let num_samples = self.num_samples();
+        let digital: Vec<Vec<i16>> = self
+            .leads_mv
+            .iter()
+            .map(|lead| {
+                lead[..num_samples]
+                    .iter()
+                    .map(|mv| (mv * WFDB_GAIN_ADU_PER_MV).round().clamp(i16::MIN as f64, i16::MAX as f64) as i16)
+                    .collect()
+            })
+            .collect();
+
+        let mut signal = Vec::with_capacity(2 * num_samples * digital.len());
+        for i in 0..num_samples {
+            for lead in &digital {
+                signal.extend_from_slice(&lead[i].to_le_bytes());
+            }
+        }
+
+        let mut header = format!("{} {} {} {}\n", record_name, digital.len(), self.sampling_rate_hz, num_samples);
+        for (name, lead) in self.lead_names.iter().zip(&digital) {
+            // The checksum is the 16-bit sum of the samples
+            let checksum = lead.iter().fold(0i16, |sum, &v| sum.wrapping_add(v));
+            header.push_str(&format!(
+                "{}.dat 16 {}/mV {} 0 {} {} 0 {}\n",
+                record_name,
+                WFDB_GAIN_ADU_PER_MV,
+                WFDB_ADC_RESOLUTION_BITS,
+                lead.first().copied().unwrap_or(0),
+                checksum,
+                name
+            ));
+        }
+        header.push_str(&format!("# Rhythm: {}\n", self.rhythm));
+
+        WfdbRecord {
+            record_name: record_name.to_string(),
+            header,
+            signal,
+        }
+    }
+}
+
+/// WFDB record: a text header and a binary signal file
+#[derive(Debug, Clone, PartialEq)]
+pub struct WfdbRecord {
+    /// Record name
+    pub record_name: String,
+    /// Contents of the `.hea` header file
+    pub header: String,
+    /// Contents of the `.dat` signal file
+    pub signal: Vec<u8>,
+}
+
+impl WfdbRecord {
+    /// Write the `.hea` and `.dat` files into a directory
+    ///
+    /// # Arguments
+    /// * `directory` - Directory to write into
+    pub fn write_to(&self, directory: &Path) -> io::Result<()> {
+        fs::write(directory.join(format!("{}.hea", self.record_name)), &self.header)?;
+        fs::write(directory.join(format!("{}.dat", self.record_name)), &self.signal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_export_twelve_lead_recording() {
+        let mut patient = initialize_patient(1, 12);
+        for _ in 0..500 {
+            update_patient(&mut patient, 0.01);
+        }
+        let heart = patient.get_organ::<Heart>().unwrap();
+        let recording = EcgRecording::from_heart(heart);
+        assert_eq!(recording.lead_names.len(), 12);
+        assert_eq!(recording.lead_names[3], "aVR");
+        assert!((recording.sampling_rate_hz - 250.0).abs() < 1e-6);
+        assert_eq!(recording.num_samples(), 1250);
+        assert!((recording.duration_s() - 5.0).abs() < 1e-6);
+        assert!((recording.start_time_s - 0.004).abs() < 1e-9);
+
+        let csv = recording.to_csv();
+        assert!(csv.contains("time_s,I,II,III,aVR,aVL,aVF,V1,V2,V3,V4,V5,V6\n"));
+        assert_eq!(csv.lines().filter(|l| !l.starts_with('#')).count(), 1251);
+        assert!(csv.contains("\n0.0040,"));
+
+        let record = recording.to_wfdb("sim001");
+        assert!(record.header.starts_with("sim001 12 250 1250\n"));
+        assert!(record.header.contains(" 16 200/mV 16 0 "));
+        assert_eq!(record.signal.len(), 2 * 12 * 1250);
+        let first = i16::from_le_bytes([record.signal[0], record.signal[1]]);
+        assert_eq!(first, (recording.leads_mv[0][0] * WFDB_GAIN_ADU_PER_MV).round() as i16);
+    }
+}
@@ -0,0 +1,376 @@
+//! Reinforcement-learning environment
+//!
+//! This module provides:
+//! - A gym-style adapter exposing the simulation as observation, action and
+//!   reward steps, so treatment policies can be trained directly against the
+//!   crate
+//! - Configurable observation spaces of vital signs and laboratory values
+//! - Configurable continuous action spaces of drug infusions, fluid boluses
+//!   and ventilator settings, each bounded like a gym `Box` dimension
+//! - Episodes ending when a stop condition is met (terminated) or when the
+//!   episode length runs out (truncated)
+//!
+//! Laboratory values are observed as the true blood values, without the
+//! specimen turnaround of `LabService`. Infusion rates and ventilator settings
+//! are applied only when the policy changes them, so the drug record holds one
+//! entry per titration rather than one per step.
+
+use crate::batch::StopCondition;
+use crate::fluids::IntakeRoute;
+use crate::labs::Analyte;
+use crate::organs::lungs::{Lungs, Ventilator};
+use crate::patient::{update_patient, Patient};
+use crate::validation::Measure;
+
+/// Quantity observed by the policy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Observation {
+    /// Vital sign or hemodynamic measure
+    Vital(Measure),
+    /// Laboratory value, read from the blood without turnaround
+    Lab(Analyte),
+}
+
+impl Observation {
+    /// Read the observation from a patient
+    ///
+    /// # Returns
+    /// The value, or NaN if the patient has no organ to measure
+    pub fn read(&self, patient: &Patient) -> f64 {
+        match self {
+            Observation::Vital(measure) => measure.measure(patient),
+            Observation::Lab(analyte) => analyte.read(patient),
+        }
+    }
+}
+
+/// Therapy a policy can set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Norepinephrine infusion rate (mcg/kg/min)
+    Norepinephrine,
+    /// Nitroprusside infusion rate (mcg/kg/min)
+    Nitroprusside,
+    /// Intravenous insulin infusion rate (units/h)
+    InsulinInfusion,
+    /// Intravenous crystalloid bolus given this step (mL, 0 for none)
+    FluidBolus,
+    /// Inspired oxygen fraction, on the ventilator if one is attached
+    Fio2,
+    /// Ventilator PEEP (cmH2O); intubates with default settings if needed
+    Peep,
+    /// Ventilator tidal volume (mL); intubates with default settings if needed
+    TidalVolume,
+    /// Ventilator rate (breaths/min); intubates with default settings if needed
+    RespiratoryRate,
+}
+
+impl Control {
+    /// Units of the control's value
+    pub fn units(&self) -> &'static str {
+        match self {
+            Control::Norepinephrine | Control::Nitroprusside => "mcg/kg/min",
+            Control::InsulinInfusion => "units/h",
+            Control::FluidBolus | Control::TidalVolume => "mL",
+            Control::Fio2 => "",
+            Control::Peep => "cmH2O",
+            Control::RespiratoryRate => "breaths/min",
+        }
+    }
+
+    /// Whether the value is given anew every step rather than held until changed
+    fn is_bolus(&self) -> bool {
+        matches!(self, Control::FluidBolus)
+    }
+
+    /// Apply a value to the patient
+    ///
+    /// # Arguments
+    /// * `patient` - Patient treated
+    /// * `value` - Setting, already within the dimension's bounds
+    fn apply(&self, patient: &mut Patient, value: f64) {
+        match self {
+            Control::Norepinephrine => patient.set_norepinephrine_infusion(value),
+            Control::Nitroprusside => patient.set_nitroprusside_infusion(value),
+            Control::InsulinInfusion => patient.set_insulin_infusion(value),
+            Control::FluidBolus => {
+                if value > 0.0 {
+                    patient.give_fluid(IntakeRoute::Intravenous, value);
+                }
+            }
+            Control::Fio2 => {
+                if let Some(lungs) = patient.get_organ_mut::<Lungs>() {
+                    match lungs.ventilator.as_mut() {
+                        Some(ventilator) => ventilator.fio2 = value.clamp(0.21, 1.0),
+                        None => lungs.set_fio2(value),
+                    }
+                }
+            }
+            Control::Peep | Control::TidalVolume | Control::RespiratoryRate => {
+                let Some(lungs) = patient.get_organ_mut::<Lungs>() else { return };
+                if lungs.ventilator.is_none() {
+                    lungs.attach_ventilator(Ventilator::default());
+                }
+                let Some(ventilator) = lungs.ventilator.as_mut() else { return };
+                match self {
+                    Control::Peep => ventilator.peep_cmh2o = value.clamp(0.0, 30.0),
+                    Control::TidalVolume => ventilator.tidal_volume_ml = value.max(0.0),
+                    _ => ventilator.rate_bpm = value.max(0.0),
+                }
+            }
+        }
+    }
+}
+
+/// One dimension of the action space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActionDimension {
+    /// Therapy set by this dimension
+    pub control: Control,
+    /// Lowest value; lower actions are clipped
+    pub low: f64,
+    /// Highest value; higher actions are clipped
+    pub high: f64,
+}
+
+impl ActionDimension {
+    /// Create an action dimension
+    ///
+    /// # Arguments
+    /// * `control` - Therapy set by this dimension
+    /// * `low` - Lowest value
+    /// * `high` - Highest value
+    pub fn new(control: Control, low: f64, high: f64) -> Self {
+        Self {
+            control,
+            low: low.min(high),
+            high: high.max(low),
+        }
+    }
+}
+
+/// Result of one environment step
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepResult {
+    /// Observation after the step, in observation-space order
+    pub observation: Vec<f64>,
+    /// Reward for the step
+    pub reward: f64,
+    /// Whether a stop condition ended the episode
+    pub terminated: bool,
+    /// Whether the episode length ran out
+    pub truncated: bool,
+    /// Name of the stop condition that ended the episode, if any
+    pub stopped_by: Option<String>,
+}
+
+/// Simulation exposed as a reinforcement-learning environment
+pub struct Environment {
+    /// Quantities observed, in order
+    pub observations: Vec<Observation>,
+    /// Therapies set by each action, in order
+    pub actions: Vec<ActionDimension>,
+    /// Simulated time between decisions (s)
+    pub decision_interval_s: f64,
+    /// Simulation time step (s)
+    pub time_step_s: f64,
+    /// Simulated length of an episode before it is truncated (s)
+    pub max_episode_s: f64,
+    /// Conditions terminating an episode, checked in order after every decision
+    pub stop_conditions: Vec<StopCondition>,
+    create: Box<dyn Fn(u64) -> Patient + Send + Sync>,
+    reward: Box<dyn Fn(&Patient) -> f64 + Send + Sync>,
+    patient: Option<Patient>,
+    /// Value last applied on each action dimension
+    applied: Vec<Option<f64>>,
+}
+
+impl Environment {
+    /// Create an environment without observations, actions or stop conditions
+    ///
+    /// Decisions are made every minute over episodes of up to six hours.
+    ///
+    /// # Arguments
+    /// * `create` - Creates the patient of an episode from a seed
+    /// * `reward` - Reward for the patient's state after a step
+    pub fn new(
+        create: impl Fn(u64) -> Patient + Send + Sync + 'static,
+        reward: impl Fn(&Patient) -> f64 + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            observations: Vec::new(),
+            actions: Vec::new(),
+            decision_interval_s: 60.0,
+            time_step_s: 1.0,
+            max_episode_s: 6.0 * 3600.0,
+            stop_conditions: Vec::new(),
+            create: Box::new(create),
+            reward: Box::new(reward),
+            patient: None,
+            applied: Vec::new(),
+        }
+    }
+
+    /// Reward for holding a measure near a target
+    ///
+    /// # Arguments
+    /// * `measure` - Quantity rewarded
+    /// * `target` - Target value
+    /// * `scale` - Deviation costing a reward of 1
+    ///
+    /// # Returns
+    /// Reward of zero at the target, falling linearly with the deviation, and
+    /// -1 when the measure cannot be read
+    pub fn target_reward(measure: Measure, target: f64, scale: f64) -> impl Fn(&Patient) -> f64 + Send + Sync {
+        move |patient| {
+            let value = measure.measure(patient);
+            if value.is_finite() {
+                -(value - target).abs() / scale.max(f64::EPSILON)
+            } else {
+                -1.0
+            }
+        }
+    }
+
+    /// Add an observed quantity
+    pub fn observe(&mut self, observation: Observation) {
+        self.observations.push(observation);
+    }
+
+    /// Add an action dimension
+    pub fn add_action(&mut self, dimension: ActionDimension) {
+        self.actions.push(dimension);
+    }
+
+    /// Add a condition terminating an episode
+    pub fn add_stop_condition(&mut self, condition: StopCondition) {
+        self.stop_conditions.push(condition);
+    }
+
+    /// Start a new episode
+    ///
+    /// # Arguments
+    /// * `seed` - Seed passed to the patient factory; the same seed reproduces the episode
+    ///
+    /// # Returns
+    /// The first observation
+    pub fn reset(&mut self, seed: u64) -> Vec<f64> {
+        let patient = (self.create)(seed);
+        self.applied = vec![None; self.actions.len()];
+        let observation = self.read(&patient);
+        self.patient = Some(patient);
+        observation
+    }
+
+    /// Apply an action and advance the patient one decision interval
+    ///
+    /// An episode is started with seed 0 if none is running. Values are
+    /// clipped to their dimension's bounds; missing values leave the therapy
+    /// unchanged and extra values are ignored.
+    ///
+    /// # Arguments
+    /// * `action` - Value per action dimension, in order
+    pub fn step(&mut self, action: &[f64]) -> StepResult {
+        if self.patient.is_none() {
+            self.reset(0);
+        }
+        self.applied.resize(self.actions.len(), None);
+        let Some(mut patient) = self.patient.take() else { unreachable!() };
+        for ((dimension, applied), &value) in self.actions.iter().zip(self.applied.iter_mut()).zip(action) {
+            if !value.is_finite() {
+                continue;
+            }
+            let value = value.clamp(dimension.low, dimension.high);
+            if dimension.control.is_bolus() || *applied != Some(value) {
+                dimension.control.apply(&mut patient, value);
+                *applied = Some(value);
+            }
+        }
+
+        let step = self.time_step_s.max(1e-3);
+        for _ in 0..(self.decision_interval_s / step).round().max(1.0) as usize {
+            update_patient(&mut patient, step);
+        }
+        let stopped_by = self
+            .stop_conditions
+            .iter()
+            .find(|condition| condition.is_met(&patient))
+            .map(|condition| condition.name.clone());
+        let result = StepResult {
+            observation: self.read(&patient),
+            reward: (self.reward)(&patient),
+            terminated: stopped_by.is_some(),
+            truncated: stopped_by.is_none() && patient.sim_time() + 1e-9 >= self.max_episode_s,
+            stopped_by,
+        };
+        self.patient = Some(patient);
+        result
+    }
+
+    /// Patient of the current episode
+    pub fn patient(&self) -> Option<&Patient> {
+        self.patient.as_ref()
+    }
+
+    /// Read the observation space from a patient
+    fn read(&self, patient: &Patient) -> Vec<f64> {
+        self.observations.iter().map(|observation| observation.read(patient)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::initialize_patient;
+    use crate::variability::Variability;
+
+    fn hemorrhage_environment() -> Environment {
+        let mut environment = Environment::new(
+            |seed| {
+                let mut patient = initialize_patient(seed as i32, 3);
+                patient.variability = Variability::seeded(seed, 1.0);
+                patient.hemorrhage(0.5 * patient.fluids.reference_blood_volume_ml);
+                patient
+            },
+            Environment::target_reward(Measure::MeanArterialPressure, 90.0, 10.0),
+        );
+        environment.observe(Observation::Vital(Measure::MeanArterialPressure));
+        environment.observe(Observation::Vital(Measure::HeartRate));
+        environment.observe(Observation::Lab(Analyte::Lactate));
+        environment.add_action(ActionDimension::new(Control::Norepinephrine, 0.0, 0.5));
+        environment.add_action(ActionDimension::new(Control::FluidBolus, 0.0, 500.0));
+        environment.max_episode_s = 600.0;
+        environment
+    }
+
+    #[test]
+    fn test_treatment_earns_reward_until_truncated() {
+        let mut untreated = hemorrhage_environment();
+        let mut treated = hemorrhage_environment();
+        assert_eq!(untreated.reset(7), treated.reset(7));
+        assert_eq!(treated.reset(7).len(), 3);
+
+        let mut untreated_return = 0.0;
+        let mut treated_return = 0.0;
+        let mut last = None;
+        for _ in 0..10 {
+            untreated_return += untreated.step(&[0.0, 0.0]).reward;
+            // Out-of-bounds values are clipped
+            let result = treated.step(&[0.1, 1000.0]);
+            treated_return += result.reward;
+            last = Some(result);
+        }
+        let last = last.unwrap();
+        assert!(treated_return > untreated_return + 5.0, "{} vs {}", treated_return, untreated_return);
+        assert!(last.truncated && !last.terminated);
+        let patient = treated.patient().unwrap();
+        assert_eq!(patient.doses.iter().filter(|dose| dose.medication == "Norepinephrine").count(), 1);
+        assert!((patient.fluid_ledger.cumulative.intake_for(IntakeRoute::Intravenous) - 5000.0).abs() < 1e-6);
+
+        treated.add_stop_condition(StopCondition::above(Measure::MeanArterialPressure, 60.0));
+        treated.reset(7);
+        let result = treated.step(&[0.5, 500.0]);
+        assert!(result.terminated && !result.truncated);
+        assert_eq!(result.stopped_by.as_deref(), Some("MeanArterialPressure > 60"));
+    }
+}
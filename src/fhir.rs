@@ -0,0 +1,445 @@
+//! FHIR R4 export
+//!
+//! This module provides (with the `fhir` feature):
+//! - Laboratory panels as DiagnosticReport resources with their results as
+//!   contained Observation resources, coded in LOINC with UCUM units, reference
+//!   ranges and interpretation flags; either from reported lab orders (with
+//!   analytic noise) or read exactly from the blood composition
+//! - Vital signs as Observation resources in the vital-signs category
+//! - Emergent conditions (ST-elevation myocardial infarction, acute kidney
+//!   injury with its KDIGO stage) as Condition resources
+//! - A collection Bundle of all of the above
+//!
+//! Resources are serialized as JSON text. Simulation time is mapped to
+//! wall-clock time from a configurable start.
+
+use std::fmt;
+
+use crate::labs::{LabFlag, LabPanel, LabReport, LabResult};
+use crate::organs::heart::{CoronaryTerritory, Heart};
+use crate::organs::kidneys::Kidneys;
+use crate::organs::lungs::Lungs;
+use crate::patient::Patient;
+use crate::timestamp::{CivilTime, DEFAULT_START_UNIX_S};
+
+/// LOINC code system
+const LOINC: &str = "http://loinc.org";
+
+/// SNOMED CT code system
+const SNOMED: &str = "http://snomed.info/sct";
+
+/// UCUM unit system
+const UCUM: &str = "http://unitsofmeasure.org";
+
+/// Minimal JSON value, serialized with `Display`
+#[derive(Debug, Clone)]
+enum Json {
+    Number(f64),
+    Text(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    fn text(value: impl Into<String>) -> Json {
+        Json::Text(value.into())
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Number(value) if value.is_finite() => write!(f, "{}", value),
+            Json::Number(_) => write!(f, "null"),
+            Json::Text(text) => {
+                write!(f, "\"")?;
+                for c in text.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "\"{}\":{}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// CodeableConcept with one coding
+fn concept(system: &str, code: &str, display: &str) -> Json {
+    Json::Object(vec![
+        (
+            "coding",
+            Json::Array(vec![Json::Object(vec![
+                ("system", Json::text(system)),
+                ("code", Json::text(code)),
+                ("display", Json::text(display)),
+            ])]),
+        ),
+        ("text", Json::text(display)),
+    ])
+}
+
+/// Observation category (laboratory or vital-signs)
+fn category(code: &str) -> Json {
+    Json::Array(vec![concept("http://terminology.hl7.org/CodeSystem/observation-category", code, code)])
+}
+
+/// Quantity with UCUM units
+fn quantity(value: f64, unit: &str) -> Json {
+    Json::Object(vec![
+        ("value", Json::Number(value)),
+        ("unit", Json::text(unit)),
+        ("system", Json::text(UCUM)),
+        ("code", Json::text(unit)),
+    ])
+}
+
+/// HL7 interpretation code of a flag
+fn interpretation(flag: LabFlag) -> Json {
+    let (code, display) = match flag {
+        LabFlag::Normal => ("N", "Normal"),
+        LabFlag::Low => ("L", "Low"),
+        LabFlag::High => ("H", "High"),
+        LabFlag::CriticalLow => ("LL", "Critical low"),
+        LabFlag::CriticalHigh => ("HH", "Critical high"),
+    };
+    Json::Array(vec![concept(
+        "http://terminology.hl7.org/CodeSystem/v3-ObservationInterpretation",
+        code,
+        display,
+    )])
+}
+
+/// Exporter of a patient's simulation output as FHIR R4 resources
+#[derive(Debug, Clone, PartialEq)]
+pub struct FhirExport {
+    /// Reference to the Patient resource the output belongs to
+    pub patient_reference: String,
+    /// Wall-clock time of simulation time zero (Unix seconds)
+    pub start_unix_s: i64,
+}
+
+impl FhirExport {
+    /// Create an exporter referring to `Patient/<id>`, starting at the
+    /// patient's clock anchor or 2024-01-01
+    pub fn new(patient: &Patient) -> Self {
+        Self {
+            patient_reference: format!("Patient/{}", patient.id),
+            start_unix_s: patient.clock.start_unix_s.unwrap_or(DEFAULT_START_UNIX_S),
+        }
+    }
+
+    /// Wall-clock instant of a simulation time
+    ///
+    /// # Arguments
+    /// * `time_s` - Simulation time (s)
+    pub fn instant(&self, time_s: f64) -> String {
+        CivilTime::from_unix(self.start_unix_s + time_s.floor() as i64).iso8601()
+    }
+
+    fn subject(&self) -> Json {
+        Json::Object(vec![("reference", Json::text(self.patient_reference.clone()))])
+    }
+
+    /// Observation of one laboratory result
+    fn lab_observation(&self, id: String, result: &LabResult, time_s: f64) -> Json {
+        let (code, display) = result.analyte.loinc();
+        let unit = result.analyte.ucum_units();
+        let (low, high) = result.reference_range;
+        Json::Object(vec![
+            ("resourceType", Json::text("Observation")),
+            ("id", Json::Text(id)),
+            ("status", Json::text("final")),
+            ("category", category("laboratory")),
+            ("code", concept(LOINC, code, display)),
+            ("subject", self.subject()),
+            ("effectiveDateTime", Json::Text(self.instant(time_s))),
+            ("valueQuantity", quantity(result.value, unit)),
+            ("interpretation", interpretation(result.flag)),
+            (
+                "referenceRange",
+                Json::Array(vec![Json::Object(vec![("low", quantity(low, unit)), ("high", quantity(high, unit))])]),
+            ),
+        ])
+    }
+
+    /// DiagnosticReport with its results as contained Observations
+    fn diagnostic_report(&self, id: String, panel: LabPanel, results: &[LabResult], drawn_s: f64, issued_s: f64) -> Json {
+        let (code, display) = panel.loinc();
+        let observations: Vec<Json> = results
+            .iter()
+            .enumerate()
+            .map(|(i, result)| self.lab_observation(format!("result-{}", i + 1), result, drawn_s))
+            .collect();
+        let references = (1..=results.len())
+            .map(|i| Json::Object(vec![("reference", Json::Text(format!("#result-{}", i)))]))
+            .collect();
+        Json::Object(vec![
+            ("resourceType", Json::text("DiagnosticReport")),
+            ("id", Json::Text(id)),
+            ("contained", Json::Array(observations)),
+            ("status", Json::text("final")),
+            (
+                "category",
+                Json::Array(vec![concept("http://terminology.hl7.org/CodeSystem/v2-0074", "LAB", "Laboratory")]),
+            ),
+            ("code", concept(LOINC, code, display)),
+            ("subject", self.subject()),
+            ("effectiveDateTime", Json::Text(self.instant(drawn_s))),
+            ("issued", Json::Text(self.instant(issued_s))),
+            ("result", Json::Array(references)),
+        ])
+    }
+
+    /// Reported lab order as a DiagnosticReport with contained Observations
+    pub fn lab_report(&self, report: &LabReport) -> String {
+        self.lab_report_json(report).to_string()
+    }
+
+    fn lab_report_json(&self, report: &LabReport) -> Json {
+        self.diagnostic_report(
+            format!("lab-{}", report.id),
+            report.panel,
+            &report.results,
+            report.ordered_at_s,
+            report.reported_at_s,
+        )
+    }
+
+    /// Panel read exactly from the patient's blood composition as a DiagnosticReport
+    ///
+    /// # Arguments
+    /// * `patient` - Patient whose blood is reported
+    /// * `panel` - Panel to report
+    /// * `time_s` - Simulation time of the reading (s)
+    pub fn blood_panel(&self, patient: &Patient, panel: LabPanel, time_s: f64) -> String {
+        self.blood_panel_json(patient, panel, time_s).to_string()
+    }
+
+    fn blood_panel_json(&self, patient: &Patient, panel: LabPanel, time_s: f64) -> Json {
+        let results: Vec<LabResult> = panel
+            .analytes()
+            .iter()
+            .map(|&analyte| LabResult::new(analyte, analyte.read(patient), &patient.demographics))
+            .collect();
+        let id = format!("{:?}-{}", panel, time_s.floor() as i64).to_lowercase();
+        self.diagnostic_report(id, panel, &results, time_s, time_s)
+    }
+
+    /// Vital signs as Observations: heart rate, respiratory rate, SpO2,
+    /// temperature and blood pressure (systolic and diastolic components)
+    ///
+    /// # Arguments
+    /// * `patient` - Patient whose vital signs are reported
+    /// * `time_s` - Simulation time of the reading (s)
+    pub fn vital_signs(&self, patient: &Patient, time_s: f64) -> Vec<String> {
+        self.vital_signs_json(patient, time_s).iter().map(Json::to_string).collect()
+    }
+
+    fn vital_signs_json(&self, patient: &Patient, time_s: f64) -> Vec<Json> {
+        let suffix = time_s.floor() as i64;
+        let vital = |id: &str, code: &str, display: &str, value: Json| {
+            Json::Object(vec![
+                ("resourceType", Json::text("Observation")),
+                ("id", Json::Text(format!("{}-{}", id, suffix))),
+                ("status", Json::text("final")),
+                ("category", category("vital-signs")),
+                ("code", concept(LOINC, code, display)),
+                ("subject", self.subject()),
+                ("effectiveDateTime", Json::Text(self.instant(time_s))),
+                ("valueQuantity", value),
+            ])
+        };
+        let mut vitals = Vec::new();
+        if let Some(heart) = patient.get_organ::<Heart>() {
+            vitals.push(vital("heart-rate", "8867-4", "Heart rate", quantity(heart.heart_rate_bpm.round(), "/min")));
+        }
+        if let Some(lungs) = patient.get_organ::<Lungs>() {
+            let rate = quantity(lungs.respiration_rate_bpm.round(), "/min");
+            vitals.push(vital("respiratory-rate", "9279-1", "Respiratory rate", rate));
+        }
+        let spo2 = quantity(patient.blood.gases.sao2_percent.round(), "%");
+        vitals.push(vital("oxygen-saturation", "59408-5", "Oxygen saturation in Arterial blood by Pulse oximetry", spo2));
+        let temperature = quantity((patient.oxygen_transport.body_temperature_c * 10.0).round() / 10.0, "Cel");
+        vitals.push(vital("body-temperature", "8310-5", "Body temperature", temperature));
+
+        let component = |code: &str, display: &str, value: f64| {
+            Json::Object(vec![
+                ("code", concept(LOINC, code, display)),
+                ("valueQuantity", quantity(value.round(), "mm[Hg]")),
+            ])
+        };
+        vitals.push(Json::Object(vec![
+            ("resourceType", Json::text("Observation")),
+            ("id", Json::Text(format!("blood-pressure-{}", suffix))),
+            ("status", Json::text("final")),
+            ("category", category("vital-signs")),
+            ("code", concept(LOINC, "85354-9", "Blood pressure panel with all children optional")),
+            ("subject", self.subject()),
+            ("effectiveDateTime", Json::Text(self.instant(time_s))),
+            (
+                "component",
+                Json::Array(vec![
+                    component("8480-6", "Systolic blood pressure", patient.blood.blood_pressure_systolic),
+                    component("8462-4", "Diastolic blood pressure", patient.blood.blood_pressure_diastolic),
+                ]),
+            ),
+        ]));
+        vitals
+    }
+
+    /// Emergent conditions as Condition resources: ST-elevation myocardial
+    /// infarction of each infarcted coronary territory, and acute kidney injury
+    /// with its KDIGO stage
+    ///
+    /// # Arguments
+    /// * `patient` - Patient whose conditions are reported
+    /// * `time_s` - Simulation time of the assessment (s)
+    pub fn conditions(&self, patient: &Patient, time_s: f64) -> Vec<String> {
+        self.conditions_json(patient, time_s).iter().map(Json::to_string).collect()
+    }
+
+    fn conditions_json(&self, patient: &Patient, time_s: f64) -> Vec<Json> {
+        let condition = |id: String, code: Json, extra: Vec<(&'static str, Json)>| {
+            let mut fields = vec![
+                ("resourceType", Json::text("Condition")),
+                ("id", Json::Text(id)),
+                (
+                    "clinicalStatus",
+                    concept("http://terminology.hl7.org/CodeSystem/condition-clinical", "active", "Active"),
+                ),
+                (
+                    "verificationStatus",
+                    concept("http://terminology.hl7.org/CodeSystem/condition-ver-status", "confirmed", "Confirmed"),
+                ),
+                ("code", code),
+                ("subject", self.subject()),
+                ("recordedDate", Json::Text(self.instant(time_s))),
+            ];
+            fields.extend(extra);
+            Json::Object(fields)
+        };
+
+        let mut conditions = Vec::new();
+        if let Some(heart) = patient.get_organ::<Heart>() {
+            for territory in heart.infarcted_territories() {
+                let (wall, artery) = match territory {
+                    CoronaryTerritory::LeftAnteriorDescending => ("Anterior", "left anterior descending artery"),
+                    CoronaryTerritory::LeftCircumflex => ("Lateral", "left circumflex artery"),
+                    CoronaryTerritory::RightCoronary => ("Inferior", "right coronary artery"),
+                };
+                let id = format!("stemi-{}", wall.to_lowercase());
+                let code = concept(SNOMED, "401303003", "Acute ST segment elevation myocardial infarction");
+                let note = Json::Array(vec![Json::Object(vec![(
+                    "text",
+                    Json::Text(format!("{} STEMI, {} territory", wall, artery)),
+                )])]);
+                conditions.push(condition(id, code, vec![("note", note)]));
+            }
+        }
+        if let Some(kidneys) = patient.get_organ::<Kidneys>() {
+            let stage = kidneys.aki_stage();
+            if stage > 0 {
+                let code = concept(SNOMED, "14669001", "Acute kidney injury");
+                let summary = Json::Object(vec![("text", Json::Text(format!("KDIGO stage {}", stage)))]);
+                let stage = Json::Array(vec![Json::Object(vec![("summary", summary)])]);
+                conditions.push(condition("aki".to_string(), code, vec![("stage", stage)]));
+            }
+        }
+        conditions
+    }
+
+    /// Collection Bundle of the patient's current blood panels (CBC, CMP, ABG,
+    /// coagulation), released lab reports, vital signs and conditions
+    ///
+    /// # Arguments
+    /// * `patient` - Patient to export
+    /// * `time_s` - Simulation time of the export (s)
+    pub fn bundle(&self, patient: &Patient, time_s: f64) -> String {
+        let mut resources: Vec<Json> = [LabPanel::Cbc, LabPanel::Cmp, LabPanel::Abg, LabPanel::Coagulation]
+            .into_iter()
+            .map(|panel| self.blood_panel_json(patient, panel, time_s))
+            .collect();
+        resources.extend(patient.labs.reports().iter().map(|report| self.lab_report_json(report)));
+        resources.extend(self.vital_signs_json(patient, time_s));
+        resources.extend(self.conditions_json(patient, time_s));
+
+        let entries = resources
+            .into_iter()
+            .map(|resource| Json::Object(vec![("resource", resource)]))
+            .collect();
+        Json::Object(vec![
+            ("resourceType", Json::text("Bundle")),
+            ("type", Json::text("collection")),
+            ("timestamp", Json::Text(self.instant(time_s))),
+            ("entry", Json::Array(entries)),
+        ])
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_export_labs_vitals_and_conditions() {
+        let mut patient = initialize_patient(7, 12);
+        patient.labs.noise = false;
+        let id = patient.order_labs(LabPanel::Troponin);
+        if let Some(heart) = patient.get_organ_mut::<Heart>() {
+            heart.set_territory_contractility(CoronaryTerritory::RightCoronary, 0.0);
+        }
+        for _ in 0..3600 {
+            update_patient(&mut patient, 1.0);
+        }
+        let export = FhirExport::new(&patient);
+
+        let report = export.lab_report(patient.labs.report(id).unwrap());
+        assert!(report.starts_with("{\"resourceType\":\"DiagnosticReport\",\"id\":\"lab-1\""));
+        assert!(report.contains("\"code\":\"67151-1\""));
+        assert!(report.contains("\"issued\":\"2024-01-01T01:00:00Z\""));
+
+        let cbc = export.blood_panel(&patient, LabPanel::Cbc, 3600.0);
+        assert!(cbc.contains("\"code\":\"718-7\""));
+        assert!(cbc.contains("\"unit\":\"g/dL\""));
+        assert_eq!(cbc.matches("\"resourceType\":\"Observation\"").count(), 5);
+
+        let vitals = export.vital_signs(&patient, 3600.0);
+        assert_eq!(vitals.len(), 5);
+        assert!(vitals.iter().any(|v| v.contains("\"code\":\"8480-6\"")));
+
+        let conditions = export.conditions(&patient, 3600.0);
+        assert_eq!(conditions.len(), 1);
+        assert!(conditions[0].contains("Inferior STEMI, right coronary artery territory"));
+
+        let bundle = export.bundle(&patient, 3600.0);
+        assert!(bundle.contains("\"type\":\"collection\""));
+        assert_eq!(bundle.matches("\"resourceType\":\"DiagnosticReport\"").count(), 5);
+    }
+}
@@ -0,0 +1,153 @@
+//! Simulation fidelity
+//!
+//! This module provides:
+//! - A full-fidelity level that updates every organ each step and samples the
+//!   EKG, EEG and capnography waveforms
+//! - A fast level for background patients: the same organ models are updated
+//!   once per coarse step instead of at every call, and no waveforms are
+//!   generated. Patients of both levels share one API, so they can run side by
+//!   side in one application
+//! - Per-organ levels switchable mid-simulation, so only the organs a scenario
+//!   focuses on are updated at every step
+//! - Step-size invariance: a step longer than the level's largest integration
+//!   step is split into equal substeps
+//!
+//! # Accuracy
+//!
+//! At full fidelity any step of up to [`MAX_INTEGRATION_STEP_S`] is integrated
+//! as given, and longer steps are split, so a run at 10 s or 10 min steps
+//! tracks the same run at 0.1 s steps: vital signs and slow quantities
+//! (troponin, bacterial load) agree within 0.1%. Chance events happen at a
+//! rate per second, not per step. At the fast level the coarse
+//! step itself is the contract; longer steps are split into coarse steps.
+
+/// Coarse integration step of the fast level (s)
+pub const FAST_INTEGRATION_STEP_S: f64 = 10.0;
+
+/// Largest step integrated at once at full fidelity; longer steps are split (s)
+pub const MAX_INTEGRATION_STEP_S: f64 = 1.0;
+
+/// Level of detail a patient is simulated at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fidelity {
+    /// Every organ updated each step, with waveforms
+    #[default]
+    Full,
+    /// Organs updated in coarse steps, without waveforms; vital signs hold
+    /// between steps
+    Fast,
+}
+
+impl Fidelity {
+    /// Time the patient accumulates before its organs are updated (s)
+    pub fn integration_step_s(&self) -> f64 {
+        match self {
+            Fidelity::Full => 0.0,
+            Fidelity::Fast => FAST_INTEGRATION_STEP_S,
+        }
+    }
+
+    /// EKG, EEG and capnography waveforms are sampled
+    pub fn generates_waveforms(&self) -> bool {
+        *self == Fidelity::Full
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::brain::Brain;
+    use crate::organs::heart::Heart;
+    use crate::organs::heart::CoronaryTerritory;
+    use crate::patient::{initialize_patient, update_patient, Patient};
+    use crate::sepsis::BundleGuideline;
+
+    #[test]
+    fn test_fast_patient_tracks_full_patient() {
+        let mut full = initialize_patient(1, 12);
+        let mut fast = initialize_patient(2, 12);
+        fast.fidelity = Fidelity::Fast;
+        for patient in [&mut full, &mut fast] {
+            for _ in 0..600 {
+                update_patient(patient, 0.1);
+            }
+            let volume_ml = 0.3 * patient.fluids.reference_blood_volume_ml;
+            patient.hemorrhage(volume_ml);
+            for _ in 0..3000 {
+                update_patient(patient, 0.1);
+            }
+        }
+
+        let heart_rate = |p: &crate::patient::Patient| p.get_organ::<Heart>().unwrap().heart_rate_bpm;
+        assert!(heart_rate(&fast) > 100.0);
+        assert!((heart_rate(&fast) - heart_rate(&full)).abs() < 2.0);
+        let map = |p: &crate::patient::Patient| p.blood.get_mean_arterial_pressure();
+        assert!((map(&fast) - map(&full)).abs() < 2.0);
+        assert!(fast.get_organ::<Heart>().unwrap().ekg_leads[0].is_empty());
+        assert!(!full.get_organ::<Heart>().unwrap().ekg_leads[0].is_empty());
+    }
+
+    #[test]
+    fn test_long_steps_match_short_steps() {
+        // An anterior infarct and a fast-growing infection, run at short and long steps
+        let run = |step_s: f64| {
+            let mut patient = initialize_patient(1, 3);
+            patient.start_infection("Lung", 4.0, BundleGuideline::HourOne);
+            if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                heart.set_territory_contractility(CoronaryTerritory::LeftAnteriorDescending, 0.2);
+            }
+            for _ in 0..(1200.0 / step_s).round() as usize {
+                update_patient(&mut patient, step_s);
+            }
+            patient
+        };
+        let short = run(0.1);
+        let heart = |p: &Patient| p.get_organ::<Heart>().unwrap().heart_rate_bpm;
+        let map = |p: &Patient| p.blood.get_mean_arterial_pressure();
+        let troponin = |p: &Patient| p.blood.chemistry.troponin_ng_l;
+        let load = |p: &Patient| p.sepsis.as_ref().unwrap().bacterial_load;
+        assert!(troponin(&short) > 100.0);
+        for step_s in [10.0, 600.0] {
+            let long = run(step_s);
+            for measure in [heart, map, troponin, load] {
+                let (a, b) = (measure(&long), measure(&short));
+                assert!((a - b).abs() <= 1e-3 * b.abs(), "{} s step: {} vs {}", step_s, a, b);
+            }
+        }
+
+        // Steps that cannot be integrated leave the patient as it was
+        let mut patient = short;
+        let (time_before, map_before) = (patient.sim_time(), map(&patient));
+        for step_s in [f64::NAN, f64::INFINITY, -10.0] {
+            update_patient(&mut patient, step_s);
+        }
+        assert_eq!(patient.sim_time(), time_before);
+        assert_eq!(map(&patient), map_before);
+        update_patient(&mut patient, 0.1);
+        assert!(map(&patient).is_finite());
+    }
+
+    #[test]
+    fn test_detailed_heart_in_fast_patient() {
+        let mut patient = initialize_patient(1, 12);
+        patient.fidelity = Fidelity::Fast;
+        patient.set_organ_fidelity("Heart", Fidelity::Full);
+        assert_eq!(patient.organ_fidelity("Heart"), Fidelity::Full);
+        assert_eq!(patient.organ_fidelity("Brain"), Fidelity::Fast);
+        for _ in 0..50 {
+            update_patient(&mut patient, 0.1);
+        }
+        // The heart runs every step with its EKG; the brain waits for a coarse step
+        assert_eq!(patient.get_organ::<Heart>().unwrap().ekg_leads[0].len(), 1250);
+        assert!(patient.get_organ::<Brain>().unwrap().eeg_waveform.is_empty());
+
+        // Switching the brain to full detail brings it up to date and starts its EEG
+        patient.set_organ_fidelity("Brain", Fidelity::Full);
+        for _ in 0..10 {
+            update_patient(&mut patient, 0.1);
+        }
+        assert_eq!(patient.get_organ::<Brain>().unwrap().eeg_waveform.len(), 256);
+        patient.clear_organ_fidelity("Heart");
+        assert_eq!(patient.organ_fidelity("Heart"), Fidelity::Fast);
+    }
+}
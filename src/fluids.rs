@@ -0,0 +1,478 @@
+//! Body fluid compartments and intake/output (I/O) ledger
+//!
+//! This module provides:
+//! - A three-compartment body water model (intracellular, interstitial, plasma)
+//! - Circulating red cell volume for blood volume and hematocrit
+//! - Starling-style exchange between plasma and interstitium, with venous
+//!   congestion driving peripheral edema
+//! - A cumulative fluid intake/output ledger with per-day totals
+//! - Daily weights derived from total body water
+
+use std::collections::HashMap;
+
+/// Seconds in one simulated day
+pub const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Normal hematocrit used to size the red cell volume (fraction)
+const NORMAL_HEMATOCRIT: f64 = 0.42;
+
+/// Central venous pressure above which capillary filtration outpaces lymph flow (mmHg)
+const EDEMA_VENOUS_PRESSURE_MMHG: f64 = 10.0;
+
+/// Route by which fluid enters the body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntakeRoute {
+    Oral,
+    Enteral,
+    Intravenous,
+    BloodProduct,
+}
+
+/// Route by which fluid leaves the body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputRoute {
+    Urine,
+    Stool,
+    Emesis,
+    Drain,
+    Blood,
+    Insensible,
+}
+
+/// Body water distributed across physiological compartments
+#[derive(Debug, Clone)]
+pub struct FluidCompartments {
+    /// Intracellular fluid volume (mL) - normally ~2/3 of total body water
+    pub intracellular_ml: f64,
+    /// Interstitial fluid volume (mL) - normally ~3/4 of extracellular water
+    pub interstitial_ml: f64,
+    /// Plasma volume (mL) - normally ~1/4 of extracellular water
+    pub plasma_ml: f64,
+    /// Circulating red cell volume (mL)
+    pub red_cell_ml: f64,
+    /// Fat-free dry mass plus fat (kg) - everything that isn't water or red cells
+    pub dry_mass_kg: f64,
+    /// Blood volume at creation, the patient's euvolemic reference (mL)
+    pub reference_blood_volume_ml: f64,
+    /// Interstitial volume at creation, the patient's edema-free reference (mL)
+    pub reference_interstitial_ml: f64,
+    /// Plasma removed with whole blood since creation (mL)
+    pub plasma_lost_with_blood_ml: f64,
+}
+
+impl FluidCompartments {
+    /// Create compartments for a patient of the given weight
+    ///
+    /// # Arguments
+    /// * `weight_kg` - Body weight in kilograms
+    ///
+    /// # Returns
+    /// Compartments with total body water at 60% of body weight
+    pub fn new(weight_kg: f64) -> Self {
+        let total_body_water_ml = weight_kg * 0.6 * 1000.0;
+        let extracellular_ml = total_body_water_ml / 3.0;
+        let plasma_ml = extracellular_ml * 0.25;
+        let red_cell_ml = plasma_ml * NORMAL_HEMATOCRIT / (1.0 - NORMAL_HEMATOCRIT);
+
+        Self {
+            intracellular_ml: total_body_water_ml - extracellular_ml,
+            interstitial_ml: extracellular_ml * 0.75,
+            plasma_ml,
+            red_cell_ml,
+            dry_mass_kg: weight_kg * 0.4 - red_cell_ml / 1000.0,
+            reference_blood_volume_ml: plasma_ml + red_cell_ml,
+            reference_interstitial_ml: extracellular_ml * 0.75,
+            plasma_lost_with_blood_ml: 0.0,
+        }
+    }
+
+    /// Get total body water (mL)
+    pub fn total_body_water_ml(&self) -> f64 {
+        self.intracellular_ml + self.interstitial_ml + self.plasma_ml
+    }
+
+    /// Get extracellular fluid volume (mL)
+    pub fn extracellular_ml(&self) -> f64 {
+        self.interstitial_ml + self.plasma_ml
+    }
+
+    /// Get circulating blood volume (mL)
+    pub fn blood_volume_ml(&self) -> f64 {
+        self.plasma_ml + self.red_cell_ml
+    }
+
+    /// Get hematocrit (fraction)
+    pub fn hematocrit(&self) -> f64 {
+        let blood_volume = self.blood_volume_ml();
+        if blood_volume > 0.0 {
+            self.red_cell_ml / blood_volume
+        } else {
+            0.0
+        }
+    }
+
+    /// Get body weight derived from dry mass, total body water and red cells (kg)
+    pub fn body_weight_kg(&self) -> f64 {
+        // 1 mL of body water weighs ~1 g
+        self.dry_mass_kg + (self.total_body_water_ml() + self.red_cell_ml) / 1000.0
+    }
+
+    /// Add fluid directly to the plasma compartment
+    pub fn add_plasma(&mut self, volume_ml: f64) {
+        self.plasma_ml += volume_ml.max(0.0);
+    }
+
+    /// Remove fluid from the plasma compartment
+    ///
+    /// # Returns
+    /// The volume actually removed (mL)
+    pub fn remove_plasma(&mut self, volume_ml: f64) -> f64 {
+        let removed = volume_ml.clamp(0.0, self.plasma_ml);
+        self.plasma_ml -= removed;
+        removed
+    }
+
+    /// Remove whole blood (plasma and red cells at the current hematocrit)
+    ///
+    /// # Returns
+    /// The volume actually removed (mL)
+    pub fn remove_blood(&mut self, volume_ml: f64) -> f64 {
+        let hematocrit = self.hematocrit();
+        let removed = volume_ml.clamp(0.0, self.blood_volume_ml());
+        self.red_cell_ml -= removed * hematocrit;
+        self.plasma_ml -= removed * (1.0 - hematocrit);
+        self.plasma_lost_with_blood_ml += removed * (1.0 - hematocrit);
+        removed
+    }
+
+    /// Add blood or a blood product
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Volume given in mL
+    /// * `hematocrit` - Red cell fraction of the product (packed cells ~0.6)
+    pub fn add_blood(&mut self, volume_ml: f64, hematocrit: f64) {
+        let volume_ml = volume_ml.max(0.0);
+        let hematocrit = hematocrit.clamp(0.0, 1.0);
+        self.red_cell_ml += volume_ml * hematocrit;
+        self.plasma_ml += volume_ml * (1.0 - hematocrit);
+    }
+
+    /// Destroy circulating red cells (hemolysis)
+    ///
+    /// # Returns
+    /// The red cell volume actually destroyed (mL)
+    pub fn destroy_red_cells(&mut self, volume_ml: f64) -> f64 {
+        let destroyed = volume_ml.clamp(0.0, self.red_cell_ml);
+        self.red_cell_ml -= destroyed;
+        destroyed
+    }
+
+    /// Scale every compartment and the dry mass with body size (growth)
+    ///
+    /// # Arguments
+    /// * `factor` - Ratio of new to current body size
+    pub fn scale(&mut self, factor: f64) {
+        let factor = factor.max(0.0);
+        self.intracellular_ml *= factor;
+        self.interstitial_ml *= factor;
+        self.plasma_ml *= factor;
+        self.red_cell_ml *= factor;
+        self.dry_mass_kg *= factor;
+        self.reference_blood_volume_ml *= factor;
+        self.reference_interstitial_ml *= factor;
+    }
+
+    /// Interstitial fluid beyond the edema-free reference (mL)
+    pub fn peripheral_edema_ml(&self) -> f64 {
+        (self.interstitial_ml - self.reference_interstitial_ml).max(0.0)
+    }
+
+    /// Exchange fluid between compartments
+    ///
+    /// # Arguments
+    /// * `albumin_g_dl` - Plasma albumin (oncotic pressure)
+    /// * `capillary_permeability` - Capillary leakiness (0.0-1.0, normal 0.5)
+    /// * `venous_pressure_mmhg` - Central venous pressure transmitted back to the capillaries
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, albumin_g_dl: f64, capillary_permeability: f64, venous_pressure_mmhg: f64, delta_time_s: f64) {
+        // Starling forces: low albumin, leaky capillaries and raised venous pressure
+        // shift the interstitial:plasma equilibrium ratio above the normal 3:1
+        let oncotic_factor = 4.0 / albumin_g_dl.max(1.0);
+        let permeability_factor = capillary_permeability / 0.5;
+        let congestion_factor = 1.0 + 0.06 * (venous_pressure_mmhg - EDEMA_VENOUS_PRESSURE_MMHG).max(0.0);
+        let target_ratio = 3.0 * oncotic_factor.powf(0.5) * permeability_factor.powf(0.5) * congestion_factor;
+
+        let extracellular = self.extracellular_ml();
+        let target_plasma = extracellular / (1.0 + target_ratio);
+        let plasma_shift = (target_plasma - self.plasma_ml) * (0.001 * delta_time_s).min(1.0);
+        self.plasma_ml += plasma_shift;
+        self.interstitial_ml -= plasma_shift;
+
+        // Osmotic equilibration keeps ICF at ~2x ECF over hours
+        let target_intracellular = (self.intracellular_ml + extracellular) * 2.0 / 3.0;
+        let cell_shift = (target_intracellular - self.intracellular_ml) * (0.0001 * delta_time_s).min(1.0);
+        self.intracellular_ml += cell_shift;
+        self.interstitial_ml -= cell_shift;
+
+        self.intracellular_ml = self.intracellular_ml.max(0.0);
+        self.interstitial_ml = self.interstitial_ml.max(0.0);
+    }
+}
+
+impl Default for FluidCompartments {
+    fn default() -> Self {
+        Self::new(70.0)
+    }
+}
+
+/// Intake and output volumes accumulated over a period
+#[derive(Debug, Clone, Default)]
+pub struct IoTotals {
+    /// Intake volume by route (mL)
+    pub intake_ml: HashMap<IntakeRoute, f64>,
+    /// Output volume by route (mL)
+    pub output_ml: HashMap<OutputRoute, f64>,
+}
+
+impl IoTotals {
+    /// Get intake volume for a route (mL)
+    pub fn intake_for(&self, route: IntakeRoute) -> f64 {
+        self.intake_ml.get(&route).copied().unwrap_or(0.0)
+    }
+
+    /// Get output volume for a route (mL)
+    pub fn output_for(&self, route: OutputRoute) -> f64 {
+        self.output_ml.get(&route).copied().unwrap_or(0.0)
+    }
+
+    /// Get total intake (mL)
+    pub fn total_intake_ml(&self) -> f64 {
+        self.intake_ml.values().sum()
+    }
+
+    /// Get total measured output (mL), excluding insensible losses
+    pub fn total_measured_output_ml(&self) -> f64 {
+        self.output_ml
+            .iter()
+            .filter(|(route, _)| **route != OutputRoute::Insensible)
+            .map(|(_, volume)| volume)
+            .sum()
+    }
+
+    /// Get total output (mL), including insensible losses
+    pub fn total_output_ml(&self) -> f64 {
+        self.output_ml.values().sum()
+    }
+
+    /// Get net fluid balance (mL) - positive means net gain
+    pub fn net_balance_ml(&self) -> f64 {
+        self.total_intake_ml() - self.total_output_ml()
+    }
+
+    fn add_intake(&mut self, route: IntakeRoute, volume_ml: f64) {
+        *self.intake_ml.entry(route).or_insert(0.0) += volume_ml;
+    }
+
+    fn add_output(&mut self, route: OutputRoute, volume_ml: f64) {
+        *self.output_ml.entry(route).or_insert(0.0) += volume_ml;
+    }
+}
+
+/// A weight recorded at the start of a simulated day
+#[derive(Debug, Clone, Copy)]
+pub struct DailyWeight {
+    /// Simulated day number (0 = admission)
+    pub day: u32,
+    /// Body weight (kg)
+    pub weight_kg: f64,
+}
+
+/// Cumulative fluid intake/output ledger with daily weights
+#[derive(Debug, Clone)]
+pub struct FluidLedger {
+    /// Weight on admission (kg)
+    pub admission_weight_kg: f64,
+    /// Totals since admission
+    pub cumulative: IoTotals,
+    /// Totals for the current (incomplete) day
+    pub current_day: IoTotals,
+    /// Totals for each completed day
+    pub completed_days: Vec<IoTotals>,
+    /// Weights recorded at the start of each day
+    pub daily_weights: Vec<DailyWeight>,
+    /// Time elapsed within the current day (s)
+    time_in_day_s: f64,
+}
+
+impl FluidLedger {
+    /// Create a new ledger
+    ///
+    /// # Arguments
+    /// * `admission_weight_kg` - Weight on admission, recorded as day 0
+    pub fn new(admission_weight_kg: f64) -> Self {
+        Self {
+            admission_weight_kg,
+            cumulative: IoTotals::default(),
+            current_day: IoTotals::default(),
+            completed_days: Vec::new(),
+            daily_weights: vec![DailyWeight {
+                day: 0,
+                weight_kg: admission_weight_kg,
+            }],
+            time_in_day_s: 0.0,
+        }
+    }
+
+    /// Record fluid intake
+    pub fn record_intake(&mut self, route: IntakeRoute, volume_ml: f64) {
+        if volume_ml <= 0.0 {
+            return;
+        }
+        self.cumulative.add_intake(route, volume_ml);
+        self.current_day.add_intake(route, volume_ml);
+    }
+
+    /// Record fluid output
+    pub fn record_output(&mut self, route: OutputRoute, volume_ml: f64) {
+        if volume_ml <= 0.0 {
+            return;
+        }
+        self.cumulative.add_output(route, volume_ml);
+        self.current_day.add_output(route, volume_ml);
+    }
+
+    /// Get the current simulated day number
+    pub fn current_day_number(&self) -> u32 {
+        self.completed_days.len() as u32
+    }
+
+    /// Advance the ledger clock, closing out days and recording daily weights
+    ///
+    /// # Arguments
+    /// * `delta_time_s` - Time step in seconds
+    /// * `current_weight_kg` - Current body weight from the compartment model
+    pub fn advance(&mut self, delta_time_s: f64, current_weight_kg: f64) {
+        self.time_in_day_s += delta_time_s;
+        while self.time_in_day_s >= SECONDS_PER_DAY {
+            self.time_in_day_s -= SECONDS_PER_DAY;
+            self.completed_days.push(std::mem::take(&mut self.current_day));
+            self.daily_weights.push(DailyWeight {
+                day: self.current_day_number(),
+                weight_kg: current_weight_kg,
+            });
+        }
+    }
+
+    /// Build an I/O report
+    ///
+    /// # Arguments
+    /// * `current_weight_kg` - Current body weight from the compartment model
+    pub fn get_io_report(&self, current_weight_kg: f64) -> IoReport {
+        IoReport {
+            day: self.current_day_number(),
+            current_day: self.current_day.clone(),
+            cumulative: self.cumulative.clone(),
+            current_weight_kg,
+            weight_change_kg: current_weight_kg - self.admission_weight_kg,
+        }
+    }
+}
+
+impl Default for FluidLedger {
+    fn default() -> Self {
+        Self::new(70.0)
+    }
+}
+
+/// Snapshot of fluid intake/output for charting
+#[derive(Debug, Clone)]
+pub struct IoReport {
+    /// Current simulated day number
+    pub day: u32,
+    /// Totals for the current day
+    pub current_day: IoTotals,
+    /// Totals since admission
+    pub cumulative: IoTotals,
+    /// Current body weight (kg)
+    pub current_weight_kg: f64,
+    /// Weight change since admission (kg)
+    pub weight_change_kg: f64,
+}
+
+impl IoReport {
+    /// Get a formatted I/O summary
+    pub fn get_summary(&self) -> String {
+        format!(
+            "=== Intake/Output (Day {}) ===\n\
+             Today In: {:.0} mL (PO: {:.0}, Enteral: {:.0}, IV: {:.0}, Blood: {:.0})\n\
+             Today Out: {:.0} mL (Urine: {:.0}, Stool: {:.0}, Emesis: {:.0}, Drain: {:.0}, Blood loss: {:.0})\n\
+             Today Net: {:+.0} mL | Insensible: {:.0} mL\n\
+             Cumulative Net: {:+.0} mL | Weight: {:.1} kg ({:+.1} kg)",
+            self.day,
+            self.current_day.total_intake_ml(),
+            self.current_day.intake_for(IntakeRoute::Oral),
+            self.current_day.intake_for(IntakeRoute::Enteral),
+            self.current_day.intake_for(IntakeRoute::Intravenous),
+            self.current_day.intake_for(IntakeRoute::BloodProduct),
+            self.current_day.total_measured_output_ml(),
+            self.current_day.output_for(OutputRoute::Urine),
+            self.current_day.output_for(OutputRoute::Stool),
+            self.current_day.output_for(OutputRoute::Emesis),
+            self.current_day.output_for(OutputRoute::Drain),
+            self.current_day.output_for(OutputRoute::Blood),
+            self.current_day.net_balance_ml(),
+            self.current_day.output_for(OutputRoute::Insensible),
+            self.cumulative.net_balance_ml(),
+            self.current_weight_kg,
+            self.weight_change_kg
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::bladder::Bladder;
+    use crate::organs::kidneys::Kidneys;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_ledger_records_daily_weight() {
+        let mut ledger = FluidLedger::new(70.0);
+        ledger.record_intake(IntakeRoute::Intravenous, 1000.0);
+        ledger.record_output(OutputRoute::Urine, 400.0);
+        ledger.advance(SECONDS_PER_DAY, 70.6);
+
+        assert_eq!(ledger.daily_weights.len(), 2);
+        assert_eq!(ledger.daily_weights[1].day, 1);
+        assert!((ledger.completed_days[0].net_balance_ml() - 600.0).abs() < 1e-9);
+        assert_eq!(ledger.current_day.total_intake_ml(), 0.0);
+    }
+
+    #[test]
+    fn test_urine_is_charted_as_it_leaves_the_bladder() {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..30 {
+            update_patient(&mut patient, 60.0);
+        }
+        let bladder = patient.get_organ::<Bladder>().unwrap();
+        assert!((bladder.urine_volume_ml - 30.0).abs() < 10.0, "{}", bladder.urine_volume_ml);
+        assert_eq!(patient.fluid_ledger.current_day.output_for(OutputRoute::Urine), 0.0);
+
+        let residual = patient.catheterize_bladder().unwrap();
+        let mut injured = patient.fork();
+        for _ in 0..3 {
+            injured.get_organ_mut::<Kidneys>().unwrap().damage_nephrons(100.0);
+        }
+        for _ in 0..60 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut injured, 60.0);
+        }
+        let hourly = patient.catheterize_bladder().unwrap();
+        assert!((hourly - 60.0).abs() < 15.0, "{}", hourly);
+        let charted = patient.fluid_ledger.current_day.output_for(OutputRoute::Urine);
+        assert!((charted - residual - hourly).abs() < 2.0, "{} {} {}", charted, residual, hourly);
+        assert!(injured.catheterize_bladder().unwrap() < hourly / 2.0);
+        assert!(!patient.get_organ::<Bladder>().unwrap().is_oliguric(patient.get_weight_kg()));
+    }
+}
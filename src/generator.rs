@@ -0,0 +1,532 @@
+//! Random patient generation
+//!
+//! This module provides:
+//! - Randomized adult patients from a seed, for batch Monte Carlo studies and
+//!   training data: sex, age, height and body mass index from population
+//!   distributions
+//! - Baseline labs drawn around shared latent factors, so values that move
+//!   together in real patients stay consistent: chloride and bicarbonate keep a
+//!   normal anion gap, calcium follows albumin, total protein contains albumin,
+//!   hemoglobin depends on sex, and renal function declines with age
+//! - Comorbidity bundles (comorbidities that cluster, such as diabetes with
+//!   chronic kidney disease and coronary disease) with configurable prevalence,
+//!   applied through the chronic disease presets
+//! - Acuity profiles giving the chance of each acuity level, with an acute
+//!   illness of matching severity started at time zero
+//!
+//! The same seed and configuration always produce the same patients. Values the
+//! organs recalculate every step (sodium, potassium, creatinine, BUN) are not
+//! drawn; they follow from the organ state the generator sets.
+
+use crate::fluids::{FluidCompartments, FluidLedger};
+use crate::growth::{self, Demographics, Sex};
+use crate::organs::heart::{CoronaryTerritory, Heart, HeartFailurePhenotype};
+use crate::organs::kidneys::Kidneys;
+use crate::organs::lungs::Lungs;
+use crate::organs::pancreas::DiabetesType;
+use crate::organs::vascular::VascularSystem;
+use crate::patient::{initialize_patient, Patient};
+use crate::presets::{self, ChildPugh, Preset, LEAN_BMI};
+use crate::sepsis::BundleGuideline;
+use crate::variability::SeededRng;
+
+/// Draws are truncated at this many standard deviations
+const MAX_STANDARD_DEVIATIONS: f64 = 2.5;
+
+/// Mean adult height, male and female (cm)
+const MEAN_HEIGHT_CM: [f64; 2] = [176.0, 163.0];
+
+/// Standard deviation of adult height (cm)
+const HEIGHT_SD_CM: f64 = 7.0;
+
+/// Mean adult body mass index (kg/m²)
+const MEAN_BMI: f64 = 26.5;
+
+/// Standard deviation of adult body mass index (kg/m²)
+const BMI_SD: f64 = 4.5;
+
+/// Body mass index from which a patient is given the physiology of obesity (kg/m²)
+const OBESITY_BMI: f64 = 30.0;
+
+/// Mean hematocrit, male and female (fraction)
+const MEAN_HEMATOCRIT: [f64; 2] = [0.45, 0.40];
+
+/// Age after which GFR declines
+const RENAL_DECLINE_ONSET_YEARS: f64 = 40.0;
+
+/// Fraction of GFR lost per year after the onset of decline
+const RENAL_DECLINE_PER_YEAR: f64 = 0.008;
+
+/// Normal anion gap without potassium (mEq/L)
+const NORMAL_ANION_GAP_MEQ_L: f64 = 12.0;
+
+/// Age at which a bundle's prevalence applies; it scales linearly with age
+const PREVALENCE_REFERENCE_AGE_YEARS: f64 = 65.0;
+
+/// Fraction of the blood volume lost in a hemorrhage of severity 1.0
+const MAX_HEMORRHAGE_FRACTION: f64 = 0.4;
+
+/// Acuity of a generated patient
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Acuity {
+    /// No acute illness
+    Stable,
+    /// Mild acute illness, managed on a ward
+    Unwell,
+    /// Serious acute illness needing urgent treatment
+    Severe,
+    /// Life-threatening acute illness
+    Critical,
+}
+
+impl Acuity {
+    /// Severity of the acute illness (0.0-1.0)
+    pub fn severity(&self) -> f64 {
+        match self {
+            Acuity::Stable => 0.0,
+            Acuity::Unwell => 0.3,
+            Acuity::Severe => 0.6,
+            Acuity::Critical => 0.9,
+        }
+    }
+}
+
+/// Chance of each acuity level in a generated population
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AcuityProfile {
+    /// Relative weight of stable patients
+    pub stable: f64,
+    /// Relative weight of unwell patients
+    pub unwell: f64,
+    /// Relative weight of severely ill patients
+    pub severe: f64,
+    /// Relative weight of critically ill patients
+    pub critical: f64,
+}
+
+impl AcuityProfile {
+    /// Create a profile from relative weights (normalized when sampling)
+    pub fn new(stable: f64, unwell: f64, severe: f64, critical: f64) -> Self {
+        Self {
+            stable: stable.max(0.0),
+            unwell: unwell.max(0.0),
+            severe: severe.max(0.0),
+            critical: critical.max(0.0),
+        }
+    }
+
+    /// Clinic population: mostly stable
+    pub fn outpatient() -> Self {
+        Self::new(0.85, 0.13, 0.02, 0.0)
+    }
+
+    /// Emergency department population
+    pub fn emergency_department() -> Self {
+        Self::new(0.3, 0.4, 0.2, 0.1)
+    }
+
+    /// Intensive care population: every patient acutely ill
+    pub fn intensive_care() -> Self {
+        Self::new(0.0, 0.1, 0.4, 0.5)
+    }
+
+    /// Draw an acuity level
+    ///
+    /// # Arguments
+    /// * `rng` - Random generator
+    pub fn sample(&self, rng: &mut SeededRng) -> Acuity {
+        let weights = [
+            (Acuity::Stable, self.stable),
+            (Acuity::Unwell, self.unwell),
+            (Acuity::Severe, self.severe),
+            (Acuity::Critical, self.critical),
+        ];
+        let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+        if total <= 0.0 {
+            return Acuity::Stable;
+        }
+        let mut draw = rng.next_f64() * total;
+        for (acuity, weight) in weights {
+            if draw < weight {
+                return acuity;
+            }
+            draw -= weight;
+        }
+        Acuity::Critical
+    }
+}
+
+/// Acute illness a patient presents with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcuteIllness {
+    /// Sepsis from a pneumonia or urinary source
+    Sepsis,
+    /// Blood loss from trauma or a bleeding ulcer
+    Hemorrhage,
+    /// Acute coronary occlusion
+    MyocardialInfarction,
+    /// Lobar pneumonia without septic shock
+    Pneumonia,
+    /// Decompensated heart failure
+    HeartFailureExacerbation,
+}
+
+impl AcuteIllness {
+    /// Every illness, in the order they are drawn
+    pub const ALL: [AcuteIllness; 5] = [
+        AcuteIllness::Sepsis,
+        AcuteIllness::Hemorrhage,
+        AcuteIllness::MyocardialInfarction,
+        AcuteIllness::Pneumonia,
+        AcuteIllness::HeartFailureExacerbation,
+    ];
+}
+
+/// Comorbidities that occur together, with their prevalence
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComorbidityBundle {
+    /// Name of the bundle
+    pub name: String,
+    /// Chance a 65-year-old has the bundle (scales linearly with age)
+    pub prevalence: f64,
+    /// Chronic diseases of the bundle, applied in order
+    pub presets: Vec<Preset>,
+}
+
+impl ComorbidityBundle {
+    /// Create a bundle
+    ///
+    /// # Arguments
+    /// * `name` - Name of the bundle
+    /// * `prevalence` - Chance a 65-year-old has the bundle (0.0-1.0)
+    /// * `presets` - Chronic diseases of the bundle
+    pub fn new(name: &str, prevalence: f64, presets: Vec<Preset>) -> Self {
+        Self {
+            name: name.to_string(),
+            prevalence: prevalence.clamp(0.0, 1.0),
+            presets,
+        }
+    }
+
+    /// Chance a patient of the given age has the bundle
+    ///
+    /// # Arguments
+    /// * `age_years` - Age in years
+    pub fn prevalence_at(&self, age_years: f64) -> f64 {
+        (self.prevalence * age_years / PREVALENCE_REFERENCE_AGE_YEARS).clamp(0.0, 1.0)
+    }
+}
+
+/// Common comorbidity bundles of an adult hospital population
+pub fn default_comorbidity_bundles() -> Vec<ComorbidityBundle> {
+    vec![
+        ComorbidityBundle::new(
+            "Cardiometabolic",
+            0.2,
+            vec![
+                Preset::Diabetes {
+                    diabetes_type: DiabetesType::Type2,
+                },
+                Preset::ChronicKidneyDisease { stage: 3 },
+                Preset::PriorMyocardialInfarction {
+                    territory: CoronaryTerritory::RightCoronary,
+                },
+            ],
+        ),
+        ComorbidityBundle::new(
+            "Heart failure",
+            0.08,
+            vec![Preset::HeartFailure {
+                phenotype: HeartFailurePhenotype::ReducedEjectionFraction,
+                severity: 0.4,
+            }],
+        ),
+        ComorbidityBundle::new("Chronic lung disease", 0.1, vec![Preset::Copd { gold_grade: 2 }]),
+        ComorbidityBundle::new(
+            "Chronic liver disease",
+            0.03,
+            vec![Preset::Cirrhosis {
+                child_pugh: ChildPugh::A,
+            }],
+        ),
+    ]
+}
+
+/// Population a generator draws patients from
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorConfig {
+    /// Youngest age drawn (years)
+    pub min_age_years: f64,
+    /// Oldest age drawn (years)
+    pub max_age_years: f64,
+    /// Fraction of patients who are female
+    pub female_fraction: f64,
+    /// Number of EKG leads to simulate
+    pub num_heart_leads: usize,
+    /// Chance of each acuity level
+    pub acuity: AcuityProfile,
+    /// Comorbidity bundles, each drawn independently
+    pub comorbidities: Vec<ComorbidityBundle>,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            min_age_years: 18.0,
+            max_age_years: 90.0,
+            female_fraction: 0.5,
+            num_heart_leads: 3,
+            acuity: AcuityProfile::emergency_department(),
+            comorbidities: default_comorbidity_bundles(),
+        }
+    }
+}
+
+/// A generated patient with what was drawn for it
+pub struct GeneratedPatient {
+    /// The patient, at the onset of any acute illness
+    pub patient: Patient,
+    /// Acuity drawn
+    pub acuity: Acuity,
+    /// Acute illness started, if not stable
+    pub illness: Option<AcuteIllness>,
+    /// Names of the comorbidity bundles applied
+    pub comorbidities: Vec<String>,
+}
+
+/// Seeded generator of randomized patients
+#[derive(Debug, Clone)]
+pub struct PatientGenerator {
+    /// Population drawn from
+    pub config: GeneratorConfig,
+    /// Identifier of the next patient
+    pub next_id: i32,
+    rng: SeededRng,
+}
+
+impl PatientGenerator {
+    /// Create a generator
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the random draws
+    /// * `config` - Population to draw from
+    pub fn new(seed: u64, config: GeneratorConfig) -> Self {
+        Self {
+            config,
+            next_id: 1,
+            rng: SeededRng::new(seed),
+        }
+    }
+
+    /// Standard normal draw, truncated
+    fn normal(&mut self) -> f64 {
+        self.rng.next_normal().clamp(-MAX_STANDARD_DEVIATIONS, MAX_STANDARD_DEVIATIONS)
+    }
+
+    /// Uniform index below `count`
+    fn index(&mut self, count: usize) -> usize {
+        ((self.rng.next_f64() * count as f64) as usize).min(count - 1)
+    }
+
+    /// Generate the next patient
+    pub fn generate(&mut self) -> GeneratedPatient {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        // Demographics and body size
+        let sex = if self.rng.next_f64() < self.config.female_fraction {
+            Sex::Female
+        } else {
+            Sex::Male
+        };
+        let sex_index = if sex == Sex::Male { 0 } else { 1 };
+        let (min_age, max_age) = (self.config.min_age_years, self.config.max_age_years.max(self.config.min_age_years));
+        let age_years = min_age + self.rng.next_f64() * (max_age - min_age);
+        let height_cm = MEAN_HEIGHT_CM[sex_index] + HEIGHT_SD_CM * self.normal();
+        let bmi = MEAN_BMI + BMI_SD * self.normal();
+        // The obesity preset adds an obese patient's adipose tissue to the lean body
+        let obese = bmi >= OBESITY_BMI;
+        let weight_kg = if obese { LEAN_BMI } else { bmi } * (height_cm / 100.0).powi(2);
+
+        let mut patient = initialize_patient(id, self.config.num_heart_leads);
+        patient.demographics = Demographics::new(sex, age_years, height_cm);
+        patient.fluids = FluidCompartments::new(weight_kg);
+        patient.fluid_ledger = FluidLedger::new(weight_kg);
+        growth::apply_age_baselines(&mut patient);
+
+        // Red cell mass for sex; the vasculature adapts to the resting viscosity
+        let hematocrit = MEAN_HEMATOCRIT[sex_index] + 0.025 * self.normal();
+        let fluids = &mut patient.fluids;
+        let red_cell_ml = fluids.plasma_ml * hematocrit / (1.0 - hematocrit);
+        fluids.dry_mass_kg -= (red_cell_ml - fluids.red_cell_ml) / 1000.0;
+        fluids.reference_blood_volume_ml += red_cell_ml - fluids.red_cell_ml;
+        fluids.red_cell_ml = red_cell_ml;
+        let cells = &mut patient.blood.cells;
+        cells.hemoglobin_g_dl = hematocrit * 100.0 / 3.0;
+        cells.rbc_count_million_per_ul *= hematocrit * 100.0 / cells.hematocrit_percent;
+        cells.hematocrit_percent = hematocrit * 100.0;
+        cells.platelet_count_thousand_per_ul = 250.0 + 50.0 * self.normal();
+        if let Some(vascular) = patient.get_organ_mut::<VascularSystem>() {
+            vascular.set_reference_hematocrit(hematocrit);
+        }
+
+        // Renal function declines with age; creatinine and BUN follow from it
+        let renal_function = 1.0 - (age_years - RENAL_DECLINE_ONSET_YEARS).max(0.0) * RENAL_DECLINE_PER_YEAR;
+        if let Some(kidneys) = patient.get_organ_mut::<Kidneys>() {
+            for nephron in kidneys.nephrons.iter_mut() {
+                nephron.filtration_efficiency = renal_function;
+            }
+            kidneys.gfr_ml_per_min = kidneys.baseline_gfr_ml_per_min * renal_function;
+        }
+
+        // Labs around shared latent factors: protein status and acid-base state
+        let nutrition = self.normal();
+        let acid_base = self.normal();
+        let chemistry = &mut patient.blood.chemistry;
+        chemistry.albumin_g_dl = 4.2 + 0.3 * nutrition;
+        chemistry.total_protein_g_dl = chemistry.albumin_g_dl + 2.9 + 0.3 * self.normal();
+        chemistry.calcium_mg_dl = 9.4 + 0.8 * (chemistry.albumin_g_dl - 4.2) + 0.2 * self.normal();
+        chemistry.bicarbonate_meq_l = 25.0 + 1.5 * acid_base;
+        let anion_gap = NORMAL_ANION_GAP_MEQ_L + 1.5 * self.normal();
+        chemistry.chloride_meq_l = chemistry.sodium_meq_l - chemistry.bicarbonate_meq_l - anion_gap;
+        chemistry.glucose_mg_dl = 90.0 + 8.0 * self.normal();
+        chemistry.magnesium_mg_dl = 2.0 + 0.12 * self.normal();
+        chemistry.phosphate_mg_dl = 3.5 + 0.4 * self.normal();
+        let liver = self.normal();
+        chemistry.alt_u_l = 25.0 * (0.3 * liver).exp();
+        chemistry.ast_u_l = 22.0 * (0.25 * liver).exp();
+        chemistry.bilirubin_total_mg_dl = 0.7 * (0.3 * self.normal()).exp();
+        patient.blood.gases.hco3_meq_l = chemistry.bicarbonate_meq_l;
+
+        // Chronic disease, more common with age
+        let mut comorbidities = Vec::new();
+        if obese {
+            let preset = Preset::Obesity { bmi };
+            presets::apply(&mut patient, preset);
+            comorbidities.push(preset.name());
+        }
+        for bundle in self.config.comorbidities.clone() {
+            if self.rng.next_f64() < bundle.prevalence_at(age_years) {
+                for &preset in &bundle.presets {
+                    presets::apply(&mut patient, preset);
+                }
+                comorbidities.push(bundle.name);
+            }
+        }
+
+        // Acute illness of the drawn acuity
+        let acuity = self.config.acuity.sample(&mut self.rng);
+        let illness = if acuity == Acuity::Stable {
+            None
+        } else {
+            let illness = AcuteIllness::ALL[self.index(AcuteIllness::ALL.len())];
+            self.start_illness(&mut patient, illness, acuity.severity());
+            Some(illness)
+        };
+
+        GeneratedPatient {
+            patient,
+            acuity,
+            illness,
+            comorbidities,
+        }
+    }
+
+    /// Generate several patients
+    ///
+    /// # Arguments
+    /// * `count` - Number of patients
+    pub fn generate_batch(&mut self, count: usize) -> Vec<GeneratedPatient> {
+        (0..count).map(|_| self.generate()).collect()
+    }
+
+    /// Start an acute illness
+    fn start_illness(&mut self, patient: &mut Patient, illness: AcuteIllness, severity: f64) {
+        match illness {
+            AcuteIllness::Sepsis => {
+                let source = ["pneumonia", "urinary tract infection", "intra-abdominal infection"][self.index(3)];
+                patient.start_infection(source, severity, BundleGuideline::HourOne);
+            }
+            AcuteIllness::Hemorrhage => {
+                let blood_volume_ml = patient.fluids.blood_volume_ml();
+                patient.hemorrhage(blood_volume_ml * MAX_HEMORRHAGE_FRACTION * severity);
+            }
+            AcuteIllness::MyocardialInfarction => {
+                let territory = [
+                    CoronaryTerritory::LeftAnteriorDescending,
+                    CoronaryTerritory::LeftCircumflex,
+                    CoronaryTerritory::RightCoronary,
+                ][self.index(3)];
+                if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                    heart.set_territory_contractility(territory, 1.0 - severity);
+                }
+            }
+            AcuteIllness::Pneumonia => {
+                let lobe = self.index(5);
+                if let Some(lungs) = patient.get_organ_mut::<Lungs>() {
+                    lungs.inflict_damage(lobe, severity);
+                }
+            }
+            AcuteIllness::HeartFailureExacerbation => {
+                patient.induce_heart_failure(HeartFailurePhenotype::ReducedEjectionFraction, severity)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::update_patient;
+
+    #[test]
+    fn test_generated_patients_are_reproducible_and_consistent() {
+        let draw = |seed| {
+            PatientGenerator::new(seed, GeneratorConfig::default())
+                .generate_batch(20)
+                .into_iter()
+                .map(|generated| {
+                    let chemistry = &generated.patient.blood.chemistry;
+                    (generated.patient.demographics.age_years, chemistry.chloride_meq_l, generated.illness)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(draw(7), draw(7));
+        assert_ne!(draw(7), draw(8));
+
+        let mut generator = PatientGenerator::new(3, GeneratorConfig::default());
+        let patients = generator.generate_batch(100);
+        for generated in &patients {
+            let patient = &generated.patient;
+            let chemistry = &patient.blood.chemistry;
+            let anion_gap = chemistry.sodium_meq_l - chemistry.chloride_meq_l - chemistry.bicarbonate_meq_l;
+            assert!((8.0..=16.0).contains(&anion_gap));
+            assert!(chemistry.total_protein_g_dl > chemistry.albumin_g_dl);
+            assert!((18.0..=90.0).contains(&patient.demographics.age_years));
+        }
+        let mean_hemoglobin = |sex| {
+            let values: Vec<f64> = patients
+                .iter()
+                .filter(|g| g.patient.demographics.sex == sex)
+                .map(|g| g.patient.blood.cells.hemoglobin_g_dl)
+                .collect();
+            values.iter().sum::<f64>() / values.len() as f64
+        };
+        assert!(mean_hemoglobin(Sex::Male) > mean_hemoglobin(Sex::Female) + 1.0);
+        assert!(patients.iter().any(|g| !g.comorbidities.is_empty()));
+
+        // An intensive care population is acutely ill, and simulates from the onset
+        let config = GeneratorConfig {
+            acuity: AcuityProfile::intensive_care(),
+            ..GeneratorConfig::default()
+        };
+        let mut generated = PatientGenerator::new(11, config).generate();
+        assert_ne!(generated.acuity, Acuity::Stable);
+        assert!(generated.illness.is_some());
+        for _ in 0..60 {
+            update_patient(&mut generated.patient, 1.0);
+        }
+        assert!(generated.patient.blood.gases.ph.is_finite());
+        assert_eq!(generated.patient.stability.total_corrections, 0);
+    }
+}
@@ -0,0 +1,416 @@
+//! Demographics, growth and development
+//!
+//! This module provides:
+//! - Patient demographics (sex, age, height)
+//! - Height and weight growth references with percentile/z-score lookup
+//! - Growth along a percentile channel over long simulated durations, slowed by
+//!   chronic illness (hypoxemia, poorly controlled diabetes)
+//! - Age-appropriate organ baselines (heart rate, blood pressure, respiratory
+//!   rate, tidal volume, renal function)
+//! - Menarche in girls
+//! - Drinking to thirst, so free-living children stay hydrated over long runs
+
+use crate::fluids::IntakeRoute;
+use crate::menstrual::MENARCHE_AGE_YEARS;
+use crate::organs::{brain, heart, kidneys, lungs, muscle};
+use crate::patient::Patient;
+
+/// Seconds in one year
+pub const SECONDS_PER_YEAR: f64 = 365.25 * 86_400.0;
+
+/// Coefficient of variation of height for age
+const HEIGHT_CV: f64 = 0.04;
+
+/// Coefficient of variation of weight for age
+const WEIGHT_CV: f64 = 0.12;
+
+/// Age after which height no longer increases (years)
+const ADULT_AGE_YEARS: f64 = 20.0;
+
+/// Median height for age (years, cm), male
+const MALE_HEIGHT_CM: [(f64, f64); 15] = [
+    (0.0, 49.9), (0.5, 67.6), (1.0, 75.7), (2.0, 87.1), (3.0, 95.2), (4.0, 102.5), (5.0, 109.2),
+    (6.0, 115.5), (8.0, 127.3), (10.0, 138.4), (12.0, 149.1), (14.0, 163.2), (16.0, 173.4),
+    (18.0, 176.1), (20.0, 176.8),
+];
+
+/// Median height for age (years, cm), female
+const FEMALE_HEIGHT_CM: [(f64, f64); 15] = [
+    (0.0, 49.1), (0.5, 65.7), (1.0, 74.0), (2.0, 85.7), (3.0, 94.1), (4.0, 101.6), (5.0, 108.4),
+    (6.0, 114.6), (8.0, 126.6), (10.0, 138.6), (12.0, 151.2), (14.0, 159.8), (16.0, 162.5),
+    (18.0, 163.1), (20.0, 163.3),
+];
+
+/// Median weight for age (years, kg), male
+const MALE_WEIGHT_KG: [(f64, f64); 15] = [
+    (0.0, 3.3), (0.5, 7.9), (1.0, 9.6), (2.0, 12.2), (3.0, 14.3), (4.0, 16.3), (5.0, 18.4),
+    (6.0, 20.7), (8.0, 25.6), (10.0, 31.9), (12.0, 40.5), (14.0, 50.8), (16.0, 60.8),
+    (18.0, 67.2), (20.0, 70.6),
+];
+
+/// Median weight for age (years, kg), female
+const FEMALE_WEIGHT_KG: [(f64, f64); 15] = [
+    (0.0, 3.2), (0.5, 7.3), (1.0, 8.9), (2.0, 11.5), (3.0, 13.9), (4.0, 16.1), (5.0, 18.2),
+    (6.0, 20.2), (8.0, 25.8), (10.0, 32.6), (12.0, 41.5), (14.0, 49.4), (16.0, 54.0),
+    (18.0, 56.6), (20.0, 58.2),
+];
+
+/// Biological sex
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+/// Patient demographics
+#[derive(Debug, Clone)]
+pub struct Demographics {
+    /// Biological sex
+    pub sex: Sex,
+    /// Age (years); advances with simulated time
+    pub age_years: f64,
+    /// Height (cm)
+    pub height_cm: f64,
+}
+
+impl Demographics {
+    /// Create demographics
+    ///
+    /// # Arguments
+    /// * `sex` - Biological sex
+    /// * `age_years` - Age in years
+    /// * `height_cm` - Height in centimeters
+    pub fn new(sex: Sex, age_years: f64, height_cm: f64) -> Self {
+        Self {
+            sex,
+            age_years: age_years.max(0.0),
+            height_cm: height_cm.max(0.0),
+        }
+    }
+}
+
+impl Default for Demographics {
+    fn default() -> Self {
+        Self::new(Sex::Male, 40.0, 175.0)
+    }
+}
+
+/// Linear interpolation in an (x, y) table, clamped at the ends
+fn interpolate(table: &[(f64, f64)], x: f64) -> f64 {
+    let (first, last) = (table[0], table[table.len() - 1]);
+    if x <= first.0 {
+        return first.1;
+    }
+    if x >= last.0 {
+        return last.1;
+    }
+    let index = table.iter().position(|&(age, _)| age > x).unwrap_or(table.len() - 1);
+    let (x0, y0) = table[index - 1];
+    let (x1, y1) = table[index];
+    y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+}
+
+/// Standard normal cumulative distribution (Abramowitz and Stegun 7.1.26)
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let polynomial = t * (0.254_829_592
+        + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - polynomial * (-x * x).exp();
+    if z >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+/// Z-score for a percentile (bisection on the normal distribution)
+fn z_for_percentile(percentile: f64) -> f64 {
+    let target = (percentile / 100.0).clamp(0.001, 0.999);
+    let (mut low, mut high) = (-4.0, 4.0);
+    for _ in 0..50 {
+        let mid = (low + high) / 2.0;
+        if normal_cdf(mid) < target {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// Median height for age (cm)
+pub fn median_height_cm(sex: Sex, age_years: f64) -> f64 {
+    match sex {
+        Sex::Male => interpolate(&MALE_HEIGHT_CM, age_years),
+        Sex::Female => interpolate(&FEMALE_HEIGHT_CM, age_years),
+    }
+}
+
+/// Median weight for age (kg)
+pub fn median_weight_kg(sex: Sex, age_years: f64) -> f64 {
+    match sex {
+        Sex::Male => interpolate(&MALE_WEIGHT_KG, age_years),
+        Sex::Female => interpolate(&FEMALE_WEIGHT_KG, age_years),
+    }
+}
+
+/// Height for age and percentile (cm)
+pub fn height_for_percentile(sex: Sex, age_years: f64, percentile: f64) -> f64 {
+    median_height_cm(sex, age_years) * (1.0 + z_for_percentile(percentile) * HEIGHT_CV)
+}
+
+/// Weight for age and percentile (kg)
+pub fn weight_for_percentile(sex: Sex, age_years: f64, percentile: f64) -> f64 {
+    median_weight_kg(sex, age_years) * (1.0 + z_for_percentile(percentile) * WEIGHT_CV)
+}
+
+/// Height-for-age percentile (0-100)
+pub fn height_percentile(sex: Sex, age_years: f64, height_cm: f64) -> f64 {
+    let z = (height_cm / median_height_cm(sex, age_years) - 1.0) / HEIGHT_CV;
+    normal_cdf(z) * 100.0
+}
+
+/// Weight-for-age percentile (0-100)
+pub fn weight_percentile(sex: Sex, age_years: f64, weight_kg: f64) -> f64 {
+    let z = (weight_kg / median_weight_kg(sex, age_years) - 1.0) / WEIGHT_CV;
+    normal_cdf(z) * 100.0
+}
+
+/// Resting heart rate for age (bpm)
+pub fn resting_heart_rate_for_age(age_years: f64) -> f64 {
+    interpolate(
+        &[(0.0, 140.0), (1.0, 120.0), (3.0, 105.0), (6.0, 95.0), (10.0, 85.0), (14.0, 78.0), (18.0, 75.0)],
+        age_years,
+    )
+}
+
+/// Resting respiratory rate for age (breaths/min)
+pub fn resting_respiratory_rate_for_age(age_years: f64) -> f64 {
+    interpolate(
+        &[(0.0, 40.0), (1.0, 30.0), (3.0, 24.0), (6.0, 20.0), (12.0, 18.0), (18.0, 16.0)],
+        age_years,
+    )
+}
+
+/// Systemic blood pressure relative to an adult for age
+///
+/// A term newborn's MAP is ~45 mmHg; adult values are reached in late adolescence
+pub fn systemic_pressure_scale_for_age(age_years: f64) -> f64 {
+    interpolate(&[(0.0, 0.46), (1.0, 0.7), (6.0, 0.77), (12.0, 0.85), (18.0, 1.0)], age_years)
+}
+
+/// Renal maturity: GFR relative to adult values for body size
+///
+/// Neonatal GFR is ~20% of adult per body size and matures by ~2 years
+pub fn renal_maturity_for_age(age_years: f64) -> f64 {
+    interpolate(&[(0.0, 0.2), (0.5, 0.6), (2.0, 1.0)], age_years)
+}
+
+/// Set organ baselines for the patient's age and size
+///
+/// # Arguments
+/// * `patient` - The patient to update
+pub fn apply_age_baselines(patient: &mut Patient) {
+    let age_years = patient.demographics.age_years;
+    let weight_kg = patient.get_weight_kg();
+    let pressure_scale = systemic_pressure_scale_for_age(age_years);
+
+    if let Some(heart) = patient.get_organ_mut::<heart::Heart>() {
+        heart.resting_heart_rate_bpm = resting_heart_rate_for_age(age_years);
+        heart.systemic_pressure_scale = pressure_scale;
+    }
+    if let Some(brain) = patient.get_organ_mut::<brain::Brain>() {
+        brain.baroreflex_set_point_mmhg = brain::NORMAL_BAROREFLEX_SET_POINT_MMHG * pressure_scale;
+        brain.pressure_scale = pressure_scale;
+    }
+    if let Some(lungs) = patient.get_organ_mut::<lungs::Lungs>() {
+        // ~7 mL/kg (500 mL at 70 kg)
+        lungs.resting_respiration_rate_bpm = resting_respiratory_rate_for_age(age_years);
+        lungs.resting_tidal_volume_ml = weight_kg * 500.0 / 70.0;
+        if lungs.ventilator.is_none() {
+            lungs.tidal_volume_ml = lungs.resting_tidal_volume_ml;
+        }
+    }
+    if let Some(kidneys) = patient.get_organ_mut::<kidneys::Kidneys>() {
+        // Filtration scales allometrically with body size
+        kidneys.baseline_gfr_ml_per_min = 100.0 * (weight_kg / 70.0).powf(0.7) * renal_maturity_for_age(age_years);
+        kidneys.autoregulation_map_mmhg = 70.0 * pressure_scale;
+    }
+    if let Some(muscle) = patient.get_organ_mut::<muscle::Muscle>() {
+        // ~40% of body weight
+        muscle.mass_kg = weight_kg * muscle::NORMAL_MUSCLE_MASS_KG / 70.0;
+    }
+}
+
+/// A growth chart entry
+#[derive(Debug, Clone)]
+pub struct GrowthRecord {
+    /// Age at measurement (years)
+    pub age_years: f64,
+    /// Height (cm)
+    pub height_cm: f64,
+    /// Weight (kg)
+    pub weight_kg: f64,
+    /// Height-for-age percentile
+    pub height_percentile: f64,
+    /// Weight-for-age percentile
+    pub weight_percentile: f64,
+    /// Body mass index (kg/m²)
+    pub bmi: f64,
+}
+
+/// Growth tracking for a pediatric patient
+#[derive(Debug, Clone)]
+pub struct Growth {
+    /// Height z-score channel the child grows along when healthy
+    pub height_z: f64,
+    /// Weight z-score channel the child grows along when healthy
+    pub weight_z: f64,
+    /// Growth velocity relative to healthy (1.0 = normal), reduced by chronic illness
+    pub growth_velocity_factor: f64,
+    /// Growth chart measurements (quarterly under 2 years, then yearly)
+    pub records: Vec<GrowthRecord>,
+    /// Weight expected from growth alone, without fluid deficits (kg)
+    pub expected_weight_kg: f64,
+    /// Mean oxygen saturation over the past weeks (%)
+    mean_spo2_percent: f64,
+    /// Mean glucose over the past weeks (mg/dL)
+    mean_glucose_mg_dl: f64,
+    /// Age at the last growth step (years)
+    last_age_years: f64,
+    /// Age at which organ baselines were last updated (years)
+    baseline_age_years: f64,
+    /// Age of the next growth chart entry (years)
+    next_record_age_years: f64,
+}
+
+impl Growth {
+    /// Start tracking growth from the patient's current size
+    ///
+    /// # Arguments
+    /// * `demographics` - Patient demographics
+    /// * `weight_kg` - Current body weight
+    pub fn new(demographics: &Demographics, weight_kg: f64) -> Self {
+        let age_years = demographics.age_years;
+        Self {
+            height_z: (demographics.height_cm / median_height_cm(demographics.sex, age_years) - 1.0) / HEIGHT_CV,
+            weight_z: (weight_kg / median_weight_kg(demographics.sex, age_years) - 1.0) / WEIGHT_CV,
+            growth_velocity_factor: 1.0,
+            records: Vec::new(),
+            expected_weight_kg: weight_kg,
+            mean_spo2_percent: 97.0,
+            mean_glucose_mg_dl: 90.0,
+            last_age_years: age_years,
+            baseline_age_years: age_years,
+            next_record_age_years: age_years,
+        }
+    }
+
+    /// Advance growth to the patient's current age
+    ///
+    /// # Arguments
+    /// * `patient` - The growing patient
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // Chronic illness is judged over weeks, not moments
+        let averaging = (delta_time_s / (30.0 * 86_400.0)).min(1.0);
+        self.mean_spo2_percent += (patient.blood.gases.sao2_percent - self.mean_spo2_percent) * averaging;
+        self.mean_glucose_mg_dl += (patient.blood.chemistry.glucose_mg_dl - self.mean_glucose_mg_dl) * averaging;
+
+        // Chronic hypoxemia (cyanotic heart disease) and poorly controlled diabetes stunt growth
+        let hypoxemia_penalty = ((92.0 - self.mean_spo2_percent) / 20.0).clamp(0.0, 0.5);
+        let hyperglycemia_penalty = ((self.mean_glucose_mg_dl - 200.0) / 300.0).clamp(0.0, 0.4);
+        self.growth_velocity_factor = (1.0 - hypoxemia_penalty - hyperglycemia_penalty).max(0.1);
+
+        let sex = patient.demographics.sex;
+        let age_years = patient.demographics.age_years;
+        let previous_age = self.last_age_years.min(ADULT_AGE_YEARS);
+        let growing_age = age_years.min(ADULT_AGE_YEARS);
+        if growing_age > previous_age {
+            let height_gain = (median_height_cm(sex, growing_age) - median_height_cm(sex, previous_age))
+                * (1.0 + self.height_z * HEIGHT_CV)
+                * self.growth_velocity_factor;
+            patient.demographics.height_cm += height_gain;
+
+            // New tissue is added in proportion to the existing body composition
+            let weight_gain = (median_weight_kg(sex, growing_age) - median_weight_kg(sex, previous_age))
+                * (1.0 + self.weight_z * WEIGHT_CV)
+                * self.growth_velocity_factor;
+            let weight_kg = patient.get_weight_kg();
+            if weight_kg > 0.0 {
+                patient.fluids.scale((weight_kg + weight_gain) / weight_kg);
+            }
+            self.expected_weight_kg += weight_gain;
+        }
+        self.last_age_years = age_years;
+
+        // Thirst: a drink replaces water lost once the deficit reaches ~0.5% of body
+        // weight, counting what is still being absorbed from the last one
+        let deficit_ml = (self.expected_weight_kg - patient.get_weight_kg()) * 1000.0 - patient.get_unabsorbed_water_ml();
+        if deficit_ml > (self.expected_weight_kg * 5.0).max(15.0) {
+            patient.give_fluid(IntakeRoute::Oral, deficit_ml.min(self.expected_weight_kg * 10.0));
+        }
+
+        if sex == Sex::Female && previous_age < MENARCHE_AGE_YEARS && growing_age >= MENARCHE_AGE_YEARS {
+            patient.start_menstrual_cycle(28.0);
+        }
+
+        if age_years - self.baseline_age_years >= 1.0 / 365.25 {
+            apply_age_baselines(patient);
+            self.baseline_age_years = age_years;
+        }
+
+        if age_years >= self.next_record_age_years {
+            self.records.push(self.measure(patient));
+            let interval = if age_years < 2.0 { 0.25 } else { 1.0 };
+            self.next_record_age_years += interval;
+        }
+    }
+
+    /// Take growth measurements
+    ///
+    /// # Arguments
+    /// * `patient` - The patient to measure
+    pub fn measure(&self, patient: &Patient) -> GrowthRecord {
+        let demographics = &patient.demographics;
+        let weight_kg = patient.get_weight_kg();
+        GrowthRecord {
+            age_years: demographics.age_years,
+            height_cm: demographics.height_cm,
+            weight_kg,
+            height_percentile: height_percentile(demographics.sex, demographics.age_years, demographics.height_cm),
+            weight_percentile: weight_percentile(demographics.sex, demographics.age_years, weight_kg),
+            bmi: crate::calculate_bmi(weight_kg, demographics.height_cm / 100.0),
+        }
+    }
+
+    /// Get a summary of the latest growth measurements
+    pub fn get_summary(&self) -> String {
+        match self.records.last() {
+            Some(record) => format!(
+                "Growth: Age={:.1} y, Height={:.1} cm (P{:.0}), Weight={:.1} kg (P{:.0}), BMI={:.1}, Velocity={:.0}%",
+                record.age_years,
+                record.height_cm,
+                record.height_percentile,
+                record.weight_kg,
+                record.weight_percentile,
+                record.bmi,
+                self.growth_velocity_factor * 100.0
+            ),
+            None => "Growth: no measurements".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_round_trip() {
+        let height = height_for_percentile(Sex::Female, 7.0, 90.0);
+        assert!((height_percentile(Sex::Female, 7.0, height) - 90.0).abs() < 0.5);
+        assert!((height_percentile(Sex::Male, 10.0, median_height_cm(Sex::Male, 10.0)) - 50.0).abs() < 0.1);
+    }
+}
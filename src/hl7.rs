@@ -0,0 +1,223 @@
+//! HL7 v2 result messages
+//!
+//! This module provides (with the `hl7` feature):
+//! - ORU^R01 unsolicited result messages (HL7 v2.5.1) for reported lab orders,
+//!   with MSH, PID, OBR and one OBX segment per result carrying its LOINC code,
+//!   UCUM units, reference range and abnormal flag
+//! - Escaping of the encoding characters in field values
+//! - Sequential message control IDs, so a stream of messages can be fed to an
+//!   interface engine test harness
+//!
+//! Segments are separated by carriage returns, as on the wire.
+
+use crate::growth::Sex;
+use crate::labs::{LabFlag, LabReport};
+use crate::patient::Patient;
+use crate::timestamp::{CivilTime, DEFAULT_START_UNIX_S};
+
+/// HL7 version the messages declare
+const HL7_VERSION: &str = "2.5.1";
+
+/// Seconds per year, for the date of birth from age
+const SECONDS_PER_YEAR: f64 = 365.25 * 86_400.0;
+
+/// Escape the HL7 encoding characters in a field value
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '|' => escaped.push_str("\\F\\"),
+            '^' => escaped.push_str("\\S\\"),
+            '~' => escaped.push_str("\\R\\"),
+            '\\' => escaped.push_str("\\E\\"),
+            '&' => escaped.push_str("\\T\\"),
+            '\r' | '\n' => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// HL7 abnormal flag (table 0078)
+fn abnormal_flag(flag: LabFlag) -> &'static str {
+    match flag {
+        LabFlag::Normal => "N",
+        LabFlag::Low => "L",
+        LabFlag::High => "H",
+        LabFlag::CriticalLow => "LL",
+        LabFlag::CriticalHigh => "HH",
+    }
+}
+
+/// Generator of HL7 v2 ORU^R01 messages for a laboratory
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hl7Export {
+    /// MSH-3 sending application
+    pub sending_application: String,
+    /// MSH-4 sending facility
+    pub sending_facility: String,
+    /// MSH-5 receiving application
+    pub receiving_application: String,
+    /// MSH-6 receiving facility
+    pub receiving_facility: String,
+    /// Wall-clock time of simulation time zero (Unix seconds)
+    pub start_unix_s: i64,
+    /// Number of the next message, used as its control ID
+    next_message: u32,
+}
+
+impl Hl7Export {
+    /// Create a generator sending from MEDICALLIB^SIMLAB, starting 2024-01-01
+    pub fn new() -> Self {
+        Self {
+            sending_application: "MEDICALLIB".to_string(),
+            sending_facility: "SIMLAB".to_string(),
+            receiving_application: "RECEIVER".to_string(),
+            receiving_facility: "FACILITY".to_string(),
+            start_unix_s: DEFAULT_START_UNIX_S,
+            next_message: 1,
+        }
+    }
+
+    /// HL7 timestamp of a simulation time
+    ///
+    /// # Arguments
+    /// * `time_s` - Simulation time (s)
+    pub fn timestamp(&self, time_s: f64) -> String {
+        CivilTime::from_unix(self.start_unix_s + time_s.floor() as i64).hl7()
+    }
+
+    /// ORU^R01 message for a reported lab order
+    ///
+    /// # Arguments
+    /// * `patient` - Patient the specimen was drawn from
+    /// * `report` - Reported order
+    ///
+    /// # Returns
+    /// The message, segments separated by carriage returns
+    pub fn oru_r01(&mut self, patient: &Patient, report: &LabReport) -> String {
+        let control_id = format!("MSG{:06}", self.next_message);
+        self.next_message += 1;
+        let drawn = self.timestamp(report.ordered_at_s);
+        let reported = self.timestamp(report.reported_at_s);
+        let birth_unix_s = self.start_unix_s - (patient.demographics.age_years * SECONDS_PER_YEAR) as i64;
+        let birth_date = &CivilTime::from_unix(birth_unix_s).hl7()[..8];
+        let sex = match patient.demographics.sex {
+            Sex::Male => "M",
+            Sex::Female => "F",
+        };
+        let (panel_code, panel_name) = report.panel.loinc();
+        let order_number = format!("ORD{:06}", report.id);
+
+        let mut segments = vec![
+            format!(
+                "MSH|^~\\&|{}|{}|{}|{}|{}||ORU^R01^ORU_R01|{}|P|{}",
+                escape(&self.sending_application),
+                escape(&self.sending_facility),
+                escape(&self.receiving_application),
+                escape(&self.receiving_facility),
+                reported,
+                control_id,
+                HL7_VERSION
+            ),
+            format!(
+                "PID|1||{}^^^{}^MR||SIMULATED^PATIENT {}||{}|{}",
+                patient.id,
+                escape(&self.sending_facility),
+                patient.id,
+                birth_date,
+                sex
+            ),
+            format!(
+                "OBR|1|{}|{}|{}^{}^LN|||{}||||||||||||||{}|||F",
+                order_number,
+                order_number,
+                panel_code,
+                escape(panel_name),
+                drawn,
+                reported
+            ),
+        ];
+        for (i, result) in report.results.iter().enumerate() {
+            let (code, name) = result.analyte.loinc();
+            let decimals = result.analyte.decimals();
+            let (low, high) = result.reference_range;
+            let units = result.analyte.ucum_units();
+            segments.push(format!(
+                "OBX|{}|NM|{}^{}^LN||{:.*}|{}^{}^UCUM|{:.*}-{:.*}|{}|||F|||{}",
+                i + 1,
+                code,
+                escape(name),
+                decimals,
+                result.value,
+                escape(units),
+                escape(units),
+                decimals,
+                low,
+                decimals,
+                high,
+                abnormal_flag(result.flag),
+                drawn
+            ));
+        }
+        segments.join("\r") + "\r"
+    }
+
+    /// ORU^R01 messages for every report the patient's laboratory has released
+    pub fn released_reports(&mut self, patient: &Patient) -> Vec<String> {
+        patient.labs.reports().iter().map(|report| self.oru_r01(patient, report)).collect()
+    }
+}
+
+impl Default for Hl7Export {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::labs::LabPanel;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_oru_message_for_basic_metabolic_results() {
+        let mut patient = initialize_patient(3, 12);
+        patient.labs.noise = false;
+        patient.blood.chemistry.potassium_meq_l = 7.0;
+        let cmp = patient.order_labs(LabPanel::Cmp);
+        patient.order_labs(LabPanel::Abg);
+        for _ in 0..60 {
+            update_patient(&mut patient, 60.0);
+        }
+        let mut export = Hl7Export::new();
+        let message = export.oru_r01(&patient, patient.labs.report(cmp).unwrap());
+        // The blood gas is released first
+        let messages = export.released_reports(&patient);
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].contains("|ORU^R01^ORU_R01|MSG000002|"));
+        assert!(messages[0].contains("|2744-1^pH of Arterial blood^LN||"));
+
+        let segments: Vec<&str> = message.trim_end_matches('\r').split('\r').collect();
+        assert_eq!(segments.len(), 3 + 14);
+        assert!(segments[0].starts_with("MSH|^~\\&|MEDICALLIB|SIMLAB|RECEIVER|FACILITY|20240101004500||ORU^R01^ORU_R01|MSG000001|P|2.5.1"));
+        assert!(segments[1].starts_with("PID|1||3^^^SIMLAB^MR||SIMULATED^PATIENT 3||198"));
+        assert!(segments[1].ends_with("|M"));
+        assert!(segments[2].starts_with("OBR|1|ORD000001|ORD000001|24323-8^"));
+        assert!(segments[2].contains("|20240101000000|"));
+        let obx: Vec<&str> = segments[4].split('|').collect();
+        assert_eq!(obx[0], "OBX");
+        assert_eq!(obx[3], "2823-3^Potassium [Moles/volume] in Serum or Plasma^LN");
+        assert_eq!(obx[6], "meq/L^meq/L^UCUM");
+        assert_eq!(obx[5], "7.0");
+        assert_eq!(obx[7], "3.5-5.0");
+        assert_eq!(obx[8], "HH");
+        assert_eq!(obx[11], "F");
+    }
+
+    #[test]
+    fn test_escapes_encoding_characters() {
+        assert_eq!(escape("a|b^c~d\\e&f"), "a\\F\\b\\S\\c\\R\\d\\E\\e\\T\\f");
+    }
+}
@@ -0,0 +1,155 @@
+//! Iron balance
+//!
+//! This module provides:
+//! - Storage iron (ferritin/hemosiderin) with dietary absorption regulated by stores
+//! - Oral and intravenous iron replacement
+//! - Iron availability for erythropoiesis, limiting red cell production when stores run out
+//! - Iron studies (serum iron, ferritin, TIBC)
+//!
+//! Every mL of red cells lost takes its iron with it, so chronic blood loss
+//! (including heavy menstrual losses) leads to iron-deficiency anemia over months.
+
+use crate::blood::BloodChemistry;
+use crate::fluids::SECONDS_PER_DAY;
+
+/// Iron in one mL of red cells (mg)
+pub const IRON_PER_RED_CELL_ML: f64 = 1.1;
+
+/// Storage iron below which erythropoiesis becomes iron-restricted (mg)
+const IRON_RESTRICTED_STORES_MG: f64 = 150.0;
+
+/// Obligate iron loss from shed gut and skin cells (mg/day)
+const OBLIGATE_LOSS_MG_PER_DAY: f64 = 1.0;
+
+/// Body iron stores and absorption
+#[derive(Debug, Clone)]
+pub struct IronStores {
+    /// Storage iron (mg) - ~1000 mg in adult men, ~300 mg in menstruating women
+    pub storage_iron_mg: f64,
+    /// Dietary iron intake (mg/day)
+    pub dietary_iron_mg_per_day: f64,
+    /// Iron absorbed from the gut over the last day (mg/day)
+    pub absorption_mg_per_day: f64,
+    /// Oral iron still in the gut (mg elemental)
+    pending_oral_iron_mg: f64,
+    /// Intravenous iron not yet released by macrophages into stores (mg)
+    pending_iv_iron_mg: f64,
+}
+
+impl IronStores {
+    /// Create iron stores
+    ///
+    /// # Arguments
+    /// * `storage_iron_mg` - Initial storage iron in mg
+    pub fn new(storage_iron_mg: f64) -> Self {
+        Self {
+            storage_iron_mg: storage_iron_mg.max(0.0),
+            dietary_iron_mg_per_day: 12.0,
+            absorption_mg_per_day: 1.0,
+            pending_oral_iron_mg: 0.0,
+            pending_iv_iron_mg: 0.0,
+        }
+    }
+
+    /// Give an oral iron supplement (e.g. ferrous sulfate 325 mg = 65 mg elemental)
+    ///
+    /// # Arguments
+    /// * `elemental_iron_mg` - Elemental iron in mg
+    pub fn give_oral_iron(&mut self, elemental_iron_mg: f64) {
+        self.pending_oral_iron_mg += elemental_iron_mg.max(0.0);
+    }
+
+    /// Give an intravenous iron infusion (e.g. iron sucrose, ferric carboxymaltose)
+    ///
+    /// # Arguments
+    /// * `iron_mg` - Iron in mg
+    pub fn give_iv_iron(&mut self, iron_mg: f64) {
+        self.pending_iv_iron_mg += iron_mg.max(0.0);
+    }
+
+    /// Fraction of gut iron absorbed (hepcidin falls as stores empty)
+    pub fn absorption_fraction(&self) -> f64 {
+        (0.2 * (1.0 - self.storage_iron_mg / 1200.0)).clamp(0.02, 0.2)
+    }
+
+    /// Iron availability for erythropoiesis (0.2 = iron-restricted, 1.0 = replete)
+    pub fn availability(&self) -> f64 {
+        0.2 + 0.8 * (self.storage_iron_mg / IRON_RESTRICTED_STORES_MG).clamp(0.0, 1.0)
+    }
+
+    /// Check for depleted iron stores
+    pub fn is_iron_deficient(&self) -> bool {
+        self.storage_iron_mg < IRON_RESTRICTED_STORES_MG * 0.5
+    }
+
+    /// Take iron from stores for new red cells
+    ///
+    /// # Arguments
+    /// * `iron_mg` - Iron needed in mg
+    ///
+    /// # Returns
+    /// The iron actually supplied (mg)
+    pub fn consume(&mut self, iron_mg: f64) -> f64 {
+        let used = iron_mg.clamp(0.0, self.storage_iron_mg);
+        self.storage_iron_mg -= used;
+        used
+    }
+
+    /// Return iron from senescent red cells to stores
+    ///
+    /// # Arguments
+    /// * `iron_mg` - Recycled iron in mg
+    pub fn recycle(&mut self, iron_mg: f64) {
+        self.storage_iron_mg += iron_mg.max(0.0);
+    }
+
+    /// Update absorption and losses, and set iron studies
+    ///
+    /// # Arguments
+    /// * `chemistry` - Blood chemistry receiving the iron studies
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, chemistry: &mut BloodChemistry, delta_time_s: f64) {
+        let days = delta_time_s / SECONDS_PER_DAY;
+        let fraction = self.absorption_fraction();
+
+        // Supplements leave the gut over a few hours
+        let oral_passed = self.pending_oral_iron_mg * (1.0 - (-delta_time_s / 14_400.0).exp());
+        self.pending_oral_iron_mg -= oral_passed;
+        let absorbed = self.dietary_iron_mg_per_day * days * fraction + oral_passed * fraction;
+
+        // IV iron is taken up by macrophages and released into stores over ~a day
+        let iv_released = self.pending_iv_iron_mg * (1.0 - (-delta_time_s / SECONDS_PER_DAY).exp());
+        self.pending_iv_iron_mg -= iv_released;
+
+        self.storage_iron_mg =
+            (self.storage_iron_mg + absorbed + iv_released - OBLIGATE_LOSS_MG_PER_DAY * days).max(0.0);
+        if days > 0.0 {
+            let averaging = days.min(1.0);
+            self.absorption_mg_per_day += (absorbed / days - self.absorption_mg_per_day) * averaging;
+        }
+
+        // Iron studies: ferritin tracks stores, TIBC rises as transferrin is upregulated
+        let availability = self.availability();
+        chemistry.ferritin_ng_ml = self.storage_iron_mg / 8.0 + self.pending_iv_iron_mg / 4.0;
+        chemistry.serum_iron_ug_dl = 30.0 + 70.0 * availability + self.pending_iv_iron_mg / 10.0;
+        chemistry.tibc_ug_dl = 300.0 + 150.0 * (1.0 - availability) / 0.8;
+    }
+
+    /// Get a summary of iron balance
+    pub fn get_summary(&self) -> String {
+        format!(
+            "Iron: Stores={:.0} mg, Absorption={:.1} mg/day ({:.0}%), Availability={:.0}%{}",
+            self.storage_iron_mg,
+            self.absorption_mg_per_day,
+            self.absorption_fraction() * 100.0,
+            self.availability() * 100.0,
+            if self.is_iron_deficient() { ", IRON DEFICIENT" } else { "" }
+        )
+    }
+}
+
+impl Default for IronStores {
+    fn default() -> Self {
+        Self::new(1000.0)
+    }
+}
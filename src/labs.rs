@@ -0,0 +1,915 @@
+//! Laboratory orders and results
+//!
+//! This module provides:
+//! - Orderable panels (CBC, CMP, ABG, VBG, troponin, coagulation, lactate, HbA1c and
+//!   drug levels) with a configurable turnaround time, so results arrive after a
+//!   delay rather than reading the patient's internal state instantly
+//! - Analytic imprecision, applied when the specimen is drawn, from a seeded
+//!   generator separate from the patient's variability
+//! - Flagging against reference ranges adjusted for sex and age, with critical
+//!   values for the results a laboratory would phone through
+//! - Conversion of venous gas results to the arterial values they estimate,
+//!   which holds for pH and bicarbonate but not PO2, and not PCO2 in shock
+
+use std::collections::HashMap;
+
+use crate::growth::{Demographics, Sex};
+use crate::patient::Patient;
+use crate::toxicology::Drug;
+use crate::variability::SeededRng;
+
+/// Age below which newborn reference ranges apply (years)
+const NEWBORN_AGE_YEARS: f64 = 1.0 / 12.0;
+
+/// Age below which pediatric reference ranges apply (years)
+const ADULT_AGE_YEARS: f64 = 18.0;
+
+/// Orderable laboratory panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LabPanel {
+    /// Complete blood count
+    Cbc,
+    /// Comprehensive metabolic panel
+    Cmp,
+    /// Arterial blood gas
+    Abg,
+    /// Venous blood gas, drawn from a central line
+    Vbg,
+    /// High-sensitivity troponin
+    Troponin,
+    /// PT/INR, aPTT and fibrinogen
+    Coagulation,
+    /// Blood lactate
+    Lactate,
+    /// Hemoglobin A1c
+    Hba1c,
+    /// Serum lipase
+    Lipase,
+    /// Serum creatine kinase
+    Ck,
+    /// Serum myoglobin
+    Myoglobin,
+    /// Serum digoxin level
+    Digoxin,
+    /// Serum lithium level
+    Lithium,
+    /// Serum phenytoin level
+    Phenytoin,
+    /// Serum acetaminophen level
+    Acetaminophen,
+    /// Serum salicylate level
+    Salicylate,
+}
+
+impl LabPanel {
+    /// Every orderable panel
+    pub const ALL: [LabPanel; 16] = [
+        LabPanel::Cbc,
+        LabPanel::Cmp,
+        LabPanel::Abg,
+        LabPanel::Vbg,
+        LabPanel::Troponin,
+        LabPanel::Coagulation,
+        LabPanel::Lactate,
+        LabPanel::Hba1c,
+        LabPanel::Lipase,
+        LabPanel::Ck,
+        LabPanel::Myoglobin,
+        LabPanel::Digoxin,
+        LabPanel::Lithium,
+        LabPanel::Phenytoin,
+        LabPanel::Acetaminophen,
+        LabPanel::Salicylate,
+    ];
+
+    /// Drug levels drawn together in an overdose workup
+    pub const DRUG_LEVELS: [LabPanel; 5] = [
+        LabPanel::Digoxin,
+        LabPanel::Lithium,
+        LabPanel::Phenytoin,
+        LabPanel::Acetaminophen,
+        LabPanel::Salicylate,
+    ];
+
+    /// Typical time from draw to result (s); point-of-care tests are fastest
+    pub fn default_turnaround_s(&self) -> f64 {
+        match self {
+            LabPanel::Abg | LabPanel::Vbg | LabPanel::Lactate => 5.0 * 60.0,
+            LabPanel::Cbc => 30.0 * 60.0,
+            LabPanel::Cmp | LabPanel::Coagulation | LabPanel::Lipase | LabPanel::Ck => 45.0 * 60.0,
+            LabPanel::Troponin
+            | LabPanel::Myoglobin
+            | LabPanel::Digoxin
+            | LabPanel::Lithium
+            | LabPanel::Phenytoin
+            | LabPanel::Acetaminophen
+            | LabPanel::Salicylate => 60.0 * 60.0,
+            LabPanel::Hba1c => 4.0 * 60.0 * 60.0,
+        }
+    }
+
+    /// LOINC code and display name the panel is reported under; single-analyte
+    /// panels use the analyte's code
+    pub fn loinc(&self) -> (&'static str, &'static str) {
+        match self {
+            LabPanel::Cbc => ("58410-2", "CBC panel - Blood by Automated count"),
+            LabPanel::Cmp => ("24323-8", "Comprehensive metabolic 2000 panel - Serum or Plasma"),
+            LabPanel::Abg => ("24336-0", "Gas panel - Arterial blood"),
+            LabPanel::Vbg => ("24339-4", "Gas panel - Venous blood"),
+            LabPanel::Troponin => Analyte::Troponin.loinc(),
+            LabPanel::Coagulation => ("38875-1", "INR and PT panel - Platelet poor plasma by Coagulation assay"),
+            LabPanel::Lactate => Analyte::Lactate.loinc(),
+            LabPanel::Hba1c => Analyte::Hba1c.loinc(),
+            LabPanel::Lipase => Analyte::Lipase.loinc(),
+            LabPanel::Ck => Analyte::Ck.loinc(),
+            LabPanel::Myoglobin => Analyte::Myoglobin.loinc(),
+            LabPanel::Digoxin => Analyte::Digoxin.loinc(),
+            LabPanel::Lithium => Analyte::Lithium.loinc(),
+            LabPanel::Phenytoin => Analyte::Phenytoin.loinc(),
+            LabPanel::Acetaminophen => Analyte::Acetaminophen.loinc(),
+            LabPanel::Salicylate => Analyte::Salicylate.loinc(),
+        }
+    }
+
+    /// Analytes reported by the panel, in report order
+    pub fn analytes(&self) -> &'static [Analyte] {
+        use Analyte::*;
+        match self {
+            LabPanel::Cbc => &[Wbc, Hemoglobin, Hematocrit, Platelets, Mcv],
+            LabPanel::Cmp => &[
+                Sodium,
+                Potassium,
+                Chloride,
+                Bicarbonate,
+                Bun,
+                Creatinine,
+                Glucose,
+                Calcium,
+                Albumin,
+                TotalProtein,
+                Bilirubin,
+                Alt,
+                Ast,
+                Alp,
+            ],
+            LabPanel::Abg => &[Ph, Paco2, Pao2, ArterialBicarbonate, BaseExcess, Sao2],
+            LabPanel::Vbg => &[VenousPh, Pvco2, Pvo2, VenousBicarbonate, Svo2],
+            LabPanel::Troponin => &[Troponin],
+            LabPanel::Coagulation => &[Pt, Inr, Aptt, Fibrinogen],
+            LabPanel::Lactate => &[Lactate],
+            LabPanel::Hba1c => &[Hba1c],
+            LabPanel::Lipase => &[Lipase],
+            LabPanel::Ck => &[Ck],
+            LabPanel::Myoglobin => &[Myoglobin],
+            LabPanel::Digoxin => &[Digoxin],
+            LabPanel::Lithium => &[Lithium],
+            LabPanel::Phenytoin => &[Phenytoin],
+            LabPanel::Acetaminophen => &[Acetaminophen],
+            LabPanel::Salicylate => &[Salicylate],
+        }
+    }
+}
+
+/// Quantity reported by a laboratory test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Analyte {
+    Wbc,
+    Hemoglobin,
+    Hematocrit,
+    Platelets,
+    Mcv,
+    Sodium,
+    Potassium,
+    Chloride,
+    Bicarbonate,
+    Bun,
+    Creatinine,
+    Glucose,
+    Calcium,
+    Albumin,
+    TotalProtein,
+    Bilirubin,
+    Alt,
+    Ast,
+    Alp,
+    Ph,
+    Paco2,
+    Pao2,
+    ArterialBicarbonate,
+    BaseExcess,
+    Sao2,
+    VenousPh,
+    Pvco2,
+    Pvo2,
+    VenousBicarbonate,
+    Svo2,
+    Troponin,
+    Pt,
+    Inr,
+    Aptt,
+    Fibrinogen,
+    Lactate,
+    Hba1c,
+    Lipase,
+    Ck,
+    Myoglobin,
+    Digoxin,
+    Lithium,
+    Phenytoin,
+    Acetaminophen,
+    Salicylate,
+}
+
+impl Analyte {
+    /// Name as printed on a report
+    pub fn name(&self) -> &'static str {
+        match self {
+            Analyte::Wbc => "WBC",
+            Analyte::Hemoglobin => "Hgb",
+            Analyte::Hematocrit => "Hct",
+            Analyte::Platelets => "Plt",
+            Analyte::Mcv => "MCV",
+            Analyte::Sodium => "Na",
+            Analyte::Potassium => "K",
+            Analyte::Chloride => "Cl",
+            Analyte::Bicarbonate => "HCO3",
+            Analyte::Bun => "BUN",
+            Analyte::Creatinine => "Cr",
+            Analyte::Glucose => "Glucose",
+            Analyte::Calcium => "Ca",
+            Analyte::Albumin => "Albumin",
+            Analyte::TotalProtein => "Total protein",
+            Analyte::Bilirubin => "Bilirubin",
+            Analyte::Alt => "ALT",
+            Analyte::Ast => "AST",
+            Analyte::Alp => "ALP",
+            Analyte::Ph => "pH",
+            Analyte::Paco2 => "PaCO2",
+            Analyte::Pao2 => "PaO2",
+            Analyte::ArterialBicarbonate => "HCO3 (arterial)",
+            Analyte::BaseExcess => "Base excess",
+            Analyte::Sao2 => "SaO2",
+            Analyte::VenousPh => "pH (venous)",
+            Analyte::Pvco2 => "PvCO2",
+            Analyte::Pvo2 => "PvO2",
+            Analyte::VenousBicarbonate => "HCO3 (venous)",
+            Analyte::Svo2 => "SvO2",
+            Analyte::Troponin => "hs-Troponin T",
+            Analyte::Pt => "PT",
+            Analyte::Inr => "INR",
+            Analyte::Aptt => "aPTT",
+            Analyte::Fibrinogen => "Fibrinogen",
+            Analyte::Lactate => "Lactate",
+            Analyte::Hba1c => "HbA1c",
+            Analyte::Lipase => "Lipase",
+            Analyte::Ck => "CK",
+            Analyte::Myoglobin => "Myoglobin",
+            Analyte::Digoxin => "Digoxin",
+            Analyte::Lithium => "Lithium",
+            Analyte::Phenytoin => "Phenytoin",
+            Analyte::Acetaminophen => "Acetaminophen",
+            Analyte::Salicylate => "Salicylate",
+        }
+    }
+
+    /// Reporting units
+    pub fn units(&self) -> &'static str {
+        match self {
+            Analyte::Wbc | Analyte::Platelets => "x10^3/µL",
+            Analyte::Hemoglobin | Analyte::Albumin | Analyte::TotalProtein => "g/dL",
+            Analyte::Hematocrit | Analyte::Sao2 | Analyte::Svo2 | Analyte::Hba1c => "%",
+            Analyte::Mcv => "fL",
+            Analyte::Sodium | Analyte::Potassium | Analyte::Chloride | Analyte::Bicarbonate => "mEq/L",
+            Analyte::ArterialBicarbonate | Analyte::VenousBicarbonate | Analyte::BaseExcess => "mEq/L",
+            Analyte::Bun
+            | Analyte::Creatinine
+            | Analyte::Glucose
+            | Analyte::Calcium
+            | Analyte::Bilirubin
+            | Analyte::Fibrinogen => "mg/dL",
+            Analyte::Alt | Analyte::Ast | Analyte::Alp | Analyte::Lipase | Analyte::Ck => "U/L",
+            Analyte::Ph | Analyte::VenousPh | Analyte::Inr => "",
+            Analyte::Paco2 | Analyte::Pao2 | Analyte::Pvco2 | Analyte::Pvo2 => "mmHg",
+            Analyte::Troponin => "ng/L",
+            Analyte::Pt | Analyte::Aptt => "s",
+            Analyte::Lactate => "mmol/L",
+            Analyte::Digoxin | Analyte::Myoglobin => "ng/mL",
+            Analyte::Lithium => "mmol/L",
+            Analyte::Phenytoin | Analyte::Acetaminophen => "µg/mL",
+            Analyte::Salicylate => "mg/dL",
+        }
+    }
+
+    /// LOINC code and display name
+    pub fn loinc(&self) -> (&'static str, &'static str) {
+        match self {
+            Analyte::Wbc => ("6690-2", "Leukocytes [#/volume] in Blood by Automated count"),
+            Analyte::Hemoglobin => ("718-7", "Hemoglobin [Mass/volume] in Blood"),
+            Analyte::Hematocrit => ("4544-3", "Hematocrit [Volume Fraction] of Blood by Automated count"),
+            Analyte::Platelets => ("777-3", "Platelets [#/volume] in Blood by Automated count"),
+            Analyte::Mcv => ("787-2", "MCV [Entitic volume] by Automated count"),
+            Analyte::Sodium => ("2951-2", "Sodium [Moles/volume] in Serum or Plasma"),
+            Analyte::Potassium => ("2823-3", "Potassium [Moles/volume] in Serum or Plasma"),
+            Analyte::Chloride => ("2075-0", "Chloride [Moles/volume] in Serum or Plasma"),
+            Analyte::Bicarbonate => ("2028-9", "Carbon dioxide, total [Moles/volume] in Serum or Plasma"),
+            Analyte::Bun => ("3094-0", "Urea nitrogen [Mass/volume] in Serum or Plasma"),
+            Analyte::Creatinine => ("2160-0", "Creatinine [Mass/volume] in Serum or Plasma"),
+            Analyte::Glucose => ("2345-7", "Glucose [Mass/volume] in Serum or Plasma"),
+            Analyte::Calcium => ("17861-6", "Calcium [Mass/volume] in Serum or Plasma"),
+            Analyte::Albumin => ("1751-7", "Albumin [Mass/volume] in Serum or Plasma"),
+            Analyte::TotalProtein => ("2885-2", "Protein [Mass/volume] in Serum or Plasma"),
+            Analyte::Bilirubin => ("1975-2", "Bilirubin.total [Mass/volume] in Serum or Plasma"),
+            Analyte::Alt => ("1742-6", "Alanine aminotransferase [Enzymatic activity/volume] in Serum or Plasma"),
+            Analyte::Ast => ("1920-8", "Aspartate aminotransferase [Enzymatic activity/volume] in Serum or Plasma"),
+            Analyte::Alp => ("6768-6", "Alkaline phosphatase [Enzymatic activity/volume] in Serum or Plasma"),
+            Analyte::Ph => ("2744-1", "pH of Arterial blood"),
+            Analyte::Paco2 => ("2019-8", "Carbon dioxide [Partial pressure] in Arterial blood"),
+            Analyte::Pao2 => ("2703-7", "Oxygen [Partial pressure] in Arterial blood"),
+            Analyte::ArterialBicarbonate => ("1960-4", "Bicarbonate [Moles/volume] in Arterial blood"),
+            Analyte::BaseExcess => ("1925-7", "Base excess in Arterial blood by calculation"),
+            Analyte::Sao2 => ("2708-6", "Oxygen saturation in Arterial blood"),
+            Analyte::VenousPh => ("2746-6", "pH of Venous blood"),
+            Analyte::Pvco2 => ("2021-4", "Carbon dioxide [Partial pressure] in Venous blood"),
+            Analyte::Pvo2 => ("2705-2", "Oxygen [Partial pressure] in Venous blood"),
+            Analyte::VenousBicarbonate => ("14627-4", "Bicarbonate [Moles/volume] in Venous blood"),
+            Analyte::Svo2 => ("2711-0", "Oxygen saturation in Venous blood"),
+            Analyte::Troponin => ("67151-1", "Troponin T.cardiac [Mass/volume] in Serum or Plasma by High sensitivity method"),
+            Analyte::Pt => ("5902-2", "Prothrombin time (PT)"),
+            Analyte::Inr => ("6301-6", "INR in Platelet poor plasma by Coagulation assay"),
+            Analyte::Aptt => ("3173-2", "aPTT in Blood by Coagulation assay"),
+            Analyte::Fibrinogen => ("3255-7", "Fibrinogen [Mass/volume] in Platelet poor plasma by Coagulation assay"),
+            Analyte::Lactate => ("2524-7", "Lactate [Moles/volume] in Serum or Plasma"),
+            Analyte::Hba1c => ("4548-4", "Hemoglobin A1c/Hemoglobin.total in Blood"),
+            Analyte::Lipase => ("3040-3", "Lipase [Enzymatic activity/volume] in Serum or Plasma"),
+            Analyte::Ck => ("2157-6", "Creatine kinase [Enzymatic activity/volume] in Serum or Plasma"),
+            Analyte::Myoglobin => ("2639-3", "Myoglobin [Mass/volume] in Serum or Plasma"),
+            Analyte::Digoxin => ("10535-3", "Digoxin [Mass/volume] in Serum or Plasma"),
+            Analyte::Lithium => ("14334-7", "Lithium [Moles/volume] in Serum or Plasma"),
+            Analyte::Phenytoin => ("3968-5", "Phenytoin [Mass/volume] in Serum or Plasma"),
+            Analyte::Acetaminophen => ("3298-9", "Acetaminophen [Mass/volume] in Serum or Plasma"),
+            Analyte::Salicylate => ("4024-6", "Salicylates [Mass/volume] in Serum or Plasma"),
+        }
+    }
+
+    /// Reporting units as a UCUM code
+    pub fn ucum_units(&self) -> &'static str {
+        match self.units() {
+            "x10^3/µL" => "10*3/uL",
+            "mEq/L" => "meq/L",
+            "mmHg" => "mm[Hg]",
+            "µg/mL" => "ug/mL",
+            "" => "1",
+            units => units,
+        }
+    }
+
+    /// True value in the patient's blood
+    pub fn read(&self, patient: &Patient) -> f64 {
+        let blood = &patient.blood;
+        let (cells, chemistry, clotting, gases) = (&blood.cells, &blood.chemistry, &blood.clotting, &blood.gases);
+        let venous = &patient.oxygen_transport;
+        match self {
+            Analyte::Wbc => cells.wbc_differential.total_count() / 1000.0,
+            Analyte::Hemoglobin => cells.hemoglobin_g_dl,
+            Analyte::Hematocrit => cells.hematocrit_percent,
+            Analyte::Platelets => cells.platelet_count_thousand_per_ul,
+            Analyte::Mcv => cells.mcv_fl,
+            Analyte::Sodium => chemistry.sodium_meq_l,
+            Analyte::Potassium => chemistry.potassium_meq_l,
+            Analyte::Chloride => chemistry.chloride_meq_l,
+            Analyte::Bicarbonate => chemistry.bicarbonate_meq_l,
+            Analyte::Bun => chemistry.bun_mg_dl,
+            Analyte::Creatinine => chemistry.creatinine_mg_dl,
+            Analyte::Glucose => chemistry.glucose_mg_dl,
+            Analyte::Calcium => chemistry.calcium_mg_dl,
+            Analyte::Albumin => chemistry.albumin_g_dl,
+            Analyte::TotalProtein => chemistry.total_protein_g_dl,
+            Analyte::Bilirubin => chemistry.bilirubin_total_mg_dl,
+            Analyte::Alt => chemistry.alt_u_l,
+            Analyte::Ast => chemistry.ast_u_l,
+            Analyte::Alp => chemistry.alp_u_l,
+            Analyte::Ph => gases.ph,
+            Analyte::Paco2 => gases.paco2_mmhg,
+            Analyte::Pao2 => gases.pao2_mmhg,
+            Analyte::ArterialBicarbonate => gases.hco3_meq_l,
+            Analyte::BaseExcess => gases.base_excess_meq_l,
+            Analyte::Sao2 => gases.sao2_percent,
+            Analyte::VenousPh => venous.mixed_venous_ph,
+            Analyte::Pvco2 => venous.mixed_venous_pco2_mmhg,
+            Analyte::Pvo2 => venous.mixed_venous_po2_mmhg,
+            Analyte::VenousBicarbonate => venous.mixed_venous_hco3_meq_l,
+            Analyte::Svo2 => venous.mixed_venous_saturation_percent,
+            Analyte::Troponin => chemistry.troponin_ng_l,
+            Analyte::Pt => clotting.pt_seconds,
+            Analyte::Inr => clotting.inr,
+            Analyte::Aptt => clotting.aptt_seconds,
+            Analyte::Fibrinogen => clotting.fibrinogen_mg_dl,
+            Analyte::Lactate => chemistry.lactate_mmol_l,
+            Analyte::Hba1c => cells.hba1c_percent,
+            Analyte::Lipase => chemistry.lipase_u_l,
+            Analyte::Ck => chemistry.ck_u_l,
+            Analyte::Myoglobin => chemistry.myoglobin_ng_ml,
+            Analyte::Digoxin => patient.toxicology.level(Drug::Digoxin),
+            Analyte::Lithium => patient.toxicology.level(Drug::Lithium),
+            Analyte::Phenytoin => patient.toxicology.level(Drug::Phenytoin),
+            Analyte::Acetaminophen => patient.toxicology.level(Drug::Acetaminophen),
+            Analyte::Salicylate => patient.toxicology.level(Drug::Salicylate),
+        }
+    }
+
+    /// Analytic imprecision as (coefficient of variation, standard deviation floor)
+    ///
+    /// The standard deviation of a measurement is the larger of the relative
+    /// and absolute terms, so values near zero keep a realistic spread.
+    pub fn imprecision(&self) -> (f64, f64) {
+        match self {
+            Analyte::Wbc => (0.03, 0.1),
+            Analyte::Hemoglobin => (0.015, 0.1),
+            Analyte::Hematocrit => (0.015, 0.3),
+            Analyte::Platelets => (0.04, 3.0),
+            Analyte::Mcv => (0.01, 0.5),
+            Analyte::Sodium => (0.007, 1.0),
+            Analyte::Potassium => (0.02, 0.05),
+            Analyte::Chloride => (0.01, 1.0),
+            Analyte::Bicarbonate | Analyte::ArterialBicarbonate | Analyte::VenousBicarbonate => (0.04, 0.5),
+            Analyte::Bun => (0.04, 0.5),
+            Analyte::Creatinine => (0.04, 0.03),
+            Analyte::Glucose => (0.02, 2.0),
+            Analyte::Calcium => (0.015, 0.1),
+            Analyte::Albumin | Analyte::TotalProtein => (0.02, 0.1),
+            Analyte::Bilirubin => (0.05, 0.05),
+            Analyte::Alt | Analyte::Ast | Analyte::Alp | Analyte::Lipase | Analyte::Ck => (0.05, 2.0),
+            Analyte::Myoglobin => (0.06, 1.0),
+            Analyte::Ph | Analyte::VenousPh => (0.0, 0.01),
+            Analyte::BaseExcess => (0.0, 0.5),
+            Analyte::Paco2 | Analyte::Pvco2 => (0.02, 1.0),
+            Analyte::Pao2 | Analyte::Pvo2 => (0.03, 2.0),
+            Analyte::Sao2 | Analyte::Svo2 => (0.0, 0.5),
+            Analyte::Troponin => (0.06, 1.0),
+            Analyte::Pt | Analyte::Aptt => (0.03, 0.3),
+            Analyte::Inr => (0.03, 0.03),
+            Analyte::Fibrinogen => (0.05, 10.0),
+            Analyte::Lactate => (0.03, 0.1),
+            Analyte::Hba1c => (0.02, 0.1),
+            // Below the assays' limits of quantitation a level reads as zero
+            Analyte::Digoxin => (0.05, 0.0),
+            Analyte::Lithium => (0.03, 0.0),
+            Analyte::Phenytoin | Analyte::Acetaminophen => (0.05, 0.0),
+            Analyte::Salicylate => (0.05, 0.0),
+        }
+    }
+
+    /// Decimal places reported
+    pub fn decimals(&self) -> usize {
+        match self {
+            Analyte::Ph | Analyte::VenousPh => 2,
+            Analyte::Creatinine | Analyte::Inr | Analyte::Potassium => 1,
+            Analyte::Wbc | Analyte::Hemoglobin | Analyte::Hematocrit | Analyte::Calcium => 1,
+            Analyte::Albumin | Analyte::TotalProtein | Analyte::Bilirubin => 1,
+            Analyte::Pt | Analyte::Aptt | Analyte::Lactate | Analyte::BaseExcess => 1,
+            Analyte::Hba1c | Analyte::Digoxin | Analyte::Lithium | Analyte::Phenytoin => 1,
+            _ => 0,
+        }
+    }
+
+    /// Reference range as (low, high) for a patient's sex and age
+    pub fn reference_range(&self, demographics: &Demographics) -> (f64, f64) {
+        let male = demographics.sex == Sex::Male;
+        let age = demographics.age_years;
+        let newborn = age < NEWBORN_AGE_YEARS;
+        let child = age < ADULT_AGE_YEARS;
+        match self {
+            Analyte::Wbc if newborn => (9.0, 30.0),
+            Analyte::Wbc if child => (5.0, 15.0),
+            Analyte::Wbc => (4.5, 11.0),
+            Analyte::Hemoglobin if newborn => (14.0, 24.0),
+            Analyte::Hemoglobin if child => (11.0, 15.5),
+            Analyte::Hemoglobin if male => (13.5, 17.5),
+            Analyte::Hemoglobin => (12.0, 15.5),
+            Analyte::Hematocrit if newborn => (42.0, 65.0),
+            Analyte::Hematocrit if child => (33.0, 45.0),
+            Analyte::Hematocrit if male => (41.0, 53.0),
+            Analyte::Hematocrit => (36.0, 46.0),
+            Analyte::Platelets => (150.0, 400.0),
+            Analyte::Mcv if newborn => (95.0, 121.0),
+            Analyte::Mcv if child => (75.0, 95.0),
+            Analyte::Mcv => (80.0, 100.0),
+            Analyte::Sodium => (135.0, 145.0),
+            Analyte::Potassium if newborn => (3.7, 5.9),
+            Analyte::Potassium => (3.5, 5.0),
+            Analyte::Chloride => (98.0, 106.0),
+            Analyte::Bicarbonate | Analyte::ArterialBicarbonate => (22.0, 28.0),
+            Analyte::VenousBicarbonate => (23.0, 29.0),
+            Analyte::Bun if child => (5.0, 18.0),
+            Analyte::Bun => (7.0, 20.0),
+            Analyte::Creatinine if child => (0.3, 0.7),
+            Analyte::Creatinine if male => (0.7, 1.3),
+            Analyte::Creatinine => (0.6, 1.1),
+            Analyte::Glucose => (70.0, 100.0),
+            Analyte::Calcium => (8.5, 10.5),
+            Analyte::Albumin => (3.5, 5.0),
+            Analyte::TotalProtein => (6.0, 8.3),
+            Analyte::Bilirubin if newborn => (0.0, 12.0),
+            Analyte::Bilirubin => (0.1, 1.2),
+            Analyte::Alt if male => (7.0, 56.0),
+            Analyte::Alt => (7.0, 45.0),
+            Analyte::Ast => (10.0, 40.0),
+            Analyte::Alp if child => (100.0, 400.0),
+            Analyte::Alp => (44.0, 147.0),
+            Analyte::Ph => (7.35, 7.45),
+            Analyte::Paco2 => (35.0, 45.0),
+            Analyte::Pao2 => (80.0, 100.0),
+            Analyte::BaseExcess => (-2.0, 2.0),
+            Analyte::Sao2 => (95.0, 100.0),
+            Analyte::VenousPh => (7.31, 7.41),
+            Analyte::Pvco2 => (41.0, 51.0),
+            Analyte::Pvo2 => (35.0, 45.0),
+            Analyte::Svo2 => (65.0, 80.0),
+            // 99th percentile upper reference limits, which differ by sex
+            Analyte::Troponin if male => (0.0, 22.0),
+            Analyte::Troponin => (0.0, 14.0),
+            Analyte::Pt => (11.0, 13.5),
+            Analyte::Inr => (0.8, 1.1),
+            Analyte::Aptt => (25.0, 35.0),
+            Analyte::Fibrinogen => (200.0, 400.0),
+            Analyte::Lactate => (0.5, 2.0),
+            Analyte::Hba1c => (4.0, 5.6),
+            Analyte::Lipase => (13.0, 60.0),
+            Analyte::Ck => (30.0, 200.0),
+            Analyte::Myoglobin => (0.0, 85.0),
+            // Therapeutic ranges; acetaminophen and salicylate are ranges for
+            // analgesic and anti-inflammatory dosing
+            Analyte::Digoxin => (0.5, 2.0),
+            Analyte::Lithium => (0.6, 1.2),
+            Analyte::Phenytoin => (10.0, 20.0),
+            Analyte::Acetaminophen => (0.0, 30.0),
+            Analyte::Salicylate => (0.0, 30.0),
+        }
+    }
+
+    /// Critical limits as (low, high); beyond them a result is phoned through
+    pub fn critical_limits(&self) -> (Option<f64>, Option<f64>) {
+        match self {
+            Analyte::Wbc => (Some(1.0), Some(30.0)),
+            Analyte::Hemoglobin => (Some(7.0), None),
+            Analyte::Platelets => (Some(20.0), Some(1000.0)),
+            Analyte::Sodium => (Some(120.0), Some(160.0)),
+            Analyte::Potassium => (Some(2.5), Some(6.5)),
+            Analyte::Bicarbonate | Analyte::ArterialBicarbonate | Analyte::VenousBicarbonate => (Some(10.0), Some(40.0)),
+            Analyte::Creatinine => (None, Some(5.0)),
+            Analyte::Glucose => (Some(40.0), Some(500.0)),
+            Analyte::Calcium => (Some(6.0), Some(13.0)),
+            Analyte::Ph | Analyte::VenousPh => (Some(7.2), Some(7.6)),
+            Analyte::Paco2 => (Some(20.0), Some(70.0)),
+            Analyte::Pao2 => (Some(40.0), None),
+            Analyte::Troponin => (None, Some(100.0)),
+            Analyte::Ck => (None, Some(5000.0)),
+            Analyte::Inr => (None, Some(5.0)),
+            Analyte::Aptt => (None, Some(100.0)),
+            Analyte::Fibrinogen => (Some(100.0), None),
+            Analyte::Lactate => (None, Some(4.0)),
+            Analyte::Digoxin => (None, Some(2.5)),
+            Analyte::Lithium => (None, Some(2.0)),
+            Analyte::Phenytoin => (None, Some(30.0)),
+            // The Rumack-Matthew treatment line at four hours
+            Analyte::Acetaminophen => (None, Some(150.0)),
+            Analyte::Salicylate => (None, Some(40.0)),
+            _ => (None, None),
+        }
+    }
+
+    /// Arterial value a venous gas result estimates, by the usual fixed offsets
+    ///
+    /// Venous pH and bicarbonate track arterial closely enough to replace an ABG
+    /// when oxygenation is not in question. Venous PCO2 screens for hypercapnia
+    /// but runs far above arterial in shock, when the low flow loads each mL of
+    /// blood with more CO2. Venous PO2 and saturation reflect tissue extraction,
+    /// not the lungs, and have no arterial equivalent.
+    ///
+    /// # Returns
+    /// The arterial analyte and its estimated value, or None if the result
+    /// does not convert
+    pub fn arterial_estimate(&self, value: f64) -> Option<(Analyte, f64)> {
+        match self {
+            Analyte::VenousPh => Some((Analyte::Ph, value + 0.03)),
+            Analyte::Pvco2 => Some((Analyte::Paco2, value - 5.0)),
+            Analyte::VenousBicarbonate => Some((Analyte::ArterialBicarbonate, value - 1.0)),
+            _ => None,
+        }
+    }
+
+    /// Flag a value against the reference range and critical limits
+    pub fn flag(&self, value: f64, demographics: &Demographics) -> LabFlag {
+        let (low, high) = self.reference_range(demographics);
+        let (critical_low, critical_high) = self.critical_limits();
+        if critical_low.is_some_and(|limit| value < limit) {
+            LabFlag::CriticalLow
+        } else if critical_high.is_some_and(|limit| value > limit) {
+            LabFlag::CriticalHigh
+        } else if value < low {
+            LabFlag::Low
+        } else if value > high {
+            LabFlag::High
+        } else {
+            LabFlag::Normal
+        }
+    }
+}
+
+/// Interpretation flag of a result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabFlag {
+    Normal,
+    Low,
+    High,
+    CriticalLow,
+    CriticalHigh,
+}
+
+impl LabFlag {
+    /// Flag as printed beside a result ("" when normal)
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            LabFlag::Normal => "",
+            LabFlag::Low => "L",
+            LabFlag::High => "H",
+            LabFlag::CriticalLow => "LL",
+            LabFlag::CriticalHigh => "HH",
+        }
+    }
+
+    /// Beyond a critical limit
+    pub fn is_critical(&self) -> bool {
+        matches!(self, LabFlag::CriticalLow | LabFlag::CriticalHigh)
+    }
+}
+
+/// Reported value of one analyte
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabResult {
+    /// Analyte measured
+    pub analyte: Analyte,
+    /// Measured value, with analytic noise, rounded as reported
+    pub value: f64,
+    /// Reference range for the patient as (low, high)
+    pub reference_range: (f64, f64),
+    /// Interpretation flag
+    pub flag: LabFlag,
+}
+
+impl LabResult {
+    /// Round a measured value as reported and flag it for the patient
+    ///
+    /// # Arguments
+    /// * `analyte` - Analyte measured
+    /// * `value` - Measured value
+    /// * `demographics` - Patient the reference range is adjusted for
+    pub fn new(analyte: Analyte, value: f64, demographics: &Demographics) -> Self {
+        let scale = 10f64.powi(analyte.decimals() as i32);
+        let value = (value * scale).round() / scale;
+        Self {
+            analyte,
+            value,
+            reference_range: analyte.reference_range(demographics),
+            flag: analyte.flag(value, demographics),
+        }
+    }
+}
+
+/// Results of one order
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabReport {
+    /// Order number
+    pub id: u32,
+    /// Panel ordered
+    pub panel: LabPanel,
+    /// Time the specimen was drawn (s)
+    pub ordered_at_s: f64,
+    /// Time the results are released (s)
+    pub reported_at_s: f64,
+    /// Results, in panel order
+    pub results: Vec<LabResult>,
+}
+
+impl LabReport {
+    /// Result of an analyte, if the panel reports it
+    pub fn result(&self, analyte: Analyte) -> Option<&LabResult> {
+        self.results.iter().find(|r| r.analyte == analyte)
+    }
+
+    /// Any result is beyond a critical limit
+    pub fn has_critical(&self) -> bool {
+        self.results.iter().any(|r| r.flag.is_critical())
+    }
+
+    /// Get a human-readable report
+    pub fn get_summary(&self) -> String {
+        let mut summary = format!("{:?} #{} (drawn T+{:.0} min):", self.panel, self.id, self.ordered_at_s / 60.0);
+        for result in &self.results {
+            summary.push_str(&format!(
+                "\n  {} {:.*} {} {}",
+                result.analyte.name(),
+                result.analyte.decimals(),
+                result.value,
+                result.analyte.units(),
+                result.flag.symbol()
+            ));
+        }
+        summary
+    }
+}
+
+/// Laboratory receiving orders and releasing results after their turnaround time
+#[derive(Debug, Clone)]
+pub struct LabService {
+    /// Apply analytic imprecision to results (off gives exact values)
+    pub noise: bool,
+    /// Time since the service started (s)
+    pub time_s: f64,
+    /// Next order number
+    next_id: u32,
+    /// Generator for analytic noise, separate from the patient's variability
+    rng: SeededRng,
+    /// Turnaround times changed from the panel defaults (s)
+    turnaround_s: HashMap<LabPanel, f64>,
+    /// Orders whose results are not yet released
+    pending: Vec<LabReport>,
+    /// Released results, oldest first
+    reported: Vec<LabReport>,
+}
+
+impl LabService {
+    /// Create a laboratory with default turnaround times
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the analytic noise, so results can be replayed
+    pub fn new(seed: u64) -> Self {
+        Self {
+            noise: true,
+            time_s: 0.0,
+            next_id: 1,
+            rng: SeededRng::new(seed),
+            turnaround_s: HashMap::new(),
+            pending: Vec::new(),
+            reported: Vec::new(),
+        }
+    }
+
+    /// Set the turnaround time of a panel (s)
+    pub fn set_turnaround_s(&mut self, panel: LabPanel, turnaround_s: f64) {
+        self.turnaround_s.insert(panel, turnaround_s.max(0.0));
+    }
+
+    /// Turnaround time of a panel (s)
+    pub fn turnaround_s(&self, panel: LabPanel) -> f64 {
+        self.turnaround_s.get(&panel).copied().unwrap_or_else(|| panel.default_turnaround_s())
+    }
+
+    /// Draw a specimen and order a panel
+    ///
+    /// The specimen reflects the patient at the time of the order; its results
+    /// are released once the panel's turnaround time has passed.
+    ///
+    /// # Arguments
+    /// * `patient` - Patient the specimen is drawn from
+    /// * `panel` - Panel to run
+    ///
+    /// # Returns
+    /// Order number, used to collect the report
+    pub fn order(&mut self, patient: &Patient, panel: LabPanel) -> u32 {
+        let results = panel
+            .analytes()
+            .iter()
+            .map(|&analyte| {
+                let mut value = analyte.read(patient);
+                if self.noise {
+                    let (cv, floor) = analyte.imprecision();
+                    value += (cv * value.abs()).max(floor) * self.rng.next_normal();
+                }
+                LabResult::new(analyte, value, &patient.demographics)
+            })
+            .collect();
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.pending.push(LabReport {
+            id,
+            panel,
+            ordered_at_s: self.time_s,
+            reported_at_s: self.time_s + self.turnaround_s(panel),
+            results,
+        });
+        self.release();
+        id
+    }
+
+    /// Advance the laboratory clock, releasing results that are due
+    ///
+    /// # Arguments
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, delta_time_s: f64) {
+        self.time_s += delta_time_s.max(0.0);
+        self.release();
+    }
+
+    /// Move due orders to the released results
+    fn release(&mut self) {
+        let time_s = self.time_s;
+        let (due, pending): (Vec<_>, Vec<_>) =
+            self.pending.drain(..).partition(|report| report.reported_at_s <= time_s + 1e-9);
+        self.pending = pending;
+        self.reported.extend(due);
+    }
+
+    /// Report of an order, once released
+    pub fn report(&self, id: u32) -> Option<&LabReport> {
+        self.reported.iter().find(|report| report.id == id)
+    }
+
+    /// Released reports, oldest first
+    pub fn reports(&self) -> &[LabReport] {
+        &self.reported
+    }
+
+    /// Order has been placed but its results are not yet released
+    pub fn is_pending(&self, id: u32) -> bool {
+        self.pending.iter().any(|report| report.id == id)
+    }
+}
+
+impl Default for LabService {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::heart::{CoronaryTerritory, Heart};
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_results_arrive_after_turnaround_with_noise() {
+        let mut patient = initialize_patient(1, 12);
+        let id = patient.order_labs(LabPanel::Cbc);
+        assert!(patient.labs.is_pending(id));
+        for _ in 0..(29 * 60) {
+            update_patient(&mut patient, 1.0);
+        }
+        assert!(patient.labs.report(id).is_none());
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        let report = patient.labs.report(id).unwrap();
+        assert!(!patient.labs.is_pending(id));
+        assert_eq!(report.results.len(), 5);
+        let hemoglobin = report.result(Analyte::Hemoglobin).unwrap();
+        assert_eq!(hemoglobin.reference_range, (13.5, 17.5));
+        assert!((hemoglobin.value - 15.0).abs() < 1.0);
+
+        // Repeat draws scatter around the true value
+        let values: Vec<f64> = (0..20)
+            .map(|_| {
+                let id = patient.order_labs(LabPanel::Cmp);
+                patient.labs.update(LabPanel::Cmp.default_turnaround_s());
+                patient.labs.report(id).unwrap().result(Analyte::Sodium).unwrap().value
+            })
+            .collect();
+        assert!(values.iter().any(|&v| v != values[0]));
+        assert!(values.iter().all(|&v| (v - patient.blood.chemistry.sodium_meq_l).abs() < 5.0));
+    }
+
+    #[test]
+    fn test_flags_use_demographic_ranges() {
+        let female = Demographics::new(Sex::Female, 40.0, 165.0);
+        let male = Demographics::default();
+        let child = Demographics::new(Sex::Male, 8.0, 128.0);
+        assert_eq!(Analyte::Hemoglobin.flag(13.0, &female), LabFlag::Normal);
+        assert_eq!(Analyte::Hemoglobin.flag(13.0, &male), LabFlag::Low);
+        assert_eq!(Analyte::Hemoglobin.flag(6.5, &male), LabFlag::CriticalLow);
+        assert_eq!(Analyte::Alp.flag(300.0, &child), LabFlag::Normal);
+        assert_eq!(Analyte::Alp.flag(300.0, &male), LabFlag::High);
+        assert_eq!(Analyte::Troponin.flag(18.0, &female), LabFlag::High);
+        assert_eq!(Analyte::Troponin.flag(18.0, &male), LabFlag::Normal);
+        assert_eq!(Analyte::Potassium.flag(7.0, &male), LabFlag::CriticalHigh);
+    }
+
+    #[test]
+    fn test_troponin_rises_after_infarct() {
+        let mut patient = initialize_patient(1, 12);
+        patient.labs.noise = false;
+        let baseline = patient.order_labs(LabPanel::Troponin);
+        patient.labs.set_turnaround_s(LabPanel::Troponin, 0.0);
+        if let Some(heart) = patient.get_organ_mut::<Heart>() {
+            heart.set_territory_contractility(CoronaryTerritory::LeftAnteriorDescending, 0.0);
+        }
+        for _ in 0..(6 * 60) {
+            update_patient(&mut patient, 60.0);
+        }
+        let later = patient.order_labs(LabPanel::Troponin);
+        let troponin = |id| patient.labs.report(id).unwrap().results[0];
+        assert_eq!(troponin(baseline).flag, LabFlag::Normal);
+        assert!(troponin(later).flag.is_critical(), "{:?}", troponin(later));
+    }
+}
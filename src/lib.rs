@@ -9,22 +9,148 @@
 //! - Digestive system (Stomach, Esophagus, Intestines, Pancreas, Liver, Gallbladder)
 //! - Urinary system (Kidneys, Bladder)
 //! - Immune system (Spleen)
+//! - Integumentary system (Skin)
+//! - Muscular system (Muscle)
 //!
 //! The library also includes a comprehensive blood system based on real blood characteristics:
 //! - Blood typing (ABO and Rh factor)
 //! - Complete blood count (CBC) with differential
 //! - Comprehensive metabolic panel (CMP)
 //! - Coagulation factors
-//! - Arterial blood gas (ABG) analysis
+//! - Arterial and venous blood gas (ABG, VBG) analysis
+//!
+//! Around the organs:
+//! - `patient`: the patient, its organs and the step that advances them; a
+//!   patient can be forked to project the outcome of an intervention
+//! - Whole-body physiology: `acid_base`, `oxygen`, `fluids`, `coagulation`,
+//!   `iron`, `tissue` and the inter-organ `signals`, with the core equations
+//!   as pure functions in `models`
+//! - Conditions and populations: `sepsis`, `obstetrics`, `neonatal`, `growth`,
+//!   `menstrual`, `wounds`, `toxicology`, `presets` and `generator`
+//! - Measurement and reporting: `labs`, `monitor`, `waveform`, `pa_catheter`,
+//!   `cardiac_output`, `doppler`, `ecg_export`, `diagnosis`, `snapshot` and
+//!   `narrative`, with FHIR R4 and HL7 v2 export behind the `fhir` and `hl7` features
+//! - Driving a patient: `scenario`, `console`, `controller`, `environment` and `acls`
+//! - How a run is simulated: `fidelity`, `variability`, `difficulty`,
+//!   `stability` and `clock`, with per-organ timing behind the `telemetry`
+//!   feature and spans and events behind the `tracing` feature
+//! - Many patients and checking the model: `cohort`, `batch`, `dataset` (the
+//!   `synthetic-dataset` binary with the `cli` feature), `sweep`, `calibration`
+//!   and `validation`
+//! - Extension: new organs through `organ` and cross-cutting pathophysiology
+//!   through `process`
+//!
+//! Each module's documentation describes the physiology it models.
 
+pub mod acid_base;
+pub mod acls;
+pub mod batch;
 pub mod blood;
+pub mod calibration;
+pub mod cardiac_output;
+pub mod clock;
+pub mod coagulation;
+pub mod cohort;
+pub mod console;
+pub mod controller;
+pub mod dataset;
+pub mod diagnosis;
+pub mod difficulty;
+pub mod doppler;
+pub mod ecg_export;
+pub mod environment;
+#[cfg(feature = "fhir")]
+pub mod fhir;
+pub mod fidelity;
+pub mod fluids;
+pub mod generator;
+pub mod growth;
+#[cfg(feature = "hl7")]
+pub mod hl7;
+pub mod iron;
+pub mod labs;
+pub mod menstrual;
+pub mod models;
+pub mod monitor;
+pub mod narrative;
+pub mod neonatal;
+pub mod obstetrics;
 pub mod organ;
+pub mod oxygen;
+pub mod pa_catheter;
 pub mod patient;
+pub mod presets;
+pub mod process;
+pub mod scenario;
+pub mod sepsis;
+pub mod signals;
+pub mod snapshot;
+pub mod stability;
+pub mod sweep;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+pub mod tissue;
+mod timestamp;
+pub mod toxicology;
+mod trace;
+pub mod validation;
+pub mod variability;
+pub mod waveform;
+pub mod wounds;
 pub mod organs;
 
+pub use acid_base::{AcidBase, AcidBaseAnalysis, AcidBaseDisorder};
+pub use acls::{find_reversible_causes, AclsAction, AclsAssistant, AclsScore, CauseFinding, CodeEvent, CodeEventKind, ReversibleCause};
+pub use batch::{BatchRun, SimulationBatch, StopCondition};
 pub use blood::{AboType, RhFactor, BloodType, BloodCells, BloodChemistry, ClottingFactors, BloodGases, BloodComposition, WbcDifferential};
-pub use organ::Organ;
-pub use patient::{Patient, initialize_patient, update_patient, get_patient_summary};
+pub use calibration::{parameter_specs, Calibration, CalibrationTarget, ParameterSpec, Parameters};
+pub use cardiac_output::{LineDamping, PulseContourMeasurement, PulseContourMonitor, ThermodilutionInjection, ThermodilutionMeasurement};
+pub use clock::SimulationClock;
+pub use coagulation::Coagulation;
+pub use cohort::{ChemistryColumns, Cohort, CohortLayout};
+pub use console::{Command, Console, ConsoleError};
+pub use controller::{ClosedLoop, ControlEvent, Controller, GlucoseInsulinController, MapNorepinephrineController};
+pub use dataset::{Dataset, DatasetConfig, DiagnosisRecord, LabRecord, MedicationRecord, OutcomeRecord, VitalsRecord};
+pub use diagnosis::{CodedDiagnosis, Diagnosis, DiagnosisEngine};
+pub use difficulty::Difficulty;
+pub use doppler::{ArterialDopplerStudy, DopplerWaveform, VenousDopplerStudy};
+pub use ecg_export::{EcgRecording, WfdbRecord};
+pub use environment::{ActionDimension, Control, Environment, Observation, StepResult};
+#[cfg(feature = "fhir")]
+pub use fhir::FhirExport;
+pub use fidelity::Fidelity;
+pub use fluids::{FluidCompartments, FluidLedger, IntakeRoute, OutputRoute, IoReport};
+pub use generator::{Acuity, AcuityProfile, AcuteIllness, ComorbidityBundle, GeneratedPatient, GeneratorConfig, PatientGenerator};
+pub use growth::{Demographics, Growth, GrowthRecord, Sex};
+#[cfg(feature = "hl7")]
+pub use hl7::Hl7Export;
+pub use iron::IronStores;
+pub use labs::{Analyte, LabFlag, LabPanel, LabReport, LabResult, LabService};
+pub use menstrual::{CyclePhase, MenstrualCycle};
+pub use monitor::{Monitor, MonitorFrame, MonitorStream, NibpReading};
+pub use narrative::{CaseEvent, CaseEventKind, CaseRecorder, StateSample};
+pub use neonatal::{ApgarScore, DuctalFlow, Newborn};
+pub use obstetrics::{Pregnancy, Uterotonic};
+pub use organ::{Organ, OrganId, DEFAULT_ORGAN_PRIORITY};
+pub use oxygen::{Calorimetry, OxygenDissociation, OxygenTransport};
+pub use pa_catheter::{PaCatheterReading, ShockProfile};
+pub use patient::{MedicationDose, Patient, initialize_patient, initialize_pediatric_patient, initialize_newborn, update_patient, get_patient_summary};
+pub use presets::{ChildPugh, Preset};
+pub use process::Process;
+pub use scenario::{Action, Comparison, Condition, FiredEvent, Quantity, Scenario, ScenarioError, ScenarioEvent, ScenarioRunner, Trigger};
+pub use sepsis::{BundleElement, BundleElementStatus, BundleGuideline, ElementCompliance, Sepsis, SepsisBundle, SepsisBundleReport};
+pub use signals::{Signal, SignalBus};
+pub use snapshot::{Change, PatientSnapshot, Significance, SnapshotChange, SnapshotDiff};
+pub use stability::{physiologic_limits, PhysiologicLimit, StabilityEvent, StabilityIssue, Stabilizer};
+pub use sweep::{ParameterSweep, SweepAxis, SweepParameter, SweepRow, SweepTable};
+#[cfg(feature = "telemetry")]
+pub use telemetry::{CountingAllocator, SectionStats, Telemetry};
+pub use tissue::{TissuePerfusion, TissueState};
+pub use toxicology::{Drug, ScreenClass, ScreenResult, ToxScreen, Toxicology};
+pub use validation::{validate_all, Measure, ReferenceRange, ValidationCheck, ValidationReport, ValidationScenario};
+pub use variability::{SeededRng, Variability};
+pub use waveform::{RingBuffer, WaveformSampler};
+pub use wounds::{Wound, WoundKind};
 
 /// Calculate Body Mass Index (BMI)
 ///
@@ -41,6 +167,62 @@ pub fn calculate_bmi(weight_kg: f64, height_m: f64) -> f64 {
     weight_kg / (height_m * height_m)
 }
 
+/// Calculate body surface area by the DuBois formula
+///
+/// # Arguments
+/// * `weight_kg` - Weight in kilograms
+/// * `height_cm` - Height in centimeters
+///
+/// # Returns
+/// Body surface area (m²)
+pub fn calculate_bsa(weight_kg: f64, height_cm: f64) -> f64 {
+    if weight_kg <= 0.0 || height_cm <= 0.0 {
+        return 0.0;
+    }
+    0.007184 * weight_kg.powf(0.425) * height_cm.powf(0.725)
+}
+
+/// Calculate ideal body weight, the weight lung size and drug dosing scale with
+///
+/// Uses the Devine formula from 152.4 cm (5 ft) and the Traub-Kichen formula
+/// for shorter patients.
+///
+/// # Arguments
+/// * `height_cm` - Height in centimeters
+/// * `sex` - Biological sex
+///
+/// # Returns
+/// Ideal body weight (kg)
+pub fn calculate_ibw(height_cm: f64, sex: Sex) -> f64 {
+    if height_cm <= 0.0 {
+        return 0.0;
+    }
+    if height_cm < 152.4 {
+        return 2.396 * (0.01863 * height_cm).exp();
+    }
+    let base_kg = match sex {
+        Sex::Male => 50.0,
+        Sex::Female => 45.5,
+    };
+    base_kg + 0.906 * (height_cm - 152.4)
+}
+
+/// Calculate adjusted body weight for dosing in patients above their ideal
+/// body weight (IBW + 40% of the excess)
+///
+/// # Arguments
+/// * `weight_kg` - Actual weight in kilograms
+/// * `ideal_body_weight_kg` - Ideal body weight in kilograms
+///
+/// # Returns
+/// Adjusted body weight (kg); the actual weight when at or below ideal
+pub fn calculate_adjusted_body_weight(weight_kg: f64, ideal_body_weight_kg: f64) -> f64 {
+    if weight_kg <= ideal_body_weight_kg {
+        return weight_kg;
+    }
+    ideal_body_weight_kg + 0.4 * (weight_kg - ideal_body_weight_kg)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,4 +232,18 @@ mod tests {
         let bmi = calculate_bmi(70.0, 1.75);
         assert!((bmi - 22.86).abs() < 0.01);
     }
+
+    #[test]
+    fn test_calculate_bsa() {
+        let bsa = calculate_bsa(70.0, 175.0);
+        assert!((bsa - 1.85).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_calculate_ibw() {
+        assert!((calculate_ibw(175.0, Sex::Male) - 70.5).abs() < 0.1);
+        assert!((calculate_ibw(165.0, Sex::Female) - 56.9).abs() < 0.1);
+        assert!((calculate_adjusted_body_weight(120.0, 70.0) - 90.0).abs() < 1e-9);
+        assert_eq!(calculate_adjusted_body_weight(60.0, 70.0), 60.0);
+    }
 }
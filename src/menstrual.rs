@@ -0,0 +1,218 @@
+//! Menstrual cycle
+//!
+//! This module provides:
+//! - Cycle phases (menstrual, follicular, ovulatory, luteal) with a fixed 14-day luteal phase
+//! - Cyclical estradiol, progesterone, LH and FSH levels, and the luteal basal temperature rise
+//! - Menstrual blood loss, including heavy menstrual bleeding
+//! - Heavier bleeding with anticoagulation and thrombocytopenia
+
+use crate::fluids::SECONDS_PER_DAY;
+use crate::patient::Patient;
+
+/// Age at menarche (years)
+pub const MENARCHE_AGE_YEARS: f64 = 12.5;
+
+/// Age at menopause (years)
+pub const MENOPAUSE_AGE_YEARS: f64 = 51.0;
+
+/// Length of the luteal phase, which is constant regardless of cycle length (days)
+const LUTEAL_PHASE_DAYS: f64 = 14.0;
+
+/// Fraction of each cycle's flow lost on each day of menses
+const DAILY_FLOW_FRACTIONS: [f64; 5] = [0.30, 0.30, 0.20, 0.12, 0.08];
+
+/// Phase of the menstrual cycle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CyclePhase {
+    Menstrual,
+    Follicular,
+    Ovulatory,
+    Luteal,
+}
+
+/// Menstrual cycle state
+#[derive(Debug, Clone)]
+pub struct MenstrualCycle {
+    /// Cycle length from one menses to the next (days), normally 21-35
+    pub cycle_length_days: f64,
+    /// Days since the first day of the current menses
+    pub day_of_cycle: f64,
+    /// Current phase
+    pub phase: CyclePhase,
+    /// Estradiol (pg/mL)
+    pub estradiol_pg_ml: f64,
+    /// Progesterone (ng/mL)
+    pub progesterone_ng_ml: f64,
+    /// Luteinizing hormone (mIU/mL)
+    pub lh_miu_ml: f64,
+    /// Follicle-stimulating hormone (mIU/mL)
+    pub fsh_miu_ml: f64,
+    /// Basal body temperature rise from progesterone (°C)
+    pub basal_temperature_shift_c: f64,
+    /// Blood lost per cycle without bleeding risk factors (mL), normally ~35, heavy >80
+    pub flow_per_cycle_ml: f64,
+    /// Blood lost so far in the current cycle (mL)
+    pub current_cycle_loss_ml: f64,
+    /// Blood lost during the previous cycle (mL)
+    pub last_cycle_loss_ml: f64,
+    /// Number of completed cycles
+    pub cycles_completed: u32,
+}
+
+impl MenstrualCycle {
+    /// Start a menstrual cycle on the first day of menses
+    ///
+    /// # Arguments
+    /// * `cycle_length_days` - Cycle length in days
+    pub fn new(cycle_length_days: f64) -> Self {
+        let mut cycle = Self {
+            cycle_length_days: cycle_length_days.clamp(21.0, 45.0),
+            day_of_cycle: 0.0,
+            phase: CyclePhase::Menstrual,
+            estradiol_pg_ml: 0.0,
+            progesterone_ng_ml: 0.0,
+            lh_miu_ml: 0.0,
+            fsh_miu_ml: 0.0,
+            basal_temperature_shift_c: 0.0,
+            flow_per_cycle_ml: 35.0,
+            current_cycle_loss_ml: 0.0,
+            last_cycle_loss_ml: 0.0,
+            cycles_completed: 0,
+        };
+        cycle.update_hormones();
+        cycle
+    }
+
+    /// Set heavy menstrual bleeding (e.g. fibroids, adenomyosis, von Willebrand disease)
+    ///
+    /// # Arguments
+    /// * `flow_per_cycle_ml` - Blood lost per cycle in mL
+    pub fn set_heavy_menstrual_bleeding(&mut self, flow_per_cycle_ml: f64) {
+        self.flow_per_cycle_ml = flow_per_cycle_ml.max(0.0);
+    }
+
+    /// Get the cycle day of ovulation
+    pub fn ovulation_day(&self) -> f64 {
+        self.cycle_length_days - LUTEAL_PHASE_DAYS
+    }
+
+    /// Check whether the patient is menstruating
+    pub fn is_menstruating(&self) -> bool {
+        self.phase == CyclePhase::Menstrual
+    }
+
+    /// Bleeding multiplier from impaired hemostasis
+    ///
+    /// # Arguments
+    /// * `inr` - International normalized ratio
+    /// * `aptt_seconds` - Activated partial thromboplastin time
+    /// * `platelets_k_ul` - Platelet count (K/µL)
+    pub fn bleeding_multiplier(inr: f64, aptt_seconds: f64, platelets_k_ul: f64) -> f64 {
+        let anticoagulation = if inr > 1.5 || aptt_seconds > 45.0 {
+            let inr_effect = 1.0 + (inr - 1.0).max(0.0) * 0.8;
+            let aptt_effect = 1.0 + (aptt_seconds / 30.0 - 1.0).max(0.0) * 0.8;
+            inr_effect.max(aptt_effect).min(4.0)
+        } else {
+            1.0
+        };
+        let thrombocytopenia = if platelets_k_ul < 50.0 { 2.0 } else { 1.0 };
+        anticoagulation * thrombocytopenia
+    }
+
+    /// Set hormone levels and phase for the current cycle day
+    fn update_hormones(&mut self) {
+        let day = self.day_of_cycle;
+        let ovulation = self.ovulation_day();
+        let bump = |center: f64, width: f64| (-((day - center) / width).powi(2) / 2.0).exp();
+
+        self.phase = if day < DAILY_FLOW_FRACTIONS.len() as f64 {
+            CyclePhase::Menstrual
+        } else if day < ovulation - 1.0 {
+            CyclePhase::Follicular
+        } else if day < ovulation + 1.0 {
+            CyclePhase::Ovulatory
+        } else {
+            CyclePhase::Luteal
+        };
+
+        // Follicular estradiol peak triggers the LH surge; the corpus luteum makes progesterone
+        let mid_luteal = ovulation + 7.0;
+        self.estradiol_pg_ml = 40.0 + 210.0 * bump(ovulation - 1.0, 1.5) + 110.0 * bump(mid_luteal, 2.5);
+        self.progesterone_ng_ml = 0.5 + 14.5 * bump(mid_luteal, 2.5);
+        self.lh_miu_ml = 5.0 + 55.0 * bump(ovulation, 0.6);
+        self.fsh_miu_ml = 5.0 + 3.0 * bump(1.0, 2.5) + 10.0 * bump(ovulation, 0.6);
+        self.basal_temperature_shift_c = 0.3 * (self.progesterone_ng_ml / 5.0).min(1.0);
+    }
+
+    /// Advance the cycle and lose menstrual blood
+    ///
+    /// # Arguments
+    /// * `patient` - The patient
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let days = delta_time_s / SECONDS_PER_DAY;
+
+        if self.is_menstruating() {
+            let day_index = (self.day_of_cycle as usize).min(DAILY_FLOW_FRACTIONS.len() - 1);
+            let clotting = &patient.blood.clotting;
+            let multiplier = Self::bleeding_multiplier(
+                clotting.inr,
+                clotting.aptt_seconds,
+                patient.blood.cells.platelet_count_thousand_per_ul,
+            );
+            let loss_ml = self.flow_per_cycle_ml * DAILY_FLOW_FRACTIONS[day_index] * multiplier * days;
+            self.current_cycle_loss_ml += patient.hemorrhage(loss_ml);
+        }
+
+        self.day_of_cycle += days;
+        if self.day_of_cycle >= self.cycle_length_days {
+            self.day_of_cycle -= self.cycle_length_days;
+            self.last_cycle_loss_ml = self.current_cycle_loss_ml;
+            self.current_cycle_loss_ml = 0.0;
+            self.cycles_completed += 1;
+        }
+        self.update_hormones();
+    }
+
+    /// Get a summary of the menstrual cycle
+    pub fn get_summary(&self) -> String {
+        format!(
+            "Menstrual Cycle: Day {:.0}/{:.0} ({:?}), E2={:.0} pg/mL, P4={:.1} ng/mL, LH={:.0}, FSH={:.0} mIU/mL, \
+             Last cycle loss={:.0} mL",
+            self.day_of_cycle + 1.0,
+            self.cycle_length_days,
+            self.phase,
+            self.estradiol_pg_ml,
+            self.progesterone_ng_ml,
+            self.lh_miu_ml,
+            self.fsh_miu_ml,
+            self.last_cycle_loss_ml
+        )
+    }
+}
+
+impl Default for MenstrualCycle {
+    fn default() -> Self {
+        Self::new(28.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lh_surge_precedes_luteal_progesterone() {
+        let mut cycle = MenstrualCycle::new(28.0);
+        cycle.day_of_cycle = cycle.ovulation_day();
+        cycle.update_hormones();
+        assert_eq!(cycle.phase, CyclePhase::Ovulatory);
+        assert!(cycle.lh_miu_ml > 50.0);
+        assert!(cycle.progesterone_ng_ml < 2.0);
+
+        cycle.day_of_cycle = 21.0;
+        cycle.update_hormones();
+        assert_eq!(cycle.phase, CyclePhase::Luteal);
+        assert!(cycle.progesterone_ng_ml > 10.0);
+    }
+}
@@ -0,0 +1,78 @@
+//! Pressure-flow autoregulation
+//!
+//! This module provides:
+//! - The autoregulation curve of a vascular bed: flow held constant across a
+//!   plateau of perfusion pressures by arteriolar tone, falling with pressure
+//!   below the lower limit and rising passively above the upper limit
+//! - Reference curves for the cerebral (Lassen) and renal circulations
+
+/// Autoregulation curve of a vascular bed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoregulationCurve {
+    /// Perfusion pressure at which flow stops (mmHg)
+    pub zero_flow_pressure_mmhg: f64,
+    /// Lower limit of the autoregulatory plateau (mmHg)
+    pub lower_limit_mmhg: f64,
+    /// Upper limit of the plateau, beyond which the arterioles are forced open (mmHg)
+    pub upper_limit_mmhg: f64,
+}
+
+impl AutoregulationCurve {
+    /// Adult cerebral autoregulation: flow constant for a cerebral perfusion
+    /// pressure of 50-150 mmHg (Lassen)
+    pub fn cerebral() -> Self {
+        Self { zero_flow_pressure_mmhg: 0.0, lower_limit_mmhg: 50.0, upper_limit_mmhg: 150.0 }
+    }
+
+    /// Renal autoregulation: renal blood flow and GFR constant from the lower
+    /// limit up to ~180 mmHg in an adult (lower limit ~70 mmHg), with filtration
+    /// ceasing at ~40 mmHg
+    ///
+    /// # Arguments
+    /// * `lower_limit_mmhg` - Mean arterial pressure below which autoregulation fails
+    pub fn renal(lower_limit_mmhg: f64) -> Self {
+        let lower = lower_limit_mmhg.max(1.0);
+        Self {
+            zero_flow_pressure_mmhg: lower * 4.0 / 7.0,
+            lower_limit_mmhg: lower,
+            upper_limit_mmhg: lower * 18.0 / 7.0,
+        }
+    }
+
+    /// Flow relative to the plateau (1.0) at a perfusion pressure
+    ///
+    /// # Arguments
+    /// * `perfusion_pressure_mmhg` - Perfusion pressure across the bed (mmHg)
+    pub fn relative_flow(&self, perfusion_pressure_mmhg: f64) -> f64 {
+        let pressure = perfusion_pressure_mmhg;
+        if pressure < self.lower_limit_mmhg {
+            let span = (self.lower_limit_mmhg - self.zero_flow_pressure_mmhg).max(1e-6);
+            ((pressure - self.zero_flow_pressure_mmhg) / span).clamp(0.0, 1.0)
+        } else if pressure > self.upper_limit_mmhg {
+            // Breakthrough: maximally constricted arterioles are distended by pressure
+            pressure / self.upper_limit_mmhg
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flow_plateaus_between_limits() {
+        let cerebral = AutoregulationCurve::cerebral();
+        assert_eq!(cerebral.relative_flow(70.0), 1.0);
+        assert_eq!(cerebral.relative_flow(140.0), 1.0);
+        assert!((cerebral.relative_flow(25.0) - 0.5).abs() < 1e-9);
+        assert!(cerebral.relative_flow(180.0) > 1.1);
+
+        let renal = AutoregulationCurve::renal(70.0);
+        assert!((renal.upper_limit_mmhg - 180.0).abs() < 1e-9);
+        assert_eq!(renal.relative_flow(40.0), 0.0);
+        assert!((renal.relative_flow(55.0) - 0.5).abs() < 1e-9);
+        assert_eq!(renal.relative_flow(100.0), 1.0);
+    }
+}
@@ -0,0 +1,14 @@
+//! Physiological models
+//!
+//! This module provides:
+//! - Pure functions for the key physiological relationships the organs are built
+//!   on, one submodule per pathway
+//! - Reference parameters for each, so the mathematics can be checked against
+//!   published values independently of the simulation loop
+//!
+//! Organs call these functions rather than restating the equations inline.
+
+pub mod autoregulation;
+pub mod oxygen_dissociation;
+pub mod poiseuille;
+pub mod starling;
@@ -0,0 +1,95 @@
+//! Oxyhemoglobin dissociation
+//!
+//! This module provides:
+//! - The Severinghaus fit of the standard adult dissociation curve
+//! - The P50 shift from pH, PaCO2, temperature and 2,3-DPG (Bohr effect)
+//! - Saturation on a shifted curve and whole-blood oxygen content
+
+/// P50 of adult hemoglobin at pH 7.4, PaCO2 40 mmHg and 37 °C (mmHg)
+pub const NORMAL_P50_MMHG: f64 = 26.8;
+
+/// Normal red cell 2,3-diphosphoglycerate (mmol/L of red cells)
+pub const NORMAL_DPG_MMOL_L: f64 = 5.0;
+
+/// Normal body temperature (°C)
+const NORMAL_TEMPERATURE_C: f64 = 37.0;
+
+/// Oxygen carried per gram of saturated hemoglobin (mL)
+const HUFNER_ML_PER_G: f64 = 1.34;
+
+/// Dissolved oxygen per mmHg of PO2 (mL/dL)
+const DISSOLVED_O2_ML_DL_PER_MMHG: f64 = 0.003;
+
+/// Saturation (fraction) on the standard curve (Severinghaus 1979)
+///
+/// # Arguments
+/// * `po2_mmhg` - PO2 (mmHg)
+pub fn standard_saturation(po2_mmhg: f64) -> f64 {
+    let po2 = po2_mmhg.max(0.1);
+    1.0 / (23_400.0 / (po2.powi(3) + 150.0 * po2) + 1.0)
+}
+
+/// P50 of the curve shifted by the conditions in the blood (mmHg)
+///
+/// Severinghaus' virtual-PO2 corrections, expressed as a P50 shift, with a
+/// linear 2,3-DPG effect.
+///
+/// # Arguments
+/// * `ph` - Blood pH
+/// * `paco2_mmhg` - PaCO2 (mmHg)
+/// * `temperature_c` - Blood temperature (°C)
+/// * `dpg_mmol_l` - Red cell 2,3-DPG (mmol/L)
+pub fn shifted_p50_mmhg(ph: f64, paco2_mmhg: f64, temperature_c: f64, dpg_mmol_l: f64) -> f64 {
+    let log_shift = 0.48 * (7.4 - ph)
+        + 0.024 * (temperature_c - NORMAL_TEMPERATURE_C)
+        + 0.06 * (paco2_mmhg.max(1.0) / 40.0).log10();
+    let dpg_factor = 1.0 + 0.065 * (dpg_mmol_l.max(0.0) - NORMAL_DPG_MMOL_L);
+    (NORMAL_P50_MMHG * 10f64.powf(log_shift) * dpg_factor).clamp(8.0, 60.0)
+}
+
+/// Saturation (fraction) on a curve with the given P50
+///
+/// The PO2 is scaled onto the standard curve, which preserves its shape.
+///
+/// # Arguments
+/// * `po2_mmhg` - PO2 (mmHg)
+/// * `p50_mmhg` - P50 of the curve (mmHg)
+pub fn saturation(po2_mmhg: f64, p50_mmhg: f64) -> f64 {
+    standard_saturation(po2_mmhg * NORMAL_P50_MMHG / p50_mmhg.max(1.0))
+}
+
+/// Oxygen content of blood (mL O2/dL): hemoglobin-bound plus dissolved
+///
+/// # Arguments
+/// * `hemoglobin_g_dl` - Hemoglobin (g/dL)
+/// * `saturation` - Hemoglobin saturation (fraction)
+/// * `po2_mmhg` - PO2 (mmHg)
+pub fn oxygen_content_ml_dl(hemoglobin_g_dl: f64, saturation: f64, po2_mmhg: f64) -> f64 {
+    HUFNER_ML_PER_G * hemoglobin_g_dl * saturation + DISSOLVED_O2_ML_DL_PER_MMHG * po2_mmhg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_curve_matches_reference_points() {
+        // Half saturated at the P50; ~75% at mixed venous and ~97% at arterial PO2
+        assert!((standard_saturation(NORMAL_P50_MMHG) - 0.5).abs() < 0.01);
+        assert!((standard_saturation(40.0) - 0.75).abs() < 0.01);
+        assert!((standard_saturation(60.0) - 0.90).abs() < 0.015);
+        assert!((standard_saturation(100.0) - 0.975).abs() < 0.005);
+
+        // Acidosis, hypercapnia and fever shift the curve right; a shifted curve
+        // is half saturated at its own P50
+        let normal = shifted_p50_mmhg(7.4, 40.0, 37.0, NORMAL_DPG_MMOL_L);
+        assert!((normal - NORMAL_P50_MMHG).abs() < 1e-9);
+        let acidotic = shifted_p50_mmhg(7.2, 60.0, 39.0, NORMAL_DPG_MMOL_L);
+        assert!(acidotic > 33.0);
+        assert!((saturation(acidotic, acidotic) - 0.5).abs() < 0.01);
+        assert!(shifted_p50_mmhg(7.5, 30.0, 34.0, 2.0) < 22.0);
+
+        // 15 g/dL fully saturated carries ~20 mL/dL
+        assert!((oxygen_content_ml_dl(15.0, 0.98, 100.0) - 20.0).abs() < 0.5);
+    }
+}
@@ -0,0 +1,68 @@
+//! Poiseuille flow
+//!
+//! This module provides:
+//! - Whole-blood viscosity from hematocrit
+//! - Hagen-Poiseuille resistance of a vessel from its length, radius and the
+//!   viscosity of the blood in it
+
+/// Viscosity of plasma at 37 °C (cP)
+pub const PLASMA_VISCOSITY_CP: f64 = 1.2;
+
+/// Exponential rise in blood viscosity per unit hematocrit
+const HEMATOCRIT_VISCOSITY_COEFFICIENT: f64 = 2.5;
+
+/// Pressure of 1 mmHg in dyn/cm²
+const DYN_PER_CM2_PER_MMHG: f64 = 1333.22;
+
+/// Whole-blood viscosity at a hematocrit (cP)
+///
+/// Red cells raise viscosity exponentially, from plasma's 1.2 cP to ~3.5 cP at a
+/// normal hematocrit and roughly double that in polycythemia.
+///
+/// # Arguments
+/// * `hematocrit` - Hematocrit (fraction)
+pub fn blood_viscosity_cp(hematocrit: f64) -> f64 {
+    PLASMA_VISCOSITY_CP * (HEMATOCRIT_VISCOSITY_COEFFICIENT * hematocrit.clamp(0.0, 0.9)).exp()
+}
+
+/// Resistance of a cylindrical vessel to laminar flow (mmHg·min/mL)
+///
+/// R = 8ηL / πr⁴: resistance rises with length and viscosity and with the
+/// inverse fourth power of the radius.
+///
+/// # Arguments
+/// * `length_cm` - Vessel length (cm)
+/// * `radius_cm` - Lumen radius (cm)
+/// * `viscosity_cp` - Blood viscosity (cP)
+pub fn resistance_mmhg_min_per_ml(length_cm: f64, radius_cm: f64, viscosity_cp: f64) -> f64 {
+    if radius_cm <= 0.0 {
+        return f64::INFINITY;
+    }
+    // cP to poise gives dyn·s/cm⁵, then mmHg·s/mL and mmHg·min/mL
+    let viscosity_poise = viscosity_cp.max(0.0) / 100.0;
+    let resistance = 8.0 * viscosity_poise * length_cm.max(0.0) / (std::f64::consts::PI * radius_cm.powi(4));
+    resistance / DYN_PER_CM2_PER_MMHG / 60.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resistance_scales_with_viscosity_length_and_radius() {
+        // Plasma at zero hematocrit, ~3.5 cP at 42%, rising steeply in polycythemia
+        assert_eq!(blood_viscosity_cp(0.0), PLASMA_VISCOSITY_CP);
+        let normal = blood_viscosity_cp(0.42);
+        assert!((3.2..3.8).contains(&normal));
+        assert!(blood_viscosity_cp(0.65) > 1.7 * normal);
+
+        // Water (0.7 cP) through a 10 cm tube of 1 mm radius: 1783 dyn·s/cm⁵
+        let resistance = resistance_mmhg_min_per_ml(10.0, 0.1, 0.7);
+        assert!((resistance * DYN_PER_CM2_PER_MMHG * 60.0 - 1782.5).abs() < 1.0);
+        // Halving the radius raises resistance sixteenfold
+        let narrowed = resistance_mmhg_min_per_ml(10.0, 0.05, 0.7);
+        assert!((narrowed / resistance - 16.0).abs() < 1e-9);
+        assert!((resistance_mmhg_min_per_ml(20.0, 0.1, 1.4) / resistance - 4.0).abs() < 1e-9);
+        assert_eq!(resistance_mmhg_min_per_ml(10.0, 0.0, 0.7), f64::INFINITY);
+    }
+}
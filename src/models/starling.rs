@@ -0,0 +1,82 @@
+//! Starling relationships
+//!
+//! This module provides:
+//! - Starling's law of the capillary: net filtration pressure from hydrostatic
+//!   and oncotic pressures, with plasma oncotic pressure from total protein
+//!   (Landis-Pappenheimer)
+//! - Starling's law of the heart: the exponential end-diastolic pressure-volume
+//!   relation that sets how much filling pressure a ventricular volume needs
+
+/// Plasma colloid oncotic pressure (Landis-Pappenheimer, mmHg)
+///
+/// # Arguments
+/// * `total_protein_g_dl` - Plasma total protein (g/dL, normal ~7)
+pub fn plasma_oncotic_pressure_mmhg(total_protein_g_dl: f64) -> f64 {
+    let protein = total_protein_g_dl.max(0.0);
+    2.1 * protein + 0.16 * protein.powi(2) + 0.009 * protein.powi(3)
+}
+
+/// Net pressure driving fluid out of a capillary (mmHg, positive = filtration)
+///
+/// # Arguments
+/// * `capillary_pressure_mmhg` - Capillary hydrostatic pressure
+/// * `interstitial_pressure_mmhg` - Interstitial hydrostatic pressure
+/// * `plasma_oncotic_mmhg` - Plasma oncotic pressure
+/// * `interstitial_oncotic_mmhg` - Interstitial oncotic pressure
+/// * `reflection_coefficient` - Fraction of protein the wall holds back (0.0-1.0)
+pub fn net_filtration_pressure_mmhg(
+    capillary_pressure_mmhg: f64,
+    interstitial_pressure_mmhg: f64,
+    plasma_oncotic_mmhg: f64,
+    interstitial_oncotic_mmhg: f64,
+    reflection_coefficient: f64,
+) -> f64 {
+    (capillary_pressure_mmhg - interstitial_pressure_mmhg)
+        - reflection_coefficient.clamp(0.0, 1.0) * (plasma_oncotic_mmhg - interstitial_oncotic_mmhg)
+}
+
+/// Ventricular end-diastolic pressure at a volume (mmHg)
+///
+/// Exponential relation through zero pressure at zero volume and the reference
+/// pressure at the reference volume; the stiffness sets how steeply pressure
+/// climbs once the ventricle fills beyond it.
+///
+/// # Arguments
+/// * `volume_ml` - End-diastolic volume (mL)
+/// * `reference_volume_ml` - Volume at which the reference pressure is reached (mL)
+/// * `reference_pressure_mmhg` - Filling pressure at the reference volume (mmHg)
+/// * `stiffness` - Exponent per reference volume
+pub fn end_diastolic_pressure_mmhg(
+    volume_ml: f64,
+    reference_volume_ml: f64,
+    reference_pressure_mmhg: f64,
+    stiffness: f64,
+) -> f64 {
+    let stiffness = stiffness.max(1e-6);
+    let scale = reference_pressure_mmhg / stiffness.exp_m1();
+    scale * (stiffness * volume_ml / reference_volume_ml.max(1e-6)).exp_m1()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starling_forces_and_diastolic_filling() {
+        // Normal plasma (7 g/dL protein) exerts ~25 mmHg oncotic pressure
+        assert!((plasma_oncotic_pressure_mmhg(7.0) - 25.6).abs() < 0.2);
+        // Arterial end of a systemic capillary filters, the venous end reabsorbs
+        let oncotic = plasma_oncotic_pressure_mmhg(7.0);
+        assert!(net_filtration_pressure_mmhg(35.0, -2.0, oncotic, 5.0, 0.9) > 10.0);
+        assert!(net_filtration_pressure_mmhg(15.0, -2.0, oncotic, 5.0, 0.9) < 0.0);
+        // A leaky wall loses the oncotic opposition
+        assert!(net_filtration_pressure_mmhg(15.0, -2.0, oncotic, 5.0, 0.1) > 0.0);
+
+        // The diastolic relation passes through its reference point and steepens
+        assert!((end_diastolic_pressure_mmhg(120.0, 120.0, 8.0, 3.0) - 8.0).abs() < 1e-9);
+        assert_eq!(end_diastolic_pressure_mmhg(0.0, 120.0, 8.0, 3.0), 0.0);
+        let compliant_rise = end_diastolic_pressure_mmhg(120.0, 120.0, 8.0, 3.0) - end_diastolic_pressure_mmhg(100.0, 120.0, 8.0, 3.0);
+        let stiff_rise = end_diastolic_pressure_mmhg(160.0, 120.0, 8.0, 3.0) - end_diastolic_pressure_mmhg(140.0, 120.0, 8.0, 3.0);
+        assert!(stiff_rise > 2.0 * compliant_rise);
+    }
+}
@@ -0,0 +1,200 @@
+//! Bedside monitor stream
+//!
+//! This module provides:
+//! - Monitor frames holding the vital signs a bedside monitor displays, with
+//!   the lead II EKG recorded since the previous frame
+//! - A monitor that cycles a non-invasive blood pressure cuff at a set interval
+//! - An iterator that advances the patient and yields a frame at each display
+//!   interval, so a monitor UI does not need to poll each organ
+
+use crate::organs::heart::Heart;
+use crate::organs::lungs::Lungs;
+use crate::patient::{update_patient, Patient};
+
+/// Default time between monitor frames (s)
+const DEFAULT_FRAME_INTERVAL_S: f64 = 1.0;
+
+/// Default time between non-invasive blood pressure cycles (s)
+const DEFAULT_NIBP_INTERVAL_S: f64 = 300.0;
+
+/// Non-invasive blood pressure reading (mmHg)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NibpReading {
+    /// Systolic pressure (mmHg)
+    pub systolic_mmhg: f64,
+    /// Diastolic pressure (mmHg)
+    pub diastolic_mmhg: f64,
+    /// Mean arterial pressure (mmHg)
+    pub mean_mmhg: f64,
+    /// Monitor time the cuff reading was taken (s)
+    pub time_s: f64,
+}
+
+/// Vital signs shown on the monitor at one moment
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorFrame {
+    /// Monitor time (s)
+    pub time_s: f64,
+    /// Heart rate (bpm)
+    pub hr: f64,
+    /// Pulse oximetry saturation (%)
+    pub spo2: f64,
+    /// Most recent cuff blood pressure
+    pub nibp: Option<NibpReading>,
+    /// End-tidal CO2 (mmHg)
+    pub etco2: f64,
+    /// Respiratory rate (breaths/min)
+    pub rr: f64,
+    /// Core temperature (°C)
+    pub temp: f64,
+    /// Lead II EKG samples since the previous frame (mV)
+    pub ecg_chunk: Vec<f64>,
+    /// Sampling rate of the EKG samples (Hz)
+    pub ecg_sample_rate_hz: f64,
+}
+
+/// Bedside monitor attached to a patient
+#[derive(Debug, Clone)]
+pub struct Monitor {
+    /// Time between frames (s)
+    pub frame_interval_s: f64,
+    /// Time between non-invasive blood pressure cycles (s)
+    pub nibp_interval_s: f64,
+    /// Time since the monitor was attached (s)
+    pub time_s: f64,
+    /// Most recent cuff blood pressure
+    pub last_nibp: Option<NibpReading>,
+    /// Time of the last EKG sample already sent (s)
+    last_ecg_sample_s: f64,
+}
+
+impl Monitor {
+    /// Attach a monitor with the default one-second frames and five-minute cuff cycles
+    pub fn new() -> Self {
+        Self::with_intervals(DEFAULT_FRAME_INTERVAL_S, DEFAULT_NIBP_INTERVAL_S)
+    }
+
+    /// Attach a monitor with the given intervals
+    ///
+    /// # Arguments
+    /// * `frame_interval_s` - Time between frames (s)
+    /// * `nibp_interval_s` - Time between cuff cycles (s)
+    pub fn with_intervals(frame_interval_s: f64, nibp_interval_s: f64) -> Self {
+        Self {
+            frame_interval_s: frame_interval_s.max(0.01),
+            nibp_interval_s: nibp_interval_s.max(1.0),
+            time_s: 0.0,
+            last_nibp: None,
+            last_ecg_sample_s: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Cycle the cuff now, as when the nurse presses the NIBP button
+    pub fn measure_nibp(&mut self, patient: &Patient) {
+        let blood = &patient.blood;
+        self.last_nibp = Some(NibpReading {
+            systolic_mmhg: blood.blood_pressure_systolic,
+            diastolic_mmhg: blood.blood_pressure_diastolic,
+            mean_mmhg: blood.get_mean_arterial_pressure(),
+            time_s: self.time_s,
+        });
+    }
+
+    /// Read the current frame from the patient
+    ///
+    /// The cuff cycles when its interval has elapsed, and the EKG chunk holds
+    /// the samples recorded since the previous frame.
+    pub fn frame(&mut self, patient: &Patient) -> MonitorFrame {
+        if self.last_nibp.is_none_or(|nibp| self.time_s - nibp.time_s >= self.nibp_interval_s - 1e-9) {
+            self.measure_nibp(patient);
+        }
+        let heart = patient.get_organ::<Heart>();
+        let lungs = patient.get_organ::<Lungs>();
+        let ecg: Vec<(f64, f64)> = heart
+            .and_then(|h| h.ekg_leads.get(1).map(|lead| h.ekg_sampler.timestamped(lead)))
+            .unwrap_or_default();
+        let ecg_chunk = ecg.iter().filter(|(t, _)| *t > self.last_ecg_sample_s).map(|&(_, v)| v).collect();
+        if let Some(&(t, _)) = ecg.last() {
+            self.last_ecg_sample_s = t;
+        }
+
+        MonitorFrame {
+            time_s: self.time_s,
+            hr: heart.map_or(0.0, |h| h.heart_rate_bpm),
+            spo2: patient.blood.gases.sao2_percent,
+            nibp: self.last_nibp,
+            etco2: lungs.map_or(0.0, |l| l.end_tidal_co2_mmhg),
+            rr: lungs.map_or(0.0, |l| l.respiration_rate_bpm),
+            temp: patient.oxygen_transport.body_temperature_c,
+            ecg_chunk,
+            ecg_sample_rate_hz: heart.map_or(0.0, |h| h.ekg_sampling_rate_hz()),
+        }
+    }
+
+    /// Stream frames while advancing the patient
+    ///
+    /// Each item advances the simulation by one frame interval in steps of
+    /// `time_step_s` and returns the frame at its end. The stream is endless;
+    /// take as many frames as needed.
+    ///
+    /// # Arguments
+    /// * `patient` - Patient to simulate
+    /// * `time_step_s` - Simulation time step (s)
+    pub fn stream<'a>(&'a mut self, patient: &'a mut Patient, time_step_s: f64) -> MonitorStream<'a> {
+        MonitorStream {
+            monitor: self,
+            patient,
+            time_step_s: time_step_s.max(1e-3),
+        }
+    }
+}
+
+impl Default for Monitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Endless stream of monitor frames from a simulated patient
+pub struct MonitorStream<'a> {
+    monitor: &'a mut Monitor,
+    patient: &'a mut Patient,
+    time_step_s: f64,
+}
+
+impl Iterator for MonitorStream<'_> {
+    type Item = MonitorFrame;
+
+    fn next(&mut self) -> Option<MonitorFrame> {
+        let steps = (self.monitor.frame_interval_s / self.time_step_s).round().max(1.0) as usize;
+        for _ in 0..steps {
+            update_patient(self.patient, self.time_step_s);
+        }
+        self.monitor.time_s += steps as f64 * self.time_step_s;
+        Some(self.monitor.frame(self.patient))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::initialize_patient;
+
+    #[test]
+    fn test_stream_frames_with_ecg_chunks_and_cuff_cycles() {
+        let mut patient = initialize_patient(1, 12);
+        let mut monitor = Monitor::with_intervals(0.5, 2.0);
+        let frames: Vec<MonitorFrame> = monitor.stream(&mut patient, 0.02).take(8).collect();
+
+        assert!((frames[0].time_s - 0.5).abs() < 1e-9);
+        assert!((frames[7].time_s - 4.0).abs() < 1e-9);
+        // Half a second of EKG at 250 Hz per frame, with no overlap
+        assert!(frames.iter().all(|f| f.ecg_chunk.len() == 125));
+        assert!((frames[0].ecg_sample_rate_hz - 250.0).abs() < 1e-9);
+        assert!(frames[0].hr > 60.0 && frames[0].spo2 > 95.0 && frames[0].etco2 > 30.0);
+        // The cuff cycles at the start and every two seconds
+        assert_eq!(frames[2].nibp.unwrap().time_s, frames[0].nibp.unwrap().time_s);
+        assert!((frames[4].nibp.unwrap().time_s - 2.5).abs() < 1e-9);
+        assert!(frames[4].nibp.unwrap().systolic_mmhg > 90.0);
+    }
+}
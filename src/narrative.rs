@@ -0,0 +1,536 @@
+//! Clinical narrative of a simulation run
+//!
+//! This module provides:
+//! - A case recorder that follows a patient step by step, sampling the vital
+//!   signs and noting clinically meaningful changes: rhythm changes, acute
+//!   infarction, onset and resolution of hypotension, hypoxemia and
+//!   hyperlactatemia, and critical lab results
+//! - Interventions noted by the caller, or taken from a scenario's event log
+//! - A prose case summary ("At 14:32 the patient developed pulseless
+//!   ventricular tachycardia degenerating to ventricular fibrillation;
+//!   Defibrillate 200 J at 14:34 restored sinus rhythm.") for case reports
+//!   and debrief documents
+//!
+//! Sinus rhythms at different rates count as one rhythm, so the narrative
+//! follows rhythm changes rather than the rate crossing 60 or 100 bpm.
+
+use crate::growth::Sex;
+use crate::labs::LabFlag;
+use crate::organs::heart::{CoronaryTerritory, Heart, Rhythm};
+use crate::patient::Patient;
+use crate::scenario::FiredEvent;
+
+/// Default interval between trajectory samples (s)
+const DEFAULT_SAMPLE_INTERVAL_S: f64 = 60.0;
+
+/// Default clock time of the start of the run (08:00, seconds after midnight)
+const DEFAULT_START_CLOCK_S: f64 = 8.0 * 3600.0;
+
+/// Mean arterial pressure below which the patient is hypotensive (mmHg)
+const HYPOTENSION_MAP_MMHG: f64 = 65.0;
+
+/// Mean arterial pressure above which hypotension has resolved (mmHg)
+const HYPOTENSION_RESOLVED_MAP_MMHG: f64 = 70.0;
+
+/// Arterial saturation below which the patient is hypoxemic (%)
+const HYPOXEMIA_SAO2_PERCENT: f64 = 90.0;
+
+/// Arterial saturation above which hypoxemia has resolved (%)
+const HYPOXEMIA_RESOLVED_SAO2_PERCENT: f64 = 92.0;
+
+/// Lactate above which hypoperfusion is reported (mmol/L)
+const HYPERLACTATEMIA_MMOL_L: f64 = 4.0;
+
+/// Lactate below which hyperlactatemia has cleared (mmol/L)
+const HYPERLACTATEMIA_RESOLVED_MMOL_L: f64 = 2.0;
+
+/// Window within which a second rhythm change continues the first (s)
+const RHYTHM_SEQUENCE_WINDOW_S: f64 = 120.0;
+
+/// Window within which a rhythm change is attributed to an intervention (s)
+const RESPONSE_WINDOW_S: f64 = 30.0;
+
+/// Vital signs at one point of the run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateSample {
+    /// Time since the recorder started (s)
+    pub time_s: f64,
+    /// Cardiac rhythm, if the patient has a heart
+    pub rhythm: Option<Rhythm>,
+    /// Heart rate (bpm)
+    pub heart_rate_bpm: f64,
+    /// Systolic pressure (mmHg)
+    pub systolic_mmhg: f64,
+    /// Diastolic pressure (mmHg)
+    pub diastolic_mmhg: f64,
+    /// Arterial saturation (%)
+    pub sao2_percent: f64,
+    /// Blood lactate (mmol/L)
+    pub lactate_mmol_l: f64,
+}
+
+impl StateSample {
+    /// Sample a patient's vital signs
+    ///
+    /// # Arguments
+    /// * `patient` - Patient to sample
+    /// * `time_s` - Time of the sample (s)
+    pub fn from_patient(patient: &Patient, time_s: f64) -> Self {
+        let heart = patient.get_organ::<Heart>();
+        Self {
+            time_s,
+            rhythm: heart.map(|h| h.rhythm),
+            heart_rate_bpm: heart.map_or(0.0, |h| h.heart_rate_bpm),
+            systolic_mmhg: patient.blood.blood_pressure_systolic,
+            diastolic_mmhg: patient.blood.blood_pressure_diastolic,
+            sao2_percent: patient.blood.gases.sao2_percent,
+            lactate_mmol_l: patient.blood.chemistry.lactate_mmol_l,
+        }
+    }
+
+    /// Mean arterial pressure (mmHg)
+    pub fn mean_arterial_pressure(&self) -> f64 {
+        self.diastolic_mmhg + (self.systolic_mmhg - self.diastolic_mmhg) / 3.0
+    }
+}
+
+/// Change worth a sentence in the case summary
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaseEventKind {
+    /// The rhythm changed
+    RhythmChange { from: Rhythm, to: Rhythm },
+    /// A coronary territory became acutely infarcted
+    Infarction { territory: CoronaryTerritory },
+    /// MAP fell below 65 mmHg (lowest MAP seen when the event was noted)
+    Hypotension { map_mmhg: f64 },
+    /// MAP recovered above 70 mmHg
+    HypotensionResolved { map_mmhg: f64 },
+    /// SaO2 fell below 90%
+    Hypoxemia { sao2_percent: f64 },
+    /// SaO2 recovered above 92%
+    HypoxemiaResolved { sao2_percent: f64 },
+    /// Lactate rose above 4 mmol/L
+    Hyperlactatemia { lactate_mmol_l: f64 },
+    /// Lactate fell below 2 mmol/L
+    HyperlactatemiaResolved { lactate_mmol_l: f64 },
+    /// A lab report came back with critical results, as "K 7.0 mEq/L"
+    CriticalLabs { panel: String, results: Vec<String> },
+    /// An intervention, as described by the caller
+    Intervention(String),
+}
+
+/// Timestamped case event
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseEvent {
+    /// Time since the recorder started (s)
+    pub time_s: f64,
+    pub kind: CaseEventKind,
+}
+
+/// Rhythm as named in prose
+fn rhythm_name(rhythm: Rhythm) -> &'static str {
+    match rhythm {
+        Rhythm::NormalSinus | Rhythm::SinusBradycardia | Rhythm::SinusTachycardia => "sinus rhythm",
+        Rhythm::Asystole => "asystole",
+        Rhythm::Paced => "a paced rhythm",
+        Rhythm::VentricularFibrillation => "ventricular fibrillation",
+        Rhythm::PulselessVentricularTachycardia => "pulseless ventricular tachycardia",
+        Rhythm::PulselessElectricalActivity => "pulseless electrical activity",
+        Rhythm::TorsadesDePointes => "torsades de pointes",
+        Rhythm::AtrialFibrillation => "atrial fibrillation",
+        Rhythm::AtrialFlutter => "atrial flutter",
+        Rhythm::SupraventricularTachycardia => "supraventricular tachycardia",
+        Rhythm::CompleteHeartBlock => "complete heart block",
+    }
+}
+
+/// Whether a rhythm is one of the sinus rhythms
+fn is_sinus(rhythm: Rhythm) -> bool {
+    matches!(rhythm, Rhythm::NormalSinus | Rhythm::SinusBradycardia | Rhythm::SinusTachycardia)
+}
+
+/// Recorder of a patient's course, for the case narrative
+#[derive(Debug, Clone)]
+pub struct CaseRecorder {
+    /// Clock time of the start of the run (seconds after midnight)
+    pub start_clock_s: f64,
+    /// Interval between trajectory samples (s)
+    pub sample_interval_s: f64,
+    /// Time since the recorder started (s)
+    pub time_s: f64,
+    /// Vital signs at each sample, oldest first
+    samples: Vec<StateSample>,
+    /// Case events, oldest first
+    events: Vec<CaseEvent>,
+    /// Rhythm at the last step
+    rhythm: Option<Rhythm>,
+    /// Territories already reported infarcted
+    infarcted: Vec<CoronaryTerritory>,
+    /// Whether the patient is currently hypotensive, hypoxemic and hyperlactatemic
+    hypotensive: bool,
+    hypoxemic: bool,
+    hyperlactatemic: bool,
+    /// Lab reports already seen
+    reports_seen: usize,
+}
+
+impl CaseRecorder {
+    /// Create a recorder starting at 08:00, sampling every minute
+    pub fn new() -> Self {
+        Self {
+            start_clock_s: DEFAULT_START_CLOCK_S,
+            sample_interval_s: DEFAULT_SAMPLE_INTERVAL_S,
+            time_s: 0.0,
+            samples: Vec::new(),
+            events: Vec::new(),
+            rhythm: None,
+            infarcted: Vec::new(),
+            hypotensive: false,
+            hypoxemic: false,
+            hyperlactatemic: false,
+            reports_seen: 0,
+        }
+    }
+
+    /// Clock time of a run time, as HH:MM
+    ///
+    /// # Arguments
+    /// * `time_s` - Time since the recorder started (s)
+    pub fn clock(&self, time_s: f64) -> String {
+        let minutes = ((self.start_clock_s + time_s) / 60.0).floor() as i64;
+        format!("{:02}:{:02}", minutes.div_euclid(60).rem_euclid(24), minutes.rem_euclid(60))
+    }
+
+    /// Follow the patient after a step
+    ///
+    /// # Arguments
+    /// * `patient` - Patient after the step
+    /// * `delta_time_s` - Length of the step (s); 0 for the initial state
+    pub fn record(&mut self, patient: &Patient, delta_time_s: f64) {
+        self.time_s += delta_time_s.max(0.0);
+        let sample = StateSample::from_patient(patient, self.time_s);
+        let due = self
+            .samples
+            .last()
+            .is_none_or(|last| self.time_s - last.time_s + 1e-9 >= self.sample_interval_s);
+        if due {
+            self.samples.push(sample);
+        }
+
+        if let (Some(from), Some(to)) = (self.rhythm, sample.rhythm) {
+            if from != to && !(is_sinus(from) && is_sinus(to)) {
+                self.push(CaseEventKind::RhythmChange { from, to });
+            }
+        }
+        self.rhythm = sample.rhythm;
+        let infarcted = patient.get_organ::<Heart>().map(|h| h.infarcted_territories()).unwrap_or_default();
+        for &territory in &infarcted {
+            if !self.infarcted.contains(&territory) {
+                self.push(CaseEventKind::Infarction { territory });
+            }
+        }
+        self.infarcted = infarcted;
+
+        let map = sample.mean_arterial_pressure();
+        if !self.hypotensive && map < HYPOTENSION_MAP_MMHG {
+            self.hypotensive = true;
+            self.push(CaseEventKind::Hypotension { map_mmhg: map });
+        } else if self.hypotensive && map > HYPOTENSION_RESOLVED_MAP_MMHG {
+            self.hypotensive = false;
+            self.push(CaseEventKind::HypotensionResolved { map_mmhg: map });
+        }
+        let sao2 = sample.sao2_percent;
+        if !self.hypoxemic && sao2 < HYPOXEMIA_SAO2_PERCENT {
+            self.hypoxemic = true;
+            self.push(CaseEventKind::Hypoxemia { sao2_percent: sao2 });
+        } else if self.hypoxemic && sao2 > HYPOXEMIA_RESOLVED_SAO2_PERCENT {
+            self.hypoxemic = false;
+            self.push(CaseEventKind::HypoxemiaResolved { sao2_percent: sao2 });
+        }
+        let lactate = sample.lactate_mmol_l;
+        if !self.hyperlactatemic && lactate > HYPERLACTATEMIA_MMOL_L {
+            self.hyperlactatemic = true;
+            self.push(CaseEventKind::Hyperlactatemia { lactate_mmol_l: lactate });
+        } else if self.hyperlactatemic && lactate < HYPERLACTATEMIA_RESOLVED_MMOL_L {
+            self.hyperlactatemic = false;
+            self.push(CaseEventKind::HyperlactatemiaResolved { lactate_mmol_l: lactate });
+        }
+
+        let reports = patient.labs.reports();
+        for report in reports.iter().skip(self.reports_seen) {
+            let results: Vec<String> = report
+                .results
+                .iter()
+                .filter(|result| result.flag.is_critical())
+                .map(|result| {
+                    let direction = if result.flag == LabFlag::CriticalLow { "low" } else { "high" };
+                    format!(
+                        "{} {:.*} {} ({})",
+                        result.analyte.name(),
+                        result.analyte.decimals(),
+                        result.value,
+                        result.analyte.units(),
+                        direction
+                    )
+                    .replace("  ", " ")
+                })
+                .collect();
+            if !results.is_empty() {
+                self.push(CaseEventKind::CriticalLabs {
+                    panel: format!("{:?}", report.panel),
+                    results,
+                });
+            }
+        }
+        self.reports_seen = reports.len();
+    }
+
+    /// Note an intervention at the current time
+    ///
+    /// # Arguments
+    /// * `description` - What was done, e.g. "Epinephrine 1 mg IV"
+    pub fn note_intervention(&mut self, description: impl Into<String>) {
+        self.push(CaseEventKind::Intervention(description.into()));
+    }
+
+    /// Note the interventions a scenario made, at the times they were made
+    ///
+    /// Scripted insults are left out: the recorder reports their consequences.
+    ///
+    /// # Arguments
+    /// * `fired` - Events from the scenario's log, with times on the recorder's clock
+    pub fn note_scenario_events(&mut self, fired: &[FiredEvent]) {
+        for event in fired.iter().filter(|event| !event.action.is_insult()) {
+            self.events.push(CaseEvent {
+                time_s: event.time_s,
+                kind: CaseEventKind::Intervention(event.action.to_string()),
+            });
+        }
+        self.events.sort_by(|a, b| a.time_s.total_cmp(&b.time_s));
+    }
+
+    fn push(&mut self, kind: CaseEventKind) {
+        self.events.push(CaseEvent { time_s: self.time_s, kind });
+    }
+
+    /// Vital signs at each sample, oldest first
+    pub fn samples(&self) -> &[StateSample] {
+        &self.samples
+    }
+
+    /// Case events, oldest first
+    pub fn events(&self) -> &[CaseEvent] {
+        &self.events
+    }
+
+    /// Write the case summary
+    ///
+    /// # Arguments
+    /// * `patient` - Patient the run followed, for the demographics and final state
+    ///
+    /// # Returns
+    /// Prose summary: presentation, course and final state, one paragraph each
+    pub fn narrative(&self, patient: &Patient) -> String {
+        let sex = match patient.demographics.sex {
+            Sex::Male => "man",
+            Sex::Female => "woman",
+        };
+        let vitals = |sample: &StateSample| {
+            format!(
+                "heart rate {:.0} bpm, blood pressure {:.0}/{:.0} mmHg, SaO2 {:.0}% and lactate {:.1} mmol/L",
+                sample.heart_rate_bpm, sample.systolic_mmhg, sample.diastolic_mmhg, sample.sao2_percent, sample.lactate_mmol_l
+            )
+        };
+        let mut paragraphs = Vec::new();
+
+        let mut presentation = format!(
+            "Patient {}, a {:.0}-year-old {}, was followed for {:.0} minutes.",
+            patient.id,
+            patient.demographics.age_years,
+            sex,
+            self.time_s / 60.0
+        );
+        if let Some(first) = self.samples.first() {
+            let rhythm = first.rhythm.map_or("no cardiac rhythm".to_string(), |r| rhythm_name(r).to_string());
+            presentation.push_str(&format!(
+                " At {} the patient was in {} with {}.",
+                self.clock(first.time_s),
+                rhythm,
+                vitals(first)
+            ));
+        }
+        paragraphs.push(presentation);
+
+        let course = self.course();
+        if course.is_empty() {
+            paragraphs.push("The course was uneventful.".to_string());
+        } else {
+            paragraphs.push(course.join(" "));
+        }
+
+        let last = StateSample::from_patient(patient, self.time_s);
+        let mut outcome = format!(
+            "At {} the patient was in {} with {}.",
+            self.clock(last.time_s),
+            last.rhythm.map_or("no cardiac rhythm", rhythm_name),
+            vitals(&last)
+        );
+        if let Some(lowest) = self
+            .samples
+            .iter()
+            .min_by(|a, b| a.mean_arterial_pressure().total_cmp(&b.mean_arterial_pressure()))
+            .filter(|lowest| lowest.mean_arterial_pressure() < HYPOTENSION_MAP_MMHG)
+        {
+            outcome.push_str(&format!(
+                " The lowest recorded MAP was {:.0} mmHg at {}.",
+                lowest.mean_arterial_pressure(),
+                self.clock(lowest.time_s)
+            ));
+        }
+        paragraphs.push(outcome);
+        paragraphs.join("\n\n")
+    }
+
+    /// Sentences describing the events, combining related ones
+    fn course(&self) -> Vec<String> {
+        let mut consumed = vec![false; self.events.len()];
+        let mut sentences = Vec::new();
+        for (i, event) in self.events.iter().enumerate() {
+            if consumed[i] {
+                continue;
+            }
+            let at = self.clock(event.time_s);
+            // The next rhythm change within a window, unless another intervention comes first
+            let next_rhythm = |window_s: f64| {
+                let (j, next) = self.events.iter().enumerate().skip(i + 1).find(|(_, next)| {
+                    matches!(next.kind, CaseEventKind::RhythmChange { .. } | CaseEventKind::Intervention(_))
+                })?;
+                match next.kind {
+                    CaseEventKind::RhythmChange { to, .. } if next.time_s - event.time_s <= window_s => {
+                        Some((j, to))
+                    }
+                    _ => None,
+                }
+            };
+            let sentence = match &event.kind {
+                CaseEventKind::RhythmChange { to, .. } => match next_rhythm(RHYTHM_SEQUENCE_WINDOW_S) {
+                    Some((j, then)) if !is_sinus(*to) && !is_sinus(then) => {
+                        consumed[j] = true;
+                        format!(
+                            "At {} the patient developed {} degenerating to {}.",
+                            at,
+                            rhythm_name(*to),
+                            rhythm_name(then)
+                        )
+                    }
+                    _ if is_sinus(*to) => format!("At {} sinus rhythm returned.", at),
+                    _ => format!("At {} the patient developed {}.", at, rhythm_name(*to)),
+                },
+                CaseEventKind::Intervention(description) => match next_rhythm(RESPONSE_WINDOW_S) {
+                    Some((j, then)) => {
+                        consumed[j] = true;
+                        let verb = if is_sinus(then) { "restored" } else { "was followed by" };
+                        format!("{} at {} {} {}.", description, at, verb, rhythm_name(then))
+                    }
+                    None => format!("{} at {}.", description, at),
+                },
+                CaseEventKind::Infarction { territory } => {
+                    let artery = match territory {
+                        CoronaryTerritory::LeftAnteriorDescending => "left anterior descending",
+                        CoronaryTerritory::LeftCircumflex => "circumflex",
+                        CoronaryTerritory::RightCoronary => "right coronary",
+                    };
+                    format!("At {} the patient developed an ST-elevation infarction in the {} territory.", at, artery)
+                }
+                CaseEventKind::Hypotension { map_mmhg } => {
+                    format!("At {} the patient became hypotensive (MAP {:.0} mmHg).", at, map_mmhg)
+                }
+                CaseEventKind::HypotensionResolved { map_mmhg } => {
+                    format!("Blood pressure recovered by {} (MAP {:.0} mmHg).", at, map_mmhg)
+                }
+                CaseEventKind::Hypoxemia { sao2_percent } => {
+                    format!("At {} the patient became hypoxemic (SaO2 {:.0}%).", at, sao2_percent)
+                }
+                CaseEventKind::HypoxemiaResolved { sao2_percent } => {
+                    format!("Oxygenation recovered by {} (SaO2 {:.0}%).", at, sao2_percent)
+                }
+                CaseEventKind::Hyperlactatemia { lactate_mmol_l } => {
+                    format!("Lactate rose to {:.1} mmol/L by {}.", lactate_mmol_l, at)
+                }
+                CaseEventKind::HyperlactatemiaResolved { lactate_mmol_l } => {
+                    format!("Lactate cleared to {:.1} mmol/L by {}.", lactate_mmol_l, at)
+                }
+                CaseEventKind::CriticalLabs { panel, results } => {
+                    format!("The {} panel resulted at {} with critical values: {}.", panel, at, results.join(", "))
+                }
+            };
+            sentences.push(sentence);
+        }
+        sentences
+    }
+}
+
+impl Default for CaseRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::labs::LabPanel;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_narrative_of_an_arrest_and_resuscitation() {
+        let mut patient = initialize_patient(7, 3);
+        patient.labs.noise = false;
+        patient.labs.set_turnaround_s(LabPanel::Cmp, 60.0);
+        let mut recorder = CaseRecorder::new();
+        recorder.start_clock_s = 14.0 * 3600.0 + 30.0 * 60.0;
+        recorder.record(&patient, 0.0);
+
+        let dt = 0.5;
+        for step in 1..=1200 {
+            let time_s = step as f64 * dt;
+            if step == 240 {
+                if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                    heart.induce_pulseless_ventricular_tachycardia();
+                }
+            }
+            if step == 300 {
+                if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                    heart.induce_ventricular_fibrillation();
+                }
+            }
+            if step == 320 {
+                patient.start_cpr();
+                recorder.note_intervention("CPR started");
+            }
+            if step == 490 {
+                patient.defibrillate(200.0);
+                recorder.note_intervention("Defibrillation at 200 J");
+                patient.stop_cpr();
+                patient.blood.chemistry.potassium_meq_l = 7.0;
+                patient.order_labs(LabPanel::Cmp);
+                patient.blood.chemistry.potassium_meq_l = 4.0;
+            }
+            update_patient(&mut patient, dt);
+            recorder.record(&patient, dt);
+            assert!((recorder.time_s - time_s).abs() < 1e-9);
+        }
+
+        let text = recorder.narrative(&patient);
+        assert!(text.starts_with("Patient 7, a "));
+        assert!(text.contains("At 14:30 the patient was in sinus rhythm with heart rate"));
+        assert!(text.contains(
+            "At 14:32 the patient developed pulseless ventricular tachycardia degenerating to ventricular fibrillation."
+        ));
+        assert!(text.contains("Defibrillation at 200 J at 14:34 restored sinus rhythm."));
+        assert!(text.contains("became hypotensive"));
+        assert!(text.contains("with critical values: K 7.0 mEq/L (high)"));
+        assert_eq!(recorder.samples().len(), 11);
+        assert_eq!(recorder.clock(10.5 * 3600.0), "01:00");
+    }
+}
@@ -0,0 +1,388 @@
+//! Newborn transition physiology
+//!
+//! This module provides:
+//! - Fetal-to-neonatal circulatory transition (lung aeration, falling pulmonary
+//!   vascular resistance, foramen ovale and ductus arteriosus shunting)
+//! - Birth depression with primary and secondary apnea
+//! - Neonatal heat loss
+//! - APGAR scoring at 1 and 5 minutes (and every 5 minutes while below 7)
+//! - NRP interventions: drying and warming, stimulation, positive-pressure ventilation
+
+use crate::growth;
+use crate::organs::lungs::{self, Ventilator};
+use crate::organs::heart;
+use crate::patient::Patient;
+
+/// Delivery room temperature (°C)
+const ROOM_TEMPERATURE_C: f64 = 25.0;
+
+/// Direction of blood flow through the ductus arteriosus
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuctalFlow {
+    /// Pulmonary to systemic (fetal pattern, pulmonary hypertension)
+    RightToLeft,
+    /// Flow in both directions during the cardiac cycle
+    Bidirectional,
+    /// Systemic to pulmonary (after pulmonary resistance falls)
+    LeftToRight,
+    /// Functionally closed
+    Closed,
+}
+
+/// APGAR score; each component is scored 0-2
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ApgarScore {
+    /// Minutes after birth at which the score was assigned
+    pub minute: u32,
+    /// Skin color
+    pub appearance: u8,
+    /// Heart rate
+    pub pulse: u8,
+    /// Reflex irritability
+    pub grimace: u8,
+    /// Muscle tone
+    pub activity: u8,
+    /// Breathing effort
+    pub respiration: u8,
+}
+
+impl ApgarScore {
+    /// Total score (0-10)
+    pub fn total(&self) -> u8 {
+        self.appearance + self.pulse + self.grimace + self.activity + self.respiration
+    }
+}
+
+/// Newborn transition state
+#[derive(Debug, Clone)]
+pub struct Newborn {
+    /// Gestational age at birth (weeks)
+    pub gestational_age_weeks: f64,
+    /// Birth weight (kg)
+    pub birth_weight_kg: f64,
+    /// Time since birth (s)
+    pub time_since_birth_s: f64,
+    /// Fetal lung fluid remaining in the airspaces (1.0 at birth, 0.0 = fully aerated)
+    pub lung_fluid: f64,
+    /// Pulmonary vascular resistance relative to the fetal state (1.0 fetal, ~0.15 transitioned)
+    pub pulmonary_vascular_resistance: f64,
+    /// Right-to-left shunt through the foramen ovale (fraction of venous return)
+    pub foramen_ovale_shunt: f64,
+    /// Patency of the ductus arteriosus (1.0 = widely open, 0.0 = closed)
+    pub ductus_patency: f64,
+    /// Right-to-left shunt through the ductus arteriosus (fraction of cardiac output)
+    pub ductal_shunt: f64,
+    /// Current ductal flow direction
+    pub ductal_flow: DuctalFlow,
+    /// Perinatal depression (0.0 = vigorous, 1.0 = severely depressed)
+    pub birth_depression: f64,
+    /// Core temperature (°C)
+    pub temperature_c: f64,
+    /// Whether the baby has been dried
+    pub is_dried: bool,
+    /// Whether the baby is under a radiant warmer
+    pub is_warmed: bool,
+    /// Postductal (lower limb) oxygen saturation (%)
+    pub postductal_spo2_percent: f64,
+    /// APGAR scores assigned so far
+    pub apgar_scores: Vec<ApgarScore>,
+    /// Temporary breathing effort from tactile stimulation
+    stimulation_effect: f64,
+    /// FiO2 for positive-pressure ventilation, if PPV has been requested
+    ppv_fio2: Option<f64>,
+    /// Whether the ventilator attached to the lungs is this model's PPV
+    ppv_attached: bool,
+}
+
+impl Newborn {
+    /// Create a newborn at the moment of birth
+    ///
+    /// # Arguments
+    /// * `gestational_age_weeks` - Gestational age at birth in weeks
+    /// * `birth_weight_kg` - Birth weight in kilograms
+    pub fn new(gestational_age_weeks: f64, birth_weight_kg: f64) -> Self {
+        Self {
+            gestational_age_weeks: gestational_age_weeks.clamp(22.0, 44.0),
+            birth_weight_kg: birth_weight_kg.max(0.3),
+            time_since_birth_s: 0.0,
+            lung_fluid: 1.0,
+            pulmonary_vascular_resistance: 1.0,
+            foramen_ovale_shunt: 0.2,
+            ductus_patency: 1.0,
+            ductal_shunt: 0.2,
+            ductal_flow: DuctalFlow::RightToLeft,
+            birth_depression: 0.0,
+            temperature_c: 37.0,
+            is_dried: false,
+            is_warmed: false,
+            postductal_spo2_percent: 60.0,
+            apgar_scores: Vec::new(),
+            stimulation_effect: 0.0,
+            ppv_fio2: None,
+            ppv_attached: false,
+        }
+    }
+
+    /// Perinatal depression from intrapartum asphyxia
+    ///
+    /// # Arguments
+    /// * `severity` - 0.0-1.0; above ~0.6 the baby is in secondary apnea and
+    ///   will not breathe with stimulation alone
+    pub fn induce_birth_depression(&mut self, severity: f64) {
+        self.birth_depression = severity.clamp(0.0, 1.0);
+    }
+
+    /// Dry the baby and place it under a radiant warmer
+    pub fn dry_and_warm(&mut self) {
+        self.is_dried = true;
+        self.is_warmed = true;
+    }
+
+    /// Rub the back or flick the soles to stimulate breathing
+    pub fn stimulate(&mut self) {
+        self.stimulation_effect = 0.6;
+    }
+
+    /// Start positive-pressure ventilation (40 breaths/min, PEEP 5, ~6 mL/kg)
+    ///
+    /// # Arguments
+    /// * `fio2` - Blended oxygen fraction (NRP starts term babies at 0.21)
+    pub fn start_ppv(&mut self, fio2: f64) {
+        self.ppv_fio2 = Some(fio2.clamp(0.21, 1.0));
+    }
+
+    /// Stop positive-pressure ventilation
+    pub fn stop_ppv(&mut self) {
+        self.ppv_fio2 = None;
+    }
+
+    /// Whether positive-pressure ventilation is being given
+    pub fn is_receiving_ppv(&self) -> bool {
+        self.ppv_fio2.is_some()
+    }
+
+    /// Most recent APGAR score
+    pub fn latest_apgar(&self) -> Option<&ApgarScore> {
+        self.apgar_scores.last()
+    }
+
+    /// Advance the transition
+    ///
+    /// # Arguments
+    /// * `patient` - The newborn patient
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let previous_minute = (self.time_since_birth_s / 60.0) as u32;
+        self.time_since_birth_s += delta_time_s;
+
+        let pao2 = patient.blood.gases.pao2_mmhg;
+        self.update_temperature(delta_time_s);
+        self.update_ppv(patient);
+        let drive = self.update_breathing(pao2, delta_time_s);
+
+        // Breaths (crying or PPV) push fetal lung fluid into the interstitium;
+        // preterm lungs lacking surfactant clear it more slowly
+        let effort = if self.ppv_attached { 1.0 } else { drive.min(1.0) };
+        let surfactant_factor = ((self.gestational_age_weeks - 24.0) / 10.0).clamp(0.2, 1.0);
+        self.lung_fluid *= (-effort * surfactant_factor * delta_time_s / 180.0).exp();
+
+        // Aeration and oxygen dilate the pulmonary vessels; hypoxia constricts them
+        let hypoxic_vasoconstriction = 0.5 * ((50.0 - pao2) / 30.0).clamp(0.0, 1.0);
+        let target_resistance = 0.15 + 0.85 * self.lung_fluid + hypoxic_vasoconstriction;
+        self.pulmonary_vascular_resistance += (target_resistance - self.pulmonary_vascular_resistance)
+            * (delta_time_s / 120.0).min(1.0);
+        let resistance = self.pulmonary_vascular_resistance;
+
+        // The foramen ovale flap closes once left atrial pressure exceeds right
+        self.foramen_ovale_shunt = 0.2 * ((resistance - 0.4) / 0.6).clamp(0.0, 1.0);
+
+        // Oxygen constricts the ductus over the first day; preterm ducts respond less
+        if pao2 > 50.0 {
+            self.ductus_patency -= surfactant_factor * delta_time_s / 43_200.0;
+            self.ductus_patency = self.ductus_patency.max(0.0);
+        }
+        self.ductal_shunt = 0.2 * self.ductus_patency * ((resistance - 0.5) / 0.5).clamp(0.0, 1.0);
+        self.ductal_flow = if self.ductus_patency < 0.05 {
+            DuctalFlow::Closed
+        } else if resistance > 0.55 {
+            DuctalFlow::RightToLeft
+        } else if resistance > 0.45 {
+            DuctalFlow::Bidirectional
+        } else {
+            DuctalFlow::LeftToRight
+        };
+
+        // Right hand (preductal) saturation sees the intracardiac and lung shunts;
+        // the lower body also receives ductal blood
+        if let Some(lungs) = patient.get_organ_mut::<lungs::Lungs>() {
+            lungs.respiratory_drive = drive;
+            lungs.right_to_left_shunt_fraction = self.foramen_ovale_shunt + 0.5 * self.lung_fluid;
+        }
+        let preductal = patient.blood.gases.sao2_percent;
+        let mixed_venous = (preductal - 25.0).max(0.0);
+        self.postductal_spo2_percent = preductal * (1.0 - self.ductal_shunt) + mixed_venous * self.ductal_shunt;
+
+        // Hypoxia slows the heart; cold stress adds to it
+        let hypoxic_factor = ((pao2 - 10.0) / 25.0).clamp(0.2, 1.0);
+        let cold_factor = 1.0 - (35.0 - self.temperature_c).max(0.0) * 0.05;
+        let resting_rate = growth::resting_heart_rate_for_age(patient.demographics.age_years);
+        if let Some(heart) = patient.get_organ_mut::<heart::Heart>() {
+            heart.resting_heart_rate_bpm = resting_rate * hypoxic_factor * cold_factor.max(0.5);
+        }
+
+        // APGAR at 1 and 5 minutes, then every 5 minutes up to 20 minutes while below 7
+        let minute = (self.time_since_birth_s / 60.0) as u32;
+        if minute > previous_minute {
+            let due = minute == 1
+                || minute == 5
+                || (minute.is_multiple_of(5) && minute <= 20 && self.latest_apgar().is_some_and(|s| s.total() < 7));
+            if due {
+                let score = self.score_apgar(patient, minute);
+                self.apgar_scores.push(score);
+            }
+        }
+    }
+
+    /// Attach or remove the PPV device on the lungs
+    fn update_ppv(&mut self, patient: &mut Patient) {
+        let Some(lungs) = patient.get_organ_mut::<lungs::Lungs>() else {
+            return;
+        };
+        match self.ppv_fio2 {
+            Some(fio2) => {
+                if lungs.ventilator.is_none() {
+                    let tidal_volume_ml = self.birth_weight_kg * 6.0;
+                    lungs.attach_ventilator(Ventilator::new(fio2, 5.0, tidal_volume_ml, 40.0));
+                    self.ppv_attached = true;
+                } else if let (true, Some(ventilator)) = (self.ppv_attached, lungs.ventilator.as_mut()) {
+                    ventilator.fio2 = fio2;
+                }
+            }
+            None => {
+                if std::mem::take(&mut self.ppv_attached) {
+                    lungs.detach_ventilator();
+                }
+            }
+        }
+    }
+
+    /// Spontaneous breathing effort
+    ///
+    /// # Returns
+    /// The respiratory drive applied to the lungs
+    fn update_breathing(&mut self, pao2: f64, delta_time_s: f64) -> f64 {
+        // Reoxygenation lets the depressed brainstem recover; ongoing asphyxia deepens it
+        if pao2 > 40.0 {
+            self.birth_depression *= (-delta_time_s / 300.0).exp();
+        } else if pao2 < 20.0 {
+            self.birth_depression = (self.birth_depression + delta_time_s / 600.0).min(1.0);
+        }
+
+        // Stimulation only works in primary apnea
+        self.stimulation_effect *= (-delta_time_s / 60.0).exp();
+        let stimulation = if self.birth_depression < 0.6 { self.stimulation_effect } else { 0.0 };
+
+        // Newborns breathe irregularly at first; the cold depresses them further
+        let cold_depression = (36.0 - self.temperature_c).max(0.0) * 0.1;
+        (1.0 - self.birth_depression * 1.4 + stimulation - cold_depression).clamp(0.0, 1.2)
+    }
+
+    /// Evaporative and radiant heat loss
+    fn update_temperature(&mut self, delta_time_s: f64) {
+        // Small babies have more surface area per kilogram and cool faster
+        let size_factor = (self.birth_weight_kg / 3.5).clamp(0.2, 1.5);
+        let (target, time_constant_s) = match (self.is_warmed, self.is_dried) {
+            (true, true) => (37.0, 1_200.0),
+            (true, false) => (37.0, 3_600.0),
+            (false, true) => (ROOM_TEMPERATURE_C, 7_200.0),
+            (false, false) => (ROOM_TEMPERATURE_C, 5_400.0),
+        };
+        let time_constant_s = time_constant_s * size_factor;
+        self.temperature_c += (target - self.temperature_c) * (delta_time_s / time_constant_s).min(1.0);
+    }
+
+    /// Assign an APGAR score from the current state
+    fn score_apgar(&self, patient: &Patient, minute: u32) -> ApgarScore {
+        let spo2 = patient.blood.gases.sao2_percent;
+        let heart_rate = patient
+            .get_organ::<heart::Heart>()
+            .map(|h| h.heart_rate_bpm)
+            .unwrap_or(0.0);
+        let breathing = if self.ppv_attached {
+            0.0
+        } else {
+            patient
+                .get_organ::<lungs::Lungs>()
+                .map(|l| l.respiratory_drive)
+                .unwrap_or(0.0)
+        };
+        let hypoxia = ((30.0 - patient.blood.gases.pao2_mmhg) / 20.0).clamp(0.0, 1.0);
+        let tone = 1.0 - self.birth_depression - hypoxia * 0.5;
+
+        let grade = |value: f64, good: f64, fair: f64| -> u8 {
+            if value >= good {
+                2
+            } else if value >= fair {
+                1
+            } else {
+                0
+            }
+        };
+        ApgarScore {
+            minute,
+            // Acrocyanosis is normal for the first minutes
+            appearance: grade(spo2, 95.0, 75.0),
+            pulse: if heart_rate >= 100.0 { 2 } else if heart_rate > 0.0 { 1 } else { 0 },
+            grimace: grade(1.0 - self.birth_depression, 0.8, 0.3),
+            activity: grade(tone, 0.7, 0.3),
+            respiration: grade(breathing, 0.8, 0.2),
+        }
+    }
+
+    /// Get a summary of the newborn transition
+    pub fn get_summary(&self) -> String {
+        let apgar = self
+            .apgar_scores
+            .iter()
+            .map(|score| format!("{}@{}min", score.total(), score.minute))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "Newborn: {:.1} min, GA={:.0} wk, {:.2} kg, Temp={:.1}°C, Lung fluid={:.0}%, PVR={:.2}, \
+             FO shunt={:.0}%, Ductus={:?} ({:.0}% open), Postductal SpO2={:.0}%, APGAR=[{}]{}",
+            self.time_since_birth_s / 60.0,
+            self.gestational_age_weeks,
+            self.birth_weight_kg,
+            self.temperature_c,
+            self.lung_fluid * 100.0,
+            self.pulmonary_vascular_resistance,
+            self.foramen_ovale_shunt * 100.0,
+            self.ductal_flow,
+            self.ductus_patency * 100.0,
+            self.postductal_spo2_percent,
+            apgar,
+            if self.is_receiving_ppv() { ", PPV" } else { "" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::growth::Sex;
+    use crate::patient::{initialize_newborn, update_patient};
+
+    #[test]
+    fn test_term_newborn_transitions_without_help() {
+        let mut patient = initialize_newborn(1, 3, Sex::Male, 40.0, 3.4);
+        for _ in 0..300 {
+            update_patient(&mut patient, 1.0);
+        }
+        let newborn = patient.newborn.as_ref().unwrap();
+        let five_minute = newborn.apgar_scores.iter().find(|score| score.minute == 5).unwrap();
+        assert!(five_minute.total() >= 7, "{:?}", newborn.apgar_scores);
+        assert!(patient.blood.gases.sao2_percent > 90.0, "{}", patient.blood.gases.sao2_percent);
+        assert!(!newborn.is_receiving_ppv());
+        assert_ne!(newborn.ductal_flow, DuctalFlow::RightToLeft);
+    }
+}
@@ -0,0 +1,440 @@
+//! Pregnancy and obstetric emergencies
+//!
+//! This module provides:
+//! - A pregnancy mode with gestational age and physiologic blood volume expansion
+//! - Preeclampsia (hypertension, proteinuria) and HELLP syndrome labs
+//! - Eclamptic seizures and magnesium sulfate prophylaxis
+//! - Postpartum hemorrhage from uterine atony, treated with uterotonics
+
+use crate::fluids::OutputRoute;
+use crate::organs::{brain, kidneys, liver, vascular};
+use crate::patient::Patient;
+
+/// Weeks of gestation at term
+pub const TERM_WEEKS: f64 = 40.0;
+
+/// Seconds in one week
+const SECONDS_PER_WEEK: f64 = 7.0 * 86_400.0;
+
+/// Serum magnesium at the bottom of the therapeutic range for seizure prophylaxis (mg/dL)
+const THERAPEUTIC_MAGNESIUM_MG_DL: f64 = 4.8;
+
+/// Drugs that contract the uterus
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Uterotonic {
+    /// First line; short-acting
+    Oxytocin,
+    /// Ergot alkaloid; sustained contraction, but raises blood pressure
+    Methylergonovine,
+    /// 15-methyl prostaglandin F2-alpha
+    Carboprost,
+    /// Prostaglandin E1; slower onset
+    Misoprostol,
+}
+
+impl Uterotonic {
+    /// Peak increase in uterine tone (0.0-1.0)
+    fn peak_effect(&self) -> f64 {
+        match self {
+            Uterotonic::Oxytocin => 0.4,
+            Uterotonic::Methylergonovine => 0.35,
+            Uterotonic::Carboprost => 0.35,
+            Uterotonic::Misoprostol => 0.25,
+        }
+    }
+
+    /// Half-life of the uterine effect (s)
+    fn effect_half_life_s(&self) -> f64 {
+        match self {
+            Uterotonic::Oxytocin => 1_800.0,
+            Uterotonic::Methylergonovine => 7_200.0,
+            Uterotonic::Carboprost => 5_400.0,
+            Uterotonic::Misoprostol => 7_200.0,
+        }
+    }
+}
+
+/// A uterotonic dose still acting on the uterus
+#[derive(Debug, Clone)]
+pub struct ActiveUterotonic {
+    pub agent: Uterotonic,
+    /// Current increase in uterine tone (0.0-1.0)
+    pub effect: f64,
+}
+
+/// Pregnancy state
+#[derive(Debug, Clone)]
+pub struct Pregnancy {
+    /// Gestational age (weeks)
+    pub gestational_age_weeks: f64,
+    /// Whether the baby and placenta have been delivered
+    pub is_delivered: bool,
+    /// Time since delivery (s)
+    pub time_since_delivery_s: f64,
+    /// Uterine blood flow (mL/min), ~700 mL/min at term
+    pub uterine_blood_flow_ml_min: f64,
+    /// Preeclampsia severity (0.0 = none, 1.0 = severe)
+    pub preeclampsia_severity: f64,
+    /// HELLP syndrome (hemolysis, elevated liver enzymes, low platelets)
+    pub hellp: bool,
+    /// Seizure threshold lowering from cerebral endothelial dysfunction (seizure at 1.0)
+    pub cortical_irritability: f64,
+    /// Number of eclamptic seizures
+    pub eclamptic_seizures: u32,
+    /// Magnesium sulfate infusion rate (g/hr)
+    pub magnesium_infusion_g_per_hr: f64,
+    /// Uterine atony (0.0 = well contracted, 1.0 = boggy, flaccid uterus)
+    pub uterine_atony: f64,
+    /// Uterine tone (0.0 = flaccid, 1.0 = firmly contracted)
+    pub uterine_tone: f64,
+    /// Uterotonic doses still acting
+    pub active_uterotonics: Vec<ActiveUterotonic>,
+    /// Cumulative postpartum blood loss (mL)
+    pub postpartum_blood_loss_ml: f64,
+    /// Current uterine bleeding rate (mL/min)
+    pub bleeding_rate_ml_min: f64,
+    /// Temporary tone increase from bimanual uterine massage
+    massage_effect: f64,
+    /// Magnesium sulfate bolus not yet distributed (g)
+    pending_magnesium_g: f64,
+}
+
+impl Pregnancy {
+    /// Create a pregnancy at the given gestational age
+    ///
+    /// # Arguments
+    /// * `gestational_age_weeks` - Gestational age in weeks
+    pub fn new(gestational_age_weeks: f64) -> Self {
+        let gestational_age_weeks = gestational_age_weeks.clamp(0.0, 44.0);
+        Self {
+            gestational_age_weeks,
+            is_delivered: false,
+            time_since_delivery_s: 0.0,
+            uterine_blood_flow_ml_min: Self::uterine_blood_flow_for(gestational_age_weeks),
+            preeclampsia_severity: 0.0,
+            hellp: false,
+            cortical_irritability: 0.0,
+            eclamptic_seizures: 0,
+            magnesium_infusion_g_per_hr: 0.0,
+            uterine_atony: 0.0,
+            uterine_tone: 1.0,
+            active_uterotonics: Vec::new(),
+            postpartum_blood_loss_ml: 0.0,
+            bleeding_rate_ml_min: 0.0,
+            massage_effect: 0.0,
+            pending_magnesium_g: 0.0,
+        }
+    }
+
+    /// Physiologic plasma and red cell expansion for a gestational age
+    ///
+    /// # Returns
+    /// (plasma, red cell) expansion in mL; ~1250 mL plasma and ~300 mL red cells by 34 weeks
+    pub fn blood_volume_expansion_ml(gestational_age_weeks: f64) -> (f64, f64) {
+        let progress = (gestational_age_weeks / 34.0).clamp(0.0, 1.0);
+        (1250.0 * progress, 300.0 * progress)
+    }
+
+    /// Uterine blood flow for a gestational age (mL/min)
+    fn uterine_blood_flow_for(gestational_age_weeks: f64) -> f64 {
+        50.0 + 650.0 * (gestational_age_weeks / TERM_WEEKS).clamp(0.0, 1.0).powi(2)
+    }
+
+    /// Develop preeclampsia
+    ///
+    /// # Arguments
+    /// * `severity` - 0.0-1.0; above ~0.6 blood pressure reaches the severe range (≥160/110)
+    pub fn induce_preeclampsia(&mut self, severity: f64) {
+        self.preeclampsia_severity = self.preeclampsia_severity.max(severity.clamp(0.0, 1.0));
+    }
+
+    /// Develop HELLP syndrome on top of severe preeclampsia
+    pub fn induce_hellp(&mut self) {
+        self.induce_preeclampsia(0.7);
+        self.hellp = true;
+    }
+
+    /// Deliver the baby and placenta
+    pub fn deliver(&mut self) {
+        if !self.is_delivered {
+            self.is_delivered = true;
+            self.time_since_delivery_s = 0.0;
+        }
+    }
+
+    /// Make the uterus fail to contract after delivery
+    ///
+    /// # Arguments
+    /// * `severity` - 0.0-1.0; severe atony bleeds several hundred mL/min
+    pub fn induce_uterine_atony(&mut self, severity: f64) {
+        self.uterine_atony = self.uterine_atony.max(severity.clamp(0.0, 1.0));
+    }
+
+    /// Give a uterotonic drug
+    pub fn give_uterotonic(&mut self, agent: Uterotonic) {
+        self.active_uterotonics.push(ActiveUterotonic {
+            agent,
+            effect: agent.peak_effect(),
+        });
+    }
+
+    /// Perform bimanual uterine massage (brief increase in tone)
+    pub fn massage_uterus(&mut self) {
+        self.massage_effect = 0.2;
+    }
+
+    /// Give a magnesium sulfate loading dose (typically 4-6 g IV)
+    pub fn give_magnesium_bolus(&mut self, grams: f64) {
+        self.pending_magnesium_g += grams.max(0.0);
+    }
+
+    /// Set the magnesium sulfate maintenance infusion (typically 1-2 g/hr)
+    pub fn set_magnesium_infusion(&mut self, grams_per_hr: f64) {
+        self.magnesium_infusion_g_per_hr = grams_per_hr.max(0.0);
+    }
+
+    /// Total uterotonic effect of all active doses
+    fn uterotonic_effect(&self) -> f64 {
+        self.active_uterotonics.iter().map(|dose| dose.effect).sum()
+    }
+
+    /// Advance the pregnancy
+    ///
+    /// # Arguments
+    /// * `patient` - The pregnant patient
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        if self.is_delivered {
+            self.time_since_delivery_s += delta_time_s;
+            // Preeclampsia resolves over the days after delivery; once HELLP has
+            // burnt out the injured liver recovers over the following days
+            self.preeclampsia_severity *= (-delta_time_s / 86_400.0).exp();
+            if self.preeclampsia_severity < 0.05 {
+                self.hellp = false;
+            }
+            if !self.hellp {
+                if let Some(liver) = patient.get_organ_mut::<liver::Liver>() {
+                    liver.hepatocellular_injury *= (-delta_time_s / 172_800.0).exp();
+                }
+            }
+        } else {
+            self.gestational_age_weeks += delta_time_s / SECONDS_PER_WEEK;
+            self.uterine_blood_flow_ml_min = Self::uterine_blood_flow_for(self.gestational_age_weeks);
+        }
+
+        self.update_magnesium(patient, delta_time_s);
+        self.update_preeclampsia(patient, delta_time_s);
+        if self.hellp {
+            self.update_hellp(patient, delta_time_s);
+        }
+        if self.is_delivered {
+            self.update_uterus(patient, delta_time_s);
+        }
+    }
+
+    /// Distribute magnesium sulfate into the extracellular fluid
+    fn update_magnesium(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // 1 g of MgSO4·7H2O contains ~99 mg elemental magnesium
+        let grams = std::mem::take(&mut self.pending_magnesium_g)
+            + self.magnesium_infusion_g_per_hr * delta_time_s / 3600.0;
+        let extracellular_dl = patient.fluids.extracellular_ml() / 100.0;
+        patient.blood.chemistry.magnesium_mg_dl += grams * 99.0 / extracellular_dl.max(1.0);
+    }
+
+    /// Endothelial dysfunction: hypertension, glomerular injury, eclampsia
+    fn update_preeclampsia(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let severity = self.preeclampsia_severity;
+
+        // Placental factors injure the maternal endothelium: endothelin rises and nitric
+        // oxide falls, constricting the arterioles. Methylergonovine is a potent
+        // vasoconstrictor as well
+        let ergot_effect: f64 = self
+            .active_uterotonics
+            .iter()
+            .filter(|dose| dose.agent == Uterotonic::Methylergonovine)
+            .map(|dose| dose.effect / dose.agent.peak_effect())
+            .sum();
+        let vasoconstriction = severity + ergot_effect * (0.3 + severity);
+        if let Some(vascular) = patient.get_organ_mut::<vascular::VascularSystem>() {
+            vascular.endothelin_level = vascular.endothelin_level.max(1.0 + 1.5 * vasoconstriction).min(2.5);
+            vascular.nitric_oxide_level = vascular.nitric_oxide_level.min(1.0 - 0.6 * severity).max(0.2);
+        }
+
+        // Glomerular endotheliosis leaks protein, and heals as the disease resolves
+        if let Some(kidneys) = patient.get_organ_mut::<kidneys::Kidneys>() {
+            let injury = &mut kidneys.glomerular_injury;
+            *injury += (severity * 0.8 - *injury) * (delta_time_s / 21_600.0).min(1.0);
+        }
+
+        // Cerebral endothelial dysfunction lowers the seizure threshold;
+        // therapeutic magnesium is protective
+        let magnesium = patient.blood.chemistry.magnesium_mg_dl;
+        let protection = ((magnesium - 2.0) / (THERAPEUTIC_MAGNESIUM_MG_DL - 2.0)).clamp(0.0, 1.0);
+        let pressure_drive = ((patient.blood.blood_pressure_diastolic - 100.0) / 20.0).clamp(0.0, 1.0);
+        let drive = (severity - 0.5).max(0.0) * 2.0 * (0.5 + pressure_drive);
+        self.cortical_irritability += drive * (1.0 - protection) * delta_time_s / 3600.0;
+        self.cortical_irritability *= (-delta_time_s / 3600.0 * protection).exp();
+
+        if self.cortical_irritability >= 1.0 {
+            if let Some(brain) = patient.get_organ_mut::<brain::Brain>() {
+                brain.start_seizure(75.0);
+            }
+            self.eclamptic_seizures += 1;
+            self.cortical_irritability = 0.0;
+        }
+    }
+
+    /// Microangiopathic hemolysis, hepatocellular injury and platelet consumption
+    fn update_hellp(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let severity = self.preeclampsia_severity.min(1.0);
+
+        // ~0.5% of red cells destroyed per hour
+        let red_cells = patient.fluids.red_cell_ml;
+        patient.fluids.destroy_red_cells(red_cells * 0.005 * severity * delta_time_s / 3600.0);
+
+        let ldh = &mut patient.blood.chemistry.ldh_u_l;
+        *ldh += (180.0 + 900.0 * severity - *ldh) * (delta_time_s / 21_600.0).min(1.0);
+
+        // Periportal necrosis develops over hours
+        if let Some(liver) = patient.get_organ_mut::<liver::Liver>() {
+            let injury = &mut liver.hepatocellular_injury;
+            *injury += (severity - *injury) * (delta_time_s / 21_600.0).min(1.0);
+        }
+
+        // Platelets are consumed in the damaged microvasculature (~1 day half-life when severe)
+        let platelets = &mut patient.blood.cells.platelet_count_thousand_per_ul;
+        *platelets *= (-severity * delta_time_s / 120_000.0).exp();
+    }
+
+    /// Uterine contraction after delivery and atonic hemorrhage
+    fn update_uterus(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        for dose in &mut self.active_uterotonics {
+            dose.effect *= 0.5_f64.powf(delta_time_s / dose.agent.effect_half_life_s());
+        }
+        self.active_uterotonics.retain(|dose| dose.effect > 0.01);
+        self.massage_effect *= (-delta_time_s / 300.0).exp();
+
+        self.uterine_tone =
+            (1.0 - self.uterine_atony + self.uterotonic_effect() + self.massage_effect).clamp(0.0, 1.0);
+
+        // A sustained contraction lets intrinsic tone recover
+        if self.uterine_tone > 0.8 {
+            self.uterine_atony *= (-delta_time_s / 1800.0).exp();
+        }
+
+        // The placental bed bleeds until the myometrium clamps its vessels
+        let relaxation = ((0.8 - self.uterine_tone) / 0.8).max(0.0);
+        self.bleeding_rate_ml_min = self.uterine_blood_flow_ml_min * 0.25 * relaxation.powf(1.5);
+
+        let lost = patient.fluids.remove_blood(self.bleeding_rate_ml_min * delta_time_s / 60.0);
+        patient.fluid_ledger.record_output(OutputRoute::Blood, lost);
+        self.postpartum_blood_loss_ml += lost;
+
+        // Uterine blood flow returns to baseline over the hours after delivery
+        self.uterine_blood_flow_ml_min = 50.0 + (self.uterine_blood_flow_ml_min - 50.0)
+            * (-delta_time_s / 21_600.0).exp();
+    }
+
+    /// Get a summary of the pregnancy
+    pub fn get_summary(&self) -> String {
+        let status = if self.is_delivered {
+            format!("Postpartum {:.1} h", self.time_since_delivery_s / 3600.0)
+        } else {
+            format!("GA={:.1} wk", self.gestational_age_weeks)
+        };
+        format!(
+            "Pregnancy: {}, Preeclampsia={:.0}%{}, Seizures={}, Uterine tone={:.0}%, Bleeding={:.0} mL/min, PPH loss={:.0} mL",
+            status,
+            self.preeclampsia_severity * 100.0,
+            if self.hellp { " (HELLP)" } else { "" },
+            self.eclamptic_seizures,
+            self.uterine_tone * 100.0,
+            self.bleeding_rate_ml_min,
+            self.postpartum_blood_loss_ml
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+
+    fn pregnant(gestational_age_weeks: f64) -> Patient {
+        let mut patient = initialize_patient(1, 3);
+        patient.start_pregnancy(gestational_age_weeks);
+        update_patient(&mut patient, 60.0);
+        patient
+    }
+
+    fn run_minutes(patient: &mut Patient, minutes: usize) {
+        for _ in 0..minutes {
+            update_patient(patient, 60.0);
+        }
+    }
+
+    #[test]
+    fn test_preeclampsia_constricts_the_arterioles_and_leaks_protein() {
+        let mut healthy = pregnant(34.0);
+        let mut preeclamptic = healthy.fork();
+        preeclamptic.pregnancy.as_mut().unwrap().induce_preeclampsia(0.8);
+        run_minutes(&mut healthy, 360);
+        run_minutes(&mut preeclamptic, 360);
+
+        let vascular = preeclamptic.get_organ::<vascular::VascularSystem>().unwrap();
+        assert!(vascular.peripheral_resistance_factor() > 1.3);
+        assert!(preeclamptic.blood.blood_pressure_diastolic >= 110.0, "{}", preeclamptic.blood.blood_pressure_diastolic);
+        assert!(preeclamptic.blood.blood_pressure_systolic > healthy.blood.blood_pressure_systolic + 25.0);
+        let proteinuria = |patient: &Patient| patient.get_organ::<kidneys::Kidneys>().unwrap().proteinuria_g_per_day;
+        assert!(proteinuria(&preeclamptic) > 1.0);
+        assert!(proteinuria(&healthy) < 0.3);
+
+        // The disease and its renal lesion resolve after delivery
+        let antepartum_proteinuria = proteinuria(&preeclamptic);
+        preeclamptic.pregnancy.as_mut().unwrap().deliver();
+        for _ in 0..24 {
+            update_patient(&mut preeclamptic, 3600.0);
+        }
+        assert!(proteinuria(&preeclamptic) < 0.85 * antepartum_proteinuria, "{antepartum_proteinuria} -> {}", proteinuria(&preeclamptic));
+    }
+
+    #[test]
+    fn test_magnesium_prevents_eclamptic_seizures() {
+        let mut untreated = pregnant(36.0);
+        untreated.pregnancy.as_mut().unwrap().induce_preeclampsia(1.0);
+        let mut treated = untreated.fork();
+        let pregnancy = treated.pregnancy.as_mut().unwrap();
+        pregnancy.give_magnesium_bolus(4.0);
+        pregnancy.set_magnesium_infusion(2.0);
+        run_minutes(&mut untreated, 180);
+        run_minutes(&mut treated, 180);
+
+        assert!(untreated.pregnancy.as_ref().unwrap().eclamptic_seizures > 0);
+        assert_eq!(treated.pregnancy.as_ref().unwrap().eclamptic_seizures, 0);
+        assert!(treated.blood.chemistry.magnesium_mg_dl > THERAPEUTIC_MAGNESIUM_MG_DL - 1.0);
+    }
+
+    #[test]
+    fn test_uterotonics_slow_atonic_postpartum_hemorrhage() {
+        let mut untreated = pregnant(39.0);
+        let pregnancy = untreated.pregnancy.as_mut().unwrap();
+        pregnancy.deliver();
+        pregnancy.induce_uterine_atony(0.9);
+        update_patient(&mut untreated, 60.0);
+        let untreated_rate = untreated.pregnancy.as_ref().unwrap().bleeding_rate_ml_min;
+        assert!(untreated_rate > 50.0, "{untreated_rate}");
+
+        let mut treated = untreated.fork();
+        let pregnancy = treated.pregnancy.as_mut().unwrap();
+        pregnancy.give_uterotonic(Uterotonic::Oxytocin);
+        pregnancy.give_uterotonic(Uterotonic::Carboprost);
+        pregnancy.massage_uterus();
+        run_minutes(&mut untreated, 20);
+        run_minutes(&mut treated, 20);
+
+        let (untreated, treated) = (untreated.pregnancy.unwrap(), treated.pregnancy.unwrap());
+        assert!(treated.uterine_tone > 0.8);
+        assert!(treated.bleeding_rate_ml_min < 0.1 * untreated.bleeding_rate_ml_min);
+        assert!(treated.postpartum_blood_loss_ml < 0.5 * untreated.postpartum_blood_loss_ml);
+    }
+}
@@ -1,4 +1,9 @@
 //! Base organ trait and common types
+//!
+//! New organs (a prosthetic device, a tumor) implement `Organ` and are added
+//! with `Patient::register_organ`, or `register_organ_with_priority` at a
+//! chosen position in the update order; several organs may share a type. Organs are `Send + Sync`, so a patient can
+//! move between threads or live in an async task.
 
 use crate::patient::Patient;
 use std::fmt;
@@ -7,8 +12,17 @@ use std::any::Any;
 /// Unique identifier for organs
 pub type OrganId = usize;
 
+/// Update priority of organs added without one, after every built-in organ
+pub const DEFAULT_ORGAN_PRIORITY: i32 = 1000;
+
+/// Spacing of the built-in organs' update priorities, leaving room between them
+pub const BUILT_IN_ORGAN_PRIORITY_STEP: i32 = 10;
+
 /// Base trait that all organs must implement
-pub trait Organ: fmt::Debug {
+///
+/// Organs are `Send + Sync`, so a patient can be moved to another thread or
+/// held in an async task, and read from several threads at once.
+pub trait Organ: fmt::Debug + Send + Sync {
     /// Update the organ's state based on the patient's condition
     ///
     /// # Arguments
@@ -33,9 +47,12 @@ pub trait Organ: fmt::Debug {
 
     /// Get a mutable reference to Any for downcasting
     fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Clone the organ's full state into a new box, for forking the patient
+    fn clone_box(&self) -> Box<dyn Organ>;
 }
 
-/// Macro to implement the as_any methods for organ types
+/// Macro to implement the as_any and clone_box methods for organ types
 #[macro_export]
 macro_rules! impl_organ_any {
     () => {
@@ -46,5 +63,77 @@ macro_rules! impl_organ_any {
         fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
             self
         }
+
+        fn clone_box(&self) -> Box<dyn $crate::organ::Organ> {
+            Box::new(self.clone())
+        }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+
+    /// Tumor consuming glucose as it grows
+    #[derive(Debug, Clone)]
+    struct Tumor {
+        id: OrganId,
+        mass_g: f64,
+        saw_heart: bool,
+    }
+
+    impl Organ for Tumor {
+        fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+            self.mass_g *= 1.0 + 1e-5 * delta_time_s;
+            patient.blood.chemistry.glucose_mg_dl -= 1e-4 * self.mass_g * delta_time_s;
+            self.saw_heart = patient.get_organ::<crate::organs::heart::Heart>().is_some()
+                || patient.organ_by_name_mut("Heart").is_some();
+        }
+
+        fn get_summary(&self) -> String {
+            format!("Tumor: {:.1} g", self.mass_g)
+        }
+
+        fn get_id(&self) -> OrganId {
+            self.id
+        }
+
+        fn get_type(&self) -> &'static str {
+            "Tumor"
+        }
+
+        crate::impl_organ_any!();
+    }
+
+    #[test]
+    fn test_registered_organs_update_in_priority_order() {
+        let mut patient = initialize_patient(1, 3);
+        let id = patient.next_organ_id();
+        assert_eq!(id, 18);
+        patient.register_organ(Box::new(Tumor { id, mass_g: 10.0, saw_heart: true }));
+        let lungs = patient.organ_priority("Lungs").unwrap();
+        patient.register_organ_with_priority(Box::new(Tumor { id: id + 1, mass_g: 20.0, saw_heart: true }), lungs - 1);
+
+        let types: Vec<_> = patient.organs().iter().map(|organ| organ.get_type()).collect();
+        assert_eq!(&types[..3], ["Heart", "Tumor", "Lungs"]);
+        assert_eq!(types.last(), Some(&"Tumor"));
+        // The first tumor in update order is the one placed before the lungs
+        assert_eq!(patient.get_organ::<Tumor>().unwrap().id, id + 1);
+        assert!(patient.get_organ::<crate::organs::lungs::Lungs>().is_some());
+        // Organs chosen at run time are found by name
+        assert_eq!(patient.organ_by_name("Tumor").unwrap().get_id(), id + 1);
+        assert!(patient.organ_by_name("Lungs").unwrap().as_any().is::<crate::organs::lungs::Lungs>());
+        assert!(patient.organ_by_name("Appendix").is_none());
+
+        update_patient(&mut patient, 1.0);
+        let fork = patient.fork();
+        let tumors = fork.get_organs::<Tumor>();
+        assert_eq!(tumors.len(), 2);
+        assert!(tumors.iter().all(|tumor| tumor.mass_g > 10.0));
+        // Other organs are out of the patient during an update; looking them up finds nothing
+        assert!(tumors.iter().all(|tumor| !tumor.saw_heart));
+        patient.get_organs_mut::<Tumor>()[1].mass_g = 0.0;
+        assert_eq!(fork.get_organs::<Tumor>()[1].mass_g, tumors[1].mass_g);
+    }
+}
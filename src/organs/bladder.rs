@@ -1,8 +1,29 @@
 //! Bladder organ simulation
+//!
+//! The bladder fills with the urine the kidneys produce and empties by voiding
+//! or through an indwelling catheter, whose urometer reports hourly output for
+//! oliguria and anuria criteria. Urine is charted in the I/O ledger when it
+//! leaves the bladder.
 
+use crate::fluids::OutputRoute;
 use crate::organ::{Organ, OrganId};
 use crate::patient::Patient;
 
+/// Hours of urometer readings kept by a catheter
+const CATHETER_HISTORY_H: usize = 24;
+
+/// Hours over which oliguria is assessed (KDIGO)
+const OLIGURIA_WINDOW_H: usize = 6;
+
+/// Output below which urine flow is oliguric (mL/kg/h)
+const OLIGURIA_ML_KG_H: f64 = 0.5;
+
+/// Hours over which anuria is assessed (KDIGO stage 3)
+const ANURIA_WINDOW_H: usize = 12;
+
+/// Output below which urine flow is anuric, ~50 mL/day (mL/h)
+const ANURIA_ML_H: f64 = 2.0;
+
 /// Bladder state
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BladderState {
@@ -11,8 +32,51 @@ pub enum BladderState {
     Voiding,
 }
 
+/// Indwelling urinary catheter draining into a urometer
+#[derive(Debug, Clone, Default)]
+pub struct UrinaryCatheter {
+    /// Urine collected in each completed hour, most recent last (mL)
+    pub hourly_output_ml: Vec<f64>,
+    /// Urine collected so far in the current hour (mL)
+    pub current_hour_ml: f64,
+    /// Time into the current hour (s)
+    pub current_hour_s: f64,
+    /// Urine collected since insertion (mL)
+    pub total_output_ml: f64,
+}
+
+impl UrinaryCatheter {
+    /// Collect drained urine and close the hour when it ends
+    fn collect(&mut self, volume_ml: f64, delta_time_s: f64) {
+        self.current_hour_ml += volume_ml;
+        self.total_output_ml += volume_ml;
+        self.current_hour_s += delta_time_s;
+        if self.current_hour_s >= 3600.0 {
+            self.current_hour_s -= 3600.0;
+            self.hourly_output_ml.push(std::mem::take(&mut self.current_hour_ml));
+            if self.hourly_output_ml.len() > CATHETER_HISTORY_H {
+                self.hourly_output_ml.remove(0);
+            }
+        }
+    }
+
+    /// Output over the last completed hour (mL/h), once an hour has passed
+    pub fn last_hour_ml(&self) -> Option<f64> {
+        self.hourly_output_ml.last().copied()
+    }
+
+    /// Mean hourly output over the last completed hours (mL/h), once that many have passed
+    ///
+    /// # Arguments
+    /// * `hours` - Number of completed hours to average
+    pub fn mean_output_ml_h(&self, hours: usize) -> Option<f64> {
+        let n = self.hourly_output_ml.len();
+        (hours > 0 && n >= hours).then(|| self.hourly_output_ml[n - hours..].iter().sum::<f64>() / hours as f64)
+    }
+}
+
 /// Bladder organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Bladder {
     id: OrganId,
     /// Current state
@@ -25,6 +89,10 @@ pub struct Bladder {
     pub capacity_ml: f64,
     /// Voiding threshold pressure (cmH2O)
     pub voiding_threshold: f64,
+    /// Indwelling catheter, if placed
+    pub catheter: Option<UrinaryCatheter>,
+    /// Urine that has left the bladder and is not yet charted (mL)
+    uncharted_ml: f64,
 }
 
 impl Bladder {
@@ -37,34 +105,86 @@ impl Bladder {
             pressure_cm_h2o: 5.0,
             capacity_ml: 500.0,
             voiding_threshold: 40.0,
+            catheter: None,
+            uncharted_ml: 0.0,
         }
     }
 
     /// Add urine from kidneys
     pub fn add_urine(&mut self, volume_ml: f64) {
-        self.urine_volume_ml += volume_ml;
-        if self.urine_volume_ml > self.capacity_ml {
-            self.urine_volume_ml = self.capacity_ml;
-        }
+        self.urine_volume_ml += volume_ml.max(0.0);
     }
 
     /// Void bladder
-    pub fn void(&mut self) {
-        self.urine_volume_ml = 0.0;
+    ///
+    /// # Returns
+    /// Volume voided (mL)
+    pub fn void(&mut self) -> f64 {
+        let voided = std::mem::take(&mut self.urine_volume_ml);
+        self.uncharted_ml += voided;
         self.state = BladderState::Filling;
+        voided
+    }
+
+    /// Place an indwelling urinary catheter, or read the urometer of one in place
+    ///
+    /// # Returns
+    /// Urine output over the last completed hour (mL/h); on insertion, the
+    /// residual volume drained, as nurses chart the first bag
+    pub fn catheterize(&mut self) -> f64 {
+        if let Some(catheter) = &self.catheter {
+            return catheter.last_hour_ml().unwrap_or(catheter.current_hour_ml);
+        }
+        let residual = self.void();
+        self.catheter = Some(UrinaryCatheter {
+            total_output_ml: residual,
+            ..UrinaryCatheter::default()
+        });
+        residual
+    }
+
+    /// Remove the catheter
+    pub fn remove_catheter(&mut self) {
+        self.catheter = None;
+    }
+
+    /// Catheter output below 0.5 mL/kg/h over the last 6 hours
+    ///
+    /// # Arguments
+    /// * `weight_kg` - Body weight in kg
+    pub fn is_oliguric(&self, weight_kg: f64) -> bool {
+        self.catheter
+            .as_ref()
+            .and_then(|c| c.mean_output_ml_h(OLIGURIA_WINDOW_H))
+            .is_some_and(|output| output < OLIGURIA_ML_KG_H * weight_kg)
+    }
+
+    /// Catheter output of under ~50 mL/day over the last 12 hours
+    pub fn is_anuric(&self) -> bool {
+        self.catheter
+            .as_ref()
+            .and_then(|c| c.mean_output_ml_h(ANURIA_WINDOW_H))
+            .is_some_and(|output| output < ANURIA_ML_H)
     }
 }
 
 impl Organ for Bladder {
-    fn update(&mut self, _patient: &mut Patient, _delta_time_s: f64) {
+    fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // A catheter keeps the bladder empty
+        if let Some(catheter) = self.catheter.as_mut() {
+            let drained = std::mem::take(&mut self.urine_volume_ml);
+            catheter.collect(drained, delta_time_s);
+            self.uncharted_ml += drained;
+        }
+
         // Calculate pressure based on volume
         let fill_ratio = self.urine_volume_ml / self.capacity_ml;
         self.pressure_cm_h2o = 5.0 + fill_ratio * 45.0;
 
         // Update state
-        if self.pressure_cm_h2o >= self.voiding_threshold {
-            self.state = BladderState::Full;
-        } else if self.urine_volume_ml > self.capacity_ml * 0.7 {
+        if self.pressure_cm_h2o >= self.voiding_threshold
+            || self.urine_volume_ml > self.capacity_ml * 0.7
+        {
             self.state = BladderState::Full;
         } else {
             self.state = BladderState::Filling;
@@ -74,12 +194,23 @@ impl Organ for Bladder {
         if self.state == BladderState::Full && self.urine_volume_ml >= self.capacity_ml {
             self.void();
         }
+        let output = std::mem::take(&mut self.uncharted_ml);
+        if output > 0.0 {
+            patient.fluid_ledger.record_output(OutputRoute::Urine, output);
+        }
     }
 
     fn get_summary(&self) -> String {
+        let catheter = match &self.catheter {
+            Some(catheter) => match catheter.last_hour_ml() {
+                Some(output) => format!(", Foley={:.0} mL/h", output),
+                None => ", Foley".to_string(),
+            },
+            None => String::new(),
+        };
         format!(
-            "Bladder: State={:?}, Volume={:.0} mL, Pressure={:.1} cmH2O",
-            self.state, self.urine_volume_ml, self.pressure_cm_h2o
+            "Bladder: State={:?}, Volume={:.0} mL, Pressure={:.1} cmH2O{}",
+            self.state, self.urine_volume_ml, self.pressure_cm_h2o, catheter
         )
     }
 
@@ -98,4 +229,8 @@ impl Organ for Bladder {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
 }
@@ -1,7 +1,61 @@
+//! Bones organ simulation
+//!
+//! The skeleton's structure and the bone marrow. Fractured ribs splint
+//! breathing, and enough segmental fractures make a flail chest. Marrow output
+//! is regulated by erythropoietin from the kidneys, G-CSF and thrombopoietin,
+//! and limited by iron; chemotherapy or toxins suppress it, with neutrophils
+//! falling first, then platelets, then red cells.
+
+use crate::blood::{BloodCells, BloodComposition, WbcDifferential, ADAG_GLUCOSE_INTERCEPT_MG_DL, ADAG_GLUCOSE_PER_HBA1C_PERCENT};
+use crate::fluids::SECONDS_PER_DAY;
+use crate::iron::IRON_PER_RED_CELL_ML;
 use crate::organ::{Organ, OrganId};
+use crate::organs::kidneys::NORMAL_ERYTHROPOIETIN_MU_ML;
 use crate::patient::Patient;
 use std::any::Any;
 
+/// Normal hematocrit the marrow defends (fraction)
+pub const NORMAL_HEMATOCRIT: f64 = 0.42;
+
+/// Red cell lifespan (days)
+const RED_CELL_LIFESPAN_DAYS: f64 = 120.0;
+
+/// Platelet lifespan (days)
+const PLATELET_LIFESPAN_DAYS: f64 = 9.0;
+
+/// Normal platelet count (K/µL)
+const NORMAL_PLATELETS_K_UL: f64 = 250.0;
+
+/// Normal absolute neutrophil count (cells/µL)
+const NORMAL_NEUTROPHILS_PER_UL: f64 = 4000.0;
+
+/// Time constant of circulating neutrophil turnover (half-life ~7 h, hours)
+const NEUTROPHIL_TURNOVER_H: f64 = 10.0;
+
+/// Transit time through the granulocyte maturation and storage pool (days)
+const GRANULOCYTE_TRANSIT_DAYS: f64 = 4.0;
+
+/// Megakaryocyte maturation time (days)
+const MEGAKARYOCYTE_MATURATION_DAYS: f64 = 5.0;
+
+/// Time constant of progenitor self-renewal after injury (days)
+const PROGENITOR_RENEWAL_DAYS: f64 = 3.0;
+
+/// Progenitor kill rate per unit of myelotoxic exposure (1/day)
+const PROGENITOR_KILL_PER_DAY: f64 = 6.0;
+
+/// Chemotherapy elimination time constant (days)
+const CHEMOTHERAPY_ELIMINATION_DAYS: f64 = 1.0;
+
+/// Filgrastim elimination time constant (half-life ~3.5 h, hours)
+const FILGRASTIM_ELIMINATION_H: f64 = 5.0;
+
+/// Time for a fracture to unite given normal calcium, red cells and protein (s)
+const FRACTURE_HEALING_S: f64 = 3_628_800.0;
+
+/// Fewest adjacent ribs broken in two places that form a flail segment
+const FLAIL_SEGMENT_RIBS: u32 = 3;
+
 /// Represents a single bone in the skeletal system
 #[derive(Debug, Clone)]
 pub struct Bone {
@@ -36,11 +90,31 @@ impl Bone {
 }
 
 /// Bone marrow - produces blood cells
+///
+/// Each lineage is regulated by its own hormone: erythropoietin from the kidneys,
+/// G-CSF from infection and neutropenia, and thrombopoietin, which rises as the
+/// platelet mass falls. Chemotherapy and toxins kill the shared progenitors, so
+/// counts fall in order of cell lifespan: neutrophils within days, platelets over
+/// one to two weeks and red cells over months.
 #[derive(Debug, Clone)]
 pub struct BoneMarrow {
     pub red_marrow_volume_ml: f64,      // Active hematopoietic tissue
     pub yellow_marrow_volume_ml: f64,   // Fatty marrow (can convert to red)
     pub production_efficiency: f64,      // 0.0-1.0
+    /// Surviving hematopoietic progenitors relative to normal
+    pub progenitor_reserve: f64,
+    /// Maturing granulocyte storage pool relative to normal
+    pub neutrophil_reserve: f64,
+    /// Megakaryocyte mass relative to normal
+    pub megakaryocyte_mass: f64,
+    /// Plasma erythropoietin (mU/mL), set from the kidneys
+    pub erythropoietin_mu_ml: f64,
+    /// Systemic infection (0.0 = none, 1.0 = septic shock)
+    pub infection_severity: f64,
+    /// Myelotoxic chemotherapy in the body (1.0 = one myelosuppressive cycle)
+    pub chemotherapy_exposure: f64,
+    /// Filgrastim (recombinant G-CSF) in the body (mcg)
+    pub filgrastim_mcg: f64,
 }
 
 impl BoneMarrow {
@@ -49,28 +123,93 @@ impl BoneMarrow {
             red_marrow_volume_ml: 2600.0,    // Average adult
             yellow_marrow_volume_ml: 1400.0,
             production_efficiency: 1.0,
+            progenitor_reserve: 1.0,
+            neutrophil_reserve: 1.0,
+            megakaryocyte_mass: 1.0,
+            erythropoietin_mu_ml: NORMAL_ERYTHROPOIETIN_MU_ML,
+            infection_severity: 0.0,
+            chemotherapy_exposure: 0.0,
+            filgrastim_mcg: 0.0,
         }
     }
 
+    /// Give a cycle of myelosuppressive chemotherapy
+    ///
+    /// # Arguments
+    /// * `intensity` - Myelotoxicity (1.0 = standard cycle with a neutrophil nadir at ~10 days)
+    pub fn give_chemotherapy(&mut self, intensity: f64) {
+        self.chemotherapy_exposure += intensity.max(0.0);
+    }
+
+    /// Give filgrastim (G-CSF)
+    ///
+    /// # Arguments
+    /// * `mcg` - Dose in mcg (typically 5 mcg/kg daily)
+    pub fn give_filgrastim(&mut self, mcg: f64) {
+        self.filgrastim_mcg += mcg.max(0.0);
+    }
+
+    /// G-CSF drive relative to normal from infection, filgrastim and neutropenia
+    ///
+    /// # Arguments
+    /// * `neutrophils_per_ul` - Absolute neutrophil count (cells/µL)
+    pub fn g_csf_drive(&self, neutrophils_per_ul: f64) -> f64 {
+        let neutropenia = (NORMAL_NEUTROPHILS_PER_UL / neutrophils_per_ul.max(100.0)).clamp(1.0, 5.0);
+        1.0 + 4.0 * self.infection_severity.clamp(0.0, 1.0)
+            + 8.0 * self.filgrastim_mcg / (self.filgrastim_mcg + 150.0)
+            + 0.25 * (neutropenia - 1.0)
+    }
+
+    /// Thrombopoietin drive relative to normal (TPO is cleared by the platelet mass)
+    ///
+    /// # Arguments
+    /// * `platelets_k_ul` - Platelet count (K/µL)
+    pub fn thrombopoietin_drive(&self, platelets_k_ul: f64) -> f64 {
+        (NORMAL_PLATELETS_K_UL / platelets_k_ul.max(5.0)).sqrt().clamp(0.7, 3.0)
+    }
+
+    /// Erythropoietin drive on red cell production relative to normal
+    pub fn erythropoietin_drive(&self) -> f64 {
+        (self.erythropoietin_mu_ml / NORMAL_ERYTHROPOIETIN_MU_ML).max(0.0).powf(0.75).clamp(0.2, 6.0)
+    }
+
     /// Calculate blood cell production rate
     pub fn get_rbc_production_rate(&self) -> f64 {
         // Millions of RBCs per second (normal ~2-3 million/sec)
-        self.red_marrow_volume_ml * 0.001 * self.production_efficiency
+        self.red_marrow_volume_ml * 0.001 * self.production_efficiency * self.progenitor_reserve * self.erythropoietin_drive()
     }
 
     pub fn get_wbc_production_rate(&self) -> f64 {
         // Thousands of WBCs per second
-        self.red_marrow_volume_ml * 0.0003 * self.production_efficiency
+        self.red_marrow_volume_ml * 0.0003 * self.production_efficiency * self.neutrophil_reserve
     }
 
     pub fn get_platelet_production_rate(&self) -> f64 {
         // Thousands of platelets per second
-        self.red_marrow_volume_ml * 0.03 * self.production_efficiency
+        self.red_marrow_volume_ml * 0.03 * self.production_efficiency * self.megakaryocyte_mass
+    }
+
+    /// Check for pancytopenia (ANC < 1500/µL, platelets < 150 K/µL and anemia)
+    ///
+    /// # Arguments
+    /// * `blood` - Current blood composition
+    pub fn is_pancytopenia(blood: &BloodComposition) -> bool {
+        blood.cells.wbc_differential.neutrophils < 1500.0
+            && blood.cells.platelet_count_thousand_per_ul < 150.0
+            && blood.get_hemoglobin_g_dl() < 12.0
+    }
+
+    /// Check for severe neutropenia (ANC < 500/µL)
+    ///
+    /// # Arguments
+    /// * `cells` - Current blood counts
+    pub fn is_severe_neutropenia(cells: &BloodCells) -> bool {
+        cells.wbc_differential.neutrophils < 500.0
     }
 }
 
 /// The skeletal system - bones, calcium homeostasis, blood cell production
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Bones {
     id: OrganId,
     pub bones: Vec<Bone>,
@@ -80,29 +219,34 @@ pub struct Bones {
     pub osteoblast_activity: f64,        // 0.0-1.0 (bone building)
     pub osteoclast_activity: f64,        // 0.0-1.0 (bone resorption)
     pub vitamin_d_receptors: f64,        // Sensitivity to vitamin D
+    /// Number of ribs fractured
+    pub fractured_ribs: u32,
+    /// Three or more adjacent ribs broken in two places, leaving a segment of
+    /// chest wall that moves independently of the rest
+    pub flail_segment: bool,
 }
 
 impl Bones {
     pub fn new(id: i32) -> Self {
-        let mut bones = Vec::new();
-
-        // Major bones of the skeleton
-        bones.push(Bone::new("Skull", 18.0));
-        bones.push(Bone::new("Cervical Vertebrae", 10.0));
-        bones.push(Bone::new("Thoracic Vertebrae", 20.0));
-        bones.push(Bone::new("Lumbar Vertebrae", 18.0));
-        bones.push(Bone::new("Sacrum", 10.0));
-        bones.push(Bone::new("Ribs", 60.0));
-        bones.push(Bone::new("Sternum", 15.0));
-        bones.push(Bone::new("Clavicle", 15.0));
-        bones.push(Bone::new("Scapula", 16.0));
-        bones.push(Bone::new("Humerus", 30.0));
-        bones.push(Bone::new("Radius", 25.0));
-        bones.push(Bone::new("Ulna", 25.0));
-        bones.push(Bone::new("Pelvis", 20.0));
-        bones.push(Bone::new("Femur", 45.0));
-        bones.push(Bone::new("Tibia", 38.0));
-        bones.push(Bone::new("Fibula", 36.0));
+        let bones = vec![
+            // Major bones of the skeleton
+            Bone::new("Skull", 18.0),
+            Bone::new("Cervical Vertebrae", 10.0),
+            Bone::new("Thoracic Vertebrae", 20.0),
+            Bone::new("Lumbar Vertebrae", 18.0),
+            Bone::new("Sacrum", 10.0),
+            Bone::new("Ribs", 60.0),
+            Bone::new("Sternum", 15.0),
+            Bone::new("Clavicle", 15.0),
+            Bone::new("Scapula", 16.0),
+            Bone::new("Humerus", 30.0),
+            Bone::new("Radius", 25.0),
+            Bone::new("Ulna", 25.0),
+            Bone::new("Pelvis", 20.0),
+            Bone::new("Femur", 45.0),
+            Bone::new("Tibia", 38.0),
+            Bone::new("Fibula", 36.0),
+        ];
 
         Self {
             id: id as usize,
@@ -113,6 +257,8 @@ impl Bones {
             osteoblast_activity: 0.5,
             osteoclast_activity: 0.5,
             vitamin_d_receptors: 1.0,
+            fractured_ribs: 0,
+            flail_segment: false,
         }
     }
 
@@ -137,6 +283,143 @@ impl Bones {
         }
     }
 
+    /// Fracture ribs (blunt chest trauma)
+    ///
+    /// # Arguments
+    /// * `count` - Number of ribs broken
+    /// * `segmental` - Each rib broken in two places; three or more form a flail segment
+    pub fn fracture_ribs(&mut self, count: u32, segmental: bool) {
+        self.fractured_ribs = (self.fractured_ribs + count).min(24);
+        self.flail_segment |= segmental && count >= FLAIL_SEGMENT_RIBS;
+        let severity = (self.fractured_ribs as f64 / 6.0).min(1.0);
+        if let Some(ribs) = self.bones.iter_mut().find(|bone| bone.name == "Ribs") {
+            ribs.fracture(severity.max(ribs.fracture_severity));
+        }
+    }
+
+    /// Check whether a flail segment destabilises the chest wall
+    pub fn is_flail_chest(&self) -> bool {
+        self.flail_segment
+    }
+
+    /// Kill progenitors with chemotherapy and toxins and let the survivors repopulate
+    fn update_progenitors(&mut self, patient: &Patient, delta_time_s: f64) {
+        let days = delta_time_s / SECONDS_PER_DAY;
+        let marrow = &mut self.bone_marrow;
+        let toxin = patient.blood.chemistry.toxin_level_au;
+        let toxicity = marrow.chemotherapy_exposure + 0.1 * toxin / (toxin + 1000.0);
+        let reserve = marrow.progenitor_reserve;
+        // Self-renewal is logistic, so a deeply ablated marrow takes a week or two to regrow
+        marrow.progenitor_reserve += (reserve * (1.0 - reserve) / PROGENITOR_RENEWAL_DAYS
+            - PROGENITOR_KILL_PER_DAY * toxicity * reserve)
+            * days;
+        marrow.progenitor_reserve = marrow.progenitor_reserve.clamp(0.01, 1.0);
+        marrow.chemotherapy_exposure *= (-days / CHEMOTHERAPY_ELIMINATION_DAYS).exp();
+        marrow.filgrastim_mcg *= (-delta_time_s / (FILGRASTIM_ELIMINATION_H * 3600.0)).exp();
+    }
+
+    /// Produce neutrophils and other leukocytes under G-CSF control
+    ///
+    /// Infection and filgrastim expand the storage pool and release it into the
+    /// blood; infection also consumes neutrophils at the site.
+    fn update_leukopoiesis(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let days = delta_time_s / SECONDS_PER_DAY;
+        let hours = delta_time_s / 3600.0;
+        let marrow = &mut self.bone_marrow;
+        let infection = marrow.infection_severity.clamp(0.0, 1.0);
+        let output = marrow.progenitor_reserve * marrow.production_efficiency;
+        let white_cells = &mut patient.blood.cells.wbc_differential;
+        let g_csf = marrow.g_csf_drive(white_cells.neutrophils);
+
+        let pool_target = output * g_csf.sqrt();
+        marrow.neutrophil_reserve += (pool_target - marrow.neutrophil_reserve)
+            * (1.0 - (-days * g_csf.sqrt() / GRANULOCYTE_TRANSIT_DAYS).exp());
+
+        // Release from the storage pool against turnover and consumption in infected tissue
+        let release = NORMAL_NEUTROPHILS_PER_UL * marrow.neutrophil_reserve * g_csf / NEUTROPHIL_TURNOVER_H;
+        let removal = white_cells.neutrophils * (1.0 + 2.0 * infection) / NEUTROPHIL_TURNOVER_H;
+        white_cells.neutrophils = (white_cells.neutrophils + (release - removal) * hours).max(0.0);
+
+        // Longer-lived lineages follow the progenitors; stress lowers lymphocytes
+        let relax = |value: &mut f64, target: f64, time_constant_days: f64| {
+            *value += (target - *value) * (1.0 - (-days / time_constant_days).exp());
+        };
+        let defaults = WbcDifferential::default();
+        relax(&mut white_cells.lymphocytes, defaults.lymphocytes * (0.5 + 0.5 * output) * (1.0 - 0.6 * infection), 3.0);
+        relax(&mut white_cells.monocytes, defaults.monocytes * output * (1.0 + infection), 1.0);
+        relax(&mut white_cells.eosinophils, defaults.eosinophils * output * (1.0 - 0.8 * infection), 2.0);
+        relax(&mut white_cells.basophils, defaults.basophils * output, 2.0);
+    }
+
+    /// Produce platelets under thrombopoietin control
+    fn update_thrombopoiesis(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let days = delta_time_s / SECONDS_PER_DAY;
+        let marrow = &mut self.bone_marrow;
+        let platelets = &mut patient.blood.cells.platelet_count_thousand_per_ul;
+        let target_mass = marrow.progenitor_reserve
+            * marrow.production_efficiency
+            * marrow.thrombopoietin_drive(*platelets)
+            * (1.0 - 0.3 * marrow.infection_severity.clamp(0.0, 1.0));
+        marrow.megakaryocyte_mass += (target_mass - marrow.megakaryocyte_mass)
+            * (1.0 - (-days / MEGAKARYOCYTE_MATURATION_DAYS).exp());
+
+        // Platelets live ~9 days, so the count follows production with that time constant
+        let production = NORMAL_PLATELETS_K_UL * marrow.megakaryocyte_mass / PLATELET_LIFESPAN_DAYS;
+        *platelets = (*platelets + (production - *platelets / PLATELET_LIFESPAN_DAYS) * days).max(0.0);
+    }
+
+    /// Produce red cells to replace senescent ones, driven by erythropoietin and limited by iron
+    ///
+    /// Red cells live ~120 days. Erythropoietin raises production up to ~6x when
+    /// renal oxygen delivery falls; inflammation blunts the response, and
+    /// iron-restricted production makes smaller, paler cells.
+    fn update_erythropoiesis(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let days = delta_time_s / SECONDS_PER_DAY;
+        let marrow = &self.bone_marrow;
+        let drive = marrow.erythropoietin_drive()
+            * marrow.progenitor_reserve
+            * (1.0 - 0.4 * marrow.infection_severity.clamp(0.0, 1.0));
+        let availability = patient.iron.availability();
+
+        let turnover_ml = patient.fluids.reference_blood_volume_ml * NORMAL_HEMATOCRIT / RED_CELL_LIFESPAN_DAYS * days;
+        let wanted_ml = turnover_ml * drive * self.bone_marrow.production_efficiency * availability;
+        let produced_ml = patient.iron.consume(wanted_ml * IRON_PER_RED_CELL_ML) / IRON_PER_RED_CELL_ML;
+
+        // Macrophages recycle the iron from senescent cells
+        let senescent_ml = patient.fluids.red_cell_ml / RED_CELL_LIFESPAN_DAYS * days;
+        let circulating_ml = patient.fluids.red_cell_ml;
+        patient.fluids.red_cell_ml += produced_ml - senescent_ml;
+        patient.iron.recycle(senescent_ml * IRON_PER_RED_CELL_ML);
+
+        // Hemoglobin glycates at a rate proportional to glucose for as long as
+        // the cell circulates; new cells arrive unglycated, so a shortened red
+        // cell lifespan lowers the HbA1c for the same glucose exposure
+        let glucose = patient.blood.get_glucose_mg_dl();
+        let cells = &mut patient.blood.cells;
+        let glycated = cells.hba1c_percent - ADAG_GLUCOSE_INTERCEPT_MG_DL / ADAG_GLUCOSE_PER_HBA1C_PERCENT;
+        let glycation = glucose
+            / (ADAG_GLUCOSE_PER_HBA1C_PERCENT * RED_CELL_LIFESPAN_DAYS)
+            * days;
+        let dilution = if circulating_ml > 0.0 { (produced_ml / circulating_ml).min(1.0) } else { 0.0 };
+        cells.hba1c_percent += glycation - glycated.max(0.0) * dilution;
+
+        // Red cell indices drift as the cell population turns over
+        let target_mcv = 90.0 - 20.0 * (1.0 - availability) / 0.8;
+        let previous_mcv = cells.mcv_fl;
+        cells.mcv_fl += (target_mcv - cells.mcv_fl) * (days / RED_CELL_LIFESPAN_DAYS * 2.0).min(1.0);
+        cells.rdw_percent = 13.0 + (target_mcv - cells.mcv_fl).abs() * 0.4 + (90.0 - cells.mcv_fl).max(0.0) * 0.1;
+        // Microcytic cells are also hypochromic, so hemoglobin falls faster than hematocrit
+        let ratio = if previous_mcv > 0.0 { cells.mcv_fl / previous_mcv } else { 1.0 };
+        cells.rbc_count_million_per_ul /= ratio;
+        cells.mch_pg *= ratio * ratio;
+        let hemoglobin = patient.blood.get_hemoglobin_g_dl() * ratio;
+        patient.blood.set_hemoglobin_g_dl(hemoglobin);
+        let cells = &mut patient.blood.cells;
+        if cells.hematocrit_percent > 0.0 {
+            cells.mchc_g_dl = hemoglobin / cells.hematocrit_percent * 100.0;
+        }
+    }
+
     /// Calculate structural integrity (0.0-1.0)
     pub fn structural_integrity(&self) -> f64 {
         let density_factor = self.average_density() / 1.1;
@@ -189,22 +472,13 @@ impl Organ for Bones {
         }
 
         // 3. Bone marrow blood cell production
-        // RBC production (erythropoiesis)
-        let rbc_production = self.bone_marrow.get_rbc_production_rate() * delta_time_s;
-        patient.blood.cells.rbc_count_million_per_ul += rbc_production * 0.0001;
-
-        // Maintain hemoglobin with RBC production
-        let target_hgb = patient.blood.cells.rbc_count_million_per_ul * 3.0;
-        patient.blood.cells.hemoglobin_g_dl =
-            patient.blood.cells.hemoglobin_g_dl * 0.99 + target_hgb * 0.01;
-
-        // WBC production (leukopoiesis) - increase neutrophils as primary WBC
-        let wbc_production = self.bone_marrow.get_wbc_production_rate() * delta_time_s;
-        patient.blood.cells.wbc_differential.neutrophils += wbc_production * 0.1;
-
-        // Platelet production (thrombopoiesis)
-        let platelet_production = self.bone_marrow.get_platelet_production_rate() * delta_time_s;
-        patient.blood.cells.platelet_count_thousand_per_ul += platelet_production * 0.001;
+        self.update_progenitors(patient, delta_time_s);
+        // RBC production (erythropoiesis) replaces senescent cells and is limited by iron
+        self.update_erythropoiesis(patient, delta_time_s);
+        // WBC production (leukopoiesis) under G-CSF control
+        self.update_leukopoiesis(patient, delta_time_s);
+        // Platelet production (thrombopoiesis) under thrombopoietin control
+        self.update_thrombopoiesis(patient, delta_time_s);
 
         // 4. Fracture healing - requires adequate blood supply and nutrients
         let healing_factor =
@@ -214,23 +488,32 @@ impl Organ for Bones {
 
         for bone in &mut self.bones {
             if bone.is_fractured() {
-                // Progress healing
-                bone.healing_progress += healing_factor * 0.01 * delta_time_s;
+                // Progress healing over weeks
+                bone.healing_progress += healing_factor * delta_time_s / FRACTURE_HEALING_S;
                 bone.healing_progress = bone.healing_progress.min(1.0);
 
-                // Reduce fracture severity as healing progresses
+                // Reduce fracture severity as the callus remodels
                 if bone.healing_progress > 0.5 {
-                    bone.fracture_severity *= 1.0 - (0.01 * delta_time_s);
+                    bone.fracture_severity *= (-delta_time_s / (0.25 * FRACTURE_HEALING_S)).exp();
                     if bone.fracture_severity < 0.1 {
                         bone.fracture_severity = 0.0; // Fully healed
                     }
                 }
             }
         }
+        // Callus stabilises a flail segment halfway to union
+        if let Some(ribs) = self.bones.iter().find(|bone| bone.name == "Ribs") {
+            if ribs.healing_progress > 0.5 {
+                self.flail_segment = false;
+            }
+            if !ribs.is_fractured() {
+                self.fractured_ribs = 0;
+            }
+        }
 
         // 5. Bone marrow efficiency depends on oxygen and nutrients
-        let o2_saturation = patient.blood.gases.sao2_percent / 100.0;
-        let glucose_factor = (patient.blood.chemistry.glucose_mg_dl / 90.0).clamp(0.5, 1.5);
+        let o2_saturation = patient.blood.get_sao2_percent() / 100.0;
+        let glucose_factor = (patient.blood.get_glucose_mg_dl() / 90.0).clamp(0.5, 1.5);
         self.bone_marrow.production_efficiency =
             (o2_saturation * glucose_factor * 0.3 + self.bone_marrow.production_efficiency * 0.7)
             .clamp(0.1, 1.0);
@@ -243,13 +526,20 @@ impl Organ for Bones {
 
     fn get_summary(&self) -> String {
         format!(
-            "Bones - Density: {:.2} g/cm³, Calcium stores: {:.1}g, Fractured bones: {}, \
-             Marrow efficiency: {:.1}%, Structural integrity: {:.1}%, \
+            "Bones - Density: {:.2} g/cm³, Calcium stores: {:.1}g, Fractured bones: {}{}, \
+             Marrow efficiency: {:.1}%, Progenitors: {:.0}%, EPO: {:.0} mU/mL, Structural integrity: {:.1}%, \
              Osteoblast/Osteoclast: {:.2}/{:.2}",
             self.average_density(),
             self.total_calcium_stores_g,
             self.fractured_bone_count(),
+            match (self.fractured_ribs, self.flail_segment) {
+                (0, _) => String::new(),
+                (ribs, true) => format!(" ({} ribs, FLAIL CHEST)", ribs),
+                (ribs, false) => format!(" ({} ribs)", ribs),
+            },
             self.bone_marrow.production_efficiency * 100.0,
+            self.bone_marrow.progenitor_reserve * 100.0,
+            self.bone_marrow.erythropoietin_mu_ml,
             self.structural_integrity() * 100.0,
             self.osteoblast_activity,
             self.osteoclast_activity
@@ -271,6 +561,10 @@ impl Organ for Bones {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
 }
 
 impl Default for BoneMarrow {
@@ -278,3 +572,55 @@ impl Default for BoneMarrow {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fidelity::Fidelity;
+    use crate::fluids::IntakeRoute;
+    use crate::organs::kidneys::Kidneys;
+    use crate::patient::{initialize_patient, update_patient};
+
+    /// Run a patient for whole days on maintenance fluids, one hour at a time
+    fn run_days(patient: &mut Patient, days: u32) {
+        for _ in 0..days * 24 {
+            patient.give_fluid(IntakeRoute::Intravenous, 85.0);
+            update_patient(patient, 3600.0);
+        }
+    }
+
+    #[test]
+    fn test_marrow_replaces_lost_red_cells_and_is_suppressed_by_chemotherapy() {
+        let mut patient = initialize_patient(1, 3);
+        patient.fidelity = Fidelity::Fast;
+        for _ in 0..60 {
+            update_patient(&mut patient, 10.0);
+        }
+
+        // Erythropoietin rises after a bleed and the marrow rebuilds the red cell mass
+        let mut bled = patient.fork();
+        bled.hemorrhage(1000.0);
+        bled.give_fluid(IntakeRoute::Intravenous, 1000.0);
+        run_days(&mut bled, 1);
+        let anemic_hemoglobin = bled.blood.cells.hemoglobin_g_dl;
+        let anemic_red_cells = bled.fluids.red_cell_ml;
+        assert!(bled.get_organ::<Kidneys>().unwrap().erythropoietin_mu_ml > 1.5 * NORMAL_ERYTHROPOIETIN_MU_ML);
+        run_days(&mut bled, 20);
+        assert!(bled.fluids.red_cell_ml > anemic_red_cells + 100.0);
+        assert!(bled.blood.cells.hemoglobin_g_dl > anemic_hemoglobin + 0.5);
+
+        // Chemotherapy ablates the progenitors: short-lived neutrophils fall first, then platelets
+        let mut treated = patient.fork();
+        let baseline_platelets = treated.blood.cells.platelet_count_thousand_per_ul;
+        treated.get_organ_mut::<Bones>().unwrap().bone_marrow.give_chemotherapy(1.0);
+        run_days(&mut treated, 10);
+        assert!(treated.blood.cells.wbc_differential.neutrophils < 1000.0);
+        run_days(&mut treated, 6);
+        assert!(treated.blood.cells.platelet_count_thousand_per_ul < 0.5 * baseline_platelets);
+        assert!(treated.blood.cells.hemoglobin_g_dl < patient.blood.cells.hemoglobin_g_dl);
+        // The surviving progenitors regrow and the counts recover
+        run_days(&mut treated, 12);
+        assert!(treated.get_organ::<Bones>().unwrap().bone_marrow.progenitor_reserve > 0.9);
+        assert!(treated.blood.cells.wbc_differential.neutrophils > 3000.0);
+    }
+}
@@ -1,15 +1,193 @@
 //! Brain organ simulation
 //!
 //! Simulates neurological vitals and autonomic control including:
-//! - 5 brain regions
-//! - Glasgow Coma Scale (GCS)
-//! - Intracranial pressure (ICP)
+//! - 6 brain regions with left and right vascular territories
+//! - Focal ischemic stroke from cerebral artery occlusion (ACA, MCA, PCA, basilar)
+//!   with core/penumbra progression and NIHSS-style deficit scoring
+//! - Glasgow Coma Scale (GCS) from brainstem arousal, global perfusion and metabolism,
+//!   focal lesions (aphasia, paralysis) and pharmacologic sedation
+//! - Intracranial pressure (ICP) from the Monro-Kellie volumes of brain tissue, CSF,
+//!   blood and mass lesions, with an exponential pressure-volume curve
+//! - Cerebral edema after infarction, intracranial hematoma, osmotherapy and CSF drainage
 //! - Cerebral perfusion pressure (CPP)
-//! - EEG waveform
+//! - Cerebral blood flow with CO2 reactivity, cerebral oxygen consumption (CMRO2),
+//!   jugular venous saturation (SjvO2) and NIRS regional oxygen saturation (rSO2)
+//! - Cushing reflex (hypertension and bradycardia) and brainstem herniation
+//! - Baroreflex and chemoreflex control of sympathetic outflow, vagal tone and
+//!   ventilation, blunted when the brainstem is injured or hypoperfused
+//! - Seizures provoked by hypoglycemia, hyponatremia, hypoxia or toxins, with raised
+//!   cerebral metabolic demand, lactic acidosis, post-ictal depression and
+//!   benzodiazepine treatment
+//! - EEG waveform and background pattern
 
+use crate::fluids::OutputRoute;
+use crate::models::autoregulation::AutoregulationCurve;
 use crate::organ::{Organ, OrganId};
 use crate::patient::Patient;
-use std::collections::VecDeque;
+use crate::signals::Signal;
+use crate::tissue::{TissuePerfusion, TissueState};
+use crate::waveform::{RingBuffer, WaveformSampler};
+
+/// Flow reaching an occluded territory through leptomeningeal collaterals (fraction of normal)
+const COLLATERAL_FLOW: f64 = 0.25;
+
+/// Intracranial pressure with normal intracranial volumes (mmHg)
+const NORMAL_ICP_MMHG: f64 = 10.0;
+
+/// Volume added to the cranium that raises ICP tenfold (pressure-volume index, mL)
+const PRESSURE_VOLUME_INDEX_ML: f64 = 25.0;
+
+/// Brain parenchyma volume (mL)
+const BRAIN_TISSUE_VOLUME_ML: f64 = 1300.0;
+
+/// Normal intracranial CSF volume (mL)
+const NORMAL_CSF_VOLUME_ML: f64 = 150.0;
+
+/// CSF left once the ventricles and basal cisterns are effaced (mL)
+const MIN_CSF_VOLUME_ML: f64 = 90.0;
+
+/// CSF a ventricular drain leaves behind in collapsed ventricles (mL)
+const MIN_DRAINED_CSF_VOLUME_ML: f64 = 20.0;
+
+/// Normal cerebral blood volume (mL)
+const NORMAL_CEREBRAL_BLOOD_VOLUME_ML: f64 = 100.0;
+
+/// CSF production by the choroid plexus (mL/min)
+const CSF_PRODUCTION_ML_PER_MIN: f64 = 0.35;
+
+/// Dural venous sinus pressure, against which CSF is absorbed (mmHg)
+const VENOUS_SINUS_PRESSURE_MMHG: f64 = 5.0;
+
+/// Extra brain water per mL of infarcted tissue at the peak of cytotoxic edema (mL)
+const INFARCT_EDEMA_FRACTION: f64 = 0.3;
+
+/// Normal cerebral blood flow (mL/100 g/min)
+const NORMAL_CBF_ML_100G_MIN: f64 = 50.0;
+
+/// Normal cerebral metabolic rate for oxygen (mL O2/100 g/min)
+const NORMAL_CMRO2_ML_100G_MIN: f64 = 3.3;
+
+/// Highest fraction of delivered oxygen the brain can extract
+const MAX_OXYGEN_EXTRACTION: f64 = 0.8;
+
+/// Arterial contribution to the NIRS signal (the rest is venous blood)
+const NIRS_ARTERIAL_FRACTION: f64 = 0.3;
+
+/// Duration of a provoked seizure that stops on its own (s)
+const PROVOKED_SEIZURE_DURATION_S: f64 = 90.0;
+
+/// Seizure duration defining status epilepticus (s)
+const STATUS_EPILEPTICUS_S: f64 = 300.0;
+
+/// Cerebral metabolic rate during a generalized seizure (relative to normal)
+const ICTAL_METABOLIC_DEMAND: f64 = 3.0;
+
+/// Lorazepam-equivalent dose giving half-maximal anticonvulsant effect (mg)
+const BENZODIAZEPINE_EC50_MG: f64 = 2.0;
+
+/// EEG sampling rate (Hz)
+const EEG_SAMPLE_RATE_HZ: f64 = 256.0;
+
+/// Length of EEG kept in the waveform buffer (s)
+const EEG_BUFFER_S: f64 = 10.0;
+
+/// Normal adult mean arterial pressure the baroreflex defends (mmHg)
+pub const NORMAL_BAROREFLEX_SET_POINT_MMHG: f64 = 93.0;
+
+/// Time constant of the baroreflex resetting to a sustained pressure (s)
+const BAROREFLEX_RESETTING_S: f64 = 1800.0;
+
+/// Change in sympathetic outflow per fractional fall in pressure below the set point
+const BAROREFLEX_GAIN: f64 = 3.0;
+
+/// Time constant of the autonomic outflow following its reflexes (s)
+const AUTONOMIC_RESPONSE_S: f64 = 10.0;
+
+/// Arterial PaCO2 above which the central chemoreceptors drive breathing (mmHg)
+const CHEMOREFLEX_PACO2_THRESHOLD_MMHG: f64 = 45.0;
+
+/// Arterial saturation below which the carotid bodies drive breathing (%)
+const CHEMOREFLEX_SAO2_THRESHOLD_PERCENT: f64 = 95.0;
+
+/// Background EEG pattern
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EegPattern {
+    /// Posterior dominant alpha rhythm
+    Normal,
+    /// Diffuse theta/delta slowing of encephalopathy
+    Slowing,
+    /// Rhythmic spike-and-wave discharges
+    Ictal,
+    /// Low-amplitude delta after a seizure
+    Postictal,
+    /// Isoelectric (no cortical activity)
+    Suppressed,
+}
+
+/// Cerebral hemisphere (or side of a midline structure)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hemisphere {
+    Left,
+    Right,
+}
+
+impl Hemisphere {
+    /// Both hemispheres
+    pub const BOTH: [Hemisphere; 2] = [Hemisphere::Left, Hemisphere::Right];
+}
+
+/// Major cerebral artery
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CerebralArtery {
+    AnteriorCerebral,
+    MiddleCerebral,
+    PosteriorCerebral,
+    /// Midline artery supplying the brainstem, cerebellum and both posterior cerebral arteries
+    Basilar,
+}
+
+impl CerebralArtery {
+    /// All cerebral arteries
+    pub const ALL: [CerebralArtery; 4] = [
+        CerebralArtery::AnteriorCerebral,
+        CerebralArtery::MiddleCerebral,
+        CerebralArtery::PosteriorCerebral,
+        CerebralArtery::Basilar,
+    ];
+
+    /// Name of the corresponding vessel in the vascular system
+    pub fn vessel_name(&self, side: Hemisphere) -> &'static str {
+        match (self, side) {
+            (CerebralArtery::AnteriorCerebral, Hemisphere::Left) => "Anterior Cerebral Artery (L)",
+            (CerebralArtery::AnteriorCerebral, Hemisphere::Right) => "Anterior Cerebral Artery (R)",
+            (CerebralArtery::MiddleCerebral, Hemisphere::Left) => "Middle Cerebral Artery (L)",
+            (CerebralArtery::MiddleCerebral, Hemisphere::Right) => "Middle Cerebral Artery (R)",
+            (CerebralArtery::PosteriorCerebral, Hemisphere::Left) => "Posterior Cerebral Artery (L)",
+            (CerebralArtery::PosteriorCerebral, Hemisphere::Right) => "Posterior Cerebral Artery (R)",
+            (CerebralArtery::Basilar, _) => "Basilar Artery",
+        }
+    }
+}
+
+/// The part of a brain region supplied by one artery
+#[derive(Debug, Clone)]
+pub struct VascularTerritory {
+    pub artery: CerebralArtery,
+    /// Fraction of the region supplied by this artery
+    pub weight: f64,
+    pub tissue: TissuePerfusion,
+}
+
+impl VascularTerritory {
+    fn new(artery: CerebralArtery, weight: f64) -> Self {
+        // Neurons stop firing below ~40% of normal flow and die within minutes below ~20%
+        Self {
+            artery,
+            weight,
+            tissue: TissuePerfusion::new(0.4, 0.2, 600.0),
+        }
+    }
+}
 
 /// Brain region
 #[derive(Debug, Clone)]
@@ -17,6 +195,127 @@ pub struct BrainRegion {
     pub name: String,
     pub metabolic_activity: f64,  // 0.0 = inactive, 1.0 = normal
     pub blood_flow_ml_per_min: f64,
+    /// Vascular territories of the left half
+    pub left_territories: Vec<VascularTerritory>,
+    /// Vascular territories of the right half
+    pub right_territories: Vec<VascularTerritory>,
+}
+
+impl BrainRegion {
+    /// Create a region supplied by the given arteries on each side
+    ///
+    /// # Arguments
+    /// * `name` - Region name
+    /// * `blood_flow_ml_per_min` - Normal regional blood flow
+    /// * `supply` - Supplying arteries and the fraction of the region each supplies
+    pub fn new(name: &str, blood_flow_ml_per_min: f64, supply: &[(CerebralArtery, f64)]) -> Self {
+        let territories: Vec<VascularTerritory> = supply
+            .iter()
+            .map(|&(artery, weight)| VascularTerritory::new(artery, weight))
+            .collect();
+        Self {
+            name: name.to_string(),
+            metabolic_activity: 1.0,
+            blood_flow_ml_per_min,
+            left_territories: territories.clone(),
+            right_territories: territories,
+        }
+    }
+
+    /// Get the territories of one side
+    pub fn territories(&self, side: Hemisphere) -> &[VascularTerritory] {
+        match side {
+            Hemisphere::Left => &self.left_territories,
+            Hemisphere::Right => &self.right_territories,
+        }
+    }
+
+    fn territories_mut(&mut self, side: Hemisphere) -> &mut [VascularTerritory] {
+        match side {
+            Hemisphere::Left => &mut self.left_territories,
+            Hemisphere::Right => &mut self.right_territories,
+        }
+    }
+
+    /// Get the territory of one side supplied by an artery
+    pub fn territory(&self, side: Hemisphere, artery: CerebralArtery) -> Option<&VascularTerritory> {
+        self.territories(side).iter().find(|t| t.artery == artery)
+    }
+
+    /// Fraction of normal function of one side (0.0-1.0)
+    pub fn function(&self, side: Hemisphere) -> f64 {
+        self.territories(side).iter().map(|t| t.weight * t.tissue.function()).sum()
+    }
+
+    /// Fraction of one side that is infarcted (0.0-1.0)
+    pub fn infarcted_fraction(&self, side: Hemisphere) -> f64 {
+        self.territories(side).iter().map(|t| t.weight * t.tissue.injury).sum()
+    }
+
+    /// Most severe tissue state on either side
+    pub fn worst_state(&self) -> TissueState {
+        let states = self.left_territories.iter().chain(&self.right_territories).map(|t| t.tissue.state);
+        if states.clone().any(|s| s == TissueState::Infarcted) {
+            TissueState::Infarcted
+        } else if states.clone().any(|s| s == TissueState::Ischemic) {
+            TissueState::Ischemic
+        } else {
+            TissueState::Healthy
+        }
+    }
+
+    /// Function lost in one artery's territory on one side (0.0-1.0)
+    fn territory_loss(&self, side: Hemisphere, artery: CerebralArtery) -> f64 {
+        self.territory(side, artery).map(|t| 1.0 - t.tissue.function()).unwrap_or(0.0)
+    }
+}
+
+/// NIH Stroke Scale-style deficit scoring
+#[derive(Debug, Clone, Default)]
+pub struct StrokeScale {
+    pub level_of_consciousness: i32, // 0-3
+    pub gaze: i32,                   // 0-2
+    pub visual_fields: i32,          // 0-3
+    pub facial_palsy: i32,           // 0-3
+    pub motor_arm_left: i32,         // 0-4
+    pub motor_arm_right: i32,        // 0-4
+    pub motor_leg_left: i32,         // 0-4
+    pub motor_leg_right: i32,        // 0-4
+    pub limb_ataxia: i32,            // 0-2
+    pub sensory: i32,                // 0-2
+    pub language: i32,               // 0-3
+    pub dysarthria: i32,             // 0-2
+    pub neglect: i32,                // 0-2
+}
+
+impl StrokeScale {
+    /// Get total score (0 = no deficit, 42 = maximum)
+    pub fn total(&self) -> i32 {
+        self.level_of_consciousness
+            + self.gaze
+            + self.visual_fields
+            + self.facial_palsy
+            + self.motor_arm_left
+            + self.motor_arm_right
+            + self.motor_leg_left
+            + self.motor_leg_right
+            + self.limb_ataxia
+            + self.sensory
+            + self.language
+            + self.dysarthria
+            + self.neglect
+    }
+
+    /// Get stroke severity category
+    pub fn category(&self) -> &'static str {
+        match self.total() {
+            0 => "No stroke symptoms",
+            1..=4 => "Minor",
+            5..=15 => "Moderate",
+            16..=20 => "Moderate to severe",
+            _ => "Severe",
+        }
+    }
 }
 
 /// Glasgow Coma Scale components
@@ -55,7 +354,7 @@ impl Default for GlasgowComaScale {
 }
 
 /// Brain organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Brain {
     id: OrganId,
     /// Frontal lobe
@@ -68,56 +367,779 @@ pub struct Brain {
     pub occipital_lobe: BrainRegion,
     /// Cerebellum
     pub cerebellum: BrainRegion,
+    /// Brainstem
+    pub brainstem: BrainRegion,
     /// Glasgow Coma Scale
     pub gcs: GlasgowComaScale,
     /// Intracranial pressure (mmHg)
     pub intracranial_pressure_mmhg: f64,
     /// Cerebral perfusion pressure (mmHg)
     pub cerebral_perfusion_pressure_mmhg: f64,
-    /// EEG waveform
-    pub eeg_waveform: VecDeque<f64>,
-    /// Autonomic control of heart rate
-    pub autonomic_heart_rate_target: f64,
-    /// Autonomic control of respiration
-    pub autonomic_respiration_target: f64,
+    /// EEG waveform (µV), sampled at `EEG_SAMPLE_RATE_HZ`
+    pub eeg_waveform: RingBuffer,
+    /// Clock placing the EEG samples
+    pub eeg_sampler: WaveformSampler,
+    /// Mean arterial pressure the baroreflex defends (mmHg); resets towards a sustained pressure
+    pub baroreflex_set_point_mmhg: f64,
+    /// Perfusion and intracranial pressure thresholds relative to an adult's (1.0);
+    /// a newborn's brain is perfused at about half the adult pressure
+    pub pressure_scale: f64,
+    /// Sympathetic outflow from the vasomotor centre above rest (-0.5 = withdrawn,
+    /// 0.0 = rest, 1.0 = maximal reflex drive), published as sympathetic tone
+    pub sympathetic_outflow: f64,
+    /// Ventilatory drive from the chemoreceptors (1.0 = rest), read by the lungs
+    pub ventilatory_drive: f64,
+    /// Remaining duration of the current seizure (s)
+    pub seizure_remaining_s: f64,
+    /// Number of seizures since creation
+    pub seizure_count: u32,
+    /// Duration of the current seizure so far (s)
+    pub seizure_elapsed_s: f64,
+    /// Remaining post-ictal period (s)
+    pub postictal_remaining_s: f64,
+    /// Metabolic provocation of seizures (>= 1.0 provokes seizures, >= 2.0 status epilepticus)
+    pub seizure_provocation: f64,
+    /// Cerebral metabolic rate relative to normal
+    pub cerebral_metabolic_demand: f64,
+    /// Benzodiazepine in the body (lorazepam-equivalent mg)
+    pub benzodiazepine_mg: f64,
+    /// Background EEG pattern
+    pub eeg_pattern: EegPattern,
+    /// Pharmacologic sedation from anesthetic or sedative infusions (0.0 = none, 1.0 = general anesthesia)
+    pub sedation_level: f64,
+    /// Length of the post-ictal period that began when the last seizure ended (s)
+    postictal_duration_s: f64,
+    /// Time since the last seizure ended (s)
+    interictal_s: f64,
+    /// Running EEG clock (s)
+    eeg_time_s: f64,
+    /// Global cerebral blood flow (mL/100 g/min)
+    pub cerebral_blood_flow_ml_100g_min: f64,
+    /// Cerebral metabolic rate for oxygen actually consumed (mL O2/100 g/min)
+    pub cmro2_ml_100g_min: f64,
+    /// Jugular venous bulb oxygen saturation (%)
+    pub jugular_venous_saturation_percent: f64,
+    /// Frontal NIRS regional oxygen saturation, indexed by hemisphere (%)
+    pub regional_oxygen_saturation_percent: [f64; 2],
+    /// Intracranial cerebrospinal fluid volume (mL)
+    pub csf_volume_ml: f64,
+    /// Cerebral blood volume (mL)
+    pub cerebral_blood_volume_ml: f64,
+    /// Extra brain water from cerebral edema (mL)
+    pub cerebral_edema_ml: f64,
+    /// Intracranial hematoma volume (mL)
+    pub hematoma_volume_ml: f64,
+    /// Cushing response to brainstem ischemia (0.0 = none, 1.0 = maximal)
+    pub cushing_response: f64,
+    /// Compression of the brainstem by herniating brain (0.0 = none, 1.0 = complete)
+    pub brainstem_compression: f64,
+    /// Opening pressure of an external ventricular drain (mmHg, None = no drain)
+    pub ventricular_drain_mmhg: Option<f64>,
+    /// CSF drained through the ventricular drain (mL)
+    pub csf_drained_ml: f64,
+    /// Blood still to enter an expanding hematoma (mL)
+    pending_hemorrhage_ml: f64,
+    /// Injured tissue whose edema has already resolved (mL)
+    resolved_injury_ml: f64,
+    /// Brain water currently drawn out by osmotherapy (mL)
+    osmotic_dehydration_ml: f64,
+    /// Fraction of normal flow through each cerebral artery, indexed by artery then side
+    artery_patency: [[f64; 2]; 4],
 }
 
 impl Brain {
     /// Create new brain
     pub fn new(id: OrganId) -> Self {
+        use CerebralArtery::*;
         Self {
             id,
-            frontal_lobe: BrainRegion {
-                name: "Frontal".to_string(),
-                metabolic_activity: 1.0,
-                blood_flow_ml_per_min: 50.0,
-            },
-            parietal_lobe: BrainRegion {
-                name: "Parietal".to_string(),
-                metabolic_activity: 1.0,
-                blood_flow_ml_per_min: 45.0,
-            },
-            temporal_lobe: BrainRegion {
-                name: "Temporal".to_string(),
-                metabolic_activity: 1.0,
-                blood_flow_ml_per_min: 45.0,
-            },
-            occipital_lobe: BrainRegion {
-                name: "Occipital".to_string(),
-                metabolic_activity: 1.0,
-                blood_flow_ml_per_min: 40.0,
-            },
-            cerebellum: BrainRegion {
-                name: "Cerebellum".to_string(),
-                metabolic_activity: 1.0,
-                blood_flow_ml_per_min: 30.0,
-            },
+            frontal_lobe: BrainRegion::new("Frontal", 50.0, &[(AnteriorCerebral, 0.4), (MiddleCerebral, 0.6)]),
+            parietal_lobe: BrainRegion::new(
+                "Parietal",
+                45.0,
+                &[(AnteriorCerebral, 0.15), (MiddleCerebral, 0.7), (PosteriorCerebral, 0.15)],
+            ),
+            temporal_lobe: BrainRegion::new("Temporal", 45.0, &[(MiddleCerebral, 0.7), (PosteriorCerebral, 0.3)]),
+            occipital_lobe: BrainRegion::new("Occipital", 40.0, &[(PosteriorCerebral, 1.0)]),
+            cerebellum: BrainRegion::new("Cerebellum", 30.0, &[(Basilar, 1.0)]),
+            brainstem: BrainRegion::new("Brainstem", 20.0, &[(Basilar, 1.0)]),
             gcs: GlasgowComaScale::default(),
             intracranial_pressure_mmhg: 10.0,
             cerebral_perfusion_pressure_mmhg: 70.0,
-            eeg_waveform: VecDeque::with_capacity(1000),
-            autonomic_heart_rate_target: 75.0,
-            autonomic_respiration_target: 16.0,
+            eeg_waveform: RingBuffer::new((EEG_SAMPLE_RATE_HZ * EEG_BUFFER_S) as usize),
+            eeg_sampler: WaveformSampler::new(EEG_SAMPLE_RATE_HZ, EEG_BUFFER_S),
+            baroreflex_set_point_mmhg: NORMAL_BAROREFLEX_SET_POINT_MMHG,
+            pressure_scale: 1.0,
+            sympathetic_outflow: 0.0,
+            ventilatory_drive: 1.0,
+            seizure_remaining_s: 0.0,
+            seizure_count: 0,
+            seizure_elapsed_s: 0.0,
+            postictal_remaining_s: 0.0,
+            seizure_provocation: 0.0,
+            cerebral_metabolic_demand: 1.0,
+            benzodiazepine_mg: 0.0,
+            eeg_pattern: EegPattern::Normal,
+            sedation_level: 0.0,
+            postictal_duration_s: 0.0,
+            interictal_s: f64::INFINITY,
+            eeg_time_s: 0.0,
+            cerebral_blood_flow_ml_100g_min: NORMAL_CBF_ML_100G_MIN,
+            cmro2_ml_100g_min: NORMAL_CMRO2_ML_100G_MIN,
+            jugular_venous_saturation_percent: 65.0,
+            regional_oxygen_saturation_percent: [75.0; 2],
+            csf_volume_ml: NORMAL_CSF_VOLUME_ML,
+            cerebral_blood_volume_ml: NORMAL_CEREBRAL_BLOOD_VOLUME_ML,
+            cerebral_edema_ml: 0.0,
+            hematoma_volume_ml: 0.0,
+            cushing_response: 0.0,
+            brainstem_compression: 0.0,
+            ventricular_drain_mmhg: None,
+            csf_drained_ml: 0.0,
+            pending_hemorrhage_ml: 0.0,
+            resolved_injury_ml: 0.0,
+            osmotic_dehydration_ml: 0.0,
+            artery_patency: [[1.0; 2]; 4],
+        }
+    }
+
+    /// Set the fraction of normal flow through a cerebral artery, from the vascular system
+    ///
+    /// # Arguments
+    /// * `artery` - Cerebral artery
+    /// * `side` - Side (ignored for the basilar artery)
+    /// * `patency` - Fraction of normal flow (0.0 = occluded, 1.0 = open)
+    pub fn set_artery_patency(&mut self, artery: CerebralArtery, side: Hemisphere, patency: f64) {
+        let patency = patency.clamp(0.0, 1.0);
+        if artery == CerebralArtery::Basilar {
+            self.artery_patency[artery as usize] = [patency; 2];
+        } else {
+            self.artery_patency[artery as usize][side as usize] = patency;
+        }
+    }
+
+    /// Get the fraction of normal flow through a cerebral artery
+    pub fn get_artery_patency(&self, artery: CerebralArtery, side: Hemisphere) -> f64 {
+        self.artery_patency[artery as usize][side as usize]
+    }
+
+    /// All brain regions
+    pub fn regions(&self) -> [&BrainRegion; 6] {
+        [
+            &self.frontal_lobe,
+            &self.parietal_lobe,
+            &self.temporal_lobe,
+            &self.occipital_lobe,
+            &self.cerebellum,
+            &self.brainstem,
+        ]
+    }
+
+    /// All brain regions, mutably
+    pub fn regions_mut(&mut self) -> [&mut BrainRegion; 6] {
+        [
+            &mut self.frontal_lobe,
+            &mut self.parietal_lobe,
+            &mut self.temporal_lobe,
+            &mut self.occipital_lobe,
+            &mut self.cerebellum,
+            &mut self.brainstem,
+        ]
+    }
+
+    /// Update flow and injury in every vascular territory
+    ///
+    /// # Arguments
+    /// * `autoregulated_flow` - Global cerebral blood flow relative to normal
+    /// * `oxygen_factor` - Arterial oxygenation relative to normal
+    /// * `delta_time_s` - Time step in seconds
+    fn update_territories(&mut self, autoregulated_flow: f64, oxygen_factor: f64, delta_time_s: f64) {
+        let mut patency = self.artery_patency;
+        let basilar = CerebralArtery::Basilar as usize;
+        // Herniation compresses the brainstem and the posterior circulation
+        for side_patency in patency[basilar].iter_mut() {
+            *side_patency *= 1.0 - self.brainstem_compression;
+        }
+        for region in self.regions_mut() {
+            for side in Hemisphere::BOTH {
+                for territory in region.territories_mut(side) {
+                    let mut artery_patency = patency[territory.artery as usize][side as usize];
+                    // The posterior cerebral arteries arise from the basilar artery
+                    if territory.artery == CerebralArtery::PosteriorCerebral {
+                        artery_patency *= patency[basilar][side as usize];
+                    }
+                    // Collaterals partly supply an occluded territory (the penumbra)
+                    let focal_flow = artery_patency + (1.0 - artery_patency) * COLLATERAL_FLOW;
+                    territory.tissue.update(focal_flow * autoregulated_flow, oxygen_factor, delta_time_s);
+                }
+            }
+        }
+    }
+
+    /// Calculate blood flow, oxygen consumption and venous saturations from the territories
+    ///
+    /// Each territory consumes oxygen in proportion to its living tissue and metabolic
+    /// demand, extracting more of what it is delivered as flow falls until extraction
+    /// is exhausted.
+    ///
+    /// # Arguments
+    /// * `sao2_percent` - Arterial oxygen saturation
+    /// * `oxygen_content_ml_dl` - Arterial oxygen content (mL O2/dL)
+    fn update_cerebral_oxygenation(&mut self, sao2_percent: f64, oxygen_content_ml_dl: f64) {
+        let demand = NORMAL_CMRO2_ML_100G_MIN * self.cerebral_metabolic_demand;
+        // Oxygen (mL O2/100 g/min) delivered to and consumed by one territory
+        let exchange = |territory: &VascularTerritory| {
+            let tissue = &territory.tissue;
+            let delivered = NORMAL_CBF_ML_100G_MIN * tissue.relative_flow * oxygen_content_ml_dl / 100.0;
+            let consumed = (demand * (1.0 - tissue.injury)).min(MAX_OXYGEN_EXTRACTION * delivered);
+            (delivered, consumed)
+        };
+        let venous_saturation = |delivered: f64, consumed: f64| {
+            if delivered > 1e-9 {
+                sao2_percent * (1.0 - consumed / delivered)
+            } else {
+                0.0
+            }
+        };
+
+        // The jugular bulb collects blood from the whole brain, weighted by regional flow
+        let (mut flow, mut delivered, mut consumed, mut weight) = (0.0, 0.0, 0.0, 0.0);
+        for region in self.regions() {
+            for side in Hemisphere::BOTH {
+                for territory in region.territories(side) {
+                    let w = territory.weight * region.blood_flow_ml_per_min;
+                    let (d, c) = exchange(territory);
+                    flow += w * NORMAL_CBF_ML_100G_MIN * territory.tissue.relative_flow;
+                    delivered += w * d;
+                    consumed += w * c;
+                    weight += w;
+                }
+            }
+        }
+        self.cerebral_blood_flow_ml_100g_min = flow / weight;
+        self.cmro2_ml_100g_min = consumed / weight;
+        self.jugular_venous_saturation_percent = venous_saturation(delivered, consumed);
+
+        // Forehead NIRS probes sample the frontal cortex of each side
+        for side in Hemisphere::BOTH {
+            let (delivered, consumed) = self
+                .frontal_lobe
+                .territories(side)
+                .iter()
+                .map(|t| {
+                    let (d, c) = exchange(t);
+                    (t.weight * d, t.weight * c)
+                })
+                .fold((0.0, 0.0), |(d, c), (td, tc)| (d + td, c + tc));
+            self.regional_oxygen_saturation_percent[side as usize] = NIRS_ARTERIAL_FRACTION * sao2_percent
+                + (1.0 - NIRS_ARTERIAL_FRACTION) * venous_saturation(delivered, consumed);
+        }
+    }
+
+    /// Check for cerebral oxygen desaturation (SjvO2 or rSO2 below 50%)
+    pub fn is_cerebral_desaturation(&self) -> bool {
+        self.jugular_venous_saturation_percent < 50.0
+            || self.regional_oxygen_saturation_percent.iter().any(|&s| s < 50.0)
+    }
+
+    /// Check for cerebral hyperemia (flow in excess of demand, SjvO2 above 75%)
+    pub fn is_cerebral_hyperemia(&self) -> bool {
+        self.jugular_venous_saturation_percent > 75.0
+    }
+
+    /// Score focal neurological deficits on an NIHSS-style scale
+    pub fn get_stroke_scale(&self) -> StrokeScale {
+        use CerebralArtery::*;
+        use Hemisphere::*;
+        let score = |loss: f64, max: i32| ((loss * max as f64).round() as i32).clamp(0, max);
+        let frontal = &self.frontal_lobe;
+        let parietal = &self.parietal_lobe;
+
+        // Each hemisphere controls the opposite side of the body
+        let face_arm_loss = |side| frontal.territory_loss(side, MiddleCerebral);
+        let leg_loss = |side| frontal.territory_loss(side, AnteriorCerebral).max(0.3 * face_arm_loss(side));
+        let brainstem_loss = 1.0 - (self.brainstem.function(Left) + self.brainstem.function(Right)) / 2.0;
+        let cerebellar_loss = 1.0 - (self.cerebellum.function(Left) + self.cerebellum.function(Right)) / 2.0;
+        let visual_loss = |side| self.occipital_lobe.territory_loss(side, PosteriorCerebral);
+        let sensory_loss = |side: Hemisphere| {
+            parietal.territory_loss(side, MiddleCerebral).max(parietal.territory_loss(side, AnteriorCerebral))
+        };
+
+        // Brainstem lesions cause weakness on both sides
+        let motor = |limb_loss: f64| score(limb_loss.max(brainstem_loss), 4);
+        let visual_fields = if visual_loss(Left) > 0.5 && visual_loss(Right) > 0.5 {
+            3
+        } else {
+            score(visual_loss(Left).max(visual_loss(Right)), 2)
+        };
+
+        // Language is dominant (left) hemisphere: Broca's area (frontal) and Wernicke's area (temporal)
+        let language_loss =
+            0.5 * frontal.territory_loss(Left, MiddleCerebral) + 0.5 * self.temporal_lobe.territory_loss(Left, MiddleCerebral);
+
+        StrokeScale {
+            level_of_consciousness: score(brainstem_loss, 3),
+            gaze: score(face_arm_loss(Left).max(face_arm_loss(Right)).max(brainstem_loss), 2),
+            visual_fields,
+            facial_palsy: score(face_arm_loss(Left).max(face_arm_loss(Right)).max(brainstem_loss), 3),
+            motor_arm_left: motor(face_arm_loss(Right)),
+            motor_arm_right: motor(face_arm_loss(Left)),
+            motor_leg_left: motor(leg_loss(Right)),
+            motor_leg_right: motor(leg_loss(Left)),
+            limb_ataxia: score(cerebellar_loss, 2),
+            sensory: score(sensory_loss(Left).max(sensory_loss(Right)), 2),
+            language: score(language_loss, 3),
+            dysarthria: score(brainstem_loss.max(0.5 * face_arm_loss(Left).max(face_arm_loss(Right))), 2),
+            // Neglect follows non-dominant (right) parietal injury
+            neglect: score(parietal.territory_loss(Right, MiddleCerebral), 2),
+        }
+    }
+
+    /// Describe focal neurological deficits
+    pub fn get_focal_deficits(&self) -> Vec<String> {
+        let scale = self.get_stroke_scale();
+        let mut deficits = Vec::new();
+        if scale.motor_arm_right + scale.motor_leg_right > 0 && scale.motor_arm_left + scale.motor_leg_left == 0 {
+            deficits.push("Right hemiparesis".to_string());
+        } else if scale.motor_arm_left + scale.motor_leg_left > 0 && scale.motor_arm_right + scale.motor_leg_right == 0 {
+            deficits.push("Left hemiparesis".to_string());
+        } else if scale.motor_arm_left + scale.motor_leg_left > 0 {
+            deficits.push("Quadriparesis".to_string());
+        }
+        if scale.language > 0 {
+            deficits.push("Aphasia".to_string());
+        }
+        if scale.neglect > 0 {
+            deficits.push("Left hemineglect".to_string());
+        }
+        let visual_left = self.occipital_lobe.territory_loss(Hemisphere::Left, CerebralArtery::PosteriorCerebral);
+        let visual_right = self.occipital_lobe.territory_loss(Hemisphere::Right, CerebralArtery::PosteriorCerebral);
+        if scale.visual_fields == 3 {
+            deficits.push("Cortical blindness".to_string());
+        } else if scale.visual_fields > 0 {
+            // Occipital injury removes the opposite visual field
+            let field = if visual_left > visual_right { "Right" } else { "Left" };
+            deficits.push(format!("{} homonymous hemianopia", field));
+        }
+        if scale.limb_ataxia > 0 {
+            deficits.push("Ataxia".to_string());
+        }
+        if scale.dysarthria > 0 {
+            deficits.push("Dysarthria".to_string());
+        }
+        deficits
+    }
+
+    /// Start a generalized tonic-clonic seizure
+    ///
+    /// # Arguments
+    /// * `duration_s` - Seizure duration in seconds
+    pub fn start_seizure(&mut self, duration_s: f64) {
+        if !self.is_seizing() {
+            self.seizure_count += 1;
+            self.seizure_elapsed_s = 0.0;
+        }
+        self.seizure_remaining_s = self.seizure_remaining_s.max(duration_s);
+    }
+
+    /// Check whether a seizure is in progress
+    pub fn is_seizing(&self) -> bool {
+        self.seizure_remaining_s > 0.0
+    }
+
+    /// Start an intracranial hemorrhage that expands over the next hour
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Final hematoma volume added, in mL
+    pub fn add_intracranial_hemorrhage(&mut self, volume_ml: f64) {
+        self.pending_hemorrhage_ml += volume_ml.max(0.0);
+    }
+
+    /// Surgically evacuate the hematoma
+    pub fn evacuate_hematoma(&mut self) {
+        self.hematoma_volume_ml = 0.0;
+        self.pending_hemorrhage_ml = 0.0;
+    }
+
+    /// Give hyperosmolar therapy (mannitol or hypertonic saline), drawing water out of the brain
+    ///
+    /// # Arguments
+    /// * `dose_g_per_kg` - Mannitol-equivalent dose in g/kg (usually 0.25-1.0)
+    pub fn give_osmotherapy(&mut self, dose_g_per_kg: f64) {
+        self.osmotic_dehydration_ml = (self.osmotic_dehydration_ml + 40.0 * dose_g_per_kg.max(0.0)).min(60.0);
+    }
+
+    /// Insert an external ventricular drain that drains CSF above an opening pressure
+    ///
+    /// # Arguments
+    /// * `opening_pressure_mmhg` - ICP above which CSF drains
+    pub fn insert_ventricular_drain(&mut self, opening_pressure_mmhg: f64) {
+        self.ventricular_drain_mmhg = Some(opening_pressure_mmhg.max(0.0));
+    }
+
+    /// Remove the external ventricular drain
+    pub fn remove_ventricular_drain(&mut self) {
+        self.ventricular_drain_mmhg = None;
+    }
+
+    /// Check whether the brain is herniating through the tentorium and foramen magnum
+    pub fn is_herniating(&self) -> bool {
+        self.brainstem_compression >= 0.5
+    }
+
+    /// Check for irreversible loss of all brain function including the brainstem
+    pub fn is_brain_dead(&self) -> bool {
+        let injury = |region: &BrainRegion| {
+            (region.infarcted_fraction(Hemisphere::Left) + region.infarcted_fraction(Hemisphere::Right)) / 2.0
+        };
+        injury(&self.brainstem) >= 0.9 && self.regions().iter().all(|r| injury(r) >= 0.5)
+    }
+
+    /// Function of the brainstem's respiratory and vasomotor centres (0.0-1.0)
+    pub fn get_brainstem_function(&self) -> f64 {
+        (self.brainstem.function(Hemisphere::Left) + self.brainstem.function(Hemisphere::Right)) / 2.0
+    }
+
+    /// Share of the brainstem still alive, ischemic or not (0.0-1.0)
+    ///
+    /// An ischemic vasomotor centre drives the Cushing response; only dead
+    /// brainstem loses vascular tone.
+    pub fn get_brainstem_viability(&self) -> f64 {
+        1.0 - (self.brainstem.infarcted_fraction(Hemisphere::Left) + self.brainstem.infarcted_fraction(Hemisphere::Right))
+            / 2.0
+    }
+
+    /// Volume of irreversibly injured brain tissue (mL)
+    fn injured_tissue_volume_ml(&self) -> f64 {
+        let total_flow: f64 = self.regions().iter().map(|r| r.blood_flow_ml_per_min).sum();
+        self.regions()
+            .iter()
+            .map(|r| {
+                let injury = r.infarcted_fraction(Hemisphere::Left) + r.infarcted_fraction(Hemisphere::Right);
+                BRAIN_TISSUE_VOLUME_ML * r.blood_flow_ml_per_min / total_flow * injury / 2.0
+            })
+            .sum()
+    }
+
+    /// Intracranial pressure from the current volumes on the pressure-volume curve
+    fn pressure_from_volumes(&self) -> f64 {
+        // Osmotherapy mostly dehydrates edematous brain
+        let dehydration = self.osmotic_dehydration_ml.min(self.cerebral_edema_ml + 10.0);
+        let added_volume = self.cerebral_edema_ml - dehydration
+            + self.hematoma_volume_ml
+            + (self.csf_volume_ml - NORMAL_CSF_VOLUME_ML)
+            + (self.cerebral_blood_volume_ml - NORMAL_CEREBRAL_BLOOD_VOLUME_ML);
+        (NORMAL_ICP_MMHG * 10f64.powf(added_volume / PRESSURE_VOLUME_INDEX_ML)).min(150.0)
+    }
+
+    /// Move the intracranial volumes and recalculate ICP (Monro-Kellie doctrine)
+    ///
+    /// The skull is rigid, so added volume first displaces CSF; once the CSF
+    /// spaces are effaced, further volume raises ICP steeply.
+    ///
+    /// # Arguments
+    /// * `patient` - The patient
+    /// * `delta_time_s` - Time step in seconds
+    fn update_intracranial_pressure(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let minutes = delta_time_s / 60.0;
+        let decay = |tau_s: f64| 1.0 - (-delta_time_s / tau_s).exp();
+
+        // An expanding hematoma draws blood from the circulation, then slowly resorbs
+        let entering = self.pending_hemorrhage_ml * decay(1200.0);
+        self.pending_hemorrhage_ml -= entering;
+        self.hematoma_volume_ml += patient.fluids.remove_blood(entering);
+        self.hematoma_volume_ml -= self.hematoma_volume_ml * decay(20.0 * 86_400.0);
+
+        // Cytotoxic edema around infarcts peaks after a few days and resolves over weeks;
+        // blood breakdown products cause perihematomal edema
+        let injured_ml = self.injured_tissue_volume_ml();
+        self.resolved_injury_ml += (injured_ml - self.resolved_injury_ml) * decay(10.0 * 86_400.0);
+        let edema_target = INFARCT_EDEMA_FRACTION * (injured_ml - self.resolved_injury_ml).max(0.0)
+            + 0.5 * self.hematoma_volume_ml;
+        self.cerebral_edema_ml += (edema_target - self.cerebral_edema_ml) * decay(86_400.0);
+        self.osmotic_dehydration_ml -= self.osmotic_dehydration_ml * decay(4.0 * 3600.0);
+
+        // CO2 dilates cerebral vessels; autoregulatory vasodilation as CPP falls
+        let paco2 = patient.blood.get_paco2_mmhg();
+        let co2_factor = (1.0 + 0.01 * (paco2 - 40.0)).clamp(0.7, 1.4);
+        let vasodilation = 1.0 + 0.1 * ((70.0 - self.adult_cpp_mmhg()) / 30.0).clamp(0.0, 1.0);
+        let blood_volume_target = NORMAL_CEREBRAL_BLOOD_VOLUME_ML * co2_factor * vasodilation;
+        self.cerebral_blood_volume_ml += (blood_volume_target - self.cerebral_blood_volume_ml) * decay(20.0);
+
+        // CSF is absorbed into the venous sinuses in proportion to ICP; a drain
+        // removes CSF above its opening pressure. Neither can take ICP below its
+        // own equilibrium within a step.
+        let volume_to_reach = |pressure_mmhg: f64, icp: f64| {
+            PRESSURE_VOLUME_INDEX_ML * (icp / pressure_mmhg).log10().max(0.0)
+        };
+        // Added volume first pushes CSF out of the skull into the spinal subarachnoid
+        // space, which buffers ICP until the CSF spaces are effaced
+        let icp = self.pressure_from_volumes();
+        let displaced = volume_to_reach(NORMAL_ICP_MMHG, icp).min((self.csf_volume_ml - MIN_CSF_VOLUME_ML).max(0.0));
+        self.csf_volume_ml -= displaced * decay(60.0);
+
+        let icp = self.pressure_from_volumes();
+        let outflow_resistance = (NORMAL_ICP_MMHG - VENOUS_SINUS_PRESSURE_MMHG) / CSF_PRODUCTION_ML_PER_MIN;
+        let absorbed = (icp - VENOUS_SINUS_PRESSURE_MMHG).max(0.0) / outflow_resistance * minutes;
+        let mut net_ml = CSF_PRODUCTION_ML_PER_MIN * minutes - absorbed;
+        if net_ml < 0.0 {
+            net_ml = net_ml.max(-volume_to_reach(NORMAL_ICP_MMHG, icp));
+        }
+        if self.csf_volume_ml > MIN_CSF_VOLUME_ML {
+            self.csf_volume_ml = (self.csf_volume_ml + net_ml).max(MIN_CSF_VOLUME_ML);
+        } else {
+            self.csf_volume_ml += net_ml.max(0.0);
+        }
+        if let Some(opening_pressure) = self.ventricular_drain_mmhg {
+            let icp = self.pressure_from_volumes();
+            let drained = (2.0 * (icp - opening_pressure).max(0.0) * minutes)
+                .min(volume_to_reach(opening_pressure.max(1.0), icp))
+                .min((self.csf_volume_ml - MIN_DRAINED_CSF_VOLUME_ML).max(0.0));
+            self.csf_volume_ml -= drained;
+            self.csf_drained_ml += patient.lose_fluid(OutputRoute::Drain, drained);
+        }
+
+        // Arterial pressure filling the cranial vessels caps ICP
+        let map = patient.blood.get_mean_arterial_pressure();
+        self.intracranial_pressure_mmhg = self.pressure_from_volumes().min(map.max(NORMAL_ICP_MMHG));
+        // Rising ICP pushes the brain down onto the brainstem; herniated brain stays herniated
+        let compression = ((self.adult_icp_mmhg() - 40.0) / 30.0).clamp(0.0, 1.0);
+        self.brainstem_compression = if self.is_herniating() {
+            self.brainstem_compression.max(compression)
+        } else {
+            compression
+        };
+    }
+
+    /// Cerebral perfusion pressure on the adult scale the thresholds are set for (mmHg)
+    fn adult_cpp_mmhg(&self) -> f64 {
+        self.cerebral_perfusion_pressure_mmhg / self.pressure_scale.max(0.1)
+    }
+
+    /// Rise in ICP on the adult scale the thresholds are set for, added to normal ICP (mmHg)
+    fn adult_icp_mmhg(&self) -> f64 {
+        NORMAL_ICP_MMHG + (self.intracranial_pressure_mmhg - NORMAL_ICP_MMHG) / self.pressure_scale.max(0.1)
+    }
+
+    /// Check for status epilepticus (a seizure lasting 5 minutes or more)
+    pub fn is_status_epilepticus(&self) -> bool {
+        self.is_seizing() && self.seizure_elapsed_s >= STATUS_EPILEPTICUS_S
+    }
+
+    /// Check whether the patient is recovering from a seizure
+    pub fn is_postictal(&self) -> bool {
+        self.postictal_remaining_s > 0.0
+    }
+
+    /// Give a benzodiazepine (e.g. lorazepam 4 mg IV, midazolam 10 mg IM = ~4 mg lorazepam)
+    ///
+    /// # Arguments
+    /// * `lorazepam_equivalent_mg` - Dose in lorazepam-equivalent mg
+    pub fn give_benzodiazepine(&mut self, lorazepam_equivalent_mg: f64) {
+        self.benzodiazepine_mg += lorazepam_equivalent_mg.max(0.0);
+    }
+
+    /// Anticonvulsant effect of the benzodiazepine on board (0.0-1.0)
+    pub fn anticonvulsant_effect(&self) -> f64 {
+        self.benzodiazepine_mg / (self.benzodiazepine_mg + BENZODIAZEPINE_EC50_MG)
+    }
+
+    /// Set the depth of pharmacologic sedation (e.g. a propofol infusion)
+    ///
+    /// # Arguments
+    /// * `level` - 0.0 = none, 0.5 = moderate sedation, 1.0 = general anesthesia
+    pub fn set_sedation_level(&mut self, level: f64) {
+        self.sedation_level = level.clamp(0.0, 1.0);
+    }
+
+    /// Total sedation from the infusion and any benzodiazepine on board (0.0-1.0)
+    pub fn total_sedation(&self) -> f64 {
+        1.0 - (1.0 - self.sedation_level) * (1.0 - 0.5 * self.anticonvulsant_effect())
+    }
+
+    /// Share of the autonomic reflexes the brainstem can still mount (0.0-1.0)
+    ///
+    /// The vasomotor and respiratory centres need a functioning brainstem and
+    /// enough perfusion to run; reflexes fail as CPP falls below 50 mmHg in an adult.
+    pub fn autonomic_integrity(&self) -> f64 {
+        self.get_brainstem_function() * ((self.adult_cpp_mmhg() - 20.0) / 30.0).clamp(0.0, 1.0)
+    }
+
+    /// Run the baroreflex and chemoreflexes and publish the autonomic outflow
+    ///
+    /// A fall in pressure below the set point raises sympathetic outflow and
+    /// withdraws vagal tone; a rise does the opposite. Hypercapnia and
+    /// hypoxemia add sympathetic drive and quicken breathing. The Cushing
+    /// reflex adds vagal tone.
+    ///
+    /// # Arguments
+    /// * `patient` - Patient whose signals receive the outflow
+    /// * `map` - Mean arterial pressure (mmHg)
+    /// * `delta_time_s` - Time step in seconds
+    fn update_autonomic_reflexes(&mut self, patient: &mut Patient, map: f64, delta_time_s: f64) {
+        let integrity = self.autonomic_integrity();
+        let set_point = self.baroreflex_set_point_mmhg;
+        let baroreflex = ((set_point - map) / set_point * BAROREFLEX_GAIN).clamp(-0.5, 1.0);
+        self.baroreflex_set_point_mmhg += (map - set_point) * (1.0 - (-delta_time_s / BAROREFLEX_RESETTING_S).exp());
+
+        let hypercapnia = (patient.blood.get_paco2_mmhg() - CHEMOREFLEX_PACO2_THRESHOLD_MMHG).max(0.0);
+        let hypoxemia = (CHEMOREFLEX_SAO2_THRESHOLD_PERCENT - patient.blood.get_sao2_percent()).max(0.0);
+        let chemoreflex = 0.02 * (hypercapnia + hypoxemia);
+
+        let response = 1.0 - (-delta_time_s / AUTONOMIC_RESPONSE_S).exp();
+        self.sympathetic_outflow += (integrity * (baroreflex + chemoreflex) - self.sympathetic_outflow) * response;
+        self.ventilatory_drive = 1.0 + integrity * (hypercapnia / 32.0 + 0.02 * hypoxemia);
+        patient.signals.publish(Signal::SympatheticTone, 1.0 + self.sympathetic_outflow);
+        let vagal_withdrawal = 0.5 * self.sympathetic_outflow.clamp(-0.5, 1.0);
+        patient.signals.publish(Signal::VagalTone, 1.0 - vagal_withdrawal + self.cushing_response);
+    }
+
+    /// Output of the brainstem respiratory centres, depressed by sedation (0.0-1.0)
+    pub fn get_respiratory_drive(&self) -> f64 {
+        self.get_brainstem_function() * (1.0 - 0.9 * self.total_sedation().powi(2))
+    }
+
+    /// Level of arousal (0.0 = unrousable, 1.0 = fully awake)
+    ///
+    /// Wakefulness needs the brainstem reticular activating system and enough
+    /// functioning cortex on both sides; sedation suppresses both.
+    ///
+    /// # Arguments
+    /// * `global_function` - Global cerebral function from perfusion, oxygenation and metabolism
+    fn level_of_arousal(&self, global_function: f64) -> f64 {
+        let cortical_regions = [&self.frontal_lobe, &self.parietal_lobe, &self.temporal_lobe, &self.occipital_lobe];
+        let cortex = cortical_regions
+            .iter()
+            .map(|r| (r.function(Hemisphere::Left) + r.function(Hemisphere::Right)) / 2.0)
+            .sum::<f64>()
+            / cortical_regions.len() as f64;
+        self.get_brainstem_function() * global_function * (0.4 + 0.6 * cortex) * (1.0 - self.total_sedation())
+    }
+
+    /// Score the Glasgow Coma Scale
+    ///
+    /// # Arguments
+    /// * `global_function` - Global cerebral function from perfusion, oxygenation and metabolism
+    fn update_gcs(&mut self, global_function: f64) {
+        let arousal = self.level_of_arousal(global_function);
+        let grade = |thresholds: &[f64]| 1 + thresholds.iter().filter(|&&t| arousal >= t).count() as i32;
+        let mut eye = grade(&[0.4, 0.65, 0.85]);
+        let mut verbal = grade(&[0.3, 0.5, 0.65, 0.85]);
+        let mut motor = grade(&[0.15, 0.3, 0.45, 0.65, 0.85]);
+
+        // Aphasia limits the verbal score and paralysis of all limbs the motor score,
+        // while the best limb on an unaffected side still obeys
+        let scale = self.get_stroke_scale();
+        verbal = match scale.language {
+            3 => 1,
+            2 => verbal.min(2),
+            1 => verbal.min(4),
+            _ => verbal,
+        };
+        if scale.motor_arm_left.min(scale.motor_arm_right) >= 4 {
+            motor = 1;
+        }
+
+        // Unresponsive during a generalized seizure; drowsy and confused afterwards
+        if self.is_seizing() {
+            eye = 1;
+            verbal = 1;
+            motor = motor.min(3);
+        } else if self.is_postictal() {
+            let depth = self.postictal_remaining_s / self.postictal_duration_s.max(1.0);
+            let (max_eye, max_verbal, max_motor) = if depth > 0.5 { (2, 2, 5) } else { (3, 4, 6) };
+            eye = eye.min(max_eye);
+            verbal = verbal.min(max_verbal);
+            motor = motor.min(max_motor);
+        }
+
+        self.gcs.eye_response = eye;
+        self.gcs.verbal_response = verbal;
+        self.gcs.motor_response = motor;
+    }
+
+    /// Metabolic provocation of seizures from the blood
+    fn metabolic_seizure_provocation(patient: &Patient) -> f64 {
+        let hypoglycemia = (60.0 - patient.blood.get_glucose_mg_dl()) / 20.0;
+        let hyponatremia = (130.0 - patient.blood.get_sodium_meq_l()) / 10.0;
+        let hypoxia = (80.0 - patient.blood.get_sao2_percent()) / 20.0;
+        let toxins = patient.blood.chemistry.toxin_level_au / 100.0;
+        hypoglycemia.max(hyponatremia).max(hypoxia).max(toxins).max(0.0)
+    }
+
+    /// Start, sustain and stop seizures, and apply their metabolic cost
+    ///
+    /// # Arguments
+    /// * `patient` - The patient
+    /// * `delta_time_s` - Time step in seconds
+    fn update_seizures(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // Benzodiazepines raise the seizure threshold; lorazepam's effect lasts ~12 hours
+        self.benzodiazepine_mg *= (-delta_time_s * std::f64::consts::LN_2 / 43_200.0).exp();
+        // Anesthetic doses of sedatives also suppress seizures (refractory status epilepticus)
+        let protection = 1.0 - (1.0 - self.anticonvulsant_effect()) * (1.0 - self.sedation_level);
+        self.seizure_provocation = Self::metabolic_seizure_provocation(patient);
+        let provocation = self.seizure_provocation * (1.0 - protection);
+
+        // Provoked seizures recur more often the stronger the provocation; severe
+        // provocation sustains them (status epilepticus)
+        let was_seizing = self.is_seizing();
+        if !was_seizing && provocation >= 1.0 && self.interictal_s >= 600.0 / provocation {
+            self.start_seizure(PROVOKED_SEIZURE_DURATION_S);
+        }
+        if self.is_seizing() {
+            if provocation >= 2.0 {
+                self.seizure_remaining_s = self.seizure_remaining_s.max(delta_time_s + 1.0);
+            }
+            // GABA-A receptors internalize during prolonged status, halving benzodiazepine efficacy
+            let efficacy = if self.seizure_elapsed_s > 1800.0 { 0.5 } else { 1.0 };
+            if protection * efficacy > 0.5 && provocation < 2.0 {
+                self.seizure_remaining_s = self.seizure_remaining_s.min(30.0);
+            }
+        }
+
+        // Convulsing muscle makes lactate
+        if self.is_seizing() {
+            let lactate = patient.blood.get_lactate_mmol_l();
+            patient.blood.set_lactate_mmol_l(lactate + (15.0 - lactate).clamp(0.0, 0.05 * delta_time_s));
+            self.seizure_elapsed_s += delta_time_s;
+            self.seizure_remaining_s = (self.seizure_remaining_s - delta_time_s).max(0.0);
+            if !self.is_seizing() {
+                // Post-ictal confusion lasts longer after longer seizures
+                self.postictal_duration_s = 600.0 + 2.0 * self.seizure_elapsed_s;
+                self.postictal_remaining_s = self.postictal_duration_s;
+                self.interictal_s = 0.0;
+            }
+        } else {
+            self.interictal_s += delta_time_s;
+            self.postictal_remaining_s = (self.postictal_remaining_s - delta_time_s).max(0.0);
+        }
+
+        // Sedatives lower cerebral metabolism by up to half
+        let activity = if self.is_seizing() {
+            ICTAL_METABOLIC_DEMAND
+        } else if self.is_postictal() {
+            0.8
+        } else {
+            1.0
+        };
+        self.cerebral_metabolic_demand = activity * (1.0 - 0.5 * self.total_sedation());
+    }
+
+    /// EEG sample for the current background pattern
+    ///
+    /// # Arguments
+    /// * `avg_activity` - Average metabolic activity of the brain regions
+    /// * `lookback_s` - How long before the end of the current step the sample falls (s)
+    fn eeg_sample(&self, avg_activity: f64, lookback_s: f64) -> f64 {
+        use std::f64::consts::PI;
+        let t = self.eeg_time_s - lookback_s;
+        match self.eeg_pattern {
+            EegPattern::Normal => 50.0 * avg_activity * (2.0 * PI * 10.0 * t).sin(),
+            EegPattern::Slowing => 80.0 * avg_activity * (2.0 * PI * 4.0 * t).sin(),
+            // High-amplitude ~3 Hz spike-and-wave discharges
+            EegPattern::Ictal => 200.0 * (2.0 * PI * 3.0 * t).sin().powi(3),
+            EegPattern::Postictal => 20.0 * (2.0 * PI * 1.0 * t).sin(),
+            EegPattern::Suppressed => 0.0,
         }
     }
 
@@ -127,91 +1149,139 @@ impl Brain {
             + self.parietal_lobe.metabolic_activity
             + self.temporal_lobe.metabolic_activity
             + self.occipital_lobe.metabolic_activity
-            + self.cerebellum.metabolic_activity)
-            / 5.0
+            + self.cerebellum.metabolic_activity
+            + self.brainstem.metabolic_activity)
+            / 6.0
     }
 }
 
 impl Organ for Brain {
     fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        self.update_intracranial_pressure(patient, delta_time_s);
+        self.update_seizures(patient, delta_time_s);
+
         // Calculate cerebral perfusion pressure
         // CPP = MAP - ICP (where MAP = mean arterial pressure)
-        let map = patient.blood.blood_pressure_diastolic
-            + (patient.blood.blood_pressure_systolic - patient.blood.blood_pressure_diastolic) / 3.0;
+        let map = patient.blood.get_mean_arterial_pressure();
         self.cerebral_perfusion_pressure_mmhg = map - self.intracranial_pressure_mmhg;
 
         // Update metabolic activity based on perfusion
-        let perfusion_factor = (self.cerebral_perfusion_pressure_mmhg / 70.0).clamp(0.0, 1.5);
-        let oxygen_factor = (patient.blood.gases.sao2_percent / 98.0).clamp(0.0, 1.0);
+        let perfusion_factor = (self.adult_cpp_mmhg() / 70.0).clamp(0.0, 1.5);
+        let oxygen_factor = (patient.blood.get_sao2_percent() / 98.0).clamp(0.0, 1.0);
 
-        self.frontal_lobe.metabolic_activity = perfusion_factor * oxygen_factor;
-        self.parietal_lobe.metabolic_activity = perfusion_factor * oxygen_factor;
-        self.temporal_lobe.metabolic_activity = perfusion_factor * oxygen_factor;
-        self.occipital_lobe.metabolic_activity = perfusion_factor * oxygen_factor;
-        self.cerebellum.metabolic_activity = perfusion_factor * oxygen_factor;
+        // Autoregulation holds cerebral blood flow constant for a CPP of ~50-150 mmHg
+        let autoregulated_flow = AutoregulationCurve::cerebral().relative_flow(self.adult_cpp_mmhg());
+        // Flow changes ~3% per mmHg of CO2
+        let co2_reactivity = (1.0 + 0.03 * (patient.blood.get_paco2_mmhg() - 40.0)).clamp(0.4, 2.0);
+        // Seizing neurons burn oxygen faster than hyperemia can match, given pressure reserve
+        let demand = self.cerebral_metabolic_demand;
+        let pressure_reserve = ((self.adult_cpp_mmhg() - 50.0) / 20.0).clamp(0.0, 1.0);
+        let hyperemia = 1.0 + 0.75 * (demand - 1.0).max(0.0) * pressure_reserve;
+        let cerebral_flow = autoregulated_flow * co2_reactivity * hyperemia;
+        self.update_territories(cerebral_flow, oxygen_factor / demand, patient.difficulty.ischemic_time_s(delta_time_s));
+        self.update_cerebral_oxygenation(
+            patient.blood.get_sao2_percent(),
+            patient.blood.calculate_oxygen_content(),
+        );
+
+        // Focal ischemia and infarction silence the affected territories
+        for region in self.regions_mut() {
+            let focal_function = (region.function(Hemisphere::Left) + region.function(Hemisphere::Right)) / 2.0;
+            region.metabolic_activity = perfusion_factor * oxygen_factor * focal_function;
+        }
 
-        // Update GCS based on metabolic activity
         let avg_activity = self.average_metabolic_activity();
 
-        if avg_activity >= 0.9 {
-            self.gcs.eye_response = 4;
-            self.gcs.verbal_response = 5;
-            self.gcs.motor_response = 6;
-        } else if avg_activity >= 0.7 {
-            self.gcs.eye_response = 3;
-            self.gcs.verbal_response = 4;
-            self.gcs.motor_response = 5;
-        } else if avg_activity >= 0.5 {
-            self.gcs.eye_response = 2;
-            self.gcs.verbal_response = 3;
-            self.gcs.motor_response = 4;
-        } else if avg_activity >= 0.3 {
-            self.gcs.eye_response = 2;
-            self.gcs.verbal_response = 2;
-            self.gcs.motor_response = 3;
-        } else {
-            self.gcs.eye_response = 1;
-            self.gcs.verbal_response = 1;
-            self.gcs.motor_response = 2;
-        }
+        // Hypoglycemia starves the whole brain of fuel
+        let glucose_factor = ((patient.blood.get_glucose_mg_dl() - 20.0) / 30.0).clamp(0.0, 1.0);
+        self.update_gcs(perfusion_factor.min(1.0) * oxygen_factor * glucose_factor);
 
-        // ICP affected by blood pressure
-        self.intracranial_pressure_mmhg = 10.0 + (map - 93.0) * 0.1;
-        self.intracranial_pressure_mmhg = self.intracranial_pressure_mmhg.clamp(5.0, 30.0);
+        // Brainstem ischemia from raised ICP triggers the Cushing reflex
+        let cushing_target = if self.adult_icp_mmhg() > 20.0 {
+            ((60.0 - self.adult_cpp_mmhg()) / 30.0).clamp(0.0, 1.0) * self.get_brainstem_viability()
+        } else {
+            0.0
+        };
+        self.cushing_response += (cushing_target - self.cushing_response) * (1.0 - (-delta_time_s / 30.0).exp());
 
         // Generate EEG waveform (simplified)
-        let eeg_amplitude = avg_activity * 50.0;
-        let eeg_value = eeg_amplitude * (delta_time_s * 10.0 * std::f64::consts::PI).sin();
-        self.eeg_waveform.push_back(eeg_value);
-        if self.eeg_waveform.len() > 1000 {
-            self.eeg_waveform.pop_front();
-        }
-
-        // Autonomic control
-        // High CO2 increases both heart rate and respiration
-        if patient.blood.gases.paco2_mmhg > 45.0 {
-            self.autonomic_heart_rate_target = 75.0 + (patient.blood.gases.paco2_mmhg - 45.0) * 0.5;
-            self.autonomic_respiration_target = 16.0 + (patient.blood.gases.paco2_mmhg - 45.0) * 0.3;
+        self.eeg_pattern = if self.is_seizing() {
+            EegPattern::Ictal
+        } else if avg_activity < 0.1 || self.total_sedation() >= 0.8 {
+            // Isoelectric or burst-suppressed
+            EegPattern::Suppressed
+        } else if self.is_postictal() {
+            EegPattern::Postictal
+        } else if avg_activity < 0.7 || self.seizure_provocation >= 0.5 || self.total_sedation() >= 0.3 {
+            EegPattern::Slowing
+        } else {
+            EegPattern::Normal
+        };
+        self.eeg_time_s += delta_time_s;
+        if patient.organ_fidelity(self.get_type()).generates_waveforms() {
+            for lookback in self.eeg_sampler.step(delta_time_s) {
+                let eeg_value = self.eeg_sample(avg_activity, lookback);
+                self.eeg_waveform.push(eeg_value);
+            }
         } else {
-            self.autonomic_heart_rate_target = 75.0;
-            self.autonomic_respiration_target = 16.0;
+            self.eeg_sampler.skip(delta_time_s);
         }
 
-        // Low oxygen increases heart rate
-        if patient.blood.gases.sao2_percent < 95.0 {
-            self.autonomic_heart_rate_target += (95.0 - patient.blood.gases.sao2_percent) * 0.5;
-        }
+        self.update_autonomic_reflexes(patient, map, delta_time_s);
     }
 
     fn get_summary(&self) -> String {
+        let scale = self.get_stroke_scale();
+        let stroke = if scale.total() > 0 {
+            format!(", NIHSS={} ({})", scale.total(), self.get_focal_deficits().join(", "))
+        } else {
+            String::new()
+        };
+        let mass = if self.hematoma_volume_ml + self.cerebral_edema_ml > 5.0 {
+            format!(", Hematoma={:.0} mL, Edema={:.0} mL", self.hematoma_volume_ml, self.cerebral_edema_ml)
+        } else {
+            String::new()
+        };
+        let herniation = if self.is_brain_dead() {
+            ", BRAIN DEATH"
+        } else if self.is_herniating() {
+            ", HERNIATION"
+        } else if self.cushing_response > 0.2 {
+            ", Cushing response"
+        } else {
+            ""
+        };
+        let oximetry = if self.is_cerebral_desaturation() {
+            format!(
+                ", CEREBRAL DESATURATION (SjvO2={:.0}%, rSO2={:.0}/{:.0}%)",
+                self.jugular_venous_saturation_percent,
+                self.regional_oxygen_saturation_percent[0],
+                self.regional_oxygen_saturation_percent[1]
+            )
+        } else {
+            String::new()
+        };
         format!(
-            "Brain: GCS={} (E{}V{}M{}), ICP={:.1} mmHg, CPP={:.1} mmHg",
+            "Brain: GCS={} (E{}V{}M{}), ICP={:.1} mmHg, CPP={:.1} mmHg{}{}{}{}{}",
             self.gcs.total(),
             self.gcs.eye_response,
             self.gcs.verbal_response,
             self.gcs.motor_response,
             self.intracranial_pressure_mmhg,
-            self.cerebral_perfusion_pressure_mmhg
+            self.cerebral_perfusion_pressure_mmhg,
+            mass,
+            herniation,
+            oximetry,
+            stroke,
+            if self.is_status_epilepticus() {
+                ", STATUS EPILEPTICUS"
+            } else if self.is_seizing() {
+                ", SEIZING"
+            } else if self.is_postictal() {
+                ", Post-ictal"
+            } else {
+                ""
+            }
         )
     }
 
@@ -230,4 +1300,271 @@ impl Organ for Brain {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::heart::Heart;
+    use crate::organs::lungs::Lungs;
+    use crate::organs::vascular::VascularSystem;
+    use crate::patient::{initialize_newborn, initialize_patient, update_patient};
+    use crate::Sex;
+
+    fn rested() -> Patient {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        patient
+    }
+
+    fn hypoglycemic(patient: &mut Patient, seconds: usize) {
+        for _ in 0..seconds {
+            patient.blood.chemistry.glucose_mg_dl = 35.0;
+            update_patient(patient, 1.0);
+        }
+    }
+
+    fn occluded_for(vessel: &str, minutes: usize) -> Patient {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..30 {
+            update_patient(&mut patient, 1.0);
+        }
+        assert!(patient.get_organ_mut::<VascularSystem>().unwrap().occlude_vessel(vessel, 1.0));
+        for _ in 0..minutes * 6 {
+            update_patient(&mut patient, 10.0);
+        }
+        patient
+    }
+
+    #[test]
+    fn test_stroke_scale_follows_the_infarcted_territory_and_its_size() {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..30 {
+            update_patient(&mut patient, 1.0);
+        }
+        assert_eq!(patient.get_organ::<Brain>().unwrap().get_stroke_scale().total(), 0);
+
+        // The dominant MCA carries language and the opposite arm; the non-dominant one attention
+        let left_mca = occluded_for("Middle Cerebral Artery (L)", 60);
+        let left_mca_scale = left_mca.get_organ::<Brain>().unwrap().get_stroke_scale();
+        assert!(left_mca_scale.language > 0);
+        assert!(left_mca_scale.motor_arm_right > 0);
+        assert_eq!(left_mca_scale.motor_arm_left, 0);
+        assert_eq!(left_mca_scale.neglect, 0);
+
+        let right_mca = occluded_for("Middle Cerebral Artery (R)", 60);
+        let right_mca_scale = right_mca.get_organ::<Brain>().unwrap().get_stroke_scale();
+        assert!(right_mca_scale.neglect > 0);
+        assert!(right_mca_scale.motor_arm_left > 0);
+        assert_eq!(right_mca_scale.language, 0);
+        assert!(right_mca.get_organ::<Brain>().unwrap().get_focal_deficits().contains(&"Left hemineglect".to_string()));
+
+        // The ACA supplies the leg more than the arm; the PCA only the visual cortex
+        let aca_scale = occluded_for("Anterior Cerebral Artery (L)", 60).get_organ::<Brain>().unwrap().get_stroke_scale();
+        assert!(aca_scale.motor_leg_right > aca_scale.motor_arm_right);
+        assert_eq!(aca_scale.language, 0);
+
+        let pca = occluded_for("Posterior Cerebral Artery (L)", 60);
+        let pca_scale = pca.get_organ::<Brain>().unwrap().get_stroke_scale();
+        assert!(pca_scale.visual_fields > 0);
+        assert_eq!(pca_scale.total(), pca_scale.visual_fields);
+        assert!(pca.get_organ::<Brain>().unwrap().get_focal_deficits().contains(&"Right homonymous hemianopia".to_string()));
+
+        // Larger territories score higher; the basilar weakens both sides and clouds consciousness
+        let basilar_scale = occluded_for("Basilar Artery", 60).get_organ::<Brain>().unwrap().get_stroke_scale();
+        assert!(left_mca_scale.total() > aca_scale.total());
+        assert!(aca_scale.total() > pca_scale.total());
+        assert!(basilar_scale.motor_arm_left > 0 && basilar_scale.motor_arm_right > 0);
+        assert!(basilar_scale.level_of_consciousness > 0);
+
+        // The score keeps pace as the core grows into the penumbra
+        let early = occluded_for("Middle Cerebral Artery (L)", 30);
+        let early_brain = early.get_organ::<Brain>().unwrap();
+        let late = occluded_for("Middle Cerebral Artery (L)", 240);
+        let late_brain = late.get_organ::<Brain>().unwrap();
+        assert!(late_brain.frontal_lobe.infarcted_fraction(Hemisphere::Left) > 5.0 * early_brain.frontal_lobe.infarcted_fraction(Hemisphere::Left));
+        assert!(late_brain.get_stroke_scale().total() > early_brain.get_stroke_scale().total());
+    }
+
+    #[test]
+    fn test_hypoglycemic_seizures_recur_until_a_benzodiazepine_is_given() {
+        let mut patient = rested();
+        let resting_gcs = patient.get_organ::<Brain>().unwrap().gcs.total();
+
+        // Moderate hypoglycemia provokes self-limiting seizures
+        hypoglycemic(&mut patient, 600);
+        let brain = patient.get_organ::<Brain>().unwrap();
+        assert!(brain.is_seizing());
+        assert_eq!(brain.eeg_pattern, EegPattern::Ictal);
+        assert_eq!(brain.cerebral_metabolic_demand, ICTAL_METABOLIC_DEMAND);
+        assert!(patient.blood.chemistry.lactate_mmol_l > 2.0);
+
+        // Lorazepam stops the seizure and prevents the next one; untreated, they recur
+        let seizures = brain.seizure_count;
+        let mut treated = rested();
+        hypoglycemic(&mut treated, 600);
+        treated.get_organ_mut::<Brain>().unwrap().give_benzodiazepine(4.0);
+        hypoglycemic(&mut treated, 60);
+        let brain = treated.get_organ::<Brain>().unwrap();
+        assert!(!brain.is_seizing());
+        assert!(brain.is_postictal());
+        assert!(brain.gcs.total() < resting_gcs);
+
+        hypoglycemic(&mut patient, 1200);
+        hypoglycemic(&mut treated, 1140);
+        assert!(patient.get_organ::<Brain>().unwrap().seizure_count > seizures);
+        assert_eq!(treated.get_organ::<Brain>().unwrap().seizure_count, seizures);
+    }
+
+    #[test]
+    fn test_cerebral_oximetry_desaturates_with_low_output_and_hypoxemia() {
+        let patient = rested();
+        let brain = patient.get_organ::<Brain>().unwrap();
+        assert!(!brain.is_cerebral_desaturation());
+        let baseline_flow = brain.cerebral_blood_flow_ml_100g_min;
+        let baseline_rso2 = brain.regional_oxygen_saturation_percent[0];
+
+        // Profound bradycardia drops perfusion pressure below the autoregulatory range:
+        // the brain extracts more of a smaller flow
+        let mut low_output = rested();
+        low_output.get_organ_mut::<Heart>().unwrap().induce_bradycardia(12.0);
+        for _ in 0..60 {
+            update_patient(&mut low_output, 1.0);
+        }
+        let brain = low_output.get_organ::<Brain>().unwrap();
+        assert!(brain.cerebral_blood_flow_ml_100g_min < 0.8 * baseline_flow);
+        assert!(brain.jugular_venous_saturation_percent < 50.0);
+        assert!(brain.regional_oxygen_saturation_percent.iter().all(|&s| s < baseline_rso2 - 10.0));
+        assert!(brain.is_cerebral_desaturation());
+
+        // Hypoxemia dilates the cerebral vessels, but the rising flow cannot hold the saturation
+        let mut hypoxemic = rested();
+        for lobe in 0..5 {
+            hypoxemic.get_organ_mut::<Lungs>().unwrap().inflict_damage(lobe, 0.9);
+        }
+        for _ in 0..300 {
+            update_patient(&mut hypoxemic, 1.0);
+        }
+        assert!(hypoxemic.blood.gases.sao2_percent < 90.0);
+        let brain = hypoxemic.get_organ::<Brain>().unwrap();
+        assert!(brain.cerebral_blood_flow_ml_100g_min > 1.2 * baseline_flow);
+        assert!(brain.regional_oxygen_saturation_percent.iter().all(|&s| s < baseline_rso2 - 3.0));
+    }
+
+    #[test]
+    fn test_gcs_falls_with_sedation_and_aphasia_limits_the_verbal_score() {
+        assert_eq!(rested().get_organ::<Brain>().unwrap().gcs.total(), 15);
+
+        // Deepening sedation lowers every component and depresses breathing
+        let sedated = |level: f64| {
+            let mut sedated = rested();
+            sedated.get_organ_mut::<Brain>().unwrap().set_sedation_level(level);
+            for _ in 0..10 {
+                update_patient(&mut sedated, 1.0);
+            }
+            sedated
+        };
+        let moderate = sedated(0.5);
+        let general = sedated(1.0);
+        let moderate_brain = moderate.get_organ::<Brain>().unwrap();
+        let general_brain = general.get_organ::<Brain>().unwrap();
+        assert!(moderate_brain.gcs.total() < 15 && moderate_brain.gcs.total() > 3);
+        assert_eq!(general_brain.gcs.total(), 3);
+        assert!(general_brain.get_respiratory_drive() < 0.2);
+
+        // A dominant-hemisphere stroke takes away speech but not wakefulness
+        let stroke = occluded_for("Middle Cerebral Artery (L)", 240);
+        let brain = stroke.get_organ::<Brain>().unwrap();
+        assert!(brain.get_stroke_scale().language >= 2);
+        assert!(brain.gcs.verbal_response <= 2);
+        assert_eq!(brain.gcs.eye_response, 4);
+    }
+
+    #[test]
+    fn test_brainstem_reflexes_drive_the_heart_until_hypoperfused() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 60.0);
+        let resting_rate = patient.get_organ::<Heart>().unwrap().heart_rate_bpm;
+
+        // A rise in resistance loads the baroreceptors: sympathetic withdrawal, vagal slowing
+        let mut constricted = patient.fork();
+        constricted.get_organ_mut::<Heart>().unwrap().systemic_resistance_scale = 1.5;
+        update_patient(&mut constricted, 120.0);
+        assert!(constricted.signals.level(Signal::SympatheticTone) < 0.8);
+        assert!(constricted.signals.level(Signal::VagalTone) > 1.1);
+        assert!(constricted.get_organ::<Heart>().unwrap().heart_rate_bpm < resting_rate - 5.0);
+
+        // After a hemorrhage the reflex outflow rises, unless the brainstem is hypoperfused
+        patient.hemorrhage(0.3 * patient.fluids.reference_blood_volume_ml);
+        let mut compressed = patient.fork();
+        compressed.get_organ_mut::<Brain>().unwrap().add_intracranial_hemorrhage(200.0);
+        for _ in 0..10 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut compressed, 60.0);
+        }
+        let brain = compressed.get_organ::<Brain>().unwrap();
+        assert!(brain.autonomic_integrity() < 0.5, "{}", brain.autonomic_integrity());
+        assert!(patient.signals.excess(Signal::SympatheticTone) > 0.05);
+        assert!(compressed.signals.excess(Signal::SympatheticTone) < patient.signals.excess(Signal::SympatheticTone) / 2.0);
+    }
+
+    #[test]
+    fn test_expanding_hematoma_raises_pressure_slows_the_heart_then_herniates() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 60.0);
+        let baseline_map = patient.blood.get_mean_arterial_pressure();
+        let baseline_rate = patient.get_organ::<Heart>().unwrap().heart_rate_bpm;
+
+        // Cushing's triad: the ischemic brainstem drives the pressure up and the rate down
+        patient.get_organ_mut::<Brain>().unwrap().add_intracranial_hemorrhage(80.0);
+        let (mut peak_map, mut rate_at_peak) = (0.0, 0.0);
+        let mut herniated_steps = 0;
+        for _ in 0..540 {
+            update_patient(&mut patient, 10.0);
+            let map = patient.blood.get_mean_arterial_pressure();
+            if map > peak_map {
+                peak_map = map;
+                rate_at_peak = patient.get_organ::<Heart>().unwrap().heart_rate_bpm;
+            }
+            if patient.get_organ::<Brain>().unwrap().is_herniating() {
+                herniated_steps += 1;
+                if herniated_steps == 30 {
+                    break;
+                }
+            }
+        }
+        assert_eq!(herniated_steps, 30);
+        assert!(peak_map > baseline_map + 20.0, "{baseline_map} -> {peak_map}");
+        assert!(rate_at_peak < baseline_rate - 15.0, "{baseline_rate} -> {rate_at_peak}");
+
+        // Removing the mass lowers the pressure but does not undo the herniation
+        patient.get_organ_mut::<Brain>().unwrap().evacuate_hematoma();
+        for _ in 0..30 {
+            update_patient(&mut patient, 60.0);
+        }
+        let brain = patient.get_organ::<Brain>().unwrap();
+        assert!(brain.intracranial_pressure_mmhg < 40.0, "{}", brain.intracranial_pressure_mmhg);
+        assert!(brain.is_herniating());
+    }
+
+    #[test]
+    fn test_newborn_brain_is_perfused_at_newborn_pressures() {
+        let mut newborn = initialize_newborn(1, 3, Sex::Female, 40.0, 3.4);
+        for _ in 0..600 {
+            update_patient(&mut newborn, 1.0);
+        }
+        let map = newborn.blood.get_mean_arterial_pressure();
+        let brain = newborn.get_organ::<Brain>().unwrap();
+        assert!((35.0..60.0).contains(&map), "{map}");
+        assert!(brain.get_brainstem_function() > 0.9);
+        assert!(brain.autonomic_integrity() > 0.9);
+        assert!(brain.cushing_response < 0.05);
+        assert!(!brain.is_herniating());
+    }
 }
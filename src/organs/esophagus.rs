@@ -1,8 +1,37 @@
 //! Esophagus organ simulation
+//!
+//! Bolus transport by peristalsis, and the esophageal consequences of portal hypertension:
+//! - Varices forming and enlarging once the portal pressure gradient is clinically significant
+//! - Rupture when variceal wall tension (pressure x radius) is too high
+//! - Acute upper GI hemorrhage into the stomach, slowed by clotting and by falling portal
+//!   pressure, and stopped by endoscopic band ligation
 
+use crate::coagulation::Coagulation;
 use crate::organ::{Organ, OrganId};
+use crate::organs::liver::CLINICALLY_SIGNIFICANT_HVPG_MMHG;
 use crate::patient::Patient;
 
+/// Time constant of variceal enlargement under portal hypertension (s)
+const VARIX_GROWTH_S: f64 = 60.0 * 86_400.0;
+
+/// Time constant of variceal regression once portal pressure falls (s)
+const VARIX_REGRESSION_S: f64 = 180.0 * 86_400.0;
+
+/// Varix diameter separating small from large varices (mm)
+const LARGE_VARIX_MM: f64 = 5.0;
+
+/// Wall tension (HVPG mmHg x radius mm) at which a varix ruptures
+const VARIX_RUPTURE_TENSION: f64 = 40.0;
+
+/// Bleeding rate per mmHg of portal gradient from a 5 mm varix (mL/min)
+const VARICEAL_BLEED_ML_MIN_PER_MMHG: f64 = 2.0;
+
+/// Time constant of clot formation over the rupture site with normal coagulation (s)
+const VARICEAL_CLOT_S: f64 = 1200.0;
+
+/// How long a plugged rupture site holds before it can bleed again (s)
+const VARICEAL_CLOT_HOLD_S: f64 = 5.0 * 86_400.0;
+
 /// Peristalsis state
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PeristalsisState {
@@ -11,6 +40,17 @@ pub enum PeristalsisState {
     Relaxing,
 }
 
+/// Endoscopic size grade of esophageal varices
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VaricealGrade {
+    /// No varices
+    None,
+    /// Small varices (< 5 mm)
+    Small,
+    /// Large varices (>= 5 mm)
+    Large,
+}
+
 /// Food bolus
 #[derive(Debug, Clone)]
 pub struct Bolus {
@@ -19,7 +59,7 @@ pub struct Bolus {
 }
 
 /// Esophagus organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Esophagus {
     id: OrganId,
     /// Peristalsis state
@@ -30,6 +70,22 @@ pub struct Esophagus {
     pub length_cm: f64,
     /// Transport speed (cm/s)
     pub transport_speed: f64,
+    /// Portal pressure gradient (HVPG) transmitted to the collaterals, set from the liver (mmHg)
+    pub portal_pressure_gradient_mmhg: f64,
+    /// Diameter of the largest esophageal varix (mm)
+    pub varix_diameter_mm: f64,
+    /// Whether a varix is actively bleeding
+    pub variceal_bleeding: bool,
+    /// Current variceal bleeding rate (mL/min)
+    pub variceal_bleed_rate_ml_min: f64,
+    /// Blood shed by ruptured varices since the patient was created (mL)
+    pub hematemesis_ml: f64,
+    /// Shed blood not yet passed on to the stomach (mL)
+    luminal_blood_ml: f64,
+    /// Clot over the rupture site (0.0 = none, 1.0 = hemostasis)
+    variceal_clot: f64,
+    /// Time the plugged rupture site still holds (s)
+    clot_hold_remaining_s: f64,
 }
 
 impl Esophagus {
@@ -41,6 +97,14 @@ impl Esophagus {
             bolus: None,
             length_cm: 25.0,
             transport_speed: 3.0,
+            portal_pressure_gradient_mmhg: 4.0,
+            varix_diameter_mm: 0.0,
+            variceal_bleeding: false,
+            variceal_bleed_rate_ml_min: 0.0,
+            hematemesis_ml: 0.0,
+            luminal_blood_ml: 0.0,
+            variceal_clot: 0.0,
+            clot_hold_remaining_s: 0.0,
         }
     }
 
@@ -52,10 +116,112 @@ impl Esophagus {
         });
         self.peristalsis_state = PeristalsisState::Contracting;
     }
+
+    /// Get the endoscopic grade of the varices
+    pub fn varix_grade(&self) -> VaricealGrade {
+        if self.varix_diameter_mm < 1.0 {
+            VaricealGrade::None
+        } else if self.varix_diameter_mm < LARGE_VARIX_MM {
+            VaricealGrade::Small
+        } else {
+            VaricealGrade::Large
+        }
+    }
+
+    /// Variceal wall tension (Laplace: pressure x radius, mmHg x mm)
+    pub fn variceal_wall_tension(&self) -> f64 {
+        self.portal_pressure_gradient_mmhg * self.varix_diameter_mm / 2.0
+    }
+
+    /// Collect the blood shed into the esophagus since the last call
+    ///
+    /// # Returns
+    /// Blood passing on to the stomach (mL)
+    pub fn take_luminal_blood(&mut self) -> f64 {
+        std::mem::take(&mut self.luminal_blood_ml)
+    }
+
+    /// Rupture a varix, starting an acute variceal hemorrhage
+    ///
+    /// # Returns
+    /// True if there were varices to rupture
+    pub fn rupture_varices(&mut self) -> bool {
+        if self.varix_grade() == VaricealGrade::None {
+            return false;
+        }
+        self.variceal_bleeding = true;
+        self.variceal_clot = 0.0;
+        self.clot_hold_remaining_s = 0.0;
+        true
+    }
+
+    /// Perform endoscopic band ligation
+    ///
+    /// Stops active variceal bleeding and eradicates the varices; they recur
+    /// if portal hypertension persists.
+    pub fn band_ligation(&mut self) {
+        self.variceal_bleeding = false;
+        self.variceal_bleed_rate_ml_min = 0.0;
+        self.varix_diameter_mm = 0.0;
+        self.variceal_clot = 0.0;
+        self.clot_hold_remaining_s = 0.0;
+    }
+
+    /// Update variceal size, rupture and bleeding
+    ///
+    /// # Arguments
+    /// * `patient` - The patient, whose blood is shed into the lumen
+    /// * `delta_time_s` - Time step in seconds
+    fn update_varices(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let gradient = self.portal_pressure_gradient_mmhg;
+        // Collaterals dilate only above the clinically significant gradient
+        let target_diameter = (gradient - CLINICALLY_SIGNIFICANT_HVPG_MMHG).clamp(0.0, 12.0);
+        let time_constant = if target_diameter > self.varix_diameter_mm { VARIX_GROWTH_S } else { VARIX_REGRESSION_S };
+        self.varix_diameter_mm +=
+            (target_diameter - self.varix_diameter_mm) * (1.0 - (-delta_time_s / time_constant).exp());
+
+        self.clot_hold_remaining_s = (self.clot_hold_remaining_s - delta_time_s).max(0.0);
+        if !self.variceal_bleeding
+            && self.clot_hold_remaining_s <= 0.0
+            && gradient >= 12.0
+            && self.variceal_wall_tension() >= VARIX_RUPTURE_TENSION
+        {
+            self.rupture_varices();
+        }
+        if !self.variceal_bleeding {
+            self.variceal_bleed_rate_ml_min = 0.0;
+            return;
+        }
+
+        // A clot forms more slowly with a prolonged INR, too few platelets or low fibrinogen
+        let clot_time = VARICEAL_CLOT_S * Coagulation::hemostatic_delay(&patient.blood);
+        self.variceal_clot += (1.0 - self.variceal_clot) * (1.0 - (-delta_time_s / clot_time).exp());
+
+        // Hypotension lowers portal pressure and with it the bleeding rate
+        self.variceal_bleed_rate_ml_min = VARICEAL_BLEED_ML_MIN_PER_MMHG
+            * gradient
+            * (self.varix_diameter_mm / LARGE_VARIX_MM)
+            * (1.0 - self.variceal_clot);
+        let shed = patient.fluids.remove_blood(self.variceal_bleed_rate_ml_min * delta_time_s / 60.0);
+        self.hematemesis_ml += shed;
+        self.luminal_blood_ml += shed;
+        if self.variceal_bleed_rate_ml_min < 1.0 {
+            self.variceal_bleeding = false;
+            self.variceal_bleed_rate_ml_min = 0.0;
+            self.clot_hold_remaining_s = VARICEAL_CLOT_HOLD_S;
+        }
+    }
+
+    /// Check for active variceal hemorrhage
+    pub fn is_variceal_hemorrhage(&self) -> bool {
+        self.variceal_bleeding
+    }
 }
 
 impl Organ for Esophagus {
-    fn update(&mut self, _patient: &mut Patient, delta_time_s: f64) {
+    fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        self.update_varices(patient, delta_time_s);
+
         if let Some(ref mut bolus) = self.bolus {
             // Move bolus down esophagus
             bolus.position_cm += self.transport_speed * delta_time_s;
@@ -73,13 +239,25 @@ impl Organ for Esophagus {
     }
 
     fn get_summary(&self) -> String {
+        let varices = match self.varix_grade() {
+            VaricealGrade::None => String::new(),
+            grade => format!(", {:?} varices ({:.1} mm)", grade, self.varix_diameter_mm),
+        };
+        let hemorrhage = if self.is_variceal_hemorrhage() {
+            format!(
+                ", VARICEAL HEMORRHAGE ({:.0} mL/min, blood loss {:.0} mL)",
+                self.variceal_bleed_rate_ml_min, self.hematemesis_ml
+            )
+        } else {
+            String::new()
+        };
         if let Some(ref bolus) = self.bolus {
             format!(
-                "Esophagus: State={:?}, Bolus at {:.1} cm",
-                self.peristalsis_state, bolus.position_cm
+                "Esophagus: State={:?}, Bolus at {:.1} cm{}{}",
+                self.peristalsis_state, bolus.position_cm, varices, hemorrhage
             )
         } else {
-            format!("Esophagus: State={:?}", self.peristalsis_state)
+            format!("Esophagus: State={:?}{}{}", self.peristalsis_state, varices, hemorrhage)
         }
     }
 
@@ -98,4 +276,53 @@ impl Organ for Esophagus {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::liver::Liver;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_cirrhosis_grows_varices_that_bleed_until_banded() {
+        let ruptured = || {
+            let mut patient = initialize_patient(1, 3);
+            patient.get_organ_mut::<Liver>().unwrap().fibrosis = 0.9;
+            update_patient(&mut patient, 10.0);
+            let liver = patient.get_organ::<Liver>().unwrap();
+            assert!(liver.is_portal_hypertension());
+            let gradient = liver.hepatic_venous_pressure_gradient();
+            let mut esophagus = std::mem::replace(patient.get_organ_mut::<Esophagus>().unwrap(), Esophagus::new(0));
+            assert_eq!(esophagus.portal_pressure_gradient_mmhg, gradient);
+            assert_eq!(esophagus.varix_grade(), VaricealGrade::None);
+
+            // Collaterals dilate over weeks of portal hypertension
+            esophagus.update_varices(&mut patient, 30.0 * 86_400.0);
+            assert_ne!(esophagus.varix_grade(), VaricealGrade::None);
+            assert!(!esophagus.is_variceal_hemorrhage());
+
+            assert!(esophagus.rupture_varices());
+            *patient.get_organ_mut::<Esophagus>().unwrap() = esophagus;
+            patient
+        };
+
+        // A ruptured varix bleeds into the gut until it is banded
+        let mut patient = ruptured();
+        let mut banded = ruptured();
+        banded.get_organ_mut::<Esophagus>().unwrap().band_ligation();
+        for _ in 0..300 {
+            update_patient(&mut patient, 1.0);
+            update_patient(&mut banded, 1.0);
+        }
+        let esophagus = patient.get_organ::<Esophagus>().unwrap();
+        assert!(esophagus.is_variceal_hemorrhage());
+        assert!(esophagus.hematemesis_ml > 50.0);
+        assert!(!banded.get_organ::<Esophagus>().unwrap().is_variceal_hemorrhage());
+        assert_eq!(banded.get_organ::<Esophagus>().unwrap().hematemesis_ml, 0.0);
+    }
 }
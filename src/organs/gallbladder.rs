@@ -1,7 +1,65 @@
 //! Gallbladder organ simulation
+//!
+//! Bile storage and gallstone disease, including:
+//! - Filling with hepatic bile between meals, concentration as the wall absorbs
+//!   water, and emptying when a meal's incretins (standing in for cholecystokinin)
+//!   rise
+//! - Cholesterol stones precipitating from bile left concentrated for long
+//!   periods, faster with lithogenic bile
+//! - Stones impacting in the cystic duct (biliary colic, then acute
+//!   cholecystitis and empyema if it stays impacted) or passing into the common
+//!   bile duct, where they obstruct bile flow and the pancreatic duct
+//! - Cholecystectomy
 
 use crate::organ::{Organ, OrganId};
 use crate::patient::Patient;
+use crate::sepsis::BundleGuideline;
+use crate::signals::Signal;
+
+/// Hepatic bile flow into the gallbladder between meals (mL/min)
+const HEPATIC_BILE_ML_MIN: f64 = 0.5;
+
+/// Incretin level at which the gallbladder contracts (relative to fasting)
+const CONTRACTION_INCRETINS: f64 = 1.2;
+
+/// Time constant of gallbladder emptying while contracting (s)
+const EMPTYING_S: f64 = 1200.0;
+
+/// Most concentrated stored bile (relative to hepatic bile)
+const MAX_BILE_CONCENTRATION: f64 = 5.0;
+
+/// Time constant of bile concentration by the gallbladder wall (s)
+const BILE_CONCENTRATION_S: f64 = 14_400.0;
+
+/// Bile concentration above which cholesterol precipitates
+const LITHOGENIC_CONCENTRATION: f64 = 4.0;
+
+/// Time for maximally concentrated normal bile to fill the gallbladder with stones (s)
+const STONE_FORMATION_S: f64 = 31_536_000.0;
+
+/// Chance per second that a contracting gallbladder full of stones impacts one
+const STONE_IMPACTION_PER_S: f64 = 1e-5;
+
+/// Fraction of impacting stones that pass through the cystic duct into the common bile duct
+const COMMON_DUCT_STONE_FRACTION: f64 = 0.25;
+
+/// Mean time for an impacted cystic duct stone to fall back into the gallbladder (s)
+const CYSTIC_DISIMPACTION_S: f64 = 14_400.0;
+
+/// Mean time for a common duct stone to pass into the duodenum (s)
+const COMMON_DUCT_PASSAGE_S: f64 = 172_800.0;
+
+/// Time constant of gallbladder wall inflammation behind an impacted stone (s)
+const CHOLECYSTITIS_ONSET_S: f64 = 43_200.0;
+
+/// Time constant of cholecystitis settling once the cystic duct drains (s)
+const CHOLECYSTITIS_RESOLUTION_S: f64 = 259_200.0;
+
+/// Inflammation at which the obstructed gallbladder's bile becomes infected (empyema)
+const EMPYEMA_INFLAMMATION: f64 = 0.9;
+
+/// Cytokine activity of a maximally inflamed gallbladder
+const CHOLECYSTITIS_CYTOKINES: f64 = 0.3;
 
 /// Gallbladder state
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -11,7 +69,7 @@ pub enum GallbladderState {
 }
 
 /// Gallbladder organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Gallbladder {
     id: OrganId,
     /// Current state
@@ -22,6 +80,22 @@ pub struct Gallbladder {
     pub bile_concentration: f64,
     /// Capacity (mL)
     pub capacity_ml: f64,
+    /// Cholesterol saturation of hepatic bile relative to normal (1.0; raised by
+    /// obesity, pregnancy and rapid weight loss)
+    pub lithogenicity: f64,
+    /// Gallstone burden (0.0 = none, 1.0 = gallbladder packed with stones)
+    pub stone_burden: f64,
+    /// Stone impacted in the cystic duct, so the gallbladder neither fills nor empties
+    pub cystic_duct_stone: bool,
+    /// Stone lodged at the lower end of the common bile duct, where it also
+    /// blocks the pancreatic duct at the ampulla
+    pub common_duct_stone: bool,
+    /// Gallbladder wall inflammation (0.0 = none, 1.0 = gangrenous cholecystitis)
+    pub inflammation: f64,
+    /// Bile infected behind an impacted stone (empyema)
+    pub empyema: bool,
+    /// Gallbladder removed
+    pub removed: bool,
 }
 
 impl Gallbladder {
@@ -33,9 +107,74 @@ impl Gallbladder {
             bile_volume_ml: 0.0,
             bile_concentration: 1.0,
             capacity_ml: 50.0,
+            lithogenicity: 1.0,
+            stone_burden: 0.0,
+            cystic_duct_stone: false,
+            common_duct_stone: false,
+            inflammation: 0.0,
+            empyema: false,
+            removed: false,
         }
     }
 
+    /// Lodge a gallstone in the cystic duct
+    pub fn lodge_cystic_duct_stone(&mut self) {
+        if !self.removed {
+            self.cystic_duct_stone = true;
+        }
+    }
+
+    /// Lodge a gallstone in the common bile duct
+    pub fn lodge_common_duct_stone(&mut self) {
+        self.common_duct_stone = true;
+    }
+
+    /// Clear the common bile duct (stone passed or extracted at ERCP)
+    pub fn clear_common_duct(&mut self) {
+        self.common_duct_stone = false;
+    }
+
+    /// Remove the gallbladder with its stones; a stone already in the common
+    /// bile duct stays there
+    pub fn cholecystectomy(&mut self) {
+        self.removed = true;
+        self.state = GallbladderState::Storing;
+        self.bile_volume_ml = 0.0;
+        self.bile_concentration = 1.0;
+        self.stone_burden = 0.0;
+        self.cystic_duct_stone = false;
+        self.inflammation = 0.0;
+        self.empyema = false;
+    }
+
+    /// Check whether the gallbladder holds stones
+    pub fn has_gallstones(&self) -> bool {
+        self.stone_burden > 0.01
+    }
+
+    /// Check whether the gallbladder wall is acutely inflamed
+    pub fn is_cholecystitis(&self) -> bool {
+        self.inflammation > 0.3
+    }
+
+    /// Check whether the gallbladder or bile duct is contracting against an impacted stone
+    pub fn is_biliary_colic(&self) -> bool {
+        self.state == GallbladderState::Contracting && (self.cystic_duct_stone || self.common_duct_stone)
+    }
+
+    /// Right upper quadrant pain (0-10): severe while colicking, and steady
+    /// once the wall is inflamed
+    pub fn pain_score(&self) -> f64 {
+        let colic: f64 = if self.is_biliary_colic() { 7.0 } else { 0.0 };
+        let cholecystitis = if self.is_cholecystitis() { 3.0 + 5.0 * self.inflammation } else { 0.0 };
+        colic.max(cholecystitis)
+    }
+
+    /// Cytokine activity provoked by an inflamed gallbladder (0.0-1.0)
+    pub fn systemic_inflammation(&self) -> f64 {
+        CHOLECYSTITIS_CYTOKINES * self.inflammation
+    }
+
     /// Store bile from liver
     pub fn store_bile(&mut self, volume_ml: f64) {
         if self.bile_volume_ml < self.capacity_ml {
@@ -51,26 +190,114 @@ impl Gallbladder {
         self.state = GallbladderState::Contracting;
         released * self.bile_concentration
     }
+
+    /// Impact or pass stones, inflame the wall behind an impacted one, and let
+    /// an empyema seed infection
+    ///
+    /// # Arguments
+    /// * `patient` - The patient, whose outcome variability decides stone movements
+    /// * `delta_time_s` - Time step in seconds
+    fn update_stones(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // A contracting gallbladder squeezes stones into the cystic duct, and small ones through it
+        let contracting = self.state == GallbladderState::Contracting;
+        if contracting
+            && !self.cystic_duct_stone
+            && patient.variability.occurs(self.stone_burden * STONE_IMPACTION_PER_S * delta_time_s)
+        {
+            if patient.variability.occurs(COMMON_DUCT_STONE_FRACTION) {
+                self.common_duct_stone = true;
+            } else {
+                self.cystic_duct_stone = true;
+            }
+        }
+        // Impacted stones fall back or pass on
+        if self.cystic_duct_stone && !contracting && patient.variability.occurs(delta_time_s / CYSTIC_DISIMPACTION_S) {
+            self.cystic_duct_stone = false;
+        }
+        if self.common_duct_stone && patient.variability.occurs(delta_time_s / COMMON_DUCT_PASSAGE_S) {
+            self.common_duct_stone = false;
+        }
+
+        // Bile trapped behind an impacted stone inflames the wall within hours
+        let (target, time_constant_s) = if self.cystic_duct_stone {
+            (1.0, CHOLECYSTITIS_ONSET_S)
+        } else {
+            (0.0, CHOLECYSTITIS_RESOLUTION_S)
+        };
+        self.inflammation += (target - self.inflammation) * (1.0 - (-delta_time_s / time_constant_s).exp());
+        if self.inflammation >= EMPYEMA_INFLAMMATION && !self.empyema {
+            self.empyema = true;
+            match patient.sepsis.as_mut() {
+                Some(sepsis) => sepsis.bacterial_load = sepsis.bacterial_load.max(0.1),
+                None => patient.start_infection("Cholecystitis", 1.0, BundleGuideline::HourOne),
+            }
+        }
+    }
 }
 
 impl Organ for Gallbladder {
-    fn update(&mut self, _patient: &mut Patient, delta_time_s: f64) {
-        // Concentrate bile over time
-        if self.bile_volume_ml > 0.0 {
-            self.bile_concentration += delta_time_s * 0.01;
-            self.bile_concentration = self.bile_concentration.min(5.0);
+    fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        if self.removed {
+            return;
         }
 
-        // Return to storing state when not actively contracting
-        if self.state == GallbladderState::Contracting {
+        // A meal's incretins (with cholecystokinin) contract the gallbladder; between
+        // meals it fills with hepatic bile, unless a stone blocks the cystic duct
+        if patient.signals.level(Signal::Incretins) >= CONTRACTION_INCRETINS {
+            let emptied = self.bile_volume_ml * (1.0 - (-delta_time_s / EMPTYING_S).exp());
+            self.release_bile(if self.cystic_duct_stone { 0.0 } else { emptied });
+        } else {
             self.state = GallbladderState::Storing;
+            if !self.cystic_duct_stone {
+                let inflow_ml = (HEPATIC_BILE_ML_MIN * delta_time_s / 60.0)
+                    .min((self.capacity_ml - self.bile_volume_ml).max(0.0));
+                let stored_ml = self.bile_volume_ml;
+                self.store_bile(inflow_ml);
+                // Fresh hepatic bile dilutes what is stored
+                if self.bile_volume_ml > 0.0 {
+                    self.bile_concentration = (self.bile_concentration * stored_ml + inflow_ml) / self.bile_volume_ml;
+                }
+            }
         }
+
+        // The wall absorbs water from stored bile, concentrating it
+        if self.bile_volume_ml > 0.0 {
+            self.bile_concentration += (MAX_BILE_CONCENTRATION - self.bile_concentration)
+                * (1.0 - (-delta_time_s / BILE_CONCENTRATION_S).exp());
+        }
+
+        // Cholesterol precipitates from bile left supersaturated, slowly growing stones
+        let supersaturation = ((self.bile_concentration - LITHOGENIC_CONCENTRATION)
+            / (MAX_BILE_CONCENTRATION - LITHOGENIC_CONCENTRATION))
+            .max(0.0);
+        self.stone_burden =
+            (self.stone_burden + self.lithogenicity * supersaturation * delta_time_s / STONE_FORMATION_S).min(1.0);
+
+        self.update_stones(patient, delta_time_s);
     }
 
     fn get_summary(&self) -> String {
+        if self.removed {
+            return format!(
+                "Gallbladder: Removed{}",
+                if self.common_duct_stone { ", COMMON DUCT STONE" } else { "" }
+            );
+        }
+        let disease = [
+            (self.has_gallstones(), ", Gallstones"),
+            (self.cystic_duct_stone, ", CYSTIC DUCT STONE"),
+            (self.common_duct_stone, ", COMMON DUCT STONE"),
+            (self.is_biliary_colic(), ", BILIARY COLIC"),
+            (self.is_cholecystitis(), ", CHOLECYSTITIS"),
+            (self.empyema, ", EMPYEMA"),
+        ]
+        .iter()
+        .filter(|(present, _)| *present)
+        .map(|(_, label)| *label)
+        .collect::<String>();
         format!(
-            "Gallbladder: State={:?}, Volume={:.0} mL, Concentration={:.1}x",
-            self.state, self.bile_volume_ml, self.bile_concentration
+            "Gallbladder: State={:?}, Volume={:.0} mL, Concentration={:.1}x{}",
+            self.state, self.bile_volume_ml, self.bile_concentration, disease
         )
     }
 
@@ -89,4 +316,52 @@ impl Organ for Gallbladder {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::labs::{Analyte, LabFlag, LabPanel};
+    use crate::organs::liver::Liver;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_gallstones_give_cholestatic_labs_and_cholecystitis() {
+        let mut patient = initialize_patient(1, 3);
+        patient.labs.noise = false;
+        patient.labs.set_turnaround_s(LabPanel::Cmp, 0.0);
+        patient.get_organ_mut::<Gallbladder>().unwrap().lithogenicity = 100.0;
+        for _ in 0..12 {
+            update_patient(&mut patient, 3600.0);
+        }
+        assert!(patient.get_organ::<Gallbladder>().unwrap().has_gallstones());
+
+        // A stone in the common duct backs conjugated bilirubin into the plasma and induces ALP
+        let mut cystic = patient.fork();
+        patient.get_organ_mut::<Gallbladder>().unwrap().lodge_common_duct_stone();
+        cystic.get_organ_mut::<Gallbladder>().unwrap().lodge_cystic_duct_stone();
+        for _ in 0..30 {
+            update_patient(&mut patient, 3600.0);
+            update_patient(&mut cystic, 3600.0);
+        }
+        let id = patient.order_labs(LabPanel::Cmp);
+        let report = patient.labs.report(id).unwrap();
+        let bilirubin = report.result(Analyte::Bilirubin).unwrap();
+        assert_eq!(report.result(Analyte::Alp).unwrap().flag, LabFlag::High);
+        assert!(bilirubin.value > 4.0, "{:?}", bilirubin);
+        assert!(patient.blood.chemistry.bilirubin_direct_mg_dl > 0.8 * bilirubin.value);
+        assert!(patient.get_organ::<Liver>().unwrap().is_obstructive_jaundice());
+
+        // A stone impacted in the cystic duct inflames the gallbladder until its bile is infected
+        let gallbladder = cystic.get_organ::<Gallbladder>().unwrap();
+        assert!(gallbladder.is_cholecystitis() && gallbladder.pain_score() > 6.0);
+        assert!(cystic.blood.chemistry.alp_u_l < 100.0);
+        assert_eq!(cystic.sepsis.as_ref().map(|sepsis| sepsis.source.as_str()), Some("Cholecystitis"));
+        assert!(cystic.cholecystectomy() && !cystic.cholecystectomy());
+        assert_eq!(cystic.get_organ::<Gallbladder>().unwrap().pain_score(), 0.0);
+    }
 }
@@ -5,10 +5,265 @@
 //! - 4 valves (mitral, tricuspid, aortic, pulmonary)
 //! - EKG waveform generation
 //! - Blood pressure regulation
+//! - Stroke volume and cardiac output from preload, afterload and contractility:
+//!   venous return, end-diastolic filling and ventricular-arterial coupling are
+//!   solved together each step, so pressure is the product of flow and resistance
+//! - The cardiovascular response to exercise: tachycardia, stronger contraction,
+//!   muscle-pumped venous return and metabolic vasodilation
+//! - Regional wall motion across the 17 myocardial segments and their coronary territories
+//! - Cardiac rhythm and external/temporary pacing
+//! - Supraventricular arrhythmias (atrial fibrillation with irregular R-R intervals,
+//!   atrial flutter, re-entrant SVT): lost atrial kick and left atrial thrombus
+//!   that can embolize to the brain
+//! - Conduction from the SA node through the AV node, His bundle and bundle branches
+//!   to the Purkinje fibres: first-, second- and third-degree AV block and right or
+//!   left bundle branch block, from conduction disease or inferior and septal infarction
+//! - Cardiac arrest rhythms (VF, pulseless VT, PEA, asystole), chest compressions,
+//!   defibrillation and resuscitation drugs
+//! - Electrolyte effects on conduction and repolarization (potassium, calcium,
+//!   magnesium), from peaked T waves to arrest
+//! - Right ventricular pressure overload from raised pulmonary vascular resistance
+//! - Atrial filling pressures from blood volume, contractility and obstructed venous return
+//! - Pericardial effusion and cardiac tamponade, relieved by pericardiocentesis
+//! - Valve disease: stenotic gradients and regurgitant volumes loading the chambers,
+//!   progressive degeneration, acute rupture and chronic atrial and ventricular dilation
+//! - Chronic heart failure with reduced (weak, dilated ventricle) or preserved (stiff
+//!   ventricle) ejection fraction, and afterload reduction with nitroprusside
 
+use crate::blood::{BloodChemistry, BloodComposition};
+use crate::growth::SECONDS_PER_YEAR;
+use crate::models::starling;
 use crate::organ::{Organ, OrganId};
+use crate::organs::vascular::NORMAL_PULMONARY_VASCULAR_RESISTANCE;
 use crate::patient::Patient;
-use std::collections::VecDeque;
+use crate::signals::Signal;
+use crate::waveform::{RingBuffer, WaveformSampler};
+
+/// Mean pressure gradient an acutely loaded, non-hypertrophied right ventricle can generate (mmHg)
+const MAX_ACUTE_RV_GRADIENT_MMHG: f64 = 32.0;
+
+/// Resting stroke volume of an adult with a 5 L blood volume (mL)
+const NORMAL_STROKE_VOLUME_ML: f64 = 70.0;
+
+/// Blood volume the normal stroke volume refers to (mL)
+const STROKE_VOLUME_REFERENCE_BLOOD_VOLUME_ML: f64 = 5000.0;
+
+/// Resting left ventricular end-diastolic volume of a 5 L adult (mL)
+const NORMAL_END_DIASTOLIC_VOLUME_ML: f64 = 120.0;
+
+/// Volume intercept of the end-systolic pressure-volume relation (mL)
+const LV_UNSTRESSED_VOLUME_ML: f64 = 10.0;
+
+/// End-systolic elastance of a normal 5 L adult ventricle (mmHg/mL)
+const NORMAL_END_SYSTOLIC_ELASTANCE: f64 = 2.2;
+
+/// Normal left ventricular end-diastolic (and left atrial) pressure (mmHg)
+const NORMAL_LV_FILLING_PRESSURE_MMHG: f64 = 8.0;
+
+/// Exponent of the end-diastolic pressure-volume relation per normal end-diastolic volume
+const DIASTOLIC_STIFFNESS: f64 = 3.0;
+
+/// Left atrial pressure beyond which filling cannot rise further (mmHg)
+const MAX_LV_FILLING_PRESSURE_MMHG: f64 = 35.0;
+
+/// Normal transmural right atrial pressure (mmHg)
+const NORMAL_RA_PRESSURE_MMHG: f64 = 4.5;
+
+/// Normal mean pulmonary artery pressure (mmHg)
+const NORMAL_MEAN_PA_PRESSURE_MMHG: f64 = 16.0;
+
+/// Normal systemic vascular resistance of a 5 L adult (Wood units, mmHg·min/L)
+const NORMAL_SYSTEMIC_VASCULAR_RESISTANCE: f64 = 17.0;
+
+/// Normal mean systemic filling pressure, driving venous return (mmHg)
+const NORMAL_MEAN_SYSTEMIC_FILLING_PRESSURE_MMHG: f64 = 10.0;
+
+/// Resistance to venous return of a 5 L adult (Wood units)
+const VENOUS_RETURN_RESISTANCE: f64 = 1.05;
+
+/// Fraction of the euvolemic blood volume that fills the vessels without stretching them
+const UNSTRESSED_VOLUME_FRACTION: f64 = 0.7;
+
+/// Fraction of the euvolemic blood volume sympathetic venoconstriction can shift into
+/// the stressed circulation
+const VENOCONSTRICTION_RESERVE_FRACTION: f64 = 0.2;
+
+/// Rise in sinus rate from rest to maximal exercise in an adult (bpm)
+const EXERCISE_HEART_RATE_RESERVE_BPM: f64 = 110.0;
+
+/// Rise in sinus rate at maximal reflex sympathetic outflow in an adult (bpm)
+const REFLEX_TACHYCARDIA_BPM: f64 = 25.0;
+
+/// Fall in systemic vascular resistance from metabolic vasodilation at maximal exercise
+const EXERCISE_VASODILATION: f64 = 0.8;
+
+/// Rise in the output the right ventricle pumps at normal filling pressure at maximal exercise
+const EXERCISE_RV_RESERVE: f64 = 4.0;
+
+/// Arterial compliance of a 5 L adult, setting pulse pressure from stroke volume (mL/mmHg)
+const ARTERIAL_COMPLIANCE_ML_PER_MMHG: f64 = 1.6;
+
+/// Largest fraction of the ventricle's ejection a flail valve lets leak back
+const MAX_REGURGITANT_FRACTION: f64 = 0.65;
+
+/// Left atrial stiffness of a normal-sized atrium in a 5 L adult (mmHg/mL)
+const LEFT_ATRIAL_STIFFNESS: f64 = 0.3;
+
+/// Right atrial stiffness in a 5 L adult (mmHg/mL)
+const RIGHT_ATRIAL_STIFFNESS: f64 = 0.15;
+
+/// Time constant of chamber dilation under chronic volume or pressure overload (s)
+const CHAMBER_REMODELING_S: f64 = 45.0 * 86_400.0;
+
+/// Forward stroke volume below which organised electrical activity has no palpable pulse (mL)
+const PULSELESS_STROKE_VOLUME_ML: f64 = 20.0;
+
+/// Time constant of fibrillation waveform decay from coarse to fine without CPR (s)
+const FIBRILLATION_DECAY_S: f64 = 300.0;
+
+/// Epinephrine elimination time constant (s)
+const EPINEPHRINE_ELIMINATION_S: f64 = 240.0;
+
+/// Amiodarone redistribution time constant after an IV bolus (s)
+const AMIODARONE_REDISTRIBUTION_S: f64 = 3600.0;
+
+/// Time without perfusion to irreversible myocardial injury (s)
+const MYOCARDIAL_ISCHEMIC_TOLERANCE_S: f64 = 600.0;
+
+/// Normal PR interval (ms)
+const NORMAL_PR_INTERVAL_MS: f64 = 160.0;
+
+/// Normal QRS duration (ms)
+const NORMAL_QRS_DURATION_MS: f64 = 90.0;
+
+/// Normal rate-corrected QT interval (ms)
+const NORMAL_QTC_MS: f64 = 400.0;
+
+/// Normal P wave amplitude (mV)
+const NORMAL_P_WAVE_MV: f64 = 0.2;
+
+/// Normal T wave amplitude (mV)
+const NORMAL_T_WAVE_MV: f64 = 0.3;
+
+/// Potassium above which conduction slows and the QRS widens (mEq/L)
+const CONDUCTION_DELAY_POTASSIUM_MEQ_L: f64 = 6.5;
+
+/// Potassium at which the sine-wave pattern degenerates into VF (mEq/L)
+const FIBRILLATION_POTASSIUM_MEQ_L: f64 = 9.0;
+
+/// Potassium at which the myocardium can no longer depolarize (mEq/L)
+const INEXCITABLE_POTASSIUM_MEQ_L: f64 = 10.0;
+
+/// Rise in the conduction potassium threshold per mg/dL of calcium above normal (mEq/L)
+const CALCIUM_MEMBRANE_STABILISATION: f64 = 0.5;
+
+/// QTc at which torsades de pointes develops (ms)
+const TORSADES_QTC_MS: f64 = 600.0;
+
+/// Volume over which an acutely filled pericardium starts to stiffen steeply (mL)
+const PERICARDIAL_RESERVE_ML: f64 = 120.0;
+
+/// Pericardial pressure that halves ventricular filling (mmHg)
+const TAMPONADE_PRESSURE_MMHG: f64 = 12.0;
+
+/// Highest pressure the pericardial sac reaches before it stops holding more fluid (mmHg)
+const MAX_PERICARDIAL_PRESSURE_MMHG: f64 = 40.0;
+
+/// Biphasic energy a defibrillation shock needs for full effect (J)
+const DEFIBRILLATION_ENERGY_J: f64 = 120.0;
+
+/// Fraction of normal cardiac output generated by good chest compressions
+const CPR_OUTPUT_FRACTION: f64 = 0.25;
+
+/// Ventricular response to atrial fibrillation relative to the sinus rate
+/// the patient's autonomic state would otherwise set
+const AF_VENTRICULAR_RESPONSE: f64 = 1.6;
+
+/// Fastest rate an adult AV node conducts (bpm)
+const MAX_AV_CONDUCTION_BPM: f64 = 200.0;
+
+/// Atrial rate of typical flutter (bpm), conducted with 2:1 block
+const FLUTTER_ATRIAL_RATE_BPM: f64 = 300.0;
+
+/// Rate of re-entrant supraventricular tachycardia in an adult (bpm)
+const SVT_RATE_BPM: f64 = 180.0;
+
+/// Coefficient of variation of the R-R interval in atrial fibrillation
+const AF_RR_VARIABILITY: f64 = 0.2;
+
+/// Fraction of ventricular filling contributed by atrial systole in a compliant ventricle
+const ATRIAL_KICK_FRACTION: f64 = 0.15;
+
+/// Time for a left atrial appendage thrombus to form in fibrillating, unanticoagulated atria (s)
+const ATRIAL_THROMBUS_FORMATION_S: f64 = 48.0 * 3600.0;
+
+/// Time constant of thrombus dissolution under anticoagulation (s)
+const ATRIAL_THROMBUS_RESOLUTION_S: f64 = 21.0 * 86_400.0;
+
+/// Thrombus burden above which restoring atrial contraction dislodges it
+const ATRIAL_THROMBUS_EMBOLIC_BURDEN: f64 = 0.5;
+
+/// Mean regional contractility below which a territory counts as infarcted
+const INFARCT_CONTRACTILITY: f64 = 0.3;
+
+/// Regional function of a healed transmural scar (akinetic, tethered by its neighbours)
+const SCAR_CONTRACTILITY: f64 = 0.1;
+
+/// Troponin released into the blood by necrosis of the whole myocardium (ng/L)
+const TROPONIN_RELEASE_PER_INJURY_NG_L: f64 = 20_000.0;
+
+/// Time constant of troponin release from injured myocytes (s)
+const TROPONIN_RELEASE_S: f64 = 6.0 * 3600.0;
+
+/// Time constant of troponin clearance from the blood (s)
+const TROPONIN_CLEARANCE_S: f64 = 10.0 * 3600.0;
+
+/// Blood troponin of a healthy heart (ng/L)
+const BASELINE_TROPONIN_NG_L: f64 = 5.0;
+
+/// Yearly embolic stroke risk of atrial fibrillation without other risk factors
+const AF_BASE_STROKE_RISK_PER_YEAR: f64 = 0.02;
+
+/// Conduction health of the AV node below which the PR interval lengthens
+const FIRST_DEGREE_BLOCK_HEALTH: f64 = 0.85;
+
+/// Conduction health below which impulses are intermittently dropped
+const SECOND_DEGREE_BLOCK_HEALTH: f64 = 0.6;
+
+/// Conduction health below which no impulses are conducted
+const THIRD_DEGREE_BLOCK_HEALTH: f64 = 0.3;
+
+/// Conduction health of a bundle branch below which it blocks
+const BUNDLE_BRANCH_BLOCK_HEALTH: f64 = 0.6;
+
+/// PR prolongation of a severely diseased but still conducting AV node (ms)
+const FIRST_DEGREE_PR_PROLONGATION_MS: f64 = 150.0;
+
+/// PR lengthening with each beat of a Wenckebach cycle (ms)
+const WENCKEBACH_PR_INCREMENT_MS: f64 = 60.0;
+
+/// QRS widening when a bundle branch blocks and the ventricle depolarizes cell to cell (ms)
+const BUNDLE_BRANCH_QRS_WIDENING_MS: f64 = 50.0;
+
+/// Junctional escape rate below a blocked AV node (bpm)
+const JUNCTIONAL_ESCAPE_BPM: f64 = 45.0;
+
+/// Ventricular escape rate below a blocked His-Purkinje system (bpm)
+const VENTRICULAR_ESCAPE_BPM: f64 = 30.0;
+
+/// Loss of conduction health in tissue whose coronary supply is completely occluded
+const ISCHEMIC_CONDUCTION_INJURY: f64 = 0.5;
+
+/// EKG sampling rate (Hz)
+const EKG_SAMPLE_RATE_HZ: f64 = 250.0;
+
+/// Length of EKG kept in the lead buffers (s)
+const EKG_BUFFER_S: f64 = 10.0;
+
+/// EKG lead names in recording order for the 3-, 5- and 12-lead configurations
+const THREE_LEAD_NAMES: [&str; 3] = ["I", "II", "III"];
+const FIVE_LEAD_NAMES: [&str; 5] = ["I", "II", "III", "aVF", "V1"];
+const TWELVE_LEAD_NAMES: [&str; 12] = ["I", "II", "III", "aVR", "aVL", "aVF", "V1", "V2", "V3", "V4", "V5", "V6"];
 
 /// Chamber state
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -17,6 +272,353 @@ pub enum ChamberState {
     Diastole,
 }
 
+/// Cardiac rhythm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rhythm {
+    NormalSinus,
+    SinusBradycardia,
+    SinusTachycardia,
+    Asystole,
+    /// Rhythm driven by a capturing pacemaker
+    Paced,
+    /// Chaotic ventricular activity without output (shockable)
+    VentricularFibrillation,
+    /// Ventricular tachycardia too fast to fill (shockable)
+    PulselessVentricularTachycardia,
+    /// Organised electrical activity without a palpable pulse
+    PulselessElectricalActivity,
+    /// Polymorphic ventricular tachycardia twisting about the baseline, from a long QT (shockable)
+    TorsadesDePointes,
+    /// Chaotic atrial activity without P waves, conducted irregularly irregularly
+    AtrialFibrillation,
+    /// Regular atrial macro-reentry with sawtooth flutter waves, conducted 2:1
+    AtrialFlutter,
+    /// Regular narrow-complex re-entrant tachycardia through the AV node
+    SupraventricularTachycardia,
+    /// Atria and ventricles beating independently, the ventricles driven by an escape rhythm
+    CompleteHeartBlock,
+}
+
+/// Pacing route
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PacingMode {
+    /// External pads through the chest wall
+    Transcutaneous,
+    /// Temporary wire in the right ventricle
+    Transvenous,
+}
+
+/// Temporary pacemaker
+#[derive(Debug, Clone)]
+pub struct Pacemaker {
+    pub mode: PacingMode,
+    /// Whether the pacemaker is delivering pulses
+    pub is_on: bool,
+    /// Pacing rate (beats per minute)
+    pub rate_bpm: f64,
+    /// Pulse output (mA)
+    pub output_ma: f64,
+    /// Output needed for capture in healthy myocardium (mA)
+    pub capture_threshold_ma: f64,
+    /// Demand mode only paces when the intrinsic rate falls below the pacing rate
+    pub demand_mode: bool,
+    /// Whether the last pulse captured the ventricle
+    pub is_capturing: bool,
+}
+
+impl Pacemaker {
+    /// Create a new pacemaker
+    ///
+    /// # Arguments
+    /// * `mode` - Transcutaneous or transvenous pacing
+    /// * `rate_bpm` - Pacing rate (beats per minute)
+    /// * `output_ma` - Pulse output (mA)
+    pub fn new(mode: PacingMode, rate_bpm: f64, output_ma: f64) -> Self {
+        let capture_threshold_ma = match mode {
+            PacingMode::Transcutaneous => 60.0,  // Typical: 40-80 mA
+            PacingMode::Transvenous => 1.0,      // Typical: 0.5-2 mA
+        };
+
+        Self {
+            mode,
+            is_on: true,
+            rate_bpm: rate_bpm.clamp(30.0, 180.0),
+            output_ma: output_ma.max(0.0),
+            capture_threshold_ma,
+            demand_mode: true,
+            is_capturing: false,
+        }
+    }
+
+    /// Output required for capture given current myocardial viability (mA)
+    /// Ischemic myocardium needs progressively more current to depolarize
+    pub fn effective_threshold_ma(&self, myocardial_viability: f64) -> f64 {
+        self.capture_threshold_ma / myocardial_viability.max(0.05).sqrt()
+    }
+}
+
+/// Coronary artery supplying a myocardial segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoronaryTerritory {
+    LeftAnteriorDescending,
+    LeftCircumflex,
+    RightCoronary,
+}
+
+impl CoronaryTerritory {
+    /// Usual abbreviation of the artery
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            CoronaryTerritory::LeftAnteriorDescending => "LAD",
+            CoronaryTerritory::LeftCircumflex => "LCx",
+            CoronaryTerritory::RightCoronary => "RCA",
+        }
+    }
+}
+
+/// One of the 17 standard left ventricular segments
+#[derive(Debug, Clone)]
+pub struct MyocardialSegment {
+    pub name: &'static str,
+    pub territory: CoronaryTerritory,
+    /// Regional contractile function (1.0 = normal, 0.0 = akinetic)
+    pub contractility: f64,
+    /// Healed infarct scar rather than viable or acutely injured myocardium
+    pub scar: bool,
+}
+
+impl MyocardialSegment {
+    /// The 17 segments with their usual coronary supply
+    pub fn standard_segments() -> Vec<MyocardialSegment> {
+        use CoronaryTerritory::*;
+        [
+            ("Basal anterior", LeftAnteriorDescending),
+            ("Basal anteroseptal", LeftAnteriorDescending),
+            ("Basal inferoseptal", RightCoronary),
+            ("Basal inferior", RightCoronary),
+            ("Basal inferolateral", LeftCircumflex),
+            ("Basal anterolateral", LeftCircumflex),
+            ("Mid anterior", LeftAnteriorDescending),
+            ("Mid anteroseptal", LeftAnteriorDescending),
+            ("Mid inferoseptal", RightCoronary),
+            ("Mid inferior", RightCoronary),
+            ("Mid inferolateral", LeftCircumflex),
+            ("Mid anterolateral", LeftCircumflex),
+            ("Apical anterior", LeftAnteriorDescending),
+            ("Apical septal", LeftAnteriorDescending),
+            ("Apical inferior", RightCoronary),
+            ("Apical lateral", LeftCircumflex),
+            ("Apex", LeftAnteriorDescending),
+        ]
+        .into_iter()
+        .map(|(name, territory)| MyocardialSegment { name, territory, contractility: 1.0, scar: false })
+        .collect()
+    }
+
+    /// Whether the segment moves abnormally (hypokinetic or worse)
+    pub fn is_hypokinetic(&self) -> bool {
+        self.contractility < 0.7
+    }
+}
+
+/// Part of the cardiac conduction pathway
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConductionSegment {
+    SinoatrialNode,
+    AtrioventricularNode,
+    HisBundle,
+    RightBundleBranch,
+    LeftBundleBranch,
+    Purkinje,
+}
+
+/// Degree of atrioventricular block
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AvBlock {
+    None,
+    /// Every impulse conducted, with a PR interval over 200 ms
+    FirstDegree,
+    /// Mobitz I (Wenckebach): the PR lengthens beat by beat until an impulse is dropped, in the AV node
+    MobitzI,
+    /// Mobitz II: impulses dropped without PR lengthening, below the AV node
+    MobitzII,
+    /// No impulses conducted; an escape rhythm drives the ventricles
+    ThirdDegree,
+}
+
+/// Block of one of the bundle branches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BundleBranchBlock {
+    /// The right ventricle depolarizes late: rSR' in V1, slurred S in I and V6
+    Right,
+    /// The left ventricle depolarizes late from the right: broad notched R in I and V6, deep S in V1
+    Left,
+}
+
+/// Conduction pathway from the sinus node to the Purkinje fibres
+///
+/// Each segment has a conduction health (1.0 = normal, 0.0 = no conduction).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConductionSystem {
+    pub sinoatrial_node: f64,
+    pub atrioventricular_node: f64,
+    pub his_bundle: f64,
+    pub right_bundle_branch: f64,
+    pub left_bundle_branch: f64,
+    pub purkinje: f64,
+}
+
+impl ConductionSystem {
+    /// A normally conducting pathway
+    pub fn healthy() -> Self {
+        Self {
+            sinoatrial_node: 1.0,
+            atrioventricular_node: 1.0,
+            his_bundle: 1.0,
+            right_bundle_branch: 1.0,
+            left_bundle_branch: 1.0,
+            purkinje: 1.0,
+        }
+    }
+
+    /// Conduction health of one segment
+    pub fn health(&self, segment: ConductionSegment) -> f64 {
+        match segment {
+            ConductionSegment::SinoatrialNode => self.sinoatrial_node,
+            ConductionSegment::AtrioventricularNode => self.atrioventricular_node,
+            ConductionSegment::HisBundle => self.his_bundle,
+            ConductionSegment::RightBundleBranch => self.right_bundle_branch,
+            ConductionSegment::LeftBundleBranch => self.left_bundle_branch,
+            ConductionSegment::Purkinje => self.purkinje,
+        }
+    }
+
+    /// Mutable conduction health of one segment
+    pub fn health_mut(&mut self, segment: ConductionSegment) -> &mut f64 {
+        match segment {
+            ConductionSegment::SinoatrialNode => &mut self.sinoatrial_node,
+            ConductionSegment::AtrioventricularNode => &mut self.atrioventricular_node,
+            ConductionSegment::HisBundle => &mut self.his_bundle,
+            ConductionSegment::RightBundleBranch => &mut self.right_bundle_branch,
+            ConductionSegment::LeftBundleBranch => &mut self.left_bundle_branch,
+            ConductionSegment::Purkinje => &mut self.purkinje,
+        }
+    }
+
+    /// Conduction below the AV node: the His bundle, then whichever bundle branch still conducts
+    fn infranodal_health(&self) -> f64 {
+        self.his_bundle.min(self.right_bundle_branch.max(self.left_bundle_branch))
+    }
+
+    /// Degree of AV block
+    ///
+    /// Disease of the AV node lengthens the PR interval and then produces
+    /// Wenckebach periodicity; disease of the His bundle, or of both bundle
+    /// branches, drops impulses abruptly (Mobitz II). Either can progress to
+    /// complete block.
+    pub fn av_block(&self) -> AvBlock {
+        let nodal = self.atrioventricular_node;
+        let infranodal = self.infranodal_health();
+        if nodal < THIRD_DEGREE_BLOCK_HEALTH || infranodal < THIRD_DEGREE_BLOCK_HEALTH {
+            AvBlock::ThirdDegree
+        } else if infranodal < SECOND_DEGREE_BLOCK_HEALTH {
+            AvBlock::MobitzII
+        } else if nodal < SECOND_DEGREE_BLOCK_HEALTH {
+            AvBlock::MobitzI
+        } else if nodal < FIRST_DEGREE_BLOCK_HEALTH {
+            AvBlock::FirstDegree
+        } else {
+            AvBlock::None
+        }
+    }
+
+    /// Blocked bundle branch, the left taking precedence when both are diseased
+    pub fn bundle_branch_block(&self) -> Option<BundleBranchBlock> {
+        if self.left_bundle_branch < BUNDLE_BRANCH_BLOCK_HEALTH {
+            Some(BundleBranchBlock::Left)
+        } else if self.right_bundle_branch < BUNDLE_BRANCH_BLOCK_HEALTH {
+            Some(BundleBranchBlock::Right)
+        } else {
+            None
+        }
+    }
+
+    /// Rate of the escape rhythm below a complete block (adult, bpm)
+    ///
+    /// A junctional pacemaker takes over below a blocked AV node; below a
+    /// blocked His-Purkinje system only a slower ventricular one remains.
+    pub fn escape_rate_bpm(&self) -> f64 {
+        if self.is_infranodal_escape() {
+            VENTRICULAR_ESCAPE_BPM
+        } else {
+            JUNCTIONAL_ESCAPE_BPM
+        }
+    }
+
+    /// Whether complete block lies below the AV node, leaving a wide ventricular escape
+    fn is_infranodal_escape(&self) -> bool {
+        self.infranodal_health() < THIRD_DEGREE_BLOCK_HEALTH
+    }
+}
+
+/// Loading conditions for one step of the circulation
+struct CirculatoryLoading {
+    /// Euvolemic blood volume relative to a 5 L adult
+    body_size: f64,
+    /// Stressed blood volume relative to normal
+    stressed_volume_ratio: f64,
+    /// Systemic vascular resistance (Wood units)
+    systemic_resistance: f64,
+    /// Resistance to venous return (Wood units)
+    venous_resistance: f64,
+    /// Pressure surrounding the heart: pericardial or intrathoracic (mmHg)
+    extracardiac_pressure_mmhg: f64,
+    /// Left ventricular contractility relative to normal
+    contractility: f64,
+    /// Fraction of normal diastolic filling the heart rate leaves time for
+    diastolic_filling: f64,
+    /// Output the right ventricle pumps at normal filling pressure, relative to rest
+    right_ventricular_reserve: f64,
+}
+
+/// Steady state of the heart and circulation under a given loading
+struct CirculatoryState {
+    mean_systemic_filling_pressure_mmhg: f64,
+    cardiac_output_l_min: f64,
+    stroke_volume_ml: f64,
+    end_diastolic_volume_ml: f64,
+    right_atrial_pressure_mmhg: f64,
+    left_atrial_pressure_mmhg: f64,
+    mean_pulmonary_artery_pressure_mmhg: f64,
+    /// Total left ventricular ejection, forward and regurgitant (mL)
+    total_stroke_volume_ml: f64,
+    /// Volume ejected across the aortic valve, including what leaks back (mL)
+    aortic_ejection_ml: f64,
+    /// Mean gradients across the mitral, tricuspid, aortic and pulmonary valves (mmHg)
+    valve_gradients_mmhg: [f64; 4],
+    /// Regurgitant volumes through the mitral, tricuspid, aortic and pulmonary valves (mL)
+    regurgitant_volumes_ml: [f64; 4],
+}
+
+/// Chronic heart failure phenotype
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartFailurePhenotype {
+    /// Heart failure with reduced ejection fraction: a weak, dilated ventricle
+    ReducedEjectionFraction,
+    /// Heart failure with preserved ejection fraction: a stiff ventricle that fills
+    /// only at high pressure
+    PreservedEjectionFraction,
+}
+
+/// One of the four heart valves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartValve {
+    Mitral,
+    Tricuspid,
+    Aortic,
+    Pulmonary,
+}
+
 /// Heart valve
 #[derive(Debug, Clone)]
 pub struct Valve {
@@ -24,6 +626,78 @@ pub struct Valve {
     pub is_open: bool,
     pub stenosis_severity: f64,  // 0.0 = normal, 1.0 = complete stenosis
     pub regurgitation_severity: f64,  // 0.0 = normal, 1.0 = severe regurgitation
+    /// Orifice area of the normal valve in a 5 L adult (cm²)
+    pub normal_area_cm2: f64,
+    /// Yearly rise in stenosis severity from degeneration (calcific, rheumatic)
+    pub stenosis_progression_per_year: f64,
+    /// Yearly rise in regurgitation severity from degeneration (myxomatous, annular dilation)
+    pub regurgitation_progression_per_year: f64,
+    /// Mean pressure gradient across the open valve from its narrowing (mmHg)
+    pub mean_gradient_mmhg: f64,
+    /// Volume leaking back through the closed valve each beat (mL)
+    pub regurgitant_volume_ml: f64,
+    /// Gorlin constant relating flow, area and gradient
+    gorlin_constant: f64,
+}
+
+impl Valve {
+    /// Create a competent, unobstructed valve
+    ///
+    /// # Arguments
+    /// * `name` - Valve name
+    /// * `is_open` - Initial leaflet position
+    /// * `normal_area_cm2` - Normal orifice area (cm²)
+    /// * `gorlin_constant` - Gorlin constant (44.3 semilunar, 37.7 atrioventricular)
+    fn new(name: &str, is_open: bool, normal_area_cm2: f64, gorlin_constant: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            is_open,
+            stenosis_severity: 0.0,
+            regurgitation_severity: 0.0,
+            normal_area_cm2,
+            stenosis_progression_per_year: 0.0,
+            regurgitation_progression_per_year: 0.0,
+            mean_gradient_mmhg: 0.0,
+            regurgitant_volume_ml: 0.0,
+            gorlin_constant,
+        }
+    }
+
+    /// Open orifice area (cm²)
+    pub fn area_cm2(&self) -> f64 {
+        self.normal_area_cm2 * (1.0 - self.stenosis_severity.clamp(0.0, 1.0)).max(0.02)
+    }
+
+    /// Fraction of the volume pushed through the valve that leaks back (0.0-0.65)
+    pub fn regurgitant_fraction(&self) -> f64 {
+        MAX_REGURGITANT_FRACTION * self.regurgitation_severity.clamp(0.0, 1.0)
+    }
+
+    /// Mean gradient the narrowing adds to drive a volume through the valve (Gorlin, mmHg)
+    ///
+    /// The few mmHg a normal valve needs are part of normal loading, so only the
+    /// excess over a normal orifice is counted.
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Volume crossing the valve each beat (mL)
+    /// * `flow_time_s` - Time the valve is open each beat (s)
+    /// * `body_size` - Blood volume relative to a 5 L adult, scaling the normal area
+    fn gradient_mmhg(&self, volume_ml: f64, flow_time_s: f64, body_size: f64) -> f64 {
+        let flow_ml_s = volume_ml.max(0.0) / flow_time_s.max(0.01);
+        let gradient = |area_cm2: f64| (flow_ml_s / (self.gorlin_constant * area_cm2 * body_size)).powi(2);
+        gradient(self.area_cm2()) - gradient(self.normal_area_cm2)
+    }
+
+    /// Advance degeneration of the leaflets
+    ///
+    /// # Arguments
+    /// * `delta_time_s` - Time step in seconds
+    fn degenerate(&mut self, delta_time_s: f64) {
+        let years = delta_time_s / SECONDS_PER_YEAR;
+        self.stenosis_severity = (self.stenosis_severity + self.stenosis_progression_per_year * years).clamp(0.0, 0.95);
+        self.regurgitation_severity =
+            (self.regurgitation_severity + self.regurgitation_progression_per_year * years).clamp(0.0, 1.0);
+    }
 }
 
 /// Heart chamber
@@ -36,7 +710,7 @@ pub struct Chamber {
 }
 
 /// Heart organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Heart {
     id: OrganId,
     /// Left atrium
@@ -55,17 +729,152 @@ pub struct Heart {
     pub aortic_valve: Valve,
     /// Pulmonary valve (right ventricle to pulmonary artery)
     pub pulmonary_valve: Valve,
+    /// Chronic left atrial enlargement (0.0 = normal, 1.0 = severe), which
+    /// absorbs a regurgitant jet at lower pressure
+    pub left_atrial_dilation: f64,
+    /// Eccentric left ventricular dilation from chronic volume overload (0.0 = normal, 1.0 = severe)
+    pub left_ventricular_dilation: f64,
+    /// Intrinsic loss of left ventricular contractile function, as in dilated or
+    /// ischemic cardiomyopathy (0.0 = normal, 1.0 = severe)
+    pub systolic_dysfunction: f64,
+    /// Impaired left ventricular relaxation and a stiff, hypertrophied wall
+    /// (0.0 = normal, 1.0 = severe)
+    pub diastolic_dysfunction: f64,
     /// Heart rate (beats per minute)
     pub heart_rate_bpm: f64,
-    /// Ejection fraction (percentage)
+    /// Ejection fraction (percentage), stroke volume over end-diastolic volume
     pub ejection_fraction_percent: f64,
+    /// Left ventricular end-diastolic volume (mL)
+    pub end_diastolic_volume_ml: f64,
+    /// Global left ventricular contractility relative to normal (1.0), from the
+    /// segments, viability, toxins, inotropes and acidosis
+    pub contractility: f64,
+    /// Regional contractile function of the 17 left ventricular segments
+    pub myocardial_segments: Vec<MyocardialSegment>,
+    /// Systemic vascular resistance (Wood units)
+    pub systemic_vascular_resistance: f64,
+    /// Mean systemic filling pressure driving venous return (mmHg)
+    pub mean_systemic_filling_pressure_mmhg: f64,
+    /// Arteriolar resistance relative to normal from the vascular tree (1.0),
+    /// set from the vascular system
+    pub peripheral_resistance_factor: f64,
     /// Aortic pressure (systolic/diastolic)
     pub aortic_pressure_systolic: f64,
     pub aortic_pressure_diastolic: f64,
-    /// EKG lead data
-    pub ekg_leads: Vec<VecDeque<f64>>,
+    /// EKG lead data (mV), sampled at `EKG_SAMPLE_RATE_HZ`
+    pub ekg_leads: Vec<RingBuffer>,
+    /// Clock placing the EKG samples
+    pub ekg_sampler: WaveformSampler,
+    /// Current rhythm
+    pub rhythm: Rhythm,
+    /// Rate generated by the heart's own conduction system (beats per minute)
+    pub intrinsic_heart_rate_bpm: f64,
+    /// Myocardial viability (0.0 = dead, 1.0 = healthy)
+    pub myocardial_viability: f64,
+    /// Multiplier on the time the myocardium survives without perfusion
+    pub ischemic_tolerance_factor: f64,
+    /// Proneness to electrolyte-triggered VF and torsades (1.0 = typical, higher
+    /// fibrillates at less extreme values)
+    pub arrhythmia_susceptibility: f64,
+    /// Response to epinephrine and norepinephrine relative to typical (1.0)
+    pub adrenergic_sensitivity: f64,
+    /// Scenario treatment responsiveness, taken from the patient's difficulty
+    treatment_responsiveness: f64,
+    /// Time without effective mechanical activity (s)
+    pub time_in_asystole_s: f64,
+    /// Attached temporary pacemaker
+    pub pacemaker: Option<Pacemaker>,
+    /// Fraction of venous return reaching the heart (1.0 = unobstructed)
+    /// Reduced by raised intrathoracic pressure (tension pneumothorax)
+    pub venous_return_factor: f64,
+    /// Resting sinus rate for the patient's age (bpm)
+    pub resting_heart_rate_bpm: f64,
+    /// Systemic pressure relative to an adult (1.0); infants and children run lower pressures
+    pub systemic_pressure_scale: f64,
+    /// Calibration multiplier on systemic vascular resistance (1.0 = model default)
+    pub systemic_resistance_scale: f64,
+    /// Calibration multiplier on mean systemic filling pressure (1.0 = model default)
+    pub filling_pressure_scale: f64,
+    /// Pulmonary vascular resistance the right ventricle pumps against (Wood units),
+    /// set from the vascular system
+    pub pulmonary_vascular_resistance: f64,
+    /// Mean pulmonary artery pressure (mmHg, normal ~15)
+    pub mean_pulmonary_artery_pressure: f64,
+    /// Right ventricular strain from pressure overload (0.0 = none, 1.0 = severe)
+    pub rv_strain: f64,
+    /// Cushing response from brainstem ischemia (0.0-1.0), set from the brain;
+    /// raises blood pressure and slows the heart through the vagus
+    pub cushing_response: f64,
+    /// Sympathetic vasomotor tone from the brainstem (1.0 = normal, 0.0 = lost), set from the brain
+    pub vasomotor_tone: f64,
+    /// Norepinephrine infusion (mcg/kg/min)
+    pub norepinephrine_mcg_kg_min: f64,
+    /// Nitroprusside infusion dilating arterioles and veins (mcg/kg/min)
+    pub nitroprusside_mcg_kg_min: f64,
+    /// Muscular work as a fraction of maximal exercise capacity (0.0 = rest, 1.0 = maximal)
+    pub exercise_intensity: f64,
+    /// Chest compressions are being delivered
+    pub cpr_in_progress: bool,
+    /// Epinephrine acting on the heart and vessels (mg)
+    pub epinephrine_mg: f64,
+    /// Amiodarone acting on the myocardium (mg)
+    pub amiodarone_mg: f64,
+    /// Fibrillation waveform amplitude (1.0 = coarse, 0.0 = fine, approaching asystole)
+    pub fibrillation_amplitude: f64,
+    /// Fluid or blood in the pericardial sac beyond the normal few mL (mL)
+    pub pericardial_effusion_ml: f64,
+    /// Pressure in the pericardial sac (mmHg)
+    pub pericardial_pressure_mmhg: f64,
+    /// PR interval (ms)
+    pub pr_interval_ms: f64,
+    /// QRS duration (ms)
+    pub qrs_duration_ms: f64,
+    /// Rate-corrected QT interval (Bazett, ms)
+    pub qtc_ms: f64,
+    /// P wave amplitude (mV); flattens and disappears in severe hyperkalemia
+    pub p_wave_amplitude_mv: f64,
+    /// T wave amplitude (mV); peaked in hyperkalemia, flat in hypokalemia
+    pub t_wave_amplitude_mv: f64,
+    /// U wave amplitude (mV), seen in hypokalemia
+    pub u_wave_amplitude_mv: f64,
+    /// Beat-to-beat variability of the R-R interval (coefficient of variation, 0.0 = regular)
+    pub rr_variability: f64,
+    /// Stroke volume (mL)
+    pub stroke_volume_ml: f64,
+    /// True cardiac output (L/min)
+    pub cardiac_output_l_min: f64,
     /// Internal cardiac cycle timer
     cardiac_cycle_time: f64,
+    /// Troponin in injured myocytes not yet released into the blood (ng/L of blood)
+    troponin_pool_ng_l: f64,
+    /// Largest myocardial injury so far, whose troponin has been released into the pool
+    peak_myocardial_injury: f64,
+    /// Sinus node rate imposed by bradyarrhythmia or asystole (None = normal automaticity)
+    sinus_rate_override: Option<f64>,
+    /// Shockable ventricular arrhythmia overriding the conducted rhythm
+    ventricular_arrhythmia: Option<Rhythm>,
+    /// Time since the ventricular or atrial arrhythmia began, for the EKG waveform (s)
+    arrhythmia_time_s: f64,
+    /// Supraventricular arrhythmia replacing sinus rhythm as the atrial driver
+    atrial_arrhythmia: Option<Rhythm>,
+    /// Length of the current beat relative to the mean R-R interval
+    beat_length_factor: f64,
+    /// Chaotic state that varies R-R intervals from beat to beat
+    rr_jitter: f64,
+    /// Thrombus in the left atrial appendage (0.0 = none, 1.0 = established)
+    pub left_atrial_thrombus: f64,
+    /// Thrombus dislodged from the left atrium awaiting its destination (burden)
+    pending_embolus: Option<f64>,
+    /// Structural health of the conduction pathway, before any ischemic injury
+    pub conduction_system: ConductionSystem,
+    /// Current degree of AV block
+    pub av_block: AvBlock,
+    /// Current bundle branch block
+    pub bundle_branch_block: Option<BundleBranchBlock>,
+    /// Rate of atrial depolarization reaching the AV node (bpm)
+    atrial_rate_bpm: f64,
+    /// Position of the current beat within a second-degree block cycle
+    block_beat_index: u32,
 }
 
 impl Heart {
@@ -76,10 +885,8 @@ impl Heart {
             _ => 12,  // Default to 12-lead
         };
 
-        let mut ekg_leads = Vec::new();
-        for _ in 0..num_leads {
-            ekg_leads.push(VecDeque::with_capacity(1000));
-        }
+        let ekg_sampler = WaveformSampler::new(EKG_SAMPLE_RATE_HZ, EKG_BUFFER_S);
+        let ekg_leads = (0..num_leads).map(|_| ekg_sampler.buffer()).collect();
 
         Self {
             id,
@@ -107,86 +914,1282 @@ impl Heart {
                 volume_ml: 120.0,
                 pressure_mmhg: 4.0,
             },
-            mitral_valve: Valve {
-                name: "Mitral".to_string(),
-                is_open: true,
-                stenosis_severity: 0.0,
-                regurgitation_severity: 0.0,
-            },
-            tricuspid_valve: Valve {
-                name: "Tricuspid".to_string(),
-                is_open: true,
-                stenosis_severity: 0.0,
-                regurgitation_severity: 0.0,
-            },
-            aortic_valve: Valve {
-                name: "Aortic".to_string(),
-                is_open: false,
-                stenosis_severity: 0.0,
-                regurgitation_severity: 0.0,
-            },
-            pulmonary_valve: Valve {
-                name: "Pulmonary".to_string(),
-                is_open: false,
-                stenosis_severity: 0.0,
-                regurgitation_severity: 0.0,
-            },
+            mitral_valve: Valve::new("Mitral", true, 5.0, 37.7),
+            tricuspid_valve: Valve::new("Tricuspid", true, 7.0, 37.7),
+            aortic_valve: Valve::new("Aortic", false, 3.5, 44.3),
+            pulmonary_valve: Valve::new("Pulmonary", false, 3.5, 44.3),
+            left_atrial_dilation: 0.0,
+            left_ventricular_dilation: 0.0,
+            systolic_dysfunction: 0.0,
+            diastolic_dysfunction: 0.0,
             heart_rate_bpm: 75.0,
-            ejection_fraction_percent: 60.0,
+            ejection_fraction_percent: 58.0,
+            end_diastolic_volume_ml: NORMAL_END_DIASTOLIC_VOLUME_ML,
+            contractility: 1.0,
+            myocardial_segments: MyocardialSegment::standard_segments(),
+            systemic_vascular_resistance: NORMAL_SYSTEMIC_VASCULAR_RESISTANCE,
+            mean_systemic_filling_pressure_mmhg: NORMAL_MEAN_SYSTEMIC_FILLING_PRESSURE_MMHG,
+            peripheral_resistance_factor: 1.0,
             aortic_pressure_systolic: 120.0,
             aortic_pressure_diastolic: 80.0,
             ekg_leads,
+            ekg_sampler,
+            rhythm: Rhythm::NormalSinus,
+            intrinsic_heart_rate_bpm: 75.0,
+            myocardial_viability: 1.0,
+            ischemic_tolerance_factor: 1.0,
+            arrhythmia_susceptibility: 1.0,
+            adrenergic_sensitivity: 1.0,
+            treatment_responsiveness: 1.0,
+            time_in_asystole_s: 0.0,
+            pacemaker: None,
+            venous_return_factor: 1.0,
+            resting_heart_rate_bpm: 75.0,
+            systemic_pressure_scale: 1.0,
+            systemic_resistance_scale: 1.0,
+            filling_pressure_scale: 1.0,
+            pulmonary_vascular_resistance: NORMAL_PULMONARY_VASCULAR_RESISTANCE,
+            mean_pulmonary_artery_pressure: 15.5,
+            rv_strain: 0.0,
+            cushing_response: 0.0,
+            vasomotor_tone: 1.0,
+            norepinephrine_mcg_kg_min: 0.0,
+            nitroprusside_mcg_kg_min: 0.0,
+            exercise_intensity: 0.0,
+            cpr_in_progress: false,
+            epinephrine_mg: 0.0,
+            amiodarone_mg: 0.0,
+            fibrillation_amplitude: 0.0,
+            pericardial_effusion_ml: 0.0,
+            pericardial_pressure_mmhg: 0.0,
+            pr_interval_ms: NORMAL_PR_INTERVAL_MS,
+            qrs_duration_ms: NORMAL_QRS_DURATION_MS,
+            qtc_ms: NORMAL_QTC_MS,
+            p_wave_amplitude_mv: NORMAL_P_WAVE_MV,
+            t_wave_amplitude_mv: NORMAL_T_WAVE_MV,
+            u_wave_amplitude_mv: 0.0,
+            rr_variability: 0.0,
+            stroke_volume_ml: NORMAL_STROKE_VOLUME_ML,
+            cardiac_output_l_min: NORMAL_STROKE_VOLUME_ML * 75.0 / 1000.0,
             cardiac_cycle_time: 0.0,
+            troponin_pool_ng_l: 0.0,
+            peak_myocardial_injury: 0.0,
+            sinus_rate_override: None,
+            ventricular_arrhythmia: None,
+            arrhythmia_time_s: 0.0,
+            atrial_arrhythmia: None,
+            beat_length_factor: 1.0,
+            rr_jitter: 0.37,
+            left_atrial_thrombus: 0.0,
+            pending_embolus: None,
+            conduction_system: ConductionSystem::healthy(),
+            av_block: AvBlock::None,
+            bundle_branch_block: None,
+            atrial_rate_bpm: 75.0,
+            block_beat_index: 0,
+        }
+    }
+
+    /// Fraction of beats that fill too little to produce a peripheral pulse
+    ///
+    /// Short R-R intervals in an irregular rhythm leave too little filling
+    /// time, and the faster the rate the more beats are affected.
+    pub fn pulse_deficit_fraction(&self) -> f64 {
+        (self.rr_variability.max(0.0) * self.heart_rate_bpm / 100.0).clamp(0.0, 0.5)
+    }
+
+    /// Induce a bradyarrhythmia (sinus node dysfunction or heart block)
+    ///
+    /// # Arguments
+    /// * `rate_bpm` - Intrinsic escape rate (beats per minute)
+    pub fn induce_bradycardia(&mut self, rate_bpm: f64) {
+        self.sinus_rate_override = Some(rate_bpm.clamp(0.0, 59.0));
+        self.atrial_arrhythmia = None;
+    }
+
+    /// Induce asystole
+    pub fn induce_asystole(&mut self) {
+        self.sinus_rate_override = Some(0.0);
+        self.atrial_arrhythmia = None;
+    }
+
+    /// Restore normal sinus node automaticity, terminating any atrial arrhythmia
+    ///
+    /// Electrical or pharmacological cardioversion restores atrial contraction,
+    /// which can dislodge a left atrial thrombus that formed while the atria
+    /// fibrillated or fluttered.
+    pub fn restore_sinus_rhythm(&mut self) {
+        self.sinus_rate_override = None;
+        let stasis = matches!(self.atrial_arrhythmia, Some(Rhythm::AtrialFibrillation | Rhythm::AtrialFlutter));
+        if stasis && self.left_atrial_thrombus > ATRIAL_THROMBUS_EMBOLIC_BURDEN {
+            self.dislodge_thrombus();
+        }
+        self.atrial_arrhythmia = None;
+    }
+
+    /// Induce atrial fibrillation
+    pub fn induce_atrial_fibrillation(&mut self) {
+        self.start_atrial_arrhythmia(Rhythm::AtrialFibrillation);
+    }
+
+    /// Induce typical atrial flutter
+    pub fn induce_atrial_flutter(&mut self) {
+        self.start_atrial_arrhythmia(Rhythm::AtrialFlutter);
+    }
+
+    /// Induce re-entrant supraventricular tachycardia (AVNRT)
+    pub fn induce_supraventricular_tachycardia(&mut self) {
+        self.start_atrial_arrhythmia(Rhythm::SupraventricularTachycardia);
+    }
+
+    /// Replace sinus rhythm with a supraventricular arrhythmia
+    fn start_atrial_arrhythmia(&mut self, rhythm: Rhythm) {
+        self.atrial_arrhythmia = Some(rhythm);
+        self.sinus_rate_override = None;
+        if self.ventricular_arrhythmia.is_none() {
+            self.arrhythmia_time_s = 0.0;
+        }
+    }
+
+    /// Detach the left atrial thrombus as an embolus
+    fn dislodge_thrombus(&mut self) {
+        self.pending_embolus = Some(self.left_atrial_thrombus.min(1.0));
+        self.left_atrial_thrombus = 0.0;
+    }
+
+    /// Take a left atrial embolus that has left the heart, for the vascular system to lodge
+    ///
+    /// # Returns
+    /// The embolus burden (0.0-1.0), if one has been released since the last call
+    pub fn take_embolus(&mut self) -> Option<f64> {
+        self.pending_embolus.take()
+    }
+
+    /// Yearly risk of cardioembolic stroke
+    ///
+    /// Atrial fibrillation or flutter carries a baseline risk that rises with
+    /// atrial enlargement, heart failure and any thrombus already in the appendage.
+    pub fn embolic_stroke_risk_per_year(&self) -> f64 {
+        let stasis = matches!(self.atrial_arrhythmia, Some(Rhythm::AtrialFibrillation | Rhythm::AtrialFlutter));
+        if !stasis && self.left_atrial_thrombus < 0.01 {
+            return 0.0;
+        }
+        let heart_failure = self.systolic_dysfunction.max(self.diastolic_dysfunction).clamp(0.0, 1.0);
+        (AF_BASE_STROKE_RISK_PER_YEAR
+            * (1.0 + self.left_atrial_dilation.clamp(0.0, 1.0))
+            * (1.0 + heart_failure)
+            * (1.0 + 4.0 * self.left_atrial_thrombus.clamp(0.0, 1.0)))
+        .min(1.0)
+    }
+
+    /// Current supraventricular arrhythmia, if the atria are not in sinus rhythm
+    pub fn atrial_arrhythmia(&self) -> Option<Rhythm> {
+        self.atrial_arrhythmia
+    }
+
+    /// Duration of the current beat (s)
+    ///
+    /// With second-degree block each cycle is one atrial beat, some of which
+    /// are not conducted.
+    fn beat_duration_s(&self) -> f64 {
+        let rate = match self.dropped_beat_cycle() {
+            Some(_) => self.atrial_rate_bpm,
+            None => self.heart_rate_bpm,
+        };
+        60.0 / rate * self.beat_length_factor
+    }
+
+    /// Number of atrial beats in each second-degree block cycle, the last of which is dropped
+    fn dropped_beat_cycle(&self) -> Option<u32> {
+        match self.av_block {
+            _ if self.rhythm == Rhythm::Paced => None,
+            AvBlock::MobitzI => Some(4),
+            AvBlock::MobitzII => Some(3),
+            _ => None,
+        }
+    }
+
+    /// Whether the current atrial beat reaches the ventricles
+    fn is_beat_conducted(&self) -> bool {
+        self.dropped_beat_cycle().is_none_or(|cycle| self.block_beat_index + 1 < cycle)
+    }
+
+    /// Damage part of the conduction system
+    ///
+    /// # Arguments
+    /// * `segment` - Part of the conduction pathway
+    /// * `severity` - Loss of conduction health (0.0-1.0)
+    pub fn damage_conduction(&mut self, segment: ConductionSegment, severity: f64) {
+        let health = self.conduction_system.health_mut(segment);
+        *health = (*health - severity.max(0.0)).clamp(0.0, 1.0);
+    }
+
+    /// Conduction pathway as it currently functions
+    ///
+    /// The AV node is supplied by the right coronary artery and the bundle
+    /// branches by the septal perforators of the LAD, with the His bundle and
+    /// the left bundle also reached from the inferior septum; an inferior or
+    /// septal infarction injures the conducting tissue within its territory.
+    pub fn effective_conduction(&self) -> ConductionSystem {
+        let supply = |names: &[&str]| {
+            let segments: Vec<f64> = self
+                .myocardial_segments
+                .iter()
+                .filter(|s| names.contains(&s.name))
+                .map(|s| s.contractility.clamp(0.0, 1.0))
+                .collect();
+            if segments.is_empty() {
+                1.0
+            } else {
+                segments.iter().sum::<f64>() / segments.len() as f64
+            }
+        };
+        let injury = |supply: f64| 1.0 - ISCHEMIC_CONDUCTION_INJURY * (1.0 - supply);
+        let inferior = supply(&["Basal inferior", "Basal inferoseptal"]);
+        let anterior_septum = supply(&["Basal anteroseptal", "Mid anteroseptal", "Apical septal"]);
+        let inferior_septum = supply(&["Mid inferoseptal"]);
+        let mut conduction = self.conduction_system.clone();
+        conduction.atrioventricular_node = conduction.atrioventricular_node.min(injury(inferior));
+        conduction.his_bundle = conduction.his_bundle.min(injury(inferior.max(anterior_septum)));
+        conduction.right_bundle_branch = conduction.right_bundle_branch.min(injury(anterior_septum));
+        conduction.left_bundle_branch =
+            conduction.left_bundle_branch.min(injury((anterior_septum + inferior_septum) / 2.0));
+        conduction
+    }
+
+    /// Conduct the atrial rate through the AV node and His-Purkinje system
+    ///
+    /// Sets the AV and bundle branch blocks and the PR and QRS intervals they
+    /// produce. Second-degree block conducts only part of the atrial beats;
+    /// complete block leaves the ventricles to an escape rhythm, with P waves
+    /// marching through independently.
+    ///
+    /// # Arguments
+    /// * `atrial_rate_bpm` - Rate of atrial impulses reaching the AV node
+    /// * `size_scale` - Resting rate relative to an adult's
+    ///
+    /// # Returns
+    /// Ventricular rate (bpm)
+    fn update_conduction(&mut self, atrial_rate_bpm: f64, size_scale: f64) -> f64 {
+        let conduction = self.effective_conduction();
+        self.atrial_rate_bpm = atrial_rate_bpm;
+        self.av_block = conduction.av_block();
+        self.bundle_branch_block = conduction.bundle_branch_block();
+        if self.dropped_beat_cycle().is_none() {
+            self.block_beat_index = 0;
         }
+
+        let nodal_delay = (FIRST_DEGREE_BLOCK_HEALTH - conduction.atrioventricular_node)
+            / (FIRST_DEGREE_BLOCK_HEALTH - SECOND_DEGREE_BLOCK_HEALTH);
+        self.pr_interval_ms += FIRST_DEGREE_PR_PROLONGATION_MS * nodal_delay.clamp(0.0, 1.0);
+        if self.av_block == AvBlock::MobitzI {
+            self.pr_interval_ms += WENCKEBACH_PR_INCREMENT_MS * self.block_beat_index as f64;
+        }
+        let ventricular_escape = self.av_block == AvBlock::ThirdDegree && conduction.is_infranodal_escape();
+        self.qrs_duration_ms += 40.0 * (1.0 - conduction.purkinje.clamp(0.0, 1.0))
+            + if self.bundle_branch_block.is_some() || ventricular_escape { BUNDLE_BRANCH_QRS_WIDENING_MS } else { 0.0 };
+
+        match self.av_block {
+            _ if atrial_rate_bpm <= 0.0 => 0.0,
+            AvBlock::MobitzI => atrial_rate_bpm * 3.0 / 4.0,
+            AvBlock::MobitzII => atrial_rate_bpm * 2.0 / 3.0,
+            AvBlock::ThirdDegree => conduction.escape_rate_bpm() * size_scale,
+            _ => atrial_rate_bpm,
+        }
+    }
+
+    /// Replace a coronary territory's myocardium with the scar of an old infarct
+    ///
+    /// The segments stay akinetic, but the injury predates the simulation: it
+    /// releases no troponin and is not reported as an infarct in progress.
+    ///
+    /// # Arguments
+    /// * `territory` - Territory of the old infarct
+    pub fn scar_territory(&mut self, territory: CoronaryTerritory) {
+        for segment in self.myocardial_segments.iter_mut().filter(|s| s.territory == territory) {
+            segment.contractility = SCAR_CONTRACTILITY;
+            segment.scar = true;
+        }
+        self.peak_myocardial_injury = self.peak_myocardial_injury.max(self.myocardial_injury());
+    }
+
+    /// Fraction of the myocardium injured, from regional wall motion loss and lost viability
+    pub fn myocardial_injury(&self) -> f64 {
+        let regional = 1.0 - self.segmental_contractility().min(1.0);
+        regional.max(1.0 - self.myocardial_viability).clamp(0.0, 1.0)
+    }
+
+    /// Release troponin from injured myocardium into the blood
+    ///
+    /// New injury loads the myocytes' troponin into a pool that leaks into the
+    /// blood over hours and is cleared over about half a day, so the level
+    /// rises within hours of an infarct and peaks around half a day later.
+    ///
+    /// # Arguments
+    /// * `chemistry` - Blood chemistry carrying the troponin
+    /// * `delta_time_s` - Time step in seconds
+    fn update_troponin(&mut self, chemistry: &mut BloodChemistry, delta_time_s: f64) {
+        let injury = self.myocardial_injury();
+        if injury > self.peak_myocardial_injury {
+            self.troponin_pool_ng_l += (injury - self.peak_myocardial_injury) * TROPONIN_RELEASE_PER_INJURY_NG_L;
+            self.peak_myocardial_injury = injury;
+        }
+        let released = self.troponin_pool_ng_l * (1.0 - (-delta_time_s / TROPONIN_RELEASE_S).exp());
+        self.troponin_pool_ng_l -= released;
+        let excess = (chemistry.troponin_ng_l - BASELINE_TROPONIN_NG_L) * (-delta_time_s / TROPONIN_CLEARANCE_S).exp();
+        chemistry.troponin_ng_l = BASELINE_TROPONIN_NG_L + excess + released;
+    }
+
+    /// Advance the atrial appendage thrombus
+    ///
+    /// Stasis in fibrillating or fluttering atria forms thrombus over about two
+    /// days, faster in an enlarged atrium; anticoagulation stops its growth and
+    /// dissolves it over weeks. With variability, a thrombus-laden atrium can
+    /// embolize spontaneously at its yearly stroke risk.
+    ///
+    /// # Arguments
+    /// * `patient` - Patient (anticoagulation and outcome variability)
+    /// * `delta_time_s` - Time step in seconds
+    fn update_atrial_thrombus(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let stasis = matches!(self.atrial_arrhythmia, Some(Rhythm::AtrialFibrillation | Rhythm::AtrialFlutter));
+        if patient.coagulation.is_anticoagulated() {
+            self.left_atrial_thrombus *= (-delta_time_s / ATRIAL_THROMBUS_RESOLUTION_S).exp();
+        } else if stasis {
+            self.left_atrial_thrombus += delta_time_s / ATRIAL_THROMBUS_FORMATION_S
+                * (1.0 + self.left_atrial_dilation.clamp(0.0, 1.0))
+                * patient.difficulty.disease_progression;
+        }
+        self.left_atrial_thrombus = self.left_atrial_thrombus.clamp(0.0, 1.0);
+
+        let probability = 1.0 - (-self.embolic_stroke_risk_per_year() * delta_time_s / SECONDS_PER_YEAR).exp();
+        if self.left_atrial_thrombus >= 0.01 && patient.variability.occurs(probability) {
+            self.dislodge_thrombus();
+        }
+    }
+
+    /// Induce ventricular fibrillation
+    pub fn induce_ventricular_fibrillation(&mut self) {
+        self.ventricular_arrhythmia = Some(Rhythm::VentricularFibrillation);
+        self.fibrillation_amplitude = 1.0;
+        self.arrhythmia_time_s = 0.0;
+    }
+
+    /// Induce pulseless ventricular tachycardia
+    pub fn induce_pulseless_ventricular_tachycardia(&mut self) {
+        self.ventricular_arrhythmia = Some(Rhythm::PulselessVentricularTachycardia);
+        self.arrhythmia_time_s = 0.0;
+    }
+
+    /// Induce torsades de pointes
+    pub fn induce_torsades_de_pointes(&mut self) {
+        self.ventricular_arrhythmia = Some(Rhythm::TorsadesDePointes);
+        self.arrhythmia_time_s = 0.0;
+    }
+
+    /// Add fluid or blood to the pericardial sac (e.g. penetrating trauma, ruptured free wall)
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Volume added (mL)
+    pub fn add_pericardial_effusion(&mut self, volume_ml: f64) {
+        self.pericardial_effusion_ml += volume_ml.max(0.0);
+    }
+
+    /// Drain the pericardial sac with a needle
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Volume aspirated (mL)
+    ///
+    /// # Returns
+    /// Volume actually removed (mL)
+    pub fn pericardiocentesis(&mut self, volume_ml: f64) -> f64 {
+        let removed = volume_ml.clamp(0.0, self.pericardial_effusion_ml);
+        self.pericardial_effusion_ml -= removed;
+        removed
+    }
+
+    /// Fraction of normal ventricular filling the pericardial pressure allows (1.0 = unrestricted)
+    pub fn pericardial_filling_factor(&self) -> f64 {
+        1.0 / (1.0 + (self.pericardial_pressure_mmhg / TAMPONADE_PRESSURE_MMHG).powi(2))
+    }
+
+    /// Check for cardiac tamponade (filling restricted by at least a third)
+    pub fn has_tamponade(&self) -> bool {
+        self.pericardial_filling_factor() < 0.67
+    }
+
+    /// Get one of the four valves
+    ///
+    /// # Arguments
+    /// * `valve` - Which valve
+    pub fn valve(&self, valve: HeartValve) -> &Valve {
+        match valve {
+            HeartValve::Mitral => &self.mitral_valve,
+            HeartValve::Tricuspid => &self.tricuspid_valve,
+            HeartValve::Aortic => &self.aortic_valve,
+            HeartValve::Pulmonary => &self.pulmonary_valve,
+        }
+    }
+
+    /// Get one of the four valves mutably
+    ///
+    /// # Arguments
+    /// * `valve` - Which valve
+    pub fn valve_mut(&mut self, valve: HeartValve) -> &mut Valve {
+        match valve {
+            HeartValve::Mitral => &mut self.mitral_valve,
+            HeartValve::Tricuspid => &mut self.tricuspid_valve,
+            HeartValve::Aortic => &mut self.aortic_valve,
+            HeartValve::Pulmonary => &mut self.pulmonary_valve,
+        }
+    }
+
+    /// Start progressive degeneration of a valve
+    ///
+    /// Calcific aortic stenosis typically narrows by 0.1 cm² a year (about 0.03
+    /// severity); degenerative mitral regurgitation worsens over years as the
+    /// regurgitant jet dilates the annulus.
+    ///
+    /// # Arguments
+    /// * `valve` - Which valve
+    /// * `stenosis_per_year` - Rise in stenosis severity per year
+    /// * `regurgitation_per_year` - Rise in regurgitation severity per year
+    pub fn start_valve_degeneration(&mut self, valve: HeartValve, stenosis_per_year: f64, regurgitation_per_year: f64) {
+        let valve = self.valve_mut(valve);
+        valve.stenosis_progression_per_year = stenosis_per_year.max(0.0);
+        valve.regurgitation_progression_per_year = regurgitation_per_year.max(0.0);
+    }
+
+    /// Acutely rupture a valve (chordal or papillary muscle rupture, endocarditic
+    /// perforation, aortic dissection), leaving it flail and freely regurgitant
+    ///
+    /// The chambers have had no time to dilate, so the regurgitant volume lands in
+    /// a small, stiff atrium or ventricle at high pressure.
+    ///
+    /// # Arguments
+    /// * `valve` - Which valve
+    pub fn rupture_valve(&mut self, valve: HeartValve) {
+        self.valve_mut(valve).regurgitation_severity = 1.0;
+    }
+
+    /// Give the heart an established chronic heart failure phenotype
+    ///
+    /// Reduced ejection fraction weakens contraction and arrives with the eccentric
+    /// dilation years of remodeling would have produced; preserved ejection fraction
+    /// stiffens the ventricle so it needs a high filling pressure for a normal
+    /// volume. Either way the raised left atrial pressure congests the lungs.
+    ///
+    /// # Arguments
+    /// * `phenotype` - Reduced or preserved ejection fraction
+    /// * `severity` - Severity of the dysfunction (0.0-1.0)
+    pub fn induce_heart_failure(&mut self, phenotype: HeartFailurePhenotype, severity: f64) {
+        let severity = severity.clamp(0.0, 1.0);
+        match phenotype {
+            HeartFailurePhenotype::ReducedEjectionFraction => {
+                self.systolic_dysfunction = severity;
+                self.left_ventricular_dilation = self.left_ventricular_dilation.max(0.3 * severity);
+            }
+            HeartFailurePhenotype::PreservedEjectionFraction => {
+                self.diastolic_dysfunction = severity;
+                self.left_atrial_dilation = self.left_atrial_dilation.max(0.5 * severity);
+            }
+        }
+    }
+
+    /// Set the contractile function of every segment a coronary artery supplies
+    ///
+    /// # Arguments
+    /// * `territory` - Coronary artery
+    /// * `contractility` - Regional function (1.0 = normal, 0.0 = akinetic)
+    pub fn set_territory_contractility(&mut self, territory: CoronaryTerritory, contractility: f64) {
+        for segment in self.myocardial_segments.iter_mut().filter(|s| s.territory == territory) {
+            segment.contractility = contractility.clamp(0.0, 1.5);
+        }
+    }
+
+    /// Mean contractile function across the 17 segments (1.0 = normal)
+    pub fn segmental_contractility(&self) -> f64 {
+        if self.myocardial_segments.is_empty() {
+            return 1.0;
+        }
+        self.myocardial_segments.iter().map(|s| s.contractility).sum::<f64>() / self.myocardial_segments.len() as f64
+    }
+
+    /// Coronary territories with acute transmural injury, whose viable segments
+    /// are on average akinetic, as in ST-elevation myocardial infarction
+    pub fn infarcted_territories(&self) -> Vec<CoronaryTerritory> {
+        use CoronaryTerritory::*;
+        [LeftAnteriorDescending, LeftCircumflex, RightCoronary]
+            .into_iter()
+            .filter(|&territory| {
+                let segments: Vec<f64> = self
+                    .myocardial_segments
+                    .iter()
+                    .filter(|s| s.territory == territory && !s.scar)
+                    .map(|s| s.contractility)
+                    .collect();
+                !segments.is_empty() && segments.iter().sum::<f64>() / (segments.len() as f64) < INFARCT_CONTRACTILITY
+            })
+            .collect()
+    }
+
+    /// Number of segments with a regional wall motion abnormality
+    pub fn hypokinetic_segment_count(&self) -> usize {
+        self.myocardial_segments.iter().filter(|s| s.is_hypokinetic()).count()
+    }
+
+    /// Uncorrected QT interval at the current rate (ms)
+    pub fn qt_interval_ms(&self) -> f64 {
+        if self.heart_rate_bpm > 0.0 {
+            self.qtc_ms * (60.0 / self.heart_rate_bpm).sqrt()
+        } else {
+            self.qtc_ms
+        }
+    }
+
+    /// Deliver an unsynchronised defibrillation shock
+    ///
+    /// Coarse fibrillation converts readily; fine fibrillation after minutes
+    /// without CPR usually does not until compressions and epinephrine restore
+    /// the waveform. Amiodarone lowers the threshold for refractory VF.
+    ///
+    /// # Arguments
+    /// * `energy_j` - Biphasic energy in joules (typically 120-200 J)
+    ///
+    /// # Returns
+    /// Whether the shock terminated a shockable rhythm
+    pub fn defibrillate(&mut self, energy_j: f64) -> bool {
+        self.defibrillate_with(energy_j, |margin| margin >= 0.0)
+    }
+
+    /// Deliver a defibrillation shock whose success is decided by the caller
+    ///
+    /// # Arguments
+    /// * `energy_j` - Biphasic energy in joules
+    /// * `decide` - Given how far the shock exceeds the defibrillation threshold
+    ///   (negative when it falls short), whether it converts
+    ///
+    /// # Returns
+    /// Whether the shock terminated a shockable rhythm
+    pub fn defibrillate_with(&mut self, energy_j: f64, decide: impl FnOnce(f64) -> bool) -> bool {
+        let Some(rhythm) = self.ventricular_arrhythmia else {
+            return false;
+        };
+        let energy = (energy_j.max(0.0) / DEFIBRILLATION_ENERGY_J).min(1.0);
+        let antiarrhythmic = self.amiodarone_mg / (self.amiodarone_mg + 150.0);
+        let threshold = match rhythm {
+            Rhythm::PulselessVentricularTachycardia | Rhythm::TorsadesDePointes => 0.1,
+            _ => 0.35 - 0.15 * antiarrhythmic,
+        };
+        let converted = self.myocardial_viability > 0.2
+            && energy > 0.0
+            && decide(self.organised_substrate(rhythm) * energy - threshold);
+        if converted {
+            self.ventricular_arrhythmia = None;
+        } else if rhythm == Rhythm::VentricularFibrillation {
+            self.fibrillation_amplitude *= 0.9;
+        }
+        converted
+    }
+
+    /// How organised the myocardium is for a shock to capture (0.0-1.0)
+    fn organised_substrate(&self, rhythm: Rhythm) -> f64 {
+        match rhythm {
+            Rhythm::VentricularFibrillation => self.fibrillation_amplitude,
+            _ => 1.0,
+        }
+    }
+
+    /// Give an IV/IO epinephrine bolus
+    ///
+    /// # Arguments
+    /// * `mg` - Dose in mg (1 mg every 3-5 minutes in arrest)
+    pub fn give_epinephrine(&mut self, mg: f64) {
+        self.epinephrine_mg += mg.max(0.0);
+    }
+
+    /// Give an IV amiodarone bolus
+    ///
+    /// # Arguments
+    /// * `mg` - Dose in mg (300 mg, then 150 mg, for refractory VF/pVT)
+    pub fn give_amiodarone(&mut self, mg: f64) {
+        self.amiodarone_mg += mg.max(0.0);
+    }
+
+    /// Epinephrine effect (0.0-1.0)
+    pub fn epinephrine_effect(&self) -> f64 {
+        let dose = self.epinephrine_mg * self.drug_sensitivity();
+        dose / (dose + 0.5)
+    }
+
+    /// Catecholamine sensitivity including the scenario's treatment responsiveness
+    fn drug_sensitivity(&self) -> f64 {
+        (self.adrenergic_sensitivity * self.treatment_responsiveness).max(0.0)
+    }
+
+    /// Check for a shockable rhythm (VF or pulseless VT)
+    pub fn is_shockable(&self) -> bool {
+        self.ventricular_arrhythmia.is_some()
+    }
+
+    /// Check for cardiac arrest (no effective output)
+    pub fn is_in_cardiac_arrest(&self) -> bool {
+        matches!(
+            self.rhythm,
+            Rhythm::Asystole
+                | Rhythm::VentricularFibrillation
+                | Rhythm::PulselessVentricularTachycardia
+                | Rhythm::PulselessElectricalActivity
+                | Rhythm::TorsadesDePointes
+        )
+    }
+
+    /// Check for a palpable central pulse
+    pub fn has_pulse(&self) -> bool {
+        !self.is_in_cardiac_arrest()
+    }
+
+    /// Attach a temporary pacemaker
+    pub fn attach_pacemaker(&mut self, pacemaker: Pacemaker) {
+        self.pacemaker = Some(pacemaker);
+    }
+
+    /// Remove the temporary pacemaker
+    ///
+    /// # Returns
+    /// The pacemaker that was attached, if any
+    pub fn remove_pacemaker(&mut self) -> Option<Pacemaker> {
+        self.pacemaker.take()
+    }
+
+    /// Determine heart rate and rhythm from intrinsic activity and pacing
+    fn update_rhythm(&mut self) {
+        self.rr_variability = match self.atrial_arrhythmia {
+            Some(Rhythm::AtrialFibrillation) if self.pacemaker.as_ref().is_none_or(|p| !p.is_capturing) => {
+                AF_RR_VARIABILITY
+            }
+            _ => 0.0,
+        };
+        // Fibrillating or pulseless-tachycardic ventricles neither fill nor eject
+        if let Some(rhythm) = self.ventricular_arrhythmia {
+            self.heart_rate_bpm = 0.0;
+            self.rhythm = rhythm;
+            if let Some(pacemaker) = self.pacemaker.as_mut() {
+                pacemaker.is_capturing = false;
+            }
+            return;
+        }
+        let viability = self.myocardial_viability;
+        let intrinsic_rate = self.intrinsic_heart_rate_bpm;
+
+        let mut paced_rate = None;
+        if let Some(pacemaker) = self.pacemaker.as_mut() {
+            let needs_pacing = !pacemaker.demand_mode || intrinsic_rate < pacemaker.rate_bpm;
+            // Non-viable myocardium cannot be captured regardless of output
+            pacemaker.is_capturing = pacemaker.is_on
+                && needs_pacing
+                && viability > 0.2
+                && pacemaker.output_ma >= pacemaker.effective_threshold_ma(viability);
+            if pacemaker.is_capturing {
+                paced_rate = Some(pacemaker.rate_bpm);
+            }
+        }
+
+        match paced_rate {
+            Some(rate) => {
+                self.heart_rate_bpm = rate;
+                self.rhythm = Rhythm::Paced;
+            }
+            None => {
+                self.heart_rate_bpm = intrinsic_rate;
+                // Sinus rhythm is named from the atrial rate, whether or not every beat is conducted
+                let sinus_rate = if self.dropped_beat_cycle().is_some() { self.atrial_rate_bpm } else { intrinsic_rate };
+                self.rhythm = if intrinsic_rate <= 0.0 {
+                    Rhythm::Asystole
+                } else if self.av_block == AvBlock::ThirdDegree {
+                    Rhythm::CompleteHeartBlock
+                } else if let Some(rhythm) = self.atrial_arrhythmia {
+                    rhythm
+                } else if sinus_rate < 60.0 {
+                    Rhythm::SinusBradycardia
+                } else if sinus_rate > 100.0 {
+                    Rhythm::SinusTachycardia
+                } else {
+                    Rhythm::NormalSinus
+                };
+            }
+        }
+    }
+
+    /// Apply potassium, calcium and magnesium to conduction and repolarization
+    ///
+    /// Rising potassium first peaks the T wave, then (above ~6.5 mEq/L) slows
+    /// conduction: the PR lengthens, P waves flatten and the QRS widens towards a
+    /// sine wave that degenerates into VF, and finally the myocardium becomes
+    /// inexcitable. Calcium raises the threshold for these conduction effects.
+    /// Hypocalcemia, hypomagnesemia and hypokalemia prolong the QT, which at the
+    /// extreme triggers torsades de pointes.
+    ///
+    /// # Arguments
+    /// * `blood` - Blood composition
+    ///
+    /// # Returns
+    /// Fraction of the sinus rate conducted (1.0 = normal, 0.0 = inexcitable)
+    fn update_electrolytes(&mut self, blood: &BloodComposition) -> f64 {
+        let potassium = blood.get_potassium_meq_l();
+        let calcium = blood.chemistry.calcium_mg_dl;
+        let magnesium = blood.chemistry.magnesium_mg_dl;
+
+        // Repolarization: the T wave follows potassium directly
+        self.t_wave_amplitude_mv = NORMAL_T_WAVE_MV
+            * (1.0 + 0.7 * (potassium - 5.0).max(0.0))
+            * (potassium / 3.5).clamp(0.3, 1.0);
+        self.u_wave_amplitude_mv = (0.1 * (3.5 - potassium)).clamp(0.0, 0.15);
+
+        // Conduction: calcium antagonises potassium's effect on the membrane
+        let conduction_potassium = potassium - CALCIUM_MEMBRANE_STABILISATION * (calcium - 9.5).max(0.0);
+        let excess = (conduction_potassium - CONDUCTION_DELAY_POTASSIUM_MEQ_L).max(0.0);
+        self.pr_interval_ms = NORMAL_PR_INTERVAL_MS + 40.0 * excess;
+        self.qrs_duration_ms = NORMAL_QRS_DURATION_MS + 45.0 * excess;
+        self.p_wave_amplitude_mv = NORMAL_P_WAVE_MV * (1.0 - excess / 1.5).clamp(0.0, 1.0);
+
+        self.qtc_ms = NORMAL_QTC_MS + 40.0 * (8.5 - calcium).max(0.0) - 15.0 * (calcium - 10.5).max(0.0)
+            + 85.0 * (1.7 - magnesium).max(0.0)
+            + 50.0 * (3.5 - potassium).max(0.0);
+
+        // Extreme values end in arrest; susceptible hearts fibrillate earlier
+        let susceptibility = self.arrhythmia_susceptibility.max(0.1);
+        let fibrillation_potassium = CONDUCTION_DELAY_POTASSIUM_MEQ_L
+            + (FIBRILLATION_POTASSIUM_MEQ_L - CONDUCTION_DELAY_POTASSIUM_MEQ_L) / susceptibility;
+        let torsades_qtc = NORMAL_QTC_MS + (TORSADES_QTC_MS - NORMAL_QTC_MS) / susceptibility;
+        if conduction_potassium >= INEXCITABLE_POTASSIUM_MEQ_L {
+            if self.ventricular_arrhythmia.is_some() {
+                self.ventricular_arrhythmia = None;
+            }
+            return 0.0;
+        }
+        if self.ventricular_arrhythmia.is_none() {
+            if conduction_potassium >= fibrillation_potassium {
+                self.induce_ventricular_fibrillation();
+            } else if self.qtc_ms >= torsades_qtc {
+                self.induce_torsades_de_pointes();
+            }
+        }
+        (1.0 - 0.3 * (conduction_potassium - 7.0).max(0.0)).clamp(0.0, 1.0)
+    }
+
+    /// Advance resuscitation drug levels and the fibrillation waveform
+    fn update_arrest(&mut self, delta_time_s: f64) {
+        self.epinephrine_mg *= (-delta_time_s / EPINEPHRINE_ELIMINATION_S).exp();
+        self.amiodarone_mg *= (-delta_time_s / AMIODARONE_REDISTRIBUTION_S).exp();
+        if self.ventricular_arrhythmia.is_some()
+            || self.atrial_arrhythmia.is_some()
+            || self.av_block == AvBlock::ThirdDegree
+        {
+            self.arrhythmia_time_s += delta_time_s;
+        }
+        // Epinephrine during compressions can restore a slow escape rhythm from asystole
+        if self.sinus_rate_override == Some(0.0)
+            && self.cpr_in_progress
+            && self.epinephrine_effect() > 0.5
+            && self.myocardial_viability > 0.5
+        {
+            self.sinus_rate_override = Some(40.0);
+        }
+        if self.ventricular_arrhythmia == Some(Rhythm::VentricularFibrillation) {
+            // Untreated, the ischemic myocardium fibrillates ever more finely;
+            // compressions and epinephrine coarsen the waveform again
+            let (target, time_constant) = if self.cpr_in_progress {
+                (0.5 + 0.4 * self.epinephrine_effect(), 120.0)
+            } else {
+                (0.0, FIBRILLATION_DECAY_S)
+            };
+            self.fibrillation_amplitude +=
+                (target - self.fibrillation_amplitude) * (1.0 - (-delta_time_s / time_constant).exp());
+        }
+    }
+
+    /// Pressures and flow generated by chest compressions during arrest
+    ///
+    /// # Arguments
+    /// * `volume_ratio` - Effective circulating volume relative to normal
+    /// * `body_size` - Reference blood volume relative to a 5 L adult
+    /// * `pressure_scale` - Size and peripheral vascular tone scaling of systemic pressure
+    fn apply_chest_compressions(&mut self, volume_ratio: f64, body_size: f64, pressure_scale: f64) {
+        // Epinephrine's vasoconstriction raises the diastolic (coronary perfusion) pressure
+        let filling = volume_ratio.clamp(0.0, 1.0);
+        let systolic = 70.0 * pressure_scale * filling;
+        let diastolic = (12.0 + 15.0 * self.epinephrine_effect()) * pressure_scale * filling;
+        self.aortic_pressure_systolic = self.aortic_pressure_systolic.max(systolic);
+        self.aortic_pressure_diastolic = self.aortic_pressure_diastolic.max(diastolic);
+        let compression_output =
+            NORMAL_STROKE_VOLUME_ML * body_size * 75.0 / 1000.0 * CPR_OUTPUT_FRACTION * filling;
+        self.cardiac_output_l_min = self.cardiac_output_l_min.max(compression_output);
+    }
+
+    /// Names of the EKG leads in the order of `ekg_leads`
+    pub fn ekg_lead_names(&self) -> &'static [&'static str] {
+        match self.ekg_leads.len() {
+            3 => &THREE_LEAD_NAMES,
+            5 => &FIVE_LEAD_NAMES,
+            _ => &TWELVE_LEAD_NAMES,
+        }
+    }
+
+    /// Rate at which EKG samples are recorded (Hz)
+    pub fn ekg_sampling_rate_hz(&self) -> f64 {
+        self.ekg_sampler.sample_rate_hz
     }
 
     /// Generate EKG waveform value
-    fn generate_ekg(&self, lead_index: usize) -> f64 {
-        let cycle_progress = self.cardiac_cycle_time / (60.0 / self.heart_rate_bpm);
+    ///
+    /// # Arguments
+    /// * `lead_index` - Lead, in the order of `ekg_lead_names`
+    /// * `lookback_s` - How long before the end of the current step the sample falls (s)
+    fn generate_ekg(&self, lead_index: usize, lookback_s: f64) -> f64 {
+        let two_pi = 2.0 * std::f64::consts::PI;
+        let t = self.arrhythmia_time_s - lookback_s;
+        match self.ventricular_arrhythmia {
+            // Irregular undulations whose amplitude falls from coarse to fine
+            Some(Rhythm::VentricularFibrillation) => {
+                let amplitude = 0.1 + 0.5 * self.fibrillation_amplitude;
+                return amplitude
+                    * ((two_pi * 5.3 * t).sin() + 0.6 * (two_pi * 7.1 * t + 1.0).sin() + 0.4 * (two_pi * 3.7 * t + 2.0).sin());
+            }
+            // Polymorphic complexes whose axis twists about the baseline
+            Some(Rhythm::TorsadesDePointes) => {
+                return 1.5 * (two_pi * 0.4 * t).sin() * (two_pi * 4.0 * t).sin();
+            }
+            // Wide, regular, sinusoidal complexes at ~180 bpm
+            Some(_) => return 1.5 * (two_pi * 3.0 * t).sin(),
+            None => {}
+        }
+        if self.heart_rate_bpm <= 0.0 {
+            // Asystole - flat line
+            return 0.0;
+        }
+
+        let beat_duration = self.beat_duration_s();
+        let cycle_progress = (self.cardiac_cycle_time - lookback_s).rem_euclid(beat_duration) / beat_duration;
         let phase = cycle_progress * 2.0 * std::f64::consts::PI;
+        let paced = self.rhythm == Rhythm::Paced;
 
         // Simple EKG waveform approximation
-        let p_wave = if cycle_progress < 0.2 {
-            0.2 * (phase * 5.0).sin()
+        // Electrolytes widen the QRS and move the T wave with the QT interval
+        let qrs_width = self.qrs_duration_ms / NORMAL_QRS_DURATION_MS;
+        let qt_stretch = self.qtc_ms / NORMAL_QTC_MS;
+        let qrs_end = (0.3 + 0.1 * qrs_width).min(0.5);
+        let t_start = (0.3 + 0.2 * qt_stretch).max(qrs_end).min(0.85);
+        let t_end = (t_start + 0.2 * qt_stretch.min(1.0)).min(0.95);
+        // Fibrillating atria give a wavering baseline and flutter a sawtooth instead
+        // of P waves; in re-entrant SVT the P wave is buried in the QRS
+        let p_wave = match self.atrial_arrhythmia {
+            _ if paced => 0.0,
+            Some(Rhythm::AtrialFibrillation) => {
+                0.05 * ((two_pi * 6.3 * t).sin() + 0.7 * (two_pi * 8.9 * t + 1.0).sin())
+            }
+            Some(Rhythm::AtrialFlutter) => {
+                let flutter_phase = (t * FLUTTER_ATRIAL_RATE_BPM / 60.0).fract();
+                -0.2 * self.p_wave_amplitude_mv / NORMAL_P_WAVE_MV * flutter_phase
+            }
+            Some(_) => 0.0,
+            // In complete heart block the P waves march through at their own rate
+            None if self.av_block == AvBlock::ThirdDegree => {
+                let atrial_progress = (t * self.atrial_rate_bpm / 60.0).fract();
+                if atrial_progress < 0.2 {
+                    self.p_wave_amplitude_mv * (atrial_progress * two_pi * 5.0).sin()
+                } else {
+                    0.0
+                }
+            }
+            None if cycle_progress < 0.2 => self.p_wave_amplitude_mv * (phase * 5.0).sin(),
+            None => 0.0,
+        };
+        // A blocked impulse leaves a P wave with no QRS or T wave after it
+        if !self.is_beat_conducted() {
+            return p_wave * lead_amplitude(lead_index);
+        }
+
+        // Pacing spike followed by a wide ventricular complex
+        let pacing_spike = if paced && cycle_progress > 0.28 && cycle_progress < 0.3 {
+            2.0
         } else {
             0.0
         };
 
-        let qrs_complex = if cycle_progress > 0.3 && cycle_progress < 0.4 {
-            1.0 * ((phase - 2.0) * 10.0).sin()
+        let qrs_complex = if paced && cycle_progress > 0.3 && cycle_progress < 0.45 {
+            1.2 * ((phase - 2.0) * 6.0).sin()
+        } else if cycle_progress > 0.3 && cycle_progress < qrs_end {
+            let qrs_progress = (cycle_progress - 0.3) / (qrs_end - 0.3);
+            self.bundle_branch_morphology(lead_index, qrs_progress)
+                .unwrap_or_else(|| ((phase - 2.0) * 10.0 / qrs_width).sin())
         } else {
             0.0
         };
 
-        let t_wave = if cycle_progress > 0.5 && cycle_progress < 0.7 {
-            0.3 * ((phase - 3.5) * 5.0).sin()
+        let t_wave = if cycle_progress > t_start && cycle_progress < t_end {
+            let t_phase = (cycle_progress - t_start) / (t_end - t_start);
+            self.t_wave_amplitude_mv * (t_phase * std::f64::consts::PI).sin()
+        } else {
+            0.0
+        };
+        let u_wave = if cycle_progress > t_end && cycle_progress < t_end + 0.05 {
+            self.u_wave_amplitude_mv * ((cycle_progress - t_end) / 0.05 * std::f64::consts::PI).sin()
         } else {
             0.0
         };
 
-        // Different leads have different amplitudes
-        let amplitude_factor = match lead_index {
-            0 => 1.0,
-            1 => 0.8,
-            2 => 0.9,
-            _ => 0.7 + (lead_index as f64 * 0.05),
+        (p_wave + pacing_spike + qrs_complex + t_wave + u_wave) * lead_amplitude(lead_index)
+    }
+
+    /// QRS shape of a bundle branch block in one lead, if it differs from normal
+    ///
+    /// Leads follow the standard order (I, II, III, aVR, aVL, aVF, V1-V6).
+    /// In right bundle branch block the right ventricle depolarizes last, adding
+    /// a late R' in V1-V2 and a slurred S wave in the lateral leads; in left
+    /// bundle branch block the whole complex is a broad notched R in the lateral
+    /// leads and a deep broad S in V1-V3.
+    ///
+    /// # Arguments
+    /// * `lead_index` - Lead
+    /// * `qrs_progress` - Progress through the QRS complex (0.0-1.0)
+    fn bundle_branch_morphology(&self, lead_index: usize, qrs_progress: f64) -> Option<f64> {
+        let pi = std::f64::consts::PI;
+        let lead = self.ekg_lead_names().get(lead_index).copied().unwrap_or("");
+        let lateral = matches!(lead, "I" | "aVL" | "V5" | "V6");
+        match self.bundle_branch_block? {
+            BundleBranchBlock::Right if lateral || matches!(lead, "V1" | "V2") => {
+                let early = if qrs_progress < 0.5 { 0.6 * (qrs_progress * 2.0 * pi).sin() } else { 0.0 };
+                let late = if qrs_progress >= 0.5 { ((qrs_progress - 0.5) * 2.0 * pi).sin() } else { 0.0 };
+                Some(early + if lateral { -0.5 * late } else { late })
+            }
+            BundleBranchBlock::Left if lateral => {
+                let notch = 1.0 - 0.3 * (qrs_progress * 2.0 * pi).sin().powi(2);
+                Some(1.2 * (qrs_progress * pi).sin() * notch)
+            }
+            BundleBranchBlock::Left if matches!(lead, "V1" | "V2" | "V3") => Some(-1.2 * (qrs_progress * pi).sin()),
+            _ => None,
+        }
+    }
+
+    /// Solve venous return against the heart's function for the steady-state circulation
+    ///
+    /// Venous return falls as right atrial pressure rises towards the mean systemic
+    /// filling pressure. The right ventricle needs more filling to deliver a given
+    /// flow into a raised pulmonary artery pressure, which in turn rises with the
+    /// left atrial pressure the left ventricle needs: its end-diastolic volume
+    /// comes from the end-diastolic pressure-volume relation, and the stroke
+    /// volume from coupling its end-systolic elastance to the arterial elastance.
+    /// Flow is the point where return and cardiac function meet, capped by what a
+    /// pressure-loaded right ventricle can generate and by the highest left atrial
+    /// pressure the lungs tolerate.
+    ///
+    /// The valves act on every beat: a stenotic valve needs a gradient (Gorlin) to
+    /// pass the stroke volume in the time it is open, which the ventricle behind it
+    /// must generate or the atrium behind it must sustain, and a regurgitant valve
+    /// makes the ventricle eject more than goes forward, with the leaking volume
+    /// raising the pressure in the chamber it returns to.
+    ///
+    /// # Arguments
+    /// * `loading` - Volume, resistances, surrounding pressure and contractility
+    fn solve_circulation(&self, loading: &CirculatoryLoading) -> CirculatoryState {
+        let body_size = loading.body_size;
+        let ratio = loading.stressed_volume_ratio.max(0.0);
+        // Overfilled veins relax, so extra volume raises filling pressure less
+        let mean_filling_pressure = NORMAL_MEAN_SYSTEMIC_FILLING_PRESSURE_MMHG
+            * self.filling_pressure_scale.max(0.0)
+            * if ratio > 1.0 { 1.0 + 0.5 * (ratio - 1.0) } else { ratio };
+        let outside = loading.extracardiac_pressure_mmhg.max(0.0);
+        let heart_rate = self.heart_rate_bpm.max(0.0);
+        if heart_rate <= 0.0 || mean_filling_pressure <= outside {
+            // Without ejection every pressure equalises at the filling pressure; a tense
+            // pericardium stops venous return but cannot push the pressure above it
+            let pressure = mean_filling_pressure;
+            return CirculatoryState {
+                mean_systemic_filling_pressure_mmhg: mean_filling_pressure,
+                cardiac_output_l_min: 0.0,
+                stroke_volume_ml: 0.0,
+                end_diastolic_volume_ml: self.end_diastolic_volume_ml,
+                right_atrial_pressure_mmhg: pressure,
+                left_atrial_pressure_mmhg: pressure,
+                mean_pulmonary_artery_pressure_mmhg: pressure,
+                total_stroke_volume_ml: 0.0,
+                aortic_ejection_ml: 0.0,
+                valve_gradients_mmhg: [0.0; 4],
+                regurgitant_volumes_ml: [0.0; 4],
+            };
+        }
+
+        let pulmonary_resistance = self.pulmonary_vascular_resistance / body_size;
+        let normal_output = NORMAL_STROKE_VOLUME_ML * body_size * 75.0 / 1000.0;
+        let end_systolic_elastance = NORMAL_END_SYSTOLIC_ELASTANCE * loading.contractility.max(0.01) / body_size;
+        let arterial_elastance = loading.systemic_resistance * heart_rate / 1000.0;
+        let unstressed_volume = LV_UNSTRESSED_VOLUME_ML * body_size;
+        // An eccentrically dilated ventricle holds more volume at the same pressure;
+        // a stiff, hypertrophied one holds less and its pressure climbs steeply
+        let diastolic_dysfunction = self.diastolic_dysfunction.clamp(0.0, 1.0);
+        let filling_volume = NORMAL_END_DIASTOLIC_VOLUME_ML
+            * body_size
+            * loading.diastolic_filling.clamp(0.2, 1.0)
+            * (1.0 + 0.8 * self.left_ventricular_dilation.clamp(0.0, 1.0))
+            * (1.0 - 0.3 * diastolic_dysfunction);
+        let diastolic_stiffness = DIASTOLIC_STIFFNESS * (1.0 + 3.0 * diastolic_dysfunction);
+
+        // Each beat divides into ejection, when the semilunar valves are open, and filling
+        let cycle_s = 60.0 / heart_rate;
+        let ejection_time_s = (0.33 * cycle_s.sqrt()).min(0.5 * cycle_s);
+        let filling_time_s = cycle_s - ejection_time_s;
+        let (mitral, tricuspid, aortic, pulmonary) =
+            (&self.mitral_valve, &self.tricuspid_valve, &self.aortic_valve, &self.pulmonary_valve);
+        let (mitral_leak, tricuspid_leak, aortic_leak, pulmonary_leak) = (
+            mitral.regurgitant_fraction(),
+            tricuspid.regurgitant_fraction(),
+            aortic.regurgitant_fraction(),
+            pulmonary.regurgitant_fraction(),
+        );
+        // A dilated atrium takes the same regurgitant jet at a lower pressure
+        let left_atrial_stiffness =
+            LEFT_ATRIAL_STIFFNESS / body_size * (1.0 - 0.7 * self.left_atrial_dilation.clamp(0.0, 1.0));
+        let right_atrial_stiffness = RIGHT_ATRIAL_STIFFNESS / body_size;
+
+        // Left heart: regurgitation makes the ventricle eject more than goes forward
+        let forward_stroke = |output: f64| output * 1000.0 / heart_rate;
+        let left_stroke = |output: f64| forward_stroke(output) / ((1.0 - mitral_leak) * (1.0 - aortic_leak));
+        let aortic_ejection = |output: f64| left_stroke(output) * (1.0 - mitral_leak);
+        let aortic_gradient = |output: f64| aortic.gradient_mmhg(aortic_ejection(output), ejection_time_s, body_size);
+        let end_diastolic_volume = |output: f64| {
+            unstressed_volume
+                + left_stroke(output)
+                + (arterial_elastance * forward_stroke(output) + aortic_gradient(output)) / end_systolic_elastance
+        };
+        let lv_filling_pressure = |output: f64| {
+            starling::end_diastolic_pressure_mmhg(
+                end_diastolic_volume(output),
+                filling_volume,
+                NORMAL_LV_FILLING_PRESSURE_MMHG,
+                diastolic_stiffness,
+            )
         };
+        let mitral_inflow = |output: f64| left_stroke(output) - aortic_ejection(output) * aortic_leak;
+        let mitral_gradient = |output: f64| mitral.gradient_mmhg(mitral_inflow(output), filling_time_s, body_size);
+        let left_atrial_pressure = |output: f64| {
+            outside
+                + lv_filling_pressure(output)
+                + mitral_gradient(output)
+                + 0.5 * left_stroke(output) * mitral_leak * left_atrial_stiffness
+        };
+        let pa_pressure = |output: f64| left_atrial_pressure(output) + output * pulmonary_resistance;
+
+        // Right heart: the same, into the pulmonary artery
+        let right_stroke = |output: f64| forward_stroke(output) / ((1.0 - tricuspid_leak) * (1.0 - pulmonary_leak));
+        let pulmonary_ejection = |output: f64| right_stroke(output) * (1.0 - tricuspid_leak);
+        let pulmonary_gradient =
+            |output: f64| pulmonary.gradient_mmhg(pulmonary_ejection(output), ejection_time_s, body_size);
+        let tricuspid_inflow = |output: f64| right_stroke(output) - pulmonary_ejection(output) * pulmonary_leak;
+        let tricuspid_gradient =
+            |output: f64| tricuspid.gradient_mmhg(tricuspid_inflow(output), filling_time_s, body_size);
+        let ra_pressure_needed = |output: f64| {
+            let right_output = right_stroke(output) * heart_rate / 1000.0;
+            NORMAL_RA_PRESSURE_MMHG
+                * (right_output / (normal_output * loading.right_ventricular_reserve)).powf(1.5)
+                * ((pa_pressure(output) + pulmonary_gradient(output)) / NORMAL_MEAN_PA_PRESSURE_MMHG).max(0.5).powi(2)
+                + tricuspid_gradient(output)
+                + 0.5 * right_stroke(output) * tricuspid_leak * right_atrial_stiffness
+        };
+        let residual = |output: f64| mean_filling_pressure - output * loading.venous_resistance - outside - ra_pressure_needed(output);
+
+        // The right ventricle can only generate so much gradient across the lungs, and
+        // the left heart can only fill to the pressure the lungs tolerate
+        let rv_limit = MAX_ACUTE_RV_GRADIENT_MMHG / pulmonary_resistance.max(1e-6);
+        let lungs_tolerate = |output: f64| left_atrial_pressure(output) - outside <= MAX_LV_FILLING_PRESSURE_MMHG;
+        let limit = if lungs_tolerate(rv_limit) { rv_limit } else { bisect(rv_limit, lungs_tolerate) };
+        let output = if residual(limit) >= 0.0 { limit } else { bisect(limit, |output| residual(output) > 0.0) };
 
-        (p_wave + qrs_complex + t_wave) * amplitude_factor
+        CirculatoryState {
+            mean_systemic_filling_pressure_mmhg: mean_filling_pressure,
+            cardiac_output_l_min: output,
+            stroke_volume_ml: forward_stroke(output),
+            end_diastolic_volume_ml: end_diastolic_volume(output),
+            right_atrial_pressure_mmhg: mean_filling_pressure - output * loading.venous_resistance,
+            left_atrial_pressure_mmhg: left_atrial_pressure(output),
+            mean_pulmonary_artery_pressure_mmhg: pa_pressure(output),
+            total_stroke_volume_ml: left_stroke(output),
+            aortic_ejection_ml: aortic_ejection(output),
+            valve_gradients_mmhg: [
+                mitral_gradient(output),
+                tricuspid_gradient(output),
+                aortic_gradient(output),
+                pulmonary_gradient(output),
+            ],
+            regurgitant_volumes_ml: [
+                left_stroke(output) * mitral_leak,
+                right_stroke(output) * tricuspid_leak,
+                aortic_ejection(output) * aortic_leak,
+                pulmonary_ejection(output) * pulmonary_leak,
+            ],
+        }
+    }
+
+    /// Dilate the left atrium and ventricle over weeks of chronic overload
+    ///
+    /// A raised atrial pressure enlarges the atrium; regurgitant volume and a failing
+    /// myocardium dilate the ventricle, which lets it hold the extra volume at a
+    /// near-normal pressure.
+    ///
+    /// # Arguments
+    /// * `state` - Current circulatory state
+    /// * `body_size` - Blood volume relative to a 5 L adult
+    /// * `delta_time_s` - Time step in seconds
+    fn update_remodeling(&mut self, state: &CirculatoryState, body_size: f64, delta_time_s: f64) {
+        let rate = (delta_time_s / CHAMBER_REMODELING_S).min(1.0);
+        let atrial_target = ((state.left_atrial_pressure_mmhg - 12.0) / 15.0).clamp(0.0, 1.0);
+        let regurgitant_ml = state.regurgitant_volumes_ml[0] + state.regurgitant_volumes_ml[2];
+        let ventricular_target = (regurgitant_ml / (NORMAL_STROKE_VOLUME_ML * body_size))
+            .max(0.3 * self.systolic_dysfunction)
+            .clamp(0.0, 1.0);
+        self.left_atrial_dilation += (atrial_target - self.left_atrial_dilation) * rate;
+        self.left_ventricular_dilation += (ventricular_target - self.left_ventricular_dilation) * rate;
+    }
+}
+
+/// Relative amplitude of the EKG in each lead
+fn lead_amplitude(lead_index: usize) -> f64 {
+    match lead_index {
+        0 => 1.0,
+        1 => 0.8,
+        2 => 0.9,
+        _ => 0.7 + (lead_index as f64 * 0.05),
+    }
+}
+
+/// Bisect for the largest value in `[0, high]` that satisfies a condition true at zero
+///
+/// # Arguments
+/// * `high` - Upper end of the search
+/// * `satisfied` - Condition that holds below the boundary and fails above it
+fn bisect(high: f64, satisfied: impl Fn(f64) -> bool) -> f64 {
+    let (mut low, mut high) = (0.0, high);
+    for _ in 0..40 {
+        let mid = 0.5 * (low + high);
+        if satisfied(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
     }
+    0.5 * (low + high)
 }
 
 impl Organ for Heart {
     fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
-        // Update cardiac cycle
-        self.cardiac_cycle_time += delta_time_s;
-        let cycle_duration = 60.0 / self.heart_rate_bpm;
+        let difficulty = patient.difficulty;
+        self.treatment_responsiveness = difficulty.treatment_responsiveness;
 
-        if self.cardiac_cycle_time >= cycle_duration {
-            self.cardiac_cycle_time = 0.0;
+        // Respond to blood chemistry
+        // High toxins reduce intrinsic heart rate and depress contractility
+        let resting_rate = self.resting_heart_rate_bpm;
+        let toxin = patient.blood.chemistry.toxin_level_au;
+        let (sinus_rate, toxic_depression) = if toxin > 50.0 {
+            (
+                (resting_rate - toxin * 0.1).max(40.0 * resting_rate / 75.0),
+                (1.0 - (toxin - 50.0) * 0.0015).max(0.2),
+            )
+        } else {
+            (resting_rate, 1.0)
+        };
+        // Baroreflex tachycardia compensates for lost (or obstructed) venous return
+        // A failing right ventricle underfills the left heart (obstructive shock)
+        let blood_volume_ml = patient.fluids.blood_volume_ml();
+        let reference_volume_ml = patient.fluids.reference_blood_volume_ml;
+        let blood_volume_ratio = blood_volume_ml / reference_volume_ml;
+        // The stiff pericardium turns a rapid effusion into a steep pressure rise
+        self.pericardial_pressure_mmhg = (3.0 * ((self.pericardial_effusion_ml / PERICARDIAL_RESERVE_ML).exp() - 1.0))
+            .min(MAX_PERICARDIAL_PRESSURE_MMHG);
+        let demand_mmhg = 5.0 * self.pulmonary_vascular_resistance;
+        let rv_output = (MAX_ACUTE_RV_GRADIENT_MMHG / demand_mmhg.max(1.0)).min(1.0);
+        let volume_ratio =
+            blood_volume_ratio * self.venous_return_factor * rv_output * self.pericardial_filling_factor();
+        // Right heart strain is also accompanied by sympathetic tachycardia
+        // Sepsis adds fever and catecholamine-driven tachycardia
+        // Exercise withdraws vagal tone and adds sympathetic drive in proportion to the work
+        // The brainstem's baroreflex and chemoreflexes add or withdraw sympathetic drive
+        let vasodilation = patient.signals.level(Signal::Cytokines).clamp(0.0, 1.0);
+        let exercise = self.exercise_intensity.clamp(0.0, 1.0);
+        let reflex = patient.signals.excess(Signal::SympatheticTone).clamp(-0.5, 1.5);
+        let sinus_rate = sinus_rate
+            + (1.0 - volume_ratio).clamp(0.0, 0.4) * 150.0 * difficulty.physiologic_reserve
+            + reflex * REFLEX_TACHYCARDIA_BPM * resting_rate / 75.0
+            + self.rv_strain * 25.0 * resting_rate / 75.0
+            + vasodilation * 35.0 * resting_rate / 75.0
+            + exercise * EXERCISE_HEART_RATE_RESERVE_BPM * resting_rate / 75.0;
+        // Vagal tone slows the sinus node and a diseased node fires more slowly (sick sinus syndrome)
+        let sinus_rate = sinus_rate
+            * (1.0 - 0.45 * patient.signals.excess(Signal::VagalTone).clamp(-0.5, 1.0))
+            * (0.3 + 0.7 * self.conduction_system.sinoatrial_node.clamp(0.0, 1.0));
+        self.intrinsic_heart_rate_bpm = match self.sinus_rate_override {
+            Some(rate) => rate.min(sinus_rate),
+            None => sinus_rate,
+        };
+
+        // Potassium, calcium and magnesium shape conduction and repolarization
+        let conduction = self.update_electrolytes(&patient.blood);
+        // Atrial arrhythmias drive the ventricles through the AV node: fibrillation
+        // faster than the sinus node would, flutter at 2:1 block and re-entry at a
+        // fixed rapid rate
+        let size_scale = resting_rate / 75.0;
+        let atrial_rate = conduction
+            * match self.atrial_arrhythmia {
+                Some(Rhythm::AtrialFibrillation) => {
+                    (self.intrinsic_heart_rate_bpm * AF_VENTRICULAR_RESPONSE).min(MAX_AV_CONDUCTION_BPM * size_scale)
+                }
+                Some(Rhythm::AtrialFlutter) => FLUTTER_ATRIAL_RATE_BPM / 2.0 * size_scale,
+                Some(_) => SVT_RATE_BPM * size_scale,
+                None => self.intrinsic_heart_rate_bpm,
+            };
+        self.intrinsic_heart_rate_bpm = self.update_conduction(atrial_rate, size_scale);
+        self.update_atrial_thrombus(patient, delta_time_s);
+        self.update_troponin(&mut patient.blood.chemistry, delta_time_s);
+
+        // Resuscitation drugs and the fibrillation waveform
+        self.update_arrest(delta_time_s);
+
+        // Rhythm and pacing
+        self.update_rhythm();
+
+        // Myocardial viability falls without perfusion and slowly recovers with it;
+        // chest compressions slow the loss
+        if self.heart_rate_bpm <= 0.0 {
+            self.time_in_asystole_s += delta_time_s;
+            let compression_perfusion = if self.cpr_in_progress { 0.6 } else { 0.0 };
+            self.myocardial_viability -= difficulty.ischemic_time_s(delta_time_s) / (MYOCARDIAL_ISCHEMIC_TOLERANCE_S * self.ischemic_tolerance_factor.max(0.1))
+                * (1.0 - compression_perfusion);
+        } else {
+            self.time_in_asystole_s = 0.0;
+            let oxygen_factor = (patient.blood.get_sao2_percent() / 98.0).clamp(0.0, 1.0);
+            self.myocardial_viability += (oxygen_factor - 0.9) * delta_time_s / 3600.0;
         }
+        self.myocardial_viability = self.myocardial_viability.clamp(0.0, 1.0);
+
+        // Update cardiac cycle
+        let cycle_progress = if self.heart_rate_bpm > 0.0 {
+            self.cardiac_cycle_time += delta_time_s;
+            let cycle_duration = self.beat_duration_s();
+
+            if self.cardiac_cycle_time >= cycle_duration {
+                self.cardiac_cycle_time = 0.0;
+                if let Some(cycle) = self.dropped_beat_cycle() {
+                    self.block_beat_index = (self.block_beat_index + 1) % cycle;
+                }
+                // An irregular rhythm draws each R-R interval afresh
+                self.rr_jitter = 3.99 * self.rr_jitter * (1.0 - self.rr_jitter);
+                self.beat_length_factor = 1.0 + self.rr_variability * 12f64.sqrt() * (self.rr_jitter - 0.5);
+            }
 
-        let cycle_progress = self.cardiac_cycle_time / cycle_duration;
+            self.cardiac_cycle_time / cycle_duration
+        } else {
+            self.cardiac_cycle_time = 0.0;
+            // Standstill - treat as diastole
+            0.5
+        };
 
         // Atrial systole (0.0 - 0.2)
         if cycle_progress < 0.2 {
@@ -195,8 +2198,8 @@ impl Organ for Heart {
             self.mitral_valve.is_open = true;
             self.tricuspid_valve.is_open = true;
         }
-        // Ventricular systole (0.2 - 0.5)
-        else if cycle_progress < 0.5 {
+        // Ventricular systole (0.2 - 0.5), unless the impulse was blocked
+        else if cycle_progress < 0.5 && self.is_beat_conducted() {
             self.left_atrium.state = ChamberState::Diastole;
             self.right_atrium.state = ChamberState::Diastole;
             self.left_ventricle.state = ChamberState::Systole;
@@ -216,47 +2219,291 @@ impl Organ for Heart {
             self.tricuspid_valve.is_open = true;
         }
 
-        // Calculate pressures
+        // Sympathetic compensation for lost venous return constricts arterioles and
+        // veins and stiffens contraction, as far as the vasomotor centre and reserve allow
+        let sympathetic = ((1.0 - volume_ratio) / 0.4).clamp(0.0, 1.0)
+            * difficulty.physiologic_reserve.min(1.5)
+            * self.vasomotor_tone.clamp(0.0, 1.0);
+        // The adrenal medulla follows the same drive, joined by the stress of sepsis and exercise
+        patient.signals.publish(
+            Signal::Catecholamines,
+            1.0 + sympathetic + reflex.max(0.0) + 0.5 * vasodilation + 2.0 * exercise,
+        );
+
+        // The Cushing reflex raises vascular tone; losing the brainstem's vasomotor centre drops it
+        let neurogenic_scale =
+            (1.0 + self.cushing_response.clamp(0.0, 1.0)) * (0.5 + 0.5 * self.vasomotor_tone.clamp(0.0, 1.0));
+        // Septic vasodilation and metabolic vasodilation in working muscle drop the
+        // resistance the ventricle ejects into; norepinephrine restores
+        // alpha-adrenergic tone and nitroprusside unloads it
+        let norepinephrine = self.norepinephrine_mcg_kg_min.max(0.0) * self.drug_sensitivity();
+        let norepinephrine_effect = norepinephrine / (norepinephrine + 0.1);
+        let nitroprusside = self.nitroprusside_mcg_kg_min.max(0.0) * self.treatment_responsiveness;
+        let nitroprusside_effect = nitroprusside / (nitroprusside + 1.0);
+        let vascular_tone = (1.0 - 0.75 * vasodilation)
+            * (1.0 - EXERCISE_VASODILATION * exercise)
+            * (1.0 + 0.5 * norepinephrine_effect)
+            * (1.0 - 0.5 * nitroprusside_effect)
+            * (1.0 + 0.3 * self.epinephrine_effect());
+        let body_size = reference_volume_ml / STROKE_VOLUME_REFERENCE_BLOOD_VOLUME_ML;
+        self.systemic_vascular_resistance = NORMAL_SYSTEMIC_VASCULAR_RESISTANCE / body_size
+            * self.systemic_pressure_scale
+            * self.systemic_resistance_scale.max(0.0)
+            * neurogenic_scale
+            * vascular_tone
+            * self.peripheral_resistance_factor.clamp(0.3, 3.0)
+            * (1.0 + 0.3 * sympathetic);
+
+        // Venoconstriction and the muscle pump recruit unstressed volume into the
+        // circulation; septic and neurogenic venodilation pool it
+        let unstressed_volume_ml = reference_volume_ml
+            * (UNSTRESSED_VOLUME_FRACTION
+                - VENOCONSTRICTION_RESERVE_FRACTION * (sympathetic + 0.3 * reflex + 0.4 * norepinephrine_effect + exercise)
+                + 0.1 * vasodilation
+                + 0.05 * nitroprusside_effect
+                + 0.1 * (1.0 - self.vasomotor_tone.clamp(0.0, 1.0)));
+        let stressed_volume_ratio =
+            (blood_volume_ml - unstressed_volume_ml) / (reference_volume_ml * (1.0 - UNSTRESSED_VOLUME_FRACTION));
+
+        // Segmental wall motion, surviving myocardium, toxins, inotropes and severe
+        // acidaemia set the contractile state
+        let acidaemia = (1.0 - 1.5 * (7.2 - patient.blood.get_ph()).max(0.0)).clamp(0.4, 1.0);
+        self.contractility = self.segmental_contractility()
+            * (0.3 + 0.7 * self.myocardial_viability)
+            * (1.0 - 0.8 * self.systolic_dysfunction.clamp(0.0, 1.0))
+            * toxic_depression
+            * acidaemia
+            * (1.0
+                + 0.5 * self.epinephrine_effect()
+                + 0.2 * norepinephrine_effect
+                + 0.2 * (sympathetic + reflex)
+                + 0.4 * exercise);
+
+        // Rapid rates shorten diastole and limit filling, though the faster relaxation
+        // of exercise keeps filling up at rates that would otherwise limit it
+        let relative_rate = self.heart_rate_bpm / (resting_rate * (1.0 + exercise)).max(1.0);
+        let diastolic_filling = (1.0 - 0.35 * (relative_rate - 1.5).max(0.0)).clamp(0.4, 1.0);
+        // Without coordinated atrial systole the ventricle loses the atrial kick,
+        // which a stiff ventricle depends on most
+        let atrial_kick_lost = match self.atrial_arrhythmia {
+            Some(_) if self.heart_rate_bpm > 0.0 => {
+                ATRIAL_KICK_FRACTION * (1.0 + self.diastolic_dysfunction.clamp(0.0, 1.0))
+            }
+            _ => 0.0,
+        };
+        let diastolic_filling = diastolic_filling * (1.0 - atrial_kick_lost);
+        // Raised intrathoracic pressure compresses the heart and great veins; the
+        // pericardium does the same when it fills
+        let intrathoracic_mmhg = 12.0 * (1.0 - self.venous_return_factor).clamp(0.0, 1.0);
+        let loading = CirculatoryLoading {
+            body_size,
+            stressed_volume_ratio,
+            systemic_resistance: self.systemic_vascular_resistance,
+            venous_resistance: VENOUS_RETURN_RESISTANCE / body_size
+                * (1.0 - 0.5 * vasodilation)
+                * (1.0 - EXERCISE_VASODILATION * exercise)
+                / self.venous_return_factor.clamp(0.05, 1.0),
+            extracardiac_pressure_mmhg: self.pericardial_pressure_mmhg.max(intrathoracic_mmhg),
+            contractility: self.contractility,
+            diastolic_filling,
+            right_ventricular_reserve: 1.0 + EXERCISE_RV_RESERVE * exercise,
+        };
+        for valve in [
+            &mut self.mitral_valve,
+            &mut self.tricuspid_valve,
+            &mut self.aortic_valve,
+            &mut self.pulmonary_valve,
+        ] {
+            valve.degenerate(delta_time_s);
+        }
+        let state = self.solve_circulation(&loading);
+        for (valve, (gradient, regurgitant)) in [
+            &mut self.mitral_valve,
+            &mut self.tricuspid_valve,
+            &mut self.aortic_valve,
+            &mut self.pulmonary_valve,
+        ]
+        .into_iter()
+        .zip(state.valve_gradients_mmhg.into_iter().zip(state.regurgitant_volumes_ml))
+        {
+            valve.mean_gradient_mmhg = gradient;
+            valve.regurgitant_volume_ml = regurgitant;
+        }
+        self.update_remodeling(&state, body_size, delta_time_s);
+        self.mean_systemic_filling_pressure_mmhg = state.mean_systemic_filling_pressure_mmhg;
+        self.cardiac_output_l_min = state.cardiac_output_l_min;
+        self.stroke_volume_ml = state.stroke_volume_ml;
+        self.end_diastolic_volume_ml = state.end_diastolic_volume_ml;
+        // Ejection fraction counts everything the ventricle ejects, forward or regurgitant
+        if state.total_stroke_volume_ml > 0.0 {
+            self.ejection_fraction_percent = 100.0 * state.total_stroke_volume_ml / state.end_diastolic_volume_ml;
+        }
+        self.left_atrium.pressure_mmhg = state.left_atrial_pressure_mmhg;
+        self.right_atrium.pressure_mmhg = state.right_atrial_pressure_mmhg;
+        self.mean_pulmonary_artery_pressure = state.mean_pulmonary_artery_pressure_mmhg;
+        self.rv_strain = ((self.mean_pulmonary_artery_pressure - 20.0) / 20.0).clamp(0.0, 1.0);
+        self.right_ventricle.volume_ml = 120.0 * body_size * (1.0 + 0.6 * self.rv_strain);
+        self.right_ventricle.pressure_mmhg = if self.right_ventricle.state == ChamberState::Systole {
+            self.mean_pulmonary_artery_pressure * 1.6 + 1.3 * self.pulmonary_valve.mean_gradient_mmhg
+        } else {
+            self.right_atrium.pressure_mmhg
+        };
+
+        // Mean pressure is flow times resistance; the arteries' compliance sets the pulse
+        // pressure from the volume ejected into them. Aortic regurgitation widens it as the
+        // leak drains the diastolic pressure; a stenotic valve slows the upstroke and narrows it,
+        // and the brisk ejection of exercise widens it. Constricted arteries are stiffer, so
+        // vasopressors widen the pulse pressure for the same stroke volume
+        let mean_pressure = state.right_atrial_pressure_mmhg + state.cardiac_output_l_min * self.systemic_vascular_resistance;
+        let pulse_pressure = state.aortic_ejection_ml * self.systemic_pressure_scale.max(0.1)
+            / (ARTERIAL_COMPLIANCE_ML_PER_MMHG * body_size)
+            * vascular_tone.max(0.0).sqrt()
+            * (1.0 - 0.5 * self.aortic_valve.stenosis_severity.clamp(0.0, 1.0))
+            * (1.0 + exercise);
+        self.aortic_pressure_systolic = mean_pressure + pulse_pressure * 2.0 / 3.0;
+        self.aortic_pressure_diastolic = (mean_pressure - pulse_pressure / 3.0).max(state.right_atrial_pressure_mmhg);
+
+        // Organised activity that moves too little blood forward to be felt is PEA
+        if self.ventricular_arrhythmia.is_none()
+            && self.heart_rate_bpm > 0.0
+            && state.stroke_volume_ml < PULSELESS_STROKE_VOLUME_ML * body_size
+        {
+            self.rhythm = Rhythm::PulselessElectricalActivity;
+        }
+        if self.cpr_in_progress && self.is_in_cardiac_arrest() {
+            // Compression pressures are mechanical, not maintained by the vasomotor centre
+            self.apply_chest_compressions(volume_ratio, body_size, self.systemic_pressure_scale * vascular_tone);
+        }
+
+        // The ventricle must exceed aortic pressure by the gradient across a stenotic valve
         if self.left_ventricle.state == ChamberState::Systole {
-            self.aortic_pressure_systolic = 100.0 + self.ejection_fraction_percent * 0.5;
-            self.left_ventricle.pressure_mmhg = self.aortic_pressure_systolic;
+            self.left_ventricle.pressure_mmhg = self.aortic_pressure_systolic + 1.3 * self.aortic_valve.mean_gradient_mmhg;
+            self.left_ventricle.volume_ml = self.end_diastolic_volume_ml - state.total_stroke_volume_ml;
         } else {
-            self.aortic_pressure_diastolic = 70.0 + self.ejection_fraction_percent * 0.2;
-            self.left_ventricle.pressure_mmhg = 8.0;
+            self.left_ventricle.pressure_mmhg = self.left_atrium.pressure_mmhg;
+            self.left_ventricle.volume_ml = self.end_diastolic_volume_ml;
         }
 
         // Update patient blood pressure
         patient.blood.blood_pressure_systolic = self.aortic_pressure_systolic;
         patient.blood.blood_pressure_diastolic = self.aortic_pressure_diastolic;
 
-        // Generate EKG data
-        let num_leads = self.ekg_leads.len();
-        let ekg_values: Vec<f64> = (0..num_leads).map(|i| self.generate_ekg(i)).collect();
-        for (lead, &ekg_value) in self.ekg_leads.iter_mut().zip(ekg_values.iter()) {
-            lead.push_back(ekg_value);
-            if lead.len() > 1000 {
-                lead.pop_front();
-            }
+        // Generate EKG data at the fixed sampling rate across the step
+        if !patient.organ_fidelity(self.get_type()).generates_waveforms() {
+            self.ekg_sampler.skip(delta_time_s);
+            return;
         }
-
-        // Respond to blood chemistry
-        // High toxins reduce heart rate and ejection fraction
-        if patient.blood.chemistry.toxin_level_au > 50.0 {
-            self.heart_rate_bpm = (75.0 - patient.blood.chemistry.toxin_level_au * 0.1).max(40.0);
-            self.ejection_fraction_percent = (60.0 - patient.blood.chemistry.toxin_level_au * 0.05).max(30.0);
-        } else {
-            self.heart_rate_bpm = 75.0;
-            self.ejection_fraction_percent = 60.0;
+        for lookback in self.ekg_sampler.step(delta_time_s) {
+            for lead_index in 0..self.ekg_leads.len() {
+                let ekg_value = self.generate_ekg(lead_index, lookback);
+                self.ekg_leads[lead_index].push(ekg_value);
+            }
         }
     }
 
     fn get_summary(&self) -> String {
+        let pacing = match &self.pacemaker {
+            Some(pacemaker) if pacemaker.is_on => format!(
+                ", Pacer={:?} {:.0} bpm @ {:.0} mA ({})",
+                pacemaker.mode,
+                pacemaker.rate_bpm,
+                pacemaker.output_ma,
+                if pacemaker.is_capturing { "capture" } else { "no capture" }
+            ),
+            _ => String::new(),
+        };
+        let rv_strain = if self.rv_strain > 0.1 {
+            format!(", RV strain={:.0}% (mPAP {:.0} mmHg)", self.rv_strain * 100.0, self.mean_pulmonary_artery_pressure)
+        } else {
+            String::new()
+        };
+        let norepinephrine = if self.norepinephrine_mcg_kg_min > 0.0 {
+            format!(", Norepinephrine={:.2} mcg/kg/min", self.norepinephrine_mcg_kg_min)
+        } else {
+            String::new()
+        };
+        let nitroprusside = if self.nitroprusside_mcg_kg_min > 0.0 {
+            format!(", Nitroprusside={:.1} mcg/kg/min", self.nitroprusside_mcg_kg_min)
+        } else {
+            String::new()
+        };
+        let heart_failure = match (self.systolic_dysfunction > 0.1, self.diastolic_dysfunction > 0.1) {
+            (false, false) => String::new(),
+            (reduced, _) => format!(
+                ", Heart failure with {} EF (LA {:.0} mmHg)",
+                if reduced { "reduced" } else { "preserved" },
+                self.left_atrium.pressure_mmhg
+            ),
+        };
+        let effusion = if self.pericardial_effusion_ml > 20.0 {
+            format!(
+                ", Pericardial effusion={:.0} mL ({:.0} mmHg)",
+                self.pericardial_effusion_ml, self.pericardial_pressure_mmhg
+            )
+        } else {
+            String::new()
+        };
+        let conduction_block = match (self.av_block, self.bundle_branch_block) {
+            (AvBlock::None, None) => String::new(),
+            (AvBlock::None, Some(bundle)) => format!(", {:?} bundle branch block", bundle),
+            (av_block, None) => format!(", AV block={:?}", av_block),
+            (av_block, Some(bundle)) => format!(", AV block={:?}, {:?} bundle branch block", av_block, bundle),
+        };
+        let intervals = if self.qrs_duration_ms > 110.0
+            || self.pr_interval_ms > 200.0
+            || self.qtc_ms > 460.0
+            || self.t_wave_amplitude_mv > 0.5
+        {
+            format!(
+                ", PR={:.0} ms, QRS={:.0} ms, QTc={:.0} ms, T={:.1} mV",
+                self.pr_interval_ms, self.qrs_duration_ms, self.qtc_ms, self.t_wave_amplitude_mv
+            )
+        } else {
+            String::new()
+        };
+        let valve_disease: String = [&self.mitral_valve, &self.tricuspid_valve, &self.aortic_valve, &self.pulmonary_valve]
+            .iter()
+            .filter_map(|valve| match (valve.stenosis_severity > 0.2, valve.regurgitation_severity > 0.2) {
+                (false, false) => None,
+                (stenotic, regurgitant) => Some(format!(
+                    ", {} {}{}{} (gradient {:.0} mmHg, regurgitant {:.0} mL)",
+                    valve.name,
+                    if stenotic { "stenosis" } else { "" },
+                    if stenotic && regurgitant { "/" } else { "" },
+                    if regurgitant { "regurgitation" } else { "" },
+                    valve.mean_gradient_mmhg,
+                    valve.regurgitant_volume_ml
+                )),
+            })
+            .collect();
+        let thrombus = if self.left_atrial_thrombus > 0.1 {
+            format!(", Left atrial thrombus={:.0}%", self.left_atrial_thrombus * 100.0)
+        } else {
+            String::new()
+        };
+        let wall_motion = match self.hypokinetic_segment_count() {
+            0 => String::new(),
+            count => format!(", Wall motion abnormality in {}/{} segments", count, self.myocardial_segments.len()),
+        };
         format!(
-            "Heart: HR={:.0} bpm, EF={:.0}%, BP={:.0}/{:.0} mmHg",
+            "Heart: Rhythm={:?}, HR={:.0} bpm, EF={:.0}%, CO={:.1} L/min, BP={:.0}/{:.0} mmHg{}{}{}{}{}{}{}{}{}{}{}",
+            self.rhythm,
             self.heart_rate_bpm,
             self.ejection_fraction_percent,
+            self.cardiac_output_l_min,
             self.aortic_pressure_systolic,
-            self.aortic_pressure_diastolic
+            self.aortic_pressure_diastolic,
+            pacing,
+            rv_strain,
+            norepinephrine,
+            nitroprusside,
+            heart_failure,
+            effusion,
+            conduction_block,
+            intervals,
+            valve_disease,
+            thrombus,
+            wall_motion
         )
     }
 
@@ -275,4 +2522,337 @@ impl Organ for Heart {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::lungs::Lungs;
+    use crate::organs::vascular::VascularSystem;
+    use crate::patient::{initialize_patient, update_patient};
+
+    fn heart(patient: &Patient) -> &Heart {
+        patient.get_organ::<Heart>().unwrap()
+    }
+
+    fn heart_mut(patient: &mut Patient) -> &mut Heart {
+        patient.get_organ_mut::<Heart>().unwrap()
+    }
+
+    fn lungs(patient: &Patient) -> &Lungs {
+        patient.get_organ::<Lungs>().unwrap()
+    }
+
+    /// R-R intervals over 30 s of a rhythm, recorded in fine steps (ms)
+    fn rr_intervals(induce: fn(&mut Heart)) -> (Patient, Vec<f64>) {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        induce(heart_mut(&mut patient));
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        let mut beats = Vec::new();
+        let mut cycle_time = 0.0;
+        for step in 0..1500 {
+            update_patient(&mut patient, 0.02);
+            if heart(&patient).cardiac_cycle_time < cycle_time {
+                beats.push(step as f64 * 20.0);
+            }
+            cycle_time = heart(&patient).cardiac_cycle_time;
+        }
+        let intervals = beats.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        (patient, intervals)
+    }
+
+    #[test]
+    fn test_atrial_arrhythmias_set_the_ventricular_rate_and_regularity() {
+        let spread = |intervals: &[f64]| {
+            intervals.iter().copied().fold(f64::MIN, f64::max) - intervals.iter().copied().fold(f64::MAX, f64::min)
+        };
+        let (sinus, intervals) = rr_intervals(|_| {});
+        let sinus_rate = heart(&sinus).heart_rate_bpm;
+        assert_eq!(spread(&intervals), 0.0);
+
+        // Fibrillation conducts faster than the sinus node, irregularly irregular
+        let (fibrillating, intervals) = rr_intervals(Heart::induce_atrial_fibrillation);
+        assert_eq!(heart(&fibrillating).rhythm, Rhythm::AtrialFibrillation);
+        assert!(heart(&fibrillating).heart_rate_bpm > 1.3 * sinus_rate);
+        assert!(spread(&intervals) > 200.0);
+
+        // Flutter conducts 2:1 at a fixed 150 and re-entrant SVT runs regularly at 180
+        let (flutter, intervals) = rr_intervals(Heart::induce_atrial_flutter);
+        assert_eq!(heart(&flutter).rhythm, Rhythm::AtrialFlutter);
+        assert!((heart(&flutter).heart_rate_bpm - 150.0).abs() < 1.0);
+        assert_eq!(spread(&intervals), 0.0);
+        let (svt, intervals) = rr_intervals(Heart::induce_supraventricular_tachycardia);
+        assert_eq!(heart(&svt).rhythm, Rhythm::SupraventricularTachycardia);
+        assert!((heart(&svt).heart_rate_bpm - 180.0).abs() < 1.0);
+        assert!(spread(&intervals) <= 20.0);
+    }
+
+    #[test]
+    fn test_cardioversion_dislodges_a_left_atrial_thrombus_into_the_brain() {
+        let mut patient = initialize_patient(1, 3);
+        heart_mut(&mut patient).induce_atrial_fibrillation();
+        update_patient(&mut patient, 1.0);
+        assert!(heart(&patient).embolic_stroke_risk_per_year() > 0.0);
+
+        heart_mut(&mut patient).left_atrial_thrombus = 0.8;
+        heart_mut(&mut patient).restore_sinus_rhythm();
+        update_patient(&mut patient, 1.0);
+        assert_eq!(heart(&patient).atrial_arrhythmia(), None);
+        assert_eq!(heart(&patient).left_atrial_thrombus, 0.0);
+        let vascular = patient.get_organ::<VascularSystem>().unwrap();
+        assert!(vascular.vessel("Middle Cerebral Artery (L)").unwrap().occlusion > 0.5);
+    }
+
+    #[test]
+    fn test_pacemaker_captures_an_asystolic_heart_above_threshold() {
+        let mut patient = initialize_patient(1, 3);
+        heart_mut(&mut patient).induce_asystole();
+        update_patient(&mut patient, 1.0);
+        assert_eq!(heart(&patient).rhythm, Rhythm::Asystole);
+        assert_eq!(heart(&patient).heart_rate_bpm, 0.0);
+
+        // Pads above threshold capture every beat at the programmed rate
+        heart_mut(&mut patient).attach_pacemaker(Pacemaker::new(PacingMode::Transcutaneous, 70.0, 80.0));
+        for _ in 0..10 {
+            update_patient(&mut patient, 1.0);
+        }
+        assert_eq!(heart(&patient).rhythm, Rhythm::Paced);
+        assert!(heart(&patient).pacemaker.as_ref().unwrap().is_capturing);
+        assert_eq!(heart(&patient).heart_rate_bpm, 70.0);
+
+        // Below threshold the pulses fail to capture and the heart is asystolic again
+        heart_mut(&mut patient).pacemaker.as_mut().unwrap().output_ma = 40.0;
+        update_patient(&mut patient, 1.0);
+        assert!(!heart(&patient).pacemaker.as_ref().unwrap().is_capturing);
+        assert_eq!(heart(&patient).rhythm, Rhythm::Asystole);
+        assert_eq!(heart(&patient).heart_rate_bpm, 0.0);
+    }
+
+    #[test]
+    fn test_demand_pacemaker_senses_an_adequate_intrinsic_rate() {
+        let mut patient = initialize_patient(1, 3);
+        heart_mut(&mut patient).attach_pacemaker(Pacemaker::new(PacingMode::Transvenous, 50.0, 5.0));
+        for _ in 0..10 {
+            update_patient(&mut patient, 1.0);
+        }
+        let intrinsic_rate = heart(&patient).heart_rate_bpm;
+        assert!(intrinsic_rate > 50.0);
+        assert!(!heart(&patient).pacemaker.as_ref().unwrap().is_capturing);
+        assert_ne!(heart(&patient).rhythm, Rhythm::Paced);
+
+        // Bradycardia below the set rate is sensed and paced
+        heart_mut(&mut patient).induce_bradycardia(35.0);
+        update_patient(&mut patient, 1.0);
+        assert_eq!(heart(&patient).rhythm, Rhythm::Paced);
+        assert_eq!(heart(&patient).heart_rate_bpm, 50.0);
+
+        // Fixed-rate (asynchronous) pacing competes with the intrinsic rhythm regardless
+        heart_mut(&mut patient).induce_bradycardia(59.0);
+        let pacemaker = heart_mut(&mut patient).pacemaker.as_mut().unwrap();
+        pacemaker.rate_bpm = 40.0;
+        pacemaker.demand_mode = false;
+        update_patient(&mut patient, 1.0);
+        assert!(heart(&patient).pacemaker.as_ref().unwrap().is_capturing);
+        assert_eq!(heart(&patient).heart_rate_bpm, 40.0);
+    }
+
+    #[test]
+    fn test_electrolytes_shape_the_ekg_and_extremes_end_in_arrest() {
+        let electrolytes = |potassium: f64, calcium: f64, magnesium: f64| {
+            let blood = BloodComposition {
+                chemistry: BloodChemistry {
+                    potassium_meq_l: potassium,
+                    calcium_mg_dl: calcium,
+                    magnesium_mg_dl: magnesium,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let mut heart = Heart::new(0, 12);
+            let conduction = heart.update_electrolytes(&blood);
+            (heart, conduction)
+        };
+        let (normal, conduction) = electrolytes(4.0, 9.5, 2.0);
+        assert_eq!(conduction, 1.0);
+        assert_eq!(normal.qrs_duration_ms, NORMAL_QRS_DURATION_MS);
+        assert_eq!(normal.ventricular_arrhythmia, None);
+
+        // Hyperkalemia peaks the T wave, then flattens the P wave and widens the QRS
+        let (hyperkalemic, conduction) = electrolytes(7.5, 9.5, 2.0);
+        assert!(hyperkalemic.t_wave_amplitude_mv > 2.0 * normal.t_wave_amplitude_mv);
+        assert!(hyperkalemic.qrs_duration_ms > 120.0);
+        assert!(hyperkalemic.p_wave_amplitude_mv < normal.p_wave_amplitude_mv);
+        assert!(conduction < 1.0);
+        // Calcium stabilises the membrane and narrows the QRS again
+        let (stabilised, _) = electrolytes(7.5, 12.0, 2.0);
+        assert!(stabilised.qrs_duration_ms < hyperkalemic.qrs_duration_ms);
+        assert_eq!(electrolytes(9.5, 9.5, 2.0).0.ventricular_arrhythmia, Some(Rhythm::VentricularFibrillation));
+        assert_eq!(electrolytes(10.5, 9.5, 2.0).1, 0.0);
+
+        // Hypokalemia brings U waves; with low calcium and magnesium the QT lengthens until torsades
+        let (hypokalemic, _) = electrolytes(2.5, 9.5, 2.0);
+        assert!(hypokalemic.u_wave_amplitude_mv > 0.0);
+        assert!(hypokalemic.qtc_ms > normal.qtc_ms);
+        assert_eq!(electrolytes(2.0, 7.0, 0.8).0.ventricular_arrhythmia, Some(Rhythm::TorsadesDePointes));
+    }
+
+    #[test]
+    fn test_heart_failure_congests_the_lungs_until_diuresed_and_unloaded() {
+        let failing = |phenotype: HeartFailurePhenotype| {
+            let mut patient = initialize_patient(1, 3);
+            for _ in 0..60 {
+                update_patient(&mut patient, 1.0);
+            }
+            patient.induce_heart_failure(phenotype, 1.0);
+            for _ in 0..180 {
+                update_patient(&mut patient, 10.0);
+            }
+            patient
+        };
+
+        // A weak ventricle and a stiff one both need high filling pressures; only one ejects poorly
+        let mut reduced = failing(HeartFailurePhenotype::ReducedEjectionFraction);
+        let preserved = failing(HeartFailurePhenotype::PreservedEjectionFraction);
+        assert!(heart(&reduced).ejection_fraction_percent < 35.0);
+        assert!(heart(&preserved).ejection_fraction_percent > 50.0);
+        assert!(heart(&reduced).left_atrium.pressure_mmhg > 18.0);
+        assert!(heart(&preserved).left_atrium.pressure_mmhg > 18.0);
+
+        // Pulmonary edema stiffens the lungs into rapid, shallow breathing; retained fluid swells the legs
+        let congested = lungs(&reduced);
+        let alveolar_edema = congested.alveolar_edema;
+        assert!(alveolar_edema > 0.1);
+        assert!(congested.respiration_rate_bpm > 16.0);
+        assert!(congested.tidal_volume_ml < congested.resting_tidal_volume_ml);
+        let peripheral_edema = reduced.fluids.peripheral_edema_ml();
+        assert!(peripheral_edema > 1000.0);
+
+        // Furosemide and nitroprusside lower the filling pressure and the edema clears
+        let left_atrial = heart(&reduced).left_atrium.pressure_mmhg;
+        reduced.give_furosemide(80.0);
+        reduced.set_nitroprusside_infusion(1.0);
+        for _ in 0..360 {
+            update_patient(&mut reduced, 10.0);
+        }
+        assert!(heart(&reduced).left_atrium.pressure_mmhg < left_atrial - 3.0);
+        assert!(lungs(&reduced).alveolar_edema < alveolar_edema);
+        assert!(reduced.fluids.peripheral_edema_ml() < peripheral_edema - 300.0);
+    }
+
+    /// Run a patient for 20 s in fine steps with part of the conduction system damaged
+    ///
+    /// # Returns
+    /// The patient and each distinct PR interval seen, in order
+    fn run_with_conduction_damage(segment: ConductionSegment, severity: f64) -> (Patient, Vec<f64>) {
+        let mut patient = initialize_patient(1, 3);
+        patient.get_organ_mut::<Heart>().unwrap().damage_conduction(segment, severity);
+        let mut pr_intervals: Vec<f64> = Vec::new();
+        for _ in 0..1000 {
+            update_patient(&mut patient, 0.02);
+            let pr_interval_ms = heart(&patient).pr_interval_ms.round();
+            if pr_intervals.last() != Some(&pr_interval_ms) {
+                pr_intervals.push(pr_interval_ms);
+            }
+        }
+        (patient, pr_intervals)
+    }
+
+    /// Number of R waves in lead II over the last 10 s of the recording, one sample per 0.02 s update
+    fn r_waves(heart: &Heart) -> usize {
+        let samples: Vec<f64> = heart.ekg_leads[1].iter().collect();
+        let window = 500;
+        samples[samples.len() - window..].windows(2).filter(|pair| pair[0] < 0.5 && pair[1] >= 0.5).count()
+    }
+
+    #[test]
+    fn test_av_block_prolongs_the_pr_and_drops_beats() {
+        let (normal, pr_intervals) = run_with_conduction_damage(ConductionSegment::AtrioventricularNode, 0.0);
+        assert_eq!(heart(&normal).av_block, AvBlock::None);
+        assert_eq!(pr_intervals, vec![NORMAL_PR_INTERVAL_MS]);
+        let normal_beats = r_waves(heart(&normal));
+
+        // First degree: every impulse conducted, late
+        let (first_degree, pr_intervals) = run_with_conduction_damage(ConductionSegment::AtrioventricularNode, 0.25);
+        assert_eq!(heart(&first_degree).av_block, AvBlock::FirstDegree);
+        assert!(pr_intervals.len() == 1 && pr_intervals[0] > 200.0);
+        assert_eq!(r_waves(heart(&first_degree)), normal_beats);
+
+        // Mobitz I: the PR lengthens beat by beat, then one P wave goes unanswered
+        let (wenckebach, pr_intervals) = run_with_conduction_damage(ConductionSegment::AtrioventricularNode, 0.5);
+        assert_eq!(heart(&wenckebach).av_block, AvBlock::MobitzI);
+        assert!(pr_intervals[..4].windows(2).all(|pair| pair[1] > pair[0]));
+        assert_eq!(pr_intervals[4], pr_intervals[0]);
+        let beats = r_waves(heart(&wenckebach));
+        assert!(beats < normal_beats && beats >= normal_beats * 3 / 4 - 1);
+
+        // Mobitz II: beats dropped below the node with a fixed PR
+        let (mobitz_ii, pr_intervals) = run_with_conduction_damage(ConductionSegment::HisBundle, 0.5);
+        assert_eq!(heart(&mobitz_ii).av_block, AvBlock::MobitzII);
+        assert_eq!(pr_intervals, vec![NORMAL_PR_INTERVAL_MS]);
+        assert!(r_waves(heart(&mobitz_ii)) <= normal_beats * 3 / 4);
+
+        // Complete block: the ventricles follow a slow escape rhythm of their own
+        let (complete, _) = run_with_conduction_damage(ConductionSegment::AtrioventricularNode, 0.8);
+        assert_eq!(heart(&complete).av_block, AvBlock::ThirdDegree);
+        assert_eq!(heart(&complete).rhythm, Rhythm::CompleteHeartBlock);
+        assert!(r_waves(heart(&complete)) <= normal_beats * 2 / 3);
+    }
+
+    #[test]
+    fn test_bundle_branch_block_widens_the_qrs() {
+        let (normal, _) = run_with_conduction_damage(ConductionSegment::RightBundleBranch, 0.0);
+        assert!(heart(&normal).qrs_duration_ms < 120.0);
+        for (segment, block) in [
+            (ConductionSegment::RightBundleBranch, BundleBranchBlock::Right),
+            (ConductionSegment::LeftBundleBranch, BundleBranchBlock::Left),
+        ] {
+            let (patient, pr_intervals) = run_with_conduction_damage(segment, 0.5);
+            let blocked = heart(&patient);
+            assert_eq!(blocked.bundle_branch_block, Some(block));
+            assert!(blocked.qrs_duration_ms >= 120.0);
+            // Conduction to the ventricles is delayed, not interrupted
+            assert_eq!(blocked.av_block, AvBlock::None);
+            assert_eq!(pr_intervals, vec![NORMAL_PR_INTERVAL_MS]);
+            assert_eq!(r_waves(blocked), r_waves(heart(&normal)));
+        }
+    }
+
+    #[test]
+    fn test_growing_effusion_tamponades_steadily_into_pea() {
+        let mut rested = initialize_patient(1, 3);
+        update_patient(&mut rested, 60.0);
+
+        let mut previous_map = f64::INFINITY;
+        let mut previous_output = f64::INFINITY;
+        for effusion_ml in [0.0, 100.0, 150.0, 200.0, 300.0, 600.0, 1000.0] {
+            let mut patient = rested.fork();
+            heart_mut(&mut patient).add_pericardial_effusion(effusion_ml);
+            for _ in 0..120 {
+                update_patient(&mut patient, 1.0);
+            }
+            let map = patient.blood.get_mean_arterial_pressure();
+            let heart = heart(&patient);
+            assert!(map <= previous_map + 0.5, "{effusion_ml} mL: MAP {previous_map} -> {map}");
+            assert!(heart.cardiac_output_l_min <= previous_output + 0.05);
+            previous_map = map;
+            previous_output = heart.cardiac_output_l_min;
+
+            // A tense pericardium stops venous return but cannot pressurise the circulation
+            assert!(heart.pericardial_pressure_mmhg <= MAX_PERICARDIAL_PRESSURE_MMHG);
+            assert!(heart.right_atrium.pressure_mmhg <= heart.mean_systemic_filling_pressure_mmhg + 1e-6);
+            if effusion_ml >= 200.0 {
+                assert_eq!(heart.rhythm, Rhythm::PulselessElectricalActivity, "{effusion_ml} mL");
+                assert!(map <= heart.mean_systemic_filling_pressure_mmhg + 1e-6, "{effusion_ml} mL: MAP {map}");
+            }
+        }
+    }
 }
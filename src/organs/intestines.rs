@@ -1,7 +1,101 @@
 //! Intestines organ simulation
+//!
+//! Chyme from the stomach is absorbed from the small bowel at rates set by its
+//! composition: sugars within minutes, protein over about an hour and fat over
+//! several. The absorbed load releases incretins that amplify the insulin
+//! response to the meal.
+//!
+//! The mucosa is fed by the superior mesenteric artery and is among the first
+//! tissues sacrificed in shock. Prolonged hypoperfusion paralyses the bowel
+//! (ileus), makes lactate, and breaks down the mucosal barrier so gut bacteria
+//! translocate into the blood; transmural necrosis perforates into the
+//! peritoneum as a late complication.
+//!
+//! Blood from an upper GI bleed is digested on its way through, appearing as
+//! melena and raising the BUN out of proportion to creatinine; a lower GI
+//! source passes as bright red stool.
 
+use crate::coagulation::Coagulation;
 use crate::organ::{Organ, OrganId};
+use crate::organs::stomach::Meal;
 use crate::patient::Patient;
+use crate::sepsis::BundleGuideline;
+use crate::signals::Signal;
+use crate::tissue::{TissuePerfusion, TissueState};
+
+/// Urea nitrogen generated from each mL of upper GI blood digested and
+/// absorbed (half of the ~24 mg of hemoglobin and plasma protein nitrogen) (mg)
+pub const UREA_NITROGEN_MG_PER_BLOOD_ML: f64 = 12.0;
+
+/// Bleeding rate from the most severe lower GI source at normal blood pressure (mL/min)
+const LOWER_GI_BLEED_ML_MIN: f64 = 8.0;
+
+/// Time constant of clot formation over a lower GI source with normal coagulation (s)
+const LOWER_GI_CLOT_S: f64 = 3600.0;
+
+/// Absorption time constant of carbohydrate from the small bowel (s)
+const CARBOHYDRATE_ABSORPTION_S: f64 = 900.0;
+
+/// Absorption time constant of protein from the small bowel (s)
+const PROTEIN_ABSORPTION_S: f64 = 3600.0;
+
+/// Absorption time constant of fat, packaged into chylomicrons (s)
+const FAT_ABSORPTION_S: f64 = 7200.0;
+
+/// Absorption time constant of water and sodium from the small bowel (s)
+const WATER_ABSORPTION_S: f64 = 1200.0;
+
+/// Energy absorbed per minute that doubles the incretin level (kcal/min)
+const INCRETIN_KCAL_MIN: f64 = 3.0;
+
+/// Lactate released by completely ischemic bowel (mmol/L per minute)
+const ISCHEMIC_GUT_LACTATE_MMOL_L_MIN: f64 = 0.1;
+
+/// Mucosal injury that lets gut bacteria translocate into the blood
+const TRANSLOCATION_INJURY: f64 = 0.1;
+
+/// Bacterial load seeded per second by a fully breached mucosa
+const TRANSLOCATION_LOAD_PER_S: f64 = 1e-5;
+
+/// Mucosal injury at which necrosis becomes transmural and perforates
+const TRANSMURAL_NECROSIS_INJURY: f64 = 0.5;
+
+/// Growth rate of the mixed faecal flora of peritonitis relative to a typical pathogen
+const PERITONITIS_VIRULENCE: f64 = 2.0;
+
+/// Stool character as charted by nursing staff
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StoolCharacter {
+    Formed,
+    Loose,
+    Watery,
+    /// Black, tarry stool from digested blood (upper GI bleed)
+    Melena,
+    /// Bright red blood per rectum (lower GI bleed)
+    Bloody,
+}
+
+/// A single bowel movement
+#[derive(Debug, Clone)]
+pub struct StoolEvent {
+    /// Time since the intestines were created (s)
+    pub time_s: f64,
+    /// Total stool volume (mL)
+    pub volume_ml: f64,
+    /// Blood contained in the stool (mL)
+    pub blood_ml: f64,
+    /// Stool character
+    pub character: StoolCharacter,
+}
+
+/// Level of a mechanical bowel obstruction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BowelObstruction {
+    /// Proximal small bowel (jejunum); contents back up into the duodenum
+    Proximal,
+    /// Distal small bowel (ileum); stagnant contents become feculent
+    Distal,
+}
 
 /// Intestinal segment
 #[derive(Debug, Clone)]
@@ -12,7 +106,7 @@ pub struct IntestinalSegment {
 }
 
 /// Intestines organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Intestines {
     id: OrganId,
     /// Duodenum (first part of small intestine)
@@ -23,12 +117,55 @@ pub struct Intestines {
     pub ileum: IntestinalSegment,
     /// Colon (large intestine)
     pub colon: IntestinalSegment,
-    /// Nutrient absorption rate (mg/min)
-    pub nutrient_absorption_rate: f64,
+    /// Food and drink in the small bowel not yet absorbed
+    pub luminal_nutrients: Meal,
+    /// Energy currently being absorbed (kcal/min)
+    pub absorption_kcal_min: f64,
     /// Water absorption rate (mL/min)
     pub water_absorption_rate: f64,
     /// Motility (0.0 = no movement, 1.0 = normal)
     pub motility: f64,
+    /// Perfusion and viability of the bowel wall
+    pub mucosa: TissuePerfusion,
+    /// Fraction of normal flow the superior mesenteric artery can carry
+    pub mesenteric_artery_patency: f64,
+    /// Whether ischemic necrosis has perforated into the peritoneum
+    pub peritonitis: bool,
+    /// Diarrhea severity (0.0 = none, 1.0 = severe secretory diarrhea)
+    pub diarrhea_severity: f64,
+    /// Stool in the rectum awaiting evacuation (mL)
+    pub rectal_content_ml: f64,
+    /// Blood from an upper GI source still in transit (mL)
+    pub upper_gi_blood_ml: f64,
+    /// Digested blood in the rectal content (mL)
+    pub rectal_digested_blood_ml: f64,
+    /// Fresh blood in the rectal content (mL)
+    pub rectal_fresh_blood_ml: f64,
+    /// Bowel movements not yet collected
+    pub stool_events: Vec<StoolEvent>,
+    /// Character of the most recent bowel movement
+    pub last_stool_character: Option<StoolCharacter>,
+    /// Severity of a bleeding lower GI source such as a diverticulum or
+    /// angiodysplasia (0.0 = none, 1.0 = brisk arterial bleeding)
+    pub lower_gi_bleed_severity: f64,
+    /// Current lower GI bleeding rate (mL/min)
+    pub lower_gi_bleed_rate_ml_min: f64,
+    /// Clot over the lower GI source (0.0 = none, 1.0 = hemostasis)
+    lower_gi_clot: f64,
+    /// Urea nitrogen from digested blood absorbed since last collected (mg)
+    absorbed_urea_nitrogen_mg: f64,
+    /// Food and drink absorbed since last collected
+    absorbed_nutrients: Meal,
+    /// Mechanical obstruction, if any
+    pub obstruction: Option<BowelObstruction>,
+    /// Time since the current obstruction began (s)
+    pub obstruction_time_s: f64,
+    /// Contents refluxed past the pylorus, not yet collected (mL)
+    reflux_ml: f64,
+    /// Feculent portion of the pending reflux (mL)
+    reflux_feculent_ml: f64,
+    /// Internal clock (s)
+    elapsed_time_s: f64,
 }
 
 impl Intestines {
@@ -56,22 +193,293 @@ impl Intestines {
                 chyme_volume_ml: 0.0,
                 absorption_rate: 0.5,
             },
-            nutrient_absorption_rate: 100.0,
+            luminal_nutrients: Meal::default(),
+            absorption_kcal_min: 0.0,
             water_absorption_rate: 50.0,
             motility: 1.0,
+            mucosa: TissuePerfusion::new(0.5, 0.25, 21_600.0),
+            mesenteric_artery_patency: 1.0,
+            peritonitis: false,
+            diarrhea_severity: 0.0,
+            rectal_content_ml: 0.0,
+            upper_gi_blood_ml: 0.0,
+            rectal_digested_blood_ml: 0.0,
+            rectal_fresh_blood_ml: 0.0,
+            stool_events: Vec::new(),
+            last_stool_character: None,
+            lower_gi_bleed_severity: 0.0,
+            lower_gi_bleed_rate_ml_min: 0.0,
+            lower_gi_clot: 0.0,
+            absorbed_urea_nitrogen_mg: 0.0,
+            absorbed_nutrients: Meal::default(),
+            obstruction: None,
+            obstruction_time_s: 0.0,
+            reflux_ml: 0.0,
+            reflux_feculent_ml: 0.0,
+            elapsed_time_s: 0.0,
         }
     }
 
     /// Receive chyme from stomach
-    pub fn receive_chyme(&mut self, volume_ml: f64) {
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Volume of chyme
+    /// * `nutrients` - Food and drink it carries
+    pub fn receive_chyme(&mut self, volume_ml: f64, nutrients: Meal) {
         self.duodenum.chyme_volume_ml += volume_ml;
+        self.luminal_nutrients.add(&nutrients);
+    }
+
+    /// Collect the food and drink absorbed since the last call
+    pub fn take_absorbed_nutrients(&mut self) -> Meal {
+        std::mem::take(&mut self.absorbed_nutrients)
+    }
+
+    /// Absorb nutrients from the small bowel lumen
+    ///
+    /// # Arguments
+    /// * `patient` - The patient, whose incretin level follows the absorbed energy
+    /// * `delta_time_s` - Time step in seconds
+    fn absorb_nutrients(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // Rapid transit in diarrhea carries nutrients past the absorbing mucosa,
+        // and ischemic mucosa absorbs little
+        let mucosal_contact = (1.0 - 0.5 * self.diarrhea_severity) * self.mucosa.function();
+        let fraction = |time_constant_s: f64| 1.0 - (-delta_time_s * mucosal_contact / time_constant_s).exp();
+        let lumen = &mut self.luminal_nutrients;
+        let absorbed = Meal {
+            carbs_g: lumen.carbs_g * fraction(CARBOHYDRATE_ABSORPTION_S),
+            protein_g: lumen.protein_g * fraction(PROTEIN_ABSORPTION_S),
+            fat_g: lumen.fat_g * fraction(FAT_ABSORPTION_S),
+            water_ml: lumen.water_ml * fraction(WATER_ABSORPTION_S),
+            sodium_mg: lumen.sodium_mg * fraction(WATER_ABSORPTION_S),
+        };
+        lumen.carbs_g -= absorbed.carbs_g;
+        lumen.protein_g -= absorbed.protein_g;
+        lumen.fat_g -= absorbed.fat_g;
+        lumen.water_ml -= absorbed.water_ml;
+        lumen.sodium_mg -= absorbed.sodium_mg;
+
+        // What is absorbed no longer fills the small bowel
+        let mut volume = absorbed.volume_ml();
+        for segment in [&mut self.jejunum, &mut self.duodenum, &mut self.ileum] {
+            let taken = segment.chyme_volume_ml.min(volume);
+            segment.chyme_volume_ml -= taken;
+            volume -= taken;
+        }
+
+        self.absorption_kcal_min = absorbed.energy_kcal() * 60.0 / delta_time_s;
+        self.absorbed_nutrients.add(&absorbed);
+        patient.signals.publish(Signal::Incretins, 1.0 + self.absorption_kcal_min / INCRETIN_KCAL_MIN);
+    }
+
+    /// Check whether the bowel is ischemic or infarcting
+    pub fn is_ischemic(&self) -> bool {
+        self.mucosa.state != TissueState::Healthy
+    }
+
+    /// Update mesenteric perfusion and the consequences of an ischemic bowel
+    ///
+    /// # Arguments
+    /// * `patient` - The patient, whose lactate and infection the ischemic gut feeds
+    /// * `delta_time_s` - Time step in seconds
+    fn update_perfusion(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // Splanchnic vessels constrict early in shock, as in the liver, and sympathetic
+        // outflow diverts flow from the gut to the heart and brain
+        let map = patient.blood.get_mean_arterial_pressure();
+        let sympathetic_excess = patient.signals.excess(Signal::SympatheticTone).max(0.0);
+        let splanchnic_perfusion = ((map - 30.0) / 65.0).clamp(0.0, 1.2) / (1.0 + sympathetic_excess);
+        let oxygen_content = (patient.blood.get_sao2_percent() / 98.0).clamp(0.0, 1.0);
+        let ischemic_time = patient.difficulty.ischemic_time_s(delta_time_s);
+        self.mucosa.update(self.mesenteric_artery_patency * splanchnic_perfusion, oxygen_content, ischemic_time);
+
+        // Underperfused and dead bowel wall both leak lactate into the portal blood
+        let ischemia = 1.0 - self.mucosa.function();
+        let lactate = patient.blood.get_lactate_mmol_l();
+        let released = ISCHEMIC_GUT_LACTATE_MMOL_L_MIN * ischemia * delta_time_s / 60.0;
+        patient.blood.set_lactate_mmol_l(lactate + released.min((15.0 - lactate).max(0.0)));
+
+        // Transmural necrosis perforates late, spilling faecal flora into the peritoneum
+        let injury = self.mucosa.injury;
+        if injury >= TRANSMURAL_NECROSIS_INJURY && !self.peritonitis {
+            self.peritonitis = true;
+            match patient.sepsis.as_mut() {
+                Some(sepsis) => sepsis.virulence = sepsis.virulence.max(PERITONITIS_VIRULENCE),
+                None => patient.start_infection("Peritonitis", PERITONITIS_VIRULENCE, BundleGuideline::HourOne),
+            }
+        }
+
+        // Bacteria cross the breached mucosal barrier into the blood
+        if injury >= TRANSLOCATION_INJURY {
+            match patient.sepsis.as_mut() {
+                Some(sepsis) => {
+                    let seeded = TRANSLOCATION_LOAD_PER_S * injury * delta_time_s;
+                    sepsis.bacterial_load = (sepsis.bacterial_load + seeded).min(1.0);
+                }
+                None => patient.start_infection("Bacterial translocation", 1.0, BundleGuideline::HourOne),
+            }
+        }
+    }
+
+    /// Add blood to the intestinal lumen from a bleeding source
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Volume of blood entering the lumen
+    /// * `upper_gi` - True for sources proximal to the ligament of Treitz
+    ///   (blood is digested in transit and appears as melena)
+    pub fn add_luminal_blood(&mut self, volume_ml: f64, upper_gi: bool) {
+        let volume_ml = volume_ml.max(0.0);
+        if upper_gi {
+            self.upper_gi_blood_ml += volume_ml;
+        } else {
+            self.rectal_content_ml += volume_ml;
+            self.rectal_fresh_blood_ml += volume_ml;
+        }
+    }
+
+    /// Start bleeding from a lower GI source (diverticulum, angiodysplasia)
+    ///
+    /// Most stop on their own as a clot forms, more slowly with impaired clotting.
+    ///
+    /// # Arguments
+    /// * `severity` - 0.0 = none, 1.0 = brisk arterial bleeding
+    pub fn develop_lower_gi_bleed(&mut self, severity: f64) {
+        self.lower_gi_bleed_severity = self.lower_gi_bleed_severity.max(severity.clamp(0.0, 1.0));
+        self.lower_gi_clot = 0.0;
+    }
+
+    /// Check for an active lower GI bleed
+    pub fn is_lower_gi_bleeding(&self) -> bool {
+        self.lower_gi_bleed_severity > 0.0
+    }
+
+    /// Collect the urea nitrogen absorbed from digested blood since the last call
+    ///
+    /// # Returns
+    /// Urea nitrogen (mg)
+    pub fn take_absorbed_urea_nitrogen(&mut self) -> f64 {
+        std::mem::take(&mut self.absorbed_urea_nitrogen_mg)
+    }
+
+    /// Update a bleeding lower GI source
+    ///
+    /// # Arguments
+    /// * `patient` - The patient, whose blood is shed into the colon
+    /// * `delta_time_s` - Time step in seconds
+    fn update_lower_gi_bleed(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        if self.lower_gi_bleed_severity <= 0.0 {
+            self.lower_gi_bleed_rate_ml_min = 0.0;
+            return;
+        }
+        let clot_time = LOWER_GI_CLOT_S * Coagulation::hemostatic_delay(&patient.blood);
+        self.lower_gi_clot += (1.0 - self.lower_gi_clot) * (1.0 - (-delta_time_s / clot_time).exp());
+
+        let pressure_factor = (patient.blood.get_mean_arterial_pressure() / 90.0).clamp(0.0, 1.5);
+        self.lower_gi_bleed_rate_ml_min =
+            LOWER_GI_BLEED_ML_MIN * self.lower_gi_bleed_severity * pressure_factor * (1.0 - self.lower_gi_clot);
+        let shed = patient.fluids.remove_blood(self.lower_gi_bleed_rate_ml_min * delta_time_s / 60.0);
+        self.add_luminal_blood(shed, false);
+        if self.lower_gi_bleed_rate_ml_min < 0.5 {
+            self.lower_gi_bleed_severity = 0.0;
+            self.lower_gi_bleed_rate_ml_min = 0.0;
+        }
+    }
+
+    /// Induce diarrhea (infectious, osmotic, or toxin-mediated)
+    ///
+    /// # Arguments
+    /// * `severity` - 0.0 = none, 1.0 = severe
+    pub fn induce_diarrhea(&mut self, severity: f64) {
+        self.diarrhea_severity = self.diarrhea_severity.max(severity.clamp(0.0, 1.0));
+    }
+
+    /// Obstruct the bowel (adhesions, hernia, volvulus)
+    ///
+    /// # Arguments
+    /// * `level` - Level of the obstruction
+    pub fn induce_obstruction(&mut self, level: BowelObstruction) {
+        if self.obstruction != Some(level) {
+            self.obstruction = Some(level);
+            self.obstruction_time_s = 0.0;
+        }
+    }
+
+    /// Relieve a bowel obstruction
+    pub fn relieve_obstruction(&mut self) {
+        self.obstruction = None;
+        self.obstruction_time_s = 0.0;
+    }
+
+    /// Collect contents refluxed into the stomach since the last call
+    ///
+    /// # Returns
+    /// (total volume, feculent portion) in mL
+    pub fn take_reflux(&mut self) -> (f64, f64) {
+        (std::mem::take(&mut self.reflux_ml), std::mem::take(&mut self.reflux_feculent_ml))
+    }
+
+    /// Collect bowel movements recorded since the last call
+    pub fn take_stool_events(&mut self) -> Vec<StoolEvent> {
+        std::mem::take(&mut self.stool_events)
+    }
+
+    /// Classify the current rectal content
+    fn classify_stool(&self) -> StoolCharacter {
+        let fresh_fraction = self.rectal_fresh_blood_ml / self.rectal_content_ml.max(1.0);
+        if fresh_fraction > 0.1 {
+            StoolCharacter::Bloody
+        } else if self.rectal_digested_blood_ml >= 50.0 {
+            // ~50-100 mL of upper GI blood is needed to produce melena
+            StoolCharacter::Melena
+        } else if self.diarrhea_severity > 0.5 {
+            StoolCharacter::Watery
+        } else if self.diarrhea_severity > 0.2 {
+            StoolCharacter::Loose
+        } else {
+            StoolCharacter::Formed
+        }
+    }
+
+    /// Evacuate the rectum when the defecation threshold is reached
+    fn check_defecation(&mut self) {
+        let character = self.classify_stool();
+
+        // Liquid or bloody content triggers urgency at lower volumes
+        let threshold_ml = match character {
+            StoolCharacter::Formed => 200.0,
+            StoolCharacter::Loose => 120.0,
+            StoolCharacter::Watery | StoolCharacter::Melena | StoolCharacter::Bloody => 80.0,
+        };
+
+        if self.rectal_content_ml >= threshold_ml {
+            self.stool_events.push(StoolEvent {
+                time_s: self.elapsed_time_s,
+                volume_ml: self.rectal_content_ml,
+                blood_ml: self.rectal_digested_blood_ml + self.rectal_fresh_blood_ml,
+                character,
+            });
+            self.last_stool_character = Some(character);
+            self.rectal_content_ml = 0.0;
+            self.rectal_digested_blood_ml = 0.0;
+            self.rectal_fresh_blood_ml = 0.0;
+        }
     }
 }
 
 impl Organ for Intestines {
     fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        self.elapsed_time_s += delta_time_s;
+        self.update_perfusion(patient, delta_time_s);
+
+        // Diarrhea speeds transit; ischemic or inflamed bowel is paralysed (ileus)
+        let ileus = if self.peritonitis { 0.0 } else { self.mucosa.function() };
+        let effective_motility = self.motility * (1.0 + self.diarrhea_severity * 2.0) * ileus;
+
         // Move chyme through segments
-        let transfer_rate = 10.0 * self.motility * delta_time_s / 60.0;
+        let transfer_rate = 10.0 * effective_motility * delta_time_s / 60.0;
+
+        // An obstruction stops transit past its level
+        let proximal_open = self.obstruction != Some(BowelObstruction::Proximal);
+        let distal_open = self.obstruction.is_none();
 
         // Duodenum -> Jejunum
         let transfer = self.duodenum.chyme_volume_ml.min(transfer_rate);
@@ -79,36 +487,107 @@ impl Organ for Intestines {
         self.jejunum.chyme_volume_ml += transfer;
 
         // Jejunum -> Ileum
-        let transfer = self.jejunum.chyme_volume_ml.min(transfer_rate);
+        let transfer = if proximal_open { self.jejunum.chyme_volume_ml.min(transfer_rate) } else { 0.0 };
         self.jejunum.chyme_volume_ml -= transfer;
         self.ileum.chyme_volume_ml += transfer;
 
         // Ileum -> Colon
-        let transfer = self.ileum.chyme_volume_ml.min(transfer_rate);
+        // Normally ~10% of colonic inflow ends up as stool; diarrhea
+        // overwhelms colonic water absorption
+        let transfer = if distal_open { self.ileum.chyme_volume_ml.min(transfer_rate) } else { 0.0 };
         self.ileum.chyme_volume_ml -= transfer;
-        self.colon.chyme_volume_ml += transfer;
+        let stool_fraction = 0.1 + self.diarrhea_severity * 0.8;
+        self.colon.chyme_volume_ml += transfer * (1.0 - stool_fraction);
+        self.rectal_content_ml += transfer * stool_fraction;
 
-        // Absorption in jejunum (main absorption site)
-        let nutrient_absorbed = self.nutrient_absorption_rate
-            * self.jejunum.absorption_rate
-            * delta_time_s / 60.0;
+        // Upper GI blood is digested in transit (~1 hour at normal motility)
+        let blood_transit = self.upper_gi_blood_ml
+            * (effective_motility * delta_time_s / 3600.0).min(1.0);
+        self.upper_gi_blood_ml -= blood_transit;
+        self.rectal_content_ml += blood_transit;
+        self.rectal_digested_blood_ml += blood_transit;
+        self.absorbed_urea_nitrogen_mg += blood_transit * UREA_NITROGEN_MG_PER_BLOOD_ML;
+        self.update_lower_gi_bleed(patient, delta_time_s);
+
+        // Obstructed bowel distends and its contents back up toward the stomach
+        if self.obstruction.is_some() {
+            self.obstruction_time_s += delta_time_s;
 
-        // Increase blood glucose from nutrient absorption
-        patient.blood.chemistry.glucose_mg_dl += nutrient_absorbed * 0.01;
+            // Biliary, pancreatic and intestinal secretions pool above the obstruction
+            let secretions = 2.0 * delta_time_s / 60.0;
+            match self.obstruction {
+                Some(BowelObstruction::Proximal) => self.jejunum.chyme_volume_ml += secretions,
+                _ => self.ileum.chyme_volume_ml += secretions,
+            }
+
+            let backflow = (self.ileum.chyme_volume_ml - 500.0).max(0.0);
+            self.ileum.chyme_volume_ml -= backflow;
+            self.jejunum.chyme_volume_ml += backflow;
+
+            let backflow = (self.jejunum.chyme_volume_ml - 300.0).max(0.0);
+            self.jejunum.chyme_volume_ml -= backflow;
+            self.duodenum.chyme_volume_ml += backflow;
+
+            // Bacterial overgrowth makes stagnant distal contents feculent over ~12 hours
+            let feculent_fraction = match self.obstruction {
+                Some(BowelObstruction::Distal) => (self.obstruction_time_s / 43_200.0).min(1.0),
+                _ => 0.0,
+            };
+            let reflux = (self.duodenum.chyme_volume_ml - 200.0).max(0.0);
+            self.duodenum.chyme_volume_ml -= reflux;
+            self.reflux_ml += reflux;
+            self.reflux_feculent_ml += reflux * feculent_fraction;
+        }
+
+        // Absorption in the small bowel
+        self.absorb_nutrients(patient, delta_time_s);
 
         // Water absorption in colon
-        let water_absorbed = self.water_absorption_rate * delta_time_s / 60.0;
+        let water_absorbed = self.water_absorption_rate
+            * (1.0 - self.diarrhea_severity * 0.8)
+            * delta_time_s / 60.0;
         self.colon.chyme_volume_ml = (self.colon.chyme_volume_ml - water_absorbed).max(0.0);
+
+        // Bowel movements
+        self.check_defecation();
+
+        // Self-limited diarrhea resolves over ~1 day
+        self.diarrhea_severity *= (-delta_time_s / 86_400.0).exp();
     }
 
     fn get_summary(&self) -> String {
+        let last_stool = match self.last_stool_character {
+            Some(character) => format!("{:?}", character),
+            None => "None".to_string(),
+        };
+        let obstruction = match self.obstruction {
+            Some(level) => format!("{:?}", level),
+            None => "None".to_string(),
+        };
+        let bleeding = if self.is_lower_gi_bleeding() {
+            format!(", LOWER GI BLEED ({:.1} mL/min)", self.lower_gi_bleed_rate_ml_min)
+        } else {
+            String::new()
+        };
+        let ischemia = if self.peritonitis {
+            format!(", PERITONITIS (necrosis {:.0}%)", self.mucosa.injury * 100.0)
+        } else if self.is_ischemic() {
+            format!(", BOWEL ISCHEMIA (flow {:.0}%, injury {:.0}%)", self.mucosa.relative_flow * 100.0, self.mucosa.injury * 100.0)
+        } else {
+            String::new()
+        };
         format!(
-            "Intestines: Motility={:.1}, Duodenum={:.0}mL, Jejunum={:.0}mL, Ileum={:.0}mL, Colon={:.0}mL",
+            "Intestines: Motility={:.1}, Duodenum={:.0}mL, Jejunum={:.0}mL, Ileum={:.0}mL, Colon={:.0}mL, Rectum={:.0}mL, Last stool={}, Obstruction={}{}{}",
             self.motility,
             self.duodenum.chyme_volume_ml,
             self.jejunum.chyme_volume_ml,
             self.ileum.chyme_volume_ml,
-            self.colon.chyme_volume_ml
+            self.colon.chyme_volume_ml,
+            self.rectal_content_ml,
+            last_stool,
+            obstruction,
+            bleeding,
+            ischemia
         )
     }
 
@@ -127,4 +606,106 @@ impl Organ for Intestines {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::vascular::VascularSystem;
+    use crate::patient::{initialize_patient, update_patient};
+
+    /// Pass chyme through fresh intestines for some hours, collecting the
+    /// bowel movements it produces
+    fn pass_chyme(setup: impl Fn(&mut Intestines), hours: usize) -> Vec<StoolEvent> {
+        let mut patient = initialize_patient(1, 3);
+        let mut intestines = Intestines::new(0);
+        intestines.receive_chyme(3000.0, Meal::water(3000.0));
+        setup(&mut intestines);
+        let mut events = Vec::new();
+        for _ in 0..hours * 360 {
+            intestines.update(&mut patient, 10.0);
+            events.extend(intestines.take_stool_events());
+        }
+        assert!(intestines.take_stool_events().is_empty());
+        events
+    }
+
+    #[test]
+    fn test_stool_events_are_charted_by_character_and_collected_once() {
+        // The small bowel and colon absorb a water load without a bowel movement
+        assert!(pass_chyme(|_| {}, 12).is_empty());
+
+        // Diarrhea overwhelms colonic absorption, and liquid stool brings urgency at small volumes
+        let watery = pass_chyme(|intestines| intestines.induce_diarrhea(0.8), 12);
+        assert!(watery.len() >= 3);
+        assert!(watery.iter().all(|e| e.character == StoolCharacter::Watery && e.blood_ml == 0.0));
+        assert!(watery.iter().all(|e| e.volume_ml < 120.0));
+        assert!(watery.windows(2).all(|pair| pair[1].time_s > pair[0].time_s));
+
+        // Upper GI blood is digested in transit and passed as melena
+        let melena = pass_chyme(|intestines| intestines.add_luminal_blood(300.0, true), 12);
+        assert!(!melena.is_empty());
+        assert!(melena.iter().any(|e| e.character == StoolCharacter::Melena && e.blood_ml > 0.0));
+        assert!(melena.iter().map(|e| e.blood_ml).sum::<f64>() > 250.0);
+    }
+
+    #[test]
+    fn test_absorbed_meal_releases_incretins_and_raises_glucose_and_triglycerides() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 600.0);
+        let plasma_ml = patient.fluids.plasma_ml;
+        patient.eat(Meal::new(90.0, 30.0, 40.0, 500.0, 1000.0));
+
+        // Water reaches the plasma only as it is absorbed
+        assert!(patient.fluids.plasma_ml < plasma_ml + 1.0);
+        assert!(patient.get_unabsorbed_water_ml() > 450.0);
+
+        let mut peak_glucose: f64 = 0.0;
+        for _ in 0..60 {
+            update_patient(&mut patient, 60.0);
+            peak_glucose = peak_glucose.max(patient.blood.chemistry.glucose_mg_dl);
+        }
+        assert!(patient.signals.level(Signal::Incretins) > 1.3);
+        assert!(peak_glucose > 100.0 && peak_glucose < 180.0, "{}", peak_glucose);
+        assert!(patient.get_unabsorbed_water_ml() < 450.0);
+
+        // Fat is absorbed slowly, so triglyceride is still rising hours later
+        for _ in 0..180 {
+            update_patient(&mut patient, 60.0);
+        }
+        assert!(patient.blood.chemistry.triglycerides_mg_dl > 120.0, "{}", patient.blood.chemistry.triglycerides_mg_dl);
+        assert!(patient.blood.chemistry.glucose_mg_dl < 120.0);
+    }
+
+    #[test]
+    fn test_ischemic_bowel_paralyses_and_seeds_infection() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 600.0);
+        let mut ischemic = patient.fork();
+        ischemic.get_organ_mut::<VascularSystem>().unwrap().occlude_vessel("Superior Mesenteric Artery", 1.0);
+        for _ in 0..60 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut ischemic, 60.0);
+        }
+
+        // The starved bowel stops moving, makes lactate and lets gut bacteria through
+        let intestines = ischemic.get_organ::<Intestines>().unwrap();
+        assert!(intestines.is_ischemic() && intestines.mucosa.function() < 0.2);
+        assert!(!intestines.peritonitis);
+        assert!(ischemic.blood.chemistry.lactate_mmol_l > patient.blood.chemistry.lactate_mmol_l + 1.5);
+        assert_eq!(ischemic.sepsis.as_ref().unwrap().source, "Bacterial translocation");
+        assert!(patient.sepsis.is_none());
+
+        // Hours later the necrosis is transmural and faecal flora reach the peritoneum
+        for _ in 0..180 {
+            update_patient(&mut ischemic, 60.0);
+        }
+        let intestines = ischemic.get_organ::<Intestines>().unwrap();
+        assert!(intestines.peritonitis, "{}", intestines.mucosa.injury);
+        assert!(ischemic.sepsis.as_ref().unwrap().virulence >= 2.0);
+    }
 }
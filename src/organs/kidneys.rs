@@ -1,9 +1,103 @@
 //! Kidneys organ simulation
 //!
-//! Blood filtration and electrolyte balance
+//! Blood filtration, erythropoietin and loop diuretic natriuresis, with tubular
+//! handling of sodium, potassium, acid and water
+//!
+//! The tubules correct plasma electrolytes at a finite rate rather than holding
+//! them at normal values, so a change made elsewhere (a potassium infusion,
+//! vomiting, hypertonic saline) persists until excreted. Aldosterone from the
+//! signal bus drives potassium and hydrogen ion secretion in the collecting
+//! duct; loop diuretics increase distal delivery and so waste potassium.
+//!
+//! Myoglobin from injured muscle is filtered and precipitates in the tubules
+//! when urine is scant and acidic (pigment nephropathy), so a brisk alkaline
+//! diuresis protects them.
 
+use crate::acid_base::NORMAL_BICARBONATE_MEQ_L;
+use crate::fluids::SECONDS_PER_DAY;
+use crate::models::autoregulation::AutoregulationCurve;
 use crate::organ::{Organ, OrganId};
+use crate::organs::bones::NORMAL_HEMATOCRIT;
+use crate::organs::muscle::NORMAL_MYOGLOBIN_NG_ML;
 use crate::patient::Patient;
+use crate::signals::Signal;
+
+/// Normal plasma erythropoietin (mU/mL)
+pub const NORMAL_ERYTHROPOIETIN_MU_ML: f64 = 10.0;
+
+/// Time constant of erythropoietin secretion and clearance (s)
+const ERYTHROPOIETIN_RESPONSE_S: f64 = 14_400.0;
+
+/// Time constant of furosemide elimination (s), a half-life of about 90 minutes
+const FUROSEMIDE_ELIMINATION_S: f64 = 7_800.0;
+
+/// Furosemide acting on the loop of Henle giving half the maximal natriuresis (mg)
+const FUROSEMIDE_HALF_EFFECT_MG: f64 = 40.0;
+
+/// Maximal diuretic urine flow at normal GFR (mL/min)
+const MAX_DIURESIS_ML_MIN: f64 = 12.0;
+
+/// Normal plasma sodium (mEq/L)
+pub const NORMAL_SODIUM_MEQ_L: f64 = 140.0;
+
+/// Normal plasma potassium (mEq/L)
+pub const NORMAL_POTASSIUM_MEQ_L: f64 = 4.0;
+
+/// Dietary and endogenous potassium the kidneys must excrete, as the rise in
+/// plasma potassium it causes after cellular buffering when they cannot (mEq/L per day)
+const POTASSIUM_LOAD_MEQ_L_PER_DAY: f64 = 1.0;
+
+/// Time constant of renal correction of a potassium disturbance at normal
+/// function (s)
+const POTASSIUM_CORRECTION_S: f64 = 8.0 * 3600.0;
+
+/// Time constant of osmoregulatory correction of sodium through free water
+/// excretion or retention at normal function (s)
+const SODIUM_CORRECTION_S: f64 = 24.0 * 3600.0;
+
+/// Rise in the defended sodium per unit of aldosterone excess (mEq/L)
+const SODIUM_PER_ALDOSTERONE_MEQ_L: f64 = 2.0;
+
+/// Rise in the defended bicarbonate per unit of aldosterone excess (mEq/L)
+const BICARBONATE_PER_ALDOSTERONE_MEQ_L: f64 = 2.0;
+
+/// Contraction alkalosis at maximal loop diuretic effect (mEq/L of bicarbonate)
+const DIURETIC_ALKALOSIS_MEQ_L: f64 = 4.0;
+
+/// Rise in the defended bicarbonate per mEq/L of hypokalemia (mEq/L)
+const BICARBONATE_PER_HYPOKALEMIA_MEQ_L: f64 = 1.5;
+
+/// Potassium wasted with bicarbonate filtered above the defended level, as the
+/// fall in plasma potassium per mEq/L of excess bicarbonate (mEq/L per day)
+const BICARBONATURIA_POTASSIUM_MEQ_L_PER_DAY: f64 = 0.3;
+
+/// Fraction of the baseline urine flow kept when the kidneys retain free water
+const MIN_URINE_FRACTION: f64 = 0.3;
+
+/// Time constant of renal urea excretion from total body water at normal function (s)
+const UREA_CLEARANCE_S: f64 = 12.0 * 3600.0;
+
+/// Time constant of myoglobin clearance at normal GFR (s), a half-life of about 3 hours
+const MYOGLOBIN_CLEARANCE_S: f64 = 15_600.0;
+
+/// Share of myoglobin clearance that is not renal
+const EXTRARENAL_MYOGLOBIN_CLEARANCE: f64 = 0.2;
+
+/// Plasma myoglobin above which it injures the tubules (ng/mL)
+const NEPHROTOXIC_MYOGLOBIN_NG_ML: f64 = 5000.0;
+
+/// Tubular injury per day for each multiple of the nephrotoxic myoglobin, at
+/// normal urine flow and pH
+const PIGMENT_INJURY_PER_DAY: f64 = 0.5;
+
+/// Urine flow that keeps filtered myoglobin dilute (mL/min)
+const PROTECTIVE_URINE_ML_MIN: f64 = 1.0;
+
+/// Rise in pigment injury per pH unit of acidemia, which acidifies the urine
+const PIGMENT_INJURY_PER_ACIDEMIA: f64 = 10.0;
+
+/// Time constant of injured tubular epithelium regenerating (s)
+const TUBULAR_REPAIR_S: f64 = 7.0 * SECONDS_PER_DAY;
 
 /// Nephron (functional unit of kidney)
 #[derive(Debug, Clone)]
@@ -12,13 +106,17 @@ pub struct Nephron {
 }
 
 /// Kidneys organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Kidneys {
     id: OrganId,
     /// Nephrons (functional units)
     pub nephrons: Vec<Nephron>,
     /// Glomerular filtration rate (mL/min)
     pub gfr_ml_per_min: f64,
+    /// Normal GFR for the patient's size and age (mL/min)
+    pub baseline_gfr_ml_per_min: f64,
+    /// Mean arterial pressure below which autoregulation fails (mmHg)
+    pub autoregulation_map_mmhg: f64,
     /// Urine output rate (mL/min)
     pub urine_output_rate: f64,
     /// Blood sodium level (mEq/L)
@@ -27,6 +125,23 @@ pub struct Kidneys {
     pub blood_potassium_meq_l: f64,
     /// Renin secretion (AU/min)
     pub renin_secretion: f64,
+    /// Glomerular endothelial injury (0.0 = none, 1.0 = severe), e.g. preeclampsia
+    pub glomerular_injury: f64,
+    /// Urinary protein excretion (g/day)
+    /// Normal: <0.15 g/day, nephrotic: >3.5 g/day
+    pub proteinuria_g_per_day: f64,
+    /// Plasma erythropoietin secreted by peritubular cells sensing oxygen (mU/mL)
+    pub erythropoietin_mu_ml: f64,
+    /// Plasma bicarbonate the tubules defend (mEq/L), set from the acid-base balance;
+    /// raised in chronic hypercapnia, lowered in chronic hypocapnia
+    pub bicarbonate_setpoint_meq_l: f64,
+    /// Furosemide acting on the loop of Henle (mg)
+    pub furosemide_mg: f64,
+    /// BUN above the level filtration sets, from an extra urea load such as
+    /// digested upper GI blood, until excreted (mg/dL)
+    pub urea_load_mg_dl: f64,
+    /// Tubular epithelial necrosis (0.0 = none, 1.0 = anuric), e.g. from myoglobin casts
+    pub tubular_injury: f64,
 }
 
 impl Kidneys {
@@ -43,10 +158,19 @@ impl Kidneys {
             id,
             nephrons,
             gfr_ml_per_min: 100.0,
+            baseline_gfr_ml_per_min: 100.0,
+            autoregulation_map_mmhg: 70.0,
             urine_output_rate: 1.0,
-            blood_sodium_meq_l: 140.0,
-            blood_potassium_meq_l: 4.0,
+            blood_sodium_meq_l: NORMAL_SODIUM_MEQ_L,
+            blood_potassium_meq_l: NORMAL_POTASSIUM_MEQ_L,
             renin_secretion: 1.0,
+            glomerular_injury: 0.0,
+            proteinuria_g_per_day: 0.1,
+            erythropoietin_mu_ml: NORMAL_ERYTHROPOIETIN_MU_ML,
+            bicarbonate_setpoint_meq_l: NORMAL_BICARBONATE_MEQ_L,
+            furosemide_mg: 0.0,
+            urea_load_mg_dl: 0.0,
+            tubular_injury: 0.0,
         }
     }
 
@@ -68,6 +192,56 @@ impl Kidneys {
         }
     }
 
+    /// Give an intravenous furosemide bolus
+    ///
+    /// # Arguments
+    /// * `mg` - Dose in mg (20-40 mg in the diuretic-naive, 1-2.5 times the oral dose
+    ///   in chronic users)
+    pub fn give_furosemide(&mut self, mg: f64) {
+        self.furosemide_mg += mg.max(0.0);
+    }
+
+    /// Add urea nitrogen for the kidneys to excrete, raising BUN until they do
+    ///
+    /// # Arguments
+    /// * `nitrogen_mg` - Urea nitrogen generated (mg)
+    /// * `total_body_water_ml` - Water urea distributes through (mL)
+    pub fn add_urea_nitrogen(&mut self, nitrogen_mg: f64, total_body_water_ml: f64) {
+        self.urea_load_mg_dl += nitrogen_mg.max(0.0) / (total_body_water_ml / 100.0).max(1.0);
+    }
+
+    /// Acute kidney injury stage (KDIGO, 0 = none to 3)
+    ///
+    /// Creatinine rises in inverse proportion to filtration, so the KDIGO
+    /// creatinine ratios of 1.5, 2 and 3 times baseline correspond to GFR
+    /// falling below 2/3, 1/2 and 1/3 of the patient's normal.
+    pub fn aki_stage(&self) -> u8 {
+        let creatinine_ratio = self.baseline_gfr_ml_per_min / self.gfr_ml_per_min.max(1e-6);
+        match creatinine_ratio {
+            r if r >= 3.0 => 3,
+            r if r >= 2.0 => 2,
+            r if r >= 1.5 => 1,
+            _ => 0,
+        }
+    }
+
+    /// Injure the tubules with filtered myoglobin, worse when little urine
+    /// carries it away and acidemia acidifies it, and clear it from the plasma
+    fn update_pigment_nephropathy(&mut self, patient: &mut Patient, relative_gfr: f64, delta_time_s: f64) {
+        let myoglobin = patient.blood.chemistry.myoglobin_ng_ml;
+        let pigment = (myoglobin / NEPHROTOXIC_MYOGLOBIN_NG_ML - 1.0).max(0.0);
+        let concentration = (PROTECTIVE_URINE_ML_MIN / self.urine_output_rate.max(0.1)).clamp(0.5, 5.0);
+        let acidemia = (7.4 - patient.blood.get_ph()).clamp(0.0, 0.5);
+        let injury_per_day = PIGMENT_INJURY_PER_DAY * pigment * concentration * (1.0 + PIGMENT_INJURY_PER_ACIDEMIA * acidemia);
+        self.tubular_injury += injury_per_day * (1.0 - self.tubular_injury) * delta_time_s / SECONDS_PER_DAY;
+        self.tubular_injury *= (-delta_time_s / TUBULAR_REPAIR_S).exp();
+
+        let clearance = EXTRARENAL_MYOGLOBIN_CLEARANCE + (1.0 - EXTRARENAL_MYOGLOBIN_CLEARANCE) * relative_gfr.max(0.0);
+        let excess = (myoglobin - NORMAL_MYOGLOBIN_NG_ML).max(0.0);
+        patient.blood.chemistry.myoglobin_ng_ml -=
+            excess * (1.0 - (-delta_time_s * clearance / MYOGLOBIN_CLEARANCE_S).exp());
+    }
+
     /// Get renin secretion (for RAAS system)
     pub fn get_renin_secretion(&self) -> f64 {
         self.renin_secretion
@@ -78,46 +252,127 @@ impl Organ for Kidneys {
     fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
         let efficiency = self.average_efficiency();
 
+        let map = patient.blood.blood_pressure_diastolic
+            + (patient.blood.blood_pressure_systolic - patient.blood.blood_pressure_diastolic) / 3.0;
+
         // GFR based on nephron efficiency
-        self.gfr_ml_per_min = 100.0 * efficiency;
+        // Autoregulation fails below a MAP of ~70 mmHg (adult); glomerular injury reduces filtration,
+        // and necrotic tubules obstruct it and leak the filtrate back
+        let autoregulation_map = self.autoregulation_map_mmhg;
+        let perfusion_factor = AutoregulationCurve::renal(autoregulation_map).relative_flow(map);
+        self.gfr_ml_per_min =
+            self.baseline_gfr_ml_per_min * efficiency * perfusion_factor * (1.0 - self.glomerular_injury * 0.3)
+                * (1.0 - self.tubular_injury);
+        let relative_gfr = self.gfr_ml_per_min / self.baseline_gfr_ml_per_min;
+
+        // Injured glomeruli leak protein
+        self.proteinuria_g_per_day = 0.1 + self.glomerular_injury * 5.0;
 
         // Urine output
-        self.urine_output_rate = self.gfr_ml_per_min * 0.01;
+        // Glucose above the renal threshold (~180 mg/dL) causes an osmotic diuresis
+        let osmotic_diuresis = (patient.blood.get_glucose_mg_dl() - 180.0).max(0.0) * 0.005 * efficiency
+            * self.baseline_gfr_ml_per_min / 100.0;
+        // Loop diuretics block sodium reabsorption; their effect needs filtered delivery
+        self.furosemide_mg *= (-delta_time_s / FUROSEMIDE_ELIMINATION_S).exp();
+        let diuretic_effect = self.furosemide_mg / (self.furosemide_mg + FUROSEMIDE_HALF_EFFECT_MG);
+        let diuresis = MAX_DIURESIS_ML_MIN * diuretic_effect * self.gfr_ml_per_min / 100.0;
+        let aldosterone = patient.signals.level(Signal::Aldosterone).clamp(0.0, 5.0);
+        let filtration = relative_gfr.clamp(0.0, 1.5);
+
+        // Potassium: principal cells secrete in proportion to aldosterone and to
+        // distal sodium delivery, which loop diuretics increase; intake keeps
+        // arriving when they cannot. Bicarbonate filtered beyond the defended level
+        // (the alkalosis of vomiting) reaches the collecting duct as a poorly
+        // reabsorbed anion and drags potassium out with it
+        let potassium = patient.blood.get_potassium_meq_l();
+        let secretion_capacity = filtration * aldosterone * (1.0 + diuretic_effect);
+        let potassium_load = POTASSIUM_LOAD_MEQ_L_PER_DAY / SECONDS_PER_DAY;
+        let bicarbonaturia = (patient.blood.chemistry.bicarbonate_meq_l - self.bicarbonate_setpoint_meq_l).max(0.0);
+        let potassium_excretion = secretion_capacity
+            * (potassium_load + (potassium - NORMAL_POTASSIUM_MEQ_L) / POTASSIUM_CORRECTION_S)
+            + filtration * BICARBONATURIA_POTASSIUM_MEQ_L_PER_DAY * bicarbonaturia / SECONDS_PER_DAY;
+        patient.blood.set_potassium_meq_l(potassium + (potassium_load - potassium_excretion) * delta_time_s);
 
-        // Maintain electrolyte balance - update both local and blood values
-        self.blood_sodium_meq_l = 140.0;
-        self.blood_potassium_meq_l = 4.0;
-        patient.blood.chemistry.sodium_meq_l = self.blood_sodium_meq_l;
-        patient.blood.chemistry.potassium_meq_l = self.blood_potassium_meq_l;
+        // Sodium: concentration is set by water; the tubules excrete or retain
+        // free water towards the sodium aldosterone-driven reabsorption defends
+        let sodium_target = NORMAL_SODIUM_MEQ_L + SODIUM_PER_ALDOSTERONE_MEQ_L * (aldosterone - 1.0).clamp(-1.0, 2.0);
+        let sodium_error = patient.blood.get_sodium_meq_l() - sodium_target;
+        let correction = efficiency * filtration.min(1.0) * delta_time_s / SODIUM_CORRECTION_S;
+        patient.blood.set_sodium_meq_l(patient.blood.get_sodium_meq_l() - sodium_error * correction.min(1.0));
+        let free_water_ml_min = -sodium_error / patient.blood.get_sodium_meq_l().max(1.0) * patient.fluids.total_body_water_ml()
+            * efficiency
+            * filtration.min(1.0)
+            / SODIUM_CORRECTION_S
+            * 60.0;
+        let baseline_urine = self.gfr_ml_per_min * 0.01;
+        self.urine_output_rate = baseline_urine
+            + free_water_ml_min.max(-(1.0 - MIN_URINE_FRACTION) * baseline_urine)
+            + osmotic_diuresis
+            + diuresis;
+        self.blood_sodium_meq_l = patient.blood.get_sodium_meq_l();
+        self.blood_potassium_meq_l = patient.blood.get_potassium_meq_l();
 
         // Update creatinine based on kidney function
-        patient.blood.chemistry.creatinine_mg_dl = 0.9 + (1.0 - self.gfr_ml_per_min / 120.0) * 3.0;
-        patient.blood.chemistry.bun_mg_dl = 12.0 + (1.0 - self.gfr_ml_per_min / 120.0) * 30.0;
+        patient.blood.chemistry.creatinine_mg_dl = 0.9 + (1.0 - relative_gfr / 1.2) * 3.0;
+        // An extra urea load is excreted in proportion to filtration, without creatinine
+        self.urea_load_mg_dl *= (-delta_time_s * relative_gfr.max(0.0) / UREA_CLEARANCE_S).exp();
+        patient.blood.chemistry.bun_mg_dl = 12.0 + (1.0 - relative_gfr / 1.2) * 30.0 + self.urea_load_mg_dl;
+
+        // Erythropoietin rises exponentially as renal oxygen delivery falls (anemia,
+        // hypoxemia); nephron loss takes the producing cells with it (anemia of CKD)
+        let oxygen_delivery = patient.fluids.hematocrit() * patient.blood.get_sao2_percent() / 97.0;
+        let target_epo =
+            NORMAL_ERYTHROPOIETIN_MU_ML * efficiency * (12.0 * (NORMAL_HEMATOCRIT - oxygen_delivery)).min(5.0).exp();
+        self.erythropoietin_mu_ml +=
+            (target_epo - self.erythropoietin_mu_ml) * (1.0 - (-delta_time_s / ERYTHROPOIETIN_RESPONSE_S).exp());
 
         // Renin secretion (RAAS system)
         // Increase renin when blood pressure is low
-        let map = patient.blood.blood_pressure_diastolic
-            + (patient.blood.blood_pressure_systolic - patient.blood.blood_pressure_diastolic) / 3.0;
-
-        if map < 90.0 {
-            self.renin_secretion = 1.0 + (90.0 - map) * 0.1;
+        let renin_threshold = autoregulation_map * 9.0 / 7.0;
+        if map < renin_threshold {
+            self.renin_secretion = 1.0 + (renin_threshold - map) * 0.1;
         } else {
             self.renin_secretion = 1.0;
         }
 
+        // Excess magnesium is filtered and excreted (half-life ~2 hours at normal GFR)
+        let magnesium = &mut patient.blood.chemistry.magnesium_mg_dl;
+        let excess_magnesium = (*magnesium - 2.0).max(0.0);
+        *magnesium -= excess_magnesium * relative_gfr * delta_time_s / 9_000.0;
+
+        // Acid-base balance
+        // Kidneys slowly regenerate or excrete bicarbonate toward the setpoint (over
+        // about a day), exchanging it for chloride. Aldosterone, volume contraction
+        // by diuretics and hypokalemia drive hydrogen ion secretion, raising the
+        // bicarbonate defended
+        let hypokalemia = (NORMAL_POTASSIUM_MEQ_L - patient.blood.get_potassium_meq_l()).max(0.0);
+        let chemistry = &mut patient.blood.chemistry;
+        let bicarbonate_target = self.bicarbonate_setpoint_meq_l
+            + BICARBONATE_PER_ALDOSTERONE_MEQ_L * (aldosterone - 1.0).clamp(-1.0, 3.0)
+            + DIURETIC_ALKALOSIS_MEQ_L * diuretic_effect
+            + BICARBONATE_PER_HYPOKALEMIA_MEQ_L * hypokalemia;
+        let renal_bicarbonate =
+            (bicarbonate_target - chemistry.bicarbonate_meq_l) * 0.00001 * efficiency * delta_time_s;
+        chemistry.bicarbonate_meq_l += renal_bicarbonate;
+        chemistry.chloride_meq_l = (chemistry.chloride_meq_l - renal_bicarbonate).clamp(60.0, 140.0);
+
+        self.update_pigment_nephropathy(patient, relative_gfr, delta_time_s);
+
         // Remove some toxins through filtration
         // Kidneys can clear approximately 0.5 toxin units per second at normal GFR
-        let toxin_clearance = (self.gfr_ml_per_min / 100.0) * 0.5 * delta_time_s;
+        let toxin_clearance = relative_gfr * 0.5 * delta_time_s;
         patient.blood.chemistry.toxin_level_au = (patient.blood.chemistry.toxin_level_au - toxin_clearance).max(0.0);
     }
 
     fn get_summary(&self) -> String {
         format!(
-            "Kidneys: GFR={:.1} mL/min, Urine={:.2} mL/min, Na+={:.0} mEq/L, K+={:.1} mEq/L",
+            "Kidneys: GFR={:.1} mL/min, Urine={:.2} mL/min, Protein={:.1} g/day, Na+={:.0} mEq/L, K+={:.1} mEq/L, EPO={:.0} mU/mL",
             self.gfr_ml_per_min,
             self.urine_output_rate,
+            self.proteinuria_g_per_day,
             self.blood_sodium_meq_l,
-            self.blood_potassium_meq_l
+            self.blood_potassium_meq_l,
+            self.erythropoietin_mu_ml
         )
     }
 
@@ -136,4 +391,34 @@ impl Organ for Kidneys {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_aldosterone_drives_potassium_excretion() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 60.0);
+        patient.blood.chemistry.potassium_meq_l = 6.5;
+        let mut injured = patient.fork();
+        injured.get_organ_mut::<Kidneys>().unwrap().damage_nephrons(100.0);
+        injured.get_organ_mut::<Kidneys>().unwrap().damage_nephrons(100.0);
+        for _ in 0..120 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut injured, 60.0);
+        }
+
+        // The load persists for hours while hyperkalemia stimulates aldosterone to clear it
+        let potassium = patient.blood.chemistry.potassium_meq_l;
+        assert!(patient.signals.level(Signal::Aldosterone) > 1.3);
+        assert!(potassium < 6.0 && potassium > 4.5, "{}", potassium);
+        assert!(injured.blood.chemistry.potassium_meq_l > potassium + 0.5);
+    }
 }
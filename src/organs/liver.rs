@@ -1,118 +1,428 @@
 //! Liver organ simulation
 //!
-//! Metabolic processing and detoxification
+//! Metabolic processing and detoxification, including:
+//! - Dual blood supply from the hepatic artery and portal vein, with the hepatic
+//!   arterial buffer response when portal flow falls
+//! - Three acinar zones on the tissue perfusion framework; the centrilobular zone
+//!   (zone 3) is the first to die in shock liver (ischemic hepatitis)
+//! - Aminotransferase release from necrosis and its clearance over days
+//! - Regeneration of necrotic tissue, and fibrosis progressing to cirrhosis under
+//!   chronic injury
+//! - Portal venous pressure from sinusoidal resistance (cirrhosis) and portal vein
+//!   obstruction, with the hepatic venous pressure gradient (HVPG)
+//! - Synthetic function: albumin and clotting factor production, bilirubin
+//!   conjugation, gluconeogenesis, toxin and lactate clearance
+//! - Cholestasis behind an obstructed common bile duct: conjugated bilirubin
+//!   regurgitates into the plasma and alkaline phosphatase is induced
+//! - First-pass uptake of absorbed glucose from the portal blood, and clearance
+//!   of postprandial triglyceride back to the fasting level
 
 use crate::organ::{Organ, OrganId};
 use crate::patient::Patient;
+use crate::tissue::TissuePerfusion;
 
-/// Hepatic lobule (functional unit of liver)
-#[derive(Debug, Clone)]
-pub struct HepaticLobule {
-    pub metabolic_capacity: f64,  // 0.0 = damaged, 1.0 = healthy
+/// Normal total hepatic blood flow (mL/min)
+const NORMAL_HEPATIC_BLOOD_FLOW_ML_MIN: f64 = 1500.0;
+
+/// Fraction of hepatic blood flow from the hepatic artery (the rest is portal)
+const HEPATIC_ARTERIAL_FLOW_FRACTION: f64 = 0.25;
+
+/// Fraction of hepatic oxygen supply from the hepatic artery
+const HEPATIC_ARTERIAL_OXYGEN_FRACTION: f64 = 0.5;
+
+/// AST released into plasma by necrosis of the whole liver (U/L)
+const AST_PER_NECROSIS: f64 = 25_000.0;
+
+/// ALT released into plasma by necrosis of the whole liver (U/L)
+const ALT_PER_NECROSIS: f64 = 18_000.0;
+
+/// Plasma clearance time constant of AST (half-life ~17 h, s)
+const AST_CLEARANCE_S: f64 = 88_000.0;
+
+/// Plasma clearance time constant of ALT (half-life ~47 h, s)
+const ALT_CLEARANCE_S: f64 = 244_000.0;
+
+/// Time constant of albumin turnover (half-life ~20 days, s)
+const ALBUMIN_TURNOVER_S: f64 = 2_500_000.0;
+
+/// Time constant of hepatocyte regeneration (s)
+const REGENERATION_S: f64 = 5.0 * 86_400.0;
+
+/// Fibrosis accrual rate under maximal chronic injury (cirrhosis in ~10 years, per s)
+const FIBROSIS_RATE_PER_S: f64 = 5.1e-9;
+
+/// Fibrosis score at which the architecture becomes cirrhotic
+const CIRRHOSIS_FIBROSIS: f64 = 0.8;
+
+/// Normal hepatic venous pressure gradient at full portal inflow (mmHg)
+const NORMAL_HVPG_MMHG: f64 = 4.0;
+
+/// HVPG at which portal hypertension becomes clinically significant (varices form, mmHg)
+pub const CLINICALLY_SIGNIFICANT_HVPG_MMHG: f64 = 10.0;
+
+/// Fraction of absorbed glucose a healthy liver takes up on first pass
+const FIRST_PASS_GLUCOSE_EXTRACTION: f64 = 0.3;
+
+/// Time constant of chylomicron triglyceride clearance (s)
+const TRIGLYCERIDE_CLEARANCE_S: f64 = 900.0;
+
+/// Bilirubin added by complete obstruction of the common bile duct (mg/dL)
+const OBSTRUCTIVE_BILIRUBIN_MG_DL: f64 = 12.0;
+
+/// Time constant of conjugated bilirubin accumulating behind, and clearing after, an obstruction (s)
+const CHOLESTATIC_BILIRUBIN_S: f64 = 172_800.0;
+
+/// Normal alkaline phosphatase (U/L)
+const NORMAL_ALP_U_L: f64 = 70.0;
+
+/// Alkaline phosphatase induced by complete biliary obstruction, as a multiple of normal
+const OBSTRUCTIVE_ALP_INDUCTION: f64 = 4.0;
+
+/// Time constant of alkaline phosphatase induction and clearance (s)
+const ALP_TURNOVER_S: f64 = 172_800.0;
+
+/// Acinar zone of the liver lobule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HepaticZone {
+    /// Periportal: first to receive oxygenated blood
+    Periportal,
+    /// Midzonal
+    Midzonal,
+    /// Centrilobular (pericentral): last in line for oxygen
+    Centrilobular,
+}
+
+impl HepaticZone {
+    /// All acinar zones, from portal tract to central vein
+    pub const ALL: [HepaticZone; 3] = [HepaticZone::Periportal, HepaticZone::Midzonal, HepaticZone::Centrilobular];
 }
 
 /// Liver organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Liver {
     id: OrganId,
-    /// Hepatic lobules
-    pub lobules: Vec<HepaticLobule>,
+    /// Perfusion and viability of each acinar zone, indexed by `HepaticZone`
+    pub zones: [TissuePerfusion; 3],
+    /// Total hepatic blood flow (mL/min)
+    pub hepatic_blood_flow_ml_min: f64,
+    /// Flow through the hepatic artery relative to normal
+    pub hepatic_arterial_flow: f64,
+    /// Portal venous inflow relative to normal
+    pub portal_venous_flow: f64,
+    /// Fraction of normal flow the hepatic artery can carry, set from the vascular system
+    pub hepatic_artery_patency: f64,
+    /// Fraction of normal flow the portal vein can carry, set from the vascular system
+    pub portal_vein_patency: f64,
+    /// Hepatic venous (wedged outflow) pressure, set from central venous pressure (mmHg)
+    pub hepatic_venous_pressure_mmhg: f64,
+    /// Portal venous pressure (mmHg)
+    pub portal_pressure_mmhg: f64,
     /// Bile production rate (mL/min)
     pub bile_production_rate: f64,
     /// Glucose production rate (gluconeogenesis, mg/min)
     pub glucose_production_rate: f64,
+    /// Triglyceride level after an overnight fast (mg/dL)
+    pub fasting_triglycerides_mg_dl: f64,
     /// ALT enzyme level (U/L)
     pub alt_level: f64,
     /// AST enzyme level (U/L)
     pub ast_level: f64,
     /// Bilirubin level (mg/dL)
     pub bilirubin_level: f64,
+    /// Conjugated bilirubin regurgitated into the plasma from obstructed bile ducts (mg/dL)
+    pub cholestatic_bilirubin: f64,
+    /// Alkaline phosphatase level (U/L)
+    pub alp_level: f64,
+    /// Obstruction of the common bile duct, set from the gallbladder (0.0 = patent, 1.0 = complete)
+    pub biliary_obstruction: f64,
     /// Angiotensinogen production (AU/min)
     pub angiotensinogen_production: f64,
+    /// Acute hepatocellular injury (0.0 = none, 1.0 = severe), e.g. HELLP syndrome
+    pub hepatocellular_injury: f64,
+    /// Ongoing chronic injury driving fibrosis (0.0 = none, 1.0 = heavy alcohol use or active hepatitis)
+    pub chronic_injury: f64,
+    /// Hepatic fibrosis (0.0 = none, >= 0.8 = cirrhosis)
+    pub fibrosis: f64,
+    /// Laboratory INR used in the MELD score, set from the blood
+    pub inr: f64,
 }
 
 impl Liver {
     /// Create new liver
     pub fn new(id: OrganId) -> Self {
-        let mut lobules = Vec::new();
-        for _ in 0..1000 {
-            lobules.push(HepaticLobule {
-                metabolic_capacity: 1.0,
-            });
-        }
+        // Centrilobular hepatocytes sit at the end of the oxygen gradient and die first
+        let zones = [
+            TissuePerfusion::new(0.2, 0.1, 28_800.0),
+            TissuePerfusion::new(0.3, 0.15, 14_400.0),
+            TissuePerfusion::new(0.4, 0.2, 7200.0),
+        ];
 
         Self {
             id,
-            lobules,
+            zones,
+            hepatic_blood_flow_ml_min: NORMAL_HEPATIC_BLOOD_FLOW_ML_MIN,
+            hepatic_arterial_flow: 1.0,
+            portal_venous_flow: 1.0,
+            hepatic_artery_patency: 1.0,
+            portal_vein_patency: 1.0,
+            hepatic_venous_pressure_mmhg: 5.0,
+            portal_pressure_mmhg: 5.0 + NORMAL_HVPG_MMHG,
             bile_production_rate: 40.0,
             glucose_production_rate: 100.0,
+            fasting_triglycerides_mg_dl: 100.0,
             alt_level: 20.0,
             ast_level: 20.0,
             bilirubin_level: 0.5,
+            cholestatic_bilirubin: 0.0,
+            alp_level: NORMAL_ALP_U_L,
+            biliary_obstruction: 0.0,
             angiotensinogen_production: 10.0,
+            hepatocellular_injury: 0.0,
+            chronic_injury: 0.0,
+            fibrosis: 0.0,
+            inr: 1.0,
         }
     }
 
-    /// Calculate average metabolic capacity
-    fn average_capacity(&self) -> f64 {
-        let total: f64 = self.lobules.iter().map(|l| l.metabolic_capacity).sum();
-        total / self.lobules.len() as f64
+    /// Get an acinar zone
+    pub fn zone(&self, zone: HepaticZone) -> &TissuePerfusion {
+        &self.zones[zone as usize]
+    }
+
+    /// Fraction of normal hepatocyte mass lost to necrosis (0.0-1.0)
+    pub fn necrosis(&self) -> f64 {
+        self.zones.iter().map(|z| z.injury).sum::<f64>() / self.zones.len() as f64
     }
 
-    /// Inflict damage to lobules
+    /// Fraction of normal liver function available (0.0-1.0)
+    ///
+    /// Living, perfused hepatocytes in each zone, reduced by cirrhotic loss of
+    /// architecture and by acute hepatocellular injury.
+    pub fn functional_capacity(&self) -> f64 {
+        let zonal = self.zones.iter().map(|z| z.function()).sum::<f64>() / self.zones.len() as f64;
+        zonal * (1.0 - 0.7 * self.fibrosis.powi(2)) * (1.0 - 0.5 * self.hepatocellular_injury)
+    }
+
+    /// Fraction of glucose absorbed from the gut taken up by the liver before
+    /// it reaches the systemic circulation
+    pub fn first_pass_glucose_extraction(&self) -> f64 {
+        FIRST_PASS_GLUCOSE_EXTRACTION * self.functional_capacity() * self.portal_venous_flow.min(1.0)
+    }
+
+    /// Inflict toxic necrosis (e.g. acetaminophen overdose)
+    ///
+    /// # Arguments
+    /// * `damage_percent` - Percentage of surviving hepatocytes killed
     pub fn inflict_damage(&mut self, damage_percent: f64) {
-        let num_to_damage = (self.lobules.len() as f64 * damage_percent / 100.0) as usize;
-        for i in 0..num_to_damage.min(self.lobules.len()) {
-            self.lobules[i].metabolic_capacity *= 0.5;
+        let fraction = (damage_percent / 100.0).clamp(0.0, 1.0);
+        for zone in &mut self.zones {
+            zone.injury += (1.0 - zone.injury) * fraction;
         }
     }
 
+    /// Get the METAVIR fibrosis stage (F0-F4)
+    pub fn fibrosis_stage(&self) -> u8 {
+        ((self.fibrosis / CIRRHOSIS_FIBROSIS * 4.0).floor() as u8).min(4)
+    }
+
+    /// Check for cirrhosis
+    pub fn is_cirrhotic(&self) -> bool {
+        self.fibrosis >= CIRRHOSIS_FIBROSIS
+    }
+
+    /// Check for ischemic hepatitis (shock liver): AST in the thousands with centrilobular necrosis
+    pub fn is_shock_liver(&self) -> bool {
+        self.ast_level > 1000.0 && self.zone(HepaticZone::Centrilobular).injury > 0.1
+    }
+
+    /// Get the hepatic venous pressure gradient (portal minus hepatic venous pressure, mmHg)
+    pub fn hepatic_venous_pressure_gradient(&self) -> f64 {
+        (self.portal_pressure_mmhg - self.hepatic_venous_pressure_mmhg).max(0.0)
+    }
+
+    /// Check for portal hypertension (HVPG > 5 mmHg)
+    pub fn is_portal_hypertension(&self) -> bool {
+        self.hepatic_venous_pressure_gradient() > 5.0
+    }
+
+    /// Check for hepatic synthetic failure
+    ///
+    /// Below ~45% function clotting factor synthesis alone raises the INR to 1.5,
+    /// whatever anticoagulants are also doing to it.
+    pub fn is_liver_failure(&self) -> bool {
+        self.functional_capacity() < 0.45
+    }
+
+    /// Conjugated (direct) bilirubin (mg/dL)
+    pub fn direct_bilirubin(&self) -> f64 {
+        0.3 * (self.bilirubin_level - self.cholestatic_bilirubin) + self.cholestatic_bilirubin
+    }
+
+    /// Check whether bile backing up behind an obstructed duct has made the patient jaundiced
+    pub fn is_obstructive_jaundice(&self) -> bool {
+        self.biliary_obstruction > 0.5 && self.cholestatic_bilirubin > 2.0
+    }
+
+    /// Calculate the MELD score
+    ///
+    /// # Arguments
+    /// * `creatinine_mg_dl` - Serum creatinine (mg/dL)
+    pub fn meld_score(&self, creatinine_mg_dl: f64) -> f64 {
+        let bilirubin = self.bilirubin_level.max(1.0);
+        let inr = self.inr.max(1.0);
+        let creatinine = creatinine_mg_dl.clamp(1.0, 4.0);
+        (3.78 * bilirubin.ln() + 11.2 * inr.ln() + 9.57 * creatinine.ln() + 6.43).round()
+    }
+
     /// Get angiotensinogen level
     pub fn get_angiotensinogen(&self) -> f64 {
         self.angiotensinogen_production
     }
+
+    /// Update hepatic blood flow and zonal perfusion
+    ///
+    /// # Arguments
+    /// * `patient` - The patient
+    /// * `delta_time_s` - Time step in seconds
+    fn update_perfusion(&mut self, patient: &Patient, delta_time_s: f64) {
+        let map = patient.blood.get_mean_arterial_pressure();
+        // Splanchnic vessels constrict early in shock
+        let splanchnic_perfusion = ((map - 30.0) / 65.0).clamp(0.0, 1.2);
+        // Cirrhotic architecture resists portal flow, diverting it through collaterals
+        self.portal_venous_flow = self.portal_vein_patency * splanchnic_perfusion * (1.0 - 0.5 * self.fibrosis);
+        // The hepatic artery dilates when portal flow falls (hepatic arterial buffer response)
+        let buffer = 1.0 + 0.5 * (1.0 - self.portal_venous_flow).clamp(0.0, 1.0);
+
+        // Splanchnic inflow meets fibrotic sinusoids (intrahepatic) or a thrombosed
+        // portal vein (prehepatic); falling splanchnic flow in shock lowers the pressure
+        let sinusoidal_resistance = 1.0 + 4.0 * self.fibrosis.powi(2);
+        let prehepatic_gradient = 16.0 * (1.0 - self.portal_vein_patency).clamp(0.0, 1.0);
+        self.portal_pressure_mmhg = self.hepatic_venous_pressure_mmhg
+            + (NORMAL_HVPG_MMHG * sinusoidal_resistance + prehepatic_gradient) * splanchnic_perfusion;
+
+        self.hepatic_arterial_flow = self.hepatic_artery_patency * splanchnic_perfusion * buffer;
+        self.hepatic_blood_flow_ml_min = NORMAL_HEPATIC_BLOOD_FLOW_ML_MIN
+            * (HEPATIC_ARTERIAL_FLOW_FRACTION * self.hepatic_arterial_flow
+                + (1.0 - HEPATIC_ARTERIAL_FLOW_FRACTION) * self.portal_venous_flow);
+
+        // Each inflow carries about half the liver's oxygen
+        let oxygen_weighted_flow = HEPATIC_ARTERIAL_OXYGEN_FRACTION * self.hepatic_arterial_flow
+            + (1.0 - HEPATIC_ARTERIAL_OXYGEN_FRACTION) * self.portal_venous_flow;
+        let oxygen_content = (patient.blood.get_sao2_percent() / 98.0).clamp(0.0, 1.0);
+        let regeneration = (1.0 - (-delta_time_s / REGENERATION_S).exp()) * (1.0 - self.fibrosis);
+        let ischemic_time = patient.difficulty.ischemic_time_s(delta_time_s);
+        for zone in &mut self.zones {
+            zone.update(oxygen_weighted_flow, oxygen_content, ischemic_time);
+            // Perfused hepatocytes divide to replace necrotic tissue
+            if zone.oxygen_supply_ratio >= zone.dysfunction_threshold {
+                zone.injury -= zone.injury * regeneration;
+            }
+        }
+    }
 }
 
 impl Organ for Liver {
     fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
-        let capacity = self.average_capacity();
+        let necrosis_before = self.necrosis();
+        self.update_perfusion(patient, delta_time_s);
+        let new_necrosis = (self.necrosis() - necrosis_before).max(0.0);
+
+        // Chronic injury lays down fibrosis over years; it regresses slowly once injury stops
+        let chronic = self.chronic_injury.clamp(0.0, 1.0);
+        self.fibrosis += (chronic * (1.0 - self.fibrosis) - 0.1 * (1.0 - chronic) * self.fibrosis)
+            * FIBROSIS_RATE_PER_S
+            * delta_time_s;
+        self.fibrosis = self.fibrosis.clamp(0.0, 1.0);
+
+        let capacity = self.functional_capacity();
 
         // Bile production
         self.bile_production_rate = 40.0 * capacity;
 
         // Glucose production (when blood glucose is low)
-        if patient.blood.chemistry.glucose_mg_dl < 80.0 {
+        if patient.blood.get_glucose_mg_dl() < 80.0 {
             let glucose_produced = self.glucose_production_rate * capacity * delta_time_s / 60.0;
-            patient.blood.chemistry.glucose_mg_dl += glucose_produced * 0.01;
+            patient.blood.set_glucose_mg_dl(patient.blood.get_glucose_mg_dl() + glucose_produced * 0.01);
         }
 
+        // Chylomicrons from a meal are cleared back to the fasting level
+        let triglycerides = &mut patient.blood.chemistry.triglycerides_mg_dl;
+        *triglycerides += (self.fasting_triglycerides_mg_dl - *triglycerides)
+            * (1.0 - (-delta_time_s / TRIGLYCERIDE_CLEARANCE_S).exp());
+
         // Detoxification - remove toxins from blood
         // Liver can clear approximately 1-2 toxin units per second at full capacity
         let detox_rate = 1.5 * capacity * delta_time_s;
         patient.blood.chemistry.toxin_level_au = (patient.blood.chemistry.toxin_level_au - detox_rate).max(0.0);
 
-        // Enzyme levels increase with damage - update both local and blood values
-        self.alt_level = 20.0 + (1.0 - capacity) * 200.0;
-        self.ast_level = 20.0 + (1.0 - capacity) * 180.0;
+        // Lactate is taken up for gluconeogenesis (Cori cycle)
+        let lactate = patient.blood.get_lactate_mmol_l();
+        let clearance = capacity * (self.hepatic_blood_flow_ml_min / NORMAL_HEPATIC_BLOOD_FLOW_ML_MIN).min(1.0);
+        let cleared = (lactate - 1.0).max(0.0) * (1.0 - (-delta_time_s * clearance / 1800.0).exp());
+        patient.blood.set_lactate_mmol_l(lactate - cleared);
+
+        // Dying hepatocytes release their enzymes at once; inflamed ones leak steadily.
+        // Plasma AST clears faster than ALT, so AST falls first after shock liver.
+        let ast_leak = 20.0 + 60.0 * chronic + 500.0 * self.hepatocellular_injury;
+        let alt_leak = 20.0 + 80.0 * chronic + 400.0 * self.hepatocellular_injury;
+        self.ast_level += new_necrosis * AST_PER_NECROSIS
+            + (ast_leak - self.ast_level) * (1.0 - (-delta_time_s / AST_CLEARANCE_S).exp());
+        self.alt_level += new_necrosis * ALT_PER_NECROSIS
+            + (alt_leak - self.alt_level) * (1.0 - (-delta_time_s / ALT_CLEARANCE_S).exp());
         patient.blood.chemistry.alt_u_l = self.alt_level;
         patient.blood.chemistry.ast_u_l = self.ast_level;
 
-        // Bilirubin increases with damage - update both local and blood values
-        self.bilirubin_level = 0.5 + (1.0 - capacity) * 5.0;
+        // Failing hepatocytes cannot conjugate and excrete bilirubin (~30% of it direct);
+        // conjugated bilirubin that cannot drain down an obstructed duct regurgitates into the plasma
+        let obstruction = self.biliary_obstruction.clamp(0.0, 1.0);
+        let bilirubin_target = 0.5 + 4.0 * (1.0 - capacity).powf(1.5) + 2.0 * self.hepatocellular_injury;
+        let mut hepatic_bilirubin = self.bilirubin_level - self.cholestatic_bilirubin;
+        hepatic_bilirubin += (bilirubin_target - hepatic_bilirubin) * (1.0 - (-delta_time_s / 43_200.0).exp());
+        self.cholestatic_bilirubin += (OBSTRUCTIVE_BILIRUBIN_MG_DL * obstruction - self.cholestatic_bilirubin)
+            * (1.0 - (-delta_time_s / CHOLESTATIC_BILIRUBIN_S).exp());
+        self.bilirubin_level = hepatic_bilirubin + self.cholestatic_bilirubin;
         patient.blood.chemistry.bilirubin_total_mg_dl = self.bilirubin_level;
-        patient.blood.chemistry.bilirubin_direct_mg_dl = self.bilirubin_level * 0.3; // ~30% is direct
+        patient.blood.chemistry.bilirubin_direct_mg_dl = self.direct_bilirubin();
+
+        // Bile acids backing up induce alkaline phosphatase in the canalicular membrane
+        let alp_target = NORMAL_ALP_U_L * (1.0 + OBSTRUCTIVE_ALP_INDUCTION * obstruction);
+        self.alp_level += (alp_target - self.alp_level) * (1.0 - (-delta_time_s / ALP_TURNOVER_S).exp());
+        patient.blood.chemistry.alp_u_l = self.alp_level;
+
+        // Synthetic function: albumin falls over weeks (clotting factors are tracked by coagulation)
+        let albumin = &mut patient.blood.chemistry.albumin_g_dl;
+        *albumin += (1.5 + 2.5 * capacity - *albumin) * (1.0 - (-delta_time_s / ALBUMIN_TURNOVER_S).exp());
 
         // Angiotensinogen production (RAAS system)
         self.angiotensinogen_production = 10.0 * capacity;
     }
 
     fn get_summary(&self) -> String {
+        let fibrosis = if self.is_cirrhotic() {
+            ", CIRRHOSIS".to_string()
+        } else if self.fibrosis_stage() > 0 {
+            format!(", Fibrosis F{}", self.fibrosis_stage())
+        } else {
+            String::new()
+        };
+        let portal = if self.is_portal_hypertension() {
+            format!(", PORTAL HTN (HVPG {:.0} mmHg)", self.hepatic_venous_pressure_gradient())
+        } else {
+            String::new()
+        };
         format!(
-            "Liver: Bile={:.0} mL/min, ALT={:.0} U/L, AST={:.0} U/L, Bili={:.1} mg/dL",
+            "Liver: Flow={:.0} mL/min, Bile={:.0} mL/min, ALT={:.0} U/L, AST={:.0} U/L, Bili={:.1} mg/dL, Function={:.0}%{}{}{}{}{}",
+            self.hepatic_blood_flow_ml_min,
             self.bile_production_rate,
             self.alt_level,
             self.ast_level,
-            self.bilirubin_level
+            self.bilirubin_level,
+            self.functional_capacity() * 100.0,
+            if self.is_shock_liver() { ", SHOCK LIVER" } else { "" },
+            fibrosis,
+            portal,
+            if self.is_obstructive_jaundice() { ", OBSTRUCTIVE JAUNDICE" } else { "" },
+            if self.is_liver_failure() { ", LIVER FAILURE" } else { "" }
         )
     }
 
@@ -131,4 +441,42 @@ impl Organ for Liver {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_shock_kills_centrilobular_hepatocytes_first_and_synthetic_function_fails() {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        let liver = patient.get_organ::<Liver>().unwrap();
+        assert!(!liver.is_shock_liver() && !liver.is_liver_failure());
+
+        // Untreated hemorrhagic shock: the zone last in line for oxygen dies first
+        patient.hemorrhage(0.6 * patient.fluids.reference_blood_volume_ml);
+        for _ in 0..360 {
+            update_patient(&mut patient, 10.0);
+        }
+        let liver = patient.get_organ::<Liver>().unwrap();
+        assert!(liver.zone(HepaticZone::Centrilobular).injury > liver.zone(HepaticZone::Periportal).injury);
+        assert!(liver.is_shock_liver());
+
+        // Massive toxic necrosis leaves too few hepatocytes to make clotting factors
+        let mut overdose = initialize_patient(1, 3);
+        overdose.get_organ_mut::<Liver>().unwrap().inflict_damage(80.0);
+        for _ in 0..4320 {
+            update_patient(&mut overdose, 10.0);
+        }
+        assert!(overdose.get_organ::<Liver>().unwrap().is_liver_failure());
+        assert!(overdose.blood.clotting.inr >= 1.5);
+    }
 }
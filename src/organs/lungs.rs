@@ -4,11 +4,174 @@
 //! - 5 lobes (right upper/middle/lower, left upper/lower)
 //! - Respiratory cycle (inspiration, expiration, pause)
 //! - Oxygen saturation
-//! - CO2 exchange and capnography
+//! - CO2 balance: metabolic production against alveolar ventilation, and capnography
+//! - Pleural spaces (pneumothorax, tension physiology, effusion, decompression)
+//! - Alveolar-capillary membrane (ARDS), shunt fraction and A-a gradient
+//! - Mechanical ventilation with FiO2 and PEEP, and ventilator-induced lung
+//!   injury from tidal volumes above 8 mL/kg ideal body weight or plateau
+//!   pressures above 30 cmH2O
+//! - Ventilator complications as chance events: barotrauma (tension pneumothorax)
+//!   at high plateau pressures, including those raised by auto-PEEP, and
+//!   ventilator-associated pneumonia with a risk that accrues with intubation days
+//! - Weaning: diaphragm atrophy from disuse on controlled ventilation,
+//!   respiratory muscle fatigue when the work of breathing outstrips muscle
+//!   strength, the rapid shallow breathing index, spontaneous breathing trials
+//!   with standard failure criteria, and extubation with chance post-extubation
+//!   stridor and failure from retained secretions
+//! - Tracheostomy: less dead space than the upper airway, secretions that plug
+//!   the tube unless coughed or suctioned out, a stoma tract that matures over a
+//!   week, decannulation, and dislodgement that disconnects the ventilator
+//! - Airway resistance (bronchospasm, COPD), air trapping and auto-PEEP
+//! - Lung and chest wall compliance in series, and rib fractures: pain that
+//!   limits each breath until relieved by analgesia, and the paradoxical
+//!   movement of a flail segment during spontaneous breathing, which positive
+//!   pressure ventilation splints
+//! - Functional residual capacity lowered by a stiff or loaded chest wall
+//!   (obesity) and by anesthesia; below the closing capacity dependent airways
+//!   close into a shunt that PEEP reopens
+//! - Upper airway collapse in a sedated or obtunded patient with obstructive
+//!   sleep apnea, and a chance that an intubation attempt fails in a difficult airway
+//! - Alveolar dead space from unperfused lung (pulmonary embolism)
+//! - Cardiogenic pulmonary edema from raised capillary (left atrial) pressure,
+//!   stiffening the lungs into rapid, shallow breathing
 
+use crate::fluids::OutputRoute;
+use crate::models::starling;
 use crate::organ::{Organ, OrganId};
 use crate::patient::Patient;
-use std::collections::VecDeque;
+use crate::sepsis::BundleGuideline;
+use crate::waveform::{RingBuffer, WaveformSampler};
+
+/// Pulmonary interstitial hydrostatic pressure (mmHg)
+const PULMONARY_INTERSTITIAL_PRESSURE_MMHG: f64 = -2.0;
+
+/// Pulmonary interstitial oncotic pressure, high from the protein the lung capillaries leak (mmHg)
+const PULMONARY_INTERSTITIAL_ONCOTIC_MMHG: f64 = 18.0;
+
+/// Reflection coefficient of the pulmonary capillary wall for plasma protein
+const PULMONARY_REFLECTION_COEFFICIENT: f64 = 0.7;
+
+/// Net filtration pressure the pulmonary lymphatics can keep up with (mmHg),
+/// reached at a capillary pressure of ~18 mmHg with normal plasma protein
+const LYMPHATIC_CLEARANCE_FILTRATION_MMHG: f64 = 14.7;
+
+/// Plasma globulins, added to albumin for total protein (g/dL)
+const PLASMA_GLOBULIN_G_DL: f64 = 3.0;
+
+/// Arterial CO2 at resting production and ventilation (mmHg)
+const RESTING_ARTERIAL_CO2_MMHG: f64 = 38.0;
+
+/// Anatomic dead space as a fraction of the resting tidal volume (~150 mL of 500 mL)
+const ANATOMIC_DEAD_SPACE_FRACTION: f64 = 0.3;
+
+/// Time constant of the body's CO2 stores at resting ventilation (s); apnea
+/// raises PaCO2 by ~13 mmHg in the first minute
+const CO2_STORE_TIME_CONSTANT_S: f64 = 180.0;
+
+/// Highest PaCO2 the model reaches (mmHg)
+const MAX_ARTERIAL_CO2_MMHG: f64 = 200.0;
+
+/// Lung-protective tidal volume (mL/kg ideal body weight)
+pub const LUNG_PROTECTIVE_TIDAL_VOLUME_ML_KG: f64 = 6.0;
+
+/// Largest tidal volume that does not overstretch the lung (mL/kg ideal body weight)
+const SAFE_TIDAL_VOLUME_ML_KG: f64 = 8.0;
+
+/// Largest plateau pressure that does not overstretch the lung (cmH2O)
+const SAFE_PLATEAU_PRESSURE_CMH2O: f64 = 30.0;
+
+/// Time for overstretch of one unit (4 mL/kg or 10 cmH2O beyond the safe limit)
+/// to inflame the alveoli as much as severe ARDS (s)
+const VENTILATOR_INJURY_S: f64 = 86_400.0;
+
+/// Chance per hour of a pneumothorax for each cmH2O of plateau pressure above 30
+const BAROTRAUMA_RISK_PER_HOUR_PER_CMH2O: f64 = 0.005;
+
+/// Air leak from a lung ruptured under positive pressure, enough for tension (mL/min)
+const BAROTRAUMA_AIR_LEAK_ML_MIN: f64 = 100.0;
+
+/// Rapid shallow breathing index above which weaning is likely to fail (breaths/min/L)
+pub const RSBI_WEANING_THRESHOLD: f64 = 105.0;
+
+/// Length of a spontaneous breathing trial (s)
+const BREATHING_TRIAL_S: f64 = 1800.0;
+
+/// Time into a breathing trial at which the breathing pattern has settled and
+/// the failure criteria apply (s)
+const BREATHING_TRIAL_SETTLING_S: f64 = 120.0;
+
+/// Respiratory muscle load, relative to a healthy patient at rest, that the muscles sustain without tiring
+const SUSTAINABLE_RESPIRATORY_LOAD: f64 = 2.5;
+
+/// Time for a load of twice the sustainable work to exhaust the respiratory muscles (s)
+const RESPIRATORY_FATIGUE_S: f64 = 1800.0;
+
+/// Time constant of recovery from respiratory muscle fatigue at rest (s)
+const RESPIRATORY_FATIGUE_RECOVERY_S: f64 = 43_200.0;
+
+/// Time constant of diaphragm atrophy while the ventilator does all the breathing (s)
+const DIAPHRAGM_ATROPHY_S: f64 = 1_209_600.0;
+
+/// Time constant of diaphragm recovery once breathing spontaneously (s)
+const DIAPHRAGM_RECOVERY_S: f64 = 604_800.0;
+
+/// Airway resistance added by severe laryngeal edema (relative to normal)
+const LARYNGEAL_EDEMA_RESISTANCE: f64 = 6.0;
+
+/// Time constant of laryngeal edema subsiding after extubation (s)
+const LARYNGEAL_EDEMA_RESOLUTION_S: f64 = 21_600.0;
+
+/// Fraction of the anatomic dead space left when a tracheostomy bypasses the upper airway
+const TRACHEOSTOMY_DEAD_SPACE_FRACTION: f64 = 0.5;
+
+/// Time for a tracheostomy stoma to form a tract the tube can be reinserted through (s)
+const STOMA_MATURATION_S: f64 = 604_800.0;
+
+/// Time for secretions to occlude a tracheostomy tube without suction or an effective cough (s)
+const TRACHEOSTOMY_SECRETION_S: f64 = 43_200.0;
+
+/// Airway resistance added by secretions nearly occluding a tracheostomy tube (relative to normal)
+const TRACHEOSTOMY_SECRETION_RESISTANCE: f64 = 20.0;
+
+/// Chance per day that a tracheostomy tube is pulled out of the trachea
+const TRACHEOSTOMY_DISLODGEMENT_RISK_PER_DAY: f64 = 0.01;
+
+/// Normal lung and chest wall compliance per mL of resting tidal volume; in series they
+/// give the normal respiratory system compliance (~50 mL/cmH2O in an adult)
+const NORMAL_COMPLIANCE_PER_TIDAL_ML: f64 = 0.2;
+
+/// Fractured ribs whose pain, unrelieved, limits breathing as much as it can
+const PAINFUL_RIB_FRACTURES: f64 = 6.0;
+
+/// Fraction of a spontaneous breath lost to splinting at maximal rib pain
+const RIB_PAIN_SPLINTING: f64 = 0.25;
+
+/// Fraction of a spontaneous breath lost to a flail segment sucked inwards on inspiration
+const FLAIL_PARADOXICAL_FRACTION: f64 = 0.15;
+
+/// Normal supine functional residual capacity per mL of resting tidal volume
+const NORMAL_FRC_PER_TIDAL_ML: f64 = 5.0;
+
+/// Closing capacity as a fraction of the normal functional residual capacity
+const CLOSING_CAPACITY_FRACTION: f64 = 0.75;
+
+/// Fraction of the functional residual capacity lost without diaphragmatic tone (anesthesia, paralysis)
+const ANESTHESIA_FRC_LOSS: f64 = 0.15;
+
+/// Shunt through dependent lung whose airways are all closed at end expiration
+const AIRWAY_CLOSURE_SHUNT: f64 = 0.4;
+
+/// Chance that the first attempt at intubation fails in a patient with no predictors of difficulty
+pub const BASELINE_DIFFICULT_AIRWAY_RISK: f64 = 0.05;
+
+/// Capnography sampling rate (Hz)
+const CAPNOGRAPHY_SAMPLE_RATE_HZ: f64 = 25.0;
+
+/// Length of capnogram kept in the waveform buffer (s)
+const CAPNOGRAPHY_BUFFER_S: f64 = 40.0;
+
+/// Peak expiratory flow per mL of resting tidal volume with normal airways (L/min)
+const PEAK_FLOW_PER_TIDAL_ML: f64 = 1.0;
 
 /// Respiratory phase
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -27,15 +190,252 @@ pub struct Lobe {
     pub ventilation_rate: f64,
 }
 
+/// Side of the chest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Breath sounds on auscultation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreathSounds {
+    Normal,
+    Wheezing,
+    Decreased,
+    Absent,
+}
+
+/// Pleural space between the lung and the chest wall
+#[derive(Debug, Clone)]
+pub struct PleuralSpace {
+    /// Air in the pleural space (mL)
+    pub air_ml: f64,
+    /// Fluid in the pleural space (mL)
+    pub fluid_ml: f64,
+    /// Air leaking from the lung into the pleural space (mL/min)
+    /// A one-way (flap valve) leak causes a tension pneumothorax
+    pub air_leak_ml_min: f64,
+    /// Pleural pressure (cmH2O); normally about -5
+    pub pressure_cmh2o: f64,
+    /// Fraction of the lung on this side that has collapsed (0.0-1.0)
+    pub collapse_fraction: f64,
+    /// A decompression needle is venting air to the atmosphere
+    pub needle_decompressed: bool,
+    /// A chest tube on suction is draining the space
+    pub chest_tube: bool,
+    /// Fluid drained by the chest tube since last collected (mL)
+    drained_fluid_ml: f64,
+}
+
+impl PleuralSpace {
+    fn new() -> Self {
+        Self {
+            air_ml: 0.0,
+            fluid_ml: 0.0,
+            air_leak_ml_min: 0.0,
+            pressure_cmh2o: -5.0,
+            collapse_fraction: 0.0,
+            needle_decompressed: false,
+            chest_tube: false,
+            drained_fluid_ml: 0.0,
+        }
+    }
+
+    /// Whether pleural pressure is high enough to compress the mediastinum
+    pub fn is_tension(&self) -> bool {
+        self.pressure_cmh2o > 5.0
+    }
+
+    /// Advance leak, drainage and resorption, then recompute pressure and collapse
+    ///
+    /// # Arguments
+    /// * `lung_volume_ml` - Volume of the lung on this side (space available before tension)
+    /// * `delta_time_s` - Time step in seconds
+    fn update(&mut self, lung_volume_ml: f64, delta_time_s: f64) {
+        self.air_ml += self.air_leak_ml_min * delta_time_s / 60.0;
+
+        // Pleural air is resorbed at ~1.5% per day
+        self.air_ml *= (-0.015 * delta_time_s / 86_400.0).exp();
+
+        // Chest tube suction evacuates air and fluid; the lung re-expands
+        if self.chest_tube {
+            self.air_ml = (self.air_ml - 2000.0 * delta_time_s / 60.0).max(0.0);
+            let drained = self.fluid_ml.min(500.0 * delta_time_s / 60.0);
+            self.fluid_ml -= drained;
+            self.drained_fluid_ml += drained;
+        }
+
+        // A decompression needle vents air until pleural pressure is atmospheric
+        // (relieves tension but leaves the lung collapsed)
+        if self.needle_decompressed {
+            let excess = self.air_ml + self.fluid_ml - lung_volume_ml;
+            if excess > 0.0 {
+                self.air_ml = (self.air_ml - excess).max(0.0);
+            }
+        }
+
+        // Collapse is proportional to occupied volume; once the lung is fully
+        // collapsed, further air raises pressure steeply
+        let occupied = self.air_ml + self.fluid_ml;
+        self.collapse_fraction = (occupied / lung_volume_ml.max(1.0)).min(1.0);
+        self.pressure_cmh2o = -5.0 + 5.0 * self.collapse_fraction
+            + (occupied - lung_volume_ml).max(0.0) * 0.02;
+    }
+}
+
+/// Mechanical ventilator settings
+#[derive(Debug, Clone)]
+pub struct Ventilator {
+    /// Fraction of inspired oxygen (0.21-1.0)
+    pub fio2: f64,
+    /// Positive end-expiratory pressure (cmH2O)
+    pub peep_cmh2o: f64,
+    /// Set tidal volume (mL)
+    pub tidal_volume_ml: f64,
+    /// Set respiratory rate (breaths/min)
+    pub rate_bpm: f64,
+    /// Plateau pressure (cmH2O), measured during an inspiratory hold
+    pub plateau_pressure_cmh2o: f64,
+}
+
+impl Ventilator {
+    /// Create a volume-control ventilator set to a lung-protective 6 mL/kg ideal body weight
+    ///
+    /// # Arguments
+    /// * `fio2` - Fraction of inspired oxygen (0.21-1.0)
+    /// * `peep_cmh2o` - PEEP (cmH2O)
+    /// * `ideal_body_weight_kg` - Patient's ideal body weight (kg)
+    /// * `rate_bpm` - Respiratory rate (breaths/min)
+    pub fn lung_protective(fio2: f64, peep_cmh2o: f64, ideal_body_weight_kg: f64, rate_bpm: f64) -> Self {
+        Self::new(fio2, peep_cmh2o, LUNG_PROTECTIVE_TIDAL_VOLUME_ML_KG * ideal_body_weight_kg, rate_bpm)
+    }
+
+    /// Create a volume-control ventilator
+    ///
+    /// # Arguments
+    /// * `fio2` - Fraction of inspired oxygen (0.21-1.0)
+    /// * `peep_cmh2o` - PEEP (cmH2O)
+    /// * `tidal_volume_ml` - Tidal volume (mL)
+    /// * `rate_bpm` - Respiratory rate (breaths/min)
+    pub fn new(fio2: f64, peep_cmh2o: f64, tidal_volume_ml: f64, rate_bpm: f64) -> Self {
+        Self {
+            fio2: fio2.clamp(0.21, 1.0),
+            peep_cmh2o: peep_cmh2o.clamp(0.0, 30.0),
+            tidal_volume_ml: tidal_volume_ml.max(0.0),
+            rate_bpm: rate_bpm.max(0.0),
+            plateau_pressure_cmh2o: 0.0,
+        }
+    }
+}
+
+impl Default for Ventilator {
+    fn default() -> Self {
+        Self::new(0.4, 5.0, 500.0, 16.0)
+    }
+}
+
+/// Reason a spontaneous breathing trial was stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrialFailure {
+    /// Respiratory rate above 35 breaths/min
+    Tachypnea,
+    /// Rapid shallow breathing index above 105
+    RapidShallowBreathing,
+    /// SpO2 below 90%
+    Desaturation,
+    /// pH below 7.32 with PaCO2 risen by 10 mmHg or more
+    RespiratoryAcidosis,
+}
+
+/// Result of a spontaneous breathing trial
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreathingTrialOutcome {
+    /// Tolerated for the full trial; the patient can be extubated
+    Passed,
+    /// Stopped early, and full ventilator support resumed
+    Failed(TrialFailure),
+}
+
+/// Spontaneous breathing trial: the patient breathes on PEEP alone while
+/// still intubated
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreathingTrial {
+    /// Time since the trial started (s)
+    pub elapsed_s: f64,
+    /// PaCO2 on full support before the trial (mmHg)
+    pub baseline_paco2_mmhg: f64,
+    /// Rapid shallow breathing index once the breathing pattern settled (breaths/min/L)
+    pub rsbi: Option<f64>,
+    /// Result, once the trial has ended
+    pub outcome: Option<BreathingTrialOutcome>,
+}
+
+/// Tracheostomy tube
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Tracheostomy {
+    /// Time since the tube was placed (s)
+    pub placed_s: f64,
+    /// Secretions in the tube (0.0 = clear, 1.0 = occluded)
+    pub secretions: f64,
+    /// Tube pulled out of the trachea
+    pub dislodged: bool,
+}
+
+impl Tracheostomy {
+    /// Check whether the stoma has formed a tract the tube can be reinserted through
+    pub fn is_stoma_mature(&self) -> bool {
+        self.placed_s >= STOMA_MATURATION_S
+    }
+
+    /// Fraction of normal airflow the tube passes (0.0 when occluded or out of the trachea)
+    pub fn patency(&self) -> f64 {
+        if self.dislodged {
+            0.0
+        } else {
+            1.0 - self.secretions.clamp(0.0, 1.0).powi(3)
+        }
+    }
+}
+
+/// Complications that followed an extubation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Extubation {
+    /// Laryngeal edema narrows the airway (post-extubation stridor)
+    pub stridor: bool,
+    /// A weak cough leaves secretions that collapse the lower lobes
+    pub retained_secretions: bool,
+}
+
 /// Bronchus
 #[derive(Debug, Clone)]
 pub struct Bronchus {
     pub name: String,
-    pub resistance: f64,  // Airway resistance
+    pub resistance: f64,  // Airway resistance (1.0 = normal)
+    /// Acute smooth muscle constriction (0.0 = none, 1.0 = severe), e.g. asthma
+    pub bronchospasm: f64,
+    /// Fixed airflow obstruction (0.0 = none, 1.0 = severe), e.g. COPD
+    pub chronic_obstruction: f64,
+}
+
+impl Bronchus {
+    /// Update airway resistance
+    ///
+    /// # Arguments
+    /// * `bronchodilation` - Beta-agonist effect (0.0-1.0)
+    /// * `delta_time_s` - Time step in seconds
+    fn update(&mut self, bronchodilation: f64, delta_time_s: f64) {
+        // Untreated spasm eases over hours
+        self.bronchospasm *= (-delta_time_s / 21_600.0).exp();
+
+        // Beta-agonists relax smooth muscle but not fixed obstruction
+        let spasm = self.bronchospasm * (1.0 - 0.85 * bronchodilation);
+        self.resistance = 1.0 + spasm * 11.0 + self.chronic_obstruction * 5.0;
+    }
 }
 
 /// Lungs organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Lungs {
     id: OrganId,
     /// Right upper lobe
@@ -54,16 +454,119 @@ pub struct Lungs {
     pub respiration_rate_bpm: f64,
     /// Tidal volume (mL)
     pub tidal_volume_ml: f64,
+    /// Resting respiratory rate for the patient's age (breaths per minute)
+    pub resting_respiration_rate_bpm: f64,
+    /// Resting tidal volume for the patient's size (mL); compliance scales with it
+    pub resting_tidal_volume_ml: f64,
+    /// Spontaneous respiratory drive (0.0 = apneic, 1.0 = normal)
+    pub respiratory_drive: f64,
+    /// Chemoreflex drive compensating a metabolic acid-base disorder (1.0 = none),
+    /// set from the acid-base balance; deepens and quickens breathing in acidosis
+    pub chemoreflex_drive: f64,
+    /// Output of the brainstem respiratory centres (0.0-1.0), set from the brain; falls with
+    /// brainstem injury and sedation
+    pub brainstem_function: f64,
+    /// Ventilatory drive from the brainstem chemoreceptors (1.0 = rest), set from the
+    /// brain; quickens breathing in hypercapnia and hypoxemia
+    pub ventilatory_drive: f64,
+    /// Strength of the respiratory muscles (0.0 = paralysed, 1.0 = normal), set
+    /// from neuromuscular transmission
+    pub respiratory_muscle_strength: f64,
     /// Oxygen saturation (%)
     pub oxygen_saturation_percent: f64,
     /// End-tidal CO2 (mmHg)
     pub end_tidal_co2_mmhg: f64,
+    /// Arterial CO2 (mmHg); exceeds end-tidal CO2 when there is alveolar dead space
+    pub arterial_co2_mmhg: f64,
+    /// CO2 exhaled (mL/min); matches metabolic production only at steady state
+    pub co2_elimination_ml_min: f64,
     /// Peak inspiratory pressure (cmH2O)
     pub peak_inspiratory_pressure: f64,
-    /// Capnography waveform
-    pub capnography_waveform: VecDeque<f64>,
+    /// Static respiratory system compliance (mL/cmH2O, ~50 in an adult), reduced by
+    /// collapse, consolidation and alveolar edema
+    pub respiratory_compliance_ml_cmh2o: f64,
+    /// Capnography waveform (mmHg), sampled at `CAPNOGRAPHY_SAMPLE_RATE_HZ`
+    pub capnography_waveform: RingBuffer,
+    /// Clock placing the capnography samples
+    pub capnography_sampler: WaveformSampler,
     /// Current respiratory phase
     pub current_phase: RespiratoryPhase,
+    /// Left pleural space
+    pub left_pleura: PleuralSpace,
+    /// Right pleural space
+    pub right_pleura: PleuralSpace,
+    /// Mediastinal shift (cm); positive = toward the left
+    pub mediastinal_shift_cm: f64,
+    /// Alveolar inflammation (0.0 = none, 1.0 = severe), e.g. ARDS
+    pub alveolar_inflammation: f64,
+    /// Pulmonary capillary permeability (0.0-1.0, normal 0.5), set from the vascular system
+    pub capillary_permeability: f64,
+    /// Pulmonary capillary hydrostatic pressure (mmHg, normal ~8), set from the
+    /// heart's left atrial pressure
+    pub pulmonary_capillary_pressure_mmhg: f64,
+    /// Alveolar flooding with edema fluid (0.0-1.0)
+    pub alveolar_edema: f64,
+    /// Fraction of cardiac output passing unventilated alveoli (normal ~0.02)
+    pub shunt_fraction: f64,
+    /// Venous admixture from outside the ventilated lung (intracardiac or ductal
+    /// right-to-left shunts, unaerated fetal lung), set by other systems
+    pub right_to_left_shunt_fraction: f64,
+    /// Fraction of ventilated alveoli without blood flow (e.g. pulmonary embolism),
+    /// set from the vascular system
+    pub alveolar_dead_space_fraction: f64,
+    /// Alveolar-arterial oxygen gradient (mmHg)
+    pub aa_gradient_mmhg: f64,
+    /// Tidal volume per kg ideal body weight (mL/kg)
+    pub tidal_volume_ml_kg_ibw: f64,
+    /// Alveolar inflammation accrued from overstretch on the ventilator (0.0-1.0)
+    pub ventilator_lung_injury: f64,
+    /// Time on the current course of mechanical ventilation (s)
+    pub ventilated_s: f64,
+    /// Inspired oxygen fraction without a ventilator (room air 0.21)
+    pub fio2: f64,
+    /// Mechanical ventilator, if the patient is intubated
+    pub ventilator: Option<Ventilator>,
+    /// Tracheostomy tube, if one is in place (otherwise ventilation is through an endotracheal tube)
+    pub tracheostomy: Option<Tracheostomy>,
+    /// Lung compliance (mL/cmH2O)
+    pub lung_compliance_ml_cmh2o: f64,
+    /// Chest wall compliance relative to normal (1.0; stiffened by obesity, kyphoscoliosis or burns)
+    pub chest_wall_compliance: f64,
+    /// Number of fractured ribs, set from the skeleton
+    pub fractured_ribs: u32,
+    /// Flail segment in the chest wall, set from the skeleton
+    pub flail_segment: bool,
+    /// Relief of chest wall pain by analgesia (0.0 = none, 1.0 = complete, e.g. an epidural)
+    pub analgesia: f64,
+    /// Collapsibility of the pharynx without muscle tone (0.0 = rigid, 1.0 = closes completely;
+    /// raised in obstructive sleep apnea)
+    pub upper_airway_collapsibility: f64,
+    /// Pharyngeal dilator tone, set from arousal and sedation (1.0 = awake)
+    pub upper_airway_tone: f64,
+    /// Chance that an intubation attempt fails (0.0-1.0)
+    pub difficult_airway_risk: f64,
+    /// Inhaled beta-agonist on the airways (mg albuterol equivalent)
+    pub beta_agonist_level_mg: f64,
+    /// End-expiratory volume trapped above FRC by incomplete exhalation (mL)
+    pub trapped_volume_ml: f64,
+    /// Intrinsic PEEP from dynamic hyperinflation (cmH2O)
+    pub auto_peep_cmh2o: f64,
+    /// Load on the respiratory muscles relative to a healthy patient at rest: the ventilation
+    /// the respiratory centre calls for per unit of CO2 produced, against the impedance of the
+    /// lungs and airways, over the force the muscles have left (0.0 on full ventilator support)
+    pub respiratory_load: f64,
+    /// Respiratory muscle fatigue (0.0 = rested, 1.0 = exhausted)
+    pub respiratory_muscle_fatigue: f64,
+    /// Diaphragm atrophy from disuse (0.0 = none, 1.0 = no force)
+    pub diaphragm_atrophy: f64,
+    /// Current or most recent spontaneous breathing trial on this course of ventilation
+    pub breathing_trial: Option<BreathingTrial>,
+    /// Laryngeal edema after extubation (0.0 = none, 1.0 = severe stridor)
+    pub laryngeal_edema: f64,
+    /// Fraction of each respiratory cycle spent in inspiration
+    inspiratory_fraction: f64,
+    /// Nebulized beta-agonist not yet deposited in the airways (mg)
+    pending_beta_agonist_mg: f64,
     /// Internal respiratory cycle timer
     respiratory_cycle_time: f64,
 }
@@ -106,14 +609,62 @@ impl Lungs {
             main_bronchus: Bronchus {
                 name: "Main".to_string(),
                 resistance: 1.0,
+                bronchospasm: 0.0,
+                chronic_obstruction: 0.0,
             },
             respiration_rate_bpm: 16.0,
             tidal_volume_ml: 500.0,
+            resting_respiration_rate_bpm: 16.0,
+            resting_tidal_volume_ml: 500.0,
+            respiratory_drive: 1.0,
+            chemoreflex_drive: 1.0,
+            brainstem_function: 1.0,
+            ventilatory_drive: 1.0,
+            respiratory_muscle_strength: 1.0,
             oxygen_saturation_percent: 98.0,
             end_tidal_co2_mmhg: 38.0,
+            arterial_co2_mmhg: 38.0,
+            co2_elimination_ml_min: 200.0,
             peak_inspiratory_pressure: 15.0,
-            capnography_waveform: VecDeque::with_capacity(1000),
+            respiratory_compliance_ml_cmh2o: 50.0,
+            capnography_waveform: RingBuffer::new((CAPNOGRAPHY_SAMPLE_RATE_HZ * CAPNOGRAPHY_BUFFER_S) as usize),
+            capnography_sampler: WaveformSampler::new(CAPNOGRAPHY_SAMPLE_RATE_HZ, CAPNOGRAPHY_BUFFER_S),
             current_phase: RespiratoryPhase::Pause,
+            left_pleura: PleuralSpace::new(),
+            right_pleura: PleuralSpace::new(),
+            mediastinal_shift_cm: 0.0,
+            alveolar_inflammation: 0.0,
+            capillary_permeability: 0.5,
+            pulmonary_capillary_pressure_mmhg: 8.0,
+            alveolar_edema: 0.0,
+            shunt_fraction: 0.02,
+            right_to_left_shunt_fraction: 0.0,
+            alveolar_dead_space_fraction: 0.0,
+            aa_gradient_mmhg: 8.0,
+            tidal_volume_ml_kg_ibw: 7.0,
+            ventilator_lung_injury: 0.0,
+            ventilated_s: 0.0,
+            fio2: 0.21,
+            ventilator: None,
+            tracheostomy: None,
+            lung_compliance_ml_cmh2o: 100.0,
+            chest_wall_compliance: 1.0,
+            fractured_ribs: 0,
+            flail_segment: false,
+            analgesia: 0.0,
+            upper_airway_collapsibility: 0.0,
+            upper_airway_tone: 1.0,
+            difficult_airway_risk: BASELINE_DIFFICULT_AIRWAY_RISK,
+            beta_agonist_level_mg: 0.0,
+            trapped_volume_ml: 0.0,
+            auto_peep_cmh2o: 0.0,
+            respiratory_load: 1.0,
+            respiratory_muscle_fatigue: 0.0,
+            diaphragm_atrophy: 0.0,
+            breathing_trial: None,
+            laryngeal_edema: 0.0,
+            inspiratory_fraction: 0.4,
+            pending_beta_agonist_mg: 0.0,
             respiratory_cycle_time: 0.0,
         }
     }
@@ -140,85 +691,984 @@ impl Lungs {
             + self.left_lower_lobe.compliance)
             / 5.0
     }
+
+    /// Injure the alveolar-capillary membrane (pneumonia, aspiration, sepsis, trauma)
+    ///
+    /// # Arguments
+    /// * `severity` - 0.0-1.0; alveolar edema develops over the following hours
+    pub fn induce_ards(&mut self, severity: f64) {
+        self.alveolar_inflammation = self.alveolar_inflammation.max(severity.clamp(0.0, 1.0));
+    }
+
+    /// Set supplemental oxygen for a spontaneously breathing patient
+    pub fn set_fio2(&mut self, fio2: f64) {
+        self.fio2 = fio2.clamp(0.21, 1.0);
+    }
+
+    /// Intubate and start mechanical ventilation
+    pub fn attach_ventilator(&mut self, ventilator: Ventilator) {
+        if self.ventilator.is_none() {
+            self.ventilated_s = 0.0;
+            self.breathing_trial = None;
+        }
+        self.ventilator = Some(ventilator);
+    }
+
+    /// Attempt intubation for mechanical ventilation
+    ///
+    /// # Arguments
+    /// * `ventilator` - Ventilator settings once the tube is in
+    /// * `occurs` - Decides whether a failure with the given probability happens
+    ///
+    /// # Returns
+    /// True if the tube was placed; a failed attempt leaves the patient unventilated
+    pub fn intubate_with(&mut self, ventilator: Ventilator, mut occurs: impl FnMut(f64) -> bool) -> bool {
+        if occurs(self.difficult_airway_risk) {
+            return false;
+        }
+        self.attach_ventilator(ventilator);
+        true
+    }
+
+    /// Stop mechanical ventilation
+    pub fn detach_ventilator(&mut self) {
+        self.ventilator = None;
+        self.ventilated_s = 0.0;
+        self.breathing_trial = None;
+        self.respiration_rate_bpm = self.resting_respiration_rate_bpm;
+        self.tidal_volume_ml = self.resting_tidal_volume_ml;
+    }
+
+    /// Ventilator whose breaths reach the lungs: none once a tracheostomy tube is out of the trachea
+    pub fn connected_ventilator(&self) -> Option<&Ventilator> {
+        self.ventilator
+            .as_ref()
+            .filter(|_| !self.tracheostomy.as_ref().is_some_and(|tracheostomy| tracheostomy.dislodged))
+    }
+
+    /// Check whether the patient is drawing their own breaths (not intubated,
+    /// disconnected, or on a breathing trial that has not failed)
+    pub fn is_breathing_spontaneously(&self) -> bool {
+        self.connected_ventilator().is_none()
+            || self.breathing_trial.as_ref().is_some_and(|trial| {
+                !matches!(trial.outcome, Some(BreathingTrialOutcome::Failed(_)))
+            })
+    }
+
+    /// Force the respiratory muscles can generate relative to healthy, rested
+    /// muscles, after weakness, disuse atrophy and fatigue
+    pub fn respiratory_muscle_capacity(&self) -> f64 {
+        self.respiratory_muscle_strength.clamp(0.0, 1.0)
+            * (1.0 - self.diaphragm_atrophy)
+            * (1.0 - 0.6 * self.respiratory_muscle_fatigue)
+    }
+
+    /// Place a tracheostomy, converting an endotracheal tube (ventilation continues
+    /// through it) or creating a surgical airway in an unintubated patient
+    pub fn place_tracheostomy(&mut self) {
+        self.tracheostomy = Some(Tracheostomy::default());
+    }
+
+    /// Clear secretions from the tracheostomy tube
+    pub fn suction_tracheostomy(&mut self) {
+        if let Some(tracheostomy) = self.tracheostomy.as_mut() {
+            tracheostomy.secretions = 0.0;
+        }
+    }
+
+    /// Pull the tracheostomy tube out of the trachea, disconnecting any ventilator
+    pub fn dislodge_tracheostomy(&mut self) {
+        if let Some(tracheostomy) = self.tracheostomy.as_mut() {
+            tracheostomy.dislodged = true;
+        }
+    }
+
+    /// Reinsert a dislodged tracheostomy tube through its stoma
+    ///
+    /// # Returns
+    /// False if the stoma has not yet formed a tract (the patient must be
+    /// intubated from above instead) or no tube is dislodged
+    pub fn recannulate_tracheostomy(&mut self) -> bool {
+        match self.tracheostomy.as_mut() {
+            Some(tracheostomy) if tracheostomy.dislodged && tracheostomy.is_stoma_mature() => {
+                tracheostomy.dislodged = false;
+                tracheostomy.secretions = 0.0;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove the tracheostomy tube from a patient breathing without a ventilator;
+    /// the stoma closes and breathing returns to the upper airway
+    ///
+    /// # Returns
+    /// False if the patient still needs the ventilator or has no tracheostomy
+    pub fn decannulate(&mut self) -> bool {
+        if self.ventilator.is_some() || self.tracheostomy.is_none() {
+            return false;
+        }
+        self.tracheostomy = None;
+        true
+    }
+
+    /// Fraction of normal airflow the airway passes (reduced by a plugged tracheostomy tube)
+    pub fn airway_patency(&self) -> f64 {
+        match &self.tracheostomy {
+            // Out of the trachea, a spontaneously breathing patient breathes through the upper airway
+            Some(tracheostomy) if tracheostomy.dislodged => 1.0 - self.upper_airway_obstruction(),
+            Some(tracheostomy) => tracheostomy.patency(),
+            // An endotracheal tube stents the pharynx open
+            None if self.ventilator.is_some() => 1.0,
+            None => 1.0 - self.upper_airway_obstruction(),
+        }
+    }
+
+    /// Narrowing of a collapsible pharynx that has lost its dilator tone (0.0 = open, 1.0 = obstructed)
+    pub fn upper_airway_obstruction(&self) -> f64 {
+        (self.upper_airway_collapsibility * (1.0 - self.upper_airway_tone)).clamp(0.0, 1.0)
+    }
+
+    /// End-expiratory lung volume (mL): a stiff or loaded chest wall and loss of
+    /// diaphragmatic tone lower it, PEEP and trapped gas raise it
+    pub fn functional_residual_capacity_ml(&self) -> f64 {
+        let tone = if self.is_breathing_spontaneously() { 1.0 } else { 1.0 - ANESTHESIA_FRC_LOSS };
+        self.resting_tidal_volume_ml * NORMAL_FRC_PER_TIDAL_ML * self.chest_wall_compliance.min(1.0) * tone
+            + self.peep_cmh2o() * self.respiratory_compliance_ml_cmh2o
+            + self.trapped_volume_ml
+    }
+
+    /// Lung volume below which dependent airways close (mL)
+    pub fn closing_capacity_ml(&self) -> f64 {
+        self.resting_tidal_volume_ml * NORMAL_FRC_PER_TIDAL_ML * CLOSING_CAPACITY_FRACTION
+    }
+
+    /// Anatomic dead space each breath must wash out before reaching the alveoli (mL)
+    pub fn anatomic_dead_space_ml(&self) -> f64 {
+        let bypassed = self.tracheostomy.as_ref().is_some_and(|tracheostomy| !tracheostomy.dislodged);
+        ANATOMIC_DEAD_SPACE_FRACTION
+            * self.resting_tidal_volume_ml
+            * if bypassed { TRACHEOSTOMY_DEAD_SPACE_FRACTION } else { 1.0 }
+    }
+
+    /// Accumulate secretions in a tracheostomy tube faster than a weak cough clears
+    /// them, and risk the tube being pulled out
+    ///
+    /// # Arguments
+    /// * `patient` - The patient, whose outcome variability decides dislodgement
+    /// * `delta_time_s` - Time step in seconds
+    fn update_tracheostomy(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let cough = if self.is_breathing_spontaneously() { 0.8 * self.respiratory_muscle_capacity() } else { 0.0 };
+        let inflammation = self.alveolar_inflammation;
+        let Some(tracheostomy) = self.tracheostomy.as_mut() else {
+            return;
+        };
+        tracheostomy.placed_s += delta_time_s;
+        if tracheostomy.dislodged {
+            return;
+        }
+        tracheostomy.secretions = (tracheostomy.secretions
+            + (1.0 + inflammation - cough) * delta_time_s / TRACHEOSTOMY_SECRETION_S)
+            .clamp(0.0, 1.0);
+        let dislodgement = 1.0 - (-TRACHEOSTOMY_DISLODGEMENT_RISK_PER_DAY * delta_time_s / 86_400.0).exp();
+        if patient.variability.occurs(dislodgement) {
+            tracheostomy.dislodged = true;
+        }
+    }
+
+    /// Pain from fractured ribs that limits breathing (0.0 = none or fully relieved, 1.0 = severe)
+    pub fn rib_pain(&self) -> f64 {
+        (self.fractured_ribs as f64 / PAINFUL_RIB_FRACTURES).min(1.0) * (1.0 - self.analgesia.clamp(0.0, 1.0))
+    }
+
+    /// Fraction of a spontaneous breath lost to a flail segment moving inwards as
+    /// the rest of the chest expands; positive pressure pushes it out with the rest
+    pub fn paradoxical_movement(&self) -> f64 {
+        if self.flail_segment && self.is_breathing_spontaneously() {
+            FLAIL_PARADOXICAL_FRACTION
+        } else {
+            0.0
+        }
+    }
+
+    /// Rapid shallow breathing index, respiratory rate over tidal volume in litres
+    /// (breaths/min/L); meaningful only while breathing spontaneously
+    pub fn rapid_shallow_breathing_index(&self) -> f64 {
+        self.respiration_rate_bpm / (self.tidal_volume_ml / 1000.0).max(0.01)
+    }
+
+    /// Screen a ventilated patient for readiness to wean: modest oxygen and PEEP
+    /// needs, intact respiratory drive and muscles that are not exhausted
+    ///
+    /// # Arguments
+    /// * `pao2_mmhg` - Arterial PO2 (mmHg)
+    pub fn is_ready_to_wean(&self, pao2_mmhg: f64) -> bool {
+        self.ventilator.is_some()
+            && self.effective_fio2() <= 0.5
+            && self.peep_cmh2o() <= 8.0
+            && self.get_pf_ratio(pao2_mmhg) >= 150.0
+            && self.brainstem_function >= 0.5
+            && self.respiratory_muscle_strength >= 0.5
+            && self.respiratory_muscle_fatigue < 0.5
+    }
+
+    /// Start a spontaneous breathing trial: the ventilator stops delivering breaths
+    /// but keeps the set FiO2 and PEEP
+    ///
+    /// # Returns
+    /// False if the patient is not ventilated
+    pub fn start_breathing_trial(&mut self) -> bool {
+        if self.ventilator.is_none() {
+            return false;
+        }
+        self.breathing_trial = Some(BreathingTrial {
+            elapsed_s: 0.0,
+            baseline_paco2_mmhg: self.arterial_co2_mmhg,
+            rsbi: None,
+            outcome: None,
+        });
+        true
+    }
+
+    /// End a breathing trial and resume full ventilator support
+    pub fn resume_full_support(&mut self) {
+        self.breathing_trial = None;
+    }
+
+    /// Chance that laryngeal edema narrows the airway after extubation, rising
+    /// with the days intubated (~3% plus 3% per day, up to 20%)
+    pub fn post_extubation_stridor_risk(&self) -> f64 {
+        if self.ventilator.is_none() {
+            return 0.0;
+        }
+        (0.03 + 0.03 * self.ventilated_s / 86_400.0).min(0.2)
+    }
+
+    /// Chance that extubation fails from secretions the patient cannot clear:
+    /// ~10% after a passed breathing trial, several times higher without one,
+    /// and higher again with weak or tired respiratory muscles
+    pub fn extubation_failure_risk(&self) -> f64 {
+        if self.ventilator.is_none() {
+            return 0.0;
+        }
+        let passed = self
+            .breathing_trial
+            .as_ref()
+            .is_some_and(|trial| trial.outcome == Some(BreathingTrialOutcome::Passed));
+        let base = if passed { 0.1 } else { 0.35 };
+        (base * (1.0 + self.respiratory_muscle_fatigue) / self.respiratory_muscle_capacity().max(0.1)).min(0.9)
+    }
+
+    /// Remove the endotracheal tube
+    ///
+    /// # Arguments
+    /// * `occurs` - Decides whether a complication with the given probability happens
+    ///
+    /// # Returns
+    /// The complications that followed
+    pub fn extubate_with(&mut self, mut occurs: impl FnMut(f64) -> bool) -> Extubation {
+        let extubation = Extubation {
+            stridor: occurs(self.post_extubation_stridor_risk()),
+            retained_secretions: occurs(self.extubation_failure_risk()),
+        };
+        if extubation.stridor {
+            self.laryngeal_edema = 1.0;
+        }
+        if extubation.retained_secretions {
+            self.inflict_damage(2, 0.5);
+            self.inflict_damage(4, 0.5);
+        }
+        self.detach_ventilator();
+        extubation
+    }
+
+    /// Waste the diaphragm on controlled ventilation, tire or rest the respiratory
+    /// muscles against the work of breathing, and apply the failure criteria of a
+    /// running breathing trial
+    ///
+    /// # Arguments
+    /// * `patient` - The patient, whose arterial pH the trial watches
+    /// * `delta_time_s` - Time step in seconds
+    fn update_weaning(&mut self, patient: &Patient, delta_time_s: f64) {
+        // An unused diaphragm wastes within days
+        let spontaneous = self.is_breathing_spontaneously();
+        if spontaneous {
+            self.diaphragm_atrophy *= (-delta_time_s / DIAPHRAGM_RECOVERY_S).exp();
+        } else {
+            self.diaphragm_atrophy += (1.0 - self.diaphragm_atrophy) * (1.0 - (-delta_time_s / DIAPHRAGM_ATROPHY_S).exp());
+        }
+
+        // Breathing more than metabolism needs, into stiffer lungs or through narrower
+        // airways, with weaker muscles, is a heavier load; exercise hyperpnea is not
+        let capacity = self.respiratory_muscle_capacity().max(0.05);
+        self.respiratory_load = if spontaneous {
+            let demanded_ventilation_ratio = self.respiration_rate_bpm * self.tidal_volume_ml
+                / capacity
+                / (self.resting_respiration_rate_bpm * self.resting_tidal_volume_ml).max(1e-6);
+            let production_ratio = patient.oxygen_transport.co2_production_ratio().max(0.25);
+            let elastance = self.resting_tidal_volume_ml * 0.5 * NORMAL_COMPLIANCE_PER_TIDAL_ML
+                / self.respiratory_compliance_ml_cmh2o.max(1.0);
+            // Effort spent sucking in a flail segment moves no air
+            demanded_ventilation_ratio / production_ratio * (0.7 * elastance + 0.3 * self.main_bronchus.resistance)
+                / capacity
+                / (1.0 - self.paradoxical_movement())
+        } else {
+            0.0
+        };
+        let load = self.respiratory_load / SUSTAINABLE_RESPIRATORY_LOAD;
+        if load > 1.0 {
+            self.respiratory_muscle_fatigue += (load - 1.0) * delta_time_s / RESPIRATORY_FATIGUE_S;
+        } else {
+            self.respiratory_muscle_fatigue -= self.respiratory_muscle_fatigue
+                * (1.0 - load)
+                * (1.0 - (-delta_time_s / RESPIRATORY_FATIGUE_RECOVERY_S).exp());
+        }
+        self.respiratory_muscle_fatigue = self.respiratory_muscle_fatigue.clamp(0.0, 1.0);
+        self.laryngeal_edema *= (-delta_time_s / LARYNGEAL_EDEMA_RESOLUTION_S).exp();
+
+        let rsbi = self.rapid_shallow_breathing_index();
+        let (respiration_rate, saturation, paco2) =
+            (self.respiration_rate_bpm, self.oxygen_saturation_percent, self.arterial_co2_mmhg);
+        let Some(trial) = self.breathing_trial.as_mut().filter(|trial| trial.outcome.is_none()) else {
+            return;
+        };
+        trial.elapsed_s += delta_time_s;
+        if trial.elapsed_s < BREATHING_TRIAL_SETTLING_S {
+            return;
+        }
+        trial.rsbi.get_or_insert(rsbi);
+        let failure = if respiration_rate > 35.0 {
+            Some(TrialFailure::Tachypnea)
+        } else if rsbi > RSBI_WEANING_THRESHOLD {
+            Some(TrialFailure::RapidShallowBreathing)
+        } else if saturation < 90.0 {
+            Some(TrialFailure::Desaturation)
+        } else if patient.blood.get_ph() < 7.32 && paco2 - trial.baseline_paco2_mmhg >= 10.0 {
+            Some(TrialFailure::RespiratoryAcidosis)
+        } else {
+            None
+        };
+        if let Some(failure) = failure {
+            trial.outcome = Some(BreathingTrialOutcome::Failed(failure));
+        } else if trial.elapsed_s >= BREATHING_TRIAL_S {
+            trial.outcome = Some(BreathingTrialOutcome::Passed);
+        }
+    }
+
+    /// Inspired oxygen fraction currently delivered
+    pub fn effective_fio2(&self) -> f64 {
+        match self.connected_ventilator() {
+            Some(ventilator) => ventilator.fio2,
+            None => self.fio2,
+        }
+    }
+
+    /// PEEP currently applied (cmH2O)
+    pub fn peep_cmh2o(&self) -> f64 {
+        self.connected_ventilator().map(|v| v.peep_cmh2o).unwrap_or(0.0)
+    }
+
+    /// PaO2/FiO2 ratio
+    pub fn get_pf_ratio(&self, pao2_mmhg: f64) -> f64 {
+        pao2_mmhg / self.effective_fio2()
+    }
+
+    /// Berlin ARDS severity from the P/F ratio, if alveolar edema is present
+    pub fn get_ards_severity(&self, pao2_mmhg: f64) -> Option<&'static str> {
+        if self.alveolar_edema < 0.1 {
+            return None;
+        }
+        let pf_ratio = self.get_pf_ratio(pao2_mmhg);
+        if pf_ratio <= 100.0 {
+            Some("Severe")
+        } else if pf_ratio <= 200.0 {
+            Some("Moderate")
+        } else if pf_ratio <= 300.0 {
+            Some("Mild")
+        } else {
+            None
+        }
+    }
+
+    /// Advance alveolar edema and compute shunt fraction
+    ///
+    /// # Arguments
+    /// * `left_collapse` / `right_collapse` - Collapse of each lung
+    /// * `albumin_g_dl` - Plasma albumin (g/dL)
+    /// * `delta_time_s` - Time step in seconds
+    fn update_alveolar_membrane(&mut self, left_collapse: f64, right_collapse: f64, albumin_g_dl: f64, delta_time_s: f64) {
+        // Inflammation and leaky capillaries flood alveoli over hours; resolution takes days
+        let permeability_edema = (self.alveolar_inflammation * 0.9
+            + (self.capillary_permeability - 0.5).max(0.0) * 2.0)
+            .clamp(0.0, 1.0);
+        // Filtration beyond what the lymphatics drain floods the alveoli within tens of
+        // minutes, and clears within hours once the pressure falls; low plasma protein
+        // lowers the capillary pressure at which this begins
+        let filtration = starling::net_filtration_pressure_mmhg(
+            self.pulmonary_capillary_pressure_mmhg,
+            PULMONARY_INTERSTITIAL_PRESSURE_MMHG,
+            starling::plasma_oncotic_pressure_mmhg(albumin_g_dl + PLASMA_GLOBULIN_G_DL),
+            PULMONARY_INTERSTITIAL_ONCOTIC_MMHG,
+            PULMONARY_REFLECTION_COEFFICIENT,
+        );
+        let hydrostatic_edema = ((filtration - LYMPHATIC_CLEARANCE_FILTRATION_MMHG) / 12.0).clamp(0.0, 1.0) * 0.8;
+        let target_edema = (permeability_edema + hydrostatic_edema).clamp(0.0, 1.0);
+        let cardiogenic = hydrostatic_edema > permeability_edema;
+        let time_constant_s = match (target_edema > self.alveolar_edema, cardiogenic) {
+            (true, true) => 1_800.0,
+            (true, false) => 7_200.0,
+            (false, true) => 7_200.0,
+            (false, false) => 43_200.0,
+        };
+        self.alveolar_edema += (target_edema - self.alveolar_edema) * (delta_time_s / time_constant_s).min(1.0);
+        self.alveolar_inflammation *= (-delta_time_s / 259_200.0).exp();
+
+        // PEEP recruits part of the flooded, collapsed alveoli
+        let recruitment = 0.6 * (1.0 - (-self.peep_cmh2o() / 8.0).exp());
+        let edema_shunt = self.alveolar_edema * 0.5 * (1.0 - recruitment);
+
+        // Collapsed lung is a shunt; hypoxic vasoconstriction diverts much of its blood flow
+        let collapse_shunt = (0.55 * right_collapse + 0.45 * left_collapse) * 0.2;
+
+        // Consolidated or damaged lobes
+        let damage_shunt = (1.0 - self.total_compliance()) * 0.3;
+
+        // Mucus plugging and V/Q mismatch behind narrowed airways
+        let airway_shunt = ((self.main_bronchus.resistance - 1.0) * 0.012).clamp(0.0, 0.15);
+
+        // Blood diverted from embolized lung overperfuses the rest (low V/Q)
+        let vq_mismatch = self.alveolar_dead_space_fraction.clamp(0.0, 0.9) * 0.4;
+
+        // Dependent airways close when the lungs empty below the closing capacity
+        let closing_capacity = self.closing_capacity_ml();
+        let closure = ((closing_capacity - self.functional_residual_capacity_ml()) / closing_capacity.max(1.0)).clamp(0.0, 1.0);
+        let closure_shunt = AIRWAY_CLOSURE_SHUNT * closure;
+
+        self.shunt_fraction =
+            (0.02 + edema_shunt + collapse_shunt + damage_shunt + airway_shunt + vq_mismatch + closure_shunt
+                + self.right_to_left_shunt_fraction).clamp(0.0, 0.8);
+    }
+
+    /// Inflame alveoli overstretched by the ventilator (volutrauma and barotrauma)
+    ///
+    /// # Arguments
+    /// * `ideal_body_weight_kg` - Patient's ideal body weight (kg)
+    /// * `delta_time_s` - Time step in seconds
+    fn update_ventilator_injury(&mut self, ideal_body_weight_kg: f64, delta_time_s: f64) {
+        self.tidal_volume_ml_kg_ibw = self.tidal_volume_ml / ideal_body_weight_kg.max(0.5);
+        let Some(ventilator) = self.connected_ventilator() else {
+            return;
+        };
+        // Flooded alveoli leave each breath to the smaller aerated "baby lung"
+        let aerated_tidal_volume = self.tidal_volume_ml_kg_ibw / (1.0 - 0.6 * self.alveolar_edema.clamp(0.0, 1.0));
+        let overstretch = (aerated_tidal_volume - SAFE_TIDAL_VOLUME_ML_KG).max(0.0) / 4.0
+            + (ventilator.plateau_pressure_cmh2o - SAFE_PLATEAU_PRESSURE_CMH2O).max(0.0) / 10.0;
+        let injury = overstretch * delta_time_s / VENTILATOR_INJURY_S * (1.0 - self.alveolar_inflammation).max(0.0);
+        self.alveolar_inflammation = (self.alveolar_inflammation + injury).min(1.0);
+        self.ventilator_lung_injury = (self.ventilator_lung_injury + injury).min(1.0);
+    }
+
+    /// Chance per hour that positive pressure ruptures alveoli into the pleura
+    pub fn barotrauma_risk_per_hour(&self) -> f64 {
+        self.ventilator.as_ref().map_or(0.0, |ventilator| {
+            BAROTRAUMA_RISK_PER_HOUR_PER_CMH2O * (ventilator.plateau_pressure_cmh2o - SAFE_PLATEAU_PRESSURE_CMH2O).max(0.0)
+        })
+    }
+
+    /// Chance per day of ventilator-associated pneumonia, highest in the first
+    /// days of intubation (~3%/day to day 5, 2%/day to day 10, 1%/day after)
+    pub fn ventilator_pneumonia_risk_per_day(&self) -> f64 {
+        if self.ventilator.is_none() {
+            return 0.0;
+        }
+        match self.ventilated_s / 86_400.0 {
+            days if days < 5.0 => 0.03,
+            days if days < 10.0 => 0.02,
+            _ => 0.01,
+        }
+    }
+
+    /// Draw the chance complications of mechanical ventilation
+    ///
+    /// # Arguments
+    /// * `patient` - The patient, whose variability decides the events and who
+    ///   may acquire a pneumonia
+    /// * `delta_time_s` - Time step in seconds
+    fn update_ventilator_complications(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        if self.ventilator.is_none() {
+            return;
+        }
+        self.ventilated_s += delta_time_s;
+
+        let barotrauma = 1.0 - (-self.barotrauma_risk_per_hour() * delta_time_s / 3600.0).exp();
+        if patient.variability.occurs(barotrauma) {
+            let side = if patient.variability.occurs(0.5) { Side::Left } else { Side::Right };
+            self.induce_pneumothorax(side, 100.0, BAROTRAUMA_AIR_LEAK_ML_MIN);
+        }
+
+        // Aspirated oropharyngeal organisms seed the dependent lower lobes
+        let pneumonia = 1.0 - (-self.ventilator_pneumonia_risk_per_day() * delta_time_s / 86_400.0).exp();
+        if patient.sepsis.is_none() && patient.variability.occurs(pneumonia) {
+            patient.start_infection("Ventilator-associated pneumonia", 1.0, BundleGuideline::HourOne);
+            self.inflict_damage(2, 0.3);
+        }
+    }
+
+    /// Trigger bronchospasm (asthma exacerbation, anaphylaxis)
+    ///
+    /// # Arguments
+    /// * `severity` - 0.0-1.0
+    pub fn induce_bronchospasm(&mut self, severity: f64) {
+        let bronchus = &mut self.main_bronchus;
+        bronchus.bronchospasm = bronchus.bronchospasm.max(severity.clamp(0.0, 1.0));
+    }
+
+    /// Set fixed airflow obstruction (COPD)
+    ///
+    /// # Arguments
+    /// * `severity` - 0.0-1.0 (GOLD 1-4 roughly 0.25 / 0.5 / 0.75 / 1.0)
+    pub fn set_chronic_obstruction(&mut self, severity: f64) {
+        self.main_bronchus.chronic_obstruction = severity.clamp(0.0, 1.0);
+    }
+
+    /// Give a nebulized or inhaled beta-agonist
+    ///
+    /// # Arguments
+    /// * `dose_mg` - Albuterol dose in mg (2.5 mg nebulized is standard)
+    pub fn give_beta_agonist(&mut self, dose_mg: f64) {
+        self.pending_beta_agonist_mg += dose_mg.max(0.0);
+    }
+
+    /// Bronchodilator effect (0.0-1.0)
+    fn bronchodilation(&self) -> f64 {
+        self.beta_agonist_level_mg / (self.beta_agonist_level_mg + 1.0)
+    }
+
+    /// Peak expiratory flow (L/min); forced flow is turbulent, so it falls with the
+    /// square root of airway resistance
+    pub fn peak_expiratory_flow_l_min(&self) -> f64 {
+        PEAK_FLOW_PER_TIDAL_ML * self.resting_tidal_volume_ml / self.main_bronchus.resistance.max(1.0).sqrt()
+    }
+
+    /// Expiratory to inspiratory time ratio (normal ~1.5), prolonged by obstruction
+    pub fn get_ie_ratio(&self) -> f64 {
+        (1.0 - self.inspiratory_fraction) / self.inspiratory_fraction
+    }
+
+    /// Update airway resistance and dynamic hyperinflation
+    ///
+    /// # Returns
+    /// Fraction of each tidal breath not exhaled before the next breath
+    fn update_airways(&mut self, delta_time_s: f64) -> f64 {
+        // Nebulized drug deposits over ~5 minutes; albuterol lasts ~4 hours
+        let deposited = self.pending_beta_agonist_mg * (delta_time_s / 300.0).min(1.0);
+        self.pending_beta_agonist_mg -= deposited;
+        self.beta_agonist_level_mg = self.beta_agonist_level_mg * 0.5_f64.powf(delta_time_s / 14_400.0) + deposited;
+        self.main_bronchus.update(self.bronchodilation(), delta_time_s);
+        // A swollen larynx narrows the upper airway, and secretions a tracheostomy tube
+        self.main_bronchus.resistance += LARYNGEAL_EDEMA_RESISTANCE * self.laryngeal_edema;
+        if let Some(tracheostomy) = self.tracheostomy.as_ref().filter(|tracheostomy| !tracheostomy.dislodged) {
+            self.main_bronchus.resistance += TRACHEOSTOMY_SECRETION_RESISTANCE * tracheostomy.secretions.powi(2);
+        }
+        let resistance = self.main_bronchus.resistance.max(1.0);
+
+        // Obstructed patients breathe in quickly and spend longer breathing out
+        self.inspiratory_fraction = 0.4 / (1.0 + 0.1 * (resistance - 1.0));
+
+        // Exhalation follows the RC time constant; time runs out before it finishes
+        let cycle_duration = 60.0 / self.respiration_rate_bpm.max(1.0);
+        let expiratory_time = cycle_duration * (1.0 - self.inspiratory_fraction);
+        let time_constant = 0.35 * resistance * self.total_compliance().max(0.1);
+        let trapped_fraction = (-expiratory_time / time_constant).exp();
+
+        // Each breath leaves a little more behind until the trapped volume equilibrates
+        let target_trapped = self.tidal_volume_ml * trapped_fraction / (1.0 - trapped_fraction).max(0.05);
+        self.trapped_volume_ml += (target_trapped - self.trapped_volume_ml) * (delta_time_s / 30.0).min(1.0);
+        let compliance_ml_cmh2o = self.resting_tidal_volume_ml * 0.1 * self.total_compliance().max(0.05);
+        self.auto_peep_cmh2o = self.trapped_volume_ml / compliance_ml_cmh2o;
+
+        trapped_fraction
+    }
+
+    /// Get the pleural space on one side
+    pub fn pleura(&self, side: Side) -> &PleuralSpace {
+        match side {
+            Side::Left => &self.left_pleura,
+            Side::Right => &self.right_pleura,
+        }
+    }
+
+    /// Get the pleural space on one side mutably
+    pub fn pleura_mut(&mut self, side: Side) -> &mut PleuralSpace {
+        match side {
+            Side::Left => &mut self.left_pleura,
+            Side::Right => &mut self.right_pleura,
+        }
+    }
+
+    /// Volume of the lung on one side (mL)
+    fn side_volume_ml(&self, side: Side) -> f64 {
+        match side {
+            Side::Left => self.left_upper_lobe.volume_ml + self.left_lower_lobe.volume_ml,
+            Side::Right => {
+                self.right_upper_lobe.volume_ml
+                    + self.right_middle_lobe.volume_ml
+                    + self.right_lower_lobe.volume_ml
+            }
+        }
+    }
+
+    /// Introduce air into a pleural space
+    ///
+    /// # Arguments
+    /// * `side` - Affected side
+    /// * `volume_ml` - Air already in the pleural space
+    /// * `leak_ml_min` - Ongoing one-way air leak; any leak eventually causes tension
+    pub fn induce_pneumothorax(&mut self, side: Side, volume_ml: f64, leak_ml_min: f64) {
+        let pleura = self.pleura_mut(side);
+        pleura.air_ml += volume_ml.max(0.0);
+        pleura.air_leak_ml_min = pleura.air_leak_ml_min.max(leak_ml_min);
+    }
+
+    /// Accumulate fluid in a pleural space
+    ///
+    /// # Arguments
+    /// * `side` - Affected side
+    /// * `volume_ml` - Fluid volume to add
+    pub fn induce_pleural_effusion(&mut self, side: Side, volume_ml: f64) {
+        self.pleura_mut(side).fluid_ml += volume_ml.max(0.0);
+    }
+
+    /// Needle decompression (2nd intercostal space or 5th ICS anterior axillary line)
+    pub fn decompress(&mut self, side: Side) {
+        self.pleura_mut(side).needle_decompressed = true;
+    }
+
+    /// Place a chest tube on suction
+    pub fn insert_chest_tube(&mut self, side: Side) {
+        self.pleura_mut(side).chest_tube = true;
+    }
+
+    /// Remove a chest tube once the leak has sealed
+    pub fn remove_chest_tube(&mut self, side: Side) {
+        self.pleura_mut(side).chest_tube = false;
+    }
+
+    /// Seal a parenchymal air leak (healing or surgical repair)
+    pub fn seal_air_leak(&mut self, side: Side) {
+        self.pleura_mut(side).air_leak_ml_min = 0.0;
+    }
+
+    /// Breath sounds heard over one side of the chest
+    pub fn get_breath_sounds(&self, side: Side) -> BreathSounds {
+        let collapse = self.pleura(side).collapse_fraction;
+        let resistance = self.main_bronchus.resistance;
+        if collapse > 0.6 {
+            BreathSounds::Absent
+        } else if collapse > 0.15 || resistance > 9.0 {
+            // Too little airflow to wheeze: the "silent chest"
+            BreathSounds::Decreased
+        } else if resistance > 2.5 {
+            BreathSounds::Wheezing
+        } else {
+            BreathSounds::Normal
+        }
+    }
+
+    /// Fraction of venous return impeded by raised intrathoracic pressure (0.0-0.7)
+    ///
+    /// Tension pneumothorax, high PEEP and auto-PEEP all raise intrathoracic pressure
+    pub fn get_venous_return_impairment(&self) -> f64 {
+        let pressure = self.left_pleura.pressure_cmh2o.max(self.right_pleura.pressure_cmh2o);
+        let peep_effect = (self.peep_cmh2o() + self.auto_peep_cmh2o - 5.0).max(0.0) / 60.0;
+        (((pressure - 5.0) / 40.0).max(0.0) + peep_effect).clamp(0.0, 0.7)
+    }
+
+    /// Collapse of each side including compression from a shifted mediastinum
+    ///
+    /// # Returns
+    /// (left, right) collapse fractions
+    fn effective_collapse(&self) -> (f64, f64) {
+        let compression = (self.mediastinal_shift_cm.abs() / 5.0) * 0.3;
+        let (left_extra, right_extra) = if self.mediastinal_shift_cm > 0.0 {
+            (compression, 0.0)
+        } else {
+            (0.0, compression)
+        };
+        (
+            (self.left_pleura.collapse_fraction + left_extra).min(1.0),
+            (self.right_pleura.collapse_fraction + right_extra).min(1.0),
+        )
+    }
+
+    /// Average compliance of lobes that are still ventilated
+    fn ventilated_compliance(&self, left_collapse: f64, right_collapse: f64) -> f64 {
+        let right = (self.right_upper_lobe.compliance
+            + self.right_middle_lobe.compliance
+            + self.right_lower_lobe.compliance)
+            * (1.0 - right_collapse);
+        let left = (self.left_upper_lobe.compliance + self.left_lower_lobe.compliance)
+            * (1.0 - left_collapse);
+        (right + left) / 5.0
+    }
 }
 
 impl Organ for Lungs {
     fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // The ventilator sets the rate and tidal volume unless the patient is on a breathing trial
+        if let Some(ventilator) = self.ventilator.as_ref().filter(|_| !self.is_breathing_spontaneously()) {
+            self.respiration_rate_bpm = ventilator.rate_bpm.max(1.0);
+            self.tidal_volume_ml = ventilator.tidal_volume_ml;
+        }
+
         // Update respiratory cycle
         self.respiratory_cycle_time += delta_time_s;
-        let cycle_duration = 60.0 / self.respiration_rate_bpm;
+        let cycle_duration = 60.0 / self.respiration_rate_bpm.max(1.0);
 
         if self.respiratory_cycle_time >= cycle_duration {
             self.respiratory_cycle_time = 0.0;
         }
 
         let cycle_progress = self.respiratory_cycle_time / cycle_duration;
+        let trapped_fraction = self.update_airways(delta_time_s);
 
         // Determine respiratory phase
-        if cycle_progress < 0.4 {
+        // Obstruction shortens inspiration and the end-expiratory pause
+        let inspiration_end = self.inspiratory_fraction;
+        let expiration_end = 1.0 - 0.2 / (1.0 + 0.5 * (self.main_bronchus.resistance - 1.0).max(0.0));
+        if cycle_progress < inspiration_end {
             self.current_phase = RespiratoryPhase::Inspiration;
-        } else if cycle_progress < 0.8 {
+        } else if cycle_progress < expiration_end {
             self.current_phase = RespiratoryPhase::Expiration;
         } else {
             self.current_phase = RespiratoryPhase::Pause;
         }
 
-        // Calculate gas exchange based on compliance
-        let compliance_factor = self.total_compliance();
-        let effective_ventilation = self.tidal_volume_ml * compliance_factor;
+        // Pleural spaces
+        let left_volume = self.side_volume_ml(Side::Left);
+        let right_volume = self.side_volume_ml(Side::Right);
+        self.left_pleura.update(left_volume, delta_time_s);
+        self.right_pleura.update(right_volume, delta_time_s);
+        let drained = std::mem::take(&mut self.left_pleura.drained_fluid_ml)
+            + std::mem::take(&mut self.right_pleura.drained_fluid_ml);
+        if drained > 0.0 {
+            patient.fluid_ledger.record_output(OutputRoute::Drain, drained);
+        }
+
+        // The mediastinum is pushed away from the higher-pressure side
+        let pressure_difference = self.right_pleura.pressure_cmh2o - self.left_pleura.pressure_cmh2o;
+        self.mediastinal_shift_cm = (pressure_difference * 0.2).clamp(-5.0, 5.0);
+        let (left_collapse, right_collapse) = self.effective_collapse();
+
+        self.update_alveolar_membrane(left_collapse, right_collapse, patient.blood.chemistry.albumin_g_dl, delta_time_s);
 
-        // Oxygen saturation
-        self.oxygen_saturation_percent = 98.0 * compliance_factor;
-        self.oxygen_saturation_percent = self.oxygen_saturation_percent.clamp(70.0, 100.0);
+        // CO2 balance
+        // Metabolism produces CO2 and alveolar ventilation clears it; at steady state
+        // PaCO2 = k × VCO2 / VA (alveolar ventilation equation), with k set so a
+        // resting patient sits at 38 mmHg
+        // Stiff, collapsed and flooded lung is ventilated unevenly, wasting part of each breath
+        let collapse_shunt = (0.55 * right_collapse + 0.45 * left_collapse) * 0.2;
+        let compliance_factor = self.total_compliance() * (1.0 - collapse_shunt) * (1.0 - 0.3 * self.alveolar_edema);
+        // Flow limitation and hyperinflated, poorly perfused alveoli reduce effective ventilation
+        let resistance = self.main_bronchus.resistance.max(1.0);
+        // Ventilation of unperfused alveoli (dead space) clears no CO2
+        let dead_space = self.alveolar_dead_space_fraction.clamp(0.0, 0.9);
+        let ventilation_efficiency = (1.0 - 0.5 * trapped_fraction) * (1.0 - dead_space)
+            / (1.0 + 0.03 * (resistance - 1.0).powf(1.5))
+            / (1.0 + 0.5 * (1.0 - compliance_factor).max(0.0))
+            * self.airway_patency();
+        // Each breath first washes out the anatomic dead space, so rapid shallow breathing
+        // clears less; a tracheostomy bypasses part of it
+        let anatomic_dead_space_ml = self.anatomic_dead_space_ml();
+        let alveolar_ventilation =
+            self.respiration_rate_bpm * (self.tidal_volume_ml - anatomic_dead_space_ml).max(0.0) * ventilation_efficiency;
+        let resting_alveolar_ventilation = self.resting_respiration_rate_bpm
+            * (self.resting_tidal_volume_ml - ANATOMIC_DEAD_SPACE_FRACTION * self.resting_tidal_volume_ml);
+        let ventilation_ratio = alveolar_ventilation / resting_alveolar_ventilation.max(1e-6);
+        let production_ratio = patient.oxygen_transport.co2_production_ratio();
+        // The body's CO2 stores buffer the imbalance: dPaCO2/dt = (production - clearance) / capacity
+        let production_rate = production_ratio * RESTING_ARTERIAL_CO2_MMHG / CO2_STORE_TIME_CONSTANT_S;
+        let clearance_rate = ventilation_ratio / CO2_STORE_TIME_CONSTANT_S;
+        self.arterial_co2_mmhg = if clearance_rate * delta_time_s < 1e-6 {
+            self.arterial_co2_mmhg + (production_rate - clearance_rate * self.arterial_co2_mmhg) * delta_time_s
+        } else {
+            let steady_state = production_rate / clearance_rate;
+            steady_state + (self.arterial_co2_mmhg - steady_state) * (-clearance_rate * delta_time_s).exp()
+        }
+        .clamp(0.0, MAX_ARTERIAL_CO2_MMHG);
+        let paco2 = self.arterial_co2_mmhg;
+        self.co2_elimination_ml_min = patient.oxygen_transport.resting_co2_production_ml_min
+            * ventilation_ratio
+            * paco2
+            / RESTING_ARTERIAL_CO2_MMHG;
+        // Gas from dead space dilutes exhaled CO2, widening the PaCO2-EtCO2 gap
+        self.end_tidal_co2_mmhg = paco2 * (1.0 - dead_space);
 
-        // CO2 clearance
-        let _co2_clearance = effective_ventilation * 0.05;
-        self.end_tidal_co2_mmhg = 38.0 + (1.0 - compliance_factor) * 20.0;
+        // Oxygenation
+        // Alveolar gas equation: PAO2 = FiO2 × (Patm - PH2O) - PaCO2 / RQ
+        let respiratory_quotient = patient.oxygen_transport.respiratory_quotient.max(0.6);
+        let alveolar_po2 = (self.effective_fio2() * (760.0 - 47.0) - paco2 / respiratory_quotient).max(0.0);
+        // Shunted mixed venous blood mixes with end-capillary blood, so low output
+        // (a high extraction ratio) deepens shunt hypoxemia. The venous blood is
+        // taken to carry the arterial content less what the tissues extract, which
+        // keeps a large shunt from overshooting between steps:
+        // CaO2 = (1 - Qs) × Cc'O2 / (1 - Qs × (1 - O2ER))
+        let hemoglobin = patient.blood.get_hemoglobin_g_dl().max(1.0);
+        let curve = patient.oxygen_transport.dissociation;
+        let capillary_content = curve.content_ml_dl(hemoglobin, alveolar_po2);
+        let venous_fraction = 1.0 - patient.oxygen_transport.extraction_ratio();
+        let arterial_content = (1.0 - self.shunt_fraction) * capillary_content
+            / (1.0 - self.shunt_fraction * venous_fraction);
+        let pao2 = curve.po2_for_content(hemoglobin, arterial_content);
+        self.aa_gradient_mmhg = (alveolar_po2 - pao2).max(0.0);
+        self.oxygen_saturation_percent = curve.saturation(pao2) * 100.0;
 
         // Update patient blood gases
-        patient.blood.gases.sao2_percent = self.oxygen_saturation_percent;
-        patient.blood.gases.paco2_mmhg = self.end_tidal_co2_mmhg;
-        patient.blood.gases.pao2_mmhg = self.oxygen_saturation_percent * 0.95; // Approximate PaO2 from SpO2
-
-        // Peak inspiratory pressure affected by compliance
-        self.peak_inspiratory_pressure = 15.0 / compliance_factor.max(0.1);
-
-        // Generate capnography waveform
-        let capno_value = match self.current_phase {
-            RespiratoryPhase::Inspiration => 0.0,
-            RespiratoryPhase::Expiration => {
-                if cycle_progress < 0.6 {
-                    self.end_tidal_co2_mmhg * (cycle_progress - 0.4) / 0.2
+        patient.blood.set_sao2_percent(self.oxygen_saturation_percent);
+        patient.blood.set_paco2_mmhg(paco2);
+        patient.blood.set_pao2_mmhg(pao2);
+
+        // Airway pressures
+        // Flooded alveoli leave a small "baby lung" to ventilate
+        let ventilated_compliance = self.ventilated_compliance(left_collapse, right_collapse)
+            * (1.0 - 0.6 * self.alveolar_edema);
+        let peep = self.peep_cmh2o();
+        // The lungs and chest wall are inflated in series, so their elastances add; stiff
+        // lungs take the whole loss of respiratory system compliance behind a normal chest wall
+        let lung_compliance = ventilated_compliance.clamp(0.05, 1.0);
+        self.lung_compliance_ml_cmh2o = self.resting_tidal_volume_ml * NORMAL_COMPLIANCE_PER_TIDAL_ML
+            * lung_compliance / (2.0 - lung_compliance);
+        let chest_wall_compliance_ml_cmh2o =
+            self.resting_tidal_volume_ml * NORMAL_COMPLIANCE_PER_TIDAL_ML * self.chest_wall_compliance.max(0.05);
+        let compliance_ml_cmh2o = 1.0 / (1.0 / self.lung_compliance_ml_cmh2o + 1.0 / chest_wall_compliance_ml_cmh2o);
+        self.respiratory_compliance_ml_cmh2o = compliance_ml_cmh2o;
+        let connected = self.connected_ventilator().is_some();
+        if let Some(ventilator) = self.ventilator.as_mut().filter(|_| connected) {
+            ventilator.plateau_pressure_cmh2o =
+                peep + self.auto_peep_cmh2o + self.tidal_volume_ml / compliance_ml_cmh2o;
+            self.peak_inspiratory_pressure = ventilator.plateau_pressure_cmh2o + 5.0 * self.main_bronchus.resistance;
+        } else {
+            self.peak_inspiratory_pressure = 15.0 / ventilated_compliance.max(0.1);
+        }
+        self.update_ventilator_injury(patient.get_ideal_body_weight_kg(), delta_time_s);
+        self.update_ventilator_complications(patient, delta_time_s);
+        self.update_weaning(patient, delta_time_s);
+        self.update_tracheostomy(patient, delta_time_s);
+
+        // Generate capnography waveform at the fixed sampling rate across the step
+        // Slow emptying of obstructed alveoli stretches the upstroke into a "shark fin"
+        let upstroke = (0.2 * (1.0 + 0.3 * (resistance - 1.0))).min(expiration_end - inspiration_end);
+        if patient.organ_fidelity(self.get_type()).generates_waveforms() {
+            for lookback in self.capnography_sampler.step(delta_time_s) {
+                let progress = (self.respiratory_cycle_time - lookback).rem_euclid(cycle_duration) / cycle_duration;
+                let capno_value = if progress < inspiration_end || progress >= expiration_end {
+                    0.0
+                } else if progress < inspiration_end + upstroke {
+                    self.end_tidal_co2_mmhg * (progress - inspiration_end) / upstroke
                 } else {
                     self.end_tidal_co2_mmhg
-                }
+                };
+                self.capnography_waveform.push(capno_value);
             }
-            RespiratoryPhase::Pause => 0.0,
-        };
-
-        self.capnography_waveform.push_back(capno_value);
-        if self.capnography_waveform.len() > 1000 {
-            self.capnography_waveform.pop_front();
+        } else {
+            self.capnography_sampler.skip(delta_time_s);
         }
 
         // Respond to blood chemistry
-        // High CO2 increases respiration rate
-        if patient.blood.gases.paco2_mmhg > 45.0 {
-            self.respiration_rate_bpm = 16.0 + (patient.blood.gases.paco2_mmhg - 45.0) * 0.5;
-            self.respiration_rate_bpm = self.respiration_rate_bpm.min(30.0);
+        // The brainstem chemoreflexes quicken breathing
+        if !self.is_breathing_spontaneously() {
+            // Rate is set by the ventilator
         } else {
-            self.respiration_rate_bpm = 16.0;
+            let resting_rate = self.resting_respiration_rate_bpm;
+            let rate = (resting_rate * self.ventilatory_drive.max(1.0)).min(resting_rate * 1.875);
+            // Dead space and stimulated pulmonary receptors cause tachypnea
+            let dead_space_drive = 1.0 + 2.0 * self.alveolar_dead_space_fraction.clamp(0.0, 0.9);
+            let rate = (rate * dead_space_drive).min(resting_rate * 1.875);
+            // Metabolic acidosis drives deep, rapid (Kussmaul) breathing; alkalosis shallow, slow breathing
+            let chemoreflex = self.chemoreflex_drive.clamp(0.5, 4.0).sqrt();
+            // Stiff, waterlogged lungs are cheaper to breathe rapidly and shallowly
+            let edema = self.alveolar_edema.clamp(0.0, 1.0);
+            // Ventilation rises with CO2 production (exercise hyperpnea, fever), holding PaCO2
+            let metabolic_drive = production_ratio.clamp(0.25, 16.0).sqrt();
+            // Weak respiratory muscles, a painful or flail chest draw shallow breaths, so the drive quickens them
+            let chest_wall = (1.0 - RIB_PAIN_SPLINTING * self.rib_pain()) * (1.0 - self.paradoxical_movement());
+            let weakness = (self.respiratory_muscle_capacity() * chest_wall).max(0.25).sqrt().recip();
+            self.respiration_rate_bpm = rate
+                * chemoreflex
+                * metabolic_drive
+                * weakness
+                * (1.0 + 0.6 * edema)
+                * self.respiratory_drive.clamp(0.0, 1.5)
+                * self.brainstem_function.clamp(0.0, 1.0);
+            // Deeper breaths add to the alveolar part of each breath, so ventilation keeps pace
+            // with production (a tracheostomy spares the bypassed dead space from each breath);
+            // paralysed, wasted or exhausted respiratory muscles move no air whatever the drive
+            let alveolar_tidal_volume_ml = self.resting_tidal_volume_ml * (1.0 - ANATOMIC_DEAD_SPACE_FRACTION);
+            let metabolic_tidal_volume_ml = anatomic_dead_space_ml + alveolar_tidal_volume_ml * metabolic_drive;
+            self.tidal_volume_ml = metabolic_tidal_volume_ml
+                * chemoreflex
+                * (1.0 - 0.3 * edema)
+                * self.respiratory_muscle_capacity()
+                * chest_wall;
         }
     }
 
     fn get_summary(&self) -> String {
+        let trial = match self.breathing_trial.as_ref().map(|trial| trial.outcome) {
+            Some(None) => format!(", SBT (RSBI {:.0})", self.rapid_shallow_breathing_index()),
+            Some(Some(BreathingTrialOutcome::Passed)) => ", SBT passed".to_string(),
+            Some(Some(BreathingTrialOutcome::Failed(failure))) => format!(", SBT failed ({:?})", failure),
+            None => String::new(),
+        };
+        let ventilated = match &self.ventilator {
+            Some(ventilator) => format!(
+                ", Vt={:.1} mL/kg IBW, Pplat={:.0} cmH2O{}{}",
+                self.tidal_volume_ml_kg_ibw,
+                ventilator.plateau_pressure_cmh2o,
+                if self.ventilator_lung_injury > 0.05 { ", VENTILATOR-INDUCED LUNG INJURY" } else { "" },
+                trial
+            ),
+            None if self.laryngeal_edema > 0.1 => ", STRIDOR".to_string(),
+            None if self.upper_airway_obstruction() > 0.3 => ", UPPER AIRWAY OBSTRUCTION".to_string(),
+            None => String::new(),
+        };
+        let chest_wall = match (self.flail_segment, self.rib_pain()) {
+            (true, _) => ", FLAIL CHEST",
+            (false, pain) if pain > 0.3 => ", Rib pain",
+            _ => "",
+        };
+        let tracheostomy = match &self.tracheostomy {
+            Some(tracheostomy) if tracheostomy.dislodged => ", TRACHEOSTOMY DISLODGED",
+            Some(tracheostomy) if tracheostomy.secretions > 0.7 => ", Tracheostomy, SECRETIONS",
+            Some(_) => ", Tracheostomy",
+            None => "",
+        };
         format!(
-            "Lungs: RR={:.0} bpm, TV={:.0} mL, SpO2={:.1}%, etCO2={:.1} mmHg, PIP={:.1} cmH2O",
+            "Lungs: RR={:.0} bpm, TV={:.0} mL, SpO2={:.1}%, etCO2={:.1} mmHg, PIP={:.1} cmH2O, \
+             FiO2={:.2}, PEEP={:.0} cmH2O, Shunt={:.0}%, Dead space={:.0}%, A-a={:.0} mmHg, \
+             Raw={:.1}x, I:E=1:{:.1}, Auto-PEEP={:.0} cmH2O, \
+             Pleura L/R={:.0}/{:.0} cmH2O, Breath sounds L/R={:?}/{:?}, Mediastinal shift={:.1} cm{}{}{}",
             self.respiration_rate_bpm,
             self.tidal_volume_ml,
             self.oxygen_saturation_percent,
             self.end_tidal_co2_mmhg,
-            self.peak_inspiratory_pressure
+            self.peak_inspiratory_pressure,
+            self.effective_fio2(),
+            self.peep_cmh2o(),
+            self.shunt_fraction * 100.0,
+            self.alveolar_dead_space_fraction * 100.0,
+            self.aa_gradient_mmhg,
+            self.main_bronchus.resistance,
+            self.get_ie_ratio(),
+            self.auto_peep_cmh2o,
+            self.left_pleura.pressure_cmh2o,
+            self.right_pleura.pressure_cmh2o,
+            self.get_breath_sounds(Side::Left),
+            self.get_breath_sounds(Side::Right),
+            self.mediastinal_shift_cm,
+            ventilated,
+            chest_wall,
+            tracheostomy
         )
     }
 
@@ -237,4 +1687,297 @@ impl Organ for Lungs {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::bones::Bones;
+    use crate::organs::heart::Heart;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_tension_pneumothorax_obstructs_venous_return_until_decompressed() {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..30 {
+            update_patient(&mut patient, 1.0);
+        }
+        let baseline_map = patient.blood.get_mean_arterial_pressure();
+
+        patient.get_organ_mut::<Lungs>().unwrap().induce_pneumothorax(Side::Right, 500.0, 500.0);
+        for _ in 0..360 {
+            update_patient(&mut patient, 1.0);
+        }
+        let lungs = patient.get_organ::<Lungs>().unwrap();
+        assert!(lungs.right_pleura.is_tension());
+        assert!(lungs.right_pleura.pressure_cmh2o > 20.0);
+        assert_eq!(lungs.get_breath_sounds(Side::Right), BreathSounds::Absent);
+        assert!(patient.get_organ::<Heart>().unwrap().venous_return_factor < 0.5);
+        let tension_map = patient.blood.get_mean_arterial_pressure();
+        assert!(tension_map < baseline_map - 20.0, "MAP {tension_map} from {baseline_map}");
+
+        // Venting the pleural space restores venous return and the blood pressure
+        patient.get_organ_mut::<Lungs>().unwrap().decompress(Side::Right);
+        for _ in 0..180 {
+            update_patient(&mut patient, 1.0);
+        }
+        let lungs = patient.get_organ::<Lungs>().unwrap();
+        assert!(!lungs.right_pleura.is_tension());
+        assert!(lungs.right_pleura.pressure_cmh2o <= 0.0);
+        assert_eq!(patient.get_organ::<Heart>().unwrap().venous_return_factor, 1.0);
+        assert!(patient.blood.get_mean_arterial_pressure() > baseline_map - 5.0);
+    }
+
+    #[test]
+    fn test_bronchospasm_obstructs_expiration_until_a_bronchodilator_is_given() {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..30 {
+            update_patient(&mut patient, 1.0);
+        }
+        let baseline_flow = patient.get_organ::<Lungs>().unwrap().peak_expiratory_flow_l_min();
+        let baseline_sao2 = patient.blood.gases.sao2_percent;
+
+        patient.get_organ_mut::<Lungs>().unwrap().induce_bronchospasm(0.7);
+        for _ in 0..300 {
+            update_patient(&mut patient, 1.0);
+        }
+        let lungs = patient.get_organ::<Lungs>().unwrap();
+        let spasm_resistance = lungs.main_bronchus.resistance;
+        let spasm_flow = lungs.peak_expiratory_flow_l_min();
+        assert!(spasm_resistance > 5.0);
+        assert!(spasm_flow < 0.5 * baseline_flow, "PEF {spasm_flow} from {baseline_flow}");
+        assert!(lungs.get_ie_ratio() > 3.0);
+        assert!(lungs.auto_peep_cmh2o > 5.0);
+        assert_eq!(lungs.get_breath_sounds(Side::Right), BreathSounds::Wheezing);
+        let spasm_sao2 = patient.blood.gases.sao2_percent;
+        assert!(spasm_sao2 < baseline_sao2 - 4.0);
+
+        patient.get_organ_mut::<Lungs>().unwrap().give_beta_agonist(2.5);
+        for _ in 0..600 {
+            update_patient(&mut patient, 1.0);
+        }
+        let lungs = patient.get_organ::<Lungs>().unwrap();
+        assert!(lungs.main_bronchus.resistance < 0.6 * spasm_resistance);
+        assert!(lungs.peak_expiratory_flow_l_min() > 1.3 * spasm_flow);
+        assert!(lungs.auto_peep_cmh2o < 4.0);
+        assert!(patient.blood.gases.sao2_percent > spasm_sao2 + 2.0);
+    }
+
+    #[test]
+    fn test_large_shunt_desaturates_without_oscillating() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 60.0);
+        patient.get_organ_mut::<Lungs>().unwrap().right_to_left_shunt_fraction = 0.7;
+        let mut readings = Vec::new();
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+            readings.push(patient.blood.gases.sao2_percent);
+        }
+        // Each second's mixing uses the tissues' current extraction, so it settles instead of overshooting
+        assert!(readings.windows(2).skip(5).all(|pair| (pair[1] - pair[0]).abs() < 2.0), "{readings:?}");
+        assert!(readings[59] > 30.0 && readings[59] < 85.0, "{}", readings[59]);
+    }
+
+    #[test]
+    fn test_ideal_body_weight_sets_lung_protective_ventilation() {
+        // In ARDS, 12 mL/kg IBW overstretches the baby lung and deepens the injury
+        let mut protective = initialize_patient(1, 3);
+        let ibw = protective.get_ideal_body_weight_kg();
+        let lungs = protective.get_organ_mut::<Lungs>().unwrap();
+        lungs.induce_ards(0.4);
+        lungs.attach_ventilator(Ventilator::lung_protective(0.5, 8.0, ibw, 16.0));
+        let mut injurious = protective.fork();
+        injurious.get_organ_mut::<Lungs>().unwrap().ventilator.as_mut().unwrap().tidal_volume_ml = 12.0 * ibw;
+        update_patient(&mut protective, 6.0 * 3600.0);
+        update_patient(&mut injurious, 6.0 * 3600.0);
+
+        let protected_lungs = protective.get_organ::<Lungs>().unwrap();
+        let injured_lungs = injurious.get_organ::<Lungs>().unwrap();
+        assert!((protected_lungs.tidal_volume_ml_kg_ibw - 6.0).abs() < 0.1);
+        assert_eq!(protected_lungs.ventilator_lung_injury, 0.0);
+        assert!(injured_lungs.ventilator_lung_injury > 0.1);
+        assert!(injured_lungs.get_pf_ratio(injurious.blood.gases.pao2_mmhg)
+            < protected_lungs.get_pf_ratio(protective.blood.gases.pao2_mmhg) - 50.0);
+    }
+
+    #[test]
+    fn test_ventilator_complications_are_chance_events() {
+        // Stiff, flooded lungs given huge breaths run a high plateau pressure
+        let mut patient = initialize_patient(1, 3);
+        let lungs = patient.get_organ_mut::<Lungs>().unwrap();
+        lungs.alveolar_edema = 0.8;
+        lungs.induce_ards(0.8);
+        lungs.attach_ventilator(Ventilator::new(1.0, 10.0, 2000.0, 16.0));
+        update_patient(&mut patient, 60.0);
+        let lungs = patient.get_organ::<Lungs>().unwrap();
+        assert!(lungs.barotrauma_risk_per_hour() > 0.2);
+        assert_eq!(lungs.ventilator_pneumonia_risk_per_day(), 0.03);
+
+        let pneumothorax = |patient: &Patient| {
+            let lungs = patient.get_organ::<Lungs>().unwrap();
+            lungs.pleura(Side::Left).air_ml + lungs.pleura(Side::Right).air_ml > 0.0
+        };
+        let mut deterministic = patient.fork();
+        update_patient(&mut deterministic, 3600.0);
+        assert!(!pneumothorax(&deterministic));
+        let ruptured = (0..8).any(|seed| {
+            let mut variable = patient.fork();
+            variable.set_outcome_variability(seed, 1.0);
+            update_patient(&mut variable, 3600.0);
+            pneumothorax(&variable)
+        });
+        assert!(ruptured);
+
+        // Pneumonia risk falls after the first days of intubation and ends with extubation
+        let lungs = patient.get_organ_mut::<Lungs>().unwrap();
+        lungs.ventilated_s = 6.0 * 86_400.0;
+        assert_eq!(lungs.ventilator_pneumonia_risk_per_day(), 0.02);
+        lungs.detach_ventilator();
+        assert_eq!(lungs.ventilator_pneumonia_risk_per_day(), 0.0);
+        assert_eq!(lungs.barotrauma_risk_per_hour(), 0.0);
+    }
+
+    #[test]
+    fn test_breathing_trial_and_extubation_liberate_from_the_ventilator() {
+        let mut patient = initialize_patient(1, 3);
+        let ibw = patient.get_ideal_body_weight_kg();
+        patient.get_organ_mut::<Lungs>().unwrap().attach_ventilator(Ventilator::lung_protective(0.4, 5.0, ibw, 14.0));
+        update_patient(&mut patient, 2.0 * 3600.0);
+        let lungs = patient.get_organ::<Lungs>().unwrap();
+        assert!(lungs.is_ready_to_wean(patient.blood.gases.pao2_mmhg));
+        assert!(lungs.diaphragm_atrophy > 0.0);
+
+        // A diaphragm wasted by a week of controlled ventilation breathes rapidly and shallowly
+        let mut wasted = patient.fork();
+        wasted.get_organ_mut::<Lungs>().unwrap().diaphragm_atrophy = 0.45;
+        for timeline in [&mut patient, &mut wasted] {
+            assert!(timeline.get_organ_mut::<Lungs>().unwrap().start_breathing_trial());
+            for _ in 0..30 {
+                update_patient(timeline, 60.0);
+            }
+        }
+        let lungs = patient.get_organ::<Lungs>().unwrap();
+        let trial = lungs.breathing_trial.as_ref().unwrap();
+        assert_eq!(trial.outcome, Some(BreathingTrialOutcome::Passed));
+        assert!(trial.rsbi.unwrap() < RSBI_WEANING_THRESHOLD / 2.0, "{:?}", trial.rsbi);
+        let wasted_lungs = wasted.get_organ::<Lungs>().unwrap();
+        assert_eq!(
+            wasted_lungs.breathing_trial.as_ref().unwrap().outcome,
+            Some(BreathingTrialOutcome::Failed(TrialFailure::RapidShallowBreathing))
+        );
+        assert!(!wasted_lungs.is_breathing_spontaneously() && wasted_lungs.respiratory_muscle_fatigue > 0.0);
+
+        // Stridor grows likelier with days intubated; a deterministic patient is spared it
+        let mut long_stay = patient.fork();
+        long_stay.get_organ_mut::<Lungs>().unwrap().ventilated_s = 7.0 * 86_400.0;
+        let lungs = long_stay.get_organ_mut::<Lungs>().unwrap();
+        assert!(lungs.post_extubation_stridor_risk() > 3.0 * patient.get_organ::<Lungs>().unwrap().post_extubation_stridor_risk());
+        let resistance = lungs.main_bronchus.resistance;
+        assert!(lungs.extubate_with(|_| true).stridor);
+        update_patient(&mut long_stay, 60.0);
+        assert!(long_stay.get_organ::<Lungs>().unwrap().main_bronchus.resistance > resistance + 3.0);
+
+        assert_eq!(patient.extubate(), Some(Default::default()));
+        assert!(patient.get_organ::<Lungs>().unwrap().ventilator.is_none());
+        assert_eq!(patient.extubate(), None);
+    }
+
+    #[test]
+    fn test_tracheostomy_dead_space_secretions_and_dislodgement() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 600.0);
+        let mut collar = patient.fork();
+        collar.get_organ_mut::<Lungs>().unwrap().place_tracheostomy();
+        update_patient(&mut collar, 1800.0);
+        update_patient(&mut patient, 1800.0);
+
+        // Bypassing the upper airway's dead space clears the same CO2 with smaller breaths
+        let lungs = patient.get_organ::<Lungs>().unwrap();
+        let trach_lungs = collar.get_organ::<Lungs>().unwrap();
+        assert!(trach_lungs.tidal_volume_ml < lungs.tidal_volume_ml - 50.0);
+        assert!(trach_lungs.respiratory_load < lungs.respiratory_load);
+        assert!((trach_lungs.arterial_co2_mmhg - lungs.arterial_co2_mmhg).abs() < 2.0);
+
+        // Secretions narrow the tube until suctioned
+        collar.get_organ_mut::<Lungs>().unwrap().tracheostomy.as_mut().unwrap().secretions = 0.9;
+        update_patient(&mut collar, 60.0);
+        assert!(collar.get_organ::<Lungs>().unwrap().main_bronchus.resistance > 10.0);
+        collar.get_organ_mut::<Lungs>().unwrap().suction_tracheostomy();
+        update_patient(&mut collar, 60.0);
+        assert!(collar.get_organ::<Lungs>().unwrap().main_bronchus.resistance < 2.0);
+        assert!(collar.get_organ_mut::<Lungs>().unwrap().decannulate());
+
+        // A dislodged tube disconnects a paralysed patient from the ventilator
+        patient.set_neuromuscular_blockade(1.0);
+        let lungs = patient.get_organ_mut::<Lungs>().unwrap();
+        lungs.attach_ventilator(Ventilator::default());
+        lungs.place_tracheostomy();
+        update_patient(&mut patient, 600.0);
+        patient.get_organ_mut::<Lungs>().unwrap().dislodge_tracheostomy();
+        let mut mature = patient.fork();
+        mature.get_organ_mut::<Lungs>().unwrap().tracheostomy.as_mut().unwrap().placed_s = 8.0 * 86_400.0;
+        for timeline in [&mut patient, &mut mature] {
+            for _ in 0..3 {
+                update_patient(timeline, 60.0);
+            }
+            assert!(timeline.blood.gases.sao2_percent < 90.0, "{}", timeline.blood.gases.sao2_percent);
+        }
+        assert_eq!(patient.extubate(), None);
+        assert!(!patient.get_organ_mut::<Lungs>().unwrap().decannulate());
+
+        // A fresh stoma closes over; a mature tract takes the tube back
+        assert!(!patient.get_organ_mut::<Lungs>().unwrap().recannulate_tracheostomy());
+        assert!(mature.get_organ_mut::<Lungs>().unwrap().recannulate_tracheostomy());
+        update_patient(&mut mature, 180.0);
+        assert!(mature.blood.gases.sao2_percent > 95.0);
+    }
+
+    #[test]
+    fn test_flail_chest_limits_breathing_until_relieved() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 600.0);
+
+        // A stiff chest wall lowers respiratory system compliance with normal lungs
+        let mut stiff = patient.fork();
+        stiff.get_organ_mut::<Lungs>().unwrap().chest_wall_compliance = 0.4;
+        update_patient(&mut stiff, 60.0);
+        let lungs = stiff.get_organ::<Lungs>().unwrap();
+        assert!(lungs.respiratory_compliance_ml_cmh2o < 35.0);
+        assert!(lungs.lung_compliance_ml_cmh2o > 90.0);
+
+        // Painful, segmentally fractured ribs: shallow, rapid breaths that retain CO2
+        let mut flail = patient.fork();
+        flail.get_organ_mut::<Bones>().unwrap().fracture_ribs(5, true);
+        assert!(flail.get_organ::<Bones>().unwrap().is_flail_chest());
+        for _ in 0..30 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut flail, 60.0);
+        }
+        let lungs = patient.get_organ::<Lungs>().unwrap();
+        let flail_lungs = flail.get_organ::<Lungs>().unwrap();
+        assert!(flail_lungs.paradoxical_movement() > 0.0 && flail_lungs.rib_pain() > 0.5);
+        assert!(flail_lungs.tidal_volume_ml < lungs.tidal_volume_ml - 100.0);
+        assert!(flail_lungs.respiration_rate_bpm > lungs.respiration_rate_bpm + 3.0);
+        assert!(flail_lungs.arterial_co2_mmhg > lungs.arterial_co2_mmhg + 5.0, "{}", flail_lungs.arterial_co2_mmhg);
+        let flail_paco2 = flail_lungs.arterial_co2_mmhg;
+
+        // Analgesia relieves the splinting; positive pressure also splints the flail segment
+        let mut relieved = flail.fork();
+        relieved.set_analgesia(1.0);
+        flail.get_organ_mut::<Lungs>().unwrap().attach_ventilator(Ventilator::default());
+        for _ in 0..30 {
+            update_patient(&mut relieved, 60.0);
+            update_patient(&mut flail, 60.0);
+        }
+        let relieved_lungs = relieved.get_organ::<Lungs>().unwrap();
+        assert_eq!(relieved_lungs.rib_pain(), 0.0);
+        assert!(relieved_lungs.arterial_co2_mmhg < flail_paco2 - 3.0);
+        let ventilated_lungs = flail.get_organ::<Lungs>().unwrap();
+        assert_eq!(ventilated_lungs.paradoxical_movement(), 0.0);
+        assert!(ventilated_lungs.arterial_co2_mmhg < flail_paco2 - 8.0);
+    }
 }
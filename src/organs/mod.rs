@@ -16,3 +16,5 @@ pub mod spleen;
 pub mod bones;
 pub mod nerves;
 pub mod vascular;
+pub mod skin;
+pub mod muscle;
@@ -0,0 +1,266 @@
+//! Skeletal muscle organ simulation
+//!
+//! The body's largest tissue by mass, including:
+//! - Metabolic demand rising with exercise and convulsions, falling as muscle
+//!   is lost to necrosis
+//! - Crush and ischemic injury: compressed muscle necroses over hours, but its
+//!   contents stay trapped until the compression is released, then wash out
+//!   in a rush (reperfusion)
+//! - Exertional rhabdomyolysis from sustained maximal exercise or seizures
+//! - Release of potassium, myoglobin and creatine kinase into the extracellular
+//!   water, and plasma drawn into the swelling necrotic muscle
+//!
+//! Myoglobin is cleared by the kidneys, where it injures the tubules (pigment
+//! nephropathy); creatine kinase is cleared slowly outside them, so it stays
+//! raised for days after the myoglobin has gone.
+
+use crate::fluids::SECONDS_PER_DAY;
+use crate::organ::{Organ, OrganId};
+use crate::patient::Patient;
+
+/// Skeletal muscle mass of a 70 kg adult (kg)
+pub const NORMAL_MUSCLE_MASS_KG: f64 = 28.0;
+
+/// Whole-body metabolic rate at maximal exercise relative to rest (METs)
+pub const MAXIMAL_EXERCISE_METABOLIC_RATE: f64 = 8.0;
+
+/// Rise in whole-body metabolic rate during a generalized convulsion (fraction of resting)
+const CONVULSION_METABOLIC_RATE: f64 = 1.0;
+
+/// Normal serum creatine kinase (U/L)
+pub const NORMAL_CK_U_L: f64 = 100.0;
+
+/// Normal serum myoglobin (ng/mL)
+pub const NORMAL_MYOGLOBIN_NG_ML: f64 = 50.0;
+
+/// Time constant of compressed muscle necrosing (s)
+const ISCHEMIC_NECROSIS_S: f64 = 4.0 * 3600.0;
+
+/// Time constant of the contents of necrotic muscle washing out once it is reperfused (s)
+const REPERFUSION_WASHOUT_S: f64 = 1800.0;
+
+/// Activity above which sustained exertion breaks down muscle (fraction of maximal)
+const EXERTIONAL_INJURY_THRESHOLD: f64 = 0.7;
+
+/// Muscle necrosing per hour at maximal activity (fraction of mass)
+const EXERTIONAL_INJURY_PER_HOUR: f64 = 0.005;
+
+/// Time constant of necrotic muscle regenerating (s)
+const REGENERATION_S: f64 = 21.0 * SECONDS_PER_DAY;
+
+/// Potassium released per kg of necrotic muscle, net of uptake by other cells (mEq)
+const POTASSIUM_MEQ_PER_KG: f64 = 10.0;
+
+/// Myoglobin released per kg of necrotic muscle (mg)
+const MYOGLOBIN_MG_PER_KG: f64 = 100.0;
+
+/// Creatine kinase released per kg of necrotic muscle (U)
+const CK_U_PER_KG: f64 = 200_000.0;
+
+/// Time constant of creatine kinase clearance by the reticuloendothelial system (s),
+/// a half-life of about 36 hours
+const CK_CLEARANCE_S: f64 = 187_000.0;
+
+/// Plasma drawn into each kg of reperfused necrotic muscle as it swells (mL)
+const EDEMA_ML_PER_KG: f64 = 500.0;
+
+/// Time constant of plasma moving into swelling muscle (s)
+const EDEMA_S: f64 = 7200.0;
+
+/// Skeletal muscle organ
+#[derive(Debug, Clone)]
+pub struct Muscle {
+    id: OrganId,
+    /// Skeletal muscle mass (kg)
+    pub mass_kg: f64,
+    /// Fraction of maximal exercise capacity being worked, set from the heart
+    pub exercise_intensity: f64,
+    /// Whether a generalized seizure is driving the muscles, set from the brain
+    pub convulsing: bool,
+    /// Fraction of the muscle mass under compression (0.0 when released)
+    pub compression: f64,
+    /// Necrotic muscle (fraction of mass)
+    pub necrosis: f64,
+    /// Necrotic muscle whose contents are still trapped by compression (fraction of mass)
+    pub trapped_necrosis: f64,
+    /// Plasma sequestered in swollen necrotic muscle (mL)
+    pub edema_ml: f64,
+}
+
+impl Muscle {
+    /// Create new skeletal muscle
+    pub fn new(id: OrganId) -> Self {
+        Self {
+            id,
+            mass_kg: NORMAL_MUSCLE_MASS_KG,
+            exercise_intensity: 0.0,
+            convulsing: false,
+            compression: 0.0,
+            necrosis: 0.0,
+            trapped_necrosis: 0.0,
+            edema_ml: 0.0,
+        }
+    }
+
+    /// Compress muscle (a limb pinned under rubble, a prolonged lie after a
+    /// fall or overdose), starting ischemic necrosis
+    ///
+    /// # Arguments
+    /// * `fraction` - Fraction of the muscle mass compressed (a leg is ~0.2)
+    pub fn crush(&mut self, fraction: f64) {
+        self.compression = self.compression.max(fraction.clamp(0.0, 1.0));
+    }
+
+    /// Release the compression, reperfusing the crushed muscle
+    pub fn release_compression(&mut self) {
+        self.compression = 0.0;
+    }
+
+    /// Whether muscle is still compressed
+    pub fn is_compressed(&self) -> bool {
+        self.compression > 0.0
+    }
+
+    /// Rise in whole-body metabolic rate from working muscle (fraction of resting);
+    /// necrotic muscle does no work
+    pub fn metabolic_demand(&self) -> f64 {
+        let convulsion = if self.convulsing { CONVULSION_METABOLIC_RATE } else { 0.0 };
+        ((MAXIMAL_EXERCISE_METABOLIC_RATE - 1.0) * self.exercise_intensity + convulsion) * (1.0 - self.necrosis)
+    }
+
+    /// Necrotic muscle mass (kg)
+    pub fn necrotic_mass_kg(&self) -> f64 {
+        self.necrosis * self.mass_kg
+    }
+
+    /// Necrose compressed muscle, whose contents stay trapped until released
+    fn update_compression(&mut self, delta_time_s: f64) {
+        let viable = (self.compression - self.trapped_necrosis).max(0.0);
+        let necrosed = viable * (1.0 - (-delta_time_s / ISCHEMIC_NECROSIS_S).exp());
+        self.trapped_necrosis += necrosed;
+        self.necrosis += necrosed;
+    }
+
+    /// Break down muscle driven beyond its capacity by exercise or seizures
+    ///
+    /// # Returns
+    /// Necrotic fraction released at once
+    fn update_exertion(&mut self, delta_time_s: f64) -> f64 {
+        let activity = self.exercise_intensity.max(if self.convulsing { 1.0 } else { 0.0 });
+        let overload = ((activity - EXERTIONAL_INJURY_THRESHOLD) / (1.0 - EXERTIONAL_INJURY_THRESHOLD)).max(0.0);
+        let necrosed = EXERTIONAL_INJURY_PER_HOUR * overload * (1.0 - self.necrosis) * delta_time_s / 3600.0;
+        self.necrosis += necrosed;
+        necrosed
+    }
+
+    /// Release the contents of necrotic muscle into the extracellular water and
+    /// draw plasma into it as it swells
+    fn release_contents(&mut self, patient: &mut Patient, released: f64, delta_time_s: f64) {
+        let released_kg = released * self.mass_kg;
+        let extracellular_ml = patient.fluids.extracellular_ml().max(1000.0);
+        let potassium = patient.blood.get_potassium_meq_l();
+        patient.blood.set_potassium_meq_l(potassium + POTASSIUM_MEQ_PER_KG * released_kg / (extracellular_ml / 1000.0));
+        let chemistry = &mut patient.blood.chemistry;
+        chemistry.myoglobin_ng_ml += MYOGLOBIN_MG_PER_KG * released_kg * 1e6 / extracellular_ml;
+        chemistry.ck_u_l += CK_U_PER_KG * released_kg / (extracellular_ml / 1000.0);
+        let excess_ck = (chemistry.ck_u_l - NORMAL_CK_U_L).max(0.0);
+        chemistry.ck_u_l -= excess_ck * (1.0 - (-delta_time_s / CK_CLEARANCE_S).exp());
+
+        // Reperfused necrotic muscle swells with plasma, and gives it back as it regenerates
+        let reperfused_kg = (self.necrosis - self.trapped_necrosis) * self.mass_kg;
+        let shift = (EDEMA_ML_PER_KG * reperfused_kg - self.edema_ml) * (1.0 - (-delta_time_s / EDEMA_S).exp());
+        if shift > 0.0 {
+            self.edema_ml += patient.fluids.remove_plasma(shift);
+        } else {
+            self.edema_ml += shift;
+            patient.fluids.add_plasma(-shift);
+        }
+    }
+}
+
+impl Organ for Muscle {
+    fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let mut released = 0.0;
+        if self.is_compressed() {
+            self.update_compression(delta_time_s);
+        } else {
+            let washout = self.trapped_necrosis * (1.0 - (-delta_time_s / REPERFUSION_WASHOUT_S).exp());
+            self.trapped_necrosis -= washout;
+            released += washout;
+        }
+        released += self.update_exertion(delta_time_s);
+
+        // Satellite cells regenerate necrotic muscle once it is reperfused
+        let regenerated = (self.necrosis - self.trapped_necrosis) * (1.0 - (-delta_time_s / REGENERATION_S).exp());
+        self.necrosis -= regenerated;
+        self.release_contents(patient, released, delta_time_s);
+    }
+
+    fn get_summary(&self) -> String {
+        format!(
+            "Muscle: Mass={:.1} kg, Necrosis={:.1}%{}{}",
+            self.mass_kg,
+            self.necrosis * 100.0,
+            if self.is_compressed() { format!(", COMPRESSED ({:.0}% of mass)", self.compression * 100.0) } else { String::new() },
+            if self.edema_ml > 50.0 { format!(", Edema={:.0} mL", self.edema_ml) } else { String::new() }
+        )
+    }
+
+    fn get_id(&self) -> OrganId {
+        self.id
+    }
+
+    fn get_type(&self) -> &'static str {
+        "Muscle"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::kidneys::Kidneys;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_released_crush_causes_rhabdomyolysis_and_pigment_nephropathy() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 600.0);
+        patient.get_organ_mut::<Muscle>().unwrap().crush(0.2);
+        for _ in 0..240 {
+            update_patient(&mut patient, 60.0);
+        }
+        // The necrotic muscle's contents stay trapped until the limb is freed
+        assert!(patient.get_organ::<Muscle>().unwrap().necrosis > 0.1);
+        assert!(patient.blood.chemistry.ck_u_l < 200.0);
+        patient.get_organ_mut::<Muscle>().unwrap().release_compression();
+
+        // Acidemia acidifies the urine, precipitating the filtered myoglobin in the tubules
+        let mut acidemic = patient.fork();
+        acidemic.blood.chemistry.bicarbonate_meq_l = 12.0;
+        let mut peak_potassium: f64 = 0.0;
+        for _ in 0..720 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut acidemic, 60.0);
+            peak_potassium = peak_potassium.max(patient.blood.chemistry.potassium_meq_l);
+        }
+        assert!(peak_potassium > 5.5, "{}", peak_potassium);
+        assert!(patient.blood.chemistry.ck_u_l > 10_000.0);
+
+        let kidneys = patient.get_organ::<Kidneys>().unwrap();
+        let acidemic_kidneys = acidemic.get_organ::<Kidneys>().unwrap();
+        assert!(acidemic_kidneys.tubular_injury > kidneys.tubular_injury + 0.2);
+        assert!(acidemic_kidneys.gfr_ml_per_min < kidneys.gfr_ml_per_min / 1.5);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::organ::{Organ, OrganId};
 use crate::patient::Patient;
+use crate::signals::Signal;
 use std::any::Any;
 use std::collections::HashMap;
 
@@ -79,7 +80,7 @@ impl NerveBundle {
 }
 
 /// Peripheral Nervous System - nerves throughout the body
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Nerves {
     id: OrganId,
     pub nerve_bundles: Vec<NerveBundle>,
@@ -95,33 +96,34 @@ pub struct Nerves {
     pub sensory_function: f64,           // 0.0-1.0
     pub motor_function: f64,             // 0.0-1.0
     pub autonomic_function: f64,         // 0.0-1.0
+    pub neuromuscular_blockade: f64,     // 0.0-1.0 (1.0 = complete paralysis)
 }
 
 impl Nerves {
     pub fn new(id: i32) -> Self {
-        let mut nerve_bundles = Vec::new();
-
-        // Major peripheral nerves
-        // Motor nerves (A-alpha fibers)
-        nerve_bundles.push(NerveBundle::new("Median Nerve", NerveFiberType::AAlpha, 50000));
-        nerve_bundles.push(NerveBundle::new("Ulnar Nerve", NerveFiberType::AAlpha, 40000));
-        nerve_bundles.push(NerveBundle::new("Radial Nerve", NerveFiberType::AAlpha, 45000));
-        nerve_bundles.push(NerveBundle::new("Sciatic Nerve", NerveFiberType::AAlpha, 80000));
-        nerve_bundles.push(NerveBundle::new("Femoral Nerve", NerveFiberType::AAlpha, 60000));
-        nerve_bundles.push(NerveBundle::new("Tibial Nerve", NerveFiberType::AAlpha, 50000));
-
-        // Sensory nerves (A-beta fibers)
-        nerve_bundles.push(NerveBundle::new("Sensory - Arms", NerveFiberType::ABeta, 100000));
-        nerve_bundles.push(NerveBundle::new("Sensory - Legs", NerveFiberType::ABeta, 120000));
-        nerve_bundles.push(NerveBundle::new("Sensory - Torso", NerveFiberType::ABeta, 80000));
-
-        // Pain fibers (A-delta and C fibers)
-        nerve_bundles.push(NerveBundle::new("Pain - Fast", NerveFiberType::ADelta, 150000));
-        nerve_bundles.push(NerveBundle::new("Pain - Slow", NerveFiberType::C, 200000));
-
-        // Autonomic nerves (B and C fibers)
-        nerve_bundles.push(NerveBundle::new("Vagus Nerve", NerveFiberType::B, 100000));
-        nerve_bundles.push(NerveBundle::new("Sympathetic Chain", NerveFiberType::C, 80000));
+        let nerve_bundles = vec![
+            // Major peripheral nerves
+            // Motor nerves (A-alpha fibers)
+            NerveBundle::new("Median Nerve", NerveFiberType::AAlpha, 50000),
+            NerveBundle::new("Ulnar Nerve", NerveFiberType::AAlpha, 40000),
+            NerveBundle::new("Radial Nerve", NerveFiberType::AAlpha, 45000),
+            NerveBundle::new("Sciatic Nerve", NerveFiberType::AAlpha, 80000),
+            NerveBundle::new("Femoral Nerve", NerveFiberType::AAlpha, 60000),
+            NerveBundle::new("Tibial Nerve", NerveFiberType::AAlpha, 50000),
+
+            // Sensory nerves (A-beta fibers)
+            NerveBundle::new("Sensory - Arms", NerveFiberType::ABeta, 100000),
+            NerveBundle::new("Sensory - Legs", NerveFiberType::ABeta, 120000),
+            NerveBundle::new("Sensory - Torso", NerveFiberType::ABeta, 80000),
+
+            // Pain fibers (A-delta and C fibers)
+            NerveBundle::new("Pain - Fast", NerveFiberType::ADelta, 150000),
+            NerveBundle::new("Pain - Slow", NerveFiberType::C, 200000),
+
+            // Autonomic nerves (B and C fibers)
+            NerveBundle::new("Vagus Nerve", NerveFiberType::B, 100000),
+            NerveBundle::new("Sympathetic Chain", NerveFiberType::C, 80000),
+        ];
 
         Self {
             id: id as usize,
@@ -138,6 +140,7 @@ impl Nerves {
             sensory_function: 1.0,
             motor_function: 1.0,
             autonomic_function: 1.0,
+            neuromuscular_blockade: 0.0,
         }
     }
 
@@ -162,6 +165,19 @@ impl Nerves {
         }
     }
 
+    /// Set the depth of neuromuscular blockade (e.g. a rocuronium infusion)
+    ///
+    /// # Arguments
+    /// * `level` - 0.0 = none, 1.0 = complete paralysis, including the respiratory muscles
+    pub fn set_neuromuscular_blockade(&mut self, level: f64) {
+        self.neuromuscular_blockade = level.clamp(0.0, 1.0);
+    }
+
+    /// Fraction of motor impulses transmitted to the muscles at the neuromuscular junction (0.0-1.0)
+    pub fn neuromuscular_transmission(&self) -> f64 {
+        1.0 - self.neuromuscular_blockade
+    }
+
     /// Calculate motor function based on motor nerve health
     fn calculate_motor_function(&self) -> f64 {
         let motor_nerves: Vec<&NerveBundle> = self.nerve_bundles
@@ -215,24 +231,17 @@ impl Organ for Nerves {
     fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
         // 1. Neurotransmitter synthesis and degradation
         // Acetylcholine - requires choline (from diet) and acetyl-CoA (from glucose)
-        let glucose_factor = (patient.blood.chemistry.glucose_mg_dl / 90.0).clamp(0.5, 1.5);
+        let glucose_factor = (patient.blood.get_glucose_mg_dl() / 90.0).clamp(0.5, 1.5);
         self.acetylcholine_level = (self.acetylcholine_level * 0.98 + glucose_factor * 0.02)
             .clamp(0.1, 2.0);
 
-        // Norepinephrine - affected by stress response and blood pressure
-        let map = patient.blood.get_mean_arterial_pressure();
-        let stress_factor = if map < 70.0 {
-            1.5 // Increase to raise BP
-        } else if map > 110.0 {
-            0.7 // Decrease to lower BP
-        } else {
-            1.0
-        };
+        // Norepinephrine - follows sympathoadrenal drive
+        let stress_factor = patient.signals.level(Signal::Catecholamines);
         self.norepinephrine_level = (self.norepinephrine_level * 0.95 + stress_factor * 0.05)
             .clamp(0.3, 2.0);
 
         // Dopamine, serotonin, GABA - affected by blood oxygen and glucose
-        let o2_sat = patient.blood.gases.sao2_percent / 100.0;
+        let o2_sat = patient.blood.get_sao2_percent() / 100.0;
         let metabolic_health = (o2_sat + glucose_factor) / 2.0;
 
         self.dopamine_level = (self.dopamine_level * 0.97 + metabolic_health * 0.03)
@@ -270,8 +279,8 @@ impl Organ for Nerves {
 
         // 3. Update nerve conduction efficiency based on blood chemistry
         // Electrolytes are critical for nerve conduction
-        let sodium_factor = (patient.blood.chemistry.sodium_meq_l / 140.0).clamp(0.5, 1.5);
-        let potassium_factor = (patient.blood.chemistry.potassium_meq_l / 4.0).clamp(0.5, 1.5);
+        let sodium_factor = (patient.blood.get_sodium_meq_l() / 140.0).clamp(0.5, 1.5);
+        let potassium_factor = (patient.blood.get_potassium_meq_l() / 4.0).clamp(0.5, 1.5);
         let calcium_factor = (patient.blood.chemistry.calcium_mg_dl / 9.5).clamp(0.5, 1.5);
 
         let electrolyte_efficiency = (sodium_factor + potassium_factor + calcium_factor) / 3.0;
@@ -312,8 +321,8 @@ impl Organ for Nerves {
         }
 
         // 7. Blood glucose effects - both hypo and hyperglycemia damage nerves
-        let glucose = patient.blood.chemistry.glucose_mg_dl;
-        if glucose < 60.0 || glucose > 180.0 {
+        let glucose = patient.blood.get_glucose_mg_dl();
+        if !(60.0..=180.0).contains(&glucose) {
             let glucose_damage = 0.0001 * delta_time_s;
             for nerve in &mut self.nerve_bundles {
                 nerve.health -= glucose_damage;
@@ -356,4 +365,8 @@ impl Organ for Nerves {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
 }
@@ -1,9 +1,71 @@
 //! Pancreas organ simulation
 //!
 //! Dual function: endocrine (hormones) and exocrine (digestive enzymes)
+//!
+//! The endocrine model tracks plasma insulin and glucagon, insulin
+//! sensitivity, and beta-cell mass so that hypoglycemia, hyperglycemia,
+//! and diabetic ketoacidosis (DKA) emerge from hormone balance.
+//!
+//! Acute pancreatitis (from a gallstone blocking the ampulla, alcohol, drugs or
+//! severe hypertriglyceridemia) leaks amylase and lipase into the blood and
+//! sequesters plasma around the gland. Inflamed parenchyma is poorly perfused,
+//! so under-resuscitated shock turns it necrotic, and necrosis sustains a
+//! systemic inflammatory response that can injure the lungs.
 
 use crate::organ::{Organ, OrganId};
 use crate::patient::Patient;
+use crate::signals::Signal;
+use crate::tissue::{TissuePerfusion, TissueState};
+
+/// Distribution volume used to convert insulin secretion to plasma concentration (mL)
+const INSULIN_DISTRIBUTION_ML: f64 = 15_000.0;
+
+/// Plasma half-life of insulin and glucagon (minutes)
+const HORMONE_HALF_LIFE_MIN: f64 = 5.0;
+
+/// Serum amylase with a healthy pancreas (U/L)
+const BASAL_AMYLASE_U_L: f64 = 60.0;
+
+/// Serum lipase with a healthy pancreas (U/L)
+const BASAL_LIPASE_U_L: f64 = 30.0;
+
+/// Plasma half-life of amylase, cleared by the kidneys (s)
+const AMYLASE_HALF_LIFE_S: f64 = 43_200.0;
+
+/// Plasma half-life of lipase, which stays raised for longer than amylase (s)
+const LIPASE_HALF_LIFE_S: f64 = 86_400.0;
+
+/// Time constant of acinar inflammation building while its cause persists (s)
+const PANCREATITIS_ONSET_S: f64 = 21_600.0;
+
+/// Time constant of acinar inflammation settling once its cause is removed (s)
+const PANCREATITIS_RESOLUTION_S: f64 = 259_200.0;
+
+/// Inflammation caused by a gallstone obstructing the pancreatic duct
+const GALLSTONE_PANCREATITIS: f64 = 0.6;
+
+/// Triglyceride level above which chylomicrons injure the pancreas (mg/dL)
+const PANCREATITIS_TRIGLYCERIDES_MG_DL: f64 = 1000.0;
+
+/// Plasma sequestered around a maximally inflamed pancreas once tissue pressure
+/// balances the leak (mL)
+const MAX_SEQUESTERED_ML: f64 = 6000.0;
+
+/// Time constant of plasma leaking around the inflamed gland (s)
+const SEQUESTRATION_S: f64 = 43_200.0;
+
+/// Cause of acute pancreatitis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PancreatitisCause {
+    /// Gallstone impacted at the ampulla, obstructing the pancreatic duct
+    Gallstone,
+    /// Alcohol binge
+    Alcohol,
+    /// Triglycerides above ~1000 mg/dL
+    Hypertriglyceridemia,
+    /// Drug toxicity (azathioprine, valproate, didanosine)
+    Drug,
+}
 
 /// Digestive enzymes
 #[derive(Debug, Clone)]
@@ -13,18 +75,58 @@ pub struct DigestiveEnzymes {
     pub lipase_concentration: f64,
 }
 
+/// Diabetes mellitus phenotype
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiabetesType {
+    /// Autoimmune beta-cell destruction - absolute insulin deficiency
+    Type1,
+    /// Insulin resistance with relative insulin deficiency
+    Type2,
+}
+
+/// Route for exogenous insulin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InsulinRoute {
+    /// Subcutaneous injection - absorbed over hours
+    Subcutaneous,
+    /// Intravenous bolus - immediate
+    Intravenous,
+}
+
 /// Pancreas organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pancreas {
     id: OrganId,
-    /// Insulin secretion rate (units/min)
+    /// Insulin secretion rate (mU/min, normal basal ~20)
     pub insulin_secretion_rate: f64,
-    /// Glucagon secretion rate (units/min)
+    /// Glucagon secretion rate (relative to basal, 1.0 = normal)
     pub glucagon_secretion_rate: f64,
+    /// Functional beta-cell mass (0.0 = none, 1.0 = normal)
+    pub beta_cell_mass: f64,
+    /// Insulin resistance (1.0 = normal sensitivity, higher = more resistant)
+    pub insulin_resistance: f64,
+    /// Diabetes phenotype, if any
+    pub diabetes: Option<DiabetesType>,
+    /// Subcutaneous insulin depot awaiting absorption (units)
+    pub subcutaneous_insulin_units: f64,
+    /// Continuous intravenous insulin infusion (units/hour)
+    pub insulin_infusion_units_per_hr: f64,
+    /// Intravenous bolus insulin not yet delivered to plasma (units)
+    pending_iv_insulin_units: f64,
     /// Digestive enzymes production
     pub digestive_enzymes: DigestiveEnzymes,
     /// Enzyme production rate (mL/min)
     pub enzyme_production_rate: f64,
+    /// Cause of acute pancreatitis, if inflamed
+    pub pancreatitis: Option<PancreatitisCause>,
+    /// Acinar inflammation (0.0 = none, 1.0 = severe acute pancreatitis)
+    pub inflammation: f64,
+    /// Perfusion and viability of the pancreatic parenchyma
+    pub parenchyma: TissuePerfusion,
+    /// Whether a stone in the common bile duct obstructs the pancreatic duct
+    pub duct_obstructed: bool,
+    /// Plasma sequestered in the retroperitoneum around the gland (mL)
+    pub sequestered_fluid_ml: f64,
 }
 
 impl Pancreas {
@@ -32,54 +134,255 @@ impl Pancreas {
     pub fn new(id: OrganId) -> Self {
         Self {
             id,
-            insulin_secretion_rate: 1.0,
-            glucagon_secretion_rate: 0.5,
+            insulin_secretion_rate: 20.0,
+            glucagon_secretion_rate: 1.0,
+            beta_cell_mass: 1.0,
+            insulin_resistance: 1.0,
+            diabetes: None,
+            subcutaneous_insulin_units: 0.0,
+            insulin_infusion_units_per_hr: 0.0,
+            pending_iv_insulin_units: 0.0,
             digestive_enzymes: DigestiveEnzymes {
                 volume_ml: 0.0,
                 amylase_concentration: 1.0,
                 lipase_concentration: 1.0,
             },
             enzyme_production_rate: 5.0,
+            pancreatitis: None,
+            inflammation: 0.0,
+            parenchyma: TissuePerfusion::new(0.5, 0.25, 21_600.0),
+            duct_obstructed: false,
+            sequestered_fluid_ml: 0.0,
+        }
+    }
+
+    /// Start acute pancreatitis from a toxic insult
+    ///
+    /// Gallstone and hypertriglyceridemic pancreatitis also arise on their own
+    /// while the duct is obstructed or triglycerides are very high.
+    ///
+    /// # Arguments
+    /// * `cause` - Cause of the pancreatitis
+    /// * `severity` - Acinar inflammation (0.0-1.0)
+    pub fn induce_pancreatitis(&mut self, cause: PancreatitisCause, severity: f64) {
+        self.inflammation = self.inflammation.max(severity.clamp(0.0, 1.0));
+        self.pancreatitis.get_or_insert(cause);
+    }
+
+    /// Fraction of the gland that is necrotic (0.0-1.0)
+    pub fn necrosis(&self) -> f64 {
+        self.parenchyma.injury
+    }
+
+    /// Systemic inflammatory response to pancreatitis (0.0 = none, 1.0 = cytokine storm)
+    ///
+    /// Oedematous pancreatitis provokes a mild response; necrosis a severe one.
+    pub fn systemic_inflammation(&self) -> f64 {
+        (self.inflammation * (0.5 + self.necrosis())).min(1.0)
+    }
+
+    /// Advance acute pancreatitis: inflammation, enzyme leak, sequestration and necrosis
+    ///
+    /// # Arguments
+    /// * `patient` - The patient, whose plasma is sequestered and enzymes rise
+    /// * `delta_time_s` - Time step in seconds
+    fn update_pancreatitis(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // Inflammation builds while its cause persists; necrotic tissue keeps it going
+        let triglycerides = patient.blood.chemistry.triglycerides_mg_dl;
+        let hypertriglyceridemia = ((triglycerides - PANCREATITIS_TRIGLYCERIDES_MG_DL) / 2000.0).clamp(0.0, 0.8);
+        let obstruction = if self.duct_obstructed { GALLSTONE_PANCREATITIS } else { 0.0 };
+        if self.pancreatitis.is_none() {
+            if obstruction > 0.0 {
+                self.pancreatitis = Some(PancreatitisCause::Gallstone);
+            } else if hypertriglyceridemia > 0.0 {
+                self.pancreatitis = Some(PancreatitisCause::Hypertriglyceridemia);
+            }
+        }
+        let target = obstruction.max(hypertriglyceridemia).max(self.necrosis());
+        let time_constant =
+            if target > self.inflammation { PANCREATITIS_ONSET_S } else { PANCREATITIS_RESOLUTION_S };
+        self.inflammation += (target - self.inflammation) * (1.0 - (-delta_time_s / time_constant).exp());
+        if self.inflammation < 0.01 && target == 0.0 {
+            self.inflammation = 0.0;
+            self.pancreatitis = None;
+        }
+
+        // Oedema and microthrombi starve the inflamed gland, more so in shock
+        let map = patient.blood.get_mean_arterial_pressure();
+        let splanchnic_perfusion = ((map - 30.0) / 65.0).clamp(0.0, 1.2);
+        let oxygen_content = (patient.blood.get_sao2_percent() / 98.0).clamp(0.0, 1.0);
+        let ischemic_time = patient.difficulty.ischemic_time_s(delta_time_s);
+        self.parenchyma.update(splanchnic_perfusion * (1.0 - 0.6 * self.inflammation), oxygen_content, ischemic_time);
+
+        // Injured acinar cells leak enzymes into the blood
+        let chemistry = &mut patient.blood.chemistry;
+        let clearance = |half_life_s: f64| 1.0 - (-delta_time_s * std::f64::consts::LN_2 / half_life_s).exp();
+        let amylase_target = BASAL_AMYLASE_U_L * (1.0 + 20.0 * self.inflammation);
+        chemistry.amylase_u_l += (amylase_target - chemistry.amylase_u_l) * clearance(AMYLASE_HALF_LIFE_S);
+        let lipase_target = BASAL_LIPASE_U_L * (1.0 + 60.0 * self.inflammation);
+        chemistry.lipase_u_l += (lipase_target - chemistry.lipase_u_l) * clearance(LIPASE_HALF_LIFE_S);
+
+        // Leaky peripancreatic capillaries sequester plasma until tissue pressure
+        // balances the leak; it is resorbed slowly as the inflammation settles
+        let sequestration_target = MAX_SEQUESTERED_ML * self.inflammation.powi(2);
+        let time_constant =
+            if sequestration_target > self.sequestered_fluid_ml { SEQUESTRATION_S } else { PANCREATITIS_RESOLUTION_S };
+        let shift = (sequestration_target - self.sequestered_fluid_ml) * (1.0 - (-delta_time_s / time_constant).exp());
+        if shift > 0.0 {
+            self.sequestered_fluid_ml += patient.fluids.remove_plasma(shift);
+        } else {
+            self.sequestered_fluid_ml += shift;
+            patient.fluids.add_plasma(-shift);
         }
     }
+
+    /// Apply a diabetes preset
+    ///
+    /// # Arguments
+    /// * `diabetes_type` - Type 1 (absent beta cells) or Type 2 (resistance)
+    pub fn apply_diabetes(&mut self, diabetes_type: DiabetesType) {
+        match diabetes_type {
+            DiabetesType::Type1 => {
+                self.beta_cell_mass = 0.0;
+                self.insulin_resistance = 1.0;
+            }
+            DiabetesType::Type2 => {
+                self.beta_cell_mass = 0.5;
+                self.insulin_resistance = 3.0;
+            }
+        }
+        self.diabetes = Some(diabetes_type);
+    }
+
+    /// Damage beta cells (e.g. autoimmune insulitis, pancreatectomy)
+    ///
+    /// # Arguments
+    /// * `damage_fraction` - Fraction of remaining beta cells destroyed (0.0-1.0)
+    pub fn damage_beta_cells(&mut self, damage_fraction: f64) {
+        self.beta_cell_mass *= 1.0 - damage_fraction.clamp(0.0, 1.0);
+    }
+
+    /// Give an exogenous insulin bolus
+    ///
+    /// # Arguments
+    /// * `units` - Insulin dose in units
+    /// * `route` - Subcutaneous (absorbed over hours) or intravenous (next update)
+    pub fn give_insulin(&mut self, units: f64, route: InsulinRoute) {
+        let units = units.max(0.0);
+        match route {
+            InsulinRoute::Subcutaneous => self.subcutaneous_insulin_units += units,
+            InsulinRoute::Intravenous => self.pending_iv_insulin_units += units,
+        }
+    }
+
+    /// Set a continuous intravenous insulin infusion
+    ///
+    /// # Arguments
+    /// * `units_per_hr` - Infusion rate (0.0 stops the infusion)
+    pub fn set_insulin_infusion(&mut self, units_per_hr: f64) {
+        self.insulin_infusion_units_per_hr = units_per_hr.max(0.0);
+    }
+
+    /// Insulin action normalized to a healthy fasting state (1.0 = normal)
+    fn insulin_effect(&self, insulin_uu_ml: f64) -> f64 {
+        insulin_uu_ml / 10.0 / self.insulin_resistance.max(0.1)
+    }
 }
 
 impl Organ for Pancreas {
     fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
-        // Endocrine function: regulate blood glucose
-        let glucose_error = patient.blood.chemistry.glucose_mg_dl - 90.0;
+        self.update_pancreatitis(patient, delta_time_s);
+        let dt_min = delta_time_s / 60.0;
+        let clearance = (-dt_min * std::f64::consts::LN_2 / HORMONE_HALF_LIFE_MIN).exp();
+        let incretins = patient.signals.level(Signal::Incretins);
+        let glucose = patient.blood.get_glucose_mg_dl();
+        let chemistry = &mut patient.blood.chemistry;
 
-        if glucose_error > 0.0 {
-            // High glucose: secrete insulin
-            self.insulin_secretion_rate = 1.0 + glucose_error * 0.05;
-            self.glucagon_secretion_rate = 0.5;
+        // Endocrine function: beta cells secrete insulin in proportion to glucose
+        // Basal ~20 mU/min at 90 mg/dL, rising steeply after meals as gut
+        // incretins amplify the response to glucose; necrosis destroys islets
+        let glucose_stimulus = (glucose / 90.0).powi(2);
+        let islets = self.beta_cell_mass * (1.0 - self.necrosis());
+        self.insulin_secretion_rate = 20.0 * islets * glucose_stimulus * incretins;
 
-            // Insulin lowers blood glucose
-            let glucose_consumed = self.insulin_secretion_rate * delta_time_s / 60.0;
-            patient.blood.chemistry.glucose_mg_dl -= glucose_consumed;
-        } else {
-            // Low glucose: secrete glucagon
-            self.insulin_secretion_rate = 0.5;
-            self.glucagon_secretion_rate = 1.0 - glucose_error * 0.05;
+        // Subcutaneous depot absorbs with ~1 hour half-life
+        let absorbed_units = self.subcutaneous_insulin_units
+            * (1.0 - (-dt_min * std::f64::consts::LN_2 / 60.0).exp());
+        self.subcutaneous_insulin_units -= absorbed_units;
 
-            // Glucagon raises blood glucose
-            let glucose_produced = self.glucagon_secretion_rate * delta_time_s / 60.0;
-            patient.blood.chemistry.glucose_mg_dl += glucose_produced;
-        }
+        let exogenous_units = absorbed_units
+            + std::mem::take(&mut self.pending_iv_insulin_units)
+            + self.insulin_infusion_units_per_hr * dt_min / 60.0;
+
+        let secreted_uu_ml = (self.insulin_secretion_rate * 1000.0 * dt_min + exogenous_units * 1.0e6)
+            / INSULIN_DISTRIBUTION_ML;
+        chemistry.insulin_uu_ml = chemistry.insulin_uu_ml * clearance + secreted_uu_ml;
+
+        // Alpha cells secrete glucagon during hypoglycemia; insulin suppresses it
+        // Glucose suppression depends on intra-islet insulin, so glucagon stays
+        // inappropriately high when insulin is absent despite hyperglycemia
+        let insulin_presence = (chemistry.insulin_uu_ml / 10.0).min(1.0);
+        let glucose_suppression = 1.0 + ((90.0 / glucose.max(20.0)).powi(2) - 1.0) * insulin_presence;
+        let insulin_suppression = (12.0 / (chemistry.insulin_uu_ml + 2.0)).clamp(0.5, 4.0);
+        self.glucagon_secretion_rate = (glucose_suppression * insulin_suppression).clamp(0.2, 6.0);
+        let glucagon_target = 75.0 * self.glucagon_secretion_rate;
+        chemistry.glucagon_pg_ml = glucagon_target + (chemistry.glucagon_pg_ml - glucagon_target) * clearance;
+
+        // Glucose fluxes (mg/dL per minute)
+        let insulin_effect = self.insulin_effect(chemistry.insulin_uu_ml);
+        let glucagon_effect = (chemistry.glucagon_pg_ml / 75.0).sqrt();
+
+        // Hepatic glucose output: stimulated by glucagon, suppressed by insulin
+        let hepatic_output = (glucagon_effect / (0.5 + 0.5 * insulin_effect)).min(4.0);
+        // Brain and red cells take up glucose without insulin (saturable)
+        let insulin_independent_uptake = 0.5 * (glucose / (glucose + 10.0)) * (100.0 / 90.0);
+        // Muscle and fat uptake requires insulin
+        let insulin_dependent_uptake = 0.5 * insulin_effect * glucose / 90.0;
+        // Renal glycosuria above the threshold of ~180 mg/dL
+        let renal_loss = (glucose - 180.0).max(0.0) * 0.01;
 
-        patient.blood.chemistry.glucose_mg_dl = patient.blood.chemistry.glucose_mg_dl.clamp(60.0, 200.0);
+        let glucose_change = hepatic_output - insulin_independent_uptake - insulin_dependent_uptake - renal_loss;
+
+        // Ketogenesis when insulin is nearly absent and glucagon is high
+        let absolute_insulin = chemistry.insulin_uu_ml / 5.0;
+        let ketone_production = 0.01 * (1.0 - absolute_insulin).max(0.0).powi(2) * glucagon_effect.powi(2);
+        let ketone_clearance = 0.02 * chemistry.beta_hydroxybutyrate_mmol_l
+            * (0.2 + 0.8 * insulin_effect.min(1.0));
+        let ketone_change = (ketone_production - ketone_clearance) * dt_min;
+        chemistry.beta_hydroxybutyrate_mmol_l = (chemistry.beta_hydroxybutyrate_mmol_l + ketone_change).max(0.0);
+        patient.blood.set_glucose_mg_dl(glucose + glucose_change * dt_min);
 
         // Exocrine function: produce digestive enzymes
-        let enzyme_produced = self.enzyme_production_rate * delta_time_s / 60.0;
+        let enzyme_produced = self.enzyme_production_rate * (1.0 - self.necrosis()) * delta_time_s / 60.0;
         self.digestive_enzymes.volume_ml += enzyme_produced;
     }
 
     fn get_summary(&self) -> String {
+        let pancreatitis = match self.pancreatitis {
+            Some(cause) => {
+                let necrotizing = if self.parenchyma.state == TissueState::Healthy && self.necrosis() < 0.05 {
+                    String::new()
+                } else {
+                    format!(", necrosis {:.0}%", self.necrosis() * 100.0)
+                };
+                format!(
+                    ", PANCREATITIS ({:?}, inflammation {:.0}%{}, sequestered {:.0} mL)",
+                    cause,
+                    self.inflammation * 100.0,
+                    necrotizing,
+                    self.sequestered_fluid_ml
+                )
+            }
+            None => String::new(),
+        };
         format!(
-            "Pancreas: Insulin={:.1} U/min, Glucagon={:.1} U/min, Enzymes={:.0} mL",
+            "Pancreas: Insulin={:.1} mU/min, Glucagon={:.1}x, Beta cells={:.0}%, Resistance={:.1}x, Enzymes={:.0} mL{}",
             self.insulin_secretion_rate,
             self.glucagon_secretion_rate,
-            self.digestive_enzymes.volume_ml
+            self.beta_cell_mass * 100.0,
+            self.insulin_resistance,
+            self.digestive_enzymes.volume_ml,
+            pancreatitis
         )
     }
 
@@ -98,4 +401,88 @@ impl Organ for Pancreas {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluids::IntakeRoute;
+    use crate::organs::gallbladder::Gallbladder;
+    use crate::organs::lungs::Lungs;
+    use crate::patient::{initialize_patient, update_patient};
+
+    /// Run a patient for whole hours, giving an hourly intravenous volume
+    fn run_hours(patient: &mut Patient, hours: usize, iv_ml_per_hr: f64) {
+        for _ in 0..hours {
+            patient.give_fluid(IntakeRoute::Intravenous, iv_ml_per_hr);
+            for _ in 0..360 {
+                update_patient(patient, 10.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_insulin_deficiency_causes_ketoacidosis_that_insulin_and_fluids_resolve() {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        patient.get_organ_mut::<Pancreas>().unwrap().apply_diabetes(DiabetesType::Type1);
+
+        // Without insulin, unopposed glucagon drives ketogenesis and the ketoacids
+        // consume bicarbonate, leaving unmeasured anions behind
+        run_hours(&mut patient, 8, 85.0);
+        let chemistry = &patient.blood.chemistry;
+        let gases = &patient.blood.gases;
+        assert!(chemistry.glucose_mg_dl > 250.0);
+        assert!(chemistry.beta_hydroxybutyrate_mmol_l > 3.0);
+        assert!(chemistry.bicarbonate_meq_l < 20.0);
+        assert!(gases.ph < 7.35);
+        assert!(gases.calculate_anion_gap(chemistry.sodium_meq_l, chemistry.chloride_meq_l) > 20.0);
+
+        // An insulin infusion halts ketogenesis, the ketones are metabolized back to
+        // bicarbonate
+        patient.get_organ_mut::<Pancreas>().unwrap().set_insulin_infusion(2.0);
+        patient.give_fluid(IntakeRoute::Intravenous, 1000.0);
+        run_hours(&mut patient, 6, 250.0);
+        let chemistry = &patient.blood.chemistry;
+        let gases = &patient.blood.gases;
+        assert!(chemistry.glucose_mg_dl < 200.0);
+        assert!(chemistry.beta_hydroxybutyrate_mmol_l < 0.5);
+        assert!(chemistry.bicarbonate_meq_l > 22.0);
+        assert!(gases.ph > 7.35);
+        assert!(gases.calculate_anion_gap(chemistry.sodium_meq_l, chemistry.chloride_meq_l) < 16.0);
+    }
+
+    #[test]
+    fn test_pancreatitis_leaks_enzymes_and_inflames_without_infection() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 600.0);
+        patient.get_organ_mut::<Gallbladder>().unwrap().lodge_common_duct_stone();
+        let mut severe = patient.fork();
+        severe.get_organ_mut::<Pancreas>().unwrap().induce_pancreatitis(PancreatitisCause::Alcohol, 1.0);
+        severe.hemorrhage(0.3 * severe.fluids.reference_blood_volume_ml);
+        for _ in 0..720 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut severe, 60.0);
+        }
+
+        // A stone at the ampulla inflames the gland, which leaks lipase and sequesters plasma
+        let pancreas = patient.get_organ::<Pancreas>().unwrap();
+        assert_eq!(pancreas.pancreatitis, Some(PancreatitisCause::Gallstone));
+        assert!(patient.blood.chemistry.lipase_u_l > 180.0, "{}", patient.blood.chemistry.lipase_u_l);
+        assert!(pancreas.sequestered_fluid_ml > 300.0);
+        assert!(pancreas.necrosis() < 0.01);
+        assert!(patient.signals.level(Signal::Cytokines) > 0.1 && patient.sepsis.is_none());
+
+        // In shock the inflamed gland necroses and the systemic response reaches the lungs
+        let pancreas = severe.get_organ::<Pancreas>().unwrap();
+        assert!(pancreas.necrosis() > 0.1, "{}", pancreas.necrosis());
+        assert!(severe.signals.level(Signal::Cytokines) > 0.5);
+        assert!(severe.get_organ::<Lungs>().unwrap().alveolar_inflammation > 0.2);
+    }
 }
@@ -0,0 +1,557 @@
+//! Skin organ simulation
+//!
+//! The body's barrier, including:
+//! - Burns by depth and percentage of total body surface area (TBSA), losing
+//!   water and heat by evaporation through the open surface, and plasma into
+//!   the burned tissue and, in large burns, the whole interstitium while the
+//!   capillaries leak over the first day
+//! - The Parkland formula for the crystalloid that replaces those losses
+//! - Lacerations that bleed until a clot seals them or they are sutured
+//! - Pressure injuries over the sacrum of an immobile patient, deepening by
+//!   stage while the pressure goes unrelieved
+//! - Healing over days to weeks, slowed by poor perfusion and a low albumin,
+//!   and the chance of infection while a wound stays open
+
+use crate::fluids::{OutputRoute, SECONDS_PER_DAY};
+use crate::organ::{Organ, OrganId};
+use crate::patient::Patient;
+use crate::sepsis::BundleGuideline;
+use crate::wounds::Wound;
+
+/// Parkland formula crystalloid for the first 24 h, per kg per %TBSA of partial and full thickness burn (mL)
+pub const PARKLAND_ML_PER_KG_TBSA: f64 = 4.0;
+
+/// Hours from the burn over which the first half of the Parkland volume is given
+const PARKLAND_FIRST_HALF_H: f64 = 8.0;
+
+/// Hours from the burn over which the whole Parkland volume is given
+const PARKLAND_DURATION_H: f64 = 24.0;
+
+/// Evaporative water loss through open burns, per %TBSA per m² of body surface area (mL/h)
+const BURN_EVAPORATION_ML_H_PER_TBSA_M2: f64 = 1.0;
+
+/// Heat carried away by each mL of water evaporated (kcal)
+const LATENT_HEAT_KCAL_PER_ML: f64 = 0.58;
+
+/// Plasma sequestered into burned tissue while its capillaries leak, per kg per %TBSA (mL)
+const BURN_EDEMA_ML_PER_KG_TBSA: f64 = 2.0;
+
+/// Time constant of plasma leaking into burned tissue (s)
+const BURN_EDEMA_S: f64 = 28_800.0;
+
+/// Time from the burn until the capillary leak closes (s)
+const BURN_LEAK_S: f64 = 86_400.0;
+
+/// Time constant of burn edema being resorbed once the leak has closed (s)
+const BURN_EDEMA_RESORPTION_S: f64 = 259_200.0;
+
+/// Rise in systemic capillary permeability per %TBSA of burn still leaking
+const BURN_CAPILLARY_LEAK_PER_TBSA: f64 = 0.01;
+
+/// Cytokine activity per %TBSA of open partial or full thickness burn
+const BURN_CYTOKINES_PER_TBSA: f64 = 0.01;
+
+/// Cytokine activity of the largest survivable burns
+const MAX_BURN_CYTOKINES: f64 = 0.6;
+
+/// Time from the burn until bacteria colonize the eschar (s)
+const COLONIZATION_S: f64 = 172_800.0;
+
+/// Chance per day of invasive burn wound infection, per %TBSA of colonized open burn
+const BURN_INFECTION_RISK_PER_TBSA_DAY: f64 = 0.005;
+
+/// Chance per day of infection in a clean open laceration
+const LACERATION_INFECTION_RISK_PER_DAY: f64 = 0.01;
+
+/// Increase in the infection risk of a contaminated laceration
+const CONTAMINATION_INFECTION_FACTOR: f64 = 5.0;
+
+/// Chance per day of infection in a pressure injury, per stage beyond the intact skin of stage 2
+const PRESSURE_INJURY_INFECTION_RISK_PER_DAY: f64 = 0.01;
+
+/// Growth rate of wound pathogens (relative to a typical pathogen)
+const WOUND_INFECTION_VIRULENCE: f64 = 1.5;
+
+/// Unrelieved pressure over the sacrum before the tissue under it is injured (s)
+const PRESSURE_TOLERANCE_S: f64 = 7_200.0;
+
+/// Time constant of tissue recovering once pressure is relieved (s)
+const PRESSURE_RECOVERY_S: f64 = 3_600.0;
+
+/// Further unrelieved pressure deepening a pressure injury by one stage (s)
+const PRESSURE_INJURY_STAGE_S: f64 = 86_400.0;
+
+/// Time to heal one stage of a pressure injury with normal perfusion and albumin (s)
+const PRESSURE_INJURY_HEALING_S: f64 = 604_800.0;
+
+/// Time to heal a sutured laceration with normal perfusion and albumin (s)
+const SUTURED_HEALING_S: f64 = 864_000.0;
+
+/// Time to heal an open laceration by secondary intention with normal perfusion and albumin (s)
+const OPEN_HEALING_S: f64 = 1_814_400.0;
+
+/// Mean arterial pressure below which wounds are underperfused (mmHg)
+const HEALING_MAP_MMHG: f64 = 65.0;
+
+/// Mean arterial pressure at which wounds stop healing (mmHg)
+const NO_HEALING_MAP_MMHG: f64 = 40.0;
+
+/// Albumin below which malnutrition slows healing (g/dL)
+const HEALING_ALBUMIN_G_DL: f64 = 3.5;
+
+/// Albumin at which healing is slowest (g/dL)
+const MIN_HEALING_ALBUMIN_G_DL: f64 = 1.5;
+
+/// Depth of a burn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BurnDepth {
+    /// Epidermis only (first degree); left out of the Parkland TBSA
+    Superficial,
+    /// Into the dermis (second degree), healing from surviving skin appendages
+    PartialThickness,
+    /// Through the dermis (third degree), healing only once excised and grafted
+    FullThickness,
+}
+
+impl BurnDepth {
+    /// Time to heal with normal perfusion and albumin (s), or None without a graft
+    fn healing_s(&self, grafted: bool) -> Option<f64> {
+        match self {
+            BurnDepth::Superficial => Some(604_800.0),
+            BurnDepth::PartialThickness => Some(1_814_400.0),
+            BurnDepth::FullThickness if grafted => Some(1_209_600.0),
+            BurnDepth::FullThickness => None,
+        }
+    }
+}
+
+/// A burn
+#[derive(Debug, Clone)]
+pub struct Burn {
+    /// Anatomical site
+    pub site: String,
+    /// Depth
+    pub depth: BurnDepth,
+    /// Body surface area burned (%)
+    pub tbsa_percent: f64,
+    /// Time since the burn (s)
+    pub age_s: f64,
+    /// Fraction re-epithelialized or taken by the graft (0.0-1.0)
+    pub healed: f64,
+    /// Whether the burn has been excised and grafted
+    pub grafted: bool,
+}
+
+impl Burn {
+    /// Check whether the burn goes through the epidermis, counting towards the Parkland TBSA
+    pub fn is_deep(&self) -> bool {
+        self.depth != BurnDepth::Superficial
+    }
+
+    /// Body surface area still open (%)
+    pub fn open_tbsa_percent(&self) -> f64 {
+        self.tbsa_percent * (1.0 - self.healed)
+    }
+}
+
+/// A laceration through the skin
+#[derive(Debug, Clone)]
+pub struct Laceration {
+    /// Bleeding from the laceration
+    pub wound: Wound,
+    /// Whether dirt or foreign material was driven into the wound
+    pub contaminated: bool,
+    /// Fraction healed (0.0-1.0)
+    pub healed: f64,
+}
+
+impl Laceration {
+    /// Check whether the laceration has been sutured closed
+    pub fn is_sutured(&self) -> bool {
+        self.wound.closed
+    }
+}
+
+/// Skin organ
+#[derive(Debug, Clone)]
+pub struct Skin {
+    id: OrganId,
+    /// Burns
+    pub burns: Vec<Burn>,
+    /// Lacerations
+    pub lacerations: Vec<Laceration>,
+    /// Plasma sequestered in burned tissue (mL)
+    pub burn_edema_ml: f64,
+    /// Water evaporating through open burns (mL/min)
+    pub evaporative_loss_ml_min: f64,
+    /// Immobility of the patient (0.0 = moving freely, 1.0 = unable to shift weight), set from consciousness and paralysis
+    pub immobility: f64,
+    /// Ischemia of the tissue over the sacrum (0.0 = none, 1.0 = injured)
+    pub pressure_ischemia: f64,
+    /// Depth of a sacral pressure injury (0.0 = none, 4.0 = stage 4, through to bone)
+    pub pressure_injury: f64,
+    /// Healing rate relative to a well-perfused, well-nourished patient (0.0-1.0)
+    pub healing_rate: f64,
+}
+
+impl Skin {
+    /// Create new skin
+    pub fn new(id: OrganId) -> Self {
+        Self {
+            id,
+            burns: Vec::new(),
+            lacerations: Vec::new(),
+            burn_edema_ml: 0.0,
+            evaporative_loss_ml_min: 0.0,
+            immobility: 0.0,
+            pressure_ischemia: 0.0,
+            pressure_injury: 0.0,
+            healing_rate: 1.0,
+        }
+    }
+
+    /// Burn the skin
+    ///
+    /// # Arguments
+    /// * `site` - Anatomical site
+    /// * `depth` - Depth of the burn
+    /// * `tbsa_percent` - Body surface area burned (%)
+    pub fn burn(&mut self, site: &str, depth: BurnDepth, tbsa_percent: f64) {
+        self.burns.push(Burn {
+            site: site.to_string(),
+            depth,
+            tbsa_percent: tbsa_percent.clamp(0.0, 100.0),
+            age_s: 0.0,
+            healed: 0.0,
+            grafted: false,
+        });
+    }
+
+    /// Excise full thickness burns and cover them with skin grafts, so they can heal
+    pub fn excise_and_graft(&mut self) {
+        for burn in self.burns.iter_mut().filter(|b| b.depth == BurnDepth::FullThickness) {
+            burn.grafted = true;
+        }
+    }
+
+    /// Lacerate the skin
+    ///
+    /// # Arguments
+    /// * `site` - Anatomical site
+    /// * `bleed_rate_ml_min` - Initial bleeding rate at normal arterial pressure (mL/min)
+    /// * `contaminated` - Whether dirt or foreign material was driven into the wound
+    pub fn lacerate(&mut self, site: &str, bleed_rate_ml_min: f64, contaminated: bool) {
+        self.lacerations.push(Laceration {
+            wound: Wound::laceration(site, bleed_rate_ml_min),
+            contaminated,
+            healed: 0.0,
+        });
+    }
+
+    /// Irrigate and suture a laceration, stopping its bleeding and healing it by primary intention
+    ///
+    /// # Arguments
+    /// * `index` - Index of the laceration
+    ///
+    /// # Returns
+    /// True if there was a laceration to suture
+    pub fn suture_laceration(&mut self, index: usize) -> bool {
+        let Some(laceration) = self.lacerations.get_mut(index) else {
+            return false;
+        };
+        laceration.wound.close();
+        laceration.contaminated = false;
+        true
+    }
+
+    /// Turn the patient, relieving the pressure over the sacrum
+    pub fn reposition(&mut self) {
+        self.pressure_ischemia = 0.0;
+    }
+
+    /// Body surface area of partial and full thickness burn (%), the Parkland TBSA
+    pub fn burn_tbsa_percent(&self) -> f64 {
+        self.burns.iter().filter(|b| b.is_deep()).map(|b| b.tbsa_percent).sum::<f64>().min(100.0)
+    }
+
+    /// Body surface area of partial and full thickness burn still open (%)
+    pub fn open_burn_tbsa_percent(&self) -> f64 {
+        self.burns.iter().filter(|b| b.is_deep()).map(|b| b.open_tbsa_percent()).sum::<f64>().min(100.0)
+    }
+
+    /// Body surface area of partial and full thickness burn whose capillaries still leak (%)
+    fn leaking_tbsa_percent(&self) -> f64 {
+        self.burns
+            .iter()
+            .filter(|b| b.is_deep() && b.age_s < BURN_LEAK_S)
+            .map(|b| b.tbsa_percent)
+            .sum::<f64>()
+            .min(100.0)
+    }
+
+    /// Parkland formula crystalloid for the first 24 h after the burn (mL)
+    ///
+    /// # Arguments
+    /// * `weight_kg` - Patient weight (kg)
+    pub fn parkland_volume_ml(&self, weight_kg: f64) -> f64 {
+        PARKLAND_ML_PER_KG_TBSA * weight_kg * self.burn_tbsa_percent()
+    }
+
+    /// Parkland crystalloid rate due now: half the volume over the first 8 h
+    /// from the burn, the rest over the next 16 h
+    ///
+    /// # Arguments
+    /// * `weight_kg` - Patient weight (kg)
+    ///
+    /// # Returns
+    /// Rate (mL/h), 0.0 before a deep burn or after the first day
+    pub fn parkland_rate_ml_h(&self, weight_kg: f64) -> f64 {
+        let Some(age_s) = self.burns.iter().filter(|b| b.is_deep()).map(|b| b.age_s).reduce(f64::max) else {
+            return 0.0;
+        };
+        let half = self.parkland_volume_ml(weight_kg) / 2.0;
+        let hours = age_s / 3600.0;
+        if hours < PARKLAND_FIRST_HALF_H {
+            half / PARKLAND_FIRST_HALF_H
+        } else if hours < PARKLAND_DURATION_H {
+            half / (PARKLAND_DURATION_H - PARKLAND_FIRST_HALF_H)
+        } else {
+            0.0
+        }
+    }
+
+    /// Heat carried away by water evaporating through open burns (kcal/min)
+    pub fn evaporative_heat_loss_kcal_min(&self) -> f64 {
+        self.evaporative_loss_ml_min * LATENT_HEAT_KCAL_PER_ML
+    }
+
+    /// Rise in systemic capillary permeability while burned capillaries leak
+    pub fn capillary_leak(&self) -> f64 {
+        BURN_CAPILLARY_LEAK_PER_TBSA * self.leaking_tbsa_percent()
+    }
+
+    /// Cytokine activity provoked by open burns (0.0-1.0)
+    pub fn systemic_inflammation(&self) -> f64 {
+        (BURN_CYTOKINES_PER_TBSA * self.open_burn_tbsa_percent()).min(MAX_BURN_CYTOKINES)
+    }
+
+    /// Stage of the sacral pressure injury (0 = none, 1-4)
+    pub fn pressure_injury_stage(&self) -> u32 {
+        self.pressure_injury.ceil().min(4.0) as u32
+    }
+
+    /// Check whether any laceration is still bleeding
+    pub fn is_bleeding(&self) -> bool {
+        self.lacerations.iter().any(|l| l.wound.is_bleeding())
+    }
+
+    /// Healing rate allowed by perfusion and albumin (0.0-1.0)
+    fn healing_capacity(map_mmhg: f64, albumin_g_dl: f64) -> f64 {
+        let perfusion = ((map_mmhg - NO_HEALING_MAP_MMHG) / (HEALING_MAP_MMHG - NO_HEALING_MAP_MMHG)).clamp(0.0, 1.0);
+        let nutrition = ((albumin_g_dl - MIN_HEALING_ALBUMIN_G_DL) / (HEALING_ALBUMIN_G_DL - MIN_HEALING_ALBUMIN_G_DL))
+            .clamp(0.1, 1.0);
+        perfusion * nutrition
+    }
+
+    /// Start a wound infection by chance, if the patient has none
+    fn infect(patient: &mut Patient, source: &str, risk_per_day: f64, delta_time_s: f64) {
+        if patient.sepsis.is_none() && patient.variability.occurs(risk_per_day * delta_time_s / SECONDS_PER_DAY) {
+            patient.start_infection(source, WOUND_INFECTION_VIRULENCE, BundleGuideline::HourOne);
+        }
+    }
+
+    /// Lose water and plasma through burns, and heal them
+    fn update_burns(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        for burn in &mut self.burns {
+            burn.age_s += delta_time_s;
+            if let Some(healing_s) = burn.depth.healing_s(burn.grafted) {
+                burn.healed = (burn.healed + self.healing_rate * delta_time_s / healing_s).min(1.0);
+            }
+        }
+
+        // Water evaporates through the open surface, charted as insensible loss
+        let open_tbsa = self.open_burn_tbsa_percent();
+        self.evaporative_loss_ml_min =
+            BURN_EVAPORATION_ML_H_PER_TBSA_M2 * open_tbsa * patient.get_body_surface_area_m2() / 60.0;
+        patient.lose_fluid(OutputRoute::Insensible, self.evaporative_loss_ml_min * delta_time_s / 60.0);
+
+        // Burned capillaries leak plasma into the tissue over the first day; it is
+        // resorbed over the days after
+        let edema_target = BURN_EDEMA_ML_PER_KG_TBSA * patient.get_weight_kg() * self.leaking_tbsa_percent();
+        let time_constant = if edema_target > self.burn_edema_ml { BURN_EDEMA_S } else { BURN_EDEMA_RESORPTION_S };
+        let shift = (edema_target - self.burn_edema_ml) * (1.0 - (-delta_time_s / time_constant).exp());
+        if shift > 0.0 {
+            self.burn_edema_ml += patient.fluids.remove_plasma(shift);
+        } else {
+            self.burn_edema_ml += shift;
+            patient.fluids.add_plasma(-shift);
+        }
+
+        // Bacteria colonizing the eschar invade the open burn
+        let colonized_tbsa: f64 = self
+            .burns
+            .iter()
+            .filter(|b| b.is_deep() && b.age_s > COLONIZATION_S)
+            .map(|b| b.open_tbsa_percent())
+            .sum();
+        let risk = BURN_INFECTION_RISK_PER_TBSA_DAY * colonized_tbsa * (2.0 - self.healing_rate);
+        Self::infect(patient, "Burn wound infection", risk, delta_time_s);
+    }
+
+    /// Bleed from lacerations, and heal them
+    fn update_lacerations(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let mut risk = 0.0;
+        for laceration in &mut self.lacerations {
+            let lost = laceration.wound.update(&patient.blood, delta_time_s);
+            if lost > 0.0 {
+                patient.hemorrhage(lost);
+            }
+            if laceration.healed >= 1.0 {
+                continue;
+            }
+            let healing_s = if laceration.is_sutured() { SUTURED_HEALING_S } else { OPEN_HEALING_S };
+            laceration.healed = (laceration.healed + self.healing_rate * delta_time_s / healing_s).min(1.0);
+            let contamination = if laceration.contaminated { CONTAMINATION_INFECTION_FACTOR } else { 1.0 };
+            risk += LACERATION_INFECTION_RISK_PER_DAY * contamination * (2.0 - self.healing_rate);
+        }
+        Self::infect(patient, "Wound infection", risk, delta_time_s);
+    }
+
+    /// Injure the tissue over the sacrum while the patient cannot shift their weight, and heal it
+    fn update_pressure(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // Poorly perfused tissue tolerates pressure for less time
+        let tolerance_s = PRESSURE_TOLERANCE_S * (0.5 + 0.5 * self.healing_rate);
+        self.pressure_ischemia += self.immobility * delta_time_s / tolerance_s
+            - (1.0 - self.immobility) * self.pressure_ischemia * (1.0 - (-delta_time_s / PRESSURE_RECOVERY_S).exp());
+        if self.pressure_ischemia >= 1.0 {
+            self.pressure_ischemia = 1.0;
+            self.pressure_injury = (self.pressure_injury + self.immobility * delta_time_s / PRESSURE_INJURY_STAGE_S).min(4.0);
+        } else {
+            self.pressure_injury =
+                (self.pressure_injury - self.healing_rate * delta_time_s / PRESSURE_INJURY_HEALING_S).max(0.0);
+        }
+
+        // Past stage 2 the skin is broken and open to infection
+        let risk = PRESSURE_INJURY_INFECTION_RISK_PER_DAY * (self.pressure_injury - 2.0).max(0.0);
+        Self::infect(patient, "Infected pressure injury", risk, delta_time_s);
+    }
+}
+
+impl Organ for Skin {
+    fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        self.healing_rate = Self::healing_capacity(
+            patient.blood.get_mean_arterial_pressure(),
+            patient.blood.chemistry.albumin_g_dl,
+        );
+        self.update_burns(patient, delta_time_s);
+        self.update_lacerations(patient, delta_time_s);
+        self.update_pressure(patient, delta_time_s);
+    }
+
+    fn get_summary(&self) -> String {
+        let burns = if self.burns.is_empty() {
+            String::new()
+        } else {
+            format!(
+                ", Burns={:.0}% TBSA ({:.0}% open), Edema={:.0} mL",
+                self.burn_tbsa_percent(),
+                self.open_burn_tbsa_percent(),
+                self.burn_edema_ml
+            )
+        };
+        let open_lacerations = self.lacerations.iter().filter(|l| l.healed < 1.0).count();
+        format!(
+            "Skin: Healing={:.0}%{}{}{}{}",
+            self.healing_rate * 100.0,
+            burns,
+            if open_lacerations > 0 { format!(", Lacerations={}", open_lacerations) } else { String::new() },
+            if self.is_bleeding() { " (BLEEDING)" } else { "" },
+            match self.pressure_injury_stage() {
+                0 => String::new(),
+                stage => format!(", PRESSURE INJURY stage {}", stage),
+            }
+        )
+    }
+
+    fn get_id(&self) -> OrganId {
+        self.id
+    }
+
+    fn get_type(&self) -> &'static str {
+        "Skin"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluids::IntakeRoute;
+    use crate::organs::brain::Brain;
+    use crate::organs::heart::Heart;
+    use crate::patient::{initialize_patient, update_patient};
+    use crate::signals::Signal;
+
+    #[test]
+    fn test_burns_lose_plasma_and_heat_that_parkland_fluids_replace() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 600.0);
+        let skin = patient.get_organ_mut::<Skin>().unwrap();
+        skin.burn("Trunk", BurnDepth::PartialThickness, 25.0);
+        skin.burn("Legs", BurnDepth::FullThickness, 15.0);
+        skin.burn("Face", BurnDepth::Superficial, 5.0);
+        skin.lacerate("Scalp", 20.0, true);
+        let weight_kg = patient.get_weight_kg();
+        let skin = patient.get_organ::<Skin>().unwrap();
+        assert!((skin.parkland_volume_ml(weight_kg) - 4.0 * weight_kg * 40.0).abs() < 1e-6);
+        assert!((skin.parkland_rate_ml_h(weight_kg) - skin.parkland_volume_ml(weight_kg) / 16.0).abs() < 1e-6);
+
+        let mut resuscitated = patient.fork();
+        for _ in 0..480 {
+            update_patient(&mut patient, 60.0);
+            let rate = resuscitated.get_organ::<Skin>().unwrap().parkland_rate_ml_h(weight_kg);
+            resuscitated.give_fluid(IntakeRoute::Intravenous, rate / 60.0);
+            update_patient(&mut resuscitated, 60.0);
+        }
+
+        // Plasma leaks into the burned and unburned tissue, and evaporates through the burn
+        let skin = patient.get_organ::<Skin>().unwrap();
+        assert!(skin.burn_edema_ml > 2000.0, "{}", skin.burn_edema_ml);
+        assert!(patient.fluid_ledger.current_day.output_for(OutputRoute::Insensible) > 500.0);
+        assert!(patient.fluids.plasma_ml < resuscitated.fluids.plasma_ml - 500.0);
+        let heart_rate = |p: &Patient| p.get_organ::<Heart>().unwrap().heart_rate_bpm;
+        assert!(heart_rate(&patient) > heart_rate(&resuscitated) + 10.0);
+        assert!(patient.oxygen_transport.body_temperature_c < 36.8);
+        assert!(patient.signals.level(Signal::Cytokines) > 0.3);
+
+        // The scalp laceration bled until it clotted
+        let laceration = &skin.lacerations[0];
+        assert!(!laceration.wound.is_bleeding() && laceration.wound.blood_lost_ml > 50.0);
+        assert!(patient.fluid_ledger.current_day.output_for(OutputRoute::Blood) >= laceration.wound.blood_lost_ml - 1e-6);
+
+        // Pressure over the sacrum of a sedated patient injures it unless they are turned
+        let mut sedated = initialize_patient(2, 3);
+        sedated.get_organ_mut::<Brain>().unwrap().set_sedation_level(0.8);
+        let mut turned = sedated.fork();
+        for minute in 0..360 {
+            if minute % 90 == 0 {
+                turned.get_organ_mut::<Skin>().unwrap().reposition();
+            }
+            update_patient(&mut sedated, 60.0);
+            update_patient(&mut turned, 60.0);
+        }
+        assert!(sedated.get_organ::<Skin>().unwrap().pressure_injury_stage() >= 1);
+        assert_eq!(turned.get_organ::<Skin>().unwrap().pressure_injury_stage(), 0);
+    }
+}
@@ -22,7 +22,7 @@ pub struct NeuralTract {
 }
 
 /// Spinal cord organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SpinalCord {
     id: OrganId,
     /// Descending motor tract
@@ -122,4 +122,8 @@ impl Organ for SpinalCord {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
 }
@@ -1,12 +1,78 @@
 //! Spleen organ simulation
+//!
+//! Blood filtration and immunity, including:
+//! - Red pulp culling of senescent and damaged red cells, which an enlarged
+//!   spleen does faster (hypersplenism)
+//! - A pool of platelets held in the red pulp (a third normally, most of them
+//!   in a spleen congested by portal hypertension)
+//! - White pulp lymphocytes proliferating during an infection and releasing
+//!   activated cells into the blood
+//! - Rupture bleeding into the peritoneum, splenectomy, and the lifelong risk
+//!   of overwhelming sepsis from encapsulated bacteria without a spleen
 
+use crate::coagulation::Coagulation;
+use crate::fluids::SECONDS_PER_DAY;
+use crate::iron::IRON_PER_RED_CELL_ML;
 use crate::organ::{Organ, OrganId};
 use crate::patient::Patient;
+use crate::sepsis::BundleGuideline;
+
+/// Normal adult spleen volume (mL)
+const NORMAL_VOLUME_ML: f64 = 150.0;
+
+/// Portal venous pressure gradient above which the spleen congests (mmHg)
+const CONGESTION_HVPG_MMHG: f64 = 5.0;
+
+/// Spleen enlargement per mmHg of portal hypertension (mL)
+const CONGESTION_ML_PER_MMHG: f64 = 40.0;
+
+/// Enlargement of a spleen whose white pulp is fully activated (mL)
+const REACTIVE_ENLARGEMENT_ML: f64 = 250.0;
+
+/// Time constant of the spleen growing or shrinking to its congested or reactive size (s)
+const REMODELING_S: f64 = 604_800.0;
+
+/// Spleen volume at which half the platelet mass is pooled in it (mL)
+const HALF_POOL_VOLUME_ML: f64 = 300.0;
+
+/// Share of senescent red cells the normal spleen culls (the rest by liver and marrow macrophages)
+const NORMAL_CULLING_SHARE: f64 = 0.5;
+
+/// Red cell lifespan without the spleen's culling (days)
+const RED_CELL_LIFESPAN_DAYS: f64 = 120.0;
+
+/// Time constant of the white pulp response to an infection (s)
+const WHITE_PULP_RESPONSE_S: f64 = 172_800.0;
+
+/// Time constant of the white pulp returning to rest after an infection (s)
+const WHITE_PULP_RESOLUTION_S: f64 = 1_209_600.0;
+
+/// Lymphocytes released into the blood per day by a fully activated white pulp (cells/µL)
+const LYMPHOCYTE_RELEASE_PER_DAY: f64 = 600.0;
+
+/// Bleeding from a shattered spleen at normal blood pressure (mL/min)
+const RUPTURE_BLEED_ML_MIN: f64 = 50.0;
+
+/// Time constant of clot forming over a minor capsular tear with normal hemostasis (s)
+const RUPTURE_CLOT_S: f64 = 600.0;
+
+/// Time constant of hemoperitoneum being resorbed (s)
+const HEMOPERITONEUM_RESORPTION_S: f64 = 1_209_600.0;
+
+/// Chance per day of overwhelming sepsis from encapsulated bacteria without a spleen
+const ENCAPSULATED_SEPSIS_RISK_PER_DAY: f64 = 1e-5;
+
+/// Protection against encapsulated bacteria from pneumococcal, meningococcal and Hib vaccines
+const VACCINE_PROTECTION: f64 = 0.7;
+
+/// Growth rate of encapsulated bacteria unopposed by splenic clearance (relative to a typical pathogen)
+const ENCAPSULATED_VIRULENCE: f64 = 3.0;
 
 /// Red pulp component (blood filtration)
 #[derive(Debug, Clone)]
 pub struct RedPulp {
-    pub rbc_breakdown_rate: f64,  // Red blood cells/min
+    pub rbc_breakdown_rate: f64,  // Red cells culled (mL/day)
+    pub platelet_pool_fraction: f64,  // Fraction of the platelet mass held in the spleen
 }
 
 /// White pulp component (immune function)
@@ -14,16 +80,33 @@ pub struct RedPulp {
 pub struct WhitePulp {
     pub lymphocyte_count: f64,  // Cells/μL
     pub macrophage_count: f64,  // Cells/μL
+    pub activation: f64,  // 0.0 = resting, 1.0 = maximal germinal centre response
 }
 
 /// Spleen organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Spleen {
     id: OrganId,
     /// Red pulp (blood filtration)
     pub red_pulp: RedPulp,
     /// White pulp (immunity)
     pub white_pulp: WhitePulp,
+    /// Spleen volume (mL)
+    pub volume_ml: f64,
+    /// Portal venous pressure gradient the splenic vein drains against (mmHg), set from the liver
+    pub portal_pressure_gradient_mmhg: f64,
+    /// Severity of a splenic rupture (0.0 = intact, 1.0 = shattered)
+    pub rupture_severity: f64,
+    /// Current bleeding rate from a ruptured spleen (mL/min)
+    pub bleed_rate_ml_min: f64,
+    /// Clot over the rupture (0.0 = none, 1.0 = hemostasis)
+    pub rupture_clot: f64,
+    /// Blood shed into the peritoneal cavity (mL)
+    pub hemoperitoneum_ml: f64,
+    /// Whether the spleen has been removed
+    pub removed: bool,
+    /// Whether the patient has been vaccinated against encapsulated bacteria
+    pub vaccinated: bool,
 }
 
 impl Spleen {
@@ -32,27 +115,203 @@ impl Spleen {
         Self {
             id,
             red_pulp: RedPulp {
-                rbc_breakdown_rate: 5000.0,
+                rbc_breakdown_rate: 0.0,
+                platelet_pool_fraction: Self::platelet_pool_fraction(NORMAL_VOLUME_ML),
             },
             white_pulp: WhitePulp {
                 lymphocyte_count: 1500.0,
                 macrophage_count: 500.0,
+                activation: 0.0,
             },
+            volume_ml: NORMAL_VOLUME_ML,
+            portal_pressure_gradient_mmhg: 4.0,
+            rupture_severity: 0.0,
+            bleed_rate_ml_min: 0.0,
+            rupture_clot: 0.0,
+            hemoperitoneum_ml: 0.0,
+            removed: false,
+            vaccinated: false,
+        }
+    }
+
+    /// Fraction of the platelet mass pooled in a spleen of a given size
+    fn platelet_pool_fraction(volume_ml: f64) -> f64 {
+        volume_ml / (volume_ml + HALF_POOL_VOLUME_ML)
+    }
+
+    /// Set the size of a long-standing splenomegaly, whose pooling the
+    /// platelet count already reflects
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Spleen volume (mL)
+    pub fn set_volume(&mut self, volume_ml: f64) {
+        self.volume_ml = volume_ml.max(0.0);
+        self.red_pulp.platelet_pool_fraction = Self::platelet_pool_fraction(self.volume_ml);
+    }
+
+    /// Tear the spleen (blunt abdominal trauma), starting bleeding into the peritoneum
+    ///
+    /// # Arguments
+    /// * `severity` - 0.0-1.0; a minor capsular tear clots, a shattered spleen keeps bleeding
+    pub fn rupture(&mut self, severity: f64) {
+        if self.removed {
+            return;
+        }
+        self.rupture_severity = self.rupture_severity.max(severity.clamp(0.0, 1.0));
+        self.rupture_clot = 0.0;
+    }
+
+    /// Remove the spleen, stopping any bleeding from it
+    pub fn splenectomy(&mut self) {
+        self.removed = true;
+        self.volume_ml = 0.0;
+        self.rupture_severity = 0.0;
+        self.bleed_rate_ml_min = 0.0;
+        self.red_pulp.rbc_breakdown_rate = 0.0;
+        self.white_pulp.lymphocyte_count = 0.0;
+        self.white_pulp.macrophage_count = 0.0;
+        self.white_pulp.activation = 0.0;
+    }
+
+    /// Vaccinate against pneumococcus, meningococcus and Haemophilus influenzae type b
+    pub fn vaccinate(&mut self) {
+        self.vaccinated = true;
+    }
+
+    /// Check for splenomegaly (spleen over ~2.5 times its normal volume)
+    pub fn is_splenomegaly(&self) -> bool {
+        self.volume_ml > 2.5 * NORMAL_VOLUME_ML
+    }
+
+    /// Check whether a ruptured spleen is still bleeding
+    pub fn is_bleeding(&self) -> bool {
+        self.bleed_rate_ml_min > 0.0
+    }
+
+    /// Check for asplenia; nuclear remnants the spleen would have pitted
+    /// (Howell-Jolly bodies) then appear on the blood film
+    pub fn is_asplenic(&self) -> bool {
+        self.removed
+    }
+
+    /// Chance per day of overwhelming sepsis from encapsulated bacteria
+    pub fn encapsulated_sepsis_risk_per_day(&self) -> f64 {
+        if !self.removed {
+            return 0.0;
+        }
+        let protection = if self.vaccinated { VACCINE_PROTECTION } else { 0.0 };
+        ENCAPSULATED_SEPSIS_RISK_PER_DAY * (1.0 - protection)
+    }
+
+    /// Summary of blood in the peritoneum, if any
+    fn hemoperitoneum_summary(&self) -> String {
+        if self.hemoperitoneum_ml > 50.0 {
+            format!(", Hemoperitoneum={:.0} mL", self.hemoperitoneum_ml)
+        } else {
+            String::new()
+        }
+    }
+
+    /// Grow or shrink towards the size set by portal congestion and the white pulp,
+    /// pooling platelets in proportion
+    fn update_size(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let congestion = (self.portal_pressure_gradient_mmhg - CONGESTION_HVPG_MMHG).max(0.0);
+        let target_ml = NORMAL_VOLUME_ML
+            + CONGESTION_ML_PER_MMHG * congestion
+            + REACTIVE_ENLARGEMENT_ML * self.white_pulp.activation;
+        self.volume_ml += (target_ml - self.volume_ml) * (1.0 - (-delta_time_s / REMODELING_S).exp());
+
+        // Platelets move between the circulation and the splenic pool as it grows
+        let pool = Self::platelet_pool_fraction(self.volume_ml);
+        patient.blood.cells.platelet_count_thousand_per_ul *= (1.0 - pool) / (1.0 - self.red_pulp.platelet_pool_fraction);
+        self.red_pulp.platelet_pool_fraction = pool;
+    }
+
+    /// Cull red cells, recycling their iron; an enlarged spleen culls more
+    fn update_culling(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let days = delta_time_s / SECONDS_PER_DAY;
+        let senescent_ml_day = patient.fluids.red_cell_ml / RED_CELL_LIFESPAN_DAYS;
+        self.red_pulp.rbc_breakdown_rate = senescent_ml_day * NORMAL_CULLING_SHARE * self.volume_ml / NORMAL_VOLUME_ML;
+
+        // The normal share is part of the marrow's red cell turnover; only the excess shortens survival
+        let excess_ml = (self.red_pulp.rbc_breakdown_rate - senescent_ml_day * NORMAL_CULLING_SHARE).max(0.0) * days;
+        let culled_ml = patient.fluids.destroy_red_cells(excess_ml);
+        patient.iron.recycle(culled_ml * IRON_PER_RED_CELL_ML);
+    }
+
+    /// Activate the white pulp with the bacterial load and release lymphocytes
+    fn update_white_pulp(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        let load = patient.sepsis.as_ref().map_or(0.0, |sepsis| sepsis.bacterial_load);
+        let activation = &mut self.white_pulp.activation;
+        let time_constant_s = if load > *activation { WHITE_PULP_RESPONSE_S } else { WHITE_PULP_RESOLUTION_S };
+        *activation += (load - *activation) * (1.0 - (-delta_time_s / time_constant_s).exp());
+        self.white_pulp.lymphocyte_count = 1500.0 * (1.0 + 2.0 * *activation);
+        self.white_pulp.macrophage_count = 500.0 * (1.0 + *activation);
+
+        let release = LYMPHOCYTE_RELEASE_PER_DAY * *activation * delta_time_s / SECONDS_PER_DAY;
+        patient.blood.cells.wbc_differential.lymphocytes += release;
+    }
+
+    /// Bleed from a ruptured spleen into the peritoneum until the tear clots
+    fn update_rupture(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        if self.rupture_severity <= 0.0 {
+            self.bleed_rate_ml_min = 0.0;
+            return;
+        }
+        // The deeper the laceration, the longer it takes to clot, if it does at all
+        let clot_time = RUPTURE_CLOT_S
+            * Coagulation::hemostatic_delay(&patient.blood)
+            * (1.0 + 9.0 * self.rupture_severity.powi(2));
+        self.rupture_clot += (1.0 - self.rupture_clot) * (1.0 - (-delta_time_s / clot_time).exp());
+
+        // Arterial bleeding slows as the blood pressure falls
+        let pressure_factor = (patient.blood.get_mean_arterial_pressure() / 90.0).clamp(0.0, 1.5);
+        self.bleed_rate_ml_min =
+            RUPTURE_BLEED_ML_MIN * self.rupture_severity * pressure_factor * (1.0 - self.rupture_clot);
+        self.hemoperitoneum_ml += patient.fluids.remove_blood(self.bleed_rate_ml_min * delta_time_s / 60.0);
+        if self.bleed_rate_ml_min < 0.5 {
+            self.rupture_severity = 0.0;
+            self.bleed_rate_ml_min = 0.0;
         }
     }
 }
 
 impl Organ for Spleen {
-    fn update(&mut self, _patient: &mut Patient, _delta_time_s: f64) {
-        // Spleen function is relatively constant
-        // Could be enhanced to respond to infections or blood disorders
+    fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        self.hemoperitoneum_ml *= (-delta_time_s / HEMOPERITONEUM_RESORPTION_S).exp();
+        if self.removed {
+            // Without the spleen's platelet pool the circulating count rises
+            let pool = self.red_pulp.platelet_pool_fraction;
+            patient.blood.cells.platelet_count_thousand_per_ul /= 1.0 - pool;
+            self.red_pulp.platelet_pool_fraction = 0.0;
+
+            // Unopsonised encapsulated bacteria are no longer filtered from the blood
+            let risk = self.encapsulated_sepsis_risk_per_day() * delta_time_s / SECONDS_PER_DAY;
+            if patient.sepsis.is_none() && patient.variability.occurs(risk) {
+                patient.start_infection("Post-splenectomy sepsis", ENCAPSULATED_VIRULENCE, BundleGuideline::HourOne);
+            }
+            return;
+        }
+
+        self.update_size(patient, delta_time_s);
+        self.update_culling(patient, delta_time_s);
+        self.update_white_pulp(patient, delta_time_s);
+        self.update_rupture(patient, delta_time_s);
     }
 
     fn get_summary(&self) -> String {
+        if self.removed {
+            return format!("Spleen: ASPLENIC (Howell-Jolly bodies){}", self.hemoperitoneum_summary());
+        }
         format!(
-            "Spleen: RBC breakdown={:.0}/min, Lymphocytes={:.0}/μL",
+            "Spleen: Volume={:.0} mL{}, Platelet pool={:.0}%, RBC culling={:.1} mL/day, Lymphocytes={:.0}/μL{}{}",
+            self.volume_ml,
+            if self.is_splenomegaly() { " (SPLENOMEGALY)" } else { "" },
+            self.red_pulp.platelet_pool_fraction * 100.0,
             self.red_pulp.rbc_breakdown_rate,
-            self.white_pulp.lymphocyte_count
+            self.white_pulp.lymphocyte_count,
+            if self.is_bleeding() { format!(", RUPTURED (bleeding {:.0} mL/min)", self.bleed_rate_ml_min) } else { String::new() },
+            self.hemoperitoneum_summary()
         )
     }
 
@@ -71,4 +330,55 @@ impl Organ for Spleen {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_ruptured_spleen_bleeds_until_removed() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 60.0);
+
+        // A congested spleen pools platelets out of the circulation
+        let platelets = patient.blood.cells.platelet_count_thousand_per_ul;
+        let mut congested = patient.fork();
+        congested.get_organ_mut::<Spleen>().unwrap().volume_ml = 700.0;
+        update_patient(&mut congested, 60.0);
+        assert!(congested.get_organ::<Spleen>().unwrap().is_splenomegaly());
+        assert!(congested.blood.cells.platelet_count_thousand_per_ul < 0.5 * platelets);
+
+        // A capsular tear clots; a shattered spleen keeps bleeding into the peritoneum
+        let mut shattered = patient.fork();
+        patient.get_organ_mut::<Spleen>().unwrap().rupture(0.2);
+        shattered.get_organ_mut::<Spleen>().unwrap().rupture(1.0);
+        for _ in 0..60 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut shattered, 60.0);
+        }
+        let spleen = patient.get_organ::<Spleen>().unwrap();
+        assert!(!spleen.is_bleeding() && spleen.hemoperitoneum_ml < 500.0, "{}", spleen.get_summary());
+        let spleen = shattered.get_organ::<Spleen>().unwrap();
+        assert!(spleen.is_bleeding() && spleen.hemoperitoneum_ml > 1500.0, "{}", spleen.get_summary());
+        assert!(shattered.blood.cells.hemoglobin_g_dl < patient.blood.cells.hemoglobin_g_dl - 1.0);
+
+        // Splenectomy stops the bleeding and releases the pooled platelets, at the
+        // price of a lifelong risk of sepsis from encapsulated bacteria
+        let platelets = shattered.blood.cells.platelet_count_thousand_per_ul;
+        assert!(shattered.splenectomy());
+        assert!(!shattered.splenectomy());
+        update_patient(&mut shattered, 60.0);
+        let spleen = shattered.get_organ_mut::<Spleen>().unwrap();
+        assert!(spleen.is_asplenic() && !spleen.is_bleeding());
+        let risk = spleen.encapsulated_sepsis_risk_per_day();
+        spleen.vaccinate();
+        assert!(risk > 0.0 && spleen.encapsulated_sepsis_risk_per_day() < 0.5 * risk);
+        assert!(shattered.blood.cells.platelet_count_thousand_per_ul > 1.3 * platelets);
+    }
 }
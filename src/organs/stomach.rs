@@ -1,7 +1,145 @@
 //! Stomach organ simulation
+//!
+//! Filling, acid digestion and emptying into the duodenum, vomiting, and peptic
+//! ulcer disease:
+//! - Meals of carbohydrate, protein, fat, water and sodium, emptied at the
+//!   few kcal per minute the duodenum allows, more slowly the fattier they are
+//! - Mucosal injury from circulating salicylate that heals over weeks once stopped
+//! - Ulcer bleeding into the lumen when the ulcer is deep enough, sooner and
+//!   longer when clotting is impaired (anticoagulants, thrombocytopenia)
+//! - Swallowed and shed blood altered to coffee grounds by acid, emptied on to
+//!   the bowel or vomited as hematemesis
 
+use crate::coagulation::Coagulation;
 use crate::organ::{Organ, OrganId};
 use crate::patient::Patient;
+use crate::toxicology::Drug;
+
+/// Rate of mucosal injury at full NSAID effect (ulcer severity per day)
+const ULCER_INJURY_PER_DAY: f64 = 0.1;
+
+/// Salicylate level giving half the maximal mucosal injury (mg/dL)
+const SALICYLATE_HALF_INJURY_MG_DL: f64 = 5.0;
+
+/// Time constant of ulcer healing once the injury stops (s)
+const ULCER_HEALING_S: f64 = 14.0 * 86_400.0;
+
+/// Ulcer severity at which the base erodes a vessel with normal clotting
+const ULCER_BLEED_SEVERITY: f64 = 0.5;
+
+/// Bleeding rate from the most severe ulcer at normal blood pressure (mL/min)
+const ULCER_BLEED_ML_MIN: f64 = 10.0;
+
+/// Time constant of clot formation over the ulcer base with normal coagulation (s)
+const ULCER_CLOT_S: f64 = 3.0 * 3600.0;
+
+/// How long a plugged ulcer base holds before it can bleed again (s)
+const ULCER_CLOT_HOLD_S: f64 = 3.0 * 86_400.0;
+
+/// Fresh blood in the stomach that provokes vomiting (mL)
+const HEMATEMESIS_BLOOD_ML: f64 = 250.0;
+
+/// Energy the duodenum lets through per minute from a meal without fat (kcal/min)
+const GASTRIC_EMPTYING_KCAL_MIN: f64 = 3.0;
+
+/// Slowing of gastric emptying by a meal all of whose energy is fat (fraction)
+const FAT_EMPTYING_DELAY: f64 = 0.5;
+
+/// Energy of carbohydrate and protein (kcal/g)
+const KCAL_PER_G_CARBOHYDRATE_OR_PROTEIN: f64 = 4.0;
+
+/// Energy of fat (kcal/g)
+const KCAL_PER_G_FAT: f64 = 9.0;
+
+/// Food and drink by macronutrient content
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Meal {
+    /// Carbohydrate (g)
+    pub carbs_g: f64,
+    /// Protein (g)
+    pub protein_g: f64,
+    /// Fat (g)
+    pub fat_g: f64,
+    /// Water, in drinks and in the food itself (mL)
+    pub water_ml: f64,
+    /// Sodium (mg)
+    pub sodium_mg: f64,
+}
+
+impl Meal {
+    /// Create a meal
+    ///
+    /// # Arguments
+    /// * `carbs_g` - Carbohydrate (g)
+    /// * `protein_g` - Protein (g)
+    /// * `fat_g` - Fat (g)
+    /// * `water_ml` - Water, in drinks and in the food itself (mL)
+    /// * `sodium_mg` - Sodium (mg)
+    pub fn new(carbs_g: f64, protein_g: f64, fat_g: f64, water_ml: f64, sodium_mg: f64) -> Self {
+        Self {
+            carbs_g: carbs_g.max(0.0),
+            protein_g: protein_g.max(0.0),
+            fat_g: fat_g.max(0.0),
+            water_ml: water_ml.max(0.0),
+            sodium_mg: sodium_mg.max(0.0),
+        }
+    }
+
+    /// A drink of plain water
+    pub fn water(volume_ml: f64) -> Self {
+        Self::new(0.0, 0.0, 0.0, volume_ml, 0.0)
+    }
+
+    /// Volume the meal occupies in the stomach (mL)
+    pub fn volume_ml(&self) -> f64 {
+        self.water_ml + 0.6 * self.carbs_g + 0.75 * self.protein_g + 1.1 * self.fat_g
+    }
+
+    /// Energy content (kcal)
+    pub fn energy_kcal(&self) -> f64 {
+        KCAL_PER_G_CARBOHYDRATE_OR_PROTEIN * (self.carbs_g + self.protein_g) + KCAL_PER_G_FAT * self.fat_g
+    }
+
+    /// Share of the energy that comes from fat (0.0-1.0)
+    pub fn fat_energy_fraction(&self) -> f64 {
+        let energy = self.energy_kcal();
+        if energy > 0.0 {
+            KCAL_PER_G_FAT * self.fat_g / energy
+        } else {
+            0.0
+        }
+    }
+
+    /// Add another meal's contents to this one
+    pub fn add(&mut self, other: &Meal) {
+        self.carbs_g += other.carbs_g;
+        self.protein_g += other.protein_g;
+        self.fat_g += other.fat_g;
+        self.water_ml += other.water_ml;
+        self.sodium_mg += other.sodium_mg;
+    }
+
+    /// Remove a fraction of every component
+    ///
+    /// # Returns
+    /// The removed portion
+    pub fn take_fraction(&mut self, fraction: f64) -> Meal {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let taken = Meal {
+            carbs_g: self.carbs_g * fraction,
+            protein_g: self.protein_g * fraction,
+            fat_g: self.fat_g * fraction,
+            water_ml: self.water_ml * fraction,
+            sodium_mg: self.sodium_mg * fraction,
+        };
+        self.carbs_g -= taken.carbs_g;
+        self.protein_g -= taken.protein_g;
+        self.fat_g -= taken.fat_g;
+        self.water_ml -= taken.water_ml;
+        self.sodium_mg -= taken.sodium_mg;
+        taken
+    }
+}
 
 /// Stomach state
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -12,15 +150,112 @@ pub enum StomachState {
     Emptying,
 }
 
+/// Character of gastric contents, as seen in vomitus or NG aspirate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GastricContent {
+    /// Clear or food-stained gastric contents
+    NonBilious,
+    /// Green, bile-stained contents (obstruction distal to the ampulla)
+    Bilious,
+    /// Acid-altered blood (slow or recent upper GI bleeding)
+    CoffeeGround,
+    /// Bright red blood (brisk upper GI bleeding)
+    Bloody,
+    /// Brown, foul-smelling stagnant bowel contents (distal obstruction)
+    Feculent,
+}
+
+/// Gastric contents expelled by emesis or drained by aspirate
+#[derive(Debug, Clone)]
+pub struct GastricOutput {
+    /// Time since the stomach was created (s)
+    pub time_s: f64,
+    /// Total volume (mL)
+    pub volume_ml: f64,
+    /// Blood contained in the output (mL)
+    pub blood_ml: f64,
+    /// Food and drink contained in the output, never absorbed (mL)
+    pub ingested_ml: f64,
+    /// Hydrochloric acid lost with the output (mEq)
+    pub acid_meq: f64,
+    /// Character of the output
+    pub character: GastricContent,
+}
+
 /// Chyme (partially digested food)
 #[derive(Debug, Clone)]
 pub struct Chyme {
     pub volume_ml: f64,
     pub ph: f64,
+    /// Bile refluxed from the duodenum (mL)
+    pub bile_ml: f64,
+    /// Fresh blood (mL)
+    pub fresh_blood_ml: f64,
+    /// Blood altered by gastric acid (mL)
+    pub digested_blood_ml: f64,
+    /// Stagnant bowel contents refluxed from an obstructed small bowel (mL)
+    pub feculent_ml: f64,
+    /// Food and drink not yet emptied
+    pub nutrients: Meal,
+}
+
+impl Chyme {
+    /// Remove a volume of chyme, taking each component proportionally
+    ///
+    /// # Returns
+    /// The removed portion
+    fn remove(&mut self, volume_ml: f64) -> Chyme {
+        let fraction = if self.volume_ml > 0.0 {
+            (volume_ml / self.volume_ml).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let removed = Chyme {
+            volume_ml: self.volume_ml * fraction,
+            ph: self.ph,
+            bile_ml: self.bile_ml * fraction,
+            fresh_blood_ml: self.fresh_blood_ml * fraction,
+            digested_blood_ml: self.digested_blood_ml * fraction,
+            feculent_ml: self.feculent_ml * fraction,
+            nutrients: self.nutrients.take_fraction(fraction),
+        };
+        self.volume_ml -= removed.volume_ml;
+        self.bile_ml -= removed.bile_ml;
+        self.fresh_blood_ml -= removed.fresh_blood_ml;
+        self.digested_blood_ml -= removed.digested_blood_ml;
+        self.feculent_ml -= removed.feculent_ml;
+        removed
+    }
+
+    /// Total blood content (mL)
+    fn blood_ml(&self) -> f64 {
+        self.fresh_blood_ml + self.digested_blood_ml
+    }
+
+    /// Free hydrogen ion carried by the contents at their pH (mEq)
+    fn acid_meq(&self) -> f64 {
+        self.volume_ml * 10f64.powf(-self.ph)
+    }
+
+    /// Classify the contents by appearance
+    fn character(&self) -> GastricContent {
+        let volume = self.volume_ml.max(1.0);
+        if self.fresh_blood_ml / volume > 0.1 {
+            GastricContent::Bloody
+        } else if self.feculent_ml / volume > 0.3 {
+            GastricContent::Feculent
+        } else if self.digested_blood_ml >= 5.0 {
+            GastricContent::CoffeeGround
+        } else if self.bile_ml / volume > 0.05 {
+            GastricContent::Bilious
+        } else {
+            GastricContent::NonBilious
+        }
+    }
 }
 
 /// Stomach organ
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Stomach {
     id: OrganId,
     /// Current state
@@ -31,6 +266,34 @@ pub struct Stomach {
     pub capacity_ml: f64,
     /// Digestion rate (mL/min)
     pub digestion_rate: f64,
+    /// Chyme emptied into the duodenum since last collected (mL)
+    emptied_volume_ml: f64,
+    /// Blood in the chyme emptied since last collected (mL)
+    emptied_blood_ml: f64,
+    /// Food and drink in the chyme emptied since last collected
+    emptied_nutrients: Meal,
+    /// Volume above which overdistension triggers vomiting (mL)
+    pub emesis_threshold_ml: f64,
+    /// Episodes of emesis not yet collected
+    pub emesis_events: Vec<GastricOutput>,
+    /// Character of the most recent gastric output
+    pub last_output_character: Option<GastricContent>,
+    /// Vomit on the next update (nausea, emetogenic drugs)
+    emesis_pending: bool,
+    /// Depth of peptic ulceration (0.0 = intact mucosa, 1.0 = ulcer eroding an artery)
+    pub ulcer_severity: f64,
+    /// Whether the ulcer is actively bleeding
+    pub ulcer_bleeding: bool,
+    /// Current ulcer bleeding rate (mL/min)
+    pub ulcer_bleed_rate_ml_min: f64,
+    /// Blood shed by the ulcer since the patient was created (mL)
+    pub ulcer_blood_loss_ml: f64,
+    /// Clot over the ulcer base (0.0 = none, 1.0 = hemostasis)
+    ulcer_clot: f64,
+    /// Time the plugged ulcer base still holds (s)
+    clot_hold_remaining_s: f64,
+    /// Internal clock (s)
+    elapsed_time_s: f64,
 }
 
 impl Stomach {
@@ -42,18 +305,64 @@ impl Stomach {
             chyme: Chyme {
                 volume_ml: 0.0,
                 ph: 2.0,  // Acidic
+                bile_ml: 0.0,
+                fresh_blood_ml: 0.0,
+                digested_blood_ml: 0.0,
+                feculent_ml: 0.0,
+                nutrients: Meal::default(),
             },
             capacity_ml: 1500.0,
             digestion_rate: 50.0,
+            emptied_volume_ml: 0.0,
+            emptied_blood_ml: 0.0,
+            emptied_nutrients: Meal::default(),
+            emesis_threshold_ml: 1000.0,
+            emesis_events: Vec::new(),
+            last_output_character: None,
+            emesis_pending: false,
+            ulcer_severity: 0.0,
+            ulcer_bleeding: false,
+            ulcer_bleed_rate_ml_min: 0.0,
+            ulcer_blood_loss_ml: 0.0,
+            ulcer_clot: 0.0,
+            clot_hold_remaining_s: 0.0,
+            elapsed_time_s: 0.0,
         }
     }
 
-    /// Add substance to stomach
-    pub fn add_substance(&mut self, volume_ml: f64, ph: f64) {
+    /// Collect chyme emptied into the duodenum since the last call
+    ///
+    /// # Returns
+    /// Volume emptied (mL)
+    pub fn take_emptied_volume(&mut self) -> f64 {
+        std::mem::take(&mut self.emptied_volume_ml)
+    }
+
+    /// Collect the blood in the chyme emptied since the last call
+    ///
+    /// # Returns
+    /// Blood emptied (mL), part of the volume returned by
+    /// [`take_emptied_volume`](Self::take_emptied_volume)
+    pub fn take_emptied_blood(&mut self) -> f64 {
+        std::mem::take(&mut self.emptied_blood_ml)
+    }
+
+    /// Collect the food and drink in the chyme emptied since the last call
+    pub fn take_emptied_nutrients(&mut self) -> Meal {
+        std::mem::take(&mut self.emptied_nutrients)
+    }
+
+    /// Eat or drink
+    ///
+    /// # Arguments
+    /// * `meal` - Food and drink swallowed
+    pub fn ingest(&mut self, meal: Meal) {
+        let volume_ml = meal.volume_ml();
         self.chyme.volume_ml += volume_ml;
-        // Mix pH values (simplified)
-        if self.chyme.volume_ml > 0.0 {
-            self.chyme.ph = (self.chyme.ph + ph) / 2.0;
+        self.chyme.nutrients.add(&meal);
+        // Food and drink buffer the acid until secretion catches up
+        if volume_ml > 0.0 {
+            self.chyme.ph = (self.chyme.ph + 7.0) / 2.0;
         }
         self.state = if self.chyme.volume_ml > 0.0 {
             StomachState::Filling
@@ -61,10 +370,160 @@ impl Stomach {
             StomachState::Empty
         };
     }
+
+    /// Add blood to the stomach from an upper GI bleeding source
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Volume of blood entering the stomach
+    pub fn add_blood(&mut self, volume_ml: f64) {
+        let volume_ml = volume_ml.max(0.0);
+        self.chyme.volume_ml += volume_ml;
+        self.chyme.fresh_blood_ml += volume_ml;
+    }
+
+    /// Develop a peptic ulcer (stress ulceration, Helicobacter pylori)
+    ///
+    /// # Arguments
+    /// * `severity` - Depth of ulceration, 0.0 = none, 1.0 = eroding an artery;
+    ///   ulcers of 0.5 and above bleed with normal clotting
+    pub fn develop_ulcer(&mut self, severity: f64) {
+        self.ulcer_severity = self.ulcer_severity.max(severity.clamp(0.0, 1.0));
+    }
+
+    /// Check for an actively bleeding ulcer
+    pub fn is_ulcer_bleeding(&self) -> bool {
+        self.ulcer_bleeding
+    }
+
+    /// Perform endoscopic hemostasis of a bleeding ulcer (clips, thermal coagulation)
+    ///
+    /// Stops the bleeding and holds the base closed; the ulcer itself still has to heal.
+    pub fn endoscopic_hemostasis(&mut self) {
+        self.ulcer_bleeding = false;
+        self.ulcer_bleed_rate_ml_min = 0.0;
+        self.ulcer_clot = 0.0;
+        self.clot_hold_remaining_s = ULCER_CLOT_HOLD_S;
+    }
+
+    /// Update ulcer depth and bleeding
+    ///
+    /// # Arguments
+    /// * `patient` - The patient, whose blood is shed into the lumen
+    /// * `delta_time_s` - Time step in seconds
+    fn update_ulcer(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // Salicylate blocks the prostaglandins protecting the mucosa; the ulcer heals once it stops
+        let salicylate = patient.toxicology.level(Drug::Salicylate);
+        let nsaid_effect = salicylate / (salicylate + SALICYLATE_HALF_INJURY_MG_DL);
+        self.ulcer_severity += ULCER_INJURY_PER_DAY * nsaid_effect * delta_time_s / 86_400.0;
+        self.ulcer_severity = (self.ulcer_severity * (-delta_time_s / ULCER_HEALING_S).exp()).min(1.0);
+
+        // Impaired clotting lets a shallower ulcer bleed
+        let hemostatic_delay = Coagulation::hemostatic_delay(&patient.blood);
+        self.clot_hold_remaining_s = (self.clot_hold_remaining_s - delta_time_s).max(0.0);
+        if !self.ulcer_bleeding
+            && self.clot_hold_remaining_s <= 0.0
+            && self.ulcer_severity * hemostatic_delay.sqrt() >= ULCER_BLEED_SEVERITY
+        {
+            self.ulcer_bleeding = true;
+            self.ulcer_clot = 0.0;
+        }
+        if !self.ulcer_bleeding {
+            self.ulcer_bleed_rate_ml_min = 0.0;
+            return;
+        }
+
+        let clot_time = ULCER_CLOT_S * hemostatic_delay;
+        self.ulcer_clot += (1.0 - self.ulcer_clot) * (1.0 - (-delta_time_s / clot_time).exp());
+
+        // Arterial bleeding slows as the blood pressure falls
+        let pressure_factor = (patient.blood.get_mean_arterial_pressure() / 90.0).clamp(0.0, 1.5);
+        self.ulcer_bleed_rate_ml_min =
+            ULCER_BLEED_ML_MIN * self.ulcer_severity * pressure_factor * (1.0 - self.ulcer_clot);
+        let shed = patient.fluids.remove_blood(self.ulcer_bleed_rate_ml_min * delta_time_s / 60.0);
+        self.ulcer_blood_loss_ml += shed;
+        self.add_blood(shed);
+        if self.ulcer_bleed_rate_ml_min < 0.5 {
+            self.ulcer_bleeding = false;
+            self.ulcer_bleed_rate_ml_min = 0.0;
+            self.clot_hold_remaining_s = ULCER_CLOT_HOLD_S;
+        }
+    }
+
+    /// Receive contents refluxed from the duodenum
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Volume refluxed (bile-stained small bowel content)
+    /// * `feculent_ml` - Portion of the reflux that is stagnant, feculent content
+    pub fn receive_reflux(&mut self, volume_ml: f64, feculent_ml: f64) {
+        let volume_ml = volume_ml.max(0.0);
+        let feculent_ml = feculent_ml.clamp(0.0, volume_ml);
+        self.chyme.volume_ml += volume_ml;
+        self.chyme.bile_ml += volume_ml - feculent_ml;
+        self.chyme.feculent_ml += feculent_ml;
+
+        // Retrograde flow into an already distended stomach precedes vomiting
+        if volume_ml > 0.0 && self.chyme.volume_ml > 400.0 {
+            self.emesis_pending = true;
+        }
+    }
+
+    /// Trigger vomiting on the next update
+    pub fn induce_emesis(&mut self) {
+        self.emesis_pending = true;
+    }
+
+    /// Drain gastric contents through a nasogastric tube
+    ///
+    /// # Arguments
+    /// * `max_volume_ml` - Maximum volume to aspirate
+    ///
+    /// # Returns
+    /// The aspirated output
+    pub fn aspirate(&mut self, max_volume_ml: f64) -> GastricOutput {
+        let character = self.chyme.character();
+        let removed = self.chyme.remove(max_volume_ml.max(0.0));
+        self.last_output_character = Some(character);
+        GastricOutput {
+            time_s: self.elapsed_time_s,
+            volume_ml: removed.volume_ml,
+            blood_ml: removed.blood_ml(),
+            ingested_ml: removed.nutrients.volume_ml(),
+            acid_meq: removed.acid_meq(),
+            character,
+        }
+    }
+
+    /// Collect episodes of emesis recorded since the last call
+    pub fn take_emesis_events(&mut self) -> Vec<GastricOutput> {
+        std::mem::take(&mut self.emesis_events)
+    }
+
+    /// Get the character of the current gastric contents
+    pub fn get_content_character(&self) -> GastricContent {
+        self.chyme.character()
+    }
+
+    /// Expel most of the gastric contents
+    fn vomit(&mut self) {
+        let character = self.chyme.character();
+        let removed = self.chyme.remove(self.chyme.volume_ml * 0.8);
+        self.emesis_events.push(GastricOutput {
+            time_s: self.elapsed_time_s,
+            volume_ml: removed.volume_ml,
+            blood_ml: removed.blood_ml(),
+            ingested_ml: removed.nutrients.volume_ml(),
+            acid_meq: removed.acid_meq(),
+            character,
+        });
+        self.last_output_character = Some(character);
+    }
 }
 
 impl Organ for Stomach {
-    fn update(&mut self, _patient: &mut Patient, delta_time_s: f64) {
+    fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        self.elapsed_time_s += delta_time_s;
+        self.update_ulcer(patient, delta_time_s);
+
         // Update state based on volume
         if self.chyme.volume_ml == 0.0 {
             self.state = StomachState::Empty;
@@ -76,20 +535,55 @@ impl Organ for Stomach {
             self.state = StomachState::Emptying;
         }
 
-        // Digest and empty chyme
+        // Digest and empty chyme; duodenal feedback holds back energy-dense and fatty chyme
         if self.chyme.volume_ml > 0.0 {
-            let digestion_amount = self.digestion_rate * delta_time_s / 60.0;
-            self.chyme.volume_ml = (self.chyme.volume_ml - digestion_amount).max(0.0);
+            let nutrients = &self.chyme.nutrients;
+            let energy_density = nutrients.energy_kcal() / self.chyme.volume_ml;
+            let energy_limit_ml_min = GASTRIC_EMPTYING_KCAL_MIN
+                * (1.0 - FAT_EMPTYING_DELAY * nutrients.fat_energy_fraction())
+                / energy_density.max(1e-9);
+            let digestion_amount = self.digestion_rate.min(energy_limit_ml_min) * delta_time_s / 60.0;
+            let emptied = self.chyme.remove(digestion_amount);
+            self.emptied_volume_ml += emptied.volume_ml;
+            self.emptied_blood_ml += emptied.blood_ml();
+            self.emptied_nutrients.add(&emptied.nutrients);
+
+            // Acid converts blood to coffee-ground material within minutes;
+            // conversion is much slower when the stomach is not acidic
+            let conversion_time_s = if self.chyme.ph < 4.0 { 300.0 } else { 3600.0 };
+            let converted = self.chyme.fresh_blood_ml * (delta_time_s / conversion_time_s).min(1.0);
+            self.chyme.fresh_blood_ml -= converted;
+            self.chyme.digested_blood_ml += converted;
 
             // Maintain acidic pH
             self.chyme.ph = (self.chyme.ph - delta_time_s * 0.1).max(1.5);
         }
+
+        // Vomiting reflex; a stomach full of fresh blood is vomited as hematemesis
+        let emesis_due = self.emesis_pending
+            || self.chyme.volume_ml > self.emesis_threshold_ml
+            || self.chyme.fresh_blood_ml > HEMATEMESIS_BLOOD_ML;
+        if emesis_due && self.chyme.volume_ml > 0.0 {
+            self.vomit();
+        }
+        self.emesis_pending = false;
     }
 
     fn get_summary(&self) -> String {
+        let ulcer = if self.ulcer_bleeding {
+            format!(", BLEEDING ULCER ({:.1} mL/min)", self.ulcer_bleed_rate_ml_min)
+        } else if self.ulcer_severity >= 0.1 {
+            format!(", Ulcer {:.2}", self.ulcer_severity)
+        } else {
+            String::new()
+        };
         format!(
-            "Stomach: State={:?}, Volume={:.0} mL, pH={:.1}",
-            self.state, self.chyme.volume_ml, self.chyme.ph
+            "Stomach: State={:?}, Volume={:.0} mL, pH={:.1}, Contents={:?}{}",
+            self.state,
+            self.chyme.volume_ml,
+            self.chyme.ph,
+            self.chyme.character(),
+            ulcer
         )
     }
 
@@ -108,4 +602,47 @@ impl Organ for Stomach {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluids::{IntakeRoute, OutputRoute};
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_repeated_vomiting_causes_hypochloremic_metabolic_alkalosis() {
+        let mut patient = initialize_patient(1, 3);
+
+        // Everything drunk comes back up once it has mixed with acid, twice an hour
+        // for 8 hours, while the lost volume is replaced intravenously
+        for _ in 0..16 {
+            patient.get_organ_mut::<Stomach>().unwrap().ingest(Meal::water(500.0));
+            for _ in 0..6 {
+                update_patient(&mut patient, 10.0);
+            }
+            patient.get_organ_mut::<Stomach>().unwrap().induce_emesis();
+            patient.give_fluid(IntakeRoute::Intravenous, 400.0);
+            for _ in 0..174 {
+                update_patient(&mut patient, 10.0);
+            }
+        }
+        assert_eq!(patient.get_organ::<Stomach>().unwrap().last_output_character, Some(GastricContent::NonBilious));
+        assert!(patient.fluid_ledger.current_day.output_for(OutputRoute::Emesis) > 5000.0);
+        let chemistry = &patient.blood.chemistry;
+        assert!(chemistry.bicarbonate_meq_l > 32.0, "HCO3 {}", chemistry.bicarbonate_meq_l);
+        assert!(chemistry.chloride_meq_l < 92.0, "Cl {}", chemistry.chloride_meq_l);
+        assert!(patient.blood.gases.ph > 7.45, "pH {}", patient.blood.gases.ph);
+
+        // The bicarbonaturia wastes potassium, and the hypokalemia keeps the alkalosis going
+        for _ in 0..1440 {
+            update_patient(&mut patient, 10.0);
+        }
+        assert!(patient.blood.chemistry.potassium_meq_l < 3.5, "K {}", patient.blood.chemistry.potassium_meq_l);
+        assert!(patient.blood.chemistry.bicarbonate_meq_l > 30.0);
+    }
 }
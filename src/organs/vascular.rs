@@ -1,7 +1,123 @@
+//! Vascular system simulation
+//!
+//! The arterial and venous tree as named vessels, each with a Poiseuille flow
+//! resistance, smooth muscle tone and endothelium. Endothelin, nitric oxide,
+//! angiotensin II and sympathetic tone set the arteriolar resistance the heart
+//! ejects into. Stenoses, dissections, thrombi and emboli narrow single vessels,
+//! so peripheral pulses, Doppler studies and the ankle-brachial index can be
+//! examined non-invasively, and a venous clot can travel to the lungs.
+
+use crate::models::poiseuille;
 use crate::organ::{Organ, OrganId};
+use crate::organs::bones::NORMAL_HEMATOCRIT;
+use crate::organs::lungs::Side;
 use crate::patient::Patient;
+use crate::signals::Signal;
 use std::any::Any;
 
+/// Normal pulmonary vascular resistance (Wood units)
+pub const NORMAL_PULMONARY_VASCULAR_RESISTANCE: f64 = 1.5;
+
+/// Arterial smooth muscle tone with balanced vasoactive signals (0.0-1.0)
+const RESTING_SMOOTH_MUSCLE_TONE: f64 = 0.5;
+
+/// Time constant of arterial tone following its vasoactive signals (s)
+const VASOMOTOR_TONE_RESPONSE_S: f64 = 60.0;
+
+/// Parent of each systemic artery, from the aorta outwards
+const ARTERIAL_TREE: &[(&str, &str)] = &[
+    ("Carotid Artery (L)", "Aorta"),
+    ("Carotid Artery (R)", "Aorta"),
+    ("Subclavian Artery (L)", "Aorta"),
+    ("Subclavian Artery (R)", "Aorta"),
+    ("Brachial Artery (L)", "Subclavian Artery (L)"),
+    ("Brachial Artery (R)", "Subclavian Artery (R)"),
+    ("Radial Artery (L)", "Brachial Artery (L)"),
+    ("Radial Artery (R)", "Brachial Artery (R)"),
+    ("Celiac Artery", "Aorta"),
+    ("Hepatic Artery", "Celiac Artery"),
+    ("Superior Mesenteric Artery", "Aorta"),
+    ("Renal Artery (L)", "Aorta"),
+    ("Renal Artery (R)", "Aorta"),
+    ("Iliac Artery (L)", "Aorta"),
+    ("Iliac Artery (R)", "Aorta"),
+    ("Femoral Artery (L)", "Iliac Artery (L)"),
+    ("Femoral Artery (R)", "Iliac Artery (R)"),
+    ("Popliteal Artery (L)", "Femoral Artery (L)"),
+    ("Popliteal Artery (R)", "Femoral Artery (R)"),
+    ("Dorsalis Pedis Artery (L)", "Popliteal Artery (L)"),
+    ("Dorsalis Pedis Artery (R)", "Popliteal Artery (R)"),
+    ("Posterior Tibial Artery (L)", "Popliteal Artery (L)"),
+    ("Posterior Tibial Artery (R)", "Popliteal Artery (R)"),
+    ("Anterior Cerebral Artery (L)", "Carotid Artery (L)"),
+    ("Anterior Cerebral Artery (R)", "Carotid Artery (R)"),
+    ("Middle Cerebral Artery (L)", "Carotid Artery (L)"),
+    ("Middle Cerebral Artery (R)", "Carotid Artery (R)"),
+    ("Posterior Cerebral Artery (L)", "Basilar Artery"),
+    ("Posterior Cerebral Artery (R)", "Basilar Artery"),
+];
+
+/// Arteries palpated in a peripheral pulse examination
+pub const PULSE_SITES: [&str; 14] = [
+    "Carotid Artery (L)",
+    "Carotid Artery (R)",
+    "Brachial Artery (L)",
+    "Brachial Artery (R)",
+    "Radial Artery (L)",
+    "Radial Artery (R)",
+    "Femoral Artery (L)",
+    "Femoral Artery (R)",
+    "Popliteal Artery (L)",
+    "Popliteal Artery (R)",
+    "Dorsalis Pedis Artery (L)",
+    "Dorsalis Pedis Artery (R)",
+    "Posterior Tibial Artery (L)",
+    "Posterior Tibial Artery (R)",
+];
+
+/// Perfusion pressure collaterals carry around a blocked artery (fraction of normal)
+const ARTERIAL_COLLATERAL_FRACTION: f64 = 0.3;
+
+/// Lowest local systolic pressure at which a pulse can be felt (mmHg): large
+/// central arteries stay palpable after the smaller distal ones are lost
+fn palpable_systolic_mmhg(artery: &str) -> f64 {
+    if artery.starts_with("Carotid") {
+        60.0
+    } else if artery.starts_with("Femoral") {
+        70.0
+    } else {
+        80.0
+    }
+}
+
+/// Strength of a palpated pulse (0-3+ scale)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PulseGrade {
+    /// 0: not palpable
+    Absent,
+    /// 1+: weak or thready
+    Diminished,
+    /// 2+: normal
+    Normal,
+    /// 3+: bounding
+    Bounding,
+}
+
+/// Palpated pulse at one artery
+#[derive(Debug, Clone)]
+pub struct PulseFinding {
+    /// Artery palpated
+    pub artery: String,
+    /// Pulse strength
+    pub grade: PulseGrade,
+    /// Local systolic pressure (mmHg)
+    pub systolic_mmhg: f64,
+    /// Local diastolic pressure (mmHg)
+    pub diastolic_mmhg: f64,
+    /// Palpable pulse rate (bpm), lower than the heart rate when beats fail to reach the periphery
+    pub rate_bpm: f64,
+}
+
 /// Type of blood vessel
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum VesselType {
@@ -36,6 +152,29 @@ impl VesselType {
     }
 }
 
+/// A lobar branch of the pulmonary arterial tree
+#[derive(Debug, Clone)]
+pub struct PulmonaryArterySegment {
+    pub name: String,
+    /// Lung the segment supplies
+    pub side: Side,
+    /// Fraction of pulmonary blood flow the segment normally carries
+    pub perfusion_share: f64,
+    /// Fraction of the segment's lumen blocked by embolus (0.0-1.0)
+    pub occlusion: f64,
+}
+
+impl PulmonaryArterySegment {
+    fn new(name: &str, side: Side, perfusion_share: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            side,
+            perfusion_share,
+            occlusion: 0.0,
+        }
+    }
+}
+
 /// A single blood vessel
 #[derive(Debug, Clone)]
 pub struct Vessel {
@@ -46,6 +185,8 @@ pub struct Vessel {
     pub baseline_diameter_mm: f64,   // For calculating vasodilation/constriction
     pub elasticity: f64,             // 0.0-1.0 (compliance)
     pub plaque_buildup: f64,         // 0.0-1.0 (atherosclerosis)
+    pub occlusion: f64,              // 0.0-1.0 (thrombus or embolus in the lumen)
+    pub dissection: f64,             // 0.0-1.0 (true lumen compressed by a dissection flap)
     pub smooth_muscle_tone: f64,     // 0.0-1.0 (vasoconstriction)
     pub endothelial_health: f64,     // 0.0-1.0 (lining of vessel)
     pub inflammation: f64,           // 0.0-1.0
@@ -53,6 +194,7 @@ pub struct Vessel {
     pub blood_flow_rate_ml_per_min: f64, // Flow rate through vessel (mL/min)
     pub pressure_mmhg: f64,          // Blood pressure in this vessel (mmHg)
     pub blood_velocity_cm_per_s: f64, // Velocity of blood flow (cm/s)
+    pub blood_viscosity_cp: f64,     // Viscosity of the blood in this vessel (cP)
 }
 
 impl Vessel {
@@ -65,13 +207,16 @@ impl Vessel {
             baseline_diameter_mm: diameter_mm,
             elasticity: 0.8,
             plaque_buildup: 0.0,
-            smooth_muscle_tone: 0.5,
+            occlusion: 0.0,
+            dissection: 0.0,
+            smooth_muscle_tone: RESTING_SMOOTH_MUSCLE_TONE,
             endothelial_health: 1.0,
             inflammation: 0.0,
             blood_volume_ml: 0.0,
             blood_flow_rate_ml_per_min: 0.0,
             pressure_mmhg: 0.0,
             blood_velocity_cm_per_s: 0.0,
+            blood_viscosity_cp: poiseuille::blood_viscosity_cp(NORMAL_HEMATOCRIT),
         };
         vessel.calculate_volume();
         vessel.pressure_mmhg = vessel.vessel_type.typical_pressure();
@@ -88,16 +233,14 @@ impl Vessel {
         self.blood_volume_ml = std::f64::consts::PI * radius_cm * radius_cm * length_cm;
     }
 
-    /// Calculate blood flow rate using simplified Poiseuille's law
+    /// Calculate blood flow rate from the pressure drop along the vessel
     /// Flow = ΔP / Resistance
     pub fn calculate_flow_rate(&mut self, upstream_pressure: f64, downstream_pressure: f64) {
         let delta_p = upstream_pressure - downstream_pressure;
         let resistance = self.flow_resistance();
 
         if resistance > 0.0 && delta_p > 0.0 {
-            // Simplified flow calculation (actual Poiseuille uses viscosity constant)
-            // Using a scaling factor to get realistic flow rates
-            self.blood_flow_rate_ml_per_min = (delta_p / resistance) * 100.0;
+            self.blood_flow_rate_ml_per_min = delta_p / resistance;
         } else {
             self.blood_flow_rate_ml_per_min = 0.0;
         }
@@ -119,20 +262,28 @@ impl Vessel {
         }
     }
 
-    /// Calculate effective diameter considering plaque buildup
+    /// Calculate effective diameter considering plaque buildup, occluding clot and dissection
     pub fn effective_diameter(&self) -> f64 {
-        self.diameter_mm * (1.0 - self.plaque_buildup * 0.8)
+        self.diameter_mm * (1.0 - self.plaque_buildup * 0.8) * (1.0 - self.occlusion) * (1.0 - self.dissection)
     }
 
-    /// Calculate resistance to blood flow (Poiseuille's law simplified)
-    /// Resistance is proportional to length and inversely proportional to radius^4
-    pub fn flow_resistance(&self) -> f64 {
-        let radius_mm = self.effective_diameter() / 2.0;
-        if radius_mm <= 0.0 {
-            return f64::MAX;
+    /// Fraction of normal flow the vessel can carry (0.0 = occluded, 1.0 = unobstructed)
+    ///
+    /// Narrowing doesn't limit flow until ~70% of the lumen is lost (critical stenosis).
+    pub fn patency(&self) -> f64 {
+        let narrowing = 1.0 - self.effective_diameter() / self.diameter_mm.max(1e-6);
+        if narrowing < 0.7 {
+            1.0
+        } else {
+            ((1.0 - narrowing) / 0.3).clamp(0.0, 1.0)
         }
-        // Simplified resistance calculation
-        self.length_cm / (radius_mm.powi(4))
+    }
+
+    /// Calculate resistance to blood flow (Poiseuille's law, mmHg·min/mL)
+    /// Resistance is proportional to length and viscosity and inversely proportional to radius^4
+    pub fn flow_resistance(&self) -> f64 {
+        let radius_cm = self.effective_diameter() / 20.0;
+        poiseuille::resistance_mmhg_min_per_ml(self.length_cm, radius_cm, self.blood_viscosity_cp)
     }
 
     /// Apply vasoconstriction (decrease diameter)
@@ -156,7 +307,7 @@ impl Vessel {
 }
 
 /// Vascular system - arteries, veins, and capillaries
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VascularSystem {
     id: OrganId,
     pub vessels: Vec<Vessel>,
@@ -175,47 +326,83 @@ pub struct VascularSystem {
     pub venous_return_l_per_min: f64,    // Blood returning to heart (L/min)
     pub mean_arterial_pressure: f64,     // MAP (mmHg)
     pub central_venous_pressure: f64,    // CVP (mmHg, normal: 2-8)
+    /// Lobar pulmonary arteries
+    pub pulmonary_segments: Vec<PulmonaryArterySegment>,
+    /// Pulmonary vascular resistance (Wood units, normal ~1.5)
+    pub pulmonary_vascular_resistance: f64,
+    /// Ventricular rate (bpm), set from the heart
+    pub heart_rate_bpm: f64,
+    /// Fraction of beats too weak to reach the periphery, set from the heart
+    pub pulse_deficit_fraction: f64,
+    /// Remaining duration of thrombolytic activity (s)
+    thrombolysis_remaining_s: f64,
+    /// Central aortic systolic pressure (mmHg)
+    aortic_systolic_mmhg: f64,
+    /// Central aortic diastolic pressure (mmHg)
+    aortic_diastolic_mmhg: f64,
+    /// Resistance of the arteriolar bed at rest, the reference for relative resistance
+    baseline_arteriolar_resistance: f64,
+    /// Blood viscosity at which the baseline resistance applies (cP)
+    reference_viscosity_cp: f64,
 }
 
 impl VascularSystem {
     pub fn new(id: i32) -> Self {
-        let mut vessels = Vec::new();
-
-        // Major arteries
-        vessels.push(Vessel::new("Aorta", VesselType::Artery, 25.0, 40.0));
-        vessels.push(Vessel::new("Carotid Artery (L)", VesselType::Artery, 8.0, 20.0));
-        vessels.push(Vessel::new("Carotid Artery (R)", VesselType::Artery, 8.0, 20.0));
-        vessels.push(Vessel::new("Subclavian Artery (L)", VesselType::Artery, 9.0, 15.0));
-        vessels.push(Vessel::new("Subclavian Artery (R)", VesselType::Artery, 9.0, 15.0));
-        vessels.push(Vessel::new("Brachial Artery (L)", VesselType::Artery, 5.0, 30.0));
-        vessels.push(Vessel::new("Brachial Artery (R)", VesselType::Artery, 5.0, 30.0));
-        vessels.push(Vessel::new("Radial Artery (L)", VesselType::Artery, 3.0, 25.0));
-        vessels.push(Vessel::new("Radial Artery (R)", VesselType::Artery, 3.0, 25.0));
-        vessels.push(Vessel::new("Celiac Artery", VesselType::Artery, 7.0, 10.0));
-        vessels.push(Vessel::new("Renal Artery (L)", VesselType::Artery, 5.0, 8.0));
-        vessels.push(Vessel::new("Renal Artery (R)", VesselType::Artery, 5.0, 8.0));
-        vessels.push(Vessel::new("Iliac Artery (L)", VesselType::Artery, 10.0, 15.0));
-        vessels.push(Vessel::new("Iliac Artery (R)", VesselType::Artery, 10.0, 15.0));
-        vessels.push(Vessel::new("Femoral Artery (L)", VesselType::Artery, 8.0, 40.0));
-        vessels.push(Vessel::new("Femoral Artery (R)", VesselType::Artery, 8.0, 40.0));
-
-        // Major veins
-        vessels.push(Vessel::new("Superior Vena Cava", VesselType::Vein, 20.0, 15.0));
-        vessels.push(Vessel::new("Inferior Vena Cava", VesselType::Vein, 22.0, 35.0));
-        vessels.push(Vessel::new("Jugular Vein (L)", VesselType::Vein, 10.0, 20.0));
-        vessels.push(Vessel::new("Jugular Vein (R)", VesselType::Vein, 10.0, 20.0));
-        vessels.push(Vessel::new("Subclavian Vein (L)", VesselType::Vein, 12.0, 15.0));
-        vessels.push(Vessel::new("Subclavian Vein (R)", VesselType::Vein, 12.0, 15.0));
-        vessels.push(Vessel::new("Femoral Vein (L)", VesselType::Vein, 10.0, 40.0));
-        vessels.push(Vessel::new("Femoral Vein (R)", VesselType::Vein, 10.0, 40.0));
-        vessels.push(Vessel::new("Renal Vein (L)", VesselType::Vein, 6.0, 8.0));
-        vessels.push(Vessel::new("Renal Vein (R)", VesselType::Vein, 6.0, 8.0));
-
-        // Arterioles and capillaries (representing thousands as aggregates)
-        // Note: diameter/length are aggregates representing the entire capillary network
-        vessels.push(Vessel::new("Systemic Arterioles", VesselType::Arteriole, 0.5, 1000.0));
-        vessels.push(Vessel::new("Systemic Capillaries", VesselType::Capillary, 1.6, 10000.0));
-        vessels.push(Vessel::new("Systemic Venules", VesselType::Venule, 0.5, 1000.0));
+        let vessels = vec![
+            // Major arteries
+            Vessel::new("Aorta", VesselType::Artery, 25.0, 40.0),
+            Vessel::new("Carotid Artery (L)", VesselType::Artery, 8.0, 20.0),
+            Vessel::new("Carotid Artery (R)", VesselType::Artery, 8.0, 20.0),
+            Vessel::new("Subclavian Artery (L)", VesselType::Artery, 9.0, 15.0),
+            Vessel::new("Subclavian Artery (R)", VesselType::Artery, 9.0, 15.0),
+            Vessel::new("Brachial Artery (L)", VesselType::Artery, 5.0, 30.0),
+            Vessel::new("Brachial Artery (R)", VesselType::Artery, 5.0, 30.0),
+            Vessel::new("Radial Artery (L)", VesselType::Artery, 3.0, 25.0),
+            Vessel::new("Radial Artery (R)", VesselType::Artery, 3.0, 25.0),
+            Vessel::new("Celiac Artery", VesselType::Artery, 7.0, 10.0),
+            Vessel::new("Hepatic Artery", VesselType::Artery, 5.0, 8.0),
+            Vessel::new("Superior Mesenteric Artery", VesselType::Artery, 6.5, 20.0),
+            Vessel::new("Renal Artery (L)", VesselType::Artery, 5.0, 8.0),
+            Vessel::new("Renal Artery (R)", VesselType::Artery, 5.0, 8.0),
+            Vessel::new("Iliac Artery (L)", VesselType::Artery, 10.0, 15.0),
+            Vessel::new("Iliac Artery (R)", VesselType::Artery, 10.0, 15.0),
+            Vessel::new("Femoral Artery (L)", VesselType::Artery, 8.0, 40.0),
+            Vessel::new("Femoral Artery (R)", VesselType::Artery, 8.0, 40.0),
+            Vessel::new("Popliteal Artery (L)", VesselType::Artery, 6.0, 15.0),
+            Vessel::new("Popliteal Artery (R)", VesselType::Artery, 6.0, 15.0),
+            Vessel::new("Dorsalis Pedis Artery (L)", VesselType::Artery, 2.0, 10.0),
+            Vessel::new("Dorsalis Pedis Artery (R)", VesselType::Artery, 2.0, 10.0),
+            Vessel::new("Posterior Tibial Artery (L)", VesselType::Artery, 2.5, 35.0),
+            Vessel::new("Posterior Tibial Artery (R)", VesselType::Artery, 2.5, 35.0),
+
+            // Cerebral arteries
+            Vessel::new("Anterior Cerebral Artery (L)", VesselType::Artery, 2.5, 10.0),
+            Vessel::new("Anterior Cerebral Artery (R)", VesselType::Artery, 2.5, 10.0),
+            Vessel::new("Middle Cerebral Artery (L)", VesselType::Artery, 3.0, 15.0),
+            Vessel::new("Middle Cerebral Artery (R)", VesselType::Artery, 3.0, 15.0),
+            Vessel::new("Posterior Cerebral Artery (L)", VesselType::Artery, 2.2, 8.0),
+            Vessel::new("Posterior Cerebral Artery (R)", VesselType::Artery, 2.2, 8.0),
+            Vessel::new("Basilar Artery", VesselType::Artery, 3.5, 3.0),
+
+            // Major veins
+            Vessel::new("Superior Vena Cava", VesselType::Vein, 20.0, 15.0),
+            Vessel::new("Inferior Vena Cava", VesselType::Vein, 22.0, 35.0),
+            Vessel::new("Jugular Vein (L)", VesselType::Vein, 10.0, 20.0),
+            Vessel::new("Jugular Vein (R)", VesselType::Vein, 10.0, 20.0),
+            Vessel::new("Subclavian Vein (L)", VesselType::Vein, 12.0, 15.0),
+            Vessel::new("Subclavian Vein (R)", VesselType::Vein, 12.0, 15.0),
+            Vessel::new("Femoral Vein (L)", VesselType::Vein, 10.0, 40.0),
+            Vessel::new("Femoral Vein (R)", VesselType::Vein, 10.0, 40.0),
+            Vessel::new("Renal Vein (L)", VesselType::Vein, 6.0, 8.0),
+            Vessel::new("Renal Vein (R)", VesselType::Vein, 6.0, 8.0),
+            Vessel::new("Portal Vein", VesselType::Vein, 12.0, 8.0),
+
+            // Arterioles and capillaries (representing thousands as aggregates)
+            // Note: diameter/length are aggregates representing the entire capillary network
+            Vessel::new("Systemic Arterioles", VesselType::Arteriole, 0.5, 1000.0),
+            Vessel::new("Systemic Capillaries", VesselType::Capillary, 1.6, 10000.0),
+            Vessel::new("Systemic Venules", VesselType::Venule, 0.5, 1000.0),
+        ];
 
         let mut system = Self {
             id: id as usize,
@@ -235,7 +422,30 @@ impl VascularSystem {
             venous_return_l_per_min: 5.0,
             mean_arterial_pressure: 93.0,  // (120 + 2*80) / 3
             central_venous_pressure: 5.0,
+            // Lower lobes receive more flow (gravity)
+            pulmonary_segments: vec![
+                PulmonaryArterySegment::new("Right Upper Lobe Artery", Side::Right, 0.18),
+                PulmonaryArterySegment::new("Right Middle Lobe Artery", Side::Right, 0.10),
+                PulmonaryArterySegment::new("Right Lower Lobe Artery", Side::Right, 0.27),
+                PulmonaryArterySegment::new("Left Upper Lobe Artery", Side::Left, 0.20),
+                PulmonaryArterySegment::new("Left Lower Lobe Artery", Side::Left, 0.25),
+            ],
+            pulmonary_vascular_resistance: NORMAL_PULMONARY_VASCULAR_RESISTANCE,
+            heart_rate_bpm: 75.0,
+            pulse_deficit_fraction: 0.0,
+            thrombolysis_remaining_s: 0.0,
+            aortic_systolic_mmhg: 120.0,
+            aortic_diastolic_mmhg: 80.0,
+            baseline_arteriolar_resistance: 1.0,
+            reference_viscosity_cp: poiseuille::blood_viscosity_cp(NORMAL_HEMATOCRIT),
         };
+        // Arteries start at their resting tone
+        for vessel in &mut system.vessels {
+            if matches!(vessel.vessel_type, VesselType::Artery | VesselType::Arteriole) {
+                vessel.constrict(0.0);
+            }
+        }
+        system.baseline_arteriolar_resistance = system.arteriolar_resistance();
         system.calculate_blood_distribution();
         system
     }
@@ -253,6 +463,36 @@ impl VascularSystem {
         (resistance_sum / 1000.0).clamp(0.5, 3.0)
     }
 
+    /// Flow resistance of the arteriolar (resistance) bed
+    fn arteriolar_resistance(&self) -> f64 {
+        self.vessels
+            .iter()
+            .filter(|v| matches!(v.vessel_type, VesselType::Arteriole))
+            .map(|v| v.flow_resistance())
+            .sum()
+    }
+
+    /// Take a hematocrit as the patient's normal, so its viscosity gives the resting resistance
+    ///
+    /// A newborn's hematocrit of ~55% is normal for it; anemia and polycythemia
+    /// then change resistance relative to this.
+    ///
+    /// # Arguments
+    /// * `hematocrit` - Resting hematocrit (fraction)
+    pub fn set_reference_hematocrit(&mut self, hematocrit: f64) {
+        let viscosity = poiseuille::blood_viscosity_cp(hematocrit);
+        self.baseline_arteriolar_resistance *= viscosity / self.reference_viscosity_cp;
+        self.reference_viscosity_cp = viscosity;
+    }
+
+    /// Arteriolar resistance relative to resting tone (1.0 = normal)
+    ///
+    /// Endothelin and angiotensin constriction raise it; nitric oxide lowers it,
+    /// and it follows blood viscosity.
+    pub fn peripheral_resistance_factor(&self) -> f64 {
+        (self.arteriolar_resistance() / self.baseline_arteriolar_resistance.max(f64::MIN_POSITIVE)).min(10.0)
+    }
+
     /// Get number of critically stenosed vessels
     pub fn critically_stenosed_count(&self) -> usize {
         self.vessels.iter().filter(|v| v.is_critically_stenosed()).count()
@@ -355,6 +595,263 @@ impl VascularSystem {
             .sum()
     }
 
+    /// Get a vessel by name
+    pub fn vessel(&self, name: &str) -> Option<&Vessel> {
+        self.vessels.iter().find(|v| v.name == name)
+    }
+
+    /// Get a vessel by name, mutably
+    pub fn vessel_mut(&mut self, name: &str) -> Option<&mut Vessel> {
+        self.vessels.iter_mut().find(|v| v.name == name)
+    }
+
+    /// Occlude a vessel with thrombus or embolus (e.g. an MCA stroke)
+    ///
+    /// # Arguments
+    /// * `name` - Vessel name (e.g. "Middle Cerebral Artery (L)")
+    /// * `severity` - Fraction of the lumen blocked (0.0-1.0)
+    ///
+    /// # Returns
+    /// True if the vessel exists
+    pub fn occlude_vessel(&mut self, name: &str, severity: f64) -> bool {
+        match self.vessel_mut(name) {
+            Some(vessel) => {
+                vessel.occlusion = severity.clamp(0.0, 1.0);
+                vessel.calculate_volume();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove the occluding clot from a vessel (mechanical thrombectomy)
+    ///
+    /// # Returns
+    /// True if the vessel exists
+    pub fn recanalize_vessel(&mut self, name: &str) -> bool {
+        self.occlude_vessel(name, 0.0)
+    }
+
+    /// Get the fraction of normal flow a vessel can carry (1.0 if it doesn't exist)
+    pub fn vessel_patency(&self, name: &str) -> f64 {
+        self.vessel(name).map(|v| v.patency()).unwrap_or(1.0)
+    }
+
+    /// Dissect an artery, compressing its true lumen with an intimal flap
+    ///
+    /// # Arguments
+    /// * `name` - Vessel name (e.g. "Subclavian Artery (L)" when an aortic dissection extends into it)
+    /// * `severity` - Fraction of the true lumen compressed (0.0-1.0)
+    ///
+    /// # Returns
+    /// True if the vessel exists
+    pub fn dissect_vessel(&mut self, name: &str, severity: f64) -> bool {
+        match self.vessel_mut(name) {
+            Some(vessel) => {
+                vessel.dissection = severity.clamp(0.0, 1.0);
+                vessel.calculate_volume();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the arteries from the aorta down to a vessel, ending with the vessel itself
+    pub fn arterial_path(&self, name: &str) -> Vec<&Vessel> {
+        let mut path = Vec::new();
+        let mut current = name;
+        while let Some(vessel) = self.vessel(current) {
+            path.push(vessel);
+            match ARTERIAL_TREE.iter().find(|(child, _)| *child == current) {
+                Some(&(_, parent)) => current = parent,
+                None => break,
+            }
+        }
+        path.reverse();
+        path
+    }
+
+    /// Get the local systolic and diastolic pressure in an artery
+    ///
+    /// Every critically narrowed segment upstream lowers the mean pressure
+    /// (collaterals keep some pressure beyond an occlusion) and damps the
+    /// pulse pressure even more.
+    ///
+    /// # Returns
+    /// (systolic, diastolic) in mmHg
+    pub fn local_blood_pressure(&self, name: &str) -> (f64, f64) {
+        let patency: f64 = self.arterial_path(name).iter().map(|v| v.patency()).product();
+        let perfusion = patency + (1.0 - patency) * ARTERIAL_COLLATERAL_FRACTION;
+        let systolic = self.aortic_systolic_mmhg;
+        let diastolic = self.aortic_diastolic_mmhg;
+        let mean = self.central_venous_pressure
+            + (diastolic + (systolic - diastolic) / 3.0 - self.central_venous_pressure) * perfusion;
+        let pulse_pressure = (systolic - diastolic) * patency * patency;
+        (mean + 2.0 * pulse_pressure / 3.0, mean - pulse_pressure / 3.0)
+    }
+
+    /// Palpate the pulse at an artery
+    ///
+    /// # Returns
+    /// The finding, or None if the vessel doesn't exist
+    pub fn palpate_pulse(&self, name: &str) -> Option<PulseFinding> {
+        self.vessel(name)?;
+        let (systolic, diastolic) = self.local_blood_pressure(name);
+        let pulse_pressure = systolic - diastolic;
+        let grade = if self.heart_rate_bpm <= 0.0 || pulse_pressure < 10.0 || systolic < palpable_systolic_mmhg(name) {
+            PulseGrade::Absent
+        } else if pulse_pressure < 25.0 {
+            PulseGrade::Diminished
+        } else if pulse_pressure > 70.0 {
+            PulseGrade::Bounding
+        } else {
+            PulseGrade::Normal
+        };
+        let rate_bpm = if grade == PulseGrade::Absent {
+            0.0
+        } else {
+            self.heart_rate_bpm * (1.0 - self.pulse_deficit_fraction.clamp(0.0, 1.0))
+        };
+        Some(PulseFinding {
+            artery: name.to_string(),
+            grade,
+            systolic_mmhg: systolic,
+            diastolic_mmhg: diastolic,
+            rate_bpm,
+        })
+    }
+
+    /// Palpate every peripheral pulse
+    pub fn get_pulse_exam(&self) -> Vec<PulseFinding> {
+        PULSE_SITES.iter().filter_map(|name| self.palpate_pulse(name)).collect()
+    }
+
+    /// Get blood pressure measured in each arm
+    ///
+    /// # Returns
+    /// [(left systolic, left diastolic), (right systolic, right diastolic)] in mmHg
+    pub fn get_arm_blood_pressures(&self) -> [(f64, f64); 2] {
+        [
+            self.local_blood_pressure("Brachial Artery (L)"),
+            self.local_blood_pressure("Brachial Artery (R)"),
+        ]
+    }
+
+    /// Get the difference between the apical heart rate and the palpated radial pulse (bpm)
+    pub fn get_pulse_deficit_bpm(&self) -> f64 {
+        self.heart_rate_bpm * self.pulse_deficit_fraction.clamp(0.0, 1.0)
+    }
+
+    /// Release a venous thrombus into the pulmonary circulation
+    ///
+    /// The embolus passes through the right heart and lodges where it fits:
+    /// large clots straddle the main pulmonary artery bifurcation (saddle
+    /// embolus), intermediate ones block a main pulmonary artery, and small
+    /// ones travel on to the lobar artery with the most remaining flow.
+    ///
+    /// # Arguments
+    /// * `source_vein` - Name of the vein the clot breaks off from (e.g. "Femoral Vein (L)")
+    /// * `clot_size` - Fraction of the pulmonary vascular bed the clot can obstruct (0.0-1.0)
+    ///
+    /// # Returns
+    /// The site where the embolus lodged, or None if `source_vein` is not a vein
+    pub fn embolize(&mut self, source_vein: &str, clot_size: f64) -> Option<String> {
+        let is_vein = self.vessels
+            .iter()
+            .any(|v| v.name == source_vein && v.vessel_type == VesselType::Vein);
+        if !is_vein {
+            return None;
+        }
+        let clot_size = clot_size.clamp(0.0, 1.0);
+
+        let side_obstruction = |side: Side| -> f64 {
+            self.pulmonary_segments
+                .iter()
+                .filter(|s| s.side == side)
+                .map(|s| s.perfusion_share * s.occlusion)
+                .sum()
+        };
+        let (site, targets): (String, Vec<usize>) = if clot_size >= 0.5 {
+            ("Main Pulmonary Artery (saddle)".to_string(), (0..self.pulmonary_segments.len()).collect())
+        } else if clot_size >= 0.2 {
+            let side = if side_obstruction(Side::Right) <= side_obstruction(Side::Left) {
+                Side::Right
+            } else {
+                Side::Left
+            };
+            let name = match side {
+                Side::Right => "Right Pulmonary Artery",
+                Side::Left => "Left Pulmonary Artery",
+            };
+            let targets = (0..self.pulmonary_segments.len())
+                .filter(|&i| self.pulmonary_segments[i].side == side)
+                .collect();
+            (name.to_string(), targets)
+        } else {
+            // Flow carries the embolus to the lobe with the most remaining perfusion
+            let target = (0..self.pulmonary_segments.len())
+                .max_by(|&a, &b| {
+                    let flow = |i: usize| {
+                        let segment = &self.pulmonary_segments[i];
+                        segment.perfusion_share * (1.0 - segment.occlusion)
+                    };
+                    flow(a).total_cmp(&flow(b))
+                })?;
+            (self.pulmonary_segments[target].name.clone(), vec![target])
+        };
+
+        let target_share: f64 = targets.iter().map(|&i| self.pulmonary_segments[i].perfusion_share).sum();
+        for &i in &targets {
+            let segment = &mut self.pulmonary_segments[i];
+            segment.occlusion = (segment.occlusion + clot_size / target_share).min(1.0);
+        }
+        Some(site)
+    }
+
+    /// Give a thrombolytic (e.g. alteplase), dissolving pulmonary emboli and
+    /// occluding arterial clots over ~2 hours
+    pub fn give_thrombolytic(&mut self) {
+        self.thrombolysis_remaining_s = 7_200.0;
+    }
+
+    /// Get the fraction of the pulmonary vascular bed obstructed by emboli (0.0-1.0)
+    pub fn get_pulmonary_obstruction(&self) -> f64 {
+        self.pulmonary_segments
+            .iter()
+            .map(|s| s.perfusion_share * s.occlusion)
+            .sum::<f64>()
+            .clamp(0.0, 1.0)
+    }
+
+    /// Lyse emboli, update pulmonary vascular resistance and D-dimer
+    fn update_pulmonary_circulation(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        // Endogenous fibrinolysis clears emboli over weeks; thrombolytics in hours
+        let lysing = self.thrombolysis_remaining_s > 0.0;
+        let lysis_time_constant_s = if lysing { 3_600.0 } else { 14.0 * 86_400.0 };
+        self.thrombolysis_remaining_s = (self.thrombolysis_remaining_s - delta_time_s).max(0.0);
+        let remaining = (-delta_time_s / lysis_time_constant_s).exp();
+        for segment in &mut self.pulmonary_segments {
+            segment.occlusion *= remaining;
+        }
+        if lysing {
+            for vessel in self.vessels.iter_mut().filter(|v| v.occlusion > 0.0) {
+                vessel.occlusion *= remaining;
+                vessel.calculate_volume();
+            }
+        }
+
+        // Resistance rises steeply once more than a third of the bed is obstructed
+        let obstruction = self.get_pulmonary_obstruction();
+        self.pulmonary_vascular_resistance =
+            NORMAL_PULMONARY_VASCULAR_RESISTANCE / (1.0 - 0.9 * obstruction).powi(2);
+
+        // Fibrin degradation products from clot breakdown (cleared over hours)
+        let lysis_factor = if lysing { 3.0 } else { 1.0 };
+        let target_d_dimer = 250.0 + obstruction * 8_000.0 * lysis_factor;
+        let clotting = &mut patient.blood.clotting;
+        clotting.d_dimer_ng_ml += (target_d_dimer - clotting.d_dimer_ng_ml) * (delta_time_s / 21_600.0).min(1.0);
+    }
+
     /// Get total blood flow through venous system
     pub fn get_total_venous_flow(&self) -> f64 {
         self.vessels
@@ -369,30 +866,27 @@ impl Organ for VascularSystem {
     fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
         // 0. Update mean arterial pressure from blood pressure
         self.mean_arterial_pressure = patient.blood.get_mean_arterial_pressure();
+        self.aortic_systolic_mmhg = patient.blood.blood_pressure_systolic;
+        self.aortic_diastolic_mmhg = patient.blood.blood_pressure_diastolic;
+        // Red cells set the viscosity, so anemia lowers and polycythemia raises resistance
+        let viscosity = poiseuille::blood_viscosity_cp(patient.fluids.hematocrit());
+        for vessel in &mut self.vessels {
+            vessel.blood_viscosity_cp = viscosity;
+        }
 
         // 1. Calculate total peripheral resistance
+        // The heart turns it into pressure together with its output, so blood
+        // pressure is not adjusted here
         self.total_peripheral_resistance = self.calculate_total_resistance();
 
-        // 2. Update blood pressure based on vascular resistance
-        // Mean arterial pressure = cardiac output × total peripheral resistance
-        // Simplified: BP increases with resistance
-        let resistance_effect = (self.total_peripheral_resistance - 1.0) * 20.0;
-
-        // Also affected by blood volume
-        let volume_effect = (self.total_blood_volume_l - 5.0) * 5.0;
-
-        // Apply to blood pressure (gently nudge toward new values)
-        patient.blood.blood_pressure_systolic += (resistance_effect + volume_effect) * 0.01;
-        patient.blood.blood_pressure_diastolic += (resistance_effect + volume_effect) * 0.007;
-
-        // 3. Nitric oxide production (vasodilator)
+        // 2. Nitric oxide production (vasodilator)
         // Produced by healthy endothelium, requires oxygen
-        let o2_sat = patient.blood.gases.sao2_percent / 100.0;
+        let o2_sat = patient.blood.get_sao2_percent() / 100.0;
         let endothelial_no_production = self.average_vessel_health() * o2_sat;
         self.nitric_oxide_level = (self.nitric_oxide_level * 0.95 + endothelial_no_production * 0.05)
             .clamp(0.2, 2.0);
 
-        // 4. Endothelin production (vasoconstrictor)
+        // 3. Endothelin production (vasoconstrictor)
         // Increased by inflammation and low oxygen
         let inflammation_avg: f64 = self.vessels.iter().map(|v| v.inflammation).sum::<f64>()
             / self.vessels.len() as f64;
@@ -400,35 +894,46 @@ impl Organ for VascularSystem {
         self.endothelin_level = (self.endothelin_level * 0.96 + endothelin_production * 0.04)
             .clamp(0.5, 2.5);
 
-        // 5. Apply vasoconstriction/dilation based on various factors
+        // 4. Apply vasoconstriction/dilation based on various factors
         let vasodilation_signal = self.nitric_oxide_level - 1.0;
         let vasoconstriction_signal = self.endothelin_level - 1.0;
 
-        // Angiotensin II from blood (vasoconstrictor)
-        let angiotensin_signal = (patient.blood.chemistry.angiotensin_ii_au - 1.0) * 0.5;
-
-        let net_tone_change = (vasoconstriction_signal + angiotensin_signal - vasodilation_signal) * 0.01 * delta_time_s;
+        // Circulating angiotensin II and sympathetic nerves constrict; adenosine from
+        // hypoxic tissue dilates
+        let angiotensin_signal = patient.signals.excess(Signal::AngiotensinII) * 0.5;
+        let sympathetic_signal = patient.signals.excess(Signal::SympatheticTone);
+        let adenosine_signal = patient.signals.excess(Signal::Adenosine) * 0.5;
+
+        // Tone settles towards the balance of constricting and dilating signals
+        let target_tone = (RESTING_SMOOTH_MUSCLE_TONE
+            + 0.1
+                * (vasoconstriction_signal + angiotensin_signal + sympathetic_signal
+                    - vasodilation_signal
+                    - adenosine_signal))
+            .clamp(0.0, 1.0);
+        let tone_response = 1.0 - (-delta_time_s / VASOMOTOR_TONE_RESPONSE_S).exp();
 
         for vessel in &mut self.vessels {
             if matches!(vessel.vessel_type, VesselType::Artery | VesselType::Arteriole) {
-                if net_tone_change > 0.0 {
-                    vessel.constrict(net_tone_change);
+                let tone_change = (target_tone - vessel.smooth_muscle_tone) * tone_response;
+                if tone_change > 0.0 {
+                    vessel.constrict(tone_change);
                 } else {
-                    vessel.dilate(-net_tone_change);
+                    vessel.dilate(-tone_change);
                 }
             }
         }
 
-        // 6. Atherosclerosis progression
+        // 5. Atherosclerosis progression
         // Risk factors: high LDL, low HDL, inflammation, high glucose
         let ldl_risk = (patient.blood.chemistry.ldl_cholesterol_mg_dl - 100.0).max(0.0) / 100.0;
         let hdl_protection = (60.0 - patient.blood.chemistry.hdl_cholesterol_mg_dl).max(0.0) / 60.0;
-        let glucose_risk = (patient.blood.chemistry.glucose_mg_dl - 100.0).max(0.0) / 100.0;
+        let glucose_risk = (patient.blood.get_glucose_mg_dl() - 100.0).max(0.0) / 100.0;
 
         self.atherosclerosis_progression = (ldl_risk + hdl_protection + glucose_risk) / 3.0;
 
-        // Apply plaque buildup (very slow process)
-        let plaque_growth = self.atherosclerosis_progression * 0.00001 * delta_time_s;
+        // Apply plaque buildup (very slow process: years to decades)
+        let plaque_growth = self.atherosclerosis_progression * 0.000000001 * delta_time_s;
 
         for vessel in &mut self.vessels {
             if matches!(vessel.vessel_type, VesselType::Artery) {
@@ -447,7 +952,7 @@ impl Organ for VascularSystem {
             }
         }
 
-        // 7. Update arterial and venous compliance
+        // 6. Update arterial and venous compliance
         let artery_health: f64 = self.vessels
             .iter()
             .filter(|v| matches!(v.vessel_type, VesselType::Artery))
@@ -464,22 +969,22 @@ impl Organ for VascularSystem {
             0.5
         };
 
-        // 8. Capillary permeability - affected by inflammation
+        // 7. Capillary permeability - affected by inflammation
         // Normal permeability allows nutrient/gas exchange
         // Too much causes edema
         self.capillary_permeability = (0.5 + inflammation_avg * 0.3).clamp(0.3, 0.9);
 
-        // 9. Blood volume regulation
+        // 8. Blood volume regulation
         // Kidneys regulate this, but we can track shifts
         // High sodium increases blood volume
-        let sodium_effect = (patient.blood.chemistry.sodium_meq_l - 140.0) / 140.0;
+        let sodium_effect = (patient.blood.get_sodium_meq_l() - 140.0) / 140.0;
         self.total_blood_volume_l += sodium_effect * 0.001 * delta_time_s;
         self.total_blood_volume_l = self.total_blood_volume_l.clamp(3.0, 7.0);
 
-        // 10. Vessel elasticity decreases with age and damage
+        // 9. Vessel elasticity decreases with age and damage
         // Toxins, high glucose, and oxidative stress reduce elasticity
         let toxin_damage = patient.blood.chemistry.toxin_level_au * 0.00001 * delta_time_s;
-        let glucose_damage = if patient.blood.chemistry.glucose_mg_dl > 180.0 {
+        let glucose_damage = if patient.blood.get_glucose_mg_dl() > 180.0 {
             0.00001 * delta_time_s
         } else {
             0.0
@@ -490,34 +995,44 @@ impl Organ for VascularSystem {
             vessel.elasticity = vessel.elasticity.max(0.2);
         }
 
-        // 11. Calculate blood distribution across compartments
+        // 10. Calculate blood distribution across compartments
         self.calculate_blood_distribution();
 
-        // 12. Calculate venous return
-        self.calculate_venous_return();
-
-        // 13. Estimate cardiac output (simplified - would normally come from heart)
-        // Cardiac output = heart rate × stroke volume
-        // Using a simple estimate based on venous return (Frank-Starling)
-        self.cardiac_output_l_per_min = self.venous_return_l_per_min;
-
-        // 14. Calculate blood flow rates through all vessels
+        // 11. Calculate blood flow rates through all vessels
+        // Cardiac output and central venous pressure are set from the heart, which
+        // solves venous return and its own output together; in steady state they match
+        self.venous_return_l_per_min = self.cardiac_output_l_per_min;
         self.calculate_flow_rates(self.cardiac_output_l_per_min * 1000.0); // Convert to mL/min
 
-        // 15. Update central venous pressure based on venous blood volume
-        // More blood in veins = higher CVP
-        let normal_venous_volume = 3500.0; // mL
-        let volume_ratio = self.venous_blood_volume_ml / normal_venous_volume;
-        self.central_venous_pressure = (5.0 * volume_ratio).clamp(0.0, 15.0);
+        // 12. Pulmonary circulation and emboli
+        self.update_pulmonary_circulation(patient, delta_time_s);
     }
 
     fn get_summary(&self) -> String {
+        let abnormal_pulses: Vec<String> = self
+            .get_pulse_exam()
+            .iter()
+            .filter(|p| p.grade != PulseGrade::Normal)
+            .map(|p| format!("{} {:?}", p.artery, p.grade))
+            .collect();
+        let [(left_systolic, _), (right_systolic, _)] = self.get_arm_blood_pressures();
+        let mut pulses = String::new();
+        if !abnormal_pulses.is_empty() {
+            pulses += &format!(", Pulses: {}", abnormal_pulses.join(", "));
+        }
+        if (left_systolic - right_systolic).abs() > 20.0 {
+            pulses += &format!(", Arm BP L/R={:.0}/{:.0} mmHg", left_systolic, right_systolic);
+        }
+        if self.get_pulse_deficit_bpm() >= 1.0 {
+            pulses += &format!(", Pulse deficit={:.0} bpm", self.get_pulse_deficit_bpm());
+        }
         format!(
             "Vascular - TPR: {:.2}, MAP: {:.0} mmHg, CVP: {:.1} mmHg, \
              Blood Vol: {:.2}L (Art: {:.0}mL, Ven: {:.0}mL, Cap: {:.0}mL), \
              CO: {:.2}L/min, VR: {:.2}L/min, \
              Vessel health: {:.1}%, Plaque: {:.1}%, Stenoses: {}, \
-             Compliance: {:.2}, NO: {:.2}, ET-1: {:.2}",
+             Compliance: {:.2}, NO: {:.2}, ET-1: {:.2}, \
+             PVR: {:.1} WU, Pulmonary obstruction: {:.0}%{}",
             self.total_peripheral_resistance,
             self.mean_arterial_pressure,
             self.central_venous_pressure,
@@ -532,7 +1047,10 @@ impl Organ for VascularSystem {
             self.critically_stenosed_count(),
             self.arterial_compliance,
             self.nitric_oxide_level,
-            self.endothelin_level
+            self.endothelin_level,
+            self.pulmonary_vascular_resistance,
+            self.get_pulmonary_obstruction() * 100.0,
+            pulses
         )
     }
 
@@ -551,4 +1069,79 @@ impl Organ for VascularSystem {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn Organ> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::heart::Heart;
+    use crate::organs::lungs::Lungs;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_saddle_embolus_from_a_leg_vein_adds_dead_space_strains_the_rv_and_desaturates() {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..30 {
+            update_patient(&mut patient, 1.0);
+        }
+        let baseline_pao2 = patient.blood.gases.pao2_mmhg;
+        let baseline_sao2 = patient.blood.gases.sao2_percent;
+        let vascular = patient.get_organ_mut::<VascularSystem>().unwrap();
+        assert_eq!(vascular.embolize("Aorta", 0.6), None);
+        assert_eq!(vascular.embolize("Femoral Vein (L)", 0.6).as_deref(), Some("Main Pulmonary Artery (saddle)"));
+        for _ in 0..300 {
+            update_patient(&mut patient, 1.0);
+        }
+
+        assert!(patient.get_organ::<VascularSystem>().unwrap().get_pulmonary_obstruction() > 0.5);
+        // Ventilated but unperfused lung: end-tidal CO2 falls well below arterial
+        let lungs = patient.get_organ::<Lungs>().unwrap();
+        assert!(lungs.alveolar_dead_space_fraction > 0.3);
+        assert!(patient.blood.gases.paco2_mmhg - lungs.end_tidal_co2_mmhg > 10.0);
+        let heart = patient.get_organ::<Heart>().unwrap();
+        assert!(heart.pulmonary_vascular_resistance > 3.0 * NORMAL_PULMONARY_VASCULAR_RESISTANCE);
+        assert!(heart.mean_pulmonary_artery_pressure > 25.0);
+        assert!(heart.rv_strain > 0.2);
+        assert!(patient.blood.gases.pao2_mmhg < baseline_pao2 - 25.0);
+        assert!(patient.blood.gases.sao2_percent < baseline_sao2 - 2.0);
+    }
+
+    #[test]
+    fn test_peripheral_pulses_are_lost_before_central_ones_as_pressure_falls() {
+        let mut vascular = VascularSystem::new(0);
+        vascular.heart_rate_bpm = 100.0;
+        let grade = |vascular: &VascularSystem, artery: &str| vascular.palpate_pulse(artery).unwrap().grade;
+
+        // (systolic, diastolic, radial, femoral, carotid palpable)
+        for (systolic, diastolic, radial, femoral, carotid) in [
+            (120.0, 80.0, true, true, true),
+            (85.0, 55.0, true, true, true),
+            (75.0, 50.0, false, true, true),
+            (65.0, 45.0, false, false, true),
+            (55.0, 40.0, false, false, false),
+        ] {
+            vascular.aortic_systolic_mmhg = systolic;
+            vascular.aortic_diastolic_mmhg = diastolic;
+            let map = diastolic + (systolic - diastolic) / 3.0;
+            for (artery, palpable) in [
+                ("Radial Artery (L)", radial),
+                ("Femoral Artery (L)", femoral),
+                ("Carotid Artery (L)", carotid),
+            ] {
+                assert_eq!(grade(&vascular, artery) != PulseGrade::Absent, palpable, "{artery} at MAP {map:.0}");
+            }
+        }
+
+        // A narrow pulse pressure is thready even when the pressure is high enough to feel
+        vascular.aortic_systolic_mmhg = 95.0;
+        vascular.aortic_diastolic_mmhg = 75.0;
+        assert_eq!(grade(&vascular, "Radial Artery (L)"), PulseGrade::Diminished);
+        let absent = vascular.get_pulse_exam().iter().filter(|p| p.grade == PulseGrade::Absent).count();
+        assert_eq!(absent, 0);
+        assert_eq!(vascular.palpate_pulse("Radial Artery (L)").unwrap().rate_bpm, 100.0);
+    }
 }
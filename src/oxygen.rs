@@ -0,0 +1,579 @@
+//! Oxygen transport
+//!
+//! This module provides:
+//! - The oxyhemoglobin dissociation curve, with its P50 shifted by pH, PaCO2,
+//!   temperature and red cell 2,3-DPG, so saturation always follows from PO2
+//! - Global oxygen delivery (DO2) against consumption (VO2), the extraction ratio
+//!   and mixed venous saturation (SvO2)
+//! - The venous side of the blood gas: CO2 loaded in the tissues raises venous
+//!   PCO2 above arterial by VCO2 over cardiac output, widening in low flow
+//!   states, with venous bicarbonate and pH following from it
+//! - Supply-dependent consumption below the critical delivery, with the oxygen
+//!   debt made up by anaerobic lactate and repaid once delivery recovers
+//! - Whole-body CO2 production (VCO2) from the oxygen consumed and the
+//!   respiratory quotient, the load ventilation must clear
+//! - A respiratory quotient following the fuel being burned: it rises towards
+//!   1.0 with feeding and insulin and falls towards 0.7 in fasting ketosis
+//! - Indirect calorimetry: VO2, VCO2, RQ and energy expenditure by the Weir
+//!   equation, as a metabolic cart reports them
+
+use crate::blood::BloodComposition;
+use crate::models::oxygen_dissociation;
+
+pub use crate::models::oxygen_dissociation::{NORMAL_DPG_MMOL_L, NORMAL_P50_MMHG};
+
+/// Normal body temperature (°C)
+pub const NORMAL_TEMPERATURE_C: f64 = 37.0;
+
+/// Resting whole-body oxygen consumption of a 5 L blood volume adult (mL/min)
+const RESTING_OXYGEN_CONSUMPTION_ML_MIN: f64 = 250.0;
+
+/// Blood volume the resting oxygen consumption refers to (mL)
+const OXYGEN_CONSUMPTION_REFERENCE_BLOOD_VOLUME_ML: f64 = 5_000.0;
+
+/// Highest fraction of delivered oxygen the tissues can extract; below the
+/// critical delivery consumption becomes supply-dependent
+const MAX_EXTRACTION_RATIO: f64 = 0.75;
+
+/// Fraction of resting demand that can go to repaying an oxygen debt
+const DEBT_REPAYMENT_FRACTION: f64 = 0.2;
+
+/// Lactate made per mmol of oxygen shortfall (mmol)
+const LACTATE_PER_MMOL_O2: f64 = 2.0;
+
+/// Lactate distribution volume as a multiple of blood volume (total body water)
+const LACTATE_DISTRIBUTION_BLOOD_VOLUMES: f64 = 8.0;
+
+/// Respiratory quotient of a mixed diet (VCO2/VO2)
+const RESTING_RESPIRATORY_QUOTIENT: f64 = 0.8;
+
+/// Respiratory quotient of fat oxidation
+const FAT_RESPIRATORY_QUOTIENT: f64 = 0.7;
+
+/// Respiratory quotient of carbohydrate oxidation
+const CARBOHYDRATE_RESPIRATORY_QUOTIENT: f64 = 1.0;
+
+/// Time constant of the fuel mix following feeding, insulin and ketosis (s)
+const SUBSTRATE_ADAPTATION_S: f64 = 1_800.0;
+
+/// Energy released per litre of oxygen consumed in the Weir equation (kcal/L)
+const WEIR_KCAL_PER_L_O2: f64 = 3.941;
+
+/// Energy attributed per litre of CO2 produced in the Weir equation (kcal/L)
+const WEIR_KCAL_PER_L_CO2: f64 = 1.106;
+
+/// Minutes in a day
+const MINUTES_PER_DAY: f64 = 1_440.0;
+
+/// Volume of one mmol of oxygen (mL)
+const ML_PER_MMOL_O2: f64 = 22.4;
+
+/// Time constant of red cell 2,3-DPG adaptation (s)
+const DPG_ADAPTATION_S: f64 = 12.0 * 3_600.0;
+
+/// Slope of the blood CO2 dissociation curve over the physiological range (mL/dL per mmHg)
+const CO2_CONTENT_PER_MMHG: f64 = 0.65;
+
+/// Largest venoarterial PCO2 difference, reached as flow stops (mmHg)
+const MAX_VENOARTERIAL_PCO2_MMHG: f64 = 60.0;
+
+/// Bicarbonate formed per mmHg of CO2 loaded in the tissues; more than plasma
+/// buffering alone, as deoxygenated hemoglobin takes up the protons (mEq/L per mmHg)
+const VENOUS_CO2_BUFFERING_MEQ_L_PER_MMHG: f64 = 0.2;
+
+/// Oxyhemoglobin dissociation curve at a given P50
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OxygenDissociation {
+    /// PO2 at which hemoglobin is half saturated (mmHg)
+    pub p50_mmhg: f64,
+}
+
+impl OxygenDissociation {
+    /// The standard adult curve (P50 26.8 mmHg)
+    pub fn standard() -> Self {
+        Self { p50_mmhg: NORMAL_P50_MMHG }
+    }
+
+    /// The curve shifted by the conditions in the blood
+    ///
+    /// Acidosis, hypercapnia, fever and raised 2,3-DPG shift it to the right (Bohr
+    /// effect), unloading oxygen more readily; alkalosis, hypothermia and stored
+    /// blood depleted of 2,3-DPG shift it to the left.
+    ///
+    /// # Arguments
+    /// * `ph` - Blood pH
+    /// * `paco2_mmhg` - PaCO2 (mmHg)
+    /// * `temperature_c` - Blood temperature (°C)
+    /// * `dpg_mmol_l` - Red cell 2,3-DPG (mmol/L)
+    pub fn shifted(ph: f64, paco2_mmhg: f64, temperature_c: f64, dpg_mmol_l: f64) -> Self {
+        Self { p50_mmhg: oxygen_dissociation::shifted_p50_mmhg(ph, paco2_mmhg, temperature_c, dpg_mmol_l) }
+    }
+
+    /// Hemoglobin saturation (fraction) at a PO2
+    pub fn saturation(&self, po2_mmhg: f64) -> f64 {
+        oxygen_dissociation::saturation(po2_mmhg, self.p50_mmhg)
+    }
+
+    /// Oxygen content of blood (mL O2/dL)
+    ///
+    /// # Arguments
+    /// * `hemoglobin_g_dl` - Hemoglobin (g/dL)
+    /// * `po2_mmhg` - PO2 (mmHg)
+    pub fn content_ml_dl(&self, hemoglobin_g_dl: f64, po2_mmhg: f64) -> f64 {
+        oxygen_dissociation::oxygen_content_ml_dl(hemoglobin_g_dl, self.saturation(po2_mmhg), po2_mmhg)
+    }
+
+    /// PO2 at which blood holds the given oxygen content (bisection)
+    ///
+    /// # Arguments
+    /// * `hemoglobin_g_dl` - Hemoglobin (g/dL)
+    /// * `content_ml_dl` - Oxygen content (mL O2/dL)
+    pub fn po2_for_content(&self, hemoglobin_g_dl: f64, content_ml_dl: f64) -> f64 {
+        let (mut low, mut high) = (0.0, 700.0);
+        for _ in 0..40 {
+            let mid = (low + high) / 2.0;
+            if self.content_ml_dl(hemoglobin_g_dl, mid) < content_ml_dl {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (low + high) / 2.0
+    }
+}
+
+/// Share of oxidative metabolism burning carbohydrate rather than fat (0.0-1.0)
+///
+/// A mixed diet at rest burns about a third carbohydrate. Absorbing a meal
+/// and insulin shift the mix towards carbohydrate; ketosis shifts it to fat.
+///
+/// # Arguments
+/// * `digesting` - Meal being absorbed (0.0 = fasting, 1.0 = full meal)
+/// * `insulin_uu_ml` - Plasma insulin (µU/mL)
+/// * `ketones_mmol_l` - Beta-hydroxybutyrate (mmol/L)
+pub fn carbohydrate_oxidation_fraction(digesting: f64, insulin_uu_ml: f64, ketones_mmol_l: f64) -> f64 {
+    let resting = (RESTING_RESPIRATORY_QUOTIENT - FAT_RESPIRATORY_QUOTIENT)
+        / (CARBOHYDRATE_RESPIRATORY_QUOTIENT - FAT_RESPIRATORY_QUOTIENT);
+    (resting + 0.4 * digesting.clamp(0.0, 1.0) + 0.1 * ((insulin_uu_ml - 10.0) / 10.0).clamp(-1.0, 2.0)
+        - 0.3 * ((ketones_mmol_l - 0.2) / 2.8).clamp(0.0, 1.0))
+    .clamp(0.0, 1.0)
+}
+
+/// Gas exchange and energy expenditure by indirect calorimetry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calorimetry {
+    /// Oxygen consumption (mL/min)
+    pub vo2_ml_min: f64,
+    /// CO2 production (mL/min)
+    pub vco2_ml_min: f64,
+    /// Respiratory quotient, VCO2/VO2
+    pub respiratory_quotient: f64,
+    /// Energy expenditure by the abbreviated Weir equation (kcal/day)
+    pub energy_expenditure_kcal_day: f64,
+}
+
+impl Calorimetry {
+    /// Derive RQ and energy expenditure from measured gas exchange
+    ///
+    /// # Arguments
+    /// * `vo2_ml_min` - Oxygen consumption (mL/min)
+    /// * `vco2_ml_min` - CO2 production (mL/min)
+    pub fn from_gas_exchange(vo2_ml_min: f64, vco2_ml_min: f64) -> Self {
+        Self {
+            vo2_ml_min,
+            vco2_ml_min,
+            respiratory_quotient: if vo2_ml_min > 0.0 { vco2_ml_min / vo2_ml_min } else { 0.0 },
+            // Weir: EE = (3.941 × VO2 + 1.106 × VCO2) × 1440, with gas volumes in L/min
+            energy_expenditure_kcal_day: (WEIR_KCAL_PER_L_O2 * vo2_ml_min + WEIR_KCAL_PER_L_CO2 * vco2_ml_min)
+                / 1_000.0
+                * MINUTES_PER_DAY,
+        }
+    }
+
+    /// Get a summary of the measurement
+    pub fn get_summary(&self) -> String {
+        format!(
+            "Indirect calorimetry: VO2={:.0} mL/min, VCO2={:.0} mL/min, RQ={:.2}, EE={:.0} kcal/day",
+            self.vo2_ml_min, self.vco2_ml_min, self.respiratory_quotient, self.energy_expenditure_kcal_day
+        )
+    }
+}
+
+impl Default for OxygenDissociation {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// Whole-body oxygen delivery and consumption
+#[derive(Debug, Clone)]
+pub struct OxygenTransport {
+    /// Core temperature used for the curve and metabolic rate (°C)
+    pub body_temperature_c: f64,
+    /// Metabolic rate relative to rest from fever-independent causes (sepsis, seizures)
+    pub metabolic_rate_factor: f64,
+    /// Physiologic reserve scaling the maximal oxygen extraction (1.0 = typical)
+    pub extraction_reserve: f64,
+    /// Red cell 2,3-DPG (mmol/L), rising over hours with anemia, hypoxemia and alkalosis
+    pub dpg_mmol_l: f64,
+    /// Current dissociation curve
+    pub dissociation: OxygenDissociation,
+    /// Arterial oxygen content (mL/dL)
+    pub arterial_content_ml_dl: f64,
+    /// Mixed venous oxygen content (mL/dL)
+    pub mixed_venous_content_ml_dl: f64,
+    /// Mixed venous PO2 (mmHg)
+    pub mixed_venous_po2_mmhg: f64,
+    /// Mixed venous saturation (%)
+    pub mixed_venous_saturation_percent: f64,
+    /// Mixed venous PCO2 (mmHg)
+    pub mixed_venous_pco2_mmhg: f64,
+    /// Mixed venous bicarbonate (mEq/L)
+    pub mixed_venous_hco3_meq_l: f64,
+    /// Mixed venous pH
+    pub mixed_venous_ph: f64,
+    /// Oxygen delivery, DO2 = CO × CaO2 × 10 (mL/min)
+    pub delivery_ml_min: f64,
+    /// Oxygen the tissues need at the current metabolic rate (mL/min)
+    pub demand_ml_min: f64,
+    /// Oxygen actually consumed, including debt repayment (mL/min)
+    pub consumption_ml_min: f64,
+    /// Fraction of delivered oxygen consumed
+    pub extraction_ratio: f64,
+    /// Accumulated oxygen shortfall not yet repaid (mL)
+    pub oxygen_debt_ml: f64,
+    /// Share of oxidative metabolism burning carbohydrate (0.0 = all fat, 1.0 = all
+    /// carbohydrate), set from feeding, insulin and ketones
+    pub carbohydrate_oxidation_fraction: f64,
+    /// CO2 produced per oxygen consumed (0.7 fat to 1.0 carbohydrate), following the fuel mix
+    pub respiratory_quotient: f64,
+    /// CO2 production, VCO2 = RQ × VO2 (mL/min)
+    pub co2_production_ml_min: f64,
+    /// CO2 production at rest for the patient's size (mL/min)
+    pub resting_co2_production_ml_min: f64,
+}
+
+impl OxygenTransport {
+    /// Create a resting adult oxygen transport
+    pub fn new() -> Self {
+        Self {
+            body_temperature_c: NORMAL_TEMPERATURE_C,
+            metabolic_rate_factor: 1.0,
+            extraction_reserve: 1.0,
+            dpg_mmol_l: NORMAL_DPG_MMOL_L,
+            dissociation: OxygenDissociation::standard(),
+            arterial_content_ml_dl: 19.5,
+            mixed_venous_content_ml_dl: 14.5,
+            mixed_venous_po2_mmhg: 40.0,
+            mixed_venous_saturation_percent: 75.0,
+            mixed_venous_pco2_mmhg: 46.0,
+            mixed_venous_hco3_meq_l: 25.2,
+            mixed_venous_ph: 7.36,
+            delivery_ml_min: 1_000.0,
+            demand_ml_min: RESTING_OXYGEN_CONSUMPTION_ML_MIN,
+            consumption_ml_min: RESTING_OXYGEN_CONSUMPTION_ML_MIN,
+            extraction_ratio: 0.25,
+            oxygen_debt_ml: 0.0,
+            carbohydrate_oxidation_fraction: carbohydrate_oxidation_fraction(0.0, 10.0, 0.2),
+            respiratory_quotient: RESTING_RESPIRATORY_QUOTIENT,
+            co2_production_ml_min: RESTING_RESPIRATORY_QUOTIENT * RESTING_OXYGEN_CONSUMPTION_ML_MIN,
+            resting_co2_production_ml_min: RESTING_RESPIRATORY_QUOTIENT * RESTING_OXYGEN_CONSUMPTION_ML_MIN,
+        }
+    }
+
+    /// Mixed venous minus arterial PCO2 (mmHg), normally 2-6 and wider when flow is low
+    pub fn venoarterial_pco2_gap_mmhg(&self, blood: &BloodComposition) -> f64 {
+        self.mixed_venous_pco2_mmhg - blood.gases.paco2_mmhg
+    }
+
+    /// Arterial minus mixed venous oxygen content (mL/dL)
+    pub fn arteriovenous_difference_ml_dl(&self) -> f64 {
+        (self.arterial_content_ml_dl - self.mixed_venous_content_ml_dl).max(0.0)
+    }
+
+    /// Fraction of the arterial oxygen content the tissues extract (O2ER)
+    pub fn extraction_ratio(&self) -> f64 {
+        if self.arterial_content_ml_dl > 1e-9 {
+            (self.arteriovenous_difference_ml_dl() / self.arterial_content_ml_dl).min(1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// CO2 production relative to rest for the patient's size
+    pub fn co2_production_ratio(&self) -> f64 {
+        if self.resting_co2_production_ml_min > 0.0 {
+            self.co2_production_ml_min / self.resting_co2_production_ml_min
+        } else {
+            1.0
+        }
+    }
+
+    /// Metabolic VO2, VCO2, RQ and energy expenditure of the tissues
+    ///
+    /// A metabolic cart reads the lungs' gas exchange instead, which departs
+    /// from this while the CO2 stores fill or empty.
+    pub fn calorimetry(&self) -> Calorimetry {
+        Calorimetry::from_gas_exchange(self.consumption_ml_min, self.co2_production_ml_min)
+    }
+
+    /// Whether consumption is limited by delivery
+    pub fn is_supply_dependent(&self) -> bool {
+        self.delivery_ml_min * self.max_extraction_ratio() < self.demand_ml_min
+    }
+
+    /// Highest fraction of delivered oxygen the tissues can extract
+    fn max_extraction_ratio(&self) -> f64 {
+        (MAX_EXTRACTION_RATIO * self.extraction_reserve).clamp(0.3, 0.9)
+    }
+
+    /// Derive saturation from PaO2 and balance delivery against consumption
+    ///
+    /// # Arguments
+    /// * `blood` - Blood composition, with PaO2, PaCO2 and pH for this step
+    /// * `cardiac_output_l_min` - Cardiac output (L/min)
+    /// * `reference_blood_volume_ml` - Normal blood volume for the patient's size (mL)
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(
+        &mut self,
+        blood: &mut BloodComposition,
+        cardiac_output_l_min: f64,
+        reference_blood_volume_ml: f64,
+        delta_time_s: f64,
+    ) {
+        let hemoglobin = blood.cells.hemoglobin_g_dl.max(0.0);
+        let pao2 = blood.gases.pao2_mmhg.max(0.0);
+
+        // Anemia, hypoxemia and alkalosis stimulate red cell 2,3-DPG synthesis
+        let target_dpg = NORMAL_DPG_MMOL_L
+            * (1.0
+                + 0.5 * ((13.5 - hemoglobin) / 13.5).max(0.0)
+                + 0.4 * ((60.0 - pao2) / 60.0).max(0.0)
+                + 1.5 * (blood.gases.ph - 7.4))
+                .clamp(0.5, 1.8);
+        self.dpg_mmol_l += (target_dpg - self.dpg_mmol_l) * (1.0 - (-delta_time_s / DPG_ADAPTATION_S).exp());
+
+        self.dissociation = OxygenDissociation::shifted(
+            blood.gases.ph,
+            blood.gases.paco2_mmhg,
+            self.body_temperature_c,
+            self.dpg_mmol_l,
+        );
+        blood.gases.sao2_percent = self.dissociation.saturation(pao2) * 100.0;
+        self.arterial_content_ml_dl = self.dissociation.content_ml_dl(hemoglobin, pao2);
+
+        // Demand scales with body size and rises ~10% per °C of fever (falls in hypothermia)
+        let cardiac_output = cardiac_output_l_min.max(0.0);
+        self.delivery_ml_min = cardiac_output * self.arterial_content_ml_dl * 10.0;
+        let resting_demand = RESTING_OXYGEN_CONSUMPTION_ML_MIN * reference_blood_volume_ml
+            / OXYGEN_CONSUMPTION_REFERENCE_BLOOD_VOLUME_ML;
+        self.demand_ml_min = resting_demand
+            * self.metabolic_rate_factor.max(0.0)
+            * 1.1f64.powf(self.body_temperature_c - NORMAL_TEMPERATURE_C);
+
+        // Below the critical delivery the shortfall is met anaerobically; spare
+        // extraction capacity afterwards repays the debt
+        let max_extraction = self.max_extraction_ratio();
+        let capacity = self.delivery_ml_min * max_extraction;
+        let minutes = delta_time_s / 60.0;
+        if capacity < self.demand_ml_min {
+            self.consumption_ml_min = capacity;
+            let shortfall_ml = (self.demand_ml_min - capacity) * minutes;
+            self.oxygen_debt_ml += shortfall_ml;
+            let distribution_l = LACTATE_DISTRIBUTION_BLOOD_VOLUMES * reference_blood_volume_ml / 1_000.0;
+            blood.chemistry.lactate_mmol_l += LACTATE_PER_MMOL_O2 * shortfall_ml / ML_PER_MMOL_O2 / distribution_l.max(0.1);
+        } else {
+            let repayment = (capacity - self.demand_ml_min)
+                .min(DEBT_REPAYMENT_FRACTION * self.demand_ml_min)
+                .min(self.oxygen_debt_ml / minutes.max(1e-6));
+            self.oxygen_debt_ml -= repayment * minutes;
+            self.consumption_ml_min = self.demand_ml_min + repayment;
+        }
+        // Aerobic metabolism releases CO2 in proportion to the oxygen it burns, more
+        // for carbohydrate than for fat
+        let substrate_quotient = FAT_RESPIRATORY_QUOTIENT
+            + (CARBOHYDRATE_RESPIRATORY_QUOTIENT - FAT_RESPIRATORY_QUOTIENT)
+                * self.carbohydrate_oxidation_fraction.clamp(0.0, 1.0);
+        self.respiratory_quotient += (substrate_quotient - self.respiratory_quotient)
+            * (1.0 - (-delta_time_s / SUBSTRATE_ADAPTATION_S).exp());
+        self.resting_co2_production_ml_min = RESTING_RESPIRATORY_QUOTIENT * resting_demand;
+        self.co2_production_ml_min = self.respiratory_quotient.clamp(0.6, 1.3) * self.consumption_ml_min;
+
+        // Fick: venous blood returns with what the tissues did not extract
+        self.extraction_ratio = if self.delivery_ml_min > 0.0 {
+            self.consumption_ml_min / self.delivery_ml_min
+        } else {
+            max_extraction
+        };
+        self.mixed_venous_content_ml_dl = self.arterial_content_ml_dl * (1.0 - self.extraction_ratio);
+        self.mixed_venous_po2_mmhg = self.dissociation.po2_for_content(hemoglobin, self.mixed_venous_content_ml_dl);
+        self.mixed_venous_saturation_percent = self.dissociation.saturation(self.mixed_venous_po2_mmhg) * 100.0;
+
+        // Fick for CO2: the blood carries away what the tissues make, so the same
+        // production loads each dL of a lower flow with more
+        let co2_loading_ml_dl = self.co2_production_ml_min / (cardiac_output * 10.0).max(1e-6);
+        let pco2_gap = (co2_loading_ml_dl / CO2_CONTENT_PER_MMHG).min(MAX_VENOARTERIAL_PCO2_MMHG);
+        let paco2 = blood.gases.paco2_mmhg.max(5.0);
+        self.mixed_venous_pco2_mmhg = paco2 + pco2_gap;
+        self.mixed_venous_hco3_meq_l = blood.gases.hco3_meq_l + VENOUS_CO2_BUFFERING_MEQ_L_PER_MMHG * pco2_gap;
+        self.mixed_venous_ph =
+            6.1 + (self.mixed_venous_hco3_meq_l.max(1.0) / (0.03 * self.mixed_venous_pco2_mmhg)).log10();
+    }
+
+    /// Get a summary of oxygen transport
+    pub fn get_summary(&self) -> String {
+        format!(
+            "Oxygen transport: DO2={:.0} mL/min, VO2={:.0} mL/min, VCO2={:.0} mL/min, RQ={:.2}, O2ER={:.0}%, SvO2={:.0}%, P50={:.1} mmHg{}",
+            self.delivery_ml_min,
+            self.consumption_ml_min,
+            self.co2_production_ml_min,
+            self.respiratory_quotient,
+            self.extraction_ratio * 100.0,
+            self.mixed_venous_saturation_percent,
+            self.dissociation.p50_mmhg,
+            if self.oxygen_debt_ml > 1.0 {
+                format!(", O2 debt={:.0} mL", self.oxygen_debt_ml)
+            } else {
+                String::new()
+            }
+        )
+    }
+}
+
+impl Default for OxygenTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curve_shift_and_supply_dependency() {
+        let standard = OxygenDissociation::standard();
+        assert!((standard.saturation(NORMAL_P50_MMHG) - 0.5).abs() < 0.01);
+        assert!(standard.saturation(100.0) > 0.97);
+
+        // Acidosis and fever shift the curve right; stored blood shifts it left
+        let febrile_acidotic = OxygenDissociation::shifted(7.1, 40.0, 40.0, NORMAL_DPG_MMOL_L);
+        assert!(febrile_acidotic.p50_mmhg > 35.0);
+        assert!(febrile_acidotic.saturation(40.0) < standard.saturation(40.0));
+        assert!(OxygenDissociation::shifted(7.4, 40.0, 37.0, 0.5).p50_mmhg < 20.0);
+
+        // Resting adult: SvO2 ~75%, no debt
+        let mut blood = BloodComposition::default();
+        let mut transport = OxygenTransport::new();
+        transport.update(&mut blood, 5.0, 5_000.0, 1.0);
+        assert!((70.0..80.0).contains(&transport.mixed_venous_saturation_percent));
+        assert!(!transport.is_supply_dependent());
+
+        // Low-flow shock: maximal extraction and lactate accumulation
+        let lactate = blood.chemistry.lactate_mmol_l;
+        for _ in 0..10 {
+            transport.update(&mut blood, 1.2, 5_000.0, 60.0);
+        }
+        assert!(transport.is_supply_dependent());
+        assert!(transport.mixed_venous_saturation_percent < 35.0);
+        assert!(transport.oxygen_debt_ml > 500.0);
+        assert!(blood.chemistry.lactate_mmol_l > lactate + 1.0);
+
+        // Restored flow repays the debt with consumption above demand
+        transport.update(&mut blood, 6.0, 5_000.0, 60.0);
+        assert!(transport.consumption_ml_min > transport.demand_ml_min);
+    }
+
+    #[test]
+    fn test_paco2_balances_production_against_ventilation() {
+        use crate::organs::lungs::{Lungs, Ventilator};
+        use crate::patient::{initialize_patient, update_patient, Patient};
+
+        let run = |setup: &dyn Fn(&mut Patient)| {
+            let mut patient = initialize_patient(1, 3);
+            setup(&mut patient);
+            for _ in 0..20 {
+                setup(&mut patient);
+                update_patient(&mut patient, 60.0);
+            }
+            patient
+        };
+        let ventilate = |patient: &mut Patient| {
+            if let Some(lungs) = patient.get_organ_mut::<Lungs>() {
+                lungs.ventilator.get_or_insert_with(Ventilator::default);
+            }
+        };
+        let baseline = run(&|_| {}).blood.gases.paco2_mmhg;
+
+        // Breathing spontaneously, ventilation keeps pace with a febrile metabolism
+        let febrile = run(&|patient| patient.oxygen_transport.body_temperature_c = 39.5);
+        assert!(febrile.oxygen_transport.co2_production_ratio() > 1.2);
+        assert!((febrile.blood.gases.paco2_mmhg - baseline).abs() < 1.5);
+        // At fixed ventilator settings the extra CO2 accumulates; paralysis lowers production
+        let ventilated_febrile = run(&|patient| {
+            ventilate(patient);
+            patient.oxygen_transport.body_temperature_c = 39.5;
+        });
+        assert!(ventilated_febrile.blood.gases.paco2_mmhg > baseline + 7.0);
+        let ventilated_paralysed = run(&|patient| {
+            ventilate(patient);
+            patient.set_neuromuscular_blockade(1.0);
+        });
+        assert!(ventilated_paralysed.blood.gases.paco2_mmhg < baseline - 2.0);
+        // Paralysed without a ventilator, CO2 climbs through apnea
+        let apneic = run(&|patient| patient.set_neuromuscular_blockade(1.0));
+        assert!(apneic.blood.gases.paco2_mmhg > 90.0);
+    }
+
+    #[test]
+    fn test_calorimetry_follows_fuel_and_ventilation() {
+        use crate::organs::lungs::{Lungs, Ventilator};
+        use crate::patient::{initialize_patient, update_patient};
+
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 600.0);
+        let resting = patient.metabolic_cart();
+        assert!((resting.respiratory_quotient - 0.8).abs() < 0.02, "{}", resting.get_summary());
+        assert!((1400.0..2400.0).contains(&resting.energy_expenditure_kcal_day), "{}", resting.get_summary());
+        assert_eq!(patient.oxygen_transport.calorimetry().vo2_ml_min, resting.vo2_ml_min);
+
+        // Fasting ketosis burns fat
+        let mut ketotic = patient.fork();
+        for _ in 0..60 {
+            ketotic.blood.chemistry.beta_hydroxybutyrate_mmol_l = 3.0;
+            ketotic.blood.chemistry.insulin_uu_ml = 2.0;
+            update_patient(&mut ketotic, 60.0);
+        }
+        assert!(ketotic.oxygen_transport.respiratory_quotient < 0.75);
+
+        // Hyperventilation exhales stored CO2, so the cart reads an RQ above the tissues'
+        if let Some(lungs) = patient.get_organ_mut::<Lungs>() {
+            lungs.ventilator = Some(Ventilator { rate_bpm: 30.0, ..Ventilator::default() });
+        }
+        update_patient(&mut patient, 60.0);
+        assert!(patient.metabolic_cart().respiratory_quotient > patient.oxygen_transport.calorimetry().respiratory_quotient + 0.2);
+    }
+
+    #[test]
+    fn test_vbg_estimates_the_abg_until_flow_is_low() {
+        use crate::labs::Analyte;
+        use crate::patient::{initialize_patient, update_patient, Patient};
+
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 120.0);
+        let estimate = |patient: &Patient, analyte: Analyte| analyte.arterial_estimate(analyte.read(patient)).map(|(_, value)| value);
+
+        // At normal flow pH and PCO2 convert; PO2 reflects the tissues and does not
+        let ph = estimate(&patient, Analyte::VenousPh).unwrap();
+        assert!((ph - patient.blood.gases.ph).abs() < 0.03, "{}", ph);
+        let paco2 = estimate(&patient, Analyte::Pvco2).unwrap();
+        assert!((paco2 - patient.blood.gases.paco2_mmhg).abs() < 3.0, "{}", paco2);
+        assert_eq!(estimate(&patient, Analyte::Pvo2), None);
+
+        // A low cardiac output loads the venous blood with CO2 the lungs still clear
+        let reference_blood_volume_ml = patient.fluids.reference_blood_volume_ml;
+        patient.oxygen_transport.update(&mut patient.blood, 1.5, reference_blood_volume_ml, 1.0);
+        let paco2 = estimate(&patient, Analyte::Pvco2).unwrap();
+        assert!(paco2 > patient.blood.gases.paco2_mmhg + 8.0, "{}", paco2);
+    }
+}
@@ -0,0 +1,217 @@
+//! Pulmonary artery (Swan-Ganz) catheter
+//!
+//! This module provides:
+//! - Right atrial, right ventricular and pulmonary artery pressures, and the
+//!   pulmonary capillary wedge pressure measured with the balloon inflated
+//! - Thermodilution cardiac output, systemic and pulmonary vascular resistance
+//!   and mixed venous oxygen saturation, with output and systemic resistance
+//!   also indexed to body surface area
+//! - Classification of the hemodynamic profile into the classic shock patterns
+//!   (hypovolemic, cardiogenic, distributive, obstructive)
+
+use crate::cardiac_output::{thermodilution_average, ThermodilutionInjection};
+use crate::organs::heart::Heart;
+use crate::patient::Patient;
+
+/// Hemodynamic profile recognised from PA catheter numbers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShockProfile {
+    /// Adequate output and filling pressures
+    Normal,
+    /// Low filling pressures, low output, high SVR
+    Hypovolemic,
+    /// High wedge pressure, low output, high SVR
+    Cardiogenic,
+    /// Low SVR with normal or high output
+    Distributive,
+    /// High right-sided pressures with a normal or low wedge and low output
+    Obstructive,
+}
+
+/// One set of PA catheter measurements
+#[derive(Debug, Clone)]
+pub struct PaCatheterReading {
+    /// Right atrial (central venous) pressure (mmHg)
+    pub right_atrial_mmhg: f64,
+    /// Right ventricular systolic pressure (mmHg)
+    pub rv_systolic_mmhg: f64,
+    /// Right ventricular end-diastolic pressure (mmHg)
+    pub rv_diastolic_mmhg: f64,
+    /// Pulmonary artery systolic pressure (mmHg)
+    pub pa_systolic_mmhg: f64,
+    /// Pulmonary artery diastolic pressure (mmHg)
+    pub pa_diastolic_mmhg: f64,
+    /// Mean pulmonary artery pressure (mmHg)
+    pub pa_mean_mmhg: f64,
+    /// Pulmonary capillary wedge pressure (mmHg), approximating left atrial pressure
+    pub wedge_mmhg: f64,
+    /// Thermodilution cardiac output (L/min)
+    pub cardiac_output_l_min: f64,
+    /// Cardiac index (cardiac output per body surface area, L/min/m²)
+    pub cardiac_index_l_min_m2: f64,
+    /// Stroke volume index (stroke volume per body surface area, mL/m²)
+    pub stroke_volume_index_ml_m2: f64,
+    /// Systemic vascular resistance (dyn·s/cm⁵)
+    pub systemic_vascular_resistance: f64,
+    /// Systemic vascular resistance index (dyn·s·m²/cm⁵)
+    pub systemic_vascular_resistance_index: f64,
+    /// Pulmonary vascular resistance (Wood units)
+    pub pulmonary_vascular_resistance_wood: f64,
+    /// Mixed venous oxygen saturation (%)
+    pub mixed_venous_saturation_percent: f64,
+}
+
+impl PaCatheterReading {
+    /// Classify the hemodynamic profile
+    pub fn shock_profile(&self) -> ShockProfile {
+        let low_output = self.cardiac_index_l_min_m2 < 2.5;
+        if self.systemic_vascular_resistance < 700.0 && !low_output {
+            ShockProfile::Distributive
+        } else if low_output && self.wedge_mmhg >= 18.0 {
+            ShockProfile::Cardiogenic
+        } else if low_output && self.right_atrial_mmhg >= 10.0 {
+            ShockProfile::Obstructive
+        } else if low_output && self.wedge_mmhg < 8.0 {
+            ShockProfile::Hypovolemic
+        } else {
+            ShockProfile::Normal
+        }
+    }
+
+    /// Get a summary of the reading
+    pub fn get_summary(&self) -> String {
+        format!(
+            "PA catheter: RA={:.0}, RV={:.0}/{:.0}, PA={:.0}/{:.0} ({:.0}), PCWP={:.0} mmHg, CO={:.1} L/min, \
+             CI={:.1} L/min/m², SVI={:.0} mL/m², SVR={:.0} (SVRI={:.0}), PVR={:.1} WU, SvO2={:.0}% - {:?}",
+            self.right_atrial_mmhg,
+            self.rv_systolic_mmhg,
+            self.rv_diastolic_mmhg,
+            self.pa_systolic_mmhg,
+            self.pa_diastolic_mmhg,
+            self.pa_mean_mmhg,
+            self.wedge_mmhg,
+            self.cardiac_output_l_min,
+            self.cardiac_index_l_min_m2,
+            self.stroke_volume_index_ml_m2,
+            self.systemic_vascular_resistance,
+            self.systemic_vascular_resistance_index,
+            self.pulmonary_vascular_resistance_wood,
+            self.mixed_venous_saturation_percent,
+            self.shock_profile()
+        )
+    }
+}
+
+/// Pressures along the catheter's path from the right atrium to the wedge position
+///
+/// # Arguments
+/// * `heart` - Heart being catheterised
+///
+/// # Returns
+/// (RA, RV systolic, PA systolic, PA diastolic, PA mean, wedge) in mmHg
+fn catheter_pressures(heart: &Heart) -> (f64, f64, f64, f64, f64, f64) {
+    let right_atrial = heart.right_atrium.pressure_mmhg;
+    let wedge = heart.left_atrium.pressure_mmhg;
+    let pa_mean = heart.mean_pulmonary_artery_pressure.max(wedge);
+    // Diastolic PA pressure sits just above the wedge, except when precapillary
+    // resistance (emboli, pulmonary hypertension) opens a diastolic-to-wedge gradient
+    let transpulmonary_gradient = pa_mean - wedge;
+    let pa_diastolic = wedge + 0.3 * transpulmonary_gradient.min(7.0) + 0.8 * (transpulmonary_gradient - 7.0).max(0.0);
+    let pa_systolic = 3.0 * pa_mean - 2.0 * pa_diastolic;
+    // A stenotic pulmonary valve adds a systolic gradient between ventricle and artery
+    let rv_systolic = pa_systolic + 1.3 * heart.pulmonary_valve.mean_gradient_mmhg;
+    (right_atrial, rv_systolic, pa_systolic, pa_diastolic, pa_mean, wedge)
+}
+
+/// Take a full set of PA catheter measurements
+///
+/// Cardiac output is the average of three iced thermodilution injections.
+///
+/// # Arguments
+/// * `patient` - The patient
+///
+/// # Returns
+/// The reading, or None if the patient has no heart
+pub fn pa_catheter_reading(patient: &Patient) -> Option<PaCatheterReading> {
+    let heart = patient.get_organ::<Heart>()?;
+    let (right_atrial, rv_systolic, pa_systolic, pa_diastolic, pa_mean, wedge) = catheter_pressures(heart);
+    let cardiac_output = thermodilution_average(heart, &ThermodilutionInjection::iced(), 3);
+
+    let map = patient.blood.get_mean_arterial_pressure();
+    let (systemic_vascular_resistance, pulmonary_vascular_resistance) = if cardiac_output > 0.1 {
+        (80.0 * (map - right_atrial).max(0.0) / cardiac_output, (pa_mean - wedge) / cardiac_output)
+    } else {
+        (0.0, 0.0)
+    };
+
+    // Indexed to body size, as hemodynamic targets are
+    let body_surface_area = patient.get_body_surface_area_m2().max(0.1);
+    let cardiac_index = cardiac_output / body_surface_area;
+    let stroke_volume_index = if heart.heart_rate_bpm > 0.0 { cardiac_index * 1000.0 / heart.heart_rate_bpm } else { 0.0 };
+
+    // Mixed venous blood is drawn from the distal port
+    let mixed_venous_saturation = patient.oxygen_transport.mixed_venous_saturation_percent;
+
+    Some(PaCatheterReading {
+        right_atrial_mmhg: right_atrial,
+        rv_systolic_mmhg: rv_systolic,
+        rv_diastolic_mmhg: right_atrial,
+        pa_systolic_mmhg: pa_systolic,
+        pa_diastolic_mmhg: pa_diastolic,
+        pa_mean_mmhg: pa_mean,
+        wedge_mmhg: wedge,
+        cardiac_output_l_min: cardiac_output,
+        cardiac_index_l_min_m2: cardiac_index,
+        stroke_volume_index_ml_m2: stroke_volume_index,
+        systemic_vascular_resistance,
+        systemic_vascular_resistance_index: systemic_vascular_resistance * body_surface_area,
+        pulmonary_vascular_resistance_wood: pulmonary_vascular_resistance,
+        mixed_venous_saturation_percent: mixed_venous_saturation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::vascular::VascularSystem;
+    use crate::patient::{initialize_patient, update_patient};
+
+    fn rested() -> Patient {
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        patient
+    }
+
+    #[test]
+    fn test_shock_profiles_from_hemorrhage_and_massive_embolism() {
+        let reading = pa_catheter_reading(&rested()).unwrap();
+        assert_eq!(reading.shock_profile(), ShockProfile::Normal);
+        assert!((4.0..=12.0).contains(&reading.wedge_mmhg));
+        assert!(reading.mixed_venous_saturation_percent > 65.0);
+
+        // Blood loss empties both sides of the heart and the tissues extract more oxygen
+        let mut bleeding = rested();
+        bleeding.hemorrhage(0.4 * bleeding.fluids.reference_blood_volume_ml);
+        for _ in 0..300 {
+            update_patient(&mut bleeding, 1.0);
+        }
+        let reading = pa_catheter_reading(&bleeding).unwrap();
+        assert_eq!(reading.shock_profile(), ShockProfile::Hypovolemic);
+        assert!(reading.wedge_mmhg < 8.0 && reading.right_atrial_mmhg < 5.0);
+        assert!(reading.systemic_vascular_resistance > 1400.0);
+        assert!(reading.mixed_venous_saturation_percent < 63.0);
+
+        // A saddle embolus backs pressure up into the right heart while the wedge stays normal
+        let mut embolized = rested();
+        embolized.get_organ_mut::<VascularSystem>().unwrap().embolize("Femoral Vein (L)", 0.8);
+        for _ in 0..300 {
+            update_patient(&mut embolized, 1.0);
+        }
+        let reading = pa_catheter_reading(&embolized).unwrap();
+        assert_eq!(reading.shock_profile(), ShockProfile::Obstructive);
+        assert!(reading.pa_mean_mmhg > 25.0 && reading.wedge_mmhg < 12.0);
+        assert!(reading.pulmonary_vascular_resistance_wood > 3.0);
+    }
+}
@@ -1,39 +1,347 @@
 //! Patient management and blood composition
+//!
+//! A patient holds its organs, blood, body fluids and patient-level systems
+//! (pregnancy, sepsis, growth, labs) and advances them together. It can be
+//! forked into independent timelines, so decision support can project the
+//! outcome of an intervention and compare it with doing nothing.
 
-use crate::blood::BloodComposition;
-use crate::organ::Organ;
+use crate::acid_base::AcidBase;
+use crate::acls::{self, AclsAssistant, CauseFinding, ReversibleCause};
+use crate::blood::{BloodComposition, DONOR_HBA1C_PERCENT};
+use crate::clock::SimulationClock;
+use crate::coagulation::Coagulation;
+use crate::difficulty::Difficulty;
+use crate::fidelity::{Fidelity, MAX_INTEGRATION_STEP_S};
+use crate::fluids::{FluidCompartments, FluidLedger, IntakeRoute, IoReport, OutputRoute};
+use crate::growth::{self, Demographics, Growth, Sex};
+use crate::iron::IronStores;
+use crate::labs::{LabPanel, LabService};
+use crate::menstrual::{self, MenstrualCycle};
+use crate::neonatal::Newborn;
+use crate::obstetrics::Pregnancy;
+use crate::organ::{Organ, OrganId, BUILT_IN_ORGAN_PRIORITY_STEP, DEFAULT_ORGAN_PRIORITY};
 use crate::organs::*;
+use crate::oxygen::{self, Calorimetry, OxygenTransport};
+use crate::process::Process;
+use crate::sepsis::{BundleGuideline, Sepsis};
+use crate::signals::{Signal, SignalBus};
+use crate::stability::Stabilizer;
+#[cfg(feature = "telemetry")]
+use crate::telemetry::{Probe, Telemetry};
+use crate::toxicology::{Drug, ScreenClass, ToxScreen, Toxicology};
+use crate::trace::{emergent, intervention};
+use crate::variability::Variability;
+use crate::wounds::Wound;
+use std::any::TypeId;
 use std::collections::HashMap;
 
+/// Storage iron in children (mg/kg), much lower than the ~14 mg/kg of adult men
+const CHILD_IRON_STORES_MG_PER_KG: f64 = 5.0;
+
+/// Platelet count rise from one apheresis unit in a 5 L blood volume (K/µL)
+const PLATELET_UNIT_INCREMENT_K_UL: f64 = 40.0;
+
+/// Volume of one apheresis platelet unit (mL)
+const PLATELET_UNIT_VOLUME_ML: f64 = 250.0;
+
+/// Volume of one unit of fresh frozen plasma (mL)
+const PLASMA_UNIT_VOLUME_ML: f64 = 250.0;
+
+/// Between-patient spread of electrolyte arrhythmia thresholds
+const ARRHYTHMIA_SUSCEPTIBILITY_CV: f64 = 0.15;
+
+/// Between-patient spread of catecholamine responsiveness
+const ADRENERGIC_SENSITIVITY_CV: f64 = 0.25;
+
+/// Between-patient spread of tolerance to ischemia (collateral supply)
+const ISCHEMIC_TOLERANCE_CV: f64 = 0.3;
+
+/// Defibrillation margin over which the odds of conversion change e-fold
+const SHOCK_OUTCOME_SPREAD: f64 = 0.05;
+
+/// Defibrillation margin gained per unit of treatment responsiveness above standard
+const SHOCK_RESPONSIVENESS_MARGIN: f64 = 0.1;
+
+/// Extracellular volume retained by the kidneys in severe chronic heart failure (fraction)
+const HEART_FAILURE_RETAINED_FRACTION: f64 = 0.3;
+
+/// Energy absorbed per minute while digesting a full meal (kcal/min)
+const FULL_MEAL_ABSORPTION_KCAL_MIN: f64 = 3.0;
+
+/// Sodium per milliequivalent (mg)
+const SODIUM_MG_PER_MEQ: f64 = 23.0;
+
+/// Nitrogen content of dietary protein (fraction by weight)
+const PROTEIN_NITROGEN_FRACTION: f64 = 0.16;
+
+/// Absorbed amino acid nitrogen deaminated to urea rather than kept for protein synthesis (fraction)
+const PROTEIN_UREAGENESIS_FRACTION: f64 = 0.5;
+
+/// Rise in metabolic rate while digesting a full meal (fraction of resting)
+const THERMIC_EFFECT_OF_FEEDING: f64 = 0.1;
+
+/// Fall in metabolic rate under complete neuromuscular blockade (fraction of resting)
+const PARALYSIS_METABOLIC_REDUCTION: f64 = 0.1;
+
+/// Rise in adenosine for each fraction of the oxygen demand left unmet
+const ADENOSINE_PER_OXYGEN_SHORTFALL: f64 = 4.0;
+
+/// Hepatic angiotensinogen production of a healthy liver (AU/min)
+const NORMAL_ANGIOTENSINOGEN_PRODUCTION: f64 = 10.0;
+
+/// Time constant of angiotensin II clearance (s)
+const ANGIOTENSIN_II_CLEARANCE_S: f64 = 20.0;
+
+/// Time constant of aldosterone secretion and clearance (s), a half-life of about 20 minutes
+const ALDOSTERONE_CLEARANCE_S: f64 = 1_800.0;
+
+/// Rise in aldosterone secretion per mEq/L of plasma potassium above normal
+const ALDOSTERONE_PER_POTASSIUM_MEQ_L: f64 = 0.5;
+
+/// Systemic inflammation from pancreatitis above which the alveolar membrane is injured
+const PANCREATIC_ARDS_INFLAMMATION: f64 = 0.4;
+
+/// Heat needed to warm the body by one degree, per kg (kcal/kg/°C)
+const BODY_HEAT_CAPACITY_KCAL_KG_C: f64 = 0.83;
+
+/// Heat generated by shivering per degree below normal temperature (kcal/min/°C)
+const SHIVERING_KCAL_MIN_PER_C: f64 = 1.5;
+
+/// Drug given to a patient, stamped with the patient's clock
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MedicationDose {
+    /// Simulation time of the dose (s)
+    pub time_s: f64,
+    /// Drug
+    pub medication: &'static str,
+    /// Dose, or rate for an infusion
+    pub amount: f64,
+    /// Units of the amount
+    pub units: &'static str,
+}
+
 /// Patient structure containing all organ systems
 pub struct Patient {
     /// Unique patient identifier
     pub id: i32,
+    /// Sex, age and height
+    pub demographics: Demographics,
     /// Blood composition and vital signs
     pub blood: BloodComposition,
-    /// All organs in the patient
+    /// Body water compartments
+    pub fluids: FluidCompartments,
+    /// Fluid intake/output ledger
+    pub fluid_ledger: FluidLedger,
+    /// Body iron stores
+    pub iron: IronStores,
+    /// Clotting factors, anticoagulants and DIC
+    pub coagulation: Coagulation,
+    /// Acid-base balance and compensation
+    pub acid_base: AcidBase,
+    /// Oxygen delivery, consumption and mixed venous saturation
+    pub oxygen_transport: OxygenTransport,
+    /// Hormones, cytokines and autonomic tone passed between organs
+    pub signals: SignalBus,
+    /// Drug levels and exposures detected by the urine drug screen
+    pub toxicology: Toxicology,
+    /// Lacerations and operative fields
+    pub wounds: Vec<Wound>,
+    /// Bacterial infection and sepsis bundle timers, if the patient is infected
+    pub sepsis: Option<Sepsis>,
+    /// ACLS assistant following a code, if one has been started
+    pub acls: Option<AclsAssistant>,
+    /// Menstrual cycle, if the patient is menstruating
+    pub menstrual_cycle: Option<MenstrualCycle>,
+    /// Pregnancy, if the patient is pregnant or postpartum
+    pub pregnancy: Option<Pregnancy>,
+    /// Newborn transition, if the patient is a neonate
+    pub newborn: Option<Newborn>,
+    /// Growth tracking, if the patient is a growing child
+    pub growth: Option<Growth>,
+    /// Seeded run-to-run variability (deterministic unless configured)
+    pub variability: Variability,
+    /// Scenario difficulty modifiers
+    pub difficulty: Difficulty,
+    /// Simulated time and its calendar anchor, shared by labs, doses and waveforms
+    pub clock: SimulationClock,
+    /// Drugs given, oldest first
+    pub doses: Vec<MedicationDose>,
+    /// Laboratory receiving orders for this patient
+    pub labs: LabService,
+    /// Guard correcting degenerate values after each step
+    pub stability: Stabilizer,
+    /// Level of detail the patient is simulated at
+    pub fidelity: Fidelity,
+    /// Time and allocations spent updating each organ
+    #[cfg(feature = "telemetry")]
+    pub telemetry: Telemetry,
+    /// Time accumulated towards the next coarse step at reduced fidelity (s)
+    pending_time_s: f64,
+    /// Level of detail of individual organs, overriding the patient's
+    organ_fidelity: HashMap<&'static str, Fidelity>,
+    /// Time each organ has accumulated towards its next coarse step (s), in organ order
+    organ_pending_s: Vec<f64>,
+    /// All organs in the patient, in update order
     organs: Vec<Box<dyn Organ>>,
-    /// Organ lookup by type name
-    organ_map: HashMap<&'static str, usize>,
+    /// Update priority of each organ, in organ order; lower runs first
+    organ_priorities: Vec<i32>,
+    /// Registered pathophysiology processes, updated after the organs
+    processes: Vec<Box<dyn Process>>,
+    /// Organ lookup by type name, every instance in update order
+    organ_map: HashMap<&'static str, Vec<usize>>,
+    /// Organ lookup by concrete type, every instance in update order
+    organ_types: HashMap<TypeId, Vec<usize>>,
+}
+
+impl Clone for Patient {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            demographics: self.demographics.clone(),
+            blood: self.blood.clone(),
+            fluids: self.fluids.clone(),
+            fluid_ledger: self.fluid_ledger.clone(),
+            iron: self.iron.clone(),
+            coagulation: self.coagulation.clone(),
+            acid_base: self.acid_base.clone(),
+            oxygen_transport: self.oxygen_transport.clone(),
+            signals: self.signals.clone(),
+            toxicology: self.toxicology.clone(),
+            wounds: self.wounds.clone(),
+            sepsis: self.sepsis.clone(),
+            acls: self.acls.clone(),
+            menstrual_cycle: self.menstrual_cycle.clone(),
+            pregnancy: self.pregnancy.clone(),
+            newborn: self.newborn.clone(),
+            growth: self.growth.clone(),
+            variability: self.variability.clone(),
+            difficulty: self.difficulty,
+            clock: self.clock,
+            doses: self.doses.clone(),
+            labs: self.labs.clone(),
+            stability: self.stability.clone(),
+            fidelity: self.fidelity,
+            #[cfg(feature = "telemetry")]
+            telemetry: self.telemetry.clone(),
+            pending_time_s: self.pending_time_s,
+            organ_fidelity: self.organ_fidelity.clone(),
+            organ_pending_s: self.organ_pending_s.clone(),
+            organs: self.organs.iter().map(|organ| organ.clone_box()).collect(),
+            organ_priorities: self.organ_priorities.clone(),
+            processes: self.processes.iter().map(|process| process.clone_box()).collect(),
+            organ_map: self.organ_map.clone(),
+            organ_types: self.organ_types.clone(),
+        }
+    }
 }
 
 impl Patient {
     /// Get a reference to an organ by type
-    pub fn get_organ<T: 'static>(&self, type_name: &'static str) -> Option<&T> {
-        self.organ_map.get(type_name).and_then(|&idx| {
-            self.organs.get(idx).and_then(|organ| {
-                organ.as_any().downcast_ref::<T>()
-            })
-        })
+    ///
+    /// When several organs share the type, the first in update order is returned.
+    /// The organs are out of the patient while they update, so an organ looking
+    /// up another from its own `update` gets `None`.
+    pub fn get_organ<T: 'static>(&self) -> Option<&T> {
+        let index = *self.organ_types.get(&TypeId::of::<T>())?.first()?;
+        self.organs.get(index)?.as_any().downcast_ref::<T>()
     }
 
     /// Get a mutable reference to an organ by type
-    pub fn get_organ_mut<T: 'static>(&mut self, type_name: &'static str) -> Option<&mut T> {
-        self.organ_map.get(type_name).and_then(|&idx| {
-            self.organs.get_mut(idx).and_then(|organ| {
-                organ.as_any_mut().downcast_mut::<T>()
-            })
-        })
+    ///
+    /// When several organs share the type, the first in update order is returned.
+    pub fn get_organ_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        let index = *self.organ_types.get(&TypeId::of::<T>())?.first()?;
+        self.organs.get_mut(index)?.as_any_mut().downcast_mut::<T>()
+    }
+
+    /// Get every organ of a type, in update order
+    pub fn get_organs<T: 'static>(&self) -> Vec<&T> {
+        self.organs
+            .iter()
+            .filter_map(|organ| organ.as_any().downcast_ref::<T>())
+            .collect()
+    }
+
+    /// Get every organ of a type mutably, in update order
+    pub fn get_organs_mut<T: 'static>(&mut self) -> Vec<&mut T> {
+        self.organs
+            .iter_mut()
+            .filter_map(|organ| organ.as_any_mut().downcast_mut::<T>())
+            .collect()
+    }
+
+    /// Get an organ by type name, for organs chosen at run time
+    ///
+    /// When several organs share the name, the first in update order is returned.
+    ///
+    /// # Arguments
+    /// * `type_name` - Organ type name, as reported by `Organ::get_type`
+    pub fn organ_by_name(&self, type_name: &str) -> Option<&dyn Organ> {
+        let index = *self.organ_map.get(type_name)?.first()?;
+        Some(self.organs.get(index)?.as_ref())
+    }
+
+    /// Get an organ mutably by type name, for organs chosen at run time
+    ///
+    /// # Arguments
+    /// * `type_name` - Organ type name, as reported by `Organ::get_type`
+    pub fn organ_by_name_mut(&mut self, type_name: &str) -> Option<&mut dyn Organ> {
+        let index = *self.organ_map.get(type_name)?.first()?;
+        Some(self.organs.get_mut(index)?.as_mut())
+    }
+
+    /// Add an organ, updated after the built-in organs
+    ///
+    /// Downstream crates can add organs (a prosthetic device, a tumor, a
+    /// custom gland) this way without changing `initialize_patient`. Several
+    /// organs may share a type; `get_organs` returns all of them.
+    ///
+    /// # Arguments
+    /// * `organ` - Organ to add; `next_organ_id` gives it an unused id
+    pub fn register_organ(&mut self, organ: Box<dyn Organ>) {
+        self.register_organ_with_priority(organ, DEFAULT_ORGAN_PRIORITY);
+    }
+
+    /// Add an organ at a position in the update order
+    ///
+    /// Organs update in ascending priority; organs of equal priority update in
+    /// the order they were added. The built-in organs have priorities 0, 10,
+    /// 20 and so on in the order `initialize_patient` creates them (see
+    /// `organ_priority`), so an organ can be placed between any two.
+    ///
+    /// # Arguments
+    /// * `organ` - Organ to add
+    /// * `priority` - Update priority; lower runs first
+    pub fn register_organ_with_priority(&mut self, organ: Box<dyn Organ>, priority: i32) {
+        let index = self.organ_priorities.partition_point(|&existing| existing <= priority);
+        self.organs.insert(index, organ);
+        self.organ_priorities.insert(index, priority);
+        self.organ_pending_s.insert(index, 0.0);
+        self.index_organs();
+    }
+
+    /// Update priority of an organ, the first of its type in update order
+    ///
+    /// # Arguments
+    /// * `type_name` - Organ type name
+    pub fn organ_priority(&self, type_name: &str) -> Option<i32> {
+        let index = *self.organ_map.get(type_name)?.first()?;
+        self.organ_priorities.get(index).copied()
+    }
+
+    /// Id not yet used by any organ, for a new organ
+    pub fn next_organ_id(&self) -> OrganId {
+        self.organs.iter().map(|organ| organ.get_id() + 1).max().unwrap_or(0)
+    }
+
+    /// Rebuild the organ lookup after the organs were reordered
+    fn index_organs(&mut self) {
+        self.organ_map.clear();
+        self.organ_types.clear();
+        for (index, organ) in self.organs.iter().enumerate() {
+            self.organ_map.entry(organ.get_type()).or_default().push(index);
+            self.organ_types.entry(organ.as_any().type_id()).or_default().push(index);
+        }
     }
 
     /// Get all organs
@@ -45,135 +353,1538 @@ impl Patient {
     pub fn organs_mut(&mut self) -> &mut [Box<dyn Organ>] {
         &mut self.organs
     }
-}
 
-/// Initialize a new patient with all organ systems
-///
-/// # Arguments
-/// * `patient_id` - Unique identifier for the patient
-/// * `num_heart_leads` - Number of EKG leads (3, 5, or 12)
-///
-/// # Returns
-/// A fully initialized Patient
-pub fn initialize_patient(patient_id: i32, num_heart_leads: usize) -> Patient {
-    let mut organs: Vec<Box<dyn Organ>> = Vec::new();
-    let mut organ_map = HashMap::new();
+    /// Simulated time since the patient was created (s)
+    pub fn sim_time(&self) -> f64 {
+        self.clock.elapsed_s
+    }
+
+    /// Place simulation time zero on the calendar
+    ///
+    /// # Arguments
+    /// * `start_unix_s` - Wall-clock time of simulation time zero (Unix seconds)
+    pub fn anchor_clock(&mut self, start_unix_s: i64) {
+        self.clock.start_unix_s = Some(start_unix_s);
+    }
+
+    /// Record a drug given now
+    fn record_dose(&mut self, medication: &'static str, amount: f64, units: &'static str) {
+        self.doses.push(MedicationDose {
+            time_s: self.clock.elapsed_s,
+            medication,
+            amount,
+            units,
+        });
+    }
+
+    /// Draw a specimen and order a laboratory panel
+    ///
+    /// # Returns
+    /// Order number; the report is available from `labs` once the panel's
+    /// turnaround time has passed
+    pub fn order_labs(&mut self, panel: LabPanel) -> u32 {
+        intervention!(patient = self.id, panel = ?panel, "lab order");
+        let mut labs = std::mem::take(&mut self.labs);
+        let id = labs.order(self, panel);
+        self.labs = labs;
+        id
+    }
+
+    /// Fork the patient into an independent timeline
+    ///
+    /// The fork carries the full state of every organ, process and patient-level
+    /// system; waveform buffers are shared until either timeline writes to them.
+    /// A seeded variability stream continues identically in both timelines
+    /// unless one is reseeded.
+    pub fn fork(&self) -> Patient {
+        self.clone()
+    }
+
+    /// Project the outcome of an intervention in a forked timeline
+    ///
+    /// The patient itself is left untouched.
+    ///
+    /// # Arguments
+    /// * `intervention` - Applied to the fork before it runs
+    /// * `duration_s` - Simulated time to project (s)
+    /// * `time_step_s` - Simulation time step (s)
+    ///
+    /// # Returns
+    /// The forked patient at the end of the projection
+    pub fn project(&self, intervention: impl FnOnce(&mut Patient), duration_s: f64, time_step_s: f64) -> Patient {
+        let mut fork = self.fork();
+        intervention(&mut fork);
+        let step = time_step_s.max(1e-3);
+        for _ in 0..(duration_s / step).round() as usize {
+            update_patient(&mut fork, step);
+        }
+        fork
+    }
+
+    /// Level of detail an organ is simulated at
+    ///
+    /// # Arguments
+    /// * `type_name` - Organ type name
+    ///
+    /// # Returns
+    /// The organ's own level if one is set, otherwise the patient's
+    pub fn organ_fidelity(&self, type_name: &str) -> Fidelity {
+        self.organ_fidelity.get(type_name).copied().unwrap_or(self.fidelity)
+    }
+
+    /// Change how often an organ is updated, mid-simulation
+    ///
+    /// The organ keeps its model and state at every level; only its update
+    /// interval and waveform sampling change. It is first brought up to date
+    /// with any time it has deferred at its old level.
+    ///
+    /// # Arguments
+    /// * `type_name` - Organ type name
+    /// * `fidelity` - New level of detail
+    pub fn set_organ_fidelity(&mut self, type_name: &'static str, fidelity: Fidelity) {
+        self.flush_organ(type_name);
+        self.organ_fidelity.insert(type_name, fidelity);
+    }
+
+    /// Return an organ to the patient's level of detail
+    ///
+    /// # Arguments
+    /// * `type_name` - Organ type name
+    pub fn clear_organ_fidelity(&mut self, type_name: &'static str) {
+        self.flush_organ(type_name);
+        self.organ_fidelity.remove(type_name);
+    }
+
+    /// Update every organ of a type over the time it has deferred at reduced fidelity
+    fn flush_organ(&mut self, type_name: &'static str) {
+        let Some(indices) = self.organ_map.get(type_name).cloned() else { return };
+        let mut organs = std::mem::take(&mut self.organs);
+        for index in indices {
+            let pending = std::mem::take(&mut self.organ_pending_s[index]);
+            if pending > 0.0 {
+                organs[index].update(self, pending);
+            }
+        }
+        self.organs = organs;
+    }
+
+    /// Shortest integration step of any organ, which the rest of the patient follows (s)
+    fn integration_step_s(&self) -> f64 {
+        self.organs
+            .iter()
+            .map(|organ| self.organ_fidelity(organ.get_type()).integration_step_s())
+            .fold(self.fidelity.integration_step_s(), f64::min)
+    }
+
+    /// Register a pathophysiology process to run each step
+    ///
+    /// # Arguments
+    /// * `process` - Process to add; it runs until it reports itself inactive
+    pub fn add_process(&mut self, process: Box<dyn Process>) {
+        self.processes.push(process);
+    }
+
+    /// Remove a registered process by name
+    ///
+    /// # Returns
+    /// The removed process, if one was registered under that name
+    pub fn remove_process(&mut self, name: &str) -> Option<Box<dyn Process>> {
+        let index = self.processes.iter().position(|process| process.get_name() == name)?;
+        Some(self.processes.remove(index))
+    }
+
+    /// Get a reference to a registered process by name
+    pub fn get_process<T: 'static>(&self, name: &str) -> Option<&T> {
+        self.processes
+            .iter()
+            .find(|process| process.get_name() == name)
+            .and_then(|process| process.as_any().downcast_ref::<T>())
+    }
+
+    /// Get a mutable reference to a registered process by name
+    pub fn get_process_mut<T: 'static>(&mut self, name: &str) -> Option<&mut T> {
+        self.processes
+            .iter_mut()
+            .find(|process| process.get_name() == name)
+            .and_then(|process| process.as_any_mut().downcast_mut::<T>())
+    }
+
+    /// Get all registered processes
+    pub fn processes(&self) -> &[Box<dyn Process>] {
+        &self.processes
+    }
+
+    /// Give fluid to the patient and record it in the I/O ledger
+    ///
+    /// # Arguments
+    /// * `route` - Route of administration
+    /// * `volume_ml` - Volume given in mL
+    pub fn give_fluid(&mut self, route: IntakeRoute, volume_ml: f64) {
+        intervention!(patient = self.id, route = ?route, volume_ml, "fluid");
+        let volume_ml = volume_ml.max(0.0);
+        if matches!(route, IntakeRoute::Oral | IntakeRoute::Enteral) {
+            if let Some(stomach) = self.get_organ_mut::<stomach::Stomach>() {
+                // Absorbed from the gut over the following half hour
+                stomach.ingest(stomach::Meal::water(volume_ml));
+                self.fluid_ledger.record_intake(route, volume_ml);
+                return;
+            }
+        }
+        if route == IntakeRoute::Intravenous {
+            if let Some(sepsis) = self.sepsis.as_mut() {
+                sepsis.bundle.record_fluid(volume_ml);
+            }
+        }
+        if route == IntakeRoute::BloodProduct {
+            // Packed red cells have a hematocrit of ~60%, glycated at the donor's HbA1c
+            let recipient_ml = self.fluids.red_cell_ml.max(0.0);
+            let donor_ml = volume_ml * 0.6;
+            if recipient_ml + donor_ml > 0.0 {
+                let cells = &mut self.blood.cells;
+                cells.hba1c_percent = (cells.hba1c_percent * recipient_ml + DONOR_HBA1C_PERCENT * donor_ml) / (recipient_ml + donor_ml);
+            }
+            self.fluids.add_blood(volume_ml, 0.6);
+        } else {
+            self.fluids.add_plasma(volume_ml);
+        }
+        self.fluid_ledger.record_intake(route, volume_ml);
+    }
+
+    /// Eat a meal and record the water it contains in the I/O ledger
+    ///
+    /// Without a stomach the meal is absorbed at once.
+    ///
+    /// # Arguments
+    /// * `meal` - Food and drink taken
+    pub fn eat(&mut self, meal: stomach::Meal) {
+        intervention!(patient = self.id, kcal = meal.energy_kcal(), water_ml = meal.water_ml, "meal");
+        self.fluid_ledger.record_intake(IntakeRoute::Oral, meal.water_ml);
+        match self.get_organ_mut::<stomach::Stomach>() {
+            Some(stomach) => stomach.ingest(meal),
+            None => absorb_meal(self, &meal),
+        }
+    }
+
+    /// Water swallowed but not yet absorbed from the gut (mL)
+    pub fn get_unabsorbed_water_ml(&self) -> f64 {
+        self.get_organ::<stomach::Stomach>().map_or(0.0, |s| s.chyme.nutrients.water_ml)
+            + self.get_organ::<intestines::Intestines>().map_or(0.0, |i| i.luminal_nutrients.water_ml)
+    }
+
+    /// Lose whole blood from the circulation and record it in the I/O ledger
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Volume of blood lost in mL
+    ///
+    /// # Returns
+    /// The volume actually removed (mL)
+    pub fn hemorrhage(&mut self, volume_ml: f64) -> f64 {
+        let removed = self.fluids.remove_blood(volume_ml);
+        self.fluid_ledger.record_output(OutputRoute::Blood, removed);
+        removed
+    }
+
+    /// Transfuse apheresis platelets
+    ///
+    /// # Arguments
+    /// * `units` - Number of units (one unit raises an adult's count by ~30-50 K/µL)
+    pub fn transfuse_platelets(&mut self, units: f64) {
+        intervention!(patient = self.id, units, "platelet transfusion");
+        let units = units.max(0.0);
+        let blood_volume = (self.fluids.plasma_ml + self.fluids.red_cell_ml).max(500.0);
+        self.blood.cells.platelet_count_thousand_per_ul += units * PLATELET_UNIT_INCREMENT_K_UL * 5000.0 / blood_volume;
+        // Platelets are suspended in donor plasma
+        self.transfuse_plasma_volume(units * PLATELET_UNIT_VOLUME_ML);
+    }
+
+    /// Transfuse fresh frozen plasma
+    ///
+    /// # Arguments
+    /// * `units` - Number of ~250 mL units (10-15 mL/kg corrects most factor deficits)
+    pub fn transfuse_plasma(&mut self, units: f64) {
+        intervention!(patient = self.id, units, "plasma transfusion");
+        self.transfuse_plasma_volume(units.max(0.0) * PLASMA_UNIT_VOLUME_ML);
+    }
+
+    /// Transfuse cryoprecipitate
+    ///
+    /// # Arguments
+    /// * `units` - Number of units (a 10-unit pool raises fibrinogen by ~70 mg/dL)
+    pub fn transfuse_cryoprecipitate(&mut self, units: f64) {
+        intervention!(patient = self.id, units, "cryoprecipitate transfusion");
+        let units = units.max(0.0);
+        self.coagulation.transfuse_cryoprecipitate(self.fluids.plasma_ml, units);
+        self.fluids.add_plasma(units * 15.0);
+        self.fluid_ledger.record_intake(IntakeRoute::BloodProduct, units * 15.0);
+    }
+
+    /// Give donor plasma carrying normal clotting factors
+    fn transfuse_plasma_volume(&mut self, volume_ml: f64) {
+        self.coagulation.transfuse_plasma(self.fluids.plasma_ml, volume_ml);
+        self.fluids.add_plasma(volume_ml);
+        self.fluid_ledger.record_intake(IntakeRoute::BloodProduct, volume_ml);
+    }
+
+    /// Start a bacterial infection that progresses to sepsis unless treated
+    ///
+    /// # Arguments
+    /// * `source` - Source of infection (e.g. "Pneumonia")
+    /// * `virulence` - Growth rate relative to a typical pathogen (1.0 = doubling every ~4 h)
+    /// * `guideline` - Guideline for the sepsis bundle timers
+    pub fn start_infection(&mut self, source: &str, virulence: f64, guideline: BundleGuideline) {
+        self.sepsis = Some(Sepsis::new(source, virulence, guideline));
+    }
+
+    /// Draw blood cultures
+    pub fn draw_blood_cultures(&mut self) {
+        intervention!(patient = self.id, "blood cultures");
+        if let Some(sepsis) = self.sepsis.as_mut() {
+            sepsis.draw_blood_cultures();
+        }
+    }
+
+    /// Measure the blood lactate
+    ///
+    /// # Returns
+    /// Lactate (mmol/L)
+    pub fn measure_lactate(&mut self) -> f64 {
+        intervention!(patient = self.id, "lactate measurement");
+        let lactate = self.blood.chemistry.lactate_mmol_l;
+        if let Some(sepsis) = self.sepsis.as_mut() {
+            let time = sepsis.elapsed_s;
+            sepsis.bundle.record_lactate(time, lactate);
+        }
+        lactate
+    }
+
+    /// Give broad-spectrum antibiotics
+    ///
+    /// # Arguments
+    /// * `covers_organism` - Whether the spectrum covers the pathogen
+    pub fn give_antibiotics(&mut self, covers_organism: bool) {
+        intervention!(patient = self.id, covers_organism, "antibiotics");
+        self.record_dose("Antibiotics", 1.0, "dose");
+        if let Some(sepsis) = self.sepsis.as_mut() {
+            sepsis.give_antibiotics(covers_organism);
+        }
+    }
+
+    /// Set the norepinephrine infusion rate
+    ///
+    /// # Arguments
+    /// * `mcg_kg_min` - Infusion rate in mcg/kg/min (typically 0.05-0.5; 0 to stop)
+    pub fn set_norepinephrine_infusion(&mut self, mcg_kg_min: f64) {
+        intervention!(patient = self.id, mcg_kg_min, "norepinephrine infusion");
+        let rate = mcg_kg_min.max(0.0);
+        self.record_dose("Norepinephrine", rate, "mcg/kg/min");
+        if let Some(heart) = self.get_organ_mut::<heart::Heart>() {
+            heart.norepinephrine_mcg_kg_min = rate;
+        }
+        if rate > 0.0 {
+            if let Some(sepsis) = self.sepsis.as_mut() {
+                let time = sepsis.elapsed_s;
+                sepsis.bundle.record_vasopressors(time);
+            }
+        }
+    }
+
+    /// Set the intravenous insulin infusion rate
+    ///
+    /// # Arguments
+    /// * `units_per_hr` - Infusion rate in units/hour (typically 1-10; 0 to stop)
+    pub fn set_insulin_infusion(&mut self, units_per_hr: f64) {
+        intervention!(patient = self.id, units_per_hr, "insulin infusion");
+        let rate = units_per_hr.max(0.0);
+        self.record_dose("Insulin", rate, "units/h");
+        if let Some(pancreas) = self.get_organ_mut::<pancreas::Pancreas>() {
+            pancreas.set_insulin_infusion(rate);
+        }
+    }
+
+    /// Set the nitroprusside infusion rate
+    ///
+    /// Afterload reduction: arteriolar dilation lets a failing ventricle eject more
+    /// and venodilation lowers its filling pressures.
+    ///
+    /// # Arguments
+    /// * `mcg_kg_min` - Infusion rate in mcg/kg/min (typically 0.3-3; 0 to stop)
+    pub fn set_nitroprusside_infusion(&mut self, mcg_kg_min: f64) {
+        intervention!(patient = self.id, mcg_kg_min, "nitroprusside infusion");
+        self.record_dose("Nitroprusside", mcg_kg_min.max(0.0), "mcg/kg/min");
+        if let Some(heart) = self.get_organ_mut::<heart::Heart>() {
+            heart.nitroprusside_mcg_kg_min = mcg_kg_min.max(0.0);
+        }
+    }
+
+    /// Set the intensity of muscular exercise
+    ///
+    /// Working muscle raises oxygen consumption, and the heart answers with a
+    /// faster rate, stronger contraction and venous return pumped by the muscles
+    /// into a vascular bed dilated by local metabolites.
+    ///
+    /// # Arguments
+    /// * `intensity` - Fraction of maximal exercise capacity (0.0 = rest, ~0.5 = moderate, 1.0 = maximal)
+    pub fn set_exercise_intensity(&mut self, intensity: f64) {
+        if let Some(heart) = self.get_organ_mut::<heart::Heart>() {
+            heart.exercise_intensity = intensity.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Set the depth of neuromuscular blockade (e.g. a rocuronium infusion)
+    ///
+    /// Paralysis lowers metabolic rate and CO2 production, and stops spontaneous
+    /// breathing: a paralysed patient needs a ventilator.
+    ///
+    /// # Arguments
+    /// * `level` - 0.0 = none, 1.0 = complete paralysis
+    pub fn set_neuromuscular_blockade(&mut self, level: f64) {
+        intervention!(patient = self.id, level, "neuromuscular blockade");
+        if let Some(nerves) = self.get_organ_mut::<nerves::Nerves>() {
+            nerves.set_neuromuscular_blockade(level);
+        }
+    }
+
+    /// Set the level of analgesia for chest wall pain (e.g. an epidural or
+    /// serratus anterior block for rib fractures)
+    ///
+    /// # Arguments
+    /// * `level` - 0.0 = none, 1.0 = complete relief
+    pub fn set_analgesia(&mut self, level: f64) {
+        intervention!(patient = self.id, level, "analgesia");
+        if let Some(lungs) = self.get_organ_mut::<lungs::Lungs>() {
+            lungs.analgesia = level.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Give an intravenous furosemide bolus
+    ///
+    /// # Arguments
+    /// * `mg` - Dose in mg (typically 40 mg, more in chronic users or renal impairment)
+    pub fn give_furosemide(&mut self, mg: f64) {
+        intervention!(patient = self.id, mg, "furosemide");
+        self.record_dose("Furosemide", mg, "mg");
+        if let Some(kidneys) = self.get_organ_mut::<kidneys::Kidneys>() {
+            kidneys.give_furosemide(mg);
+        }
+    }
+
+    /// Place an indwelling urinary catheter, or read its urometer if one is in place
+    ///
+    /// # Returns
+    /// Urine output over the last completed hour (mL/h), or the residual volume
+    /// drained on insertion; None if the patient has no bladder
+    pub fn catheterize_bladder(&mut self) -> Option<f64> {
+        intervention!(patient = self.id, "urinary catheter");
+        self.get_organ_mut::<bladder::Bladder>().map(|bladder| bladder.catheterize())
+    }
+
+    /// Give the patient established chronic heart failure
+    ///
+    /// Besides the heart's own phenotype, months of low renal perfusion leave the
+    /// patient with retained salt and water (up to ~3 L in an adult at full
+    /// severity), which is what raises the filling pressures into congestion. The
+    /// retained volume predates the admission, so it is not charted as intake.
+    ///
+    /// # Arguments
+    /// * `phenotype` - Reduced or preserved ejection fraction
+    /// * `severity` - Severity of the dysfunction (0.0-1.0)
+    pub fn induce_heart_failure(&mut self, phenotype: heart::HeartFailurePhenotype, severity: f64) {
+        let severity = severity.clamp(0.0, 1.0);
+        if let Some(heart) = self.get_organ_mut::<heart::Heart>() {
+            heart.induce_heart_failure(phenotype, severity);
+        }
+        let retained_ml = HEART_FAILURE_RETAINED_FRACTION * severity * self.fluids.extracellular_ml();
+        self.fluids.add_plasma(retained_ml);
+    }
+
+    /// Make repeated runs of a scenario differ plausibly
+    ///
+    /// Draws this patient's arrhythmia susceptibility, catecholamine sensitivity
+    /// and ischemic tolerance of the heart, brain territories and liver, and
+    /// makes individual treatment outcomes such as defibrillation probabilistic.
+    /// The same seed reproduces the same run; a magnitude of 0.0 restores the
+    /// deterministic model.
+    ///
+    /// # Arguments
+    /// * `seed` - Random seed
+    /// * `magnitude` - Scale of variation (0.0 = none, 1.0 = typical, up to 2.0)
+    pub fn set_outcome_variability(&mut self, seed: u64, magnitude: f64) {
+        let mut variability = Variability::seeded(seed, magnitude);
+        if let Some(heart) = self.get_organ_mut::<heart::Heart>() {
+            heart.arrhythmia_susceptibility = variability.factor(ARRHYTHMIA_SUSCEPTIBILITY_CV);
+            heart.adrenergic_sensitivity = variability.factor(ADRENERGIC_SENSITIVITY_CV);
+            heart.ischemic_tolerance_factor = variability.factor(ISCHEMIC_TOLERANCE_CV);
+        }
+        if let Some(brain) = self.get_organ_mut::<brain::Brain>() {
+            for region in brain.regions_mut() {
+                for territory in region.left_territories.iter_mut().chain(region.right_territories.iter_mut()) {
+                    territory.tissue.tolerance_factor = variability.factor(ISCHEMIC_TOLERANCE_CV);
+                }
+            }
+        }
+        if let Some(liver) = self.get_organ_mut::<liver::Liver>() {
+            let tolerance = variability.factor(ISCHEMIC_TOLERANCE_CV);
+            for zone in liver.zones.iter_mut() {
+                zone.tolerance_factor = tolerance;
+            }
+        }
+        self.variability = variability;
+    }
+
+    /// Start an ACLS assistant that follows the code timeline
+    pub fn start_acls_assistant(&mut self) {
+        self.acls = Some(AclsAssistant::new());
+    }
+
+    /// Start chest compressions
+    pub fn start_cpr(&mut self) {
+        intervention!(patient = self.id, "cpr started");
+        if let Some(heart) = self.get_organ_mut::<heart::Heart>() {
+            heart.cpr_in_progress = true;
+        }
+        if let Some(acls) = self.acls.as_mut() {
+            acls.record_cpr_started();
+        }
+    }
+
+    /// Stop chest compressions
+    pub fn stop_cpr(&mut self) {
+        intervention!(patient = self.id, "cpr stopped");
+        if let Some(heart) = self.get_organ_mut::<heart::Heart>() {
+            heart.cpr_in_progress = false;
+        }
+        if let Some(acls) = self.acls.as_mut() {
+            acls.record_cpr_stopped();
+        }
+    }
+
+    /// Pause for a rhythm check
+    ///
+    /// # Returns
+    /// The rhythm on the monitor, or None if the patient has no heart
+    pub fn check_rhythm(&mut self) -> Option<heart::Rhythm> {
+        let rhythm = self.get_organ::<heart::Heart>()?.rhythm;
+        if let Some(acls) = self.acls.as_mut() {
+            acls.record_rhythm_check(rhythm);
+        }
+        Some(rhythm)
+    }
+
+    /// Deliver a biphasic defibrillation shock
+    ///
+    /// # Arguments
+    /// * `energy_j` - Shock energy in joules
+    ///
+    /// # Returns
+    /// Whether the shock terminated the arrhythmia
+    pub fn defibrillate(&mut self, energy_j: f64) -> bool {
+        // With variability a shock near the threshold converts only some of the time
+        let mut variability = std::mem::take(&mut self.variability);
+        let bonus = SHOCK_RESPONSIVENESS_MARGIN * (self.difficulty.treatment_responsiveness - 1.0);
+        let converted = self.get_organ_mut::<heart::Heart>().is_some_and(|heart| {
+            heart.defibrillate_with(energy_j, |margin| variability.succeeds(margin + bonus, SHOCK_OUTCOME_SPREAD))
+        });
+        self.variability = variability;
+        if let Some(acls) = self.acls.as_mut() {
+            acls.record_shock(energy_j, converted);
+        }
+        intervention!(patient = self.id, energy_j, converted, "defibrillation");
+        converted
+    }
+
+    /// Extubate a ventilated patient
+    ///
+    /// # Returns
+    /// The complications that followed, or None if the patient was not intubated
+    /// or is ventilated through a tracheostomy
+    pub fn extubate(&mut self) -> Option<lungs::Extubation> {
+        // With variability stridor and reintubation for secretions happen only some of the time
+        let mut variability = std::mem::take(&mut self.variability);
+        let extubation = self
+            .get_organ_mut::<lungs::Lungs>()
+            .filter(|lungs| lungs.ventilator.is_some() && lungs.tracheostomy.is_none())
+            .map(|lungs| lungs.extubate_with(|probability| variability.occurs(probability)));
+        self.variability = variability;
+        intervention!(patient = self.id, ?extubation, "extubation");
+        extubation
+    }
+
+    /// Attempt intubation and start mechanical ventilation
+    ///
+    /// # Arguments
+    /// * `ventilator` - Ventilator settings once the tube is in
+    ///
+    /// # Returns
+    /// True if the tube was placed; with variability an attempt in a difficult airway can fail
+    pub fn intubate(&mut self, ventilator: lungs::Ventilator) -> bool {
+        let mut variability = std::mem::take(&mut self.variability);
+        let intubated = self
+            .get_organ_mut::<lungs::Lungs>()
+            .is_some_and(|lungs| lungs.intubate_with(ventilator, |probability| variability.occurs(probability)));
+        self.variability = variability;
+        intervention!(patient = self.id, intubated, "intubation");
+        intubated
+    }
 
-    // Create all organs
-    let heart = Box::new(heart::Heart::new(0, num_heart_leads));
-    organ_map.insert("Heart", organs.len());
-    organs.push(heart);
+    /// Give an intravenous epinephrine bolus
+    ///
+    /// # Arguments
+    /// * `mg` - Dose in mg (1 mg in cardiac arrest)
+    pub fn give_epinephrine(&mut self, mg: f64) {
+        intervention!(patient = self.id, mg, "epinephrine");
+        self.record_dose("Epinephrine", mg, "mg");
+        if let Some(heart) = self.get_organ_mut::<heart::Heart>() {
+            heart.give_epinephrine(mg);
+        }
+        if let Some(acls) = self.acls.as_mut() {
+            acls.record_epinephrine();
+        }
+    }
 
-    let lungs = Box::new(lungs::Lungs::new(1));
-    organ_map.insert("Lungs", organs.len());
-    organs.push(lungs);
+    /// Give an intravenous amiodarone bolus
+    ///
+    /// # Arguments
+    /// * `mg` - Dose in mg (300 mg, then 150 mg in refractory VF/pVT)
+    pub fn give_amiodarone(&mut self, mg: f64) {
+        intervention!(patient = self.id, mg, "amiodarone");
+        self.record_dose("Amiodarone", mg, "mg");
+        if let Some(heart) = self.get_organ_mut::<heart::Heart>() {
+            heart.give_amiodarone(mg);
+        }
+        if let Some(acls) = self.acls.as_mut() {
+            acls.record_amiodarone(mg);
+        }
+    }
 
-    let brain = Box::new(brain::Brain::new(2));
-    organ_map.insert("Brain", organs.len());
-    organs.push(brain);
+    /// Reversible causes of arrest actually present in the model, most severe first
+    ///
+    /// Ground truth for scenario engines checking what the learner found.
+    pub fn reversible_causes(&self) -> Vec<CauseFinding> {
+        acls::find_reversible_causes(self)
+    }
 
-    let spinal_cord = Box::new(spinal_cord::SpinalCord::new(3));
-    organ_map.insert("SpinalCord", organs.len());
-    organs.push(spinal_cord);
+    /// Drain a pericardial effusion with a needle
+    ///
+    /// # Arguments
+    /// * `volume_ml` - Volume aspirated (mL)
+    ///
+    /// # Returns
+    /// Volume actually removed (mL)
+    pub fn pericardiocentesis(&mut self, volume_ml: f64) -> f64 {
+        let removed = self
+            .get_organ_mut::<heart::Heart>()
+            .map_or(0.0, |heart| heart.pericardiocentesis(volume_ml));
+        if removed > 0.0 {
+            self.fluid_ledger.record_output(OutputRoute::Drain, removed);
+        }
+        intervention!(patient = self.id, volume_ml, removed, "pericardiocentesis");
+        self.address_reversible_cause(ReversibleCause::Tamponade);
+        removed
+    }
 
-    let stomach = Box::new(stomach::Stomach::new(4));
-    organ_map.insert("Stomach", organs.len());
-    organs.push(stomach);
+    /// Remove the gallbladder
+    ///
+    /// # Returns
+    /// False if there was no gallbladder to remove
+    pub fn cholecystectomy(&mut self) -> bool {
+        intervention!(patient = self.id, "cholecystectomy");
+        self.get_organ_mut::<gallbladder::Gallbladder>()
+            .filter(|gallbladder| !gallbladder.removed)
+            .map(|gallbladder| gallbladder.cholecystectomy())
+            .is_some()
+    }
 
-    let esophagus = Box::new(esophagus::Esophagus::new(5));
-    organ_map.insert("Esophagus", organs.len());
-    organs.push(esophagus);
+    /// Remove the spleen (for rupture, or hypersplenism)
+    ///
+    /// # Returns
+    /// False if there was no spleen to remove
+    pub fn splenectomy(&mut self) -> bool {
+        intervention!(patient = self.id, "splenectomy");
+        self.get_organ_mut::<spleen::Spleen>()
+            .filter(|spleen| !spleen.removed)
+            .map(|spleen| spleen.splenectomy())
+            .is_some()
+    }
 
-    let intestines = Box::new(intestines::Intestines::new(6));
-    organ_map.insert("Intestines", organs.len());
-    organs.push(intestines);
+    /// Record that a reversible cause of arrest was treated
+    ///
+    /// The treatment itself (needle decompression, fluids, rewarming, ...) is
+    /// applied through the relevant organ or patient methods; this logs it
+    /// against the Hs and Ts checklist.
+    ///
+    /// # Arguments
+    /// * `cause` - The cause addressed
+    pub fn address_reversible_cause(&mut self, cause: ReversibleCause) {
+        intervention!(patient = self.id, cause = ?cause, "reversible cause addressed");
+        let present = cause.is_present(self);
+        if let Some(acls) = self.acls.as_mut() {
+            acls.record_cause_addressed(cause, present);
+        }
+    }
 
-    let pancreas = Box::new(pancreas::Pancreas::new(7));
-    organ_map.insert("Pancreas", organs.len());
-    organs.push(pancreas);
+    /// Make the patient pregnant
+    ///
+    /// Plasma and red cell volume expand to the level expected for the
+    /// gestational age, which becomes the patient's new euvolemic reference.
+    ///
+    /// # Arguments
+    /// * `gestational_age_weeks` - Gestational age in weeks
+    pub fn start_pregnancy(&mut self, gestational_age_weeks: f64) {
+        let pregnancy = Pregnancy::new(gestational_age_weeks);
+        let (plasma_ml, red_cell_ml) = Pregnancy::blood_volume_expansion_ml(pregnancy.gestational_age_weeks);
+        self.fluids.plasma_ml += plasma_ml;
+        self.fluids.red_cell_ml += red_cell_ml;
+        self.fluids.reference_blood_volume_ml += plasma_ml + red_cell_ml;
+        self.pregnancy = Some(pregnancy);
+    }
 
-    let liver = Box::new(liver::Liver::new(8));
-    organ_map.insert("Liver", organs.len());
-    organs.push(liver);
+    /// Start menstrual cycles on the first day of menses
+    ///
+    /// Cycles pause during pregnancy and stop at menopause.
+    ///
+    /// # Arguments
+    /// * `cycle_length_days` - Cycle length in days (normally 21-35)
+    pub fn start_menstrual_cycle(&mut self, cycle_length_days: f64) {
+        self.menstrual_cycle = Some(MenstrualCycle::new(cycle_length_days));
+    }
 
-    let gallbladder = Box::new(gallbladder::Gallbladder::new(9));
-    organ_map.insert("Gallbladder", organs.len());
-    organs.push(gallbladder);
+    /// Remove fluid from the patient and record it in the I/O ledger
+    ///
+    /// # Arguments
+    /// * `route` - Route of loss
+    /// * `volume_ml` - Volume lost in mL
+    ///
+    /// # Returns
+    /// The volume actually removed (mL)
+    pub fn lose_fluid(&mut self, route: OutputRoute, volume_ml: f64) -> f64 {
+        let removed = self.fluids.remove_plasma(volume_ml);
+        self.fluid_ledger.record_output(route, removed);
+        removed
+    }
 
-    let kidneys = Box::new(kidneys::Kidneys::new(10));
-    organ_map.insert("Kidneys", organs.len());
-    organs.push(kidneys);
+    /// Drain gastric contents through a nasogastric tube and record the output
+    ///
+    /// # Arguments
+    /// * `max_volume_ml` - Maximum volume to aspirate
+    ///
+    /// # Returns
+    /// The aspirated output, or None if the patient has no stomach
+    pub fn aspirate_gastric(&mut self, max_volume_ml: f64) -> Option<stomach::GastricOutput> {
+        let output = self.get_organ_mut::<stomach::Stomach>()?.aspirate(max_volume_ml);
+        let water = self.fluids.remove_plasma(output.volume_ml - output.blood_ml - output.ingested_ml);
+        self.fluid_ledger.record_output(OutputRoute::Drain, water + output.blood_ml + output.ingested_ml);
+        lose_gastric_acid(self, &output);
+        Some(output)
+    }
 
-    let bladder = Box::new(bladder::Bladder::new(11));
-    organ_map.insert("Bladder", organs.len());
-    organs.push(bladder);
+    /// Get body weight derived from the fluid compartment model (kg)
+    pub fn get_weight_kg(&self) -> f64 {
+        self.fluids.body_weight_kg()
+    }
 
-    let spleen = Box::new(spleen::Spleen::new(12));
-    organ_map.insert("Spleen", organs.len());
-    organs.push(spleen);
+    /// Get body surface area by the DuBois formula (m²)
+    pub fn get_body_surface_area_m2(&self) -> f64 {
+        crate::calculate_bsa(self.get_weight_kg(), self.demographics.height_cm)
+    }
 
-    let bones = Box::new(bones::Bones::new(13));
-    organ_map.insert("Bones", organs.len());
-    organs.push(bones);
+    /// Get ideal body weight for the patient's height and sex (kg)
+    pub fn get_ideal_body_weight_kg(&self) -> f64 {
+        crate::calculate_ibw(self.demographics.height_cm, self.demographics.sex)
+    }
 
-    let nerves = Box::new(nerves::Nerves::new(14));
-    organ_map.insert("Nerves", organs.len());
-    organs.push(nerves);
+    /// Get adjusted body weight for dosing (kg)
+    pub fn get_adjusted_body_weight_kg(&self) -> f64 {
+        crate::calculate_adjusted_body_weight(self.get_weight_kg(), self.get_ideal_body_weight_kg())
+    }
 
-    let vascular = Box::new(vascular::VascularSystem::new(15));
-    organ_map.insert("VascularSystem", organs.len());
-    organs.push(vascular);
+    /// Get cardiac output per body surface area (L/min/m²)
+    ///
+    /// # Returns
+    /// The cardiac index, or None if the patient has no heart
+    pub fn get_cardiac_index(&self) -> Option<f64> {
+        let heart = self.get_organ::<heart::Heart>()?;
+        Some(heart.cardiac_output_l_min / self.get_body_surface_area_m2().max(0.1))
+    }
 
-    Patient {
+    /// Get stroke volume per body surface area (mL/m²)
+    ///
+    /// # Returns
+    /// The stroke volume index, or None if the patient has no heart
+    pub fn get_stroke_volume_index(&self) -> Option<f64> {
+        let heart = self.get_organ::<heart::Heart>()?;
+        Some(heart.stroke_volume_ml / self.get_body_surface_area_m2().max(0.1))
+    }
+
+    /// Get the current intake/output report
+    pub fn get_io_report(&self) -> IoReport {
+        self.fluid_ledger.get_io_report(self.get_weight_kg())
+    }
+
+    /// Measure gas exchange with a metabolic cart (indirect calorimetry)
+    ///
+    /// The cart reads the CO2 the lungs exhale, so hyperventilation washing out
+    /// the CO2 stores raises the measured RQ above the tissues' until PaCO2
+    /// settles, as it does at the bedside.
+    pub fn metabolic_cart(&self) -> Calorimetry {
+        let vco2 = self
+            .get_organ::<lungs::Lungs>()
+            .map_or(self.oxygen_transport.co2_production_ml_min, |l| l.co2_elimination_ml_min);
+        Calorimetry::from_gas_exchange(self.oxygen_transport.consumption_ml_min, vco2)
+    }
+
+    /// Give a drug whose serum level is monitored
+    ///
+    /// # Arguments
+    /// * `drug` - Drug given
+    /// * `mg` - Dose in mg (lithium as lithium carbonate); an overdose is a large oral dose
+    /// * `route` - Route of administration
+    pub fn give_drug(&mut self, drug: Drug, mg: f64, route: IntakeRoute) {
+        intervention!(patient = self.id, drug = drug.name(), mg, route = ?route, "drug");
+        self.record_dose(drug.name(), mg, "mg");
+        self.toxicology.give(drug, mg, route);
+    }
+
+    /// Collect urine for a point-of-care drug screen
+    pub fn urine_drug_screen(&self) -> ToxScreen {
+        intervention!(patient = self.id, "urine drug screen");
+        self.toxicology.screen()
+    }
+}
+
+/// Initialize a new patient with all organ systems
+///
+/// # Arguments
+/// * `patient_id` - Unique identifier for the patient
+/// * `num_heart_leads` - Number of EKG leads (3, 5, or 12)
+///
+/// # Returns
+/// A fully initialized Patient
+pub fn initialize_patient(patient_id: i32, num_heart_leads: usize) -> Patient {
+    // Create all organs, in update order
+    let organs: Vec<Box<dyn Organ>> = vec![
+        Box::new(heart::Heart::new(0, num_heart_leads)),
+        Box::new(lungs::Lungs::new(1)),
+        Box::new(brain::Brain::new(2)),
+        Box::new(spinal_cord::SpinalCord::new(3)),
+        Box::new(stomach::Stomach::new(4)),
+        Box::new(esophagus::Esophagus::new(5)),
+        Box::new(intestines::Intestines::new(6)),
+        Box::new(pancreas::Pancreas::new(7)),
+        Box::new(liver::Liver::new(8)),
+        Box::new(gallbladder::Gallbladder::new(9)),
+        Box::new(kidneys::Kidneys::new(10)),
+        Box::new(bladder::Bladder::new(11)),
+        Box::new(spleen::Spleen::new(12)),
+        Box::new(bones::Bones::new(13)),
+        Box::new(nerves::Nerves::new(14)),
+        Box::new(vascular::VascularSystem::new(15)),
+        Box::new(skin::Skin::new(16)),
+        Box::new(muscle::Muscle::new(17)),
+    ];
+    let organ_priorities = (0..organs.len() as i32).map(|index| index * BUILT_IN_ORGAN_PRIORITY_STEP).collect();
+
+    let mut patient = Patient {
         id: patient_id,
+        demographics: Demographics::default(),
         blood: BloodComposition::default(),
+        fluids: FluidCompartments::default(),
+        fluid_ledger: FluidLedger::default(),
+        iron: IronStores::default(),
+        coagulation: Coagulation::default(),
+        acid_base: AcidBase::default(),
+        oxygen_transport: OxygenTransport::default(),
+        signals: SignalBus::default(),
+        toxicology: Toxicology::default(),
+        wounds: Vec::new(),
+        sepsis: None,
+        acls: None,
+        menstrual_cycle: None,
+        pregnancy: None,
+        newborn: None,
+        growth: None,
+        variability: Variability::default(),
+        difficulty: Difficulty::default(),
+        clock: SimulationClock::new(),
+        doses: Vec::new(),
+        labs: LabService::default(),
+        stability: Stabilizer::default(),
+        fidelity: Fidelity::default(),
+        #[cfg(feature = "telemetry")]
+        telemetry: Telemetry::default(),
+        pending_time_s: 0.0,
+        organ_fidelity: HashMap::new(),
+        organ_pending_s: vec![0.0; organs.len()],
         organs,
-        organ_map,
+        organ_priorities,
+        processes: Vec::new(),
+        organ_map: HashMap::new(),
+        organ_types: HashMap::new(),
+    };
+    patient.index_organs();
+    patient
+}
+
+/// Initialize a child of the given age
+///
+/// Height and weight are set from the growth reference for the requested
+/// percentiles, organ baselines are set for age and size, and growth is tracked
+/// as simulated time passes.
+///
+/// # Arguments
+/// * `patient_id` - Unique identifier for the patient
+/// * `num_heart_leads` - Number of EKG leads (3, 5, or 12)
+/// * `sex` - Biological sex
+/// * `age_years` - Age in years
+/// * `height_percentile` - Height-for-age percentile (0-100)
+/// * `weight_percentile` - Weight-for-age percentile (0-100)
+///
+/// # Returns
+/// A pediatric Patient
+pub fn initialize_pediatric_patient(
+    patient_id: i32,
+    num_heart_leads: usize,
+    sex: Sex,
+    age_years: f64,
+    height_percentile: f64,
+    weight_percentile: f64,
+) -> Patient {
+    let mut patient = initialize_patient(patient_id, num_heart_leads);
+    let height_cm = growth::height_for_percentile(sex, age_years, height_percentile);
+    let weight_kg = growth::weight_for_percentile(sex, age_years, weight_percentile);
+
+    patient.demographics = Demographics::new(sex, age_years, height_cm);
+    patient.fluids = FluidCompartments::new(weight_kg);
+    patient.fluid_ledger = FluidLedger::new(weight_kg);
+    patient.iron = IronStores::new(weight_kg * CHILD_IRON_STORES_MG_PER_KG);
+    growth::apply_age_baselines(&mut patient);
+    patient.growth = Some(Growth::new(&patient.demographics, weight_kg));
+    if sex == Sex::Female && age_years >= menstrual::MENARCHE_AGE_YEARS {
+        patient.start_menstrual_cycle(28.0);
     }
+    patient
+}
+
+/// Initialize a newborn at the moment of birth
+///
+/// Organ baselines are scaled to the baby's size (heart rate ~140 bpm, MAP
+/// roughly equal to gestational age, respiratory rate ~40) and the circulation
+/// starts in the fetal configuration. The baby grows as simulated time passes.
+///
+/// # Arguments
+/// * `patient_id` - Unique identifier for the patient
+/// * `num_heart_leads` - Number of EKG leads (3, 5, or 12)
+/// * `sex` - Biological sex
+/// * `gestational_age_weeks` - Gestational age at birth in weeks
+/// * `birth_weight_kg` - Birth weight in kilograms
+///
+/// # Returns
+/// A newborn Patient
+pub fn initialize_newborn(
+    patient_id: i32,
+    num_heart_leads: usize,
+    sex: Sex,
+    gestational_age_weeks: f64,
+    birth_weight_kg: f64,
+) -> Patient {
+    let mut patient = initialize_patient(patient_id, num_heart_leads);
+    let newborn = Newborn::new(gestational_age_weeks, birth_weight_kg);
+    let weight_kg = newborn.birth_weight_kg;
+
+    // Newborn hematocrit is ~55%
+    let mut fluids = FluidCompartments::new(weight_kg);
+    let extra_red_cell_ml = fluids.plasma_ml * 0.55 / 0.45 - fluids.red_cell_ml;
+    fluids.red_cell_ml += extra_red_cell_ml;
+    fluids.dry_mass_kg -= extra_red_cell_ml / 1000.0;
+    fluids.reference_blood_volume_ml += extra_red_cell_ml;
+    let hematocrit = fluids.hematocrit();
+    patient.fluids = fluids;
+    patient.fluid_ledger = FluidLedger::new(weight_kg);
+    patient.iron = IronStores::new(weight_kg * CHILD_IRON_STORES_MG_PER_KG);
+    if let Some(vascular) = patient.get_organ_mut::<vascular::VascularSystem>() {
+        vascular.set_reference_hematocrit(hematocrit);
+    }
+
+    // Length scales with birth weight along the same percentile channel
+    let weight_percentile = growth::weight_percentile(sex, 0.0, weight_kg);
+    let length_cm = growth::height_for_percentile(sex, 0.0, weight_percentile);
+    patient.demographics = Demographics::new(sex, 0.0, length_cm);
+    growth::apply_age_baselines(&mut patient);
+    if let Some(heart) = patient.get_organ_mut::<heart::Heart>() {
+        // Adult MAP ~98 mmHg; a newborn's MAP is roughly its gestational age
+        heart.systemic_pressure_scale = (newborn.gestational_age_weeks + 5.0) / 98.0;
+    }
+    if let Some(brain) = patient.get_organ_mut::<brain::Brain>() {
+        brain.pressure_scale = (newborn.gestational_age_weeks + 5.0) / 98.0;
+        brain.baroreflex_set_point_mmhg = brain::NORMAL_BAROREFLEX_SET_POINT_MMHG * brain.pressure_scale;
+    }
+
+    patient.growth = Some(Growth::new(&patient.demographics, weight_kg));
+    patient.newborn = Some(newborn);
+    patient
 }
 
 /// Update patient state and all organ systems
 ///
+/// At reduced fidelity the time accumulates and the organs are updated once a
+/// whole coarse step is due; the rest of the patient follows the most detailed organ.
+/// Steps longer than the fidelity's largest step are split into equal substeps,
+/// so one long step gives the same result as many short ones (see
+/// [`MAX_INTEGRATION_STEP_S`]). Negative or non-finite steps are ignored.
+///
 /// # Arguments
 /// * `patient` - Mutable reference to the patient
 /// * `delta_time_s` - Time step in seconds
 pub fn update_patient(patient: &mut Patient, delta_time_s: f64) {
+    if !delta_time_s.is_finite() || delta_time_s < 0.0 {
+        return;
+    }
+    patient.pending_time_s += delta_time_s;
+    let integration_step_s = patient.integration_step_s();
+    if patient.pending_time_s + 1e-9 < integration_step_s {
+        return;
+    }
+    let delta_time_s = std::mem::take(&mut patient.pending_time_s);
+    #[cfg(feature = "telemetry")]
+    let tick_probe = Probe::start();
+    #[cfg(feature = "tracing")]
+    let _tick_span =
+        tracing::debug_span!("update_patient", patient = patient.id, time_s = patient.sim_time(), delta_time_s).entered();
+    let max_step_s = integration_step_s.max(MAX_INTEGRATION_STEP_S);
+    let substeps = (delta_time_s / max_step_s - 1e-9).ceil().max(1.0);
+    for _ in 0..substeps as usize {
+        step_patient(patient, delta_time_s / substeps);
+    }
+    #[cfg(feature = "telemetry")]
+    patient.telemetry.record_tick(tick_probe);
+}
+
+/// Advance the patient by one integration step
+///
+/// # Arguments
+/// * `patient` - Mutable reference to the patient
+/// * `delta_time_s` - Time step in seconds, at most the largest integration step
+fn step_patient(patient: &mut Patient, delta_time_s: f64) {
+    patient.clock.advance(delta_time_s);
+    #[cfg(feature = "tracing")]
+    let rhythm = patient.get_organ::<heart::Heart>().map(|h| h.rhythm);
+
     // Update all organs
-    for i in 0..patient.organs.len() {
-        // Split the borrows to allow organ to access patient
-        let (_left, right) = patient.organs.split_at_mut(i);
-        if let Some((organ, _)) = right.split_first_mut() {
-            let mut temp_patient = Patient {
-                id: patient.id,
-                blood: patient.blood.clone(),
-                organs: Vec::new(),
-                organ_map: HashMap::new(),
-            };
-            organ.update(&mut temp_patient, delta_time_s);
-            patient.blood = temp_patient.blood;
+    // Organs are moved out while updating so each one can access the patient;
+    // organs at reduced fidelity wait until a whole coarse step is due
+    let mut organs = std::mem::take(&mut patient.organs);
+    for (index, organ) in organs.iter_mut().enumerate() {
+        let type_name = organ.get_type();
+        let organ_integration_step_s = patient.organ_fidelity(type_name).integration_step_s();
+        let pending = &mut patient.organ_pending_s[index];
+        *pending += delta_time_s;
+        if *pending + 1e-9 < organ_integration_step_s {
+            continue;
+        }
+        let organ_step_s = std::mem::take(pending);
+        #[cfg(feature = "telemetry")]
+        let probe = Probe::start();
+        #[cfg(feature = "tracing")]
+        let _organ_span = tracing::trace_span!("organ", organ = type_name, step_s = organ_step_s).entered();
+        organ.update(patient, organ_step_s);
+        #[cfg(feature = "telemetry")]
+        patient.telemetry.record_organ(type_name, probe);
+    }
+    patient.organs = organs;
+    #[cfg(feature = "tracing")]
+    if let Some(heart) = patient.get_organ::<heart::Heart>() {
+        if rhythm.is_some_and(|rhythm| rhythm != heart.rhythm) {
+            emergent!(info, patient = patient.id, from = ?rhythm, to = ?heart.rhythm, "rhythm changed");
+        }
+    }
+
+    // Pregnancy and the newborn transition act on the organs between their updates
+    if let Some(mut pregnancy) = patient.pregnancy.take() {
+        pregnancy.update(patient, delta_time_s);
+        patient.pregnancy = Some(pregnancy);
+    }
+    if let Some(mut newborn) = patient.newborn.take() {
+        newborn.update(patient, delta_time_s);
+        patient.newborn = Some(newborn);
+    }
+
+    patient.demographics.age_years += delta_time_s / growth::SECONDS_PER_YEAR;
+    if let Some(mut growth) = patient.growth.take() {
+        growth.update(patient, delta_time_s);
+        patient.growth = Some(growth);
+    }
+
+    // Cycles pause during pregnancy and end at menopause
+    if patient.demographics.age_years >= menstrual::MENOPAUSE_AGE_YEARS {
+        patient.menstrual_cycle = None;
+    }
+    if patient.pregnancy.is_none() {
+        if let Some(mut cycle) = patient.menstrual_cycle.take() {
+            cycle.update(patient, delta_time_s);
+            patient.menstrual_cycle = Some(cycle);
         }
     }
+    patient.iron.update(&mut patient.blood.chemistry, delta_time_s);
+
+    // Drugs are cleared by the kidneys and liver; a benzodiazepine given for
+    // seizures shows on the urine screen like an ingested one
+    let renal_function = patient
+        .get_organ::<kidneys::Kidneys>()
+        .map_or(1.0, |k| k.gfr_ml_per_min / k.baseline_gfr_ml_per_min);
+    let hepatic_function = patient.get_organ::<liver::Liver>().map_or(1.0, |l| l.functional_capacity());
+    if patient.get_organ::<brain::Brain>().is_some_and(|b| b.benzodiazepine_mg > 0.1) {
+        patient.toxicology.expose(ScreenClass::Benzodiazepines);
+    }
+    let (weight_kg, ideal_weight_kg) = (patient.get_weight_kg(), patient.get_ideal_body_weight_kg());
+    patient.toxicology.update(weight_kg, ideal_weight_kg, renal_function, hepatic_function, delta_time_s);
+
+    // Infection drives the septic host response
+    if let Some(mut sepsis) = patient.sepsis.take() {
+        sepsis.update(patient, delta_time_s);
+        patient.sepsis = Some(sepsis);
+    }
+    // Pancreatitis provokes the same cytokine response without infection, and
+    // when severe injures the alveolar membrane; cholecystitis and burns provoke a milder one
+    let pancreatic_inflammation = patient.get_organ::<pancreas::Pancreas>().map_or(0.0, |p| p.systemic_inflammation());
+    let biliary_inflammation = patient.get_organ::<gallbladder::Gallbladder>().map_or(0.0, |g| g.systemic_inflammation());
+    let burn_inflammation = patient.get_organ::<skin::Skin>().map_or(0.0, |s| s.systemic_inflammation());
+    let infection = patient.sepsis.as_ref().map_or(0.0, |s| s.severity);
+    patient.signals.publish(
+        Signal::Cytokines,
+        infection.max(pancreatic_inflammation).max(biliary_inflammation).max(burn_inflammation),
+    );
+    if pancreatic_inflammation > PANCREATIC_ARDS_INFLAMMATION {
+        if let Some(lungs) = patient.get_organ_mut::<lungs::Lungs>() {
+            let severity = (pancreatic_inflammation - PANCREATIC_ARDS_INFLAMMATION) / (1.0 - PANCREATIC_ARDS_INFLAMMATION);
+            lungs.induce_ards(severity);
+        }
+    }
+
+    // Registered processes are moved out while updating, like the organs; any a
+    // process registers during its update run from the next step
+    let mut processes = std::mem::take(&mut patient.processes);
+    for process in processes.iter_mut() {
+        process.update(patient, delta_time_s);
+    }
+    processes.retain(|process| {
+        let active = process.is_active();
+        if !active {
+            emergent!(info, patient = patient.id, process = process.get_name(), "process finished");
+        }
+        active
+    });
+    processes.append(&mut patient.processes);
+    patient.processes = processes;
+
+    // The liver makes clotting factors; necrotic tissue releases tissue factor
+    if let Some((synthesis, necrosis)) =
+        patient.get_organ::<liver::Liver>().map(|l| (l.functional_capacity(), l.necrosis()))
+    {
+        patient.coagulation.hepatic_synthesis = synthesis;
+        patient.coagulation.tissue_necrosis = necrosis;
+    }
+    patient.coagulation.update(&mut patient.blood, &patient.fluids, delta_time_s);
+    let inr = patient.blood.clotting.inr;
+    if let Some(liver) = patient.get_organ_mut::<liver::Liver>() {
+        liver.inr = inr;
+    }
+
+    // Wounds bleed until a clot seals them
+    let blood = &patient.blood;
+    let wound_loss: f64 = patient.wounds.iter_mut().map(|wound| wound.update(blood, delta_time_s)).sum();
+    if wound_loss > 0.0 {
+        patient.hemorrhage(wound_loss);
+    }
+
+    // pH follows PaCO2 from the lungs and the metabolic bicarbonate; ventilation
+    // and the kidneys compensate
+    patient.acid_base.update(&mut patient.blood, delta_time_s);
+    let chemoreflex_drive = patient.acid_base.chemoreflex_drive;
+    let bicarbonate_setpoint = patient.acid_base.renal_bicarbonate_setpoint_meq_l;
+    if let Some(lungs) = patient.get_organ_mut::<lungs::Lungs>() {
+        lungs.chemoreflex_drive = chemoreflex_drive;
+    }
+    if let Some(kidneys) = patient.get_organ_mut::<kidneys::Kidneys>() {
+        kidneys.bicarbonate_setpoint_meq_l = bicarbonate_setpoint;
+    }
+
+    // Saturation follows PaO2 on a curve shifted by this step's pH; delivery falling
+    // below demand runs up an oxygen debt paid in lactate
+    let cardiac_output = patient.get_organ::<heart::Heart>().map_or(0.0, |h| h.cardiac_output_l_min);
+    // Working and convulsing muscle raises demand; necrotic muscle does no work
+    let convulsing = patient.get_organ::<brain::Brain>().is_some_and(|b| b.seizure_remaining_s > 0.0);
+    let exercise = patient.get_organ::<heart::Heart>().map_or(0.0, |h| h.exercise_intensity);
+    let muscle_demand = patient.get_organ_mut::<muscle::Muscle>().map_or(0.0, |muscle| {
+        muscle.exercise_intensity = exercise;
+        muscle.convulsing = convulsing;
+        muscle.metabolic_demand()
+    });
+    // Digesting a meal raises metabolic rate (the thermic effect of food); paralysis
+    // removes the work of breathing and muscle tone
+    let digesting = patient.get_organ::<intestines::Intestines>().map_or(0.0, |i| {
+        (i.absorption_kcal_min / FULL_MEAL_ABSORPTION_KCAL_MIN).clamp(0.0, 1.0)
+    });
+    let transmission = patient.get_organ::<nerves::Nerves>().map_or(1.0, |n| n.neuromuscular_transmission());
+    if let Some(lungs) = patient.get_organ_mut::<lungs::Lungs>() {
+        lungs.respiratory_muscle_strength = transmission;
+    }
+    // Water evaporating from open burns carries heat away; below normal temperature
+    // shivering, lost under paralysis, makes heat to replace it
+    let heat_loss = patient.get_organ::<skin::Skin>().map_or(0.0, |s| s.evaporative_heat_loss_kcal_min());
+    let heat_capacity = BODY_HEAT_CAPACITY_KCAL_KG_C * patient.get_weight_kg().max(1.0);
+    let transport = &mut patient.oxygen_transport;
+    if let Some(newborn) = &patient.newborn {
+        transport.body_temperature_c = newborn.temperature_c;
+    } else {
+        let hypothermia = (oxygen::NORMAL_TEMPERATURE_C - transport.body_temperature_c).max(0.0);
+        let shivering = SHIVERING_KCAL_MIN_PER_C * hypothermia * transmission;
+        transport.body_temperature_c += (shivering - heat_loss) * delta_time_s / 60.0 / heat_capacity;
+    }
+    transport.extraction_reserve = patient.difficulty.physiologic_reserve;
+    let chemistry = &patient.blood.chemistry;
+    transport.carbohydrate_oxidation_fraction = oxygen::carbohydrate_oxidation_fraction(
+        digesting,
+        chemistry.insulin_uu_ml,
+        chemistry.beta_hydroxybutyrate_mmol_l,
+    );
+    transport.metabolic_rate_factor = 1.0
+        + 0.3 * patient.signals.level(Signal::Cytokines)
+        + muscle_demand
+        + THERMIC_EFFECT_OF_FEEDING * digesting
+        - PARALYSIS_METABOLIC_REDUCTION * (1.0 - transmission);
+    transport.update(&mut patient.blood, cardiac_output, patient.fluids.reference_blood_volume_ml, delta_time_s);
+    // Tissue short of oxygen releases adenosine, dilating its arterioles
+    let shortfall = 1.0 - transport.consumption_ml_min / transport.demand_ml_min.max(1.0);
+    patient.signals.publish(Signal::Adenosine, 1.0 + ADENOSINE_PER_OXYGEN_SHORTFALL * shortfall.max(0.0));
+
+    // The ACLS assistant follows the monitor after the organs have updated
+    if let Some(mut acls) = patient.acls.take() {
+        acls.update(patient, delta_time_s);
+        patient.acls = Some(acls);
+    }
+    #[cfg(feature = "tracing")]
+    let released = patient.labs.reports().len();
+    patient.labs.update(delta_time_s);
+    #[cfg(feature = "tracing")]
+    for report in &patient.labs.reports()[released..] {
+        emergent!(
+            info,
+            patient = patient.id,
+            id = report.id,
+            panel = ?report.panel,
+            critical = report.has_critical(),
+            "lab report released"
+        );
+    }
+
+    // The kidneys' erythropoietin and systemic infection regulate the marrow
+    let erythropoietin = patient.get_organ::<kidneys::Kidneys>().map(|k| k.erythropoietin_mu_ml);
+    let sepsis = patient.signals.level(Signal::Cytokines);
+    if let Some(bones) = patient.get_organ_mut::<bones::Bones>() {
+        if let Some(erythropoietin) = erythropoietin {
+            bones.bone_marrow.erythropoietin_mu_ml = erythropoietin;
+        }
+        bones.bone_marrow.infection_severity = sepsis;
+    }
 
     // Simulate RAAS (Renin-Angiotensin-Aldosterone System)
     // Kidneys produce renin, liver produces angiotensinogen
-    let renin_secretion = if let Some(kidneys) = patient.get_organ::<kidneys::Kidneys>("Kidneys") {
+    let renin_secretion = if let Some(kidneys) = patient.get_organ::<kidneys::Kidneys>() {
         kidneys.get_renin_secretion()
     } else {
         0.0
     };
 
-    let angiotensinogen = if let Some(liver) = patient.get_organ::<liver::Liver>("Liver") {
+    let angiotensinogen = if let Some(liver) = patient.get_organ::<liver::Liver>() {
         liver.get_angiotensinogen()
     } else {
         0.0
     };
 
-    // Angiotensin II production
-    let angiotensin_production = renin_secretion * angiotensinogen * 0.1;
-    patient.blood.chemistry.angiotensin_ii_au += angiotensin_production * delta_time_s;
+    // Angiotensin II settles where production balances clearance (1.0 with normal
+    // renin and angiotensinogen)
+    let angiotensin_target = renin_secretion * angiotensinogen / NORMAL_ANGIOTENSINOGEN_PRODUCTION;
+    patient.signals.relax(Signal::AngiotensinII, angiotensin_target, ANGIOTENSIN_II_CLEARANCE_S, delta_time_s);
+
+    // The adrenal cortex secretes aldosterone in response to angiotensin II and
+    // to hyperkalemia
+    let potassium_stimulus = 1.0
+        + ALDOSTERONE_PER_POTASSIUM_MEQ_L * (patient.blood.chemistry.potassium_meq_l - kidneys::NORMAL_POTASSIUM_MEQ_L);
+    let aldosterone_target = patient.signals.level(Signal::AngiotensinII) * potassium_stimulus.max(0.0);
+    patient.signals.relax(Signal::Aldosterone, aldosterone_target, ALDOSTERONE_CLEARANCE_S, delta_time_s);
 
-    // Angiotensin II decay
-    patient.blood.chemistry.angiotensin_ii_au *= 0.95_f64.powf(delta_time_s);
+    // Raised intrathoracic pressure impedes venous return to the heart
+    let venous_return_impairment = patient
+        .get_organ::<lungs::Lungs>()
+        .map(|l| l.get_venous_return_impairment())
+        .unwrap_or(0.0);
+    let (capillary_permeability, pulmonary_obstruction, pulmonary_vascular_resistance) = patient
+        .get_organ::<vascular::VascularSystem>()
+        .map(|v| (v.capillary_permeability, v.get_pulmonary_obstruction(), v.pulmonary_vascular_resistance))
+        .unwrap_or((0.5, 0.0, vascular::NORMAL_PULMONARY_VASCULAR_RESISTANCE));
+    if let Some(lungs) = patient.get_organ_mut::<lungs::Lungs>() {
+        lungs.capillary_permeability = capillary_permeability;
+        // Embolized lung is ventilated but not perfused; local bronchoconstriction
+        // diverts part of its ventilation elsewhere
+        lungs.alveolar_dead_space_fraction = pulmonary_obstruction * 0.6;
+    }
+    // A left atrial embolus follows carotid flow, most often into the middle cerebral artery
+    let embolus = patient.get_organ_mut::<heart::Heart>().and_then(|h| h.take_embolus());
+    if let (Some(burden), Some(vascular)) =
+        (embolus, patient.get_organ_mut::<vascular::VascularSystem>())
+    {
+        let artery = brain::CerebralArtery::MiddleCerebral.vessel_name(brain::Hemisphere::Left);
+        let occlusion = vascular.vessel(artery).map_or(0.0, |v| v.occlusion);
+        vascular.occlude_vessel(artery, occlusion.max(0.5 + 0.5 * burden));
+    }
+    // Cerebral artery occlusions reach the brain's vascular territories
+    let cerebral_patency: Vec<(brain::CerebralArtery, brain::Hemisphere, f64)> = patient
+        .get_organ::<vascular::VascularSystem>()
+        .map(|v| {
+            brain::CerebralArtery::ALL
+                .iter()
+                .flat_map(|&artery| {
+                    brain::Hemisphere::BOTH
+                        .iter()
+                        .map(move |&side| (artery, side, v.vessel_patency(artery.vessel_name(side))))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(brain) = patient.get_organ_mut::<brain::Brain>() {
+        for (artery, side, patency) in cerebral_patency {
+            brain.set_artery_patency(artery, side, patency);
+        }
+    }
+    // Hepatic artery and portal vein occlusion reach the liver; hepatic veins drain at CVP
+    let hepatic_inflow = patient.get_organ::<vascular::VascularSystem>().map(|v| {
+        let arterial: f64 = v.arterial_path("Hepatic Artery").iter().map(|vessel| vessel.patency()).product();
+        (arterial, v.vessel_patency("Portal Vein"), v.central_venous_pressure)
+    });
+    if let (Some((arterial, portal, cvp)), Some(liver)) =
+        (hepatic_inflow, patient.get_organ_mut::<liver::Liver>())
+    {
+        liver.hepatic_artery_patency = arterial;
+        liver.portal_vein_patency = portal;
+        liver.hepatic_venous_pressure_mmhg = cvp;
+    }
+    // Mesenteric occlusion starves the bowel wall
+    let mesenteric_patency = patient.get_organ::<vascular::VascularSystem>().map(|v| {
+        v.arterial_path("Superior Mesenteric Artery").iter().map(|vessel| vessel.patency()).product::<f64>()
+    });
+    if let (Some(patency), Some(intestines)) = (mesenteric_patency, patient.get_organ_mut::<intestines::Intestines>()) {
+        intestines.mesenteric_artery_patency = patency;
+    }
+    // A stone at the ampulla obstructs both the bile duct and the pancreatic duct
+    let common_duct_stone = patient.get_organ::<gallbladder::Gallbladder>().is_some_and(|g| g.common_duct_stone);
+    if let Some(pancreas) = patient.get_organ_mut::<pancreas::Pancreas>() {
+        pancreas.duct_obstructed = common_duct_stone;
+    }
+    if let Some(liver) = patient.get_organ_mut::<liver::Liver>() {
+        liver.biliary_obstruction = if common_duct_stone { 1.0 } else { 0.0 };
+    }
+    // Broken ribs splint and destabilise the chest wall
+    let ribs = patient.get_organ::<bones::Bones>().map(|b| (b.fractured_ribs, b.flail_segment));
+    if let (Some((fractured_ribs, flail_segment)), Some(lungs)) = (ribs, patient.get_organ_mut::<lungs::Lungs>()) {
+        lungs.fractured_ribs = fractured_ribs;
+        lungs.flail_segment = flail_segment;
+    }
+    // Portal hypertension decompresses through esophageal collaterals and congests the spleen
+    let hvpg = patient.get_organ::<liver::Liver>().map(|l| l.hepatic_venous_pressure_gradient());
+    if let (Some(hvpg), Some(esophagus)) = (hvpg, patient.get_organ_mut::<esophagus::Esophagus>()) {
+        esophagus.portal_pressure_gradient_mmhg = hvpg;
+    }
+    if let (Some(hvpg), Some(spleen)) = (hvpg, patient.get_organ_mut::<spleen::Spleen>()) {
+        spleen.portal_pressure_gradient_mmhg = hvpg;
+    }
+    // Brainstem ischemia and herniation act through the cardiovascular and respiratory centres
+    // The heart's output and filling pressure drive flow through the vascular tree,
+    // whose arteriolar resistance the heart ejects into
+    let (heart_rate, pulse_deficit_fraction, cardiac_output, right_atrial_pressure, left_atrial_pressure) = patient
+        .get_organ::<heart::Heart>()
+        .map(|h| {
+            (
+                h.heart_rate_bpm,
+                h.pulse_deficit_fraction(),
+                h.cardiac_output_l_min,
+                h.right_atrium.pressure_mmhg,
+                h.left_atrium.pressure_mmhg,
+            )
+        })
+        .unwrap_or((75.0, 0.0, 5.0, 5.0, 8.0));
+    let mut peripheral_resistance_factor = 1.0;
+    if let Some(vascular) = patient.get_organ_mut::<vascular::VascularSystem>() {
+        vascular.heart_rate_bpm = heart_rate;
+        vascular.pulse_deficit_fraction = pulse_deficit_fraction;
+        vascular.cardiac_output_l_per_min = cardiac_output;
+        vascular.central_venous_pressure = right_atrial_pressure;
+        peripheral_resistance_factor = vascular.peripheral_resistance_factor();
+    }
+    let (cushing_response, brainstem_viability, central_respiratory_drive, ventilatory_drive, gcs) = patient
+        .get_organ::<brain::Brain>()
+        .map(|b| (b.cushing_response, b.get_brainstem_viability(), b.get_respiratory_drive(), b.ventilatory_drive, b.gcs.total()))
+        .unwrap_or((0.0, 1.0, 1.0, 1.0, 15));
+    if let Some(lungs) = patient.get_organ_mut::<lungs::Lungs>() {
+        lungs.brainstem_function = central_respiratory_drive;
+        lungs.ventilatory_drive = ventilatory_drive;
+        // Sedation and coma relax the muscles holding the pharynx open
+        lungs.upper_airway_tone = ((gcs - 3) as f64 / 12.0).clamp(0.0, 1.0);
+        // Left atrial pressure backs up into the pulmonary capillaries
+        lungs.pulmonary_capillary_pressure_mmhg = left_atrial_pressure;
+    }
+    // A patient who is obtunded or paralysed no longer shifts their weight off the sacrum
+    let transmission = patient.get_organ::<nerves::Nerves>().map_or(1.0, |n| n.neuromuscular_transmission());
+    if let Some(skin) = patient.get_organ_mut::<skin::Skin>() {
+        skin.immobility = (1.0 - (gcs - 3) as f64 / 12.0).max(1.0 - transmission).clamp(0.0, 1.0);
+    }
+    if let Some(heart) = patient.get_organ_mut::<heart::Heart>() {
+        heart.venous_return_factor = 1.0 - venous_return_impairment;
+        heart.pulmonary_vascular_resistance = pulmonary_vascular_resistance;
+        heart.cushing_response = cushing_response;
+        heart.vasomotor_tone = brainstem_viability;
+        heart.peripheral_resistance_factor = peripheral_resistance_factor;
+    }
+
+    // Variceal blood runs down into the stomach, or out if there is none
+    let variceal_blood = patient
+        .get_organ_mut::<esophagus::Esophagus>()
+        .map(|e| e.take_luminal_blood())
+        .unwrap_or(0.0);
+    match patient.get_organ_mut::<stomach::Stomach>() {
+        Some(stomach) => stomach.add_blood(variceal_blood),
+        None => patient.fluid_ledger.record_output(OutputRoute::Blood, variceal_blood),
+    }
+
+    // Gastric emptying into the duodenum, upper GI blood digested on its way through
+    let (emptied, emptied_blood, emptied_nutrients) = patient
+        .get_organ_mut::<stomach::Stomach>()
+        .map(|s| (s.take_emptied_volume(), s.take_emptied_blood(), s.take_emptied_nutrients()))
+        .unwrap_or_default();
+    let (stool_events, reflux_ml, reflux_feculent_ml, urea_nitrogen_mg, absorbed) =
+        match patient.get_organ_mut::<intestines::Intestines>() {
+            Some(intestines) => {
+                intestines.receive_chyme(emptied - emptied_blood, emptied_nutrients);
+                intestines.add_luminal_blood(emptied_blood, true);
+                let (reflux_ml, reflux_feculent_ml) = intestines.take_reflux();
+                let urea_nitrogen_mg = intestines.take_absorbed_urea_nitrogen();
+                let absorbed = intestines.take_absorbed_nutrients();
+                (intestines.take_stool_events(), reflux_ml, reflux_feculent_ml, urea_nitrogen_mg, absorbed)
+            }
+            None => (Vec::new(), 0.0, 0.0, 0.0, emptied_nutrients),
+        };
+    absorb_meal(patient, &absorbed);
+    let total_body_water_ml = patient.fluids.total_body_water_ml();
+    if let Some(kidneys) = patient.get_organ_mut::<kidneys::Kidneys>() {
+        kidneys.add_urea_nitrogen(urea_nitrogen_mg, total_body_water_ml);
+    }
+
+    // Obstructed bowel refluxes back into the stomach
+    let emesis_events = match patient.get_organ_mut::<stomach::Stomach>() {
+        Some(stomach) => {
+            stomach.receive_reflux(reflux_ml, reflux_feculent_ml);
+            stomach.take_emesis_events()
+        }
+        None => Vec::new(),
+    };
+
+    // Fluid balance
+    // The whole stool volume is charted, but blood in stool already left the circulation
+    for event in &stool_events {
+        let water = patient.fluids.remove_plasma(event.volume_ml - event.blood_ml);
+        patient.fluid_ledger.record_output(OutputRoute::Stool, water + event.blood_ml);
+    }
+    for event in &emesis_events {
+        let water = patient.fluids.remove_plasma(event.volume_ml - event.blood_ml - event.ingested_ml);
+        patient.fluid_ledger.record_output(OutputRoute::Emesis, water + event.blood_ml + event.ingested_ml);
+        lose_gastric_acid(patient, event);
+    }
+
+    // Urine comes from the kidneys and is charted when it leaves the bladder,
+    // insensible losses ~10 mL/kg/day
+    let urine_rate = patient
+        .get_organ::<kidneys::Kidneys>()
+        .map(|k| k.urine_output_rate)
+        .unwrap_or(0.0);
+    let urine_ml = patient.fluids.remove_plasma(urine_rate * delta_time_s / 60.0);
+    match patient.get_organ_mut::<bladder::Bladder>() {
+        Some(bladder) => bladder.add_urine(urine_ml),
+        None => patient.fluid_ledger.record_output(OutputRoute::Urine, urine_ml),
+    }
+
+    let insensible_rate = patient.get_weight_kg() * 10.0 / 1440.0;
+    patient.lose_fluid(OutputRoute::Insensible, insensible_rate * delta_time_s / 60.0);
+
+    let albumin = patient.blood.chemistry.albumin_g_dl;
+    let central_venous_pressure = patient
+        .get_organ::<vascular::VascularSystem>()
+        .map_or(5.0, |v| v.central_venous_pressure);
+    // A large burn leaks plasma into unburned tissue as well as burned
+    let burn_leak = patient.get_organ::<skin::Skin>().map_or(0.0, |s| s.capillary_leak());
+    let capillary_permeability = (capillary_permeability + burn_leak).min(0.9);
+    patient.fluids.update(albumin, capillary_permeability, central_venous_pressure, delta_time_s);
+
+    let weight_kg = patient.get_weight_kg();
+    patient.fluid_ledger.advance(delta_time_s, weight_kg);
+
+    // Red cell indices follow the red cell and plasma volumes (hemorrhage, dilution, transfusion)
+    let cells = &mut patient.blood.cells;
+    let hematocrit_percent = patient.fluids.hematocrit() * 100.0;
+    if cells.hematocrit_percent > 0.0 {
+        let ratio = hematocrit_percent / cells.hematocrit_percent;
+        cells.hemoglobin_g_dl *= ratio;
+        cells.rbc_count_million_per_ul *= ratio;
+    }
+    cells.hematocrit_percent = hematocrit_percent;
+
+    // NaN, infinite and impossible values are corrected before the next step reads them
+    let mut stability = std::mem::take(&mut patient.stability);
+    stability.stabilize(patient, delta_time_s);
+    patient.stability = stability;
+}
+
+/// Leave behind the bicarbonate generated with gastric acid that was vomited or
+/// drained, with the chloride lost alongside it (hypochloremic metabolic alkalosis)
+///
+/// # Arguments
+/// * `patient` - The patient
+/// * `output` - Gastric contents lost
+fn lose_gastric_acid(patient: &mut Patient, output: &stomach::GastricOutput) {
+    let extracellular_l = patient.fluids.extracellular_ml().max(1000.0) / 1000.0;
+    AcidBase::add_base(&mut patient.blood.chemistry, output.acid_meq / extracellular_l, true);
+}
+
+/// Take up food and drink absorbed from the gut into the blood
+///
+/// # Arguments
+/// * `patient` - The patient
+/// * `absorbed` - Nutrients absorbed this step
+fn absorb_meal(patient: &mut Patient, absorbed: &stomach::Meal) {
+    if absorbed.volume_ml() <= 0.0 {
+        return;
+    }
+
+    // Water and salt shift plasma sodium by their tonicity relative to body water
+    let total_body_water_l = patient.fluids.total_body_water_ml() / 1000.0;
+    let chemistry = &mut patient.blood.chemistry;
+    let sodium_meq = absorbed.sodium_mg / SODIUM_MG_PER_MEQ;
+    chemistry.sodium_meq_l += (sodium_meq - chemistry.sodium_meq_l * absorbed.water_ml / 1000.0)
+        / (total_body_water_l + absorbed.water_ml / 1000.0).max(1.0);
+    patient.fluids.add_plasma(absorbed.water_ml);
+
+    // Portal glucose is partly taken up by the liver before it reaches the
+    // extracellular fluid; chylomicron fat enters the plasma via the thoracic duct
+    let extraction = patient.get_organ::<liver::Liver>().map_or(0.0, |l| l.first_pass_glucose_extraction());
+    let extracellular_dl = (patient.fluids.extracellular_ml() / 100.0).max(1.0);
+    let plasma_dl = (patient.fluids.plasma_ml / 100.0).max(1.0);
+    let chemistry = &mut patient.blood.chemistry;
+    chemistry.glucose_mg_dl += absorbed.carbs_g * 1000.0 * (1.0 - extraction) / extracellular_dl;
+    chemistry.triglycerides_mg_dl += absorbed.fat_g * 1000.0 / plasma_dl;
+
+    // Amino acids beyond what is used for protein synthesis are deaminated to urea
+    let urea_nitrogen_mg = absorbed.protein_g * 1000.0 * PROTEIN_NITROGEN_FRACTION * PROTEIN_UREAGENESIS_FRACTION;
+    let total_body_water_ml = patient.fluids.total_body_water_ml();
+    if let Some(kidneys) = patient.get_organ_mut::<kidneys::Kidneys>() {
+        kidneys.add_urea_nitrogen(urea_nitrogen_mg, total_body_water_ml);
+    }
 }
 
 /// Get a summary of all patient vitals
@@ -187,3 +1898,57 @@ pub fn get_patient_summary(patient: &Patient) -> String {
         patient.blood.chemistry.toxin_level_au
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forked_timelines_diverge_independently() {
+        let mut patient = initialize_patient(1, 12);
+        for _ in 0..300 {
+            update_patient(&mut patient, 0.1);
+        }
+        patient.hemorrhage(0.3 * patient.fluids.reference_blood_volume_ml);
+        let bleeding = patient.project(|_| {}, 300.0, 0.1);
+        let transfused = patient.project(|p| p.give_fluid(IntakeRoute::BloodProduct, 1500.0), 300.0, 0.1);
+
+        let heart_rate = |p: &Patient| p.get_organ::<heart::Heart>().unwrap().heart_rate_bpm;
+        assert!(heart_rate(&transfused) < heart_rate(&bleeding) - 5.0);
+        assert!(transfused.blood.get_mean_arterial_pressure() > bleeding.blood.get_mean_arterial_pressure());
+        // The original timeline has not moved
+        let heart = patient.get_organ::<heart::Heart>().unwrap();
+        assert_eq!(heart.ekg_leads[0].len(), 2500);
+        assert_eq!(patient.fork().blood.blood_pressure_systolic, patient.blood.blood_pressure_systolic);
+    }
+
+    #[test]
+    fn test_patient_moves_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Patient>();
+
+        let patient = initialize_patient(1, 3);
+        let mut patient = std::thread::spawn(move || {
+            let mut patient = patient;
+            for _ in 0..10 {
+                update_patient(&mut patient, 0.1);
+            }
+            patient
+        })
+        .join()
+        .unwrap();
+        update_patient(&mut patient, 0.1);
+
+        // A shared patient can be read from several threads at once
+        let shared = std::sync::Arc::new(patient);
+        let readers: Vec<_> = (0..2)
+            .map(|_| {
+                let shared = shared.clone();
+                std::thread::spawn(move || shared.blood.get_mean_arterial_pressure())
+            })
+            .collect();
+        for reader in readers {
+            assert_eq!(reader.join().unwrap(), shared.blood.get_mean_arterial_pressure());
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Chronic disease presets
+//!
+//! This module provides:
+//! - Presets for common comorbidities (chronic kidney disease, COPD, diabetes,
+//!   cirrhosis, an old myocardial infarction, chronic heart failure and
+//!   obesity) that set the baseline organ state and the labs the organs do not
+//!   recalculate
+//! - Construction of a patient with several comorbidities at once, so
+//!   scenarios do not have to hand-tune dozens of fields
+//!
+//! Presets describe established, stable disease: they set the chronic state
+//! the organs then simulate from, without an acute event to recover from.
+
+use crate::organs::gallbladder::Gallbladder;
+use crate::organs::heart::{CoronaryTerritory, Heart, HeartFailurePhenotype};
+use crate::organs::kidneys::Kidneys;
+use crate::organs::liver::Liver;
+use crate::organs::lungs::Lungs;
+use crate::organs::pancreas::{DiabetesType, Pancreas};
+use crate::organs::spleen::Spleen;
+use crate::patient::{initialize_patient, Patient};
+
+/// GFR at the middle of each CKD stage, G1 to G5 (mL/min/1.73 m²)
+const CKD_STAGE_GFR: [f64; 5] = [95.0, 75.0, 45.0, 22.0, 10.0];
+
+/// Serum phosphate retained in each CKD stage, G1 to G5 (mg/dL)
+const CKD_STAGE_PHOSPHATE_MG_DL: [f64; 5] = [3.5, 3.5, 3.8, 4.8, 6.0];
+
+/// Normal GFR the CKD stages are measured against (mL/min/1.73 m²)
+const NORMAL_GFR_ML_MIN: f64 = 100.0;
+
+/// Fixed airflow obstruction per GOLD grade
+const COPD_OBSTRUCTION_PER_GOLD_GRADE: f64 = 0.25;
+
+/// Fasting glucose in treated but imperfectly controlled diabetes (mg/dL), type 1 and type 2
+const DIABETES_GLUCOSE_MG_DL: [f64; 2] = [180.0, 150.0];
+
+/// Body mass index above which the excess weight is adipose tissue (kg/m²)
+pub const LEAN_BMI: f64 = 25.0;
+
+/// Loss of chest wall compliance per unit of BMI above lean (relative to normal)
+const CHEST_WALL_STIFFENING_PER_BMI: f64 = 0.04;
+
+/// Pharyngeal collapsibility per unit of BMI above lean (obstructive sleep apnea)
+const PHARYNGEAL_COLLAPSIBILITY_PER_BMI: f64 = 0.04;
+
+/// Added chance of a failed intubation attempt per unit of BMI above lean
+const DIFFICULT_AIRWAY_RISK_PER_BMI: f64 = 0.01;
+
+/// Insulin resistance added per unit of BMI above lean (relative to normal)
+const INSULIN_RESISTANCE_PER_BMI: f64 = 0.05;
+
+/// Cholesterol saturation of bile added per unit of BMI above lean (relative to normal)
+const LITHOGENICITY_PER_BMI: f64 = 0.1;
+
+/// Child-Pugh class of cirrhosis, from compensated to decompensated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChildPugh {
+    A,
+    B,
+    C,
+}
+
+impl ChildPugh {
+    /// Hepatic fibrosis (0.8 is the threshold of cirrhosis)
+    fn fibrosis(&self) -> f64 {
+        match self {
+            ChildPugh::A => 0.8,
+            ChildPugh::B => 0.88,
+            ChildPugh::C => 1.0,
+        }
+    }
+
+    /// Serum albumin (g/dL)
+    fn albumin_g_dl(&self) -> f64 {
+        match self {
+            ChildPugh::A => 3.6,
+            ChildPugh::B => 3.0,
+            ChildPugh::C => 2.5,
+        }
+    }
+
+    /// Spleen congested by portal hypertension (mL)
+    fn spleen_volume_ml(&self) -> f64 {
+        match self {
+            ChildPugh::A => 500.0,
+            ChildPugh::B => 600.0,
+            ChildPugh::C => 700.0,
+        }
+    }
+
+    /// Platelet count lowered by splenic sequestration (K/µL)
+    fn platelets_k_ul(&self) -> f64 {
+        match self {
+            ChildPugh::A => 140.0,
+            ChildPugh::B => 100.0,
+            ChildPugh::C => 70.0,
+        }
+    }
+}
+
+/// Established chronic disease to start a patient with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Preset {
+    /// Chronic kidney disease by KDIGO GFR stage (1-5)
+    ChronicKidneyDisease { stage: u8 },
+    /// COPD by GOLD spirometric grade (1-4)
+    Copd { gold_grade: u8 },
+    /// Diabetes mellitus
+    Diabetes { diabetes_type: DiabetesType },
+    /// Cirrhosis by Child-Pugh class
+    Cirrhosis { child_pugh: ChildPugh },
+    /// Healed myocardial infarction leaving a scar in one coronary territory
+    PriorMyocardialInfarction { territory: CoronaryTerritory },
+    /// Chronic heart failure
+    HeartFailure { phenotype: HeartFailurePhenotype, severity: f64 },
+    /// Obesity by body mass index (kg/m²), adding adipose tissue to reach it
+    Obesity { bmi: f64 },
+}
+
+impl Preset {
+    /// Name of the condition
+    pub fn name(&self) -> String {
+        match self {
+            Preset::ChronicKidneyDisease { stage } => format!("CKD stage {}", stage.clamp(&1, &5)),
+            Preset::Copd { gold_grade } => format!("COPD GOLD {}", gold_grade.clamp(&1, &4)),
+            Preset::Diabetes { diabetes_type } => format!("Diabetes mellitus {:?}", diabetes_type),
+            Preset::Cirrhosis { child_pugh } => format!("Cirrhosis, Child-Pugh {:?}", child_pugh),
+            Preset::PriorMyocardialInfarction { territory } => format!("Old myocardial infarction ({:?})", territory),
+            Preset::HeartFailure { phenotype, .. } => match phenotype {
+                HeartFailurePhenotype::ReducedEjectionFraction => "Heart failure with reduced ejection fraction".to_string(),
+                HeartFailurePhenotype::PreservedEjectionFraction => {
+                    "Heart failure with preserved ejection fraction".to_string()
+                }
+            },
+            Preset::Obesity { bmi } => match bmi {
+                bmi if *bmi >= 40.0 => format!("Class III obesity (BMI {:.0})", bmi),
+                bmi if *bmi >= 35.0 => format!("Class II obesity (BMI {:.0})", bmi),
+                bmi => format!("Class I obesity (BMI {:.0})", bmi),
+            },
+        }
+    }
+}
+
+/// Give a patient an established chronic disease
+///
+/// # Arguments
+/// * `patient` - Patient to modify
+/// * `preset` - Disease and its severity
+pub fn apply(patient: &mut Patient, preset: Preset) {
+    match preset {
+        Preset::ChronicKidneyDisease { stage } => {
+            let stage = stage.clamp(1, 5) as usize - 1;
+            let function = CKD_STAGE_GFR[stage] / NORMAL_GFR_ML_MIN;
+            if let Some(kidneys) = patient.get_organ_mut::<Kidneys>() {
+                for nephron in kidneys.nephrons.iter_mut() {
+                    nephron.filtration_efficiency = function;
+                }
+                kidneys.gfr_ml_per_min = kidneys.baseline_gfr_ml_per_min * function;
+            }
+            patient.blood.chemistry.phosphate_mg_dl = CKD_STAGE_PHOSPHATE_MG_DL[stage];
+        }
+        Preset::Copd { gold_grade } => {
+            let obstruction = gold_grade.clamp(1, 4) as f64 * COPD_OBSTRUCTION_PER_GOLD_GRADE;
+            if let Some(lungs) = patient.get_organ_mut::<Lungs>() {
+                lungs.set_chronic_obstruction(obstruction);
+            }
+        }
+        Preset::Diabetes { diabetes_type } => {
+            if let Some(pancreas) = patient.get_organ_mut::<Pancreas>() {
+                pancreas.apply_diabetes(diabetes_type);
+            }
+            patient.blood.chemistry.glucose_mg_dl = match diabetes_type {
+                DiabetesType::Type1 => DIABETES_GLUCOSE_MG_DL[0],
+                DiabetesType::Type2 => DIABETES_GLUCOSE_MG_DL[1],
+            };
+        }
+        Preset::Cirrhosis { child_pugh } => {
+            if let Some(liver) = patient.get_organ_mut::<Liver>() {
+                liver.fibrosis = child_pugh.fibrosis();
+            }
+            if let Some(spleen) = patient.get_organ_mut::<Spleen>() {
+                spleen.set_volume(child_pugh.spleen_volume_ml());
+            }
+            let chemistry = &mut patient.blood.chemistry;
+            chemistry.albumin_g_dl = child_pugh.albumin_g_dl();
+            chemistry.total_protein_g_dl = chemistry.albumin_g_dl + 3.0;
+            patient.blood.cells.platelet_count_thousand_per_ul = child_pugh.platelets_k_ul();
+        }
+        Preset::PriorMyocardialInfarction { territory } => {
+            if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                heart.scar_territory(territory);
+            }
+        }
+        Preset::HeartFailure { phenotype, severity } => patient.induce_heart_failure(phenotype, severity),
+        Preset::Obesity { bmi } => {
+            // Adipose tissue is mostly fat, adding weight but little body water
+            let height_m = patient.demographics.height_cm / 100.0;
+            let adipose_kg = (bmi * height_m.powi(2) - patient.get_weight_kg()).max(0.0);
+            patient.fluids.dry_mass_kg += adipose_kg;
+
+            // The loaded chest wall lowers FRC, and a fat-laden pharynx collapses without tone
+            let excess = (bmi - LEAN_BMI).max(0.0);
+            if let Some(lungs) = patient.get_organ_mut::<Lungs>() {
+                lungs.chest_wall_compliance /= 1.0 + CHEST_WALL_STIFFENING_PER_BMI * excess;
+                lungs.upper_airway_collapsibility = (PHARYNGEAL_COLLAPSIBILITY_PER_BMI * excess).min(1.0);
+                lungs.difficult_airway_risk = (lungs.difficult_airway_risk + DIFFICULT_AIRWAY_RISK_PER_BMI * excess).min(1.0);
+            }
+            if let Some(pancreas) = patient.get_organ_mut::<Pancreas>() {
+                pancreas.insulin_resistance = pancreas.insulin_resistance.max(1.0 + INSULIN_RESISTANCE_PER_BMI * excess);
+            }
+            if let Some(gallbladder) = patient.get_organ_mut::<Gallbladder>() {
+                gallbladder.lithogenicity = 1.0 + LITHOGENICITY_PER_BMI * excess;
+            }
+        }
+    }
+}
+
+/// Create an adult patient with chronic diseases
+///
+/// # Arguments
+/// * `patient_id` - Unique identifier for the patient
+/// * `num_heart_leads` - Number of EKG leads to simulate
+/// * `presets` - Diseases to start with, applied in order
+pub fn initialize_with(patient_id: i32, num_heart_leads: usize, presets: &[Preset]) -> Patient {
+    let mut patient = initialize_patient(patient_id, num_heart_leads);
+    for &preset in presets {
+        apply(&mut patient, preset);
+    }
+    patient
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fluids::IntakeRoute;
+    use crate::organs::brain::Brain;
+    use crate::organs::lungs::Ventilator;
+    use crate::patient::update_patient;
+    use crate::toxicology::Drug;
+
+    #[test]
+    fn test_comorbid_patient_starts_with_chronic_disease() {
+        let mut patient = initialize_with(
+            1,
+            3,
+            &[
+                Preset::ChronicKidneyDisease { stage: 4 },
+                Preset::Diabetes { diabetes_type: DiabetesType::Type2 },
+                Preset::Cirrhosis { child_pugh: ChildPugh::B },
+                Preset::PriorMyocardialInfarction { territory: CoronaryTerritory::LeftAnteriorDescending },
+            ],
+        );
+        let baseline_troponin = patient.blood.chemistry.troponin_ng_l;
+        for _ in 0..600 {
+            update_patient(&mut patient, 1.0);
+        }
+
+        let kidneys = patient.get_organ::<Kidneys>().unwrap();
+        assert!(kidneys.gfr_ml_per_min > 15.0 && kidneys.gfr_ml_per_min < 30.0);
+        assert!(patient.blood.chemistry.creatinine_mg_dl > 2.0);
+        assert!(patient.get_organ::<Pancreas>().unwrap().diabetes == Some(DiabetesType::Type2));
+        assert!(patient.blood.chemistry.glucose_mg_dl > 120.0);
+        let liver = patient.get_organ::<Liver>().unwrap();
+        assert!(liver.is_cirrhotic() && liver.is_portal_hypertension());
+        assert!(patient.get_organ::<Spleen>().unwrap().is_splenomegaly());
+        assert!(patient.blood.chemistry.albumin_g_dl < 3.5);
+
+        // An old scar lowers the ejection fraction without an infarct in progress
+        let heart = patient.get_organ::<Heart>().unwrap();
+        assert_eq!(heart.hypokinetic_segment_count(), 7);
+        assert!(heart.infarcted_territories().is_empty());
+        assert!(heart.ejection_fraction_percent < 50.0);
+        assert!(patient.blood.chemistry.troponin_ng_l <= baseline_troponin + 1.0);
+        assert_eq!(Preset::ChronicKidneyDisease { stage: 9 }.name(), "CKD stage 5");
+    }
+
+    #[test]
+    fn test_obesity_lowers_frc_and_obstructs_the_sedated_airway() {
+        let mut lean = initialize_patient(1, 3);
+        let mut obese = initialize_with(2, 3, &[Preset::Obesity { bmi: 40.0 }]);
+        assert!(obese.get_weight_kg() > 110.0);
+        assert_eq!(Preset::Obesity { bmi: 40.0 }.name(), "Class III obesity (BMI 40)");
+        for _ in 0..60 {
+            update_patient(&mut lean, 60.0);
+            update_patient(&mut obese, 60.0);
+        }
+
+        // Airways close above the shrunken FRC, shunting blood past them
+        let lungs = obese.get_organ::<Lungs>().unwrap();
+        assert!(lungs.functional_residual_capacity_ml() < lungs.closing_capacity_ml());
+        assert!(obese.blood.gases.pao2_mmhg < lean.blood.gases.pao2_mmhg - 10.0);
+        assert!(lungs.difficult_airway_risk > 0.1);
+        assert!(obese.blood.chemistry.glucose_mg_dl > lean.blood.chemistry.glucose_mg_dl + 5.0);
+
+        // PEEP holds the small airways open under a paralysed, ventilated patient
+        let mut ventilated = obese.fork();
+        let ideal_weight_kg = ventilated.get_ideal_body_weight_kg();
+        for peep_cmh2o in [0.0, 10.0] {
+            let ventilator = Ventilator::lung_protective(0.4, peep_cmh2o, ideal_weight_kg, 14.0);
+            ventilated.get_organ_mut::<Lungs>().unwrap().attach_ventilator(ventilator);
+            update_patient(&mut ventilated, 60.0);
+            let shunt = ventilated.get_organ::<Lungs>().unwrap().shunt_fraction;
+            assert!(if peep_cmh2o > 0.0 { shunt < 0.06 } else { shunt > 0.1 }, "{}", shunt);
+        }
+
+        // Sedation relaxes the fat-laden pharynx, which the lean patient tolerates
+        for patient in [&mut lean, &mut obese] {
+            patient.get_organ_mut::<Brain>().unwrap().set_sedation_level(0.5);
+            for _ in 0..30 {
+                update_patient(patient, 60.0);
+            }
+        }
+        assert!(obese.get_organ::<Lungs>().unwrap().upper_airway_obstruction() > 0.2);
+        assert!(lean.get_organ::<Lungs>().unwrap().upper_airway_obstruction() == 0.0);
+        assert!(obese.blood.gases.paco2_mmhg > lean.blood.gases.paco2_mmhg + 5.0);
+
+        // Phenytoin spreads into the adipose tissue, diluting the same loading dose
+        for patient in [&mut lean, &mut obese] {
+            patient.give_drug(Drug::Phenytoin, 1000.0, IntakeRoute::Intravenous);
+            update_patient(patient, 600.0);
+        }
+        assert!(obese.toxicology.level(Drug::Phenytoin) < 0.7 * lean.toxicology.level(Drug::Phenytoin));
+    }
+}
@@ -0,0 +1,122 @@
+//! Pathophysiology process plugins
+//!
+//! This module provides:
+//! - A `Process` trait for cross-cutting processes (a ketoacidosis, a drug
+//!   infusion, a toxidrome) that act on the whole patient rather than belonging
+//!   to one organ
+//! - Registration on the patient, so downstream crates can add pathologies
+//!   without forking organ modules
+//!
+//! Processes are updated each step after the organs and the built-in systemic
+//! processes, with mutable access to the patient's blood, fluids, patient-level
+//! systems and organs. A process that reports itself inactive is removed.
+
+use crate::patient::Patient;
+use std::any::Any;
+use std::fmt;
+
+/// A pathophysiological process registered on a patient
+///
+/// Like organs, processes are `Send + Sync` so the patient carrying them is too.
+pub trait Process: fmt::Debug + Send + Sync {
+    /// Advance the process by one step
+    ///
+    /// # Arguments
+    /// * `patient` - Mutable reference to the patient (the process itself is
+    ///   detached from it while updating)
+    /// * `delta_time_s` - Time step in seconds
+    fn update(&mut self, patient: &mut Patient, delta_time_s: f64);
+
+    /// Name the process is registered and looked up by
+    fn get_name(&self) -> &str;
+
+    /// Get a summary of the process's state
+    fn get_summary(&self) -> String;
+
+    /// Whether the process is still running; inactive processes are removed after their update
+    fn is_active(&self) -> bool {
+        true
+    }
+
+    /// Get a reference to Any for downcasting
+    fn as_any(&self) -> &dyn Any;
+
+    /// Get a mutable reference to Any for downcasting
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Clone the process's full state into a new box, for forking the patient
+    fn clone_box(&self) -> Box<dyn Process>;
+}
+
+/// Macro to implement the as_any and clone_box methods for process types
+#[macro_export]
+macro_rules! impl_process_any {
+    () => {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn $crate::process::Process> {
+            Box::new(self.clone())
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::acid_base::AcidBase;
+    use crate::patient::{initialize_patient, update_patient};
+
+    /// Ketoacid production that stops after a fixed duration
+    #[derive(Debug, Clone)]
+    struct Ketosis {
+        acid_meq_l_per_s: f64,
+        remaining_s: f64,
+    }
+
+    impl Process for Ketosis {
+        fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+            AcidBase::add_acid(&mut patient.blood.chemistry, self.acid_meq_l_per_s * delta_time_s, true);
+            self.remaining_s -= delta_time_s;
+        }
+
+        fn get_name(&self) -> &str {
+            "Ketosis"
+        }
+
+        fn get_summary(&self) -> String {
+            format!("Ketosis: {:.0} s remaining", self.remaining_s)
+        }
+
+        fn is_active(&self) -> bool {
+            self.remaining_s > 0.0
+        }
+
+        crate::impl_process_any!();
+    }
+
+    #[test]
+    fn test_registered_process_acts_until_inactive() {
+        let mut patient = initialize_patient(1, 3);
+        let bicarbonate = patient.blood.chemistry.bicarbonate_meq_l;
+        patient.add_process(Box::new(Ketosis { acid_meq_l_per_s: 0.01, remaining_s: 300.0 }));
+        assert!(patient.get_process::<Ketosis>("Ketosis").is_some());
+
+        for _ in 0..20 {
+            update_patient(&mut patient, 10.0);
+        }
+        assert!(patient.get_process::<Ketosis>("Ketosis").unwrap().remaining_s < 150.0);
+        assert!(patient.blood.chemistry.bicarbonate_meq_l < bicarbonate - 1.0);
+
+        for _ in 0..20 {
+            update_patient(&mut patient, 10.0);
+        }
+        assert!(patient.get_process::<Ketosis>("Ketosis").is_none());
+        assert!(patient.processes().is_empty());
+    }
+}
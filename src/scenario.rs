@@ -0,0 +1,1245 @@
+//! Scripted scenarios
+//!
+//! This module provides:
+//! - Declarative scripts of timed events ("at 300 s occlude the LAD", "at 600 s
+//!   give 1 mg epinephrine") and conditional events ("when MAP < 65 start
+//!   norepinephrine"), written as JSON or a TOML subset
+//! - Actions covering the patient's interventions and insults, each checked
+//!   with its parameters when the script is loaded
+//! - A runner that advances a patient through the script and logs each event
+//!   as it fires, so curriculum scenarios can be shared as files instead of
+//!   hand-written simulation loops
+//!
+//! A script has a `name`, a `duration_s`, an optional `time_step_s` (0.1 s by
+//! default) and a list of `events`. Each event has either `at` (seconds) or
+//! `when` (a condition such as `"K > 6.0"`), an `action` and the action's
+//! parameters:
+//!
+//! ```text
+//! name = "Anterior STEMI"
+//! duration_s = 900
+//!
+//! [[events]]
+//! at = 60
+//! action = "occlude_coronary"
+//! territory = "LAD"
+//!
+//! [[events]]
+//! when = "map < 65"
+//! action = "norepinephrine"
+//! mcg_kg_min = 0.1
+//! ```
+//!
+//! Conditions compare a vital sign (`heart_rate`, `systolic`, `diastolic`,
+//! `map`, `pulse_pressure`, `cardiac_output`, `cardiac_index`, `stroke_volume`,
+//! `stroke_volume_index`,
+//! `ejection_fraction`, `wedge`, `sao2`, `svo2`, `vo2`, `vco2`, `rq`,
+//! `energy_expenditure`) or a lab analyte (by its report name such as `K` or
+//! `Cr`, or its full name such as `potassium`) with a
+//! threshold, read from the patient's true state rather than a sampled lab. A
+//! conditional event fires once, the first time its condition holds.
+
+use crate::fluids::IntakeRoute;
+use crate::labs::{Analyte, LabPanel};
+use crate::organs::heart::{CoronaryTerritory, Heart, HeartFailurePhenotype};
+use crate::organs::lungs::Lungs;
+use crate::organs::stomach::Meal;
+use crate::patient::{update_patient, Patient};
+use crate::sepsis::BundleGuideline;
+use crate::validation::Measure;
+use std::fmt;
+
+/// Default simulation time step of a script (s)
+const DEFAULT_TIME_STEP_S: f64 = 0.1;
+
+/// Deepest nesting of arrays and objects a JSON script may use
+const MAX_JSON_DEPTH: usize = 32;
+
+/// Vital signs a condition can compare, by script name
+const VITAL_SIGNS: [(&str, Measure); 17] = [
+    ("heart_rate", Measure::HeartRate),
+    ("systolic", Measure::SystolicPressure),
+    ("diastolic", Measure::DiastolicPressure),
+    ("map", Measure::MeanArterialPressure),
+    ("pulse_pressure", Measure::PulsePressure),
+    ("cardiac_output", Measure::CardiacOutput),
+    ("cardiac_index", Measure::CardiacIndex),
+    ("stroke_volume", Measure::StrokeVolume),
+    ("stroke_volume_index", Measure::StrokeVolumeIndex),
+    ("ejection_fraction", Measure::EjectionFraction),
+    ("wedge", Measure::LeftAtrialPressure),
+    ("sao2", Measure::ArterialSaturation),
+    ("svo2", Measure::MixedVenousSaturation),
+    ("vo2", Measure::OxygenConsumption),
+    ("vco2", Measure::Co2Production),
+    ("rq", Measure::RespiratoryQuotient),
+    ("energy_expenditure", Measure::EnergyExpenditure),
+];
+
+/// Error loading a script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScenarioError {
+    /// Line of the script the error was found on, for syntax errors
+    pub line: Option<usize>,
+    /// What is wrong
+    pub message: String,
+}
+
+impl ScenarioError {
+    fn syntax(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+
+    fn invalid(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+/// Value parsed from a script, before it is checked against the scenario's shape
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Table(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn kind(&self) -> &'static str {
+        match self {
+            Value::Bool(_) => "a boolean",
+            Value::Number(_) => "a number",
+            Value::String(_) => "a string",
+            Value::Array(_) => "an array",
+            Value::Table(_) => "a table",
+        }
+    }
+}
+
+/// Recursive-descent parser for JSON scripts
+struct JsonParser<'a> {
+    text: &'a str,
+    position: usize,
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn parse(text: &'a str) -> Result<Value, ScenarioError> {
+        let mut parser = Self { text, position: 0, depth: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.position < text.len() {
+            return Err(parser.error("unexpected text after the script"));
+        }
+        Ok(value)
+    }
+
+    fn error(&self, message: impl Into<String>) -> ScenarioError {
+        ScenarioError::syntax(self.text[..self.position].matches('\n').count() + 1, message)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ScenarioError> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(self.error(format!("expected '{}', found '{}'", expected, c))),
+            None => Err(self.error(format!("expected '{}', found the end of the script", expected))),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ScenarioError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.nested(Self::object),
+            Some('[') => self.nested(Self::array),
+            Some('"') => Ok(Value::String(self.string()?)),
+            Some('t') | Some('f') => self.boolean(),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) => Err(self.error(format!("unexpected '{}'", c))),
+            None => Err(self.error("unexpected end of the script")),
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Value, ScenarioError>) -> Result<Value, ScenarioError> {
+        if self.depth == MAX_JSON_DEPTH {
+            return Err(self.error("arrays and objects are nested too deeply"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<Value, ScenarioError> {
+        self.expect('{')?;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.next();
+            return Ok(Value::Table(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a quoted key"));
+            }
+            let key = self.string()?;
+            if entries.iter().any(|(existing, _)| *existing == key) {
+                return Err(self.error(format!("duplicate key \"{}\"", key)));
+            }
+            self.expect(':')?;
+            entries.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Value::Table(entries)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, ScenarioError> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.next();
+            return Ok(Value::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Value::Array(items)),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ScenarioError> {
+        self.next();
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => {
+                    let escaped = match self.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => {
+                            let digits = self.text.get(self.position..self.position + 4).unwrap_or("");
+                            let code = u32::from_str_radix(digits, 16).ok().and_then(char::from_u32);
+                            self.position += digits.len();
+                            code.ok_or_else(|| self.error("invalid unicode escape"))?
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    string.push(escaped);
+                }
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    fn boolean(&mut self) -> Result<Value, ScenarioError> {
+        for (literal, value) in [("true", true), ("false", false)] {
+            if self.text[self.position..].starts_with(literal) {
+                self.position += literal.len();
+                return Ok(Value::Bool(value));
+            }
+        }
+        Err(self.error("expected true or false"))
+    }
+
+    fn number(&mut self) -> Result<Value, ScenarioError> {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+            self.next();
+        }
+        let literal = &self.text[start..self.position];
+        literal
+            .parse()
+            .map(Value::Number)
+            .map_err(|_| self.error(format!("invalid number '{}'", literal)))
+    }
+}
+
+/// Parser for the TOML subset scripts use: `key = value` pairs, `[table]`
+/// headers and `[[array]]` tables, with strings, numbers, booleans and
+/// single-line arrays as values
+struct TomlParser;
+
+impl TomlParser {
+    fn parse(text: &str) -> Result<Value, ScenarioError> {
+        let mut root: Vec<(String, Value)> = Vec::new();
+        // Key path of the table the following pairs go into, and whether it is
+        // the last element of an array of tables
+        let mut current: Option<(String, bool)> = None;
+        for (index, raw_line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = Self::strip_comment(raw_line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+                let name = Self::key(name, line_number)?;
+                match root.iter_mut().find(|(key, _)| *key == name) {
+                    Some((_, Value::Array(tables))) => tables.push(Value::Table(Vec::new())),
+                    Some(_) => return Err(ScenarioError::syntax(line_number, format!("\"{}\" is not an array", name))),
+                    None => root.push((name.clone(), Value::Array(vec![Value::Table(Vec::new())]))),
+                }
+                current = Some((name, true));
+            } else if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let name = Self::key(name, line_number)?;
+                if root.iter().any(|(key, _)| *key == name) {
+                    return Err(ScenarioError::syntax(line_number, format!("duplicate table \"{}\"", name)));
+                }
+                root.push((name.clone(), Value::Table(Vec::new())));
+                current = Some((name, false));
+            } else {
+                let (key, value) = line
+                    .split_once('=')
+                    .ok_or_else(|| ScenarioError::syntax(line_number, "expected 'key = value'"))?;
+                let key = Self::key(key, line_number)?;
+                let value = Self::value(value.trim(), line_number)?;
+                let table = match &current {
+                    None => &mut root,
+                    Some((name, is_array)) => {
+                        let (_, table) = root.iter_mut().find(|(key, _)| key == name).expect("table was created");
+                        let table = match table {
+                            Value::Array(tables) if *is_array => tables.last_mut().expect("array has a table"),
+                            table => table,
+                        };
+                        match table {
+                            Value::Table(entries) => entries,
+                            _ => unreachable!("headers create tables"),
+                        }
+                    }
+                };
+                if table.iter().any(|(existing, _)| *existing == key) {
+                    return Err(ScenarioError::syntax(line_number, format!("duplicate key \"{}\"", key)));
+                }
+                table.push((key, value));
+            }
+        }
+        Ok(Value::Table(root))
+    }
+
+    /// Line without its comment, leaving '#' inside strings alone
+    fn strip_comment(line: &str) -> &str {
+        let mut quote = None;
+        for (i, c) in line.char_indices() {
+            match (quote, c) {
+                (None, '#') => return &line[..i],
+                (None, '"') | (None, '\'') => quote = Some(c),
+                (Some(q), c) if c == q => quote = None,
+                _ => {}
+            }
+        }
+        line
+    }
+
+    fn key(key: &str, line_number: usize) -> Result<String, ScenarioError> {
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(ScenarioError::syntax(line_number, format!("invalid key '{}'", key)));
+        }
+        Ok(key.to_string())
+    }
+
+    fn value(value: &str, line_number: usize) -> Result<Value, ScenarioError> {
+        let error = |message: String| ScenarioError::syntax(line_number, message);
+        if let Some(items) = value.strip_prefix('[') {
+            let items = items.strip_suffix(']').ok_or_else(|| error("unterminated array".to_string()))?;
+            return items
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Self::value(item, line_number))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array);
+        }
+        if let Some(string) = value.strip_prefix('\'') {
+            return string
+                .strip_suffix('\'')
+                .map(|s| Value::String(s.to_string()))
+                .ok_or_else(|| error("unterminated string".to_string()));
+        }
+        if value.starts_with('"') {
+            // Basic strings share JSON's escapes
+            return match JsonParser::parse(value) {
+                Ok(Value::String(string)) => Ok(Value::String(string)),
+                _ => Err(error(format!("invalid string {}", value))),
+            };
+        }
+        match value {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ => value
+                .replace('_', "")
+                .parse()
+                .map(Value::Number)
+                .map_err(|_| error(format!("invalid value '{}'", value))),
+        }
+    }
+}
+
+/// Parameters of one event, taken one at a time so leftovers can be reported
+struct Fields {
+    event: usize,
+    entries: Vec<(String, Value)>,
+}
+
+impl Fields {
+    fn error(&self, message: impl fmt::Display) -> ScenarioError {
+        ScenarioError::invalid(format!("event {}: {}", self.event + 1, message))
+    }
+
+    fn take(&mut self, key: &str) -> Option<Value> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    fn number(&mut self, key: &str, default: Option<f64>) -> Result<f64, ScenarioError> {
+        match (self.take(key), default) {
+            (Some(Value::Number(number)), _) => Ok(number),
+            (Some(value), _) => Err(self.error(format!("\"{}\" must be a number, not {}", key, value.kind()))),
+            (None, Some(default)) => Ok(default),
+            (None, None) => Err(self.error(format!("missing \"{}\"", key))),
+        }
+    }
+
+    fn string(&mut self, key: &str, default: Option<&str>) -> Result<String, ScenarioError> {
+        match (self.take(key), default) {
+            (Some(Value::String(string)), _) => Ok(string),
+            (Some(value), _) => Err(self.error(format!("\"{}\" must be a string, not {}", key, value.kind()))),
+            (None, Some(default)) => Ok(default.to_string()),
+            (None, None) => Err(self.error(format!("missing \"{}\"", key))),
+        }
+    }
+
+    fn boolean(&mut self, key: &str, default: bool) -> Result<bool, ScenarioError> {
+        match self.take(key) {
+            Some(Value::Bool(value)) => Ok(value),
+            Some(value) => Err(self.error(format!("\"{}\" must be a boolean, not {}", key, value.kind()))),
+            None => Ok(default),
+        }
+    }
+
+    /// Look a string parameter up among named choices
+    fn choice<T: Copy>(&mut self, key: &str, default: Option<&str>, choices: &[(&str, T)]) -> Result<T, ScenarioError> {
+        let name = self.string(key, default)?;
+        choices
+            .iter()
+            .find(|(choice, _)| choice.eq_ignore_ascii_case(&name))
+            .map(|&(_, value)| value)
+            .ok_or_else(|| {
+                let names: Vec<&str> = choices.iter().map(|(choice, _)| *choice).collect();
+                self.error(format!("unknown {} \"{}\" (expected one of {})", key, name, names.join(", ")))
+            })
+    }
+
+    fn finish(self) -> Result<(), ScenarioError> {
+        match self.entries.first() {
+            Some((key, _)) => Err(self.error(format!("unexpected parameter \"{}\"", key))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Comparison of a condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparison {
+    /// Operator as written in scripts
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+        }
+    }
+
+    fn holds(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Greater => value > threshold,
+            Comparison::GreaterOrEqual => value >= threshold,
+            Comparison::Less => value < threshold,
+            Comparison::LessOrEqual => value <= threshold,
+        }
+    }
+}
+
+/// Quantity a condition reads from the patient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantity {
+    /// Vital sign or hemodynamic output
+    Vital(Measure),
+    /// Laboratory analyte, read without sampling delay or noise
+    Lab(Analyte),
+}
+
+impl Quantity {
+    /// Look a quantity up by its script name
+    ///
+    /// # Arguments
+    /// * `name` - Vital sign name (e.g. "map") or analyte name (e.g. "K" or "potassium")
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(&(_, measure)) = VITAL_SIGNS.iter().find(|(vital, _)| vital.eq_ignore_ascii_case(name)) {
+            return Some(Quantity::Vital(measure));
+        }
+        LabPanel::ALL
+            .iter()
+            .flat_map(|panel| panel.analytes().iter())
+            .find(|analyte| analyte.name().eq_ignore_ascii_case(name) || format!("{:?}", analyte).eq_ignore_ascii_case(name))
+            .map(|&analyte| Quantity::Lab(analyte))
+    }
+
+    /// Current value in the patient
+    pub fn read(&self, patient: &Patient) -> f64 {
+        match self {
+            Quantity::Vital(measure) => measure.measure(patient),
+            Quantity::Lab(analyte) => analyte.read(patient),
+        }
+    }
+
+    /// Name of the quantity
+    pub fn name(&self) -> &'static str {
+        match self {
+            Quantity::Vital(measure) => VITAL_SIGNS
+                .iter()
+                .find(|(_, vital)| vital == measure)
+                .map_or("vital", |(name, _)| name),
+            Quantity::Lab(analyte) => analyte.name(),
+        }
+    }
+}
+
+/// Threshold on a quantity that triggers an event
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Condition {
+    /// Quantity compared
+    pub quantity: Quantity,
+    /// How it is compared
+    pub comparison: Comparison,
+    /// Value it is compared with
+    pub threshold: f64,
+}
+
+impl Condition {
+    /// Parse a condition such as "K > 6.0" or "map<65"
+    ///
+    /// # Returns
+    /// The condition, or None if the quantity, operator or threshold is not recognized
+    pub fn parse(text: &str) -> Option<Self> {
+        let operators = [
+            (">=", Comparison::GreaterOrEqual),
+            ("<=", Comparison::LessOrEqual),
+            (">", Comparison::Greater),
+            ("<", Comparison::Less),
+        ];
+        let (quantity, comparison, threshold) = operators
+            .iter()
+            .find_map(|&(symbol, comparison)| text.split_once(symbol).map(|(q, t)| (q, comparison, t)))?;
+        Some(Self {
+            quantity: Quantity::from_name(quantity.trim())?,
+            comparison,
+            threshold: threshold.trim().parse().ok()?,
+        })
+    }
+
+    /// Whether the condition holds in the patient now
+    pub fn is_met(&self, patient: &Patient) -> bool {
+        self.comparison.holds(self.quantity.read(patient), self.threshold)
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.quantity.name(), self.comparison.symbol(), self.threshold)
+    }
+}
+
+/// When an event fires
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    /// At a scenario time (s)
+    At(f64),
+    /// The first time a condition holds
+    When(Condition),
+}
+
+/// Intervention or insult applied by an event
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// Occlude a coronary artery (`occlude_coronary`: `territory` LAD, LCx or
+    /// RCA; `severity` 0-1, complete by default)
+    OccludeCoronary { territory: CoronaryTerritory, severity: f64 },
+    /// Ventricular fibrillation (`induce_ventricular_fibrillation`)
+    InduceVentricularFibrillation,
+    /// Asystole (`induce_asystole`)
+    InduceAsystole,
+    /// Chronic heart failure (`induce_heart_failure`: `phenotype` reduced or
+    /// preserved, `severity` 0-1)
+    InduceHeartFailure { phenotype: HeartFailurePhenotype, severity: f64 },
+    /// Damage a lung lobe (`damage_lung`: `lobe` index, `severity` 0-1)
+    DamageLung { lobe: usize, severity: f64 },
+    /// Blood loss (`hemorrhage`: `volume_ml`)
+    Hemorrhage { volume_ml: f64 },
+    /// Start an infection (`start_infection`: `source`, `virulence` 1 by
+    /// default, `guideline` hour_one or sep1)
+    StartInfection { source: String, virulence: f64, guideline: BundleGuideline },
+    /// Fluid (`give_fluid`: `volume_ml`, `route` iv, oral, enteral or blood)
+    GiveFluid { route: IntakeRoute, volume_ml: f64 },
+    /// Meal (`eat`: `carbs_g`, `protein_g`, `fat_g`, `water_ml` and `sodium_mg`,
+    /// each 0 by default)
+    Eat { meal: Meal },
+    /// Platelets (`transfuse_platelets`: `units`)
+    TransfusePlatelets { units: f64 },
+    /// Fresh frozen plasma (`transfuse_plasma`: `units`)
+    TransfusePlasma { units: f64 },
+    /// Cryoprecipitate (`transfuse_cryoprecipitate`: `units`)
+    TransfuseCryoprecipitate { units: f64 },
+    /// Blood cultures (`draw_blood_cultures`)
+    DrawBloodCultures,
+    /// Antibiotics (`give_antibiotics`: `covers_organism`, true by default)
+    GiveAntibiotics { covers_organism: bool },
+    /// Norepinephrine infusion rate (`norepinephrine`: `mcg_kg_min`)
+    Norepinephrine { mcg_kg_min: f64 },
+    /// Nitroprusside infusion rate (`nitroprusside`: `mcg_kg_min`)
+    Nitroprusside { mcg_kg_min: f64 },
+    /// Intravenous insulin infusion rate (`insulin_infusion`: `units_per_hr`)
+    InsulinInfusion { units_per_hr: f64 },
+    /// Furosemide bolus (`give_furosemide`: `mg`, 40 by default)
+    GiveFurosemide { mg: f64 },
+    /// Inspired oxygen fraction (`set_fio2`: `fio2`)
+    SetFio2 { fio2: f64 },
+    /// Exercise intensity (`exercise`: `intensity` 0-1)
+    Exercise { intensity: f64 },
+    /// Start compressions (`start_cpr`)
+    StartCpr,
+    /// Stop compressions (`stop_cpr`)
+    StopCpr,
+    /// Shock (`defibrillate`: `energy_j`, 200 by default)
+    Defibrillate { energy_j: f64 },
+    /// Epinephrine bolus (`give_epinephrine`: `mg`, 1 by default)
+    GiveEpinephrine { mg: f64 },
+    /// Amiodarone bolus (`give_amiodarone`: `mg`, 300 by default)
+    GiveAmiodarone { mg: f64 },
+    /// Lab order (`order_labs`: `panel` cbc, cmp, abg, vbg, troponin, coagulation or lactate)
+    OrderLabs { panel: LabPanel },
+}
+
+impl Action {
+    fn parse(name: &str, fields: &mut Fields) -> Result<Self, ScenarioError> {
+        let territories = [
+            ("LAD", CoronaryTerritory::LeftAnteriorDescending),
+            ("LCx", CoronaryTerritory::LeftCircumflex),
+            ("RCA", CoronaryTerritory::RightCoronary),
+        ];
+        let phenotypes = [
+            ("reduced", HeartFailurePhenotype::ReducedEjectionFraction),
+            ("preserved", HeartFailurePhenotype::PreservedEjectionFraction),
+        ];
+        let guidelines = [("hour_one", BundleGuideline::HourOne), ("sep1", BundleGuideline::Sep1)];
+        let routes = [
+            ("iv", IntakeRoute::Intravenous),
+            ("oral", IntakeRoute::Oral),
+            ("enteral", IntakeRoute::Enteral),
+            ("blood", IntakeRoute::BloodProduct),
+        ];
+        let panels = [
+            ("cbc", LabPanel::Cbc),
+            ("cmp", LabPanel::Cmp),
+            ("abg", LabPanel::Abg),
+            ("vbg", LabPanel::Vbg),
+            ("troponin", LabPanel::Troponin),
+            ("coagulation", LabPanel::Coagulation),
+            ("lactate", LabPanel::Lactate),
+            ("hba1c", LabPanel::Hba1c),
+            ("lipase", LabPanel::Lipase),
+            ("ck", LabPanel::Ck),
+            ("myoglobin", LabPanel::Myoglobin),
+            ("digoxin", LabPanel::Digoxin),
+            ("lithium", LabPanel::Lithium),
+            ("phenytoin", LabPanel::Phenytoin),
+            ("acetaminophen", LabPanel::Acetaminophen),
+            ("salicylate", LabPanel::Salicylate),
+        ];
+        let action = match name {
+            "occlude_coronary" => Action::OccludeCoronary {
+                territory: fields.choice("territory", None, &territories)?,
+                severity: fields.number("severity", Some(1.0))?.clamp(0.0, 1.0),
+            },
+            "induce_ventricular_fibrillation" => Action::InduceVentricularFibrillation,
+            "induce_asystole" => Action::InduceAsystole,
+            "induce_heart_failure" => Action::InduceHeartFailure {
+                phenotype: fields.choice("phenotype", None, &phenotypes)?,
+                severity: fields.number("severity", None)?,
+            },
+            "damage_lung" => Action::DamageLung {
+                lobe: fields.number("lobe", Some(0.0))?.max(0.0) as usize,
+                severity: fields.number("severity", None)?,
+            },
+            "hemorrhage" => Action::Hemorrhage {
+                volume_ml: fields.number("volume_ml", None)?,
+            },
+            "start_infection" => Action::StartInfection {
+                source: fields.string("source", None)?,
+                virulence: fields.number("virulence", Some(1.0))?,
+                guideline: fields.choice("guideline", Some("hour_one"), &guidelines)?,
+            },
+            "give_fluid" => Action::GiveFluid {
+                route: fields.choice("route", Some("iv"), &routes)?,
+                volume_ml: fields.number("volume_ml", None)?,
+            },
+            "eat" => Action::Eat {
+                meal: Meal::new(
+                    fields.number("carbs_g", Some(0.0))?,
+                    fields.number("protein_g", Some(0.0))?,
+                    fields.number("fat_g", Some(0.0))?,
+                    fields.number("water_ml", Some(0.0))?,
+                    fields.number("sodium_mg", Some(0.0))?,
+                ),
+            },
+            "transfuse_platelets" => Action::TransfusePlatelets {
+                units: fields.number("units", Some(1.0))?,
+            },
+            "transfuse_plasma" => Action::TransfusePlasma {
+                units: fields.number("units", Some(1.0))?,
+            },
+            "transfuse_cryoprecipitate" => Action::TransfuseCryoprecipitate {
+                units: fields.number("units", Some(1.0))?,
+            },
+            "draw_blood_cultures" => Action::DrawBloodCultures,
+            "give_antibiotics" => Action::GiveAntibiotics {
+                covers_organism: fields.boolean("covers_organism", true)?,
+            },
+            "norepinephrine" => Action::Norepinephrine {
+                mcg_kg_min: fields.number("mcg_kg_min", None)?,
+            },
+            "nitroprusside" => Action::Nitroprusside {
+                mcg_kg_min: fields.number("mcg_kg_min", None)?,
+            },
+            "insulin_infusion" => Action::InsulinInfusion {
+                units_per_hr: fields.number("units_per_hr", None)?,
+            },
+            "give_furosemide" => Action::GiveFurosemide {
+                mg: fields.number("mg", Some(40.0))?,
+            },
+            "set_fio2" => Action::SetFio2 {
+                fio2: fields.number("fio2", None)?,
+            },
+            "exercise" => Action::Exercise {
+                intensity: fields.number("intensity", None)?,
+            },
+            "start_cpr" => Action::StartCpr,
+            "stop_cpr" => Action::StopCpr,
+            "defibrillate" => Action::Defibrillate {
+                energy_j: fields.number("energy_j", Some(200.0))?,
+            },
+            "give_epinephrine" => Action::GiveEpinephrine {
+                mg: fields.number("mg", Some(1.0))?,
+            },
+            "give_amiodarone" => Action::GiveAmiodarone {
+                mg: fields.number("mg", Some(300.0))?,
+            },
+            "order_labs" => Action::OrderLabs {
+                panel: fields.choice("panel", None, &panels)?,
+            },
+            _ => return Err(fields.error(format!("unknown action \"{}\"", name))),
+        };
+        Ok(action)
+    }
+
+    /// Whether the action is an insult scripted to create the case, rather than treatment
+    pub fn is_insult(&self) -> bool {
+        matches!(
+            self,
+            Action::OccludeCoronary { .. }
+                | Action::InduceVentricularFibrillation
+                | Action::InduceAsystole
+                | Action::InduceHeartFailure { .. }
+                | Action::DamageLung { .. }
+                | Action::Hemorrhage { .. }
+                | Action::StartInfection { .. }
+        )
+    }
+
+    /// Apply the action to a patient
+    pub fn apply(&self, patient: &mut Patient) {
+        let heart = |patient: &mut Patient, act: &dyn Fn(&mut Heart)| {
+            if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                act(heart);
+            }
+        };
+        match self {
+            Action::OccludeCoronary { territory, severity } => {
+                heart(patient, &|h| h.set_territory_contractility(*territory, 1.0 - severity));
+            }
+            Action::InduceVentricularFibrillation => heart(patient, &|h| h.induce_ventricular_fibrillation()),
+            Action::InduceAsystole => heart(patient, &|h| h.induce_asystole()),
+            Action::InduceHeartFailure { phenotype, severity } => patient.induce_heart_failure(*phenotype, *severity),
+            Action::DamageLung { lobe, severity } => {
+                if let Some(lungs) = patient.get_organ_mut::<Lungs>() {
+                    lungs.inflict_damage(*lobe, *severity);
+                }
+            }
+            Action::Hemorrhage { volume_ml } => {
+                patient.hemorrhage(*volume_ml);
+            }
+            Action::StartInfection {
+                source,
+                virulence,
+                guideline,
+            } => patient.start_infection(source, *virulence, *guideline),
+            Action::GiveFluid { route, volume_ml } => patient.give_fluid(*route, *volume_ml),
+            Action::Eat { meal } => patient.eat(*meal),
+            Action::TransfusePlatelets { units } => patient.transfuse_platelets(*units),
+            Action::TransfusePlasma { units } => patient.transfuse_plasma(*units),
+            Action::TransfuseCryoprecipitate { units } => patient.transfuse_cryoprecipitate(*units),
+            Action::DrawBloodCultures => patient.draw_blood_cultures(),
+            Action::GiveAntibiotics { covers_organism } => patient.give_antibiotics(*covers_organism),
+            Action::Norepinephrine { mcg_kg_min } => patient.set_norepinephrine_infusion(*mcg_kg_min),
+            Action::Nitroprusside { mcg_kg_min } => patient.set_nitroprusside_infusion(*mcg_kg_min),
+            Action::InsulinInfusion { units_per_hr } => patient.set_insulin_infusion(*units_per_hr),
+            Action::GiveFurosemide { mg } => patient.give_furosemide(*mg),
+            Action::SetFio2 { fio2 } => {
+                if let Some(lungs) = patient.get_organ_mut::<Lungs>() {
+                    lungs.set_fio2(*fio2);
+                }
+            }
+            Action::Exercise { intensity } => patient.set_exercise_intensity(*intensity),
+            Action::StartCpr => patient.start_cpr(),
+            Action::StopCpr => patient.stop_cpr(),
+            Action::Defibrillate { energy_j } => {
+                patient.defibrillate(*energy_j);
+            }
+            Action::GiveEpinephrine { mg } => patient.give_epinephrine(*mg),
+            Action::GiveAmiodarone { mg } => patient.give_amiodarone(*mg),
+            Action::OrderLabs { panel } => {
+                patient.order_labs(*panel);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::OccludeCoronary { territory, severity } => {
+                write!(f, "Occlude {:?} ({:.0}%)", territory, severity * 100.0)
+            }
+            Action::InduceVentricularFibrillation => write!(f, "Ventricular fibrillation"),
+            Action::InduceAsystole => write!(f, "Asystole"),
+            Action::InduceHeartFailure { phenotype, severity } => {
+                write!(f, "Heart failure, {:?} (severity {:.2})", phenotype, severity)
+            }
+            Action::DamageLung { lobe, severity } => write!(f, "Lung lobe {} damage {:.0}%", lobe, severity * 100.0),
+            Action::Hemorrhage { volume_ml } => write!(f, "Hemorrhage {:.0} mL", volume_ml),
+            Action::StartInfection { source, .. } => write!(f, "Infection: {}", source),
+            Action::GiveFluid { route, volume_ml } => write!(f, "{:?} fluid {:.0} mL", route, volume_ml),
+            Action::Eat { meal } => write!(f, "Meal of {:.0} kcal", meal.energy_kcal()),
+            Action::TransfusePlatelets { units } => write!(f, "Transfuse {} unit(s) of platelets", units),
+            Action::TransfusePlasma { units } => write!(f, "Transfuse {} unit(s) of plasma", units),
+            Action::TransfuseCryoprecipitate { units } => write!(f, "Transfuse {} unit(s) of cryoprecipitate", units),
+            Action::DrawBloodCultures => write!(f, "Draw blood cultures"),
+            Action::GiveAntibiotics { covers_organism } => {
+                write!(f, "Antibiotics ({})", if *covers_organism { "covering" } else { "not covering" })
+            }
+            Action::Norepinephrine { mcg_kg_min } => write!(f, "Norepinephrine {} mcg/kg/min", mcg_kg_min),
+            Action::Nitroprusside { mcg_kg_min } => write!(f, "Nitroprusside {} mcg/kg/min", mcg_kg_min),
+            Action::InsulinInfusion { units_per_hr } => write!(f, "Insulin {} units/h IV", units_per_hr),
+            Action::GiveFurosemide { mg } => write!(f, "Furosemide {} mg IV", mg),
+            Action::SetFio2 { fio2 } => write!(f, "FiO2 {:.2}", fio2),
+            Action::Exercise { intensity } => write!(f, "Exercise at {:.0}%", intensity * 100.0),
+            Action::StartCpr => write!(f, "Start CPR"),
+            Action::StopCpr => write!(f, "Stop CPR"),
+            Action::Defibrillate { energy_j } => write!(f, "Defibrillate {} J", energy_j),
+            Action::GiveEpinephrine { mg } => write!(f, "Epinephrine {} mg IV", mg),
+            Action::GiveAmiodarone { mg } => write!(f, "Amiodarone {} mg IV", mg),
+            Action::OrderLabs { panel } => write!(f, "Order {:?} panel", panel),
+        }
+    }
+}
+
+/// Scripted event
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioEvent {
+    /// When the event fires
+    pub trigger: Trigger,
+    /// What it does
+    pub action: Action,
+}
+
+/// Scenario script
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenario {
+    /// Name of the scenario
+    pub name: String,
+    /// Length of the scenario (s)
+    pub duration_s: f64,
+    /// Simulation time step (s)
+    pub time_step_s: f64,
+    /// Events, in script order
+    pub events: Vec<ScenarioEvent>,
+}
+
+impl Scenario {
+    /// Load a script, as JSON if it starts with '{' and as TOML otherwise
+    ///
+    /// # Arguments
+    /// * `text` - Script text
+    pub fn parse(text: &str) -> Result<Self, ScenarioError> {
+        if text.trim_start().starts_with('{') {
+            Self::from_json(text)
+        } else {
+            Self::from_toml(text)
+        }
+    }
+
+    /// Load a JSON script
+    ///
+    /// # Arguments
+    /// * `text` - Script text
+    pub fn from_json(text: &str) -> Result<Self, ScenarioError> {
+        Self::from_value(JsonParser::parse(text)?)
+    }
+
+    /// Load a TOML script
+    ///
+    /// # Arguments
+    /// * `text` - Script text
+    pub fn from_toml(text: &str) -> Result<Self, ScenarioError> {
+        Self::from_value(TomlParser::parse(text)?)
+    }
+
+    fn from_value(value: Value) -> Result<Self, ScenarioError> {
+        let Value::Table(entries) = value else {
+            return Err(ScenarioError::invalid("the script must be a table"));
+        };
+        let mut name = "Scenario".to_string();
+        let mut duration_s = None;
+        let mut time_step_s = DEFAULT_TIME_STEP_S;
+        let mut events = Vec::new();
+        for (key, value) in entries {
+            match (key.as_str(), value) {
+                ("name", Value::String(string)) => name = string,
+                ("duration_s", Value::Number(number)) if number > 0.0 && number.is_finite() => duration_s = Some(number),
+                ("time_step_s", Value::Number(number)) if number > 0.0 && number.is_finite() => time_step_s = number,
+                ("events", Value::Array(items)) => {
+                    for (index, item) in items.into_iter().enumerate() {
+                        events.push(Self::event(index, item)?);
+                    }
+                }
+                ("name" | "duration_s" | "time_step_s" | "events", value) => {
+                    return Err(ScenarioError::invalid(format!("invalid \"{}\": {}", key, value.kind())));
+                }
+                _ => return Err(ScenarioError::invalid(format!("unexpected key \"{}\"", key))),
+            }
+        }
+        Ok(Self {
+            name,
+            duration_s: duration_s.ok_or_else(|| ScenarioError::invalid("missing \"duration_s\""))?,
+            time_step_s,
+            events,
+        })
+    }
+
+    fn event(index: usize, value: Value) -> Result<ScenarioEvent, ScenarioError> {
+        let Value::Table(entries) = value else {
+            return Err(ScenarioError::invalid(format!("event {}: must be a table", index + 1)));
+        };
+        let mut fields = Fields { event: index, entries };
+        let at = match fields.take("at") {
+            Some(Value::Number(at)) => Some(at),
+            Some(value) => return Err(fields.error(format!("\"at\" must be a number, not {}", value.kind()))),
+            None => None,
+        };
+        let when = match fields.take("when") {
+            Some(Value::String(when)) => {
+                Some(Condition::parse(&when).ok_or_else(|| fields.error(format!("invalid condition \"{}\"", when)))?)
+            }
+            Some(value) => return Err(fields.error(format!("\"when\" must be a string, not {}", value.kind()))),
+            None => None,
+        };
+        let trigger = match (at, when) {
+            (Some(at), None) => Trigger::At(at),
+            (None, Some(condition)) => Trigger::When(condition),
+            _ => return Err(fields.error("needs exactly one of \"at\" or \"when\"")),
+        };
+        let name = fields.string("action", None)?;
+        let action = Action::parse(&name, &mut fields)?;
+        fields.finish()?;
+        Ok(ScenarioEvent { trigger, action })
+    }
+
+    /// Run the whole scenario on a patient
+    ///
+    /// # Returns
+    /// The events that fired, in order
+    pub fn run(&self, patient: &mut Patient) -> Vec<FiredEvent> {
+        let mut runner = ScenarioRunner::new(self.clone());
+        runner.run(patient);
+        runner.log
+    }
+}
+
+/// Record of an event that fired
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiredEvent {
+    /// Scenario time it fired at (s)
+    pub time_s: f64,
+    /// Index of the event in the script
+    pub event: usize,
+    /// What it did
+    pub action: Action,
+}
+
+impl fmt::Display for FiredEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:.1} s] {}", self.time_s, self.action)
+    }
+}
+
+/// Advances a patient through a scenario
+#[derive(Debug, Clone)]
+pub struct ScenarioRunner {
+    /// Scenario being run
+    pub scenario: Scenario,
+    /// Scenario time elapsed (s)
+    pub time_s: f64,
+    /// Whether each event has fired
+    fired: Vec<bool>,
+    /// Events fired so far
+    log: Vec<FiredEvent>,
+}
+
+impl ScenarioRunner {
+    /// Start a scenario at time zero
+    pub fn new(scenario: Scenario) -> Self {
+        Self {
+            fired: vec![false; scenario.events.len()],
+            scenario,
+            time_s: 0.0,
+            log: Vec::new(),
+        }
+    }
+
+    /// Whether the scenario has run its duration
+    pub fn is_finished(&self) -> bool {
+        self.time_s + self.scenario.time_step_s / 2.0 >= self.scenario.duration_s
+    }
+
+    /// Fire the events that are due, then advance the patient one time step
+    ///
+    /// # Arguments
+    /// * `patient` - Patient the scenario drives
+    ///
+    /// # Returns
+    /// The events fired this step
+    pub fn step(&mut self, patient: &mut Patient) -> &[FiredEvent] {
+        let first = self.log.len();
+        if self.is_finished() {
+            return &self.log[first..];
+        }
+        let dt = self.scenario.time_step_s;
+        for (index, event) in self.scenario.events.iter().enumerate() {
+            if self.fired[index] {
+                continue;
+            }
+            let due = match &event.trigger {
+                Trigger::At(at_s) => *at_s < self.time_s + dt / 2.0,
+                Trigger::When(condition) => condition.is_met(patient),
+            };
+            if due {
+                event.action.apply(patient);
+                self.fired[index] = true;
+                self.log.push(FiredEvent {
+                    time_s: self.time_s,
+                    event: index,
+                    action: event.action.clone(),
+                });
+            }
+        }
+        update_patient(patient, dt);
+        self.time_s += dt;
+        &self.log[first..]
+    }
+
+    /// Run the rest of the scenario
+    pub fn run(&mut self, patient: &mut Patient) {
+        while !self.is_finished() {
+            self.step(patient);
+        }
+    }
+
+    /// Events fired so far, in order
+    pub fn log(&self) -> &[FiredEvent] {
+        &self.log
+    }
+
+    /// Get the event log as text, one event per line
+    pub fn get_summary(&self) -> String {
+        let mut summary = format!("Scenario: {} ({:.0}/{:.0} s)", self.scenario.name, self.time_s, self.scenario.duration_s);
+        for event in &self.log {
+            summary.push_str(&format!("\n  {}", event));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::initialize_patient;
+
+    const STEMI_TOML: &str = r#"
+        # Anterior STEMI with cardiogenic shock
+        name = "Anterior STEMI"
+        duration_s = 120
+        time_step_s = 0.5
+
+        [[events]]
+        at = 10
+        action = "occlude_coronary"
+        territory = "LAD"
+
+        [[events]]
+        when = "map < 85"
+        action = "norepinephrine"
+        mcg_kg_min = 0.1
+
+        [[events]]
+        at = 30
+        action = "order_labs"
+        panel = "troponin"
+    "#;
+
+    #[test]
+    fn test_toml_and_json_scripts_load_the_same_scenario() {
+        let json = r#"{
+            "name": "Anterior STEMI",
+            "duration_s": 120,
+            "time_step_s": 0.5,
+            "events": [
+                {"at": 10, "action": "occlude_coronary", "territory": "LAD"},
+                {"when": "map < 85", "action": "norepinephrine", "mcg_kg_min": 0.1},
+                {"at": 30, "action": "order_labs", "panel": "troponin"}
+            ]
+        }"#;
+        let scenario = Scenario::parse(STEMI_TOML).unwrap();
+        assert_eq!(Scenario::parse(json).unwrap(), scenario);
+        assert_eq!(scenario.events.len(), 3);
+        assert_eq!(
+            scenario.events[0].action,
+            Action::OccludeCoronary {
+                territory: CoronaryTerritory::LeftAnteriorDescending,
+                severity: 1.0
+            }
+        );
+        let Trigger::When(condition) = scenario.events[1].trigger else { panic!("expected a condition") };
+        assert_eq!(condition.quantity, Quantity::Vital(Measure::MeanArterialPressure));
+        assert_eq!(condition.comparison, Comparison::Less);
+        assert_eq!(Condition::parse("potassium >= 6").unwrap().quantity, Quantity::Lab(Analyte::Potassium));
+        assert_eq!(Condition::parse("K>6.0").unwrap().to_string(), "K > 6");
+    }
+
+    #[test]
+    fn test_events_fire_at_their_time_or_condition() {
+        let scenario = Scenario::parse(STEMI_TOML).unwrap();
+        let mut patient = initialize_patient(1, 3);
+        let mut runner = ScenarioRunner::new(scenario);
+        runner.run(&mut patient);
+        assert!(runner.is_finished());
+        assert!((runner.time_s - 120.0).abs() < 1e-6);
+
+        let log = runner.log();
+        assert_eq!(log.len(), 3);
+        assert_eq!(log[0].event, 0);
+        assert!((log[0].time_s - 10.0).abs() < 1e-6);
+        // Pressure falls after the occlusion, before the labs are ordered
+        assert_eq!(log[1].event, 1);
+        assert!(log[1].time_s > 10.0 && log[1].time_s < 30.0);
+        assert_eq!(log[2].event, 2);
+        assert!(patient.get_organ::<Heart>().unwrap().norepinephrine_mcg_kg_min > 0.0);
+        assert!(runner.get_summary().contains("Occlude LeftAnteriorDescending (100%)"));
+        assert!(patient.labs.is_pending(1) || patient.labs.report(1).is_some());
+    }
+
+    #[test]
+    fn test_invalid_scripts_are_reported() {
+        let error = Scenario::from_json("{\"duration_s\": 10,\n \"events\": [}").unwrap_err();
+        assert_eq!(error.line, Some(2));
+
+        let error = Scenario::from_toml("duration_s = 10\n[[events]]\nat = 5\naction = \"start_dialysis\"").unwrap_err();
+        assert_eq!(error.to_string(), "event 1: unknown action \"start_dialysis\"");
+
+        let error = Scenario::from_toml("duration_s = 10\n[[events]]\nat = 5\naction = \"hemorrhage\"\nvolume = 500")
+            .unwrap_err();
+        assert_eq!(error.message, "event 1: missing \"volume_ml\"");
+
+        let error = Scenario::from_toml("duration_s = 10\n[[events]]\nwhen = \"lactate ~ 4\"\naction = \"start_cpr\"")
+            .unwrap_err();
+        assert!(error.message.contains("invalid condition"));
+
+        assert!(Scenario::from_toml("name = \"No duration\"").is_err());
+        assert_eq!(Scenario::from_toml("duration_s = oops").unwrap_err().line, Some(1));
+        assert!(Scenario::from_toml("duration_s = inf").is_err());
+        assert!(Scenario::from_json("{\"duration_s\": 1e999}").is_err());
+        assert!(Scenario::from_toml("duration_s = 10\ntime_step_s = inf").is_err());
+        assert!(Scenario::from_toml("duration_s = 10\ntime_step_s = nan").is_err());
+
+        let deep = format!("{{\"duration_s\": 10, \"events\": {}{}}}", "[".repeat(100_000), "]".repeat(100_000));
+        assert_eq!(Scenario::from_json(&deep).unwrap_err().message, "arrays and objects are nested too deeply");
+    }
+}
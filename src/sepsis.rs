@@ -0,0 +1,518 @@
+//! Sepsis and the sepsis bundle
+//!
+//! This module provides:
+//! - A bacterial infection that grows until effective antibiotics are given, and the
+//!   host response that follows it: vasodilation, lactate production, neutrophilia
+//!   and DIC
+//! - Sepsis onset ("time zero") when the host response crosses the threshold for
+//!   organ dysfunction
+//! - Bundle timers from time zero for blood cultures, lactate, antibiotics, fluid
+//!   resuscitation, vasopressors and repeat lactate, checked against the Hour-1
+//!   (Surviving Sepsis Campaign) or SEP-1 (3 h / 6 h) windows
+//! - Compliance metrics for quality-training scenarios
+
+use crate::patient::Patient;
+use crate::signals::Signal;
+
+/// Host response at which sepsis is recognised (time zero)
+pub const SEPSIS_ONSET_SEVERITY: f64 = 0.2;
+
+/// Bacterial doubling time at virulence 1.0 (s)
+const BACTERIAL_DOUBLING_S: f64 = 14_400.0;
+
+/// Delay before an effective antibiotic starts killing bacteria (s)
+const ANTIBIOTIC_ONSET_S: f64 = 3600.0;
+
+/// Bacterial killing rate with effective antibiotics (1/s, ~4 h time constant)
+const ANTIBIOTIC_KILL_PER_S: f64 = 1.0 / 14_400.0;
+
+/// Time constant of the host response following the bacterial load (s)
+const HOST_RESPONSE_S: f64 = 10_800.0;
+
+/// Lactate elevation above baseline at full host response (mmol/L)
+const SEPTIC_LACTATE_MMOL_L: f64 = 4.0;
+
+/// Crystalloid bolus recommended for sepsis-induced hypoperfusion (mL/kg)
+pub const RESUSCITATION_FLUID_ML_PER_KG: f64 = 30.0;
+
+/// Mean arterial pressure target (mmHg)
+pub const MAP_TARGET_MMHG: f64 = 65.0;
+
+/// Lactate defining hypoperfusion that mandates fluids (mmol/L)
+const HYPOPERFUSION_LACTATE_MMOL_L: f64 = 4.0;
+
+/// Lactate above which a repeat measurement is required (mmol/L)
+const REPEAT_LACTATE_THRESHOLD_MMOL_L: f64 = 2.0;
+
+/// Guideline defining the bundle windows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BundleGuideline {
+    /// Surviving Sepsis Campaign Hour-1 bundle: everything within an hour
+    HourOne,
+    /// CMS SEP-1: initial elements within 3 h, vasopressors and repeat lactate within 6 h
+    Sep1,
+}
+
+/// Element of the sepsis bundle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BundleElement {
+    /// Blood cultures drawn before antibiotics
+    BloodCultures,
+    /// Initial lactate measured
+    Lactate,
+    /// Broad-spectrum antibiotics given
+    Antibiotics,
+    /// 30 mL/kg crystalloid for hypotension or lactate >= 4 mmol/L
+    FluidResuscitation,
+    /// Vasopressors for hypotension persisting after fluids
+    Vasopressors,
+    /// Lactate remeasured when the initial value was above 2 mmol/L
+    RepeatLactate,
+}
+
+impl BundleElement {
+    /// All elements in bundle order
+    pub const ALL: [BundleElement; 6] = [
+        BundleElement::BloodCultures,
+        BundleElement::Lactate,
+        BundleElement::Antibiotics,
+        BundleElement::FluidResuscitation,
+        BundleElement::Vasopressors,
+        BundleElement::RepeatLactate,
+    ];
+
+    /// Window from time zero in which the element must be completed (s)
+    ///
+    /// # Arguments
+    /// * `guideline` - Guideline defining the windows
+    pub fn window_s(&self, guideline: BundleGuideline) -> f64 {
+        match guideline {
+            BundleGuideline::HourOne => 3600.0,
+            BundleGuideline::Sep1 => match self {
+                BundleElement::Vasopressors | BundleElement::RepeatLactate => 6.0 * 3600.0,
+                _ => 3.0 * 3600.0,
+            },
+        }
+    }
+}
+
+/// Compliance state of one bundle element
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElementCompliance {
+    /// Not indicated for this patient
+    NotRequired,
+    /// Indicated, not yet done, window still open
+    Pending,
+    /// Done within the window
+    Met,
+    /// Done late, out of order, or not done when the window closed
+    Missed,
+}
+
+/// Status of one bundle element
+#[derive(Debug, Clone)]
+pub struct BundleElementStatus {
+    /// The element
+    pub element: BundleElement,
+    /// Whether the element is indicated
+    pub required: bool,
+    /// Time from time zero at which it was completed (s; negative if done before onset)
+    pub completed_after_onset_s: Option<f64>,
+    /// Time from time zero by which it was due (s)
+    pub deadline_after_onset_s: f64,
+    /// Compliance
+    pub compliance: ElementCompliance,
+}
+
+/// Bundle compliance report
+#[derive(Debug, Clone)]
+pub struct SepsisBundleReport {
+    /// Guideline used
+    pub guideline: BundleGuideline,
+    /// Time since time zero (s)
+    pub time_since_onset_s: f64,
+    /// Per-element status
+    pub elements: Vec<BundleElementStatus>,
+    /// Time from time zero to antibiotics (s), if given
+    pub antibiotic_delay_s: Option<f64>,
+}
+
+impl SepsisBundleReport {
+    /// Get the status of one element
+    pub fn element(&self, element: BundleElement) -> Option<&BundleElementStatus> {
+        self.elements.iter().find(|status| status.element == element)
+    }
+
+    /// Percentage of required elements met (100 when nothing is required yet)
+    pub fn compliance_percent(&self) -> f64 {
+        let required: Vec<_> = self.elements.iter().filter(|status| status.required).collect();
+        if required.is_empty() {
+            return 100.0;
+        }
+        let met = required.iter().filter(|status| status.compliance == ElementCompliance::Met).count();
+        met as f64 / required.len() as f64 * 100.0
+    }
+
+    /// Check whether every required element has been met (all-or-none bundle compliance)
+    pub fn all_met(&self) -> bool {
+        self.elements
+            .iter()
+            .all(|status| !status.required || status.compliance == ElementCompliance::Met)
+    }
+
+    /// Check whether any required element has been missed
+    pub fn any_missed(&self) -> bool {
+        self.elements.iter().any(|status| status.compliance == ElementCompliance::Missed)
+    }
+
+    /// Get a summary of the report
+    pub fn get_summary(&self) -> String {
+        let elements: Vec<String> = self
+            .elements
+            .iter()
+            .filter(|status| status.required)
+            .map(|status| {
+                let timing = match status.completed_after_onset_s {
+                    Some(time) => format!(" at {:.0} min", time / 60.0),
+                    None => String::new(),
+                };
+                format!("{:?} {:?}{}", status.element, status.compliance, timing)
+            })
+            .collect();
+        format!(
+            "Sepsis bundle ({:?}, T+{:.0} min): {:.0}% compliant{} - {}",
+            self.guideline,
+            self.time_since_onset_s / 60.0,
+            self.compliance_percent(),
+            if self.all_met() { ", ALL MET" } else { "" },
+            elements.join(", ")
+        )
+    }
+}
+
+/// Sepsis bundle timers and completion times
+///
+/// Times are on the infection clock (seconds since the infection started).
+#[derive(Debug, Clone)]
+pub struct SepsisBundle {
+    /// Guideline defining the windows
+    pub guideline: BundleGuideline,
+    /// Time zero, once sepsis is recognised (s)
+    pub onset_time_s: Option<f64>,
+    /// When blood cultures were drawn (s)
+    pub cultures_drawn_s: Option<f64>,
+    /// When the initial lactate was measured (s) and its value (mmol/L)
+    pub initial_lactate: Option<(f64, f64)>,
+    /// When the lactate was remeasured (s)
+    pub repeat_lactate_s: Option<f64>,
+    /// When antibiotics were first given (s)
+    pub antibiotics_given_s: Option<f64>,
+    /// Crystalloid given since time zero (mL)
+    pub fluid_given_ml: f64,
+    /// When the 30 mL/kg target was reached (s)
+    pub fluid_target_reached_s: Option<f64>,
+    /// When vasopressors were started (s)
+    pub vasopressors_started_s: Option<f64>,
+    /// Whether the MAP fell below 65 mmHg after time zero
+    pub hypotension: bool,
+    /// Whether hypotension persisted after the fluid target was reached
+    pub persistent_hypotension: bool,
+}
+
+impl SepsisBundle {
+    /// Create bundle timers that start at sepsis onset
+    ///
+    /// # Arguments
+    /// * `guideline` - Guideline defining the windows
+    pub fn new(guideline: BundleGuideline) -> Self {
+        Self {
+            guideline,
+            onset_time_s: None,
+            cultures_drawn_s: None,
+            initial_lactate: None,
+            repeat_lactate_s: None,
+            antibiotics_given_s: None,
+            fluid_given_ml: 0.0,
+            fluid_target_reached_s: None,
+            vasopressors_started_s: None,
+            hypotension: false,
+            persistent_hypotension: false,
+        }
+    }
+
+    /// Record blood cultures being drawn
+    pub fn record_cultures(&mut self, time_s: f64) {
+        self.cultures_drawn_s.get_or_insert(time_s);
+    }
+
+    /// Record a lactate measurement
+    pub fn record_lactate(&mut self, time_s: f64, lactate_mmol_l: f64) {
+        match self.initial_lactate {
+            None => self.initial_lactate = Some((time_s, lactate_mmol_l)),
+            Some(_) => {
+                self.repeat_lactate_s.get_or_insert(time_s);
+            }
+        }
+    }
+
+    /// Record antibiotics being given
+    pub fn record_antibiotics(&mut self, time_s: f64) {
+        self.antibiotics_given_s.get_or_insert(time_s);
+    }
+
+    /// Record intravenous crystalloid
+    pub fn record_fluid(&mut self, volume_ml: f64) {
+        if self.onset_time_s.is_some() {
+            self.fluid_given_ml += volume_ml.max(0.0);
+        }
+    }
+
+    /// Record a vasopressor infusion being started
+    pub fn record_vasopressors(&mut self, time_s: f64) {
+        self.vasopressors_started_s.get_or_insert(time_s);
+    }
+
+    /// Advance the indications for fluids and vasopressors
+    ///
+    /// # Arguments
+    /// * `time_s` - Current time on the infection clock (s)
+    /// * `map_mmhg` - Mean arterial pressure (mmHg)
+    /// * `weight_kg` - Body weight for the fluid target (kg)
+    pub fn update(&mut self, time_s: f64, map_mmhg: f64, weight_kg: f64) {
+        if self.onset_time_s.is_none() {
+            return;
+        }
+        if map_mmhg < MAP_TARGET_MMHG {
+            self.hypotension = true;
+        }
+        if self.fluid_target_reached_s.is_none() && self.fluid_given_ml >= RESUSCITATION_FLUID_ML_PER_KG * weight_kg {
+            self.fluid_target_reached_s = Some(time_s);
+        }
+        if self.fluid_target_reached_s.is_some() && map_mmhg < MAP_TARGET_MMHG {
+            self.persistent_hypotension = true;
+        }
+    }
+
+    /// Build the compliance report
+    ///
+    /// # Arguments
+    /// * `time_s` - Current time on the infection clock (s)
+    ///
+    /// # Returns
+    /// The report, or None before time zero
+    pub fn report(&self, time_s: f64) -> Option<SepsisBundleReport> {
+        let onset = self.onset_time_s?;
+        let initial_lactate = self.initial_lactate.map(|(_, value)| value);
+        let elements = BundleElement::ALL
+            .iter()
+            .map(|&element| {
+                let (required, completed) = match element {
+                    BundleElement::BloodCultures => (true, self.cultures_drawn_s),
+                    BundleElement::Lactate => (true, self.initial_lactate.map(|(time, _)| time)),
+                    BundleElement::Antibiotics => (true, self.antibiotics_given_s),
+                    BundleElement::FluidResuscitation => (
+                        self.hypotension || initial_lactate.is_some_and(|l| l >= HYPOPERFUSION_LACTATE_MMOL_L),
+                        self.fluid_target_reached_s,
+                    ),
+                    BundleElement::Vasopressors => (self.persistent_hypotension, self.vasopressors_started_s),
+                    BundleElement::RepeatLactate => (
+                        initial_lactate.is_some_and(|l| l > REPEAT_LACTATE_THRESHOLD_MMOL_L),
+                        self.repeat_lactate_s,
+                    ),
+                };
+                let deadline = element.window_s(self.guideline);
+                let completed_after_onset = completed.map(|time| time - onset);
+                // Cultures drawn after antibiotics are less likely to grow the organism
+                let in_order = element != BundleElement::BloodCultures
+                    || match (self.cultures_drawn_s, self.antibiotics_given_s) {
+                        (Some(cultures), Some(antibiotics)) => cultures <= antibiotics,
+                        (None, Some(_)) => false,
+                        _ => true,
+                    };
+                let compliance = if !required {
+                    ElementCompliance::NotRequired
+                } else if !in_order {
+                    ElementCompliance::Missed
+                } else {
+                    match completed_after_onset {
+                        Some(delay) if delay <= deadline => ElementCompliance::Met,
+                        Some(_) => ElementCompliance::Missed,
+                        None if time_s - onset > deadline => ElementCompliance::Missed,
+                        None => ElementCompliance::Pending,
+                    }
+                };
+                BundleElementStatus {
+                    element,
+                    required,
+                    completed_after_onset_s: completed_after_onset,
+                    deadline_after_onset_s: deadline,
+                    compliance,
+                }
+            })
+            .collect();
+        Some(SepsisBundleReport {
+            guideline: self.guideline,
+            time_since_onset_s: time_s - onset,
+            elements,
+            antibiotic_delay_s: self.antibiotics_given_s.map(|time| time - onset),
+        })
+    }
+}
+
+/// A bacterial infection with the host's septic response
+#[derive(Debug, Clone)]
+pub struct Sepsis {
+    /// Source of infection (e.g. "Pneumonia", "Urinary tract")
+    pub source: String,
+    /// Growth rate relative to a typical pathogen (1.0 = doubling every ~4 h)
+    pub virulence: f64,
+    /// Bacterial load relative to overwhelming infection (0.0-1.0)
+    pub bacterial_load: f64,
+    /// Host response (0.0 = none, 1.0 = septic shock)
+    pub severity: f64,
+    /// Time since the infection started (s)
+    pub elapsed_s: f64,
+    /// Whether the antibiotic given covers the organism
+    pub antibiotic_coverage: bool,
+    /// Bundle timers from sepsis onset
+    pub bundle: SepsisBundle,
+}
+
+impl Sepsis {
+    /// Start an infection
+    ///
+    /// # Arguments
+    /// * `source` - Source of infection
+    /// * `virulence` - Growth rate relative to a typical pathogen (1.0 = doubling every ~4 h)
+    /// * `guideline` - Guideline for the bundle timers
+    pub fn new(source: &str, virulence: f64, guideline: BundleGuideline) -> Self {
+        Self {
+            source: source.to_string(),
+            virulence: virulence.max(0.0),
+            bacterial_load: 0.02,
+            severity: 0.0,
+            elapsed_s: 0.0,
+            antibiotic_coverage: false,
+            bundle: SepsisBundle::new(guideline),
+        }
+    }
+
+    /// Check whether sepsis has been recognised (time zero has passed)
+    pub fn is_septic(&self) -> bool {
+        self.bundle.onset_time_s.is_some()
+    }
+
+    /// Draw blood cultures
+    pub fn draw_blood_cultures(&mut self) {
+        self.bundle.record_cultures(self.elapsed_s);
+    }
+
+    /// Give antibiotics
+    ///
+    /// # Arguments
+    /// * `covers_organism` - Whether the spectrum covers the pathogen
+    pub fn give_antibiotics(&mut self, covers_organism: bool) {
+        self.bundle.record_antibiotics(self.elapsed_s);
+        self.antibiotic_coverage |= covers_organism;
+    }
+
+    /// Bundle compliance report, once sepsis has been recognised
+    pub fn bundle_report(&self) -> Option<SepsisBundleReport> {
+        self.bundle.report(self.elapsed_s)
+    }
+
+    /// Advance the infection, the host response and the bundle
+    ///
+    /// Writes the host response to the coagulation and heart inputs and adds
+    /// lactate to the blood.
+    ///
+    /// # Arguments
+    /// * `patient` - The patient
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(&mut self, patient: &mut Patient, delta_time_s: f64) {
+        self.elapsed_s += delta_time_s;
+
+        // Logistic bacterial growth until an effective antibiotic has had time to act
+        let difficulty = patient.difficulty;
+        let growth =
+            self.virulence * difficulty.disease_progression * std::f64::consts::LN_2 / BACTERIAL_DOUBLING_S;
+        let killing = match self.bundle.antibiotics_given_s {
+            Some(given) if self.antibiotic_coverage && self.elapsed_s - given >= ANTIBIOTIC_ONSET_S => {
+                ANTIBIOTIC_KILL_PER_S * difficulty.treatment_responsiveness
+            }
+            _ => 0.0,
+        };
+        let load = self.bacterial_load;
+        self.bacterial_load = (load + (growth * load * (1.0 - load) - killing * load) * delta_time_s).clamp(0.0, 1.0);
+        self.severity += (self.bacterial_load - self.severity) * (1.0 - (-delta_time_s / HOST_RESPONSE_S).exp());
+
+        if self.bundle.onset_time_s.is_none() && self.severity >= SEPSIS_ONSET_SEVERITY {
+            self.bundle.onset_time_s = Some(self.elapsed_s);
+        }
+
+        // Host response: tissue factor (DIC), vasodilation and aerobic glycolysis
+        patient.coagulation.sepsis_severity = self.severity;
+        patient.signals.publish(Signal::Cytokines, self.severity);
+        let chemistry = &mut patient.blood.chemistry;
+        chemistry.lactate_mmol_l += SEPTIC_LACTATE_MMOL_L * self.severity.powi(2) * delta_time_s / 1800.0;
+
+        let map = patient.blood.get_mean_arterial_pressure();
+        let weight = patient.get_weight_kg();
+        self.bundle.update(self.elapsed_s, map, weight);
+    }
+
+    /// Get a summary of the infection
+    pub fn get_summary(&self) -> String {
+        let state = if self.severity >= 0.6 {
+            "SEPTIC SHOCK"
+        } else if self.is_septic() {
+            "SEPSIS"
+        } else {
+            "infection"
+        };
+        let mut summary = format!(
+            "Sepsis ({}): {}, load={:.2}, host response={:.2}",
+            self.source, state, self.bacterial_load, self.severity
+        );
+        if let Some(report) = self.bundle_report() {
+            summary.push_str(&format!(" | {}", report.get_summary()));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_compliance() {
+        let mut bundle = SepsisBundle::new(BundleGuideline::HourOne);
+        bundle.onset_time_s = Some(1000.0);
+        bundle.record_lactate(1200.0, 4.5);
+        bundle.record_cultures(1500.0);
+        bundle.record_antibiotics(1800.0);
+        bundle.update(2000.0, 60.0, 70.0);
+        bundle.record_fluid(2100.0);
+        bundle.update(3000.0, 62.0, 70.0);
+        bundle.record_vasopressors(3500.0);
+        bundle.record_lactate(4000.0, 3.0);
+
+        let report = bundle.report(4000.0).unwrap();
+        assert!(report.all_met(), "{}", report.get_summary());
+        assert_eq!(report.antibiotic_delay_s, Some(800.0));
+
+        // Cultures drawn after antibiotics, and a late fluid bolus
+        let mut bundle = SepsisBundle::new(BundleGuideline::HourOne);
+        bundle.onset_time_s = Some(0.0);
+        bundle.record_lactate(600.0, 1.5);
+        bundle.record_antibiotics(900.0);
+        bundle.record_cultures(1200.0);
+        bundle.update(1200.0, 58.0, 70.0);
+        let report = bundle.report(5000.0).unwrap();
+        assert_eq!(report.element(BundleElement::BloodCultures).unwrap().compliance, ElementCompliance::Missed);
+        assert_eq!(report.element(BundleElement::FluidResuscitation).unwrap().compliance, ElementCompliance::Missed);
+        assert_eq!(report.element(BundleElement::RepeatLactate).unwrap().compliance, ElementCompliance::NotRequired);
+        assert!(!report.all_met());
+    }
+}
@@ -0,0 +1,170 @@
+//! Inter-organ signal bus
+//!
+//! This module provides:
+//! - The hormones, cytokines and autonomic outflows organs use to talk to each
+//!   other: circulating catecholamines, sympathetic and vagal tone, cytokines,
+//!   adenosine, angiotensin II, aldosterone and the gut incretins
+//! - A bus organs publish their outputs to and read their inputs from during
+//!   update, so a new producer or consumer needs no field on another organ
+//!
+//! Every signal is a single level with a resting baseline. Relative signals
+//! are 1.0 at rest; cytokines are an activity from 0.0 (none) to 1.0 (septic
+//! shock). A level stays where it was last published, so a consumer updating
+//! before its producer in a step reads the previous step's value.
+
+use std::fmt;
+
+/// Signal carried between organs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signal {
+    /// Circulating catecholamines from sympathoadrenal drive (relative, 1.0 = rest)
+    Catecholamines,
+    /// Sympathetic nerve outflow from the brainstem vasomotor centre (relative, 1.0 = rest)
+    SympatheticTone,
+    /// Parasympathetic outflow through the vagus (relative, 1.0 = rest)
+    VagalTone,
+    /// Inflammatory cytokine activity (0.0 = none, 1.0 = septic shock)
+    Cytokines,
+    /// Interstitial adenosine released by tissue starved of oxygen (relative, 1.0 = rest)
+    Adenosine,
+    /// Circulating angiotensin II (relative, 1.0 = normal renin and angiotensinogen)
+    AngiotensinII,
+    /// Adrenal aldosterone acting on the collecting duct (relative, 1.0 = rest)
+    Aldosterone,
+    /// Gut incretins released as a meal is absorbed (relative, 1.0 = fasting)
+    Incretins,
+}
+
+impl Signal {
+    /// Every signal, in bus order
+    pub const ALL: [Signal; 8] = [
+        Signal::Catecholamines,
+        Signal::SympatheticTone,
+        Signal::VagalTone,
+        Signal::Cytokines,
+        Signal::Adenosine,
+        Signal::AngiotensinII,
+        Signal::Aldosterone,
+        Signal::Incretins,
+    ];
+
+    /// Level at rest in a healthy patient
+    pub fn baseline(&self) -> f64 {
+        match self {
+            Signal::Cytokines => 0.0,
+            _ => 1.0,
+        }
+    }
+
+    /// Short display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Signal::Catecholamines => "Catecholamines",
+            Signal::SympatheticTone => "Sympathetic tone",
+            Signal::VagalTone => "Vagal tone",
+            Signal::Cytokines => "Cytokines",
+            Signal::Adenosine => "Adenosine",
+            Signal::AngiotensinII => "Angiotensin II",
+            Signal::Aldosterone => "Aldosterone",
+            Signal::Incretins => "Incretins",
+        }
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Current level of every signal
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalBus {
+    levels: [f64; Signal::ALL.len()],
+}
+
+impl SignalBus {
+    /// Create a bus with every signal at its baseline
+    pub fn new() -> Self {
+        Self {
+            levels: Signal::ALL.map(|signal| signal.baseline()),
+        }
+    }
+
+    /// Set a signal's level
+    pub fn publish(&mut self, signal: Signal, level: f64) {
+        self.levels[signal as usize] = level;
+    }
+
+    /// Move a signal towards a target level with first-order kinetics, for
+    /// hormones whose level is set by production against clearance
+    ///
+    /// # Arguments
+    /// * `signal` - Signal to move
+    /// * `target` - Level where production balances clearance
+    /// * `time_constant_s` - Clearance time constant (s)
+    /// * `delta_time_s` - Time step in seconds
+    pub fn relax(&mut self, signal: Signal, target: f64, time_constant_s: f64, delta_time_s: f64) {
+        let level = &mut self.levels[signal as usize];
+        *level += (target - *level) * (1.0 - (-delta_time_s / time_constant_s).exp());
+    }
+
+    /// Current level of a signal
+    pub fn level(&self, signal: Signal) -> f64 {
+        self.levels[signal as usize]
+    }
+
+    /// Departure of a signal from its baseline
+    pub fn excess(&self, signal: Signal) -> f64 {
+        self.level(signal) - signal.baseline()
+    }
+
+    /// Return every signal to its baseline
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Get a summary of the signal levels
+    pub fn get_summary(&self) -> String {
+        let levels: Vec<String> = Signal::ALL
+            .iter()
+            .map(|signal| format!("{} {:.2}", signal, self.level(*signal)))
+            .collect();
+        format!("Signals: {}", levels.join(", "))
+    }
+}
+
+impl Default for SignalBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+    use crate::sepsis::BundleGuideline;
+
+    #[test]
+    fn test_organs_exchange_signals_through_the_bus() {
+        let mut patient = initialize_patient(1, 3);
+        update_patient(&mut patient, 60.0);
+        for signal in [Signal::Catecholamines, Signal::VagalTone, Signal::AngiotensinII] {
+            assert!((patient.signals.level(signal) - 1.0).abs() < 0.2, "{}", patient.signals.get_summary());
+        }
+        assert_eq!(patient.signals.level(Signal::Cytokines), 0.0);
+
+        // Cytokines published by the infection dilate the vessels the heart ejects into
+        let mut septic = patient.fork();
+        septic.start_infection("Pneumonia", 1.0, BundleGuideline::HourOne);
+        let sepsis = septic.sepsis.as_mut().unwrap();
+        sepsis.bacterial_load = 0.8;
+        sepsis.severity = 0.8;
+        update_patient(&mut septic, 600.0);
+        update_patient(&mut patient, 600.0);
+        assert!(septic.signals.level(Signal::Cytokines) > 0.5);
+        assert!(septic.blood.get_mean_arterial_pressure() < patient.blood.get_mean_arterial_pressure() - 5.0);
+        assert!(septic.signals.excess(Signal::Catecholamines) > patient.signals.excess(Signal::Catecholamines));
+    }
+}
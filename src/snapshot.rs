@@ -0,0 +1,442 @@
+//! Patient snapshots and their differences
+//!
+//! This module provides:
+//! - Snapshots of a patient's vital signs, true lab values, rhythm, organ
+//!   states and recognized diagnoses at one moment
+//! - A diff between two snapshots listing what changed (labs that moved, organ
+//!   states and diagnoses that began or ended, rhythm transitions), each with
+//!   a clinical significance, for tests, debriefs and "what changed since the
+//!   last assessment" panels
+//!
+//! A lab value has moved when the change exceeds its reference change value,
+//! 2.77 analytic standard deviations, the smallest difference between two
+//! results unlikely to be measurement noise. Vital signs have fixed thresholds
+//! of clinical interest.
+
+use crate::diagnosis::{Diagnosis, DiagnosisEngine};
+use crate::labs::{Analyte, LabFlag, LabPanel};
+use crate::organs::brain::Brain;
+use crate::organs::esophagus::Esophagus;
+use crate::organs::heart::{Heart, Rhythm};
+use crate::organs::intestines::Intestines;
+use crate::organs::kidneys::Kidneys;
+use crate::organs::liver::Liver;
+use crate::organs::lungs::Lungs;
+use crate::organs::stomach::Stomach;
+use crate::patient::Patient;
+use crate::validation::Measure;
+use std::fmt;
+
+/// Reference change value as a multiple of the analytic standard deviation (95%, two-sided)
+const REFERENCE_CHANGE_FACTOR: f64 = 2.77;
+
+/// Vital signs compared, with the smallest change of interest and the critical limits
+const VITAL_SIGNS: [(Measure, f64, Option<f64>, Option<f64>); 8] = [
+    (Measure::HeartRate, 10.0, Some(40.0), Some(150.0)),
+    (Measure::SystolicPressure, 10.0, Some(90.0), Some(200.0)),
+    (Measure::DiastolicPressure, 10.0, None, Some(120.0)),
+    (Measure::MeanArterialPressure, 10.0, Some(65.0), None),
+    (Measure::CardiacIndex, 0.5, Some(2.2), None),
+    (Measure::ArterialSaturation, 3.0, Some(88.0), None),
+    (Measure::Lactate, 1.0, None, Some(4.0)),
+    (Measure::ArterialPh, 0.05, Some(7.2), Some(7.6)),
+];
+
+/// Diagnoses that are an immediate threat to life
+const CRITICAL_DIAGNOSES: [Diagnosis; 6] = [
+    Diagnosis::CardiacArrest,
+    Diagnosis::VentricularFibrillation,
+    Diagnosis::VentricularTachycardia,
+    Diagnosis::SepticShock,
+    Diagnosis::HypovolemicShock,
+    Diagnosis::AcuteRespiratoryFailure,
+];
+
+/// How much a change matters clinically
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Significance {
+    /// Beyond measurement noise but still within the reference range
+    Minor,
+    /// Abnormal, or a change of state
+    Notable,
+    /// Beyond a critical limit or an immediate threat to life
+    Critical,
+}
+
+/// Patient state at one moment
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatientSnapshot {
+    /// Simulation time of the snapshot (s)
+    pub time_s: f64,
+    /// Vital signs
+    pub vitals: Vec<(Measure, f64)>,
+    /// True lab values with their flags, without analytic noise
+    pub labs: Vec<(Analyte, f64, LabFlag)>,
+    /// Cardiac rhythm, if the patient has a heart
+    pub rhythm: Option<Rhythm>,
+    /// Organ states present, by organ ("Kidneys", "AKI stage 2")
+    pub organ_states: Vec<(&'static str, String)>,
+    /// Diagnoses the patient meets
+    pub diagnoses: Vec<Diagnosis>,
+}
+
+impl PatientSnapshot {
+    /// Take a snapshot of a patient
+    ///
+    /// # Arguments
+    /// * `patient` - Patient to record
+    pub fn from_patient(patient: &Patient) -> Self {
+        let mut analytes: Vec<Analyte> = Vec::new();
+        for analyte in LabPanel::ALL.iter().flat_map(|panel| panel.analytes()) {
+            if !analytes.contains(analyte) {
+                analytes.push(*analyte);
+            }
+        }
+        let labs = analytes
+            .into_iter()
+            .map(|analyte| {
+                let value = analyte.read(patient);
+                (analyte, value, analyte.flag(value, &patient.demographics))
+            })
+            .collect();
+
+        Self {
+            time_s: patient.labs.time_s,
+            vitals: VITAL_SIGNS.iter().map(|&(measure, ..)| (measure, measure.measure(patient))).collect(),
+            labs,
+            rhythm: patient.get_organ::<Heart>().map(|h| h.rhythm),
+            organ_states: organ_states(patient),
+            diagnoses: DiagnosisEngine::new().assess(patient),
+        }
+    }
+
+    /// Changes from this snapshot to a later one
+    ///
+    /// # Arguments
+    /// * `other` - Later snapshot
+    ///
+    /// # Returns
+    /// The changes, most significant first
+    pub fn diff(&self, other: &PatientSnapshot) -> SnapshotDiff {
+        let mut changes = Vec::new();
+
+        if self.rhythm != other.rhythm {
+            let arrest = matches!(
+                other.rhythm,
+                Some(
+                    Rhythm::Asystole
+                        | Rhythm::VentricularFibrillation
+                        | Rhythm::PulselessVentricularTachycardia
+                        | Rhythm::PulselessElectricalActivity
+                        | Rhythm::TorsadesDePointes
+                )
+            );
+            changes.push(SnapshotChange {
+                change: Change::Rhythm {
+                    before: self.rhythm,
+                    after: other.rhythm,
+                },
+                significance: if arrest { Significance::Critical } else { Significance::Notable },
+            });
+        }
+
+        for (&(measure, before), &(_, after)) in self.vitals.iter().zip(&other.vitals) {
+            let Some(&(_, threshold, low, high)) = VITAL_SIGNS.iter().find(|(m, ..)| *m == measure) else {
+                continue;
+            };
+            if !before.is_finite() || !after.is_finite() || (after - before).abs() < threshold {
+                continue;
+            }
+            let critical = low.is_some_and(|low| after < low) || high.is_some_and(|high| after > high);
+            changes.push(SnapshotChange {
+                change: Change::Vital { measure, before, after },
+                significance: if critical { Significance::Critical } else { Significance::Notable },
+            });
+        }
+
+        for (&(analyte, before, flag_before), &(_, after, flag_after)) in self.labs.iter().zip(&other.labs) {
+            let (cv, floor) = analyte.imprecision();
+            let reference_change = REFERENCE_CHANGE_FACTOR * (cv * before.abs()).max(floor);
+            if flag_before == flag_after && (after - before).abs() <= reference_change {
+                continue;
+            }
+            let significance = match flag_after {
+                flag if flag.is_critical() => Significance::Critical,
+                LabFlag::Normal if flag_before == LabFlag::Normal => Significance::Minor,
+                _ => Significance::Notable,
+            };
+            changes.push(SnapshotChange {
+                change: Change::Lab {
+                    analyte,
+                    before,
+                    after,
+                    flag: flag_after,
+                },
+                significance,
+            });
+        }
+
+        for (organ, state) in &other.organ_states {
+            if !self.organ_states.contains(&(organ, state.clone())) {
+                changes.push(SnapshotChange {
+                    change: Change::OrganState {
+                        organ,
+                        state: state.clone(),
+                        present: true,
+                    },
+                    significance: Significance::Notable,
+                });
+            }
+        }
+        for (organ, state) in &self.organ_states {
+            if !other.organ_states.contains(&(organ, state.clone())) {
+                changes.push(SnapshotChange {
+                    change: Change::OrganState {
+                        organ,
+                        state: state.clone(),
+                        present: false,
+                    },
+                    significance: Significance::Notable,
+                });
+            }
+        }
+
+        for diagnosis in other.diagnoses.iter().filter(|d| !self.diagnoses.contains(d)) {
+            let critical =
+                CRITICAL_DIAGNOSES.contains(diagnosis) || matches!(diagnosis, Diagnosis::StElevationMyocardialInfarction(_));
+            changes.push(SnapshotChange {
+                change: Change::Diagnosis {
+                    diagnosis: *diagnosis,
+                    present: true,
+                },
+                significance: if critical { Significance::Critical } else { Significance::Notable },
+            });
+        }
+        for diagnosis in self.diagnoses.iter().filter(|d| !other.diagnoses.contains(d)) {
+            changes.push(SnapshotChange {
+                change: Change::Diagnosis {
+                    diagnosis: *diagnosis,
+                    present: false,
+                },
+                significance: Significance::Notable,
+            });
+        }
+
+        // Stable sort keeps rhythm, vitals, labs, organ states and diagnoses in order within a level
+        changes.sort_by_key(|change| std::cmp::Reverse(change.significance));
+        SnapshotDiff {
+            elapsed_s: other.time_s - self.time_s,
+            changes,
+        }
+    }
+}
+
+/// Organ states present in a patient, by organ
+fn organ_states(patient: &Patient) -> Vec<(&'static str, String)> {
+    let mut states = Vec::new();
+    let mut push = |organ: &'static str, state: &str| states.push((organ, state.to_string()));
+    if let Some(heart) = patient.get_organ::<Heart>() {
+        if heart.is_in_cardiac_arrest() {
+            push("Heart", "Cardiac arrest");
+        }
+        if heart.has_tamponade() {
+            push("Heart", "Tamponade");
+        }
+    }
+    if let Some(lungs) = patient.get_organ::<Lungs>() {
+        if let Some(severity) = lungs.get_ards_severity(patient.blood.gases.pao2_mmhg) {
+            push("Lungs", &format!("ARDS ({})", severity));
+        }
+    }
+    if let Some(kidneys) = patient.get_organ::<Kidneys>() {
+        let stage = kidneys.aki_stage();
+        if stage > 0 {
+            push("Kidneys", &format!("AKI stage {}", stage));
+        }
+    }
+    if let Some(liver) = patient.get_organ::<Liver>() {
+        if liver.is_shock_liver() {
+            push("Liver", "Shock liver");
+        }
+        if liver.is_liver_failure() {
+            push("Liver", "Liver failure");
+        }
+        if liver.is_portal_hypertension() {
+            push("Liver", "Portal hypertension");
+        }
+    }
+    if let Some(brain) = patient.get_organ::<Brain>() {
+        if brain.is_seizing() {
+            push("Brain", "Seizure");
+        }
+        if brain.is_herniating() {
+            push("Brain", "Herniation");
+        }
+        if brain.is_brain_dead() {
+            push("Brain", "Brain death");
+        }
+    }
+    if patient.get_organ::<Esophagus>().is_some_and(|e| e.is_variceal_hemorrhage()) {
+        push("Esophagus", "Variceal hemorrhage");
+    }
+    if patient.get_organ::<Stomach>().is_some_and(|s| s.is_ulcer_bleeding()) {
+        push("Stomach", "Bleeding peptic ulcer");
+    }
+    if patient.get_organ::<Intestines>().is_some_and(|i| i.is_lower_gi_bleeding()) {
+        push("Intestines", "Lower GI bleed");
+    }
+    states
+}
+
+/// What changed between two snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The cardiac rhythm changed
+    Rhythm { before: Option<Rhythm>, after: Option<Rhythm> },
+    /// A vital sign moved by more than its threshold of interest
+    Vital { measure: Measure, before: f64, after: f64 },
+    /// A lab value moved beyond its reference change value or changed flag
+    Lab { analyte: Analyte, before: f64, after: f64, flag: LabFlag },
+    /// An organ state began (present) or ended
+    OrganState { organ: &'static str, state: String, present: bool },
+    /// A diagnosis began (present) or resolved
+    Diagnosis { diagnosis: Diagnosis, present: bool },
+}
+
+/// One change with its clinical significance
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotChange {
+    /// What changed
+    pub change: Change,
+    /// How much it matters
+    pub significance: Significance,
+}
+
+impl fmt::Display for SnapshotChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.change {
+            Change::Rhythm { before, after } => {
+                let name = |rhythm: &Option<Rhythm>| rhythm.map_or("none".to_string(), |r| format!("{:?}", r));
+                write!(f, "Rhythm {} -> {}", name(before), name(after))
+            }
+            Change::Vital { measure, before, after } => write!(f, "{:?} {:.2} -> {:.2}", measure, before, after),
+            Change::Lab {
+                analyte,
+                before,
+                after,
+                flag,
+            } => write!(
+                f,
+                "{} {:.*} -> {:.*} {} {}",
+                analyte.name(),
+                analyte.decimals(),
+                before,
+                analyte.decimals(),
+                after,
+                analyte.units(),
+                flag.symbol()
+            ),
+            Change::OrganState { organ, state, present } => {
+                write!(f, "{}: {} {}", organ, state, if *present { "began" } else { "ended" })
+            }
+            Change::Diagnosis { diagnosis, present } => {
+                write!(f, "{} {}", diagnosis.icd10().1, if *present { "diagnosed" } else { "resolved" })
+            }
+        }
+    }
+}
+
+/// Changes between two snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotDiff {
+    /// Time between the snapshots (s)
+    pub elapsed_s: f64,
+    /// Changes, most significant first
+    pub changes: Vec<SnapshotChange>,
+}
+
+impl SnapshotDiff {
+    /// Nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Changes at or above a significance
+    ///
+    /// # Arguments
+    /// * `minimum` - Least significance reported
+    pub fn at_least(&self, minimum: Significance) -> impl Iterator<Item = &SnapshotChange> {
+        self.changes.iter().filter(move |change| change.significance >= minimum)
+    }
+
+    /// Get a human-readable list of the changes
+    pub fn get_summary(&self) -> String {
+        let mut summary = format!("Changes over {:.0} min:", self.elapsed_s / 60.0);
+        if self.changes.is_empty() {
+            summary.push_str(" none");
+        }
+        for change in &self.changes {
+            let marker = match change.significance {
+                Significance::Critical => "!!",
+                Significance::Notable => "! ",
+                Significance::Minor => "  ",
+            };
+            summary.push_str(&format!("\n{} {}", marker, change));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::organs::heart::CoronaryTerritory;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_diff_reports_infarct_and_arrest() {
+        // A settled patient changes only within measurement noise
+        let mut patient = initialize_patient(1, 3);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        let before = PatientSnapshot::from_patient(&patient);
+        for _ in 0..60 {
+            update_patient(&mut patient, 1.0);
+        }
+        let quiet = before.diff(&PatientSnapshot::from_patient(&patient));
+        assert!(quiet.at_least(Significance::Notable).next().is_none(), "{}", quiet.get_summary());
+
+        if let Some(heart) = patient.get_organ_mut::<Heart>() {
+            heart.set_territory_contractility(CoronaryTerritory::LeftAnteriorDescending, 0.0);
+        }
+        for _ in 0..(3 * 3600) {
+            update_patient(&mut patient, 1.0);
+        }
+        if let Some(heart) = patient.get_organ_mut::<Heart>() {
+            heart.induce_ventricular_fibrillation();
+        }
+        update_patient(&mut patient, 1.0);
+        let diff = before.diff(&PatientSnapshot::from_patient(&patient));
+
+        assert!(diff.elapsed_s > 3.0 * 3600.0);
+        assert_eq!(diff.changes[0].significance, Significance::Critical);
+        assert!(diff.changes.contains(&SnapshotChange {
+            change: Change::Rhythm {
+                before: Some(Rhythm::NormalSinus),
+                after: Some(Rhythm::VentricularFibrillation),
+            },
+            significance: Significance::Critical,
+        }));
+        assert!(diff.changes.iter().any(|c| matches!(
+            c.change,
+            Change::Lab { analyte: Analyte::Troponin, flag, .. } if flag.is_critical()
+        )));
+        assert!(diff.changes.iter().any(|c| c.change
+            == Change::Diagnosis {
+                diagnosis: Diagnosis::StElevationMyocardialInfarction(CoronaryTerritory::LeftAnteriorDescending),
+                present: true,
+            }));
+        assert!(diff.get_summary().contains("!! Rhythm NormalSinus -> VentricularFibrillation"));
+    }
+}
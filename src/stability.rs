@@ -0,0 +1,248 @@
+//! Numerical stabilization
+//!
+//! This module provides:
+//! - Limits of the values compatible with life for the key shared quantities
+//!   (blood gases, electrolytes, blood counts, pressures, temperature, vital signs)
+//! - Detection of non-finite values (NaN, infinity) and values beyond those
+//!   limits after each step, as a feedback loop driven to an extreme can produce
+//! - Correction before the next step reads them: non-finite values fall back
+//!   to their last valid value and impossible ones are clamped to the limit
+//! - Diagnostic events recording every correction, so a runaway model is
+//!   visible instead of silently corrupting the organs downstream
+
+use crate::organs::heart::Heart;
+use crate::organs::lungs::Lungs;
+use crate::patient::Patient;
+use crate::trace::emergent;
+
+/// Most recent events kept
+const MAX_STABILITY_EVENTS: usize = 256;
+
+/// Range of a quantity compatible with life
+#[derive(Debug, Clone, Copy)]
+pub struct PhysiologicLimit {
+    /// Quantity checked
+    pub name: &'static str,
+    /// Lowest possible value
+    pub low: f64,
+    /// Highest possible value
+    pub high: f64,
+    /// Value used when the quantity has never been valid
+    pub normal: f64,
+    access: fn(&mut Patient) -> Option<&mut f64>,
+}
+
+/// Define a limit on a quantity reached through the patient
+macro_rules! limit {
+    ($name:literal, $low:expr, $high:expr, $normal:expr, |$patient:ident| $access:expr) => {
+        PhysiologicLimit {
+            name: $name,
+            low: $low,
+            high: $high,
+            normal: $normal,
+            access: |$patient| $access,
+        }
+    };
+}
+
+/// Every quantity the stabilizer checks
+pub fn physiologic_limits() -> Vec<PhysiologicLimit> {
+    vec![
+        limit!("pH", 6.5, 8.0, 7.4, |p| Some(&mut p.blood.gases.ph)),
+        limit!("PaCO2", 5.0, 200.0, 40.0, |p| Some(&mut p.blood.gases.paco2_mmhg)),
+        limit!("PaO2", 10.0, 700.0, 95.0, |p| Some(&mut p.blood.gases.pao2_mmhg)),
+        limit!("HCO3 (arterial)", 1.0, 60.0, 24.0, |p| Some(&mut p.blood.gases.hco3_meq_l)),
+        limit!("Base excess", -40.0, 40.0, 0.0, |p| Some(&mut p.blood.gases.base_excess_meq_l)),
+        limit!("SaO2", 0.0, 100.0, 97.0, |p| Some(&mut p.blood.gases.sao2_percent)),
+        limit!("Bicarbonate", 1.0, 60.0, 24.0, |p| Some(&mut p.blood.chemistry.bicarbonate_meq_l)),
+        limit!("Sodium", 90.0, 200.0, 140.0, |p| Some(&mut p.blood.chemistry.sodium_meq_l)),
+        limit!("Potassium", 1.0, 12.0, 4.0, |p| Some(&mut p.blood.chemistry.potassium_meq_l)),
+        limit!("Chloride", 50.0, 160.0, 102.0, |p| Some(&mut p.blood.chemistry.chloride_meq_l)),
+        limit!("Calcium", 2.0, 20.0, 9.5, |p| Some(&mut p.blood.chemistry.calcium_mg_dl)),
+        limit!("Glucose", 5.0, 2000.0, 90.0, |p| Some(&mut p.blood.chemistry.glucose_mg_dl)),
+        limit!("Lactate", 0.0, 40.0, 1.0, |p| Some(&mut p.blood.chemistry.lactate_mmol_l)),
+        limit!("Creatinine", 0.1, 30.0, 1.0, |p| Some(&mut p.blood.chemistry.creatinine_mg_dl)),
+        limit!("BUN", 1.0, 300.0, 14.0, |p| Some(&mut p.blood.chemistry.bun_mg_dl)),
+        limit!("Hemoglobin", 1.0, 25.0, 15.0, |p| Some(&mut p.blood.cells.hemoglobin_g_dl)),
+        limit!("Hematocrit", 3.0, 80.0, 45.0, |p| Some(&mut p.blood.cells.hematocrit_percent)),
+        limit!("Platelets", 0.0, 3000.0, 250.0, |p| Some(&mut p.blood.cells.platelet_count_thousand_per_ul)),
+        limit!("INR", 0.5, 20.0, 1.0, |p| Some(&mut p.blood.clotting.inr)),
+        limit!("Systolic pressure", 0.0, 350.0, 120.0, |p| Some(&mut p.blood.blood_pressure_systolic)),
+        limit!("Diastolic pressure", 0.0, 250.0, 80.0, |p| Some(&mut p.blood.blood_pressure_diastolic)),
+        limit!("Body temperature", 20.0, 45.0, 37.0, |p| Some(&mut p.oxygen_transport.body_temperature_c)),
+        limit!("Heart rate", 0.0, 350.0, 70.0, |p| p.get_organ_mut::<Heart>().map(|h| &mut h.heart_rate_bpm)),
+        limit!("Cardiac output", 0.0, 40.0, 5.0, |p| {
+            p.get_organ_mut::<Heart>().map(|h| &mut h.cardiac_output_l_min)
+        }),
+        limit!("Respiratory rate", 0.0, 120.0, 14.0, |p| {
+            p.get_organ_mut::<Lungs>().map(|l| &mut l.respiration_rate_bpm)
+        }),
+    ]
+}
+
+/// What was wrong with a corrected value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StabilityIssue {
+    /// NaN or infinite
+    NotFinite,
+    /// Beyond the range compatible with life
+    OutOfRange,
+}
+
+/// Record of one correction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StabilityEvent {
+    /// Simulation time of the correction (s)
+    pub time_s: f64,
+    /// Quantity corrected
+    pub quantity: &'static str,
+    /// Value found
+    pub value: f64,
+    /// Value it was replaced with
+    pub corrected: f64,
+    /// What was wrong with it
+    pub issue: StabilityIssue,
+}
+
+/// Guard correcting degenerate values after each step
+#[derive(Debug, Clone)]
+pub struct Stabilizer {
+    /// Check and correct values each step
+    pub enabled: bool,
+    /// Time since the stabilizer started (s)
+    pub time_s: f64,
+    /// Corrections made since the start
+    pub total_corrections: u64,
+    /// Quantities checked
+    limits: Vec<PhysiologicLimit>,
+    /// Last valid value of each quantity, in limit order
+    last_valid: Vec<Option<f64>>,
+    /// Most recent corrections, oldest first
+    events: Vec<StabilityEvent>,
+}
+
+impl Stabilizer {
+    /// Create an enabled stabilizer checking every physiologic limit
+    pub fn new() -> Self {
+        let limits = physiologic_limits();
+        Self {
+            enabled: true,
+            time_s: 0.0,
+            total_corrections: 0,
+            last_valid: vec![None; limits.len()],
+            limits,
+            events: Vec::new(),
+        }
+    }
+
+    /// Quantities checked
+    pub fn limits(&self) -> &[PhysiologicLimit] {
+        &self.limits
+    }
+
+    /// Check every quantity after a step, correcting the degenerate ones
+    ///
+    /// # Arguments
+    /// * `patient` - Patient to check
+    /// * `delta_time_s` - Time step in seconds
+    ///
+    /// # Returns
+    /// Number of values corrected
+    pub fn stabilize(&mut self, patient: &mut Patient, delta_time_s: f64) -> usize {
+        self.time_s += delta_time_s.max(0.0);
+        if !self.enabled {
+            return 0;
+        }
+        let mut corrections = 0;
+        for (limit, last_valid) in self.limits.iter().zip(self.last_valid.iter_mut()) {
+            let Some(value) = (limit.access)(patient) else { continue };
+            let found = *value;
+            let (corrected, issue) = if !found.is_finite() {
+                (last_valid.unwrap_or(limit.normal), Some(StabilityIssue::NotFinite))
+            } else if found < limit.low || found > limit.high {
+                (found.clamp(limit.low, limit.high), Some(StabilityIssue::OutOfRange))
+            } else {
+                (found, None)
+            };
+            *value = corrected;
+            *last_valid = Some(corrected);
+            if let Some(issue) = issue {
+                emergent!(warn, quantity = limit.name, value = found, corrected, issue = ?issue, "value corrected");
+                corrections += 1;
+                if self.events.len() == MAX_STABILITY_EVENTS {
+                    self.events.remove(0);
+                }
+                self.events.push(StabilityEvent {
+                    time_s: self.time_s,
+                    quantity: limit.name,
+                    value: found,
+                    corrected,
+                    issue,
+                });
+            }
+        }
+        self.total_corrections += corrections as u64;
+        corrections
+    }
+
+    /// Most recent corrections, oldest first
+    pub fn events(&self) -> &[StabilityEvent] {
+        &self.events
+    }
+
+    /// Take the recorded corrections, leaving none
+    pub fn drain_events(&mut self) -> Vec<StabilityEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+impl Default for Stabilizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_degenerate_values_are_corrected_and_reported() {
+        let mut patient = initialize_patient(1, 12);
+        for _ in 0..10 {
+            update_patient(&mut patient, 0.1);
+        }
+        assert!(patient.stability.events().is_empty());
+
+        // A collapsed PaCO2 drives the Henderson-Hasselbalch pH to infinity
+        patient.blood.gases.paco2_mmhg = f64::NAN;
+        patient.blood.chemistry.glucose_mg_dl = 1.0e6;
+        let mut stabilizer = Stabilizer::new();
+        stabilizer.stabilize(&mut patient, 0.0);
+        assert_eq!(patient.blood.gases.paco2_mmhg, 40.0);
+        assert_eq!(patient.blood.chemistry.glucose_mg_dl, 2000.0);
+        let events = stabilizer.drain_events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].quantity, "PaCO2");
+        assert_eq!(events[0].issue, StabilityIssue::NotFinite);
+        assert_eq!(events[1].issue, StabilityIssue::OutOfRange);
+
+        // Within a simulation, a non-finite value falls back to its last valid value
+        let heart_rate = patient.get_organ::<Heart>().unwrap().heart_rate_bpm;
+        if let Some(heart) = patient.get_organ_mut::<Heart>() {
+            heart.heart_rate_bpm = f64::INFINITY;
+        }
+        let mut stability = std::mem::take(&mut patient.stability);
+        stability.stabilize(&mut patient, 0.1);
+        assert_eq!(patient.get_organ::<Heart>().unwrap().heart_rate_bpm, heart_rate);
+        assert_eq!(stability.events()[0].quantity, "Heart rate");
+
+        patient.stability = stability;
+        for _ in 0..10 {
+            update_patient(&mut patient, 0.1);
+        }
+        assert!(patient.blood.gases.ph.is_finite());
+        assert_eq!(patient.stability.total_corrections, 1);
+    }
+}
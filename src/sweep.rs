@@ -0,0 +1,321 @@
+//! Parameter sweeps and sensitivity analysis
+//!
+//! This module provides:
+//! - Sweeps of one or more model parameters (valve or arterial stenosis,
+//!   hemorrhage rate, FiO2, exercise) across ranges of values, every
+//!   combination run as its own simulation, in parallel across the available cores
+//! - A table of outcome metrics per combination, exportable as CSV
+//! - Sensitivity of each outcome to each parameter (least-squares slope)
+
+use crate::organs::heart::{Heart, HeartValve};
+use crate::organs::lungs::Lungs;
+use crate::organs::vascular::VascularSystem;
+use crate::patient::{initialize_patient, update_patient, Patient};
+use crate::validation::Measure;
+use crate::wounds::Wound;
+
+/// Model parameter a sweep varies
+#[derive(Debug, Clone, PartialEq)]
+pub enum SweepParameter {
+    /// Stenosis severity of a heart valve (0.0-1.0)
+    ValveStenosis(HeartValve),
+    /// Fraction of a named artery's lumen narrowed by plaque (0.0-0.8)
+    ArterialStenosis(String),
+    /// Bleeding rate of an uncontrolled laceration at normal arterial pressure (mL/min)
+    HemorrhageRate,
+    /// Fraction of inspired oxygen (0.21-1.0)
+    Fio2,
+    /// Muscular work as a fraction of maximal exercise capacity (0.0-1.0)
+    ExerciseIntensity,
+}
+
+impl SweepParameter {
+    /// Set the parameter on a patient before the run
+    ///
+    /// # Arguments
+    /// * `patient` - Patient to modify
+    /// * `value` - Parameter value
+    pub fn apply(&self, patient: &mut Patient, value: f64) {
+        match self {
+            SweepParameter::ValveStenosis(valve) => {
+                if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                    heart.valve_mut(*valve).stenosis_severity = value.clamp(0.0, 1.0);
+                }
+            }
+            SweepParameter::ArterialStenosis(name) => {
+                if let Some(vessel) = patient
+                    .get_organ_mut::<VascularSystem>()
+                    .and_then(|v| v.vessel_mut(name))
+                {
+                    vessel.plaque_buildup = (value / 0.8).clamp(0.0, 1.0);
+                    vessel.calculate_volume();
+                }
+            }
+            SweepParameter::HemorrhageRate => {
+                if value > 0.0 {
+                    patient.wounds.push(Wound::laceration("Sweep laceration", value));
+                }
+            }
+            SweepParameter::Fio2 => {
+                if let Some(lungs) = patient.get_organ_mut::<Lungs>() {
+                    lungs.set_fio2(value);
+                }
+            }
+            SweepParameter::ExerciseIntensity => patient.set_exercise_intensity(value),
+        }
+    }
+
+    /// Column name in the result table
+    pub fn name(&self) -> String {
+        match self {
+            SweepParameter::ValveStenosis(valve) => format!("{:?}Stenosis", valve),
+            SweepParameter::ArterialStenosis(name) => format!("Stenosis {}", name),
+            SweepParameter::HemorrhageRate => "HemorrhageRate".to_string(),
+            SweepParameter::Fio2 => "Fio2".to_string(),
+            SweepParameter::ExerciseIntensity => "ExerciseIntensity".to_string(),
+        }
+    }
+}
+
+/// One parameter and the values it takes
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepAxis {
+    /// Parameter varied
+    pub parameter: SweepParameter,
+    /// Values the parameter takes
+    pub values: Vec<f64>,
+}
+
+impl SweepAxis {
+    /// Evenly spaced values from `start` to `end` inclusive
+    ///
+    /// # Arguments
+    /// * `parameter` - Parameter varied
+    /// * `start` - First value
+    /// * `end` - Last value
+    /// * `steps` - Number of values (at least 1)
+    pub fn linear(parameter: SweepParameter, start: f64, end: f64, steps: usize) -> Self {
+        let steps = steps.max(1);
+        let values = (0..steps)
+            .map(|i| if steps == 1 { start } else { start + (end - start) * i as f64 / (steps - 1) as f64 })
+            .collect();
+        Self { parameter, values }
+    }
+}
+
+/// Sweep of model parameters over every combination of their values
+#[derive(Debug, Clone)]
+pub struct ParameterSweep {
+    /// Parameters varied; the runs cover every combination of their values
+    pub axes: Vec<SweepAxis>,
+    /// Outcomes recorded at the end of each run
+    pub measures: Vec<Measure>,
+    /// Simulated time of each run after the parameters are applied (s)
+    pub duration_s: f64,
+    /// Simulation time step (s)
+    pub time_step_s: f64,
+    /// Simulated time before the parameters are applied, for the patient to settle (s)
+    pub baseline_s: f64,
+}
+
+impl ParameterSweep {
+    /// Create a sweep with no parameters yet
+    ///
+    /// # Arguments
+    /// * `duration_s` - Simulated time of each run (s)
+    /// * `measures` - Outcomes to record
+    pub fn new(duration_s: f64, measures: Vec<Measure>) -> Self {
+        Self {
+            axes: Vec::new(),
+            measures,
+            duration_s: duration_s.max(0.0),
+            time_step_s: 0.1,
+            baseline_s: 60.0,
+        }
+    }
+
+    /// Add a parameter to vary
+    pub fn add_axis(&mut self, axis: SweepAxis) {
+        self.axes.push(axis);
+    }
+
+    /// Every combination of parameter values, the last axis varying fastest
+    pub fn combinations(&self) -> Vec<Vec<f64>> {
+        self.axes.iter().fold(vec![Vec::new()], |combinations, axis| {
+            combinations
+                .iter()
+                .flat_map(|prefix| {
+                    axis.values.iter().map(move |&value| {
+                        let mut combination = prefix.clone();
+                        combination.push(value);
+                        combination
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Run the sweep in a standard adult
+    pub fn run(&self) -> SweepTable {
+        self.run_with(|_| {})
+    }
+
+    /// Run the sweep in a patient prepared by `setup`
+    ///
+    /// Each combination is simulated in a fresh patient: `setup` runs first,
+    /// the patient settles for the baseline period, the parameters are applied
+    /// and the run continues for the sweep duration. Runs are spread over the
+    /// available cores.
+    ///
+    /// # Arguments
+    /// * `setup` - Prepares each patient (demographics, disease, treatment)
+    pub fn run_with(&self, setup: impl Fn(&mut Patient) + Sync) -> SweepTable {
+        let combinations = self.combinations();
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get()).min(combinations.len()).max(1);
+        let mut outcomes: Vec<(usize, Vec<f64>)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|worker| {
+                    let (combinations, setup) = (&combinations, &setup);
+                    scope.spawn(move || {
+                        combinations
+                            .iter()
+                            .enumerate()
+                            .skip(worker)
+                            .step_by(workers)
+                            .map(|(index, values)| (index, self.simulate(values, setup)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles.into_iter().flat_map(|h| h.join().expect("sweep run panicked")).collect()
+        });
+        outcomes.sort_by_key(|(index, _)| *index);
+
+        SweepTable {
+            parameters: self.axes.iter().map(|a| a.parameter.clone()).collect(),
+            measures: self.measures.clone(),
+            rows: combinations
+                .into_iter()
+                .zip(outcomes)
+                .map(|(parameters, (_, outcomes))| SweepRow { parameters, outcomes })
+                .collect(),
+        }
+    }
+
+    /// Simulate one combination of parameter values
+    fn simulate(&self, values: &[f64], setup: &impl Fn(&mut Patient)) -> Vec<f64> {
+        let mut patient = initialize_patient(1, 12);
+        setup(&mut patient);
+        let step = self.time_step_s.max(1e-3);
+        for _ in 0..(self.baseline_s / step).round() as usize {
+            update_patient(&mut patient, step);
+        }
+        for (axis, &value) in self.axes.iter().zip(values) {
+            axis.parameter.apply(&mut patient, value);
+        }
+        for _ in 0..(self.duration_s / step).round() as usize {
+            update_patient(&mut patient, step);
+        }
+        self.measures.iter().map(|m| m.measure(&patient)).collect()
+    }
+}
+
+/// One run of a sweep
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepRow {
+    /// Parameter values, in axis order
+    pub parameters: Vec<f64>,
+    /// Outcomes, in measure order
+    pub outcomes: Vec<f64>,
+}
+
+/// Outcomes of every run of a sweep
+#[derive(Debug, Clone)]
+pub struct SweepTable {
+    /// Parameters varied, in column order
+    pub parameters: Vec<SweepParameter>,
+    /// Outcomes recorded, in column order
+    pub measures: Vec<Measure>,
+    /// One row per combination of parameter values
+    pub rows: Vec<SweepRow>,
+}
+
+impl SweepTable {
+    /// Values of one outcome across the runs
+    pub fn column(&self, measure: Measure) -> Option<Vec<f64>> {
+        let index = self.measures.iter().position(|&m| m == measure)?;
+        Some(self.rows.iter().map(|r| r.outcomes[index]).collect())
+    }
+
+    /// Sensitivity of an outcome to a parameter
+    ///
+    /// The least-squares slope of the outcome against the parameter across all
+    /// runs, in outcome units per parameter unit. With several parameters
+    /// swept over a full grid, the other parameters average out.
+    ///
+    /// # Arguments
+    /// * `axis` - Index of the parameter
+    /// * `measure` - Outcome
+    ///
+    /// # Returns
+    /// The slope, or None if the outcome was not recorded or the parameter did not vary
+    pub fn sensitivity(&self, axis: usize, measure: Measure) -> Option<f64> {
+        let outcomes = self.column(measure)?;
+        let values: Vec<f64> = self.rows.iter().map(|r| r.parameters.get(axis).copied()).collect::<Option<_>>()?;
+        let n = values.len() as f64;
+        let mean_value = values.iter().sum::<f64>() / n;
+        let mean_outcome = outcomes.iter().sum::<f64>() / n;
+        let covariance: f64 =
+            values.iter().zip(&outcomes).map(|(v, o)| (v - mean_value) * (o - mean_outcome)).sum();
+        let variance: f64 = values.iter().map(|v| (v - mean_value).powi(2)).sum();
+        (variance > 0.0).then(|| covariance / variance)
+    }
+
+    /// Format the table as CSV, one row per run
+    pub fn to_csv(&self) -> String {
+        let header: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|p| p.name())
+            .chain(self.measures.iter().map(|m| match m.units() {
+                "" => format!("{:?}", m),
+                units => format!("{:?} ({})", m, units),
+            }))
+            .map(|name| if name.contains(',') { format!("\"{}\"", name) } else { name })
+            .collect();
+        let mut csv = header.join(",");
+        csv.push('\n');
+        for row in &self.rows {
+            let cells: Vec<String> = row.parameters.iter().chain(&row.outcomes).map(|v| format!("{}", v)).collect();
+            csv.push_str(&cells.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_grid_and_sensitivity() {
+        let mut sweep = ParameterSweep::new(120.0, vec![Measure::HeartRate, Measure::ArterialSaturation]);
+        sweep.add_axis(SweepAxis::linear(SweepParameter::HemorrhageRate, 0.0, 400.0, 3));
+        sweep.add_axis(SweepAxis::linear(SweepParameter::Fio2, 0.21, 1.0, 2));
+        assert_eq!(sweep.combinations().len(), 6);
+        assert_eq!(sweep.combinations()[1], vec![0.0, 1.0]);
+
+        let table = sweep.run();
+        assert_eq!(table.rows.len(), 6);
+        assert_eq!(table.rows[5].parameters, vec![400.0, 1.0]);
+        // Bleeding speeds the heart; oxygen raises the saturation
+        assert!(table.sensitivity(0, Measure::HeartRate).unwrap() > 0.0);
+        assert!(table.sensitivity(1, Measure::ArterialSaturation).unwrap() > 0.0);
+        assert!(table.sensitivity(0, Measure::Lactate).is_none());
+
+        let csv = table.to_csv();
+        assert!(csv.starts_with("HemorrhageRate,Fio2,HeartRate (bpm),ArterialSaturation (%)\n"));
+        assert_eq!(csv.lines().count(), 7);
+    }
+}
@@ -0,0 +1,222 @@
+//! Simulation telemetry
+//!
+//! This module provides (with the `telemetry` feature):
+//! - Time spent updating each organ per tick (µs), with the latest, mean and
+//!   peak, and the time of the whole tick, so real-time applications can
+//!   budget the hot organs
+//! - Heap allocations made by each organ per tick, counted when
+//!   `CountingAllocator` is installed as the program's global allocator
+//! - A ranking of organs by their mean cost per tick
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::time::Instant;
+
+thread_local! {
+    /// Allocations made on this thread through `CountingAllocator`
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Global allocator counting the allocations of each thread
+///
+/// Install it in the application to have allocations reported:
+///
+/// ```text
+/// #[global_allocator]
+/// static ALLOCATOR: medicallib::telemetry::CountingAllocator = medicallib::telemetry::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn count() {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::count();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::count();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::count();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Allocations made so far on the current thread (0 unless `CountingAllocator` is installed)
+pub fn allocation_count() -> u64 {
+    ALLOCATIONS.try_with(Cell::get).unwrap_or(0)
+}
+
+/// Start of a measured section
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Probe {
+    started: Instant,
+    allocations: u64,
+}
+
+impl Probe {
+    /// Start measuring
+    pub(crate) fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            allocations: allocation_count(),
+        }
+    }
+}
+
+/// Cost of one measured section across ticks
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SectionStats {
+    /// Ticks measured
+    pub ticks: u64,
+    /// Time of the latest tick (µs)
+    pub last_us: f64,
+    /// Time of all ticks (µs)
+    pub total_us: f64,
+    /// Longest tick (µs)
+    pub max_us: f64,
+    /// Allocations in the latest tick
+    pub last_allocations: u64,
+    /// Allocations in all ticks
+    pub total_allocations: u64,
+}
+
+impl SectionStats {
+    /// Mean time per tick (µs)
+    pub fn mean_us(&self) -> f64 {
+        if self.ticks == 0 {
+            return 0.0;
+        }
+        self.total_us / self.ticks as f64
+    }
+
+    /// Mean allocations per tick
+    pub fn mean_allocations(&self) -> f64 {
+        if self.ticks == 0 {
+            return 0.0;
+        }
+        self.total_allocations as f64 / self.ticks as f64
+    }
+
+    fn record(&mut self, probe: Probe) {
+        let elapsed_us = probe.started.elapsed().as_secs_f64() * 1e6;
+        let allocations = allocation_count().saturating_sub(probe.allocations);
+        self.ticks += 1;
+        self.last_us = elapsed_us;
+        self.total_us += elapsed_us;
+        self.max_us = self.max_us.max(elapsed_us);
+        self.last_allocations = allocations;
+        self.total_allocations += allocations;
+    }
+}
+
+/// Per-organ and per-tick cost of a patient's simulation
+#[derive(Debug, Clone, Default)]
+pub struct Telemetry {
+    /// Cost of each organ's update, by organ type
+    organs: HashMap<&'static str, SectionStats>,
+    /// Cost of whole ticks
+    tick: SectionStats,
+}
+
+impl Telemetry {
+    /// Record an organ update that started at `probe`
+    pub(crate) fn record_organ(&mut self, type_name: &'static str, probe: Probe) {
+        self.organs.entry(type_name).or_default().record(probe);
+    }
+
+    /// Record a whole tick that started at `probe`
+    pub(crate) fn record_tick(&mut self, probe: Probe) {
+        self.tick.record(probe);
+    }
+
+    /// Cost of an organ's updates
+    ///
+    /// # Arguments
+    /// * `type_name` - Organ type, e.g. "Heart"
+    pub fn organ(&self, type_name: &str) -> Option<&SectionStats> {
+        self.organs.get(type_name)
+    }
+
+    /// Cost of whole ticks, including the systemic models between the organs
+    pub fn tick(&self) -> &SectionStats {
+        &self.tick
+    }
+
+    /// Organs ranked from the highest to the lowest mean time per tick
+    pub fn hottest_organs(&self) -> Vec<(&'static str, SectionStats)> {
+        let mut organs: Vec<(&'static str, SectionStats)> = self.organs.iter().map(|(&name, &stats)| (name, stats)).collect();
+        organs.sort_by(|a, b| b.1.mean_us().total_cmp(&a.1.mean_us()));
+        organs
+    }
+
+    /// Clear every measurement
+    pub fn reset(&mut self) {
+        self.organs.clear();
+        self.tick = SectionStats::default();
+    }
+
+    /// Get a human-readable cost table, hottest organ first
+    pub fn get_summary(&self) -> String {
+        let mut summary = format!(
+            "Tick: mean {:.1} µs, max {:.1} µs, {:.1} allocations ({} ticks)",
+            self.tick.mean_us(),
+            self.tick.max_us,
+            self.tick.mean_allocations(),
+            self.tick.ticks
+        );
+        for (name, stats) in self.hottest_organs() {
+            summary.push_str(&format!(
+                "\n  {}: mean {:.1} µs, max {:.1} µs, {:.1} allocations",
+                name,
+                stats.mean_us(),
+                stats.max_us,
+                stats.mean_allocations()
+            ));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_organ_timings_accumulate_per_tick() {
+        let mut patient = initialize_patient(1, 12);
+        for _ in 0..20 {
+            update_patient(&mut patient, 0.1);
+        }
+        let telemetry = &patient.telemetry;
+        assert_eq!(telemetry.tick().ticks, 20);
+        let heart = telemetry.organ("Heart").unwrap();
+        assert_eq!(heart.ticks, 20);
+        assert!(heart.total_us > 0.0 && heart.max_us >= heart.mean_us());
+        assert!(telemetry.tick().total_us >= heart.total_us);
+        assert_eq!(telemetry.hottest_organs().len(), patient.organs().len());
+        assert!(telemetry.get_summary().contains("Heart: mean"));
+
+        // Without the counting allocator installed no allocations are seen
+        assert_eq!(allocation_count(), 0);
+        assert_eq!(heart.total_allocations, 0);
+
+        patient.telemetry.reset();
+        assert!(patient.telemetry.organ("Heart").is_none());
+        assert_eq!(patient.telemetry.tick(), &SectionStats::default());
+    }
+}
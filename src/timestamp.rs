@@ -0,0 +1,77 @@
+//! Wall-clock timestamps for exported records
+//!
+//! This module provides:
+//! - Conversion of Unix seconds to a UTC calendar date and time
+//! - Formatting as an ISO 8601 instant (FHIR) or a compact HL7 v2 timestamp
+
+/// Default wall-clock time of simulation time zero (2024-01-01T00:00:00Z, Unix seconds)
+pub const DEFAULT_START_UNIX_S: i64 = 1_704_067_200;
+
+/// UTC calendar date and time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CivilTime {
+    pub year: i64,
+    pub month: i64,
+    pub day: i64,
+    pub hour: i64,
+    pub minute: i64,
+    pub second: i64,
+}
+
+impl CivilTime {
+    /// Calendar time of Unix seconds (proleptic Gregorian calendar)
+    pub fn from_unix(unix_s: i64) -> Self {
+        let days = unix_s.div_euclid(86_400);
+        let seconds = unix_s.rem_euclid(86_400);
+        // Days are counted in 400-year eras starting on 0000-03-01
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        Self {
+            year: year_of_era + era * 400 + if month <= 2 { 1 } else { 0 },
+            month,
+            day: day_of_year - (153 * mp + 2) / 5 + 1,
+            hour: seconds / 3600,
+            minute: seconds % 3600 / 60,
+            second: seconds % 60,
+        }
+    }
+
+    /// ISO 8601 instant, e.g. 2024-01-01T00:00:00Z
+    pub fn iso8601(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// HL7 v2 timestamp, e.g. 20240101000000
+    #[cfg(feature = "hl7")]
+    pub fn hl7(&self) -> String {
+        format!(
+            "{:04}{:02}{:02}{:02}{:02}{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn civil(year: i64, month: i64, day: i64, hour: i64, minute: i64, second: i64) -> CivilTime {
+        CivilTime { year, month, day, hour, minute, second }
+    }
+
+    #[test]
+    fn test_calendar_time_from_unix_seconds() {
+        assert_eq!(CivilTime::from_unix(0), civil(1970, 1, 1, 0, 0, 0));
+        assert_eq!(CivilTime::from_unix(DEFAULT_START_UNIX_S), civil(2024, 1, 1, 0, 0, 0));
+        assert_eq!(CivilTime::from_unix(951_782_400 + 3_723), civil(2000, 2, 29, 1, 2, 3));
+        assert_eq!(CivilTime::from_unix(-1), civil(1969, 12, 31, 23, 59, 59));
+    }
+}
@@ -0,0 +1,431 @@
+//! Drug levels and toxicology screening
+//!
+//! This module provides:
+//! - Serum levels of the drugs measured in overdose and therapeutic monitoring
+//!   (digoxin, lithium, phenytoin, acetaminophen, salicylate) from oral
+//!   absorption, distribution and renal or hepatic elimination
+//! - A qualitative urine drug screen whose immunoassays turn positive shortly
+//!   after exposure and stay positive for each class's detection window
+//!
+//! Hydrophilic drugs distribute into lean tissue only, so in obesity their
+//! volume follows ideal rather than total body weight; lipophilic drugs also
+//! dissolve in the adipose tissue.
+//!
+//! Elimination follows the organs that clear each drug, so lithium and digoxin
+//! accumulate in kidney injury and acetaminophen in liver failure. Metabolism
+//! of phenytoin saturates within the therapeutic range, so small dose increases
+//! raise the level disproportionately, and that of acetaminophen and salicylate
+//! in overdose, prolonging their half-lives.
+
+use std::fmt;
+
+use crate::fluids::IntakeRoute;
+
+/// Delay before a drug or metabolite reaches the screening cutoff in urine (s)
+const URINE_APPEARANCE_S: f64 = 3600.0;
+
+/// Lithium carbonate carrying one mmol of lithium ion (mg)
+const LITHIUM_CARBONATE_MG_PER_MMOL: f64 = 36.95;
+
+/// Weight relative to ideal above which the excess is adipose tissue
+const LEAN_WEIGHT_RATIO: f64 = 1.2;
+
+/// Drug measured by a serum level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Drug {
+    Digoxin,
+    /// Dosed as lithium carbonate
+    Lithium,
+    Phenytoin,
+    Acetaminophen,
+    Salicylate,
+}
+
+impl Drug {
+    /// Every drug with a serum level
+    pub const ALL: [Drug; 5] = [Drug::Digoxin, Drug::Lithium, Drug::Phenytoin, Drug::Acetaminophen, Drug::Salicylate];
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Drug::Digoxin => "Digoxin",
+            Drug::Lithium => "Lithium",
+            Drug::Phenytoin => "Phenytoin",
+            Drug::Acetaminophen => "Acetaminophen",
+            Drug::Salicylate => "Salicylate",
+        }
+    }
+
+    /// Apparent volume of distribution (L/kg)
+    pub fn volume_of_distribution_l_kg(&self) -> f64 {
+        match self {
+            Drug::Digoxin => 7.0,
+            Drug::Lithium => 0.8,
+            Drug::Phenytoin => 0.7,
+            Drug::Acetaminophen => 0.9,
+            Drug::Salicylate => 0.2,
+        }
+    }
+
+    /// Volume of distribution per kg of adipose tissue relative to lean tissue
+    pub fn adipose_distribution(&self) -> f64 {
+        match self {
+            Drug::Digoxin | Drug::Lithium | Drug::Salicylate => 0.0,
+            Drug::Phenytoin => 1.3,
+            Drug::Acetaminophen => 0.4,
+        }
+    }
+
+    /// Elimination half-life with normal kidneys and liver, at concentrations
+    /// well below saturation (h)
+    pub fn half_life_h(&self) -> f64 {
+        match self {
+            Drug::Digoxin => 36.0,
+            Drug::Lithium => 24.0,
+            // Vmax ~7 mg/kg/day over Km 4 mg/L in 0.7 L/kg
+            Drug::Phenytoin => 6.7,
+            Drug::Acetaminophen => 2.0,
+            Drug::Salicylate => 2.5,
+        }
+    }
+
+    /// Concentration at which metabolism runs at half its maximal rate
+    /// (Michaelis constant, mg/L); None when elimination stays first-order
+    pub fn saturation_mg_l(&self) -> Option<f64> {
+        match self {
+            Drug::Phenytoin => Some(4.0),
+            Drug::Acetaminophen => Some(100.0),
+            Drug::Salicylate => Some(150.0),
+            Drug::Digoxin | Drug::Lithium => None,
+        }
+    }
+
+    /// Fraction of elimination by the kidneys; the rest is hepatic
+    pub fn renal_fraction(&self) -> f64 {
+        match self {
+            Drug::Digoxin => 0.7,
+            Drug::Lithium => 0.95,
+            Drug::Phenytoin => 0.0,
+            Drug::Acetaminophen => 0.05,
+            Drug::Salicylate => 0.3,
+        }
+    }
+
+    /// Time constant of absorption of an oral dose (s)
+    pub fn absorption_s(&self) -> f64 {
+        match self {
+            Drug::Digoxin => 3600.0,
+            Drug::Lithium => 5400.0,
+            Drug::Phenytoin => 4.0 * 3600.0,
+            Drug::Acetaminophen => 1800.0,
+            Drug::Salicylate => 3600.0,
+        }
+    }
+
+    /// Serum level reported for a concentration of the dosed compound (mg/L)
+    pub fn level_from_mg_l(&self, mg_l: f64) -> f64 {
+        match self {
+            // ng/mL
+            Drug::Digoxin => mg_l * 1000.0,
+            // mmol/L of lithium ion
+            Drug::Lithium => mg_l / LITHIUM_CARBONATE_MG_PER_MMOL,
+            // µg/mL
+            Drug::Phenytoin | Drug::Acetaminophen => mg_l,
+            // mg/dL
+            Drug::Salicylate => mg_l / 10.0,
+        }
+    }
+}
+
+impl fmt::Display for Drug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Drug class detected by a urine immunoassay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScreenClass {
+    Amphetamines,
+    Barbiturates,
+    Benzodiazepines,
+    Cannabinoids,
+    /// Detected as the metabolite benzoylecgonine
+    Cocaine,
+    Methadone,
+    Opiates,
+    Phencyclidine,
+    TricyclicAntidepressants,
+}
+
+impl ScreenClass {
+    /// Every class on the screen, in report order
+    pub const ALL: [ScreenClass; 9] = [
+        ScreenClass::Amphetamines,
+        ScreenClass::Barbiturates,
+        ScreenClass::Benzodiazepines,
+        ScreenClass::Cannabinoids,
+        ScreenClass::Cocaine,
+        ScreenClass::Methadone,
+        ScreenClass::Opiates,
+        ScreenClass::Phencyclidine,
+        ScreenClass::TricyclicAntidepressants,
+    ];
+
+    /// Name as printed on a report
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScreenClass::Amphetamines => "Amphetamines",
+            ScreenClass::Barbiturates => "Barbiturates",
+            ScreenClass::Benzodiazepines => "Benzodiazepines",
+            ScreenClass::Cannabinoids => "Cannabinoids",
+            ScreenClass::Cocaine => "Cocaine",
+            ScreenClass::Methadone => "Methadone",
+            ScreenClass::Opiates => "Opiates",
+            ScreenClass::Phencyclidine => "PCP",
+            ScreenClass::TricyclicAntidepressants => "Tricyclics",
+        }
+    }
+
+    /// Time after a single exposure the urine stays above the screening cutoff (h)
+    pub fn detection_window_h(&self) -> f64 {
+        match self {
+            ScreenClass::Amphetamines => 48.0,
+            ScreenClass::Barbiturates => 48.0,
+            ScreenClass::Benzodiazepines => 72.0,
+            ScreenClass::Cannabinoids => 72.0,
+            ScreenClass::Cocaine => 72.0,
+            ScreenClass::Methadone => 72.0,
+            ScreenClass::Opiates => 48.0,
+            ScreenClass::Phencyclidine => 192.0,
+            ScreenClass::TricyclicAntidepressants => 168.0,
+        }
+    }
+}
+
+impl fmt::Display for ScreenClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Result of one immunoassay on the urine drug screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenResult {
+    /// Class tested
+    pub class: ScreenClass,
+    /// Above the screening cutoff
+    pub positive: bool,
+}
+
+/// Qualitative urine drug screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToxScreen {
+    /// Simulation time of collection (s)
+    pub collected_s: f64,
+    /// One result per class, in report order
+    pub results: Vec<ScreenResult>,
+}
+
+impl ToxScreen {
+    /// Classes that screened positive
+    pub fn positives(&self) -> Vec<ScreenClass> {
+        self.results.iter().filter(|r| r.positive).map(|r| r.class).collect()
+    }
+
+    /// Result for a class
+    pub fn is_positive(&self, class: ScreenClass) -> bool {
+        self.results.iter().any(|r| r.class == class && r.positive)
+    }
+
+    /// Get a summary of the screen
+    pub fn get_summary(&self) -> String {
+        let results: Vec<String> = self
+            .results
+            .iter()
+            .map(|r| format!("{} {}", r.class, if r.positive { "POS" } else { "neg" }))
+            .collect();
+        format!("Urine drug screen: {}", results.join(", "))
+    }
+}
+
+/// Drugs in the body and exposures the urine screen detects
+#[derive(Debug, Clone)]
+pub struct Toxicology {
+    /// Oral doses not yet absorbed (mg)
+    gut_mg: [f64; Drug::ALL.len()],
+    /// Absorbed drug in the body (mg)
+    body_mg: [f64; Drug::ALL.len()],
+    /// Serum level of each drug, in its reporting units
+    levels: [f64; Drug::ALL.len()],
+    /// Most recent exposure to each screened class (simulation time, s)
+    last_exposure_s: [Option<f64>; ScreenClass::ALL.len()],
+    /// Time since the toxicology state was created (s)
+    pub elapsed_s: f64,
+}
+
+impl Toxicology {
+    /// Create a drug-free state
+    pub fn new() -> Self {
+        Self {
+            gut_mg: [0.0; Drug::ALL.len()],
+            body_mg: [0.0; Drug::ALL.len()],
+            levels: [0.0; Drug::ALL.len()],
+            last_exposure_s: [None; ScreenClass::ALL.len()],
+            elapsed_s: 0.0,
+        }
+    }
+
+    /// Give a dose of a drug
+    ///
+    /// # Arguments
+    /// * `drug` - Drug given
+    /// * `mg` - Dose in mg (lithium as lithium carbonate)
+    /// * `route` - Intravenous doses enter the blood at once; oral and enteral
+    ///   doses are absorbed over the drug's absorption time
+    pub fn give(&mut self, drug: Drug, mg: f64, route: IntakeRoute) {
+        let mg = mg.max(0.0);
+        match route {
+            IntakeRoute::Intravenous => self.body_mg[drug as usize] += mg,
+            _ => self.gut_mg[drug as usize] += mg,
+        }
+    }
+
+    /// Record exposure to a screened drug class now
+    pub fn expose(&mut self, class: ScreenClass) {
+        self.last_exposure_s[class as usize] = Some(self.elapsed_s);
+    }
+
+    /// Current serum level of a drug, in its reporting units
+    pub fn level(&self, drug: Drug) -> f64 {
+        self.levels[drug as usize]
+    }
+
+    /// Drug still in the gut and available to activated charcoal (mg)
+    pub fn unabsorbed_mg(&self, drug: Drug) -> f64 {
+        self.gut_mg[drug as usize]
+    }
+
+    /// Collect urine and run the immunoassay screen
+    pub fn screen(&self) -> ToxScreen {
+        let results = ScreenClass::ALL
+            .iter()
+            .map(|&class| {
+                let positive = self.last_exposure_s[class as usize].is_some_and(|exposure| {
+                    let since = self.elapsed_s - exposure;
+                    (URINE_APPEARANCE_S..=class.detection_window_h() * 3600.0).contains(&since)
+                });
+                ScreenResult { class, positive }
+            })
+            .collect();
+        ToxScreen {
+            collected_s: self.elapsed_s,
+            results,
+        }
+    }
+
+    /// Absorb, distribute and eliminate every drug
+    ///
+    /// # Arguments
+    /// * `weight_kg` - Body weight, scaling the volumes of distribution
+    /// * `ideal_weight_kg` - Ideal body weight; weight well above it is adipose tissue
+    /// * `renal_function` - GFR relative to normal
+    /// * `hepatic_function` - Liver function relative to normal
+    /// * `delta_time_s` - Time step in seconds
+    pub fn update(
+        &mut self,
+        weight_kg: f64,
+        ideal_weight_kg: f64,
+        renal_function: f64,
+        hepatic_function: f64,
+        delta_time_s: f64,
+    ) {
+        self.elapsed_s += delta_time_s;
+        let weight_kg = weight_kg.max(1.0);
+        let lean_kg = if ideal_weight_kg > 0.0 { weight_kg.min(LEAN_WEIGHT_RATIO * ideal_weight_kg) } else { weight_kg };
+        let adipose_kg = weight_kg - lean_kg;
+        for drug in Drug::ALL {
+            let i = drug as usize;
+            let absorbed = self.gut_mg[i] * (1.0 - (-delta_time_s / drug.absorption_s()).exp());
+            self.gut_mg[i] -= absorbed;
+            self.body_mg[i] += absorbed;
+
+            let volume_l = drug.volume_of_distribution_l_kg() * (lean_kg + drug.adipose_distribution() * adipose_kg);
+            let clearance = drug.renal_fraction() * renal_function.max(0.0)
+                + (1.0 - drug.renal_fraction()) * hepatic_function.max(0.0);
+            let saturation = drug
+                .saturation_mg_l()
+                .map_or(1.0, |km| km / (km + self.body_mg[i] / volume_l));
+            let rate = std::f64::consts::LN_2 / (drug.half_life_h() * 3600.0) * clearance * saturation;
+            self.body_mg[i] *= (-rate * delta_time_s).exp();
+            self.levels[i] = drug.level_from_mg_l(self.body_mg[i] / volume_l);
+        }
+    }
+
+    /// Get a summary of the measurable drug levels
+    pub fn get_summary(&self) -> String {
+        let levels: Vec<String> = Drug::ALL
+            .iter()
+            .filter(|drug| self.level(**drug) > 0.0)
+            .map(|drug| format!("{} {:.1}", drug, self.level(*drug)))
+            .collect();
+        if levels.is_empty() {
+            "Toxicology: no drugs on board".to_string()
+        } else {
+            format!("Toxicology: {}", levels.join(", "))
+        }
+    }
+}
+
+impl Default for Toxicology {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::labs::{Analyte, LabFlag, LabPanel};
+    use crate::organs::kidneys::Kidneys;
+    use crate::patient::{initialize_patient, update_patient};
+
+    #[test]
+    fn test_overdose_levels_and_screen_follow_exposure() {
+        let mut patient = initialize_patient(1, 3);
+        patient.labs.noise = false;
+        patient.give_drug(Drug::Acetaminophen, 20_000.0, IntakeRoute::Oral);
+        patient.give_drug(Drug::Lithium, 1_800.0, IntakeRoute::Oral);
+        patient.toxicology.expose(ScreenClass::Opiates);
+        assert!(patient.urine_drug_screen().positives().is_empty());
+
+        let mut anuric = patient.fork();
+        anuric.get_organ_mut::<Kidneys>().unwrap().damage_nephrons(100.0);
+        anuric.get_organ_mut::<Kidneys>().unwrap().damage_nephrons(100.0);
+        for _ in 0..240 {
+            update_patient(&mut patient, 60.0);
+            update_patient(&mut anuric, 60.0);
+        }
+
+        // A 4-hour acetaminophen level above the treatment line is phoned through
+        for panel in LabPanel::DRUG_LEVELS {
+            patient.labs.set_turnaround_s(panel, 0.0);
+        }
+        let ids: Vec<u32> = LabPanel::DRUG_LEVELS.iter().map(|&panel| patient.order_labs(panel)).collect();
+        let result = |analyte| {
+            ids.iter()
+                .find_map(|&id| patient.labs.report(id).unwrap().result(analyte))
+                .unwrap()
+        };
+        assert_eq!(result(Analyte::Acetaminophen).flag, LabFlag::CriticalHigh, "{:?}", result(Analyte::Acetaminophen));
+        assert_eq!(result(Analyte::Digoxin).value, 0.0);
+        assert_eq!(result(Analyte::Lithium).flag, LabFlag::Normal);
+        // Lithium accumulates without the kidneys
+        assert!(anuric.toxicology.level(Drug::Lithium) > patient.toxicology.level(Drug::Lithium) * 1.05);
+
+        let screen = patient.urine_drug_screen();
+        assert_eq!(screen.positives(), [ScreenClass::Opiates]);
+        patient.toxicology.update(patient.get_weight_kg(), patient.get_ideal_body_weight_kg(), 1.0, 1.0, 48.0 * 3600.0);
+        assert!(!patient.urine_drug_screen().is_positive(ScreenClass::Opiates));
+    }
+}
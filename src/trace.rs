@@ -0,0 +1,116 @@
+//! Structured tracing
+//!
+//! This module provides (with the `tracing` feature):
+//! - Events for interventions given to a patient, under the
+//!   `medicallib::intervention` target
+//! - Events for emergent changes in the simulation (rhythm changes, released
+//!   lab reports, finished processes, stability corrections), under the
+//!   `medicallib::event` target
+//!
+//! `update_patient` runs in an `update_patient` span, with one `organ` span
+//! per organ update, so a host's subscriber can time and filter the simulation
+//! alongside its own spans. Without the feature the macros expand to nothing.
+
+/// Emit an intervention event
+macro_rules! intervention {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::info!(target: "medicallib::intervention", $($arg)+);
+    };
+}
+
+/// Emit an emergent simulation event
+macro_rules! emergent {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!(target: "medicallib::event", $($arg)+);
+    };
+}
+
+pub(crate) use emergent;
+pub(crate) use intervention;
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::labs::LabPanel;
+    use crate::organs::heart::Heart;
+    use crate::patient::{initialize_patient, update_patient};
+    use std::fmt;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Subscriber recording span names and event messages
+    #[derive(Default)]
+    struct Recorder {
+        next_id: AtomicU64,
+        spans: Arc<Mutex<Vec<&'static str>>>,
+        events: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    struct Message(String);
+
+    impl Visit for Message {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == "message" {
+                self.0 = format!("{:?}", value);
+            }
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            self.spans.lock().unwrap().push(span.metadata().name());
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut message = Message(String::new());
+            event.record(&mut message);
+            self.events.lock().unwrap().push((event.metadata().target().to_string(), message.0));
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_spans_and_events_reach_the_subscriber() {
+        let recorder = Recorder::default();
+        let (spans, events) = (recorder.spans.clone(), recorder.events.clone());
+        tracing::subscriber::with_default(recorder, || {
+            let mut patient = initialize_patient(1, 3);
+            patient.order_labs(LabPanel::Lactate);
+            if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                heart.induce_ventricular_fibrillation();
+            }
+            patient.give_epinephrine(1.0);
+            for _ in 0..20 {
+                update_patient(&mut patient, 60.0);
+            }
+        });
+
+        let spans = spans.lock().unwrap();
+        assert_eq!(spans.iter().filter(|&&name| name == "update_patient").count(), 20);
+        assert!(spans.iter().filter(|&&name| name == "organ").count() >= 20 * 10);
+
+        let events = events.lock().unwrap();
+        let targets = |target: &str| events.iter().filter(|(t, _)| t == target).map(|(_, m)| m.as_str()).collect::<Vec<_>>();
+        let interventions = targets("medicallib::intervention");
+        assert_eq!(interventions, ["lab order", "epinephrine"]);
+        let emergent = targets("medicallib::event");
+        assert!(emergent.contains(&"rhythm changed"));
+        assert!(emergent.contains(&"lab report released"));
+    }
+}
@@ -0,0 +1,361 @@
+//! Validation against published physiological ranges
+//!
+//! This module provides:
+//! - Canonical scenarios (healthy rest, moderate exercise, 30% hemorrhage and a
+//!   complete LAD occlusion) that set up a standard adult and run it to a new
+//!   steady state
+//! - Literature-derived reference ranges for the key outputs of each scenario
+//! - A report of the outputs that fall outside their range, so the realism of
+//!   the model can be checked as a regression criterion
+
+use crate::organs::heart::{CoronaryTerritory, Heart};
+use crate::patient::{initialize_patient, update_patient, Patient};
+
+/// Simulation time step for the validation runs (s)
+const VALIDATION_STEP_S: f64 = 0.1;
+
+/// Time the patient settles before the scenario starts (s)
+const BASELINE_S: f64 = 60.0;
+
+/// Time from the start of the scenario to the measurements (s)
+const SCENARIO_S: f64 = 600.0;
+
+/// Output compared against a reference range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Measure {
+    /// Heart rate (bpm)
+    HeartRate,
+    /// Systolic blood pressure (mmHg)
+    SystolicPressure,
+    /// Diastolic blood pressure (mmHg)
+    DiastolicPressure,
+    /// Mean arterial pressure (mmHg)
+    MeanArterialPressure,
+    /// Systolic minus diastolic pressure (mmHg)
+    PulsePressure,
+    /// Cardiac output (L/min)
+    CardiacOutput,
+    /// Stroke volume (mL)
+    StrokeVolume,
+    /// Cardiac output per body surface area (L/min/m²)
+    CardiacIndex,
+    /// Stroke volume per body surface area (mL/m²)
+    StrokeVolumeIndex,
+    /// Left ventricular ejection fraction (%)
+    EjectionFraction,
+    /// Left atrial pressure, the pulmonary capillary wedge pressure (mmHg)
+    LeftAtrialPressure,
+    /// Arterial oxygen saturation (%)
+    ArterialSaturation,
+    /// Mixed venous oxygen saturation (%)
+    MixedVenousSaturation,
+    /// Whole-body oxygen consumption (mL/min)
+    OxygenConsumption,
+    /// Exhaled CO2 by metabolic cart (mL/min)
+    Co2Production,
+    /// Respiratory quotient by metabolic cart (VCO2/VO2)
+    RespiratoryQuotient,
+    /// Energy expenditure by metabolic cart, Weir equation (kcal/day)
+    EnergyExpenditure,
+    /// Blood lactate (mmol/L)
+    Lactate,
+    /// Arterial pH
+    ArterialPh,
+}
+
+impl Measure {
+    /// Read this output from a patient
+    ///
+    /// # Arguments
+    /// * `patient` - Patient to measure
+    ///
+    /// # Returns
+    /// The measured value, or NaN if the patient has no heart to measure
+    pub fn measure(&self, patient: &Patient) -> f64 {
+        let blood = &patient.blood;
+        let heart = patient.get_organ::<Heart>();
+        let from_heart = |read: fn(&Heart) -> f64| heart.map_or(f64::NAN, read);
+        match self {
+            Measure::HeartRate => from_heart(|h| h.heart_rate_bpm),
+            Measure::SystolicPressure => blood.blood_pressure_systolic,
+            Measure::DiastolicPressure => blood.blood_pressure_diastolic,
+            Measure::MeanArterialPressure => blood.get_mean_arterial_pressure(),
+            Measure::PulsePressure => blood.blood_pressure_systolic - blood.blood_pressure_diastolic,
+            Measure::CardiacOutput => from_heart(|h| h.cardiac_output_l_min),
+            Measure::StrokeVolume => from_heart(|h| h.stroke_volume_ml),
+            Measure::CardiacIndex => patient.get_cardiac_index().unwrap_or(f64::NAN),
+            Measure::StrokeVolumeIndex => patient.get_stroke_volume_index().unwrap_or(f64::NAN),
+            Measure::EjectionFraction => from_heart(|h| h.ejection_fraction_percent),
+            Measure::LeftAtrialPressure => from_heart(|h| h.left_atrium.pressure_mmhg),
+            Measure::ArterialSaturation => blood.gases.sao2_percent,
+            Measure::MixedVenousSaturation => patient.oxygen_transport.mixed_venous_saturation_percent,
+            Measure::OxygenConsumption => patient.oxygen_transport.consumption_ml_min,
+            Measure::Co2Production => patient.metabolic_cart().vco2_ml_min,
+            Measure::RespiratoryQuotient => patient.metabolic_cart().respiratory_quotient,
+            Measure::EnergyExpenditure => patient.metabolic_cart().energy_expenditure_kcal_day,
+            Measure::Lactate => blood.chemistry.lactate_mmol_l,
+            Measure::ArterialPh => blood.gases.ph,
+        }
+    }
+
+    /// Units the measure is reported in
+    pub fn units(&self) -> &'static str {
+        match self {
+            Measure::HeartRate => "bpm",
+            Measure::SystolicPressure
+            | Measure::DiastolicPressure
+            | Measure::MeanArterialPressure
+            | Measure::PulsePressure
+            | Measure::LeftAtrialPressure => "mmHg",
+            Measure::CardiacOutput => "L/min",
+            Measure::StrokeVolume => "mL",
+            Measure::CardiacIndex => "L/min/m²",
+            Measure::StrokeVolumeIndex => "mL/m²",
+            Measure::EjectionFraction | Measure::ArterialSaturation | Measure::MixedVenousSaturation => "%",
+            Measure::OxygenConsumption | Measure::Co2Production => "mL/min",
+            Measure::EnergyExpenditure => "kcal/day",
+            Measure::Lactate => "mmol/L",
+            Measure::ArterialPh | Measure::RespiratoryQuotient => "",
+        }
+    }
+}
+
+/// Published range an output should fall within
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceRange {
+    /// Output the range applies to
+    pub measure: Measure,
+    /// Lowest acceptable value
+    pub low: f64,
+    /// Highest acceptable value
+    pub high: f64,
+    /// Source of the range
+    pub source: &'static str,
+}
+
+impl ReferenceRange {
+    /// Create a reference range
+    ///
+    /// # Arguments
+    /// * `measure` - Output the range applies to
+    /// * `low` - Lowest acceptable value
+    /// * `high` - Highest acceptable value
+    /// * `source` - Source of the range
+    pub const fn new(measure: Measure, low: f64, high: f64, source: &'static str) -> Self {
+        Self { measure, low, high, source }
+    }
+
+    /// Whether a value falls within the range
+    pub fn contains(&self, value: f64) -> bool {
+        value >= self.low && value <= self.high
+    }
+}
+
+/// Canonical scenario with known physiological responses
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValidationScenario {
+    /// Healthy adult at rest
+    HealthyRest,
+    /// Sustained exercise at half of maximal capacity
+    ModerateExercise,
+    /// Sudden loss of 30% of the blood volume (ATLS class II-III)
+    Hemorrhage30Percent,
+    /// Complete occlusion of the left anterior descending artery (anterior STEMI)
+    CompleteLadOcclusion,
+}
+
+impl ValidationScenario {
+    /// All canonical scenarios
+    pub const ALL: [ValidationScenario; 4] = [
+        ValidationScenario::HealthyRest,
+        ValidationScenario::ModerateExercise,
+        ValidationScenario::Hemorrhage30Percent,
+        ValidationScenario::CompleteLadOcclusion,
+    ];
+
+    /// Apply the scenario's intervention to a settled patient
+    ///
+    /// # Arguments
+    /// * `patient` - Patient at baseline
+    pub fn start(&self, patient: &mut Patient) {
+        match self {
+            ValidationScenario::HealthyRest => {}
+            ValidationScenario::ModerateExercise => patient.set_exercise_intensity(0.5),
+            ValidationScenario::Hemorrhage30Percent => {
+                let volume_ml = 0.3 * patient.fluids.reference_blood_volume_ml;
+                patient.hemorrhage(volume_ml);
+            }
+            ValidationScenario::CompleteLadOcclusion => {
+                if let Some(heart) = patient.get_organ_mut::<Heart>() {
+                    heart.set_territory_contractility(CoronaryTerritory::LeftAnteriorDescending, 0.0);
+                }
+            }
+        }
+    }
+
+    /// Published ranges for the scenario's key outputs
+    pub fn reference_ranges(&self) -> Vec<ReferenceRange> {
+        use Measure::*;
+        match self {
+            ValidationScenario::HealthyRest => vec![
+                ReferenceRange::new(HeartRate, 60.0, 100.0, "AHA adult resting heart rate"),
+                ReferenceRange::new(SystolicPressure, 90.0, 130.0, "ACC/AHA 2017 normal and elevated blood pressure"),
+                ReferenceRange::new(DiastolicPressure, 60.0, 85.0, "ACC/AHA 2017 normal blood pressure"),
+                ReferenceRange::new(MeanArterialPressure, 70.0, 105.0, "Guyton & Hall, Textbook of Medical Physiology"),
+                ReferenceRange::new(CardiacOutput, 4.0, 8.0, "Guyton & Hall, Textbook of Medical Physiology"),
+                ReferenceRange::new(StrokeVolume, 60.0, 100.0, "Guyton & Hall, Textbook of Medical Physiology"),
+                ReferenceRange::new(EjectionFraction, 52.0, 72.0, "ASE/EACVI 2015 chamber quantification"),
+                ReferenceRange::new(LeftAtrialPressure, 4.0, 12.0, "Normal pulmonary capillary wedge pressure"),
+                ReferenceRange::new(ArterialSaturation, 95.0, 100.0, "Normal arterial blood gas"),
+                ReferenceRange::new(MixedVenousSaturation, 65.0, 80.0, "Normal mixed venous oximetry"),
+                ReferenceRange::new(OxygenConsumption, 200.0, 350.0, "Resting VO2 of 3.5 mL/kg/min (1 MET)"),
+                ReferenceRange::new(Lactate, 0.5, 2.0, "Normal venous lactate"),
+                ReferenceRange::new(ArterialPh, 7.35, 7.45, "Normal arterial blood gas"),
+            ],
+            ValidationScenario::ModerateExercise => vec![
+                ReferenceRange::new(HeartRate, 110.0, 150.0, "ACSM guidelines, 50% of heart rate reserve"),
+                ReferenceRange::new(SystolicPressure, 140.0, 190.0, "ACSM normal exercise blood pressure response"),
+                ReferenceRange::new(DiastolicPressure, 60.0, 90.0, "ACSM normal exercise blood pressure response"),
+                ReferenceRange::new(CardiacOutput, 10.0, 16.0, "Fick principle at a VO2 of 1.2-1.8 L/min"),
+                ReferenceRange::new(StrokeVolume, 80.0, 130.0, "Stroke volume plateau at 40-60% VO2max"),
+                ReferenceRange::new(ArterialSaturation, 94.0, 100.0, "Saturation maintained in healthy exercise"),
+                ReferenceRange::new(MixedVenousSaturation, 30.0, 55.0, "Mixed venous oximetry during exercise"),
+                ReferenceRange::new(OxygenConsumption, 1000.0, 1800.0, "4-6 METs in a 70 kg adult"),
+                ReferenceRange::new(Lactate, 0.5, 2.5, "Below the lactate threshold"),
+            ],
+            ValidationScenario::Hemorrhage30Percent => vec![
+                ReferenceRange::new(HeartRate, 100.0, 140.0, "ATLS class II-III hemorrhage"),
+                ReferenceRange::new(SystolicPressure, 70.0, 110.0, "ATLS class III hemorrhage"),
+                ReferenceRange::new(PulsePressure, 15.0, 35.0, "ATLS narrowed pulse pressure"),
+                ReferenceRange::new(CardiacOutput, 3.0, 5.5, "Graded hemorrhage studies, reduced cardiac output"),
+                ReferenceRange::new(StrokeVolume, 30.0, 65.0, "Graded hemorrhage studies"),
+                ReferenceRange::new(MixedVenousSaturation, 50.0, 70.0, "Mixed venous desaturation in hypovolemia"),
+            ],
+            ValidationScenario::CompleteLadOcclusion => vec![
+                ReferenceRange::new(HeartRate, 60.0, 110.0, "Uncomplicated anterior STEMI"),
+                ReferenceRange::new(SystolicPressure, 90.0, 140.0, "Uncomplicated anterior STEMI (Killip I-II)"),
+                ReferenceRange::new(CardiacOutput, 3.5, 6.5, "Forrester hemodynamic subsets"),
+                ReferenceRange::new(EjectionFraction, 30.0, 50.0, "Acute anterior STEMI echocardiography"),
+                ReferenceRange::new(LeftAtrialPressure, 10.0, 25.0, "Forrester hemodynamic subsets"),
+                ReferenceRange::new(MixedVenousSaturation, 55.0, 75.0, "Mixed venous oximetry after myocardial infarction"),
+            ],
+        }
+    }
+
+    /// Run the scenario in a standard adult and compare it with its reference ranges
+    ///
+    /// # Returns
+    /// Every key output with its reference range
+    pub fn run(&self) -> ValidationReport {
+        let mut patient = initialize_patient(1, 12);
+        run_for(&mut patient, BASELINE_S);
+        self.start(&mut patient);
+        run_for(&mut patient, SCENARIO_S);
+        ValidationReport {
+            scenario: *self,
+            checks: self
+                .reference_ranges()
+                .into_iter()
+                .map(|range| ValidationCheck { value: range.measure.measure(&patient), range })
+                .collect(),
+        }
+    }
+}
+
+/// Advance a patient by a period of time
+fn run_for(patient: &mut Patient, duration_s: f64) {
+    for _ in 0..(duration_s / VALIDATION_STEP_S).round() as usize {
+        update_patient(patient, VALIDATION_STEP_S);
+    }
+}
+
+/// One output measured against its reference range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValidationCheck {
+    /// Reference range
+    pub range: ReferenceRange,
+    /// Value the model produced
+    pub value: f64,
+}
+
+impl ValidationCheck {
+    /// Whether the value falls within the reference range
+    pub fn passed(&self) -> bool {
+        self.range.contains(self.value)
+    }
+}
+
+/// Outcome of a validation scenario
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    /// Scenario that was run
+    pub scenario: ValidationScenario,
+    /// Key outputs against their reference ranges
+    pub checks: Vec<ValidationCheck>,
+}
+
+impl ValidationReport {
+    /// Whether every output falls within its reference range
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.passed())
+    }
+
+    /// Outputs outside their reference range
+    pub fn failures(&self) -> Vec<&ValidationCheck> {
+        self.checks.iter().filter(|c| !c.passed()).collect()
+    }
+
+    /// Get a summary of the validation run
+    pub fn get_summary(&self) -> String {
+        let lines: Vec<String> = self
+            .checks
+            .iter()
+            .map(|c| {
+                format!(
+                    "  {} {:?}={:.2} {} (expected {}-{}; {})",
+                    if c.passed() { "ok  " } else { "FAIL" },
+                    c.range.measure,
+                    c.value,
+                    c.range.measure.units(),
+                    c.range.low,
+                    c.range.high,
+                    c.range.source
+                )
+            })
+            .collect();
+        format!(
+            "Validation {:?}: {}/{} within range\n{}",
+            self.scenario,
+            self.checks.len() - self.failures().len(),
+            self.checks.len(),
+            lines.join("\n")
+        )
+    }
+}
+
+/// Run every canonical scenario
+///
+/// # Returns
+/// One report per scenario
+pub fn validate_all() -> Vec<ValidationReport> {
+    ValidationScenario::ALL.iter().map(|s| s.run()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_scenarios_within_published_ranges() {
+        for report in validate_all() {
+            assert!(report.passed(), "{}", report.get_summary());
+        }
+    }
+
+    #[test]
+    fn test_reference_range_bounds() {
+        let range = ReferenceRange::new(Measure::HeartRate, 60.0, 100.0, "test");
+        assert!(range.contains(60.0) && range.contains(100.0));
+        assert!(!range.contains(59.9) && !range.contains(f64::NAN));
+    }
+}
@@ -0,0 +1,171 @@
+//! Run-to-run variability
+//!
+//! This module provides:
+//! - A small seeded pseudo-random generator (SplitMix64), so a run can be
+//!   replayed exactly from its seed
+//! - A variability magnitude: 0.0 keeps the simulation fully deterministic,
+//!   1.0 gives typical between-patient variation
+//! - Bounded multiplicative factors for per-patient traits (arrhythmia
+//!   susceptibility, drug sensitivity, ischemic tolerance) and probability-weighted
+//!   outcomes for individual events such as a defibrillation shock
+//! - Chance events (a spontaneous embolus) that only happen with variability
+
+/// Draws are truncated at this many standard deviations
+const MAX_STANDARD_DEVIATIONS: f64 = 2.5;
+
+/// Seeded SplitMix64 pseudo-random number generator
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// Create a generator from a seed
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal sample (Box-Muller)
+    pub fn next_normal(&mut self) -> f64 {
+        let u1 = self.next_f64().max(f64::MIN_POSITIVE);
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
+
+/// Configurable stochasticity shared by a patient's systems
+#[derive(Debug, Clone)]
+pub struct Variability {
+    /// Seed the generator was started from
+    pub seed: u64,
+    /// Scale of variation (0.0 = deterministic, 1.0 = typical, up to 2.0)
+    pub magnitude: f64,
+    rng: SeededRng,
+}
+
+impl Variability {
+    /// No variability: every run of a scenario is identical
+    pub fn deterministic() -> Self {
+        Self::seeded(0, 0.0)
+    }
+
+    /// Variability drawn from a seed
+    ///
+    /// # Arguments
+    /// * `seed` - Generator seed; the same seed reproduces the same run
+    /// * `magnitude` - Scale of variation (0.0-2.0)
+    pub fn seeded(seed: u64, magnitude: f64) -> Self {
+        Self {
+            seed,
+            magnitude: magnitude.clamp(0.0, 2.0),
+            rng: SeededRng::new(seed),
+        }
+    }
+
+    /// Whether any variation is applied
+    pub fn is_deterministic(&self) -> bool {
+        self.magnitude <= 0.0
+    }
+
+    /// Bounded standard normal deviate scaled by the magnitude (0.0 when deterministic)
+    pub fn deviate(&mut self) -> f64 {
+        if self.is_deterministic() {
+            return 0.0;
+        }
+        self.magnitude * self.rng.next_normal().clamp(-MAX_STANDARD_DEVIATIONS, MAX_STANDARD_DEVIATIONS)
+    }
+
+    /// Multiplicative factor around 1.0, log-normally distributed
+    ///
+    /// # Arguments
+    /// * `coefficient_of_variation` - Typical relative spread at magnitude 1.0
+    pub fn factor(&mut self, coefficient_of_variation: f64) -> f64 {
+        (coefficient_of_variation.max(0.0) * self.deviate()).exp()
+    }
+
+    /// Decide an outcome that succeeds when its margin is positive
+    ///
+    /// Deterministically the outcome is a hard threshold; with variability the
+    /// probability of success rises smoothly with the margin, so borderline
+    /// attempts sometimes fail and marginal ones sometimes succeed.
+    ///
+    /// # Arguments
+    /// * `margin` - How far the attempt exceeds its threshold
+    /// * `spread` - Margin that shifts the odds by a factor of e at magnitude 1.0
+    pub fn succeeds(&mut self, margin: f64, spread: f64) -> bool {
+        if self.is_deterministic() || spread <= 0.0 {
+            return margin >= 0.0;
+        }
+        let probability = 1.0 / (1.0 + (-margin / (spread * self.magnitude)).exp());
+        self.rng.next_f64() < probability
+    }
+
+    /// Decide whether a chance event happens, such as a spontaneous embolus
+    ///
+    /// A deterministic patient never has chance events; with variability the
+    /// event happens with the given probability.
+    ///
+    /// # Arguments
+    /// * `probability` - Probability of the event during this draw (0.0-1.0)
+    pub fn occurs(&mut self, probability: f64) -> bool {
+        !self.is_deterministic() && self.rng.next_f64() < probability.clamp(0.0, 1.0)
+    }
+}
+
+impl Default for Variability {
+    fn default() -> Self {
+        Self::deterministic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_variability_is_bounded_and_reproducible() {
+        let mut deterministic = Variability::deterministic();
+        assert_eq!(deterministic.factor(0.3), 1.0);
+        assert!(deterministic.succeeds(0.0, 0.05));
+        assert!(!deterministic.succeeds(-0.01, 0.05));
+
+        let draws = |seed| {
+            let mut variability = Variability::seeded(seed, 1.0);
+            (0..200).map(|_| variability.factor(0.2)).collect::<Vec<_>>()
+        };
+        let first = draws(7);
+        assert_eq!(first, draws(7));
+        assert_ne!(first, draws(8));
+        let bound = (0.2 * MAX_STANDARD_DEVIATIONS).exp();
+        assert!(first.iter().all(|&f| f <= bound && f >= 1.0 / bound));
+
+        // Odds of success rise with the margin
+        let mut variability = Variability::seeded(3, 1.0);
+        let rate = |variability: &mut Variability, margin| {
+            (0..1000).filter(|_| variability.succeeds(margin, 0.05)).count()
+        };
+        let marginal = rate(&mut variability, 0.0);
+        assert!((400..600).contains(&marginal));
+        assert!(rate(&mut variability, 0.1) > 800);
+        assert!(rate(&mut variability, -0.1) < 200);
+
+        // Chance events happen at their probability, and never deterministically
+        assert!(!(0..1000).any(|_| deterministic.occurs(0.5)));
+        let events = (0..1000).filter(|_| variability.occurs(0.2)).count();
+        assert!((150..250).contains(&events));
+    }
+}
@@ -0,0 +1,244 @@
+//! Fixed-rate waveform sampling
+//!
+//! This module provides:
+//! - A sample clock that places waveform samples at a fixed rate, independent
+//!   of the simulation time step, so waveform shape does not depend on step size
+//! - Timestamps for the buffered samples of a waveform
+//! - A preallocated ring buffer holding the most recent samples, read without
+//!   copying as two slices or an iterator, so a patient's waveform memory is
+//!   fixed when it is created; clones share the storage until one of them writes
+
+use std::sync::Arc;
+
+/// Fixed-capacity buffer of the most recent samples, overwriting the oldest
+#[derive(Debug, Clone, PartialEq)]
+pub struct RingBuffer {
+    /// Storage, allocated once at full capacity and shared between clones until written
+    data: Arc<Vec<f64>>,
+    /// Index of the oldest sample
+    start: usize,
+    /// Number of samples held
+    len: usize,
+}
+
+impl RingBuffer {
+    /// Create an empty buffer
+    ///
+    /// # Arguments
+    /// * `capacity` - Number of samples kept (at least 1)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            data: Arc::new(vec![0.0; capacity.max(1)]),
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Number of samples the buffer keeps
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Number of samples held
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// No samples are held
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append a sample, overwriting the oldest when full
+    pub fn push(&mut self, value: f64) {
+        let capacity = self.data.len();
+        let data = Arc::make_mut(&mut self.data);
+        if self.len < capacity {
+            data[(self.start + self.len) % capacity] = value;
+            self.len += 1;
+        } else {
+            data[self.start] = value;
+            self.start = (self.start + 1) % capacity;
+        }
+    }
+
+    /// Remove every sample, keeping the storage
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+    }
+
+    /// Sample by age order (0 = oldest)
+    pub fn get(&self, index: usize) -> Option<f64> {
+        (index < self.len).then(|| self.data[(self.start + index) % self.data.len()])
+    }
+
+    /// Most recent sample
+    pub fn latest(&self) -> Option<f64> {
+        self.len.checked_sub(1).and_then(|last| self.get(last))
+    }
+
+    /// The samples, oldest first, as two contiguous slices without copying
+    pub fn as_slices(&self) -> (&[f64], &[f64]) {
+        let end = self.start + self.len;
+        if end <= self.data.len() {
+            (&self.data[self.start..end], &[])
+        } else {
+            (&self.data[self.start..], &self.data[..end - self.data.len()])
+        }
+    }
+
+    /// Iterate over the samples, oldest first
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = f64> + '_ {
+        let (older, newer) = self.as_slices();
+        older.iter().chain(newer).copied()
+    }
+}
+
+impl std::ops::Index<usize> for RingBuffer {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &f64 {
+        assert!(index < self.len, "index {} out of range for ring buffer of length {}", index, self.len);
+        &self.data[(self.start + index) % self.data.len()]
+    }
+}
+
+/// Clock placing waveform samples at a fixed rate within each simulation step
+#[derive(Debug, Clone)]
+pub struct WaveformSampler {
+    /// Samples per second (Hz)
+    pub sample_rate_hz: f64,
+    /// Number of samples kept in a waveform buffer
+    pub capacity: usize,
+    /// Simulation time at the end of the last step (s)
+    time_s: f64,
+    /// Index of the next sample; sample n falls at n / sample_rate_hz
+    next_sample: u64,
+    /// Times of the buffered samples (s)
+    times_s: RingBuffer,
+}
+
+impl WaveformSampler {
+    /// Create a sampler
+    ///
+    /// # Arguments
+    /// * `sample_rate_hz` - Samples per second
+    /// * `buffer_s` - Length of waveform kept (s)
+    pub fn new(sample_rate_hz: f64, buffer_s: f64) -> Self {
+        let sample_rate_hz = sample_rate_hz.max(1.0);
+        let capacity = (sample_rate_hz * buffer_s).ceil().max(1.0) as usize;
+        Self {
+            sample_rate_hz,
+            capacity,
+            time_s: 0.0,
+            next_sample: 1,
+            times_s: RingBuffer::new(capacity),
+        }
+    }
+
+    /// Advance the clock by one simulation step
+    ///
+    /// # Arguments
+    /// * `delta_time_s` - Time step in seconds
+    ///
+    /// # Returns
+    /// For each sample falling within the step, in order, how long before the
+    /// end of the step it lies (s); the waveform is evaluated at those times
+    pub fn step(&mut self, delta_time_s: f64) -> impl Iterator<Item = f64> {
+        self.time_s += delta_time_s.max(0.0);
+        // Samples that would fall out of the buffer within this step are never seen
+        let end = (self.time_s * self.sample_rate_hz + 1e-9).floor() as u64 + 1;
+        let first = self.next_sample.max(end.saturating_sub(self.capacity as u64));
+        for n in first..end {
+            self.times_s.push(n as f64 / self.sample_rate_hz);
+        }
+        self.next_sample = end.max(first);
+        let (time_s, rate) = (self.time_s, self.sample_rate_hz);
+        (first..end).map(move |n| (time_s - n as f64 / rate).max(0.0))
+    }
+
+    /// Advance the clock by one simulation step without sampling
+    ///
+    /// The samples that would have fallen within the step are skipped, leaving
+    /// a gap in the timestamps.
+    ///
+    /// # Arguments
+    /// * `delta_time_s` - Time step in seconds
+    pub fn skip(&mut self, delta_time_s: f64) {
+        self.time_s += delta_time_s.max(0.0);
+        self.next_sample = (self.time_s * self.sample_rate_hz + 1e-9).floor() as u64 + 1;
+    }
+
+    /// Create a buffer holding this sampler's length of waveform
+    pub fn buffer(&self) -> RingBuffer {
+        RingBuffer::new(self.capacity)
+    }
+
+    /// Time of the oldest buffered sample (s)
+    pub fn start_time_s(&self) -> f64 {
+        self.times_s.get(0).unwrap_or(self.time_s)
+    }
+
+    /// Pair each sample of a buffer filled by this sampler with its time
+    ///
+    /// # Returns
+    /// (time in s, value) for each sample, oldest first
+    pub fn timestamped(&self, buffer: &RingBuffer) -> Vec<(f64, f64)> {
+        let skip = self.times_s.len().saturating_sub(buffer.len());
+        self.times_s.iter().skip(skip).zip(buffer.iter()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_count_independent_of_step() {
+        let mut coarse = WaveformSampler::new(250.0, 10.0);
+        let mut fine = WaveformSampler::new(250.0, 10.0);
+        let coarse_samples: usize = (0..10).map(|_| coarse.step(0.1).count()).sum();
+        let fine_samples: usize = (0..1000).map(|_| fine.step(0.001).count()).sum();
+        assert_eq!(coarse_samples, 250);
+        assert_eq!(fine_samples, 250);
+
+        let mut sampler = WaveformSampler::new(10.0, 0.5);
+        let mut buffer = sampler.buffer();
+        for lookback in sampler.step(0.25) {
+            buffer.push(lookback);
+        }
+        assert_eq!(buffer.len(), 2);
+        assert!((buffer[0] - 0.15).abs() < 1e-9);
+        for _ in sampler.step(0.5) {
+            buffer.push(0.0);
+        }
+        assert_eq!(buffer.len(), 5);
+        let stamped = sampler.timestamped(&buffer);
+        assert!((stamped[0].0 - 0.3).abs() < 1e-9);
+        assert!((stamped[4].0 - 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ring_buffer_wraps_without_growing() {
+        let mut buffer = RingBuffer::new(4);
+        for value in 1..=6 {
+            buffer.push(value as f64);
+        }
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(buffer.capacity(), 4);
+        assert_eq!(buffer.as_slices(), (&[3.0, 4.0][..], &[5.0, 6.0][..]));
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(buffer.latest(), Some(6.0));
+        assert_eq!(buffer[0], 3.0);
+        assert_eq!(buffer.get(4), None);
+
+        // A clone shares the samples until either side writes
+        let mut branch = buffer.clone();
+        branch.push(7.0);
+        assert_eq!(buffer.latest(), Some(6.0));
+        assert_eq!(branch.latest(), Some(7.0));
+        buffer.clear();
+        assert!(buffer.is_empty());
+    }
+}